@@ -0,0 +1,63 @@
+use dupe::Dupe;
+
+/// A 64-bit float stored by its bit pattern, so that it can live in hashable and totally ordered
+/// IR nodes.
+#[derive(Debug, Copy, Clone, Dupe, PartialOrd, Ord, PartialEq, Eq, Hash)]
+pub struct Float64(u64);
+
+impl Float64 {
+  pub fn new(value: f64) -> Self {
+    Self(value.to_bits())
+  }
+
+  pub fn value(self) -> f64 {
+    f64::from_bits(self.0)
+  }
+
+  /// Prints the value in a form accepted by both the samlang lexer and JavaScript.
+  /// Non-finite values use the JavaScript spelling.
+  pub fn pretty_print(self) -> String {
+    let value = self.value();
+    if value.is_nan() {
+      return "NaN".to_string();
+    }
+    if value.is_infinite() {
+      return if value > 0.0 { "Infinity".to_string() } else { "-Infinity".to_string() };
+    }
+    let printed = format!("{value:?}");
+    if printed.contains('.') {
+      printed
+    } else if let Some((mantissa, exponent)) = printed.split_once('e') {
+      format!("{mantissa}.0e{exponent}")
+    } else {
+      format!("{printed}.0")
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::Float64;
+  use dupe::Dupe;
+  use pretty_assertions::assert_eq;
+
+  #[test]
+  fn boilterplate() {
+    assert!(Float64::new(1.0) == Float64::new(1.0).dupe());
+    assert!(Float64::new(1.0) < Float64::new(2.0));
+    assert_eq!(1.5, Float64::new(1.5).value());
+    assert!(!format!("{:?}", Float64::new(1.0)).is_empty());
+  }
+
+  #[test]
+  fn pretty_print_tests() {
+    assert_eq!("1.0", Float64::new(1.0).pretty_print());
+    assert_eq!("-0.0", Float64::new(-0.0).pretty_print());
+    assert_eq!("3.25", Float64::new(3.25).pretty_print());
+    assert_eq!("1.0e100", Float64::new(1e100).pretty_print());
+    assert_eq!("1.5e-7", Float64::new(1.5e-7).pretty_print());
+    assert_eq!("NaN", Float64::new(f64::NAN).pretty_print());
+    assert_eq!("Infinity", Float64::new(f64::INFINITY).pretty_print());
+    assert_eq!("-Infinity", Float64::new(f64::NEG_INFINITY).pretty_print());
+  }
+}
@@ -1,3 +1,4 @@
+use super::Float64;
 use dupe::Dupe;
 use enum_as_inner::EnumAsInner;
 use itertools::Itertools;
@@ -65,6 +66,7 @@ impl FunctionType {
 pub enum Type {
   Int32,
   Int31,
+  Float,
   Id(IdType),
 }
 
@@ -103,6 +105,7 @@ impl Type {
     match self {
       Self::Int32 => "int".to_string(),
       Self::Int31 => "i31".to_string(),
+      Self::Float => "float".to_string(),
       Self::Id(id) => id.pretty_print(heap),
     }
   }
@@ -110,6 +113,7 @@ impl Type {
 
 pub const INT_TYPE: Type = Type::Int32;
 pub const INT31_TYPE: Type = Type::Int31;
+pub const FLOAT_TYPE: Type = Type::Float;
 pub static STRING_TYPE: LazyLock<Type> = LazyLock::new(|| {
   Type::Id(IdType {
     name: TypeName { module_reference: Some(ModuleReference::ROOT), type_name: PStr::STR_TYPE },
@@ -142,6 +146,28 @@ impl ClosureTypeDefinition {
   }
 }
 
+/// An interface used as a value type. Methods exclude the receiver, which is the interface itself.
+#[derive(Debug, Clone)]
+pub struct InterfaceTypeDefinition {
+  pub name: TypeName,
+  pub type_parameters: Vec<PStr>,
+  pub methods: Vec<(PStr, FunctionType)>,
+}
+
+impl InterfaceTypeDefinition {
+  pub fn pretty_print(&self, heap: &samlang_heap::Heap) -> String {
+    format!(
+      "interface type {} = [{}]",
+      name_with_tparams(heap, self.name, &self.type_parameters),
+      self
+        .methods
+        .iter()
+        .map(|(n, t)| format!("{}: {}", n.as_str(heap), t.pretty_print(heap)))
+        .join(", ")
+    )
+  }
+}
+
 #[derive(Debug, Clone, EnumAsInner)]
 pub enum TypeDefinitionMappings {
   Struct(Vec<Type>),
@@ -193,6 +219,7 @@ pub enum BinaryOperator {
   LOR,
   SHL,
   SHR,
+  ASHR,
   XOR,
   LT,
   LE,
@@ -200,27 +227,38 @@ pub enum BinaryOperator {
   GE,
   EQ,
   NE,
+  FMUL,
+  FDIV,
+  FPLUS,
+  FMINUS,
+  FLT,
+  FLE,
+  FGT,
+  FGE,
+  FEQ,
+  FNE,
 }
 
 impl BinaryOperator {
   pub fn as_str(&self) -> &'static str {
     match self {
-      Self::MUL => "*",
-      Self::DIV => "/",
+      Self::MUL | Self::FMUL => "*",
+      Self::DIV | Self::FDIV => "/",
       Self::MOD => "%",
-      Self::PLUS => "+",
-      Self::MINUS => "-",
+      Self::PLUS | Self::FPLUS => "+",
+      Self::MINUS | Self::FMINUS => "-",
       Self::LAND => "&",
       Self::LOR => "|",
       Self::SHL => "<<",
       Self::SHR => ">>>",
+      Self::ASHR => ">>",
       Self::XOR => "^",
-      Self::LT => "<",
-      Self::LE => "<=",
-      Self::GT => ">",
-      Self::GE => ">=",
-      Self::EQ => "==",
-      Self::NE => "!=",
+      Self::LT | Self::FLT => "<",
+      Self::LE | Self::FLE => "<=",
+      Self::GT | Self::FGT => ">",
+      Self::GE | Self::FGE => ">=",
+      Self::EQ | Self::FEQ => "==",
+      Self::NE | Self::FNE => "!=",
     }
   }
 }
@@ -287,11 +325,30 @@ impl FunctionNameExpression {
   }
 }
 
+/// A module-level constant, read from a global initialized by `initializer`.
+#[derive(Debug, Clone, Dupe, PartialEq, Eq)]
+pub struct GlobalConstant {
+  pub initializer: FunctionName,
+  pub type_: Type,
+}
+
+impl GlobalConstant {
+  pub fn debug_print(&self, heap: &samlang_heap::Heap) -> String {
+    format!(
+      "({}: {})",
+      self.initializer.type_name.pretty_print(heap),
+      self.type_.pretty_print(heap)
+    )
+  }
+}
+
 #[derive(Debug, Clone, Dupe, PartialEq, Eq, EnumAsInner)]
 pub enum Expression {
   IntLiteral(i32),
   Int31Zero,
+  FloatLiteral(Float64),
   StringName(PStr),
+  GlobalConstant(GlobalConstant),
   Variable(VariableName),
 }
 
@@ -300,6 +357,10 @@ impl Expression {
     Self::IntLiteral(value)
   }
 
+  pub fn float(value: f64) -> Self {
+    Self::FloatLiteral(Float64::new(value))
+  }
+
   pub fn var_name(name: PStr, type_: Type) -> Self {
     Self::Variable(VariableName { name, type_ })
   }
@@ -308,7 +369,9 @@ impl Expression {
     match self {
       Self::IntLiteral(_) => &INT_TYPE,
       Self::Int31Zero => &INT31_TYPE,
+      Self::FloatLiteral(_) => &FLOAT_TYPE,
       Self::StringName(_) => &STRING_TYPE,
+      Self::GlobalConstant(g) => &g.type_,
       Self::Variable(v) => &v.type_,
     }
   }
@@ -317,14 +380,20 @@ impl Expression {
     match self {
       Self::IntLiteral(i) => i.to_string(),
       Self::Int31Zero => "0 as i31".to_string(),
+      Self::FloatLiteral(f) => f.pretty_print(),
       Self::StringName(n) => format!("\"{}\"", n.as_str(heap)),
+      Self::GlobalConstant(g) => g.debug_print(heap),
       Self::Variable(v) => v.debug_print(heap),
     }
   }
 
   pub fn convert_to_callee(self) -> Option<Callee> {
     match self {
-      Self::IntLiteral(_) | Self::Int31Zero | Self::StringName(_) => None,
+      Self::IntLiteral(_)
+      | Self::Int31Zero
+      | Self::FloatLiteral(_)
+      | Self::StringName(_)
+      | Self::GlobalConstant(_) => None,
       Self::Variable(v) => Some(Callee::Variable(v)),
     }
   }
@@ -386,6 +455,17 @@ pub enum Statement {
     s2: Vec<Statement>,
     final_assignments: Vec<(PStr, Type, Expression, Expression)>,
   },
+  SingleIf {
+    condition: Expression,
+    invert_condition: bool,
+    statements: Vec<Statement>,
+  },
+  Break,
+  /// Loop variables are (name, type, initial value, value for the next iteration).
+  While {
+    loop_variables: Vec<(PStr, Type, Expression, Expression)>,
+    statements: Vec<Statement>,
+  },
   LateInitDeclaration {
     name: PStr,
     type_: Type,
@@ -411,6 +491,13 @@ pub enum Statement {
     function_name: FunctionNameExpression,
     context: Expression,
   },
+  /// Upcasts the context, whose type implements the interface, to an interface value.
+  InterfaceInit {
+    interface_variable_name: PStr,
+    interface_type: IdType,
+    context: Expression,
+  },
+  Return(Expression),
 }
 
 impl Statement {
@@ -543,6 +630,46 @@ impl Statement {
         }
         collector.push(format!("{}}}\n", "  ".repeat(level)));
       }
+      Self::SingleIf { condition, invert_condition, statements } => {
+        let invert_str = if *invert_condition { "!" } else { "" };
+        collector.push(format!(
+          "{}if {}{} {{\n",
+          "  ".repeat(level),
+          invert_str,
+          condition.debug_print(heap)
+        ));
+        for s in statements {
+          s.debug_print_internal(heap, level + 1, collector);
+        }
+        collector.push(format!("{}}}\n", "  ".repeat(level)));
+      }
+      Self::Break => {
+        collector.push(format!("{}break;\n", "  ".repeat(level)));
+      }
+      Self::While { loop_variables, statements } => {
+        for (n, t, initial_value, _) in loop_variables {
+          collector.push(format!(
+            "{}let {}: {} = {};\n",
+            "  ".repeat(level),
+            n.as_str(heap),
+            t.pretty_print(heap),
+            initial_value.debug_print(heap)
+          ));
+        }
+        collector.push(format!("{}while (true) {{\n", "  ".repeat(level)));
+        for s in statements {
+          s.debug_print_internal(heap, level + 1, collector);
+        }
+        for (n, _, _, loop_value) in loop_variables {
+          collector.push(format!(
+            "{}{} = {};\n",
+            "  ".repeat(level + 1),
+            n.as_str(heap),
+            loop_value.debug_print(heap)
+          ));
+        }
+        collector.push(format!("{}}}\n", "  ".repeat(level)));
+      }
       Self::LateInitDeclaration { name, type_ } => {
         collector.push(format!(
           "{}let {}: {};\n",
@@ -596,6 +723,18 @@ impl Statement {
           context.debug_print(heap)
         ));
       }
+      Self::InterfaceInit { interface_variable_name, interface_type, context } => {
+        collector.push(format!(
+          "{}let {}: {} = Interface {{ context: {} }};\n",
+          "  ".repeat(level),
+          interface_variable_name.as_str(heap),
+          interface_type.pretty_print(heap),
+          context.debug_print(heap)
+        ));
+      }
+      Self::Return(e) => {
+        collector.push(format!("{}return {};\n", "  ".repeat(level), e.debug_print(heap)));
+      }
     }
   }
 
@@ -657,7 +796,10 @@ pub struct GlobalString(pub PStr);
 #[derive(Debug)]
 pub struct Sources {
   pub global_variables: Vec<GlobalString>,
+  /// In initialization order: every constant comes after the constants it reads.
+  pub global_constants: Vec<GlobalConstant>,
   pub closure_types: Vec<ClosureTypeDefinition>,
+  pub interface_types: Vec<InterfaceTypeDefinition>,
   pub type_definitions: Vec<TypeDefinition>,
   pub main_function_names: Vec<FunctionName>,
   pub functions: Vec<Function>,
@@ -669,9 +811,20 @@ impl Sources {
     for (i, v) in self.global_variables.iter().enumerate() {
       lines.push(format!("const GLOBAL_STRING_{} = '{}';\n", i, v.0.as_str(heap)));
     }
+    for g in &self.global_constants {
+      lines.push(format!(
+        "const {}: {} = {}();\n",
+        g.initializer.type_name.pretty_print(heap),
+        g.type_.pretty_print(heap),
+        g.initializer.pretty_print(heap)
+      ));
+    }
     for d in &self.closure_types {
       lines.push(d.pretty_print(heap));
     }
+    for d in &self.interface_types {
+      lines.push(d.pretty_print(heap));
+    }
     for d in &self.type_definitions {
       lines.push(d.pretty_print(heap));
     }
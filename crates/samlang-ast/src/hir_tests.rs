@@ -82,6 +82,17 @@ mod tests {
     Expression::var_name(PStr::LOWER_A, INT_TYPE).convert_to_callee();
     Expression::StringName(PStr::LOWER_A).convert_to_callee();
     ZERO.convert_to_callee();
+    let global_constant = Expression::GlobalConstant(GlobalConstant {
+      initializer: FunctionName {
+        type_name: TypeName::new_for_test(PStr::UPPER_A),
+        fn_name: PStr::INIT,
+      },
+      type_: INT_TYPE,
+    });
+    assert!(global_constant.clone().convert_to_callee().is_none());
+    assert!(global_constant.type_().as_id().is_none());
+    assert!(!format!("{global_constant:?}").is_empty());
+    assert_eq!("(DUMMY_A: int)", global_constant.debug_print(heap));
     let call = Statement::Call {
       callee: Callee::FunctionName(FunctionNameExpression {
         name: FunctionName {
@@ -166,6 +177,9 @@ mod tests {
     assert_eq!("_Str", STRING_TYPE.pretty_print(heap));
     assert_eq!("0", ZERO.dupe().debug_print(heap));
     assert_eq!("0 as i31", Expression::Int31Zero.debug_print(heap));
+    assert_eq!("float", FLOAT_TYPE.pretty_print(heap));
+    assert_eq!("1.5", Expression::float(1.5).debug_print(heap));
+    assert_eq!("float", Expression::float(1.5).type_().pretty_print(heap));
     assert_eq!("(a: int)", Expression::var_name(PStr::LOWER_A, INT_TYPE).debug_print(heap));
     assert_eq!(
       "(a: DUMMY_A<int, DUMMY_B>)",
@@ -301,12 +315,24 @@ mod tests {
           e1: ZERO,
           e2: ZERO,
         },
+        Statement::Binary {
+          name: heap.alloc_str_for_test("dd"),
+          operator: BinaryOperator::ASHR,
+          e1: ZERO,
+          e2: ZERO,
+        },
         Statement::Binary {
           name: heap.alloc_str_for_test("dd"),
           operator: BinaryOperator::XOR,
           e1: ZERO,
           e2: ZERO,
         },
+        Statement::Binary {
+          name: heap.alloc_str_for_test("dd"),
+          operator: BinaryOperator::FMUL,
+          e1: Expression::float(1.5),
+          e2: Expression::float(2.0),
+        },
         Statement::LateInitDeclaration { name: heap.alloc_str_for_test("cast"), type_: INT_TYPE },
         Statement::LateInitAssignment {
           name: heap.alloc_str_for_test("cast"),
@@ -330,6 +356,20 @@ mod tests {
           }],
           final_assignments: vec![(PStr::LOWER_A, INT_TYPE, ZERO, ZERO)],
         },
+        Statement::While {
+          loop_variables: vec![(
+            PStr::LOWER_I,
+            INT_TYPE,
+            ZERO,
+            Expression::var_name(PStr::LOWER_J, INT_TYPE),
+          )],
+          statements: vec![Statement::SingleIf {
+            condition: ZERO,
+            invert_condition: true,
+            statements: vec![Statement::Break],
+          }],
+        },
+        Statement::SingleIf { condition: ZERO, invert_condition: false, statements: vec![] },
       ],
       s2: vec![
         Statement::Not { name: heap.alloc_str_for_test("dd"), operand: ZERO },
@@ -412,6 +452,7 @@ mod tests {
           ),
           index: 0,
         },
+        Statement::Return(ZERO),
       ],
       final_assignments: vec![(
         heap.alloc_str_for_test("bar"),
@@ -436,7 +477,9 @@ if 0 {
   let dd = 0 | 0;
   let dd = 0 << 0;
   let dd = 0 >>> 0;
+  let dd = 0 >> 0;
   let dd = 0 ^ 0;
+  let dd = 1.5 * 2.0;
   let cast: int;
   cast = 0;
   let [_, _: int] if tagof(0)==0 {
@@ -446,6 +489,15 @@ if 0 {
     let dd = 0 ^ 0;
     a = 0;
   }
+  let i: int = 0;
+  while (true) {
+    if !0 {
+      break;
+    }
+    i = (j: int);
+  }
+  if 0 {
+  }
   bar = (b1: int);
 } else {
   let dd = !0;
@@ -459,6 +511,7 @@ if 0 {
   $stresso<int>((d: int));
   (d: int)((d: int));
   let f: int = (big: DUMMY_FooBar)[0];
+  return 0;
   bar = (b2: int);
 }"#;
     assert_eq!(expected, stmt.debug_print(heap));
@@ -470,6 +523,13 @@ if 0 {
 
     let sources1 = Sources {
       global_variables: vec![GlobalString(heap.alloc_str_for_test("dev_meggo_vibez"))],
+      global_constants: vec![GlobalConstant {
+        initializer: FunctionName {
+          type_name: TypeName::new_for_test(PStr::UPPER_A),
+          fn_name: PStr::INIT,
+        },
+        type_: INT_TYPE,
+      }],
       closure_types: vec![
         ClosureTypeDefinition {
           name: TypeName { module_reference: None, type_name: PStr::UPPER_C },
@@ -478,6 +538,7 @@ if 0 {
         }
         .clone(),
       ],
+      interface_types: Vec::new(),
       type_definitions: vec![
         TypeDefinition {
           name: TypeName { module_reference: None, type_name: heap.alloc_str_for_test("Foo") },
@@ -519,6 +580,8 @@ if 0 {
     assert!(!format!("{sources1:?}").is_empty());
     let expected1 = r#"const GLOBAL_STRING_0 = 'dev_meggo_vibez';
 
+const DUMMY_A: int = DUMMY_A$init();
+
 closure type C = () -> int
 object type Foo = [int, int]
 function Foo$Bar(f: int): int {
@@ -531,7 +594,9 @@ sources.mains = [Foo$ddd]"#;
 
     let sources2 = Sources {
       global_variables: Vec::new(),
+      global_constants: Vec::new(),
       closure_types: Vec::new(),
+      interface_types: Vec::new(),
       type_definitions: Vec::new(),
       main_function_names: Vec::new(),
       functions: vec![Function {
@@ -1,3 +1,5 @@
+mod float;
+pub use float::Float64;
 mod loc;
 pub use loc::{Location, Position};
 mod reason;
@@ -1,4 +1,5 @@
 use super::{
+  Float64,
   hir::{BinaryOperator, GlobalString},
   mir::{FunctionName, SymbolTable, TypeNameId},
 };
@@ -35,6 +36,7 @@ impl FunctionType {
 pub enum Type {
   Int32,
   Int31,
+  Float,
   AnyPointer,
   Id(TypeNameId),
   Fn(FunctionType),
@@ -53,6 +55,7 @@ impl Type {
     match self {
       Self::Int32 => collector.push_str("number"),
       Self::Int31 => collector.push_str("i31"),
+      Self::Float => collector.push_str("number"),
       Self::AnyPointer => collector.push_str("any"),
       Self::Id(id) => id.write_encoded(collector, heap, table),
       Self::Fn(function) => function.pretty_print(collector, heap, table),
@@ -63,6 +66,7 @@ impl Type {
     match (self, other) {
       (Self::Int32, Self::Int32)
       | (Self::Int31, Self::Int31)
+      | (Self::Float, Self::Float)
       | (Self::AnyPointer, Self::AnyPointer) => true,
       (Self::Id(n1), Self::Id(n2)) => n1 == n2,
       (Self::Fn(f1), Self::Fn(f2)) => {
@@ -81,13 +85,23 @@ impl Type {
 
 pub const INT_32_TYPE: Type = Type::Int32;
 pub const INT_31_TYPE: Type = Type::Int31;
+pub const FLOAT_TYPE: Type = Type::Float;
 pub const ANY_POINTER_TYPE: Type = Type::AnyPointer;
 
+/// A module-level constant, read from a global initialized by `initializer`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GlobalConstant {
+  pub initializer: FunctionName,
+  pub type_: Type,
+}
+
 #[derive(Debug, Clone, EnumAsInner)]
 pub enum Expression {
   Int32Literal(i32),
   Int31Literal(i32),
+  FloatLiteral(Float64),
   StringName(PStr),
+  GlobalConstant(GlobalConstant),
   Variable(PStr, Type),
   FnName(FunctionName, FunctionType),
 }
@@ -97,6 +111,10 @@ impl Expression {
     Self::Int32Literal(value)
   }
 
+  pub fn float(value: f64) -> Self {
+    Self::FloatLiteral(Float64::new(value))
+  }
+
   fn pretty_print(
     &self,
     collector: &mut String,
@@ -110,11 +128,15 @@ impl Expression {
         let i32_form = i * 2 + 1;
         collector.push_str(&i32_form.to_string())
       }
+      Self::FloatLiteral(f) => collector.push_str(&f.pretty_print()),
       Self::Variable(n, _) => collector.push_str(n.as_str(heap)),
       Self::StringName(n) => {
         collector.push_str("GLOBAL_STRING_");
         collector.push_str(&str_table.get(n).unwrap().to_string());
       }
+      Self::GlobalConstant(g) => {
+        g.initializer.type_name.write_encoded(collector, heap, symbol_table)
+      }
       Self::FnName(n, _) => n.write_encoded(collector, heap, symbol_table),
     }
   }
@@ -197,6 +219,7 @@ pub enum Statement {
     type_: Type,
     expression_list: Vec<Expression>,
   },
+  Return(Expression),
 }
 
 impl Statement {
@@ -279,7 +302,13 @@ impl Statement {
           | BinaryOperator::GT
           | BinaryOperator::GE
           | BinaryOperator::EQ
-          | BinaryOperator::NE => {
+          | BinaryOperator::NE
+          | BinaryOperator::FLT
+          | BinaryOperator::FLE
+          | BinaryOperator::FGT
+          | BinaryOperator::FGE
+          | BinaryOperator::FEQ
+          | BinaryOperator::FNE => {
             let is_str_cmp = matches!(operator, BinaryOperator::EQ | BinaryOperator::NE)
               && (e1.type_is_str() || e2.type_is_str());
             // Necessary to make TS happy
@@ -308,7 +337,12 @@ impl Statement {
           | BinaryOperator::LOR
           | BinaryOperator::SHL
           | BinaryOperator::SHR
-          | BinaryOperator::XOR => {
+          | BinaryOperator::ASHR
+          | BinaryOperator::XOR
+          | BinaryOperator::FMUL
+          | BinaryOperator::FDIV
+          | BinaryOperator::FPLUS
+          | BinaryOperator::FMINUS => {
             e1.pretty_print(collector, heap, symbol_table, str_table);
             collector.push(' ');
             collector.push_str(operator.as_str());
@@ -504,6 +538,12 @@ impl Statement {
         Self::print_expression_list(collector, heap, symbol_table, str_table, expression_list);
         collector.push_str("];\n");
       }
+      Self::Return(e) => {
+        Self::append_spaces(collector, level);
+        collector.push_str("return ");
+        e.pretty_print(collector, heap, symbol_table, str_table);
+        collector.push_str(";\n");
+      }
     }
   }
 }
@@ -563,6 +603,8 @@ pub struct TypeDefinition {
 pub struct Sources {
   pub symbol_table: SymbolTable,
   pub global_variables: Vec<GlobalString>,
+  /// In initialization order: every constant comes after the constants it reads.
+  pub global_constants: Vec<GlobalConstant>,
   pub type_definitions: Vec<TypeDefinition>,
   pub main_function_names: Vec<FunctionName>,
   pub functions: Vec<Function>,
@@ -592,6 +634,94 @@ pub fn ts_prolog() -> String {
   FunctionName::STR_FROM_INT.write_encoded(&mut collector, heap, table);
   collector.push_str(" = (_: number, v: number): _Str => [1, String(v) as unknown as number];\n");
 
+  collector.push_str("const ");
+  FunctionName::STR_FROM_FLOAT.write_encoded(&mut collector, heap, table);
+  collector.push_str(" = (_: number, v: number): _Str => [1, String(v) as unknown as number];\n");
+
+  // Str methods count code points rather than UTF-16 units, and only map ASCII in trim and case
+  // conversion, so that they agree with the UTF-8 implementation in libsam.wat.
+  collector.push_str("const ");
+  FunctionName::STR_LENGTH.write_encoded(&mut collector, heap, table);
+  collector.push_str(" = ([, v]: _Str): number => Array.from(v as unknown as string).length;\n");
+
+  collector.push_str("const ");
+  FunctionName::STR_BYTE_LENGTH.write_encoded(&mut collector, heap, table);
+  collector.push_str(
+    " = ([, v]: _Str): number => new TextEncoder().encode(v as unknown as string).length;\n",
+  );
+
+  collector.push_str("const ");
+  FunctionName::STR_CHAR_AT.write_encoded(&mut collector, heap, table);
+  collector.push_str(
+    " = ([, v]: _Str, i: number): _Str => { const c = Array.from(v as unknown as string)[i]; if (c === undefined) { throw Error('Str index out of bounds'); } return [1, c as unknown as number]; };\n",
+  );
+
+  collector.push_str("const ");
+  FunctionName::STR_CODE_POINT_AT.write_encoded(&mut collector, heap, table);
+  collector.push_str(
+    " = ([, v]: _Str, i: number): number => { const c = Array.from(v as unknown as string)[i]; if (c === undefined) { throw Error('Str index out of bounds'); } return c.codePointAt(0) as number; };\n",
+  );
+
+  collector.push_str("const ");
+  FunctionName::STR_SUBSTRING.write_encoded(&mut collector, heap, table);
+  collector.push_str(
+    " = ([, v]: _Str, start: number, end: number): _Str => { const cs = Array.from(v as unknown as string); if (start < 0 || start > end || end > cs.length) { throw Error('Str index out of bounds'); } return [1, cs.slice(start, end).join('') as unknown as number]; };\n",
+  );
+
+  collector.push_str("const ");
+  FunctionName::STR_INDEX_OF.write_encoded(&mut collector, heap, table);
+  collector.push_str(
+    " = ([, v]: _Str, [, n]: _Str): number => { const s = v as unknown as string; const i = s.indexOf(n as unknown as string); return i < 0 ? -1 : Array.from(s.slice(0, i)).length; };\n",
+  );
+
+  collector.push_str("const ");
+  FunctionName::STR_SPLIT.write_encoded(&mut collector, heap, table);
+  collector.push_str(
+    " = ([, v]: _Str, [, sep]: _Str): _Vec => { const s = v as unknown as string; const parts = (sep as unknown as string) === '' ? Array.from(s) : s.split(sep as unknown as string); return parts.map((p) => [1, p]); };\n",
+  );
+
+  collector.push_str("const ");
+  FunctionName::STR_TRIM.write_encoded(&mut collector, heap, table);
+  collector.push_str(
+    " = ([, v]: _Str): _Str => [1, (v as unknown as string).replace(/^[\\t-\\r ]+|[\\t-\\r ]+$/g, '') as unknown as number];\n",
+  );
+
+  collector.push_str("const ");
+  FunctionName::STR_STARTS_WITH.write_encoded(&mut collector, heap, table);
+  collector.push_str(
+    " = ([, v]: _Str, [, p]: _Str): number => Number((v as unknown as string).startsWith(p as unknown as string));\n",
+  );
+
+  // Compares code points rather than UTF-16 units, which matches UTF-8 byte order.
+  collector.push_str("const ");
+  FunctionName::STR_COMPARE.write_encoded(&mut collector, heap, table);
+  collector.push_str(
+    " = ([, v]: _Str, [, o]: _Str): number => { const a = Array.from(v as unknown as string, (c) => c.codePointAt(0) as number); const b = Array.from(o as unknown as string, (c) => c.codePointAt(0) as number); for (let i = 0; i < a.length && i < b.length; i++) { if (a[i] !== b[i]) { return a[i] < b[i] ? -1 : 1; } } return a.length === b.length ? 0 : a.length < b.length ? -1 : 1; };\n",
+  );
+
+  collector.push_str("const ");
+  FunctionName::STR_TO_UPPER.write_encoded(&mut collector, heap, table);
+  collector.push_str(
+    " = ([, v]: _Str): _Str => [1, (v as unknown as string).replace(/[a-z]+/g, (s) => s.toUpperCase()) as unknown as number];\n",
+  );
+
+  collector.push_str("const ");
+  FunctionName::STR_TO_LOWER.write_encoded(&mut collector, heap, table);
+  collector.push_str(
+    " = ([, v]: _Str): _Str => [1, (v as unknown as string).replace(/[A-Z]+/g, (s) => s.toLowerCase()) as unknown as number];\n",
+  );
+
+  collector.push_str("const ");
+  FunctionName::FLOAT_FROM_INT.write_encoded(&mut collector, heap, table);
+  collector.push_str(" = (_: number, v: number): number => v;\n");
+
+  // Saturating truncation toward zero, with NaN mapped to 0, matching i32.trunc_sat_f64_s.
+  collector.push_str("const ");
+  FunctionName::FLOAT_TO_INT.write_encoded(&mut collector, heap, table);
+  collector.push_str(
+    " = (_: number, v: number): number => Math.max(-2147483648, Math.min(2147483647, Math.trunc(v))) || 0;\n",
+  );
+
   collector.push_str("const ");
   FunctionName::PROCESS_PANIC.write_encoded(&mut collector, heap, table);
   collector
@@ -654,6 +784,27 @@ pub fn ts_prolog() -> String {
   collector
 }
 
+/// Global strings keep samlang escape sequences, which are valid in a JS template literal except
+/// for `\0` followed by a digit. Backticks and `${` must be escaped to stay literal.
+fn push_template_literal_content(collector: &mut String, content: &str) {
+  let mut chars = content.chars().peekable();
+  while let Some(c) = chars.next() {
+    match c {
+      '\\' => match chars.next() {
+        Some('0') => collector.push_str("\\x00"),
+        Some(escaped) => {
+          collector.push('\\');
+          collector.push(escaped);
+        }
+        None => collector.push('\\'),
+      },
+      '`' => collector.push_str("\\`"),
+      '$' if chars.peek() == Some(&'{') => collector.push_str("\\$"),
+      c => collector.push(c),
+    }
+  }
+}
+
 impl Sources {
   pub fn pretty_print(&self, heap: &Heap) -> String {
     let mut collector = ts_prolog();
@@ -663,7 +814,7 @@ impl Sources {
       collector.push_str("const GLOBAL_STRING_");
       collector.push_str(&i.to_string());
       collector.push_str(": _Str = [0, `");
-      collector.push_str(s.as_str(heap));
+      push_template_literal_content(&mut collector, s.as_str(heap));
       collector.push_str("` as unknown as number];\n");
       str_lookup_table.insert(*s, i);
     }
@@ -688,6 +839,15 @@ impl Sources {
     for f in &self.functions {
       f.pretty_print(&mut collector, heap, &self.symbol_table, &str_lookup_table);
     }
+    for GlobalConstant { initializer, type_ } in &self.global_constants {
+      collector.push_str("const ");
+      initializer.type_name.write_encoded(&mut collector, heap, &self.symbol_table);
+      collector.push_str(": ");
+      type_.pretty_print(&mut collector, heap, &self.symbol_table);
+      collector.push_str(" = ");
+      initializer.write_encoded(&mut collector, heap, &self.symbol_table);
+      collector.push_str("();\n");
+    }
     collector
   }
 }
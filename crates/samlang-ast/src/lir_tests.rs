@@ -73,7 +73,9 @@ mod tests {
       global_variables: vec![
         GlobalString(heap.alloc_str_for_test("dev_meggo_vibez")),
         GlobalString(heap.alloc_str_for_test(r#"f"\""#)),
+        GlobalString(heap.alloc_str_for_test(r#"`${a}`\01\u{1F600}"#)),
       ],
+      global_constants: Vec::new(),
       type_definitions: vec![
         TypeDefinition {
           name: table.create_type_name_for_test(heap.alloc_str_for_test("Foo")),
@@ -271,6 +273,7 @@ mod tests {
                 name: heap.alloc_str_for_test("c"),
                 assigned_expression: ZERO,
               },
+              Statement::Return(ZERO),
               Statement::Break(ZERO),
             ],
             final_assignments: vec![(
@@ -288,6 +291,7 @@ mod tests {
     let expected = format!(
       r#"{}const GLOBAL_STRING_0: _Str = [0, `dev_meggo_vibez` as unknown as number];
 const GLOBAL_STRING_1: _Str = [0, `f"\"` as unknown as number];
+const GLOBAL_STRING_2: _Str = [0, `\`\${{a}}\`\x001\u{{1F600}}` as unknown as number];
 type _Foo = [number, i31];
 type _Foo = [];
 function __$main(): number {{
@@ -347,6 +351,7 @@ function __$f(v1: (t0: number) => number): number {{
     let c = 0 as unknown as number;
     let c: number = undefined as any;
     c = 0;
+    return 0;
     break;
     bar = b2;
   }}
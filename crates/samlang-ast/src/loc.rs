@@ -6,6 +6,10 @@ pub struct Position(pub u32, pub u32);
 
 pub const DUMMY_POSITION: Position = Position(u32::MAX, u32::MAX);
 
+/// Set on the end line of locations synthesized by the parser. The rest of the end line holds the
+/// width of the one-line anchor, and the end column holds the index of the synthesized token.
+const SYNTHESIZED_LINE_BIT: u32 = 1 << 31;
+
 impl Position {
   pub fn is_dummy(self) -> bool {
     self == DUMMY_POSITION
@@ -40,6 +44,23 @@ impl Location {
     }
   }
 
+  /// The location of the `index`-th token synthesized at `anchor`. Variables are identified by their
+  /// locations, so synthesized tokens need distinct locations that still map back to `anchor`.
+  pub fn synthesized(anchor: Location, index: u32) -> Self {
+    debug_assert_eq!(anchor.start.0, anchor.end.0);
+    let width = anchor.end.1 - anchor.start.1;
+    Self { end: Position(SYNTHESIZED_LINE_BIT | width, index), ..anchor }
+  }
+
+  /// Maps a location spanning synthesized tokens back to their anchor.
+  pub fn resolve_synthesized(self) -> Self {
+    if self.end.is_dummy() || self.end.0 & SYNTHESIZED_LINE_BIT == 0 {
+      return self;
+    }
+    let width = self.end.0 & !SYNTHESIZED_LINE_BIT;
+    Self { end: Position(self.start.0, self.start.1 + width), ..self }
+  }
+
   pub fn contains_position(&self, position: Position) -> bool {
     self.start <= position && self.end >= position
   }
@@ -122,6 +143,22 @@ mod tests {
     assert_eq!(false, Location::from_pos(1, 3, 3, 1).contains(&Location::from_pos(1, 2, 3, 2)));
   }
 
+  #[test]
+  fn location_synthesized_test() {
+    let anchor = Location::from_pos(6, 56, 6, 63);
+    let first = Location::synthesized(anchor, 0);
+    let last = Location::synthesized(anchor, 40);
+    assert_ne!(first, last);
+    assert_eq!(anchor, first.resolve_synthesized());
+    assert_eq!(anchor, first.union(&last).resolve_synthesized());
+    assert_eq!(anchor, anchor.resolve_synthesized());
+    assert_eq!(Location::dummy(), Location::dummy().resolve_synthesized());
+    assert_eq!(
+      Location::full_document(ModuleReference::DUMMY),
+      Location::full_document(ModuleReference::DUMMY).resolve_synthesized()
+    );
+  }
+
   #[test]
   fn location_union_test() {
     assert_eq!(
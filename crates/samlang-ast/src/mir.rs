@@ -1,4 +1,4 @@
-use super::hir;
+use super::{Float64, hir};
 use dupe::Dupe;
 use enum_as_inner::EnumAsInner;
 use itertools::Itertools;
@@ -56,6 +56,12 @@ impl TypeName {
     suffix: Vec::new(),
     sub_type_tag: None,
   };
+  const FLOAT: TypeName = TypeName {
+    module_reference: ModuleReference::ROOT,
+    type_name: PStr::FLOAT_TYPE,
+    suffix: Vec::new(),
+    sub_type_tag: None,
+  };
 
   fn encoded(&self, collector: &mut String, heap: &Heap, table: &SymbolTable) {
     collector.push_str(&self.module_reference.encoded(heap));
@@ -69,6 +75,7 @@ impl TypeName {
       match t {
         Type::Int32 => collector.push_str("int"),
         Type::Int31 => collector.push_str("i31"),
+        Type::Float => collector.push_str("float"),
         Type::Id(id) => id.write_encoded(collector, heap, table),
       }
     }
@@ -95,6 +102,7 @@ impl TypeNameId {
   pub const STR: TypeNameId = TypeNameId(1);
   pub const PROCESS: TypeNameId = TypeNameId(2);
   pub const VEC: TypeNameId = TypeNameId(3);
+  pub const FLOAT: TypeNameId = TypeNameId(4);
 
   pub(super) fn write_encoded(&self, collector: &mut String, heap: &Heap, table: &SymbolTable) {
     // STR and VEC are special - they're builtin GC types defined in libsam.wat,
@@ -133,6 +141,7 @@ impl Default for SymbolTable {
     table.create_type_name_internal(TypeName::STR);
     table.create_type_name_internal(TypeName::PROCESS);
     table.create_type_name_internal(TypeName::VEC);
+    table.create_type_name_internal(TypeName::FLOAT);
     table
   }
 }
@@ -214,6 +223,27 @@ impl SymbolTable {
     })
   }
 
+  /// The type name of the vtable struct backing values of the given interface type.
+  pub fn derived_vtable_type_name(&mut self, heap: &mut Heap, id: TypeNameId) -> TypeNameId {
+    let base = self.type_name_lookup_table.get(&id).unwrap();
+    let (module_reference, suffix) = (base.module_reference, base.suffix.clone());
+    let type_name = heap.alloc_string(format!("{}$VTable", base.type_name.as_str(heap)));
+    self.create_type_name_internal(TypeName {
+      module_reference,
+      type_name,
+      suffix,
+      sub_type_tag: None,
+    })
+  }
+
+  /// Splits a specialized type name into the unspecialized type name and its type arguments.
+  pub fn split_type_name_suffix(&mut self, id: TypeNameId) -> (TypeNameId, Vec<Type>) {
+    let base = self.type_name_lookup_table.get(&id).unwrap();
+    let (module_reference, type_name, suffix) =
+      (base.module_reference, base.type_name, base.suffix.clone());
+    (self.create_simple_type_name(module_reference, type_name), suffix)
+  }
+
   /// If the given TypeNameId is a subtype (has a sub_type_tag), returns the parent TypeNameId.
   /// Otherwise returns None.
   pub fn get_parent_type_if_subtype(&self, id: TypeNameId) -> Option<TypeNameId> {
@@ -277,6 +307,7 @@ impl SymbolTable {
 pub enum Type {
   Int32,
   Int31,
+  Float,
   Id(TypeNameId),
 }
 
@@ -289,6 +320,7 @@ impl Type {
     match self {
       Self::Int32 => "int".to_string(),
       Self::Int31 => "i31".to_string(),
+      Self::Float => "float".to_string(),
       Self::Id(id) => id.encoded_for_test(heap, table),
     }
   }
@@ -296,6 +328,7 @@ impl Type {
 
 pub const INT_32_TYPE: Type = Type::Int32;
 pub const INT_31_TYPE: Type = Type::Int31;
+pub const FLOAT_TYPE: Type = Type::Float;
 
 #[derive(Debug, Clone)]
 pub struct ClosureTypeDefinition {
@@ -313,6 +346,23 @@ impl ClosureTypeDefinition {
   }
 }
 
+/// An interface used as a value type. Methods exclude the receiver, which is the interface itself.
+#[derive(Debug, Clone)]
+pub struct InterfaceTypeDefinition {
+  pub name: TypeNameId,
+  pub methods: Vec<FunctionType>,
+}
+
+impl InterfaceTypeDefinition {
+  pub fn pretty_print(&self, heap: &Heap, table: &SymbolTable) -> String {
+    format!(
+      "interface type {} = [{}]",
+      self.name.encoded_for_test(heap, table),
+      self.methods.iter().map(|it| it.pretty_print(heap, table)).join(", ")
+    )
+  }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum EnumTypeDefinition {
   Boxed(Vec<Type>),
@@ -399,6 +449,37 @@ impl FunctionName {
     FunctionName { type_name: TypeNameId::STR, fn_name: PStr::CONCAT };
   pub const STR_EQ: FunctionName =
     FunctionName { type_name: TypeNameId::STR, fn_name: PStr::STR_EQ };
+  pub const STR_FROM_FLOAT: FunctionName =
+    FunctionName { type_name: TypeNameId::STR, fn_name: PStr::FROM_FLOAT };
+  pub const STR_LENGTH: FunctionName =
+    FunctionName { type_name: TypeNameId::STR, fn_name: PStr::LENGTH };
+  pub const STR_BYTE_LENGTH: FunctionName =
+    FunctionName { type_name: TypeNameId::STR, fn_name: PStr::BYTE_LENGTH };
+  pub const STR_CHAR_AT: FunctionName =
+    FunctionName { type_name: TypeNameId::STR, fn_name: PStr::CHAR_AT };
+  pub const STR_CODE_POINT_AT: FunctionName =
+    FunctionName { type_name: TypeNameId::STR, fn_name: PStr::CODE_POINT_AT };
+  pub const STR_SUBSTRING: FunctionName =
+    FunctionName { type_name: TypeNameId::STR, fn_name: PStr::SUBSTRING };
+  pub const STR_INDEX_OF: FunctionName =
+    FunctionName { type_name: TypeNameId::STR, fn_name: PStr::INDEX_OF };
+  pub const STR_SPLIT: FunctionName =
+    FunctionName { type_name: TypeNameId::STR, fn_name: PStr::SPLIT };
+  pub const STR_TRIM: FunctionName =
+    FunctionName { type_name: TypeNameId::STR, fn_name: PStr::TRIM };
+  pub const STR_STARTS_WITH: FunctionName =
+    FunctionName { type_name: TypeNameId::STR, fn_name: PStr::STARTS_WITH };
+  pub const STR_COMPARE: FunctionName =
+    FunctionName { type_name: TypeNameId::STR, fn_name: PStr::COMPARE };
+  pub const STR_TO_UPPER: FunctionName =
+    FunctionName { type_name: TypeNameId::STR, fn_name: PStr::TO_UPPER };
+  pub const STR_TO_LOWER: FunctionName =
+    FunctionName { type_name: TypeNameId::STR, fn_name: PStr::TO_LOWER };
+
+  pub const FLOAT_FROM_INT: FunctionName =
+    FunctionName { type_name: TypeNameId::FLOAT, fn_name: PStr::FROM_INT };
+  pub const FLOAT_TO_INT: FunctionName =
+    FunctionName { type_name: TypeNameId::FLOAT, fn_name: PStr::TO_INT };
 
   pub const VEC_EMPTY: FunctionName =
     FunctionName { type_name: TypeNameId::VEC, fn_name: PStr::EMPTY_FN };
@@ -424,6 +505,12 @@ impl FunctionName {
   /// when the source-level element type is `int`. Defined in libsam.wat.
   pub const UNWRAP_I31: FunctionName =
     FunctionName { type_name: TypeNameId::EMPTY, fn_name: PStr::UNWRAP_I31 };
+  /// Helpers that box an f64 into a `(ref $_Float)` and unbox it from a `(ref eq)`.
+  /// Used by the WASM lowering for `Vec<float>` elements. Defined in libsam.wat.
+  pub const WRAP_F64: FunctionName =
+    FunctionName { type_name: TypeNameId::EMPTY, fn_name: PStr::WRAP_F64 };
+  pub const UNWRAP_F64: FunctionName =
+    FunctionName { type_name: TypeNameId::EMPTY, fn_name: PStr::UNWRAP_F64 };
 
   pub const BUILTIN_FREE: FunctionName =
     FunctionName { type_name: TypeNameId::EMPTY, fn_name: PStr::FREE_FN };
@@ -456,11 +543,30 @@ pub struct FunctionNameExpression {
   pub type_: FunctionType,
 }
 
+/// A module-level constant, read from a global initialized by `initializer`.
+#[derive(Debug, Clone, Dupe, Copy, PartialEq, Eq, Hash)]
+pub struct GlobalConstant {
+  pub initializer: FunctionName,
+  pub type_: Type,
+}
+
+impl GlobalConstant {
+  pub fn debug_print(&self, heap: &Heap, table: &SymbolTable) -> String {
+    format!(
+      "({}: {})",
+      self.initializer.type_name.encoded_for_test(heap, table),
+      self.type_.pretty_print(heap, table)
+    )
+  }
+}
+
 #[derive(Debug, Clone, Dupe, Copy, EnumAsInner)]
 pub enum Expression {
   Int32Literal(i32),
   Int31Literal(i32),
+  FloatLiteral(Float64),
   StringName(PStr),
+  GlobalConstant(GlobalConstant),
   Variable(VariableName),
 }
 
@@ -476,9 +582,22 @@ impl Ord for Expression {
         Self::Int31Literal(i2) => i1.cmp(i2),
         _ => Ordering::Less,
       },
-      Self::StringName(n1) => match other {
+      Self::FloatLiteral(f1) => match other {
         Self::Int32Literal(_) | Self::Int31Literal(_) => Ordering::Greater,
+        Self::FloatLiteral(f2) => f1.cmp(f2),
+        _ => Ordering::Less,
+      },
+      Self::StringName(n1) => match other {
+        Self::Int32Literal(_) | Self::Int31Literal(_) | Self::FloatLiteral(_) => Ordering::Greater,
         Self::StringName(n2) => n1.cmp(n2),
+        Self::GlobalConstant(_) | Self::Variable(_) => Ordering::Less,
+      },
+      Self::GlobalConstant(g1) => match other {
+        Self::Int32Literal(_)
+        | Self::Int31Literal(_)
+        | Self::FloatLiteral(_)
+        | Self::StringName(_) => Ordering::Greater,
+        Self::GlobalConstant(g2) => g1.initializer.cmp(&g2.initializer),
         Self::Variable(_) => Ordering::Less,
       },
       Self::Variable(v1) => match other {
@@ -508,7 +627,9 @@ impl Hash for Expression {
     core::mem::discriminant(self).hash(state);
     match self {
       Self::Int32Literal(i) | Self::Int31Literal(i) => i.hash(state),
+      Self::FloatLiteral(f) => f.hash(state),
       Self::StringName(n) => n.hash(state),
+      Self::GlobalConstant(g) => g.initializer.hash(state),
       Self::Variable(v) => v.hash(state),
     }
   }
@@ -519,22 +640,43 @@ impl Expression {
     Self::Int32Literal(value)
   }
 
+  pub fn float(value: f64) -> Self {
+    Self::FloatLiteral(Float64::new(value))
+  }
+
   pub fn var_name(name: PStr, type_: Type) -> Self {
     Self::Variable(VariableName { name, type_ })
   }
 
+  pub fn type_(&self) -> Type {
+    match self {
+      Self::Int32Literal(_) => INT_32_TYPE,
+      Self::Int31Literal(_) => INT_31_TYPE,
+      Self::FloatLiteral(_) => FLOAT_TYPE,
+      Self::StringName(_) => Type::Id(TypeNameId::STR),
+      Self::GlobalConstant(g) => g.type_,
+      Self::Variable(v) => v.type_,
+    }
+  }
+
   pub fn debug_print(&self, heap: &Heap, table: &SymbolTable) -> String {
     match self {
       Self::Int32Literal(i) => i.to_string(),
       Self::Int31Literal(i) => format!("{i} as i31"),
+      Self::FloatLiteral(f) => f.pretty_print(),
       Self::StringName(n) => format!("\"{}\"", n.as_str(heap)),
+      Self::GlobalConstant(g) => g.debug_print(heap, table),
       Self::Variable(v) => v.debug_print(heap, table),
     }
   }
 
   pub fn convert_to_callee(self) -> Option<Callee> {
     match self {
-      Self::Int32Literal(_) | Self::Int31Literal(_) | Self::StringName(_) => None,
+      Self::Int32Literal(_)
+      | Self::Int31Literal(_)
+      | Self::FloatLiteral(_)
+      | Self::StringName(_)
+      | Self::GlobalConstant(_) => None,
       Self::Variable(v) => Some(Callee::Variable(v)),
     }
   }
@@ -555,6 +697,11 @@ pub struct Binary {
 pub enum Callee {
   FunctionName(FunctionNameExpression),
   Variable(VariableName),
+  /// The method at `index` of the receiver's vtable, called with the receiver's context.
+  InterfaceMethod {
+    receiver: Expression,
+    index: usize,
+  },
 }
 
 impl Callee {
@@ -562,6 +709,9 @@ impl Callee {
     match self {
       Self::FunctionName(f) => f.name.encoded_for_test(heap, table),
       Self::Variable(v) => v.debug_print(heap, table),
+      Self::InterfaceMethod { receiver, index } => {
+        format!("{}.vtable[{}]", receiver.debug_print(heap, table), index)
+      }
     }
   }
 }
@@ -658,6 +808,14 @@ pub enum Statement {
     function_name: FunctionNameExpression,
     context: Expression,
   },
+  /// Upcasts the context to an interface value whose vtable holds the implementing functions.
+  InterfaceInit {
+    interface_variable_name: PStr,
+    interface_type_name: TypeNameId,
+    vtable: Vec<FunctionNameExpression>,
+    context: Expression,
+  },
+  Return(Expression),
 }
 
 impl Statement {
@@ -701,14 +859,21 @@ impl Statement {
       | hir::BinaryOperator::MOD
       | hir::BinaryOperator::MINUS
       | hir::BinaryOperator::SHL
-      | hir::BinaryOperator::SHR => (op, normalized_e1, normalized_e2),
+      | hir::BinaryOperator::SHR
+      | hir::BinaryOperator::ASHR
+      | hir::BinaryOperator::FDIV
+      | hir::BinaryOperator::FMINUS => (op, normalized_e1, normalized_e2),
       hir::BinaryOperator::MUL
       | hir::BinaryOperator::PLUS
       | hir::BinaryOperator::LAND
       | hir::BinaryOperator::LOR
       | hir::BinaryOperator::XOR
       | hir::BinaryOperator::EQ
-      | hir::BinaryOperator::NE => {
+      | hir::BinaryOperator::NE
+      | hir::BinaryOperator::FMUL
+      | hir::BinaryOperator::FPLUS
+      | hir::BinaryOperator::FEQ
+      | hir::BinaryOperator::FNE => {
         if normalized_e1 > normalized_e2 {
           (op, normalized_e1, normalized_e2)
         } else {
@@ -722,6 +887,13 @@ impl Statement {
           (op, normalized_e1, normalized_e2)
         }
       }
+      hir::BinaryOperator::FLT => {
+        if normalized_e1 < normalized_e2 {
+          (hir::BinaryOperator::FGT, normalized_e2, normalized_e1)
+        } else {
+          (op, normalized_e1, normalized_e2)
+        }
+      }
       hir::BinaryOperator::LE => {
         if normalized_e1 < normalized_e2 {
           (hir::BinaryOperator::GE, normalized_e2, normalized_e1)
@@ -729,6 +901,13 @@ impl Statement {
           (op, normalized_e1, normalized_e2)
         }
       }
+      hir::BinaryOperator::FLE => {
+        if normalized_e1 < normalized_e2 {
+          (hir::BinaryOperator::FGE, normalized_e2, normalized_e1)
+        } else {
+          (op, normalized_e1, normalized_e2)
+        }
+      }
       hir::BinaryOperator::GT => {
         if normalized_e1 < normalized_e2 {
           (hir::BinaryOperator::LT, normalized_e2, normalized_e1)
@@ -736,6 +915,13 @@ impl Statement {
           (op, normalized_e1, normalized_e2)
         }
       }
+      hir::BinaryOperator::FGT => {
+        if normalized_e1 < normalized_e2 {
+          (hir::BinaryOperator::FLT, normalized_e2, normalized_e1)
+        } else {
+          (op, normalized_e1, normalized_e2)
+        }
+      }
       hir::BinaryOperator::GE => {
         if normalized_e1 < normalized_e2 {
           (hir::BinaryOperator::LE, normalized_e2, normalized_e1)
@@ -743,6 +929,13 @@ impl Statement {
           (op, normalized_e1, normalized_e2)
         }
       }
+      hir::BinaryOperator::FGE => {
+        if normalized_e1 < normalized_e2 {
+          (hir::BinaryOperator::FLE, normalized_e2, normalized_e1)
+        } else {
+          (op, normalized_e1, normalized_e2)
+        }
+      }
     }
   }
 
@@ -962,6 +1155,29 @@ impl Statement {
           context.debug_print(heap, table)
         ));
       }
+      Self::InterfaceInit { interface_variable_name, interface_type_name, vtable, context } => {
+        let vtable_str = vtable
+          .iter()
+          .map(|f| {
+            format!(
+              "{}: {}",
+              f.name.encoded_for_test(heap, table),
+              f.type_.pretty_print(heap, table)
+            )
+          })
+          .join(", ");
+        collector.push(format!(
+          "{}let {}: {} = Interface {{ vtable: [{}], context: {} }};\n",
+          "  ".repeat(level),
+          interface_variable_name.as_str(heap),
+          interface_type_name.encoded_for_test(heap, table),
+          vtable_str,
+          context.debug_print(heap, table)
+        ));
+      }
+      Self::Return(e) => {
+        collector.push(format!("{}return {};\n", "  ".repeat(level), e.debug_print(heap, table)));
+      }
     }
   }
 
@@ -1014,7 +1230,10 @@ impl Function {
 pub struct Sources {
   pub symbol_table: SymbolTable,
   pub global_variables: Vec<hir::GlobalString>,
+  /// In initialization order: every constant comes after the constants it reads.
+  pub global_constants: Vec<GlobalConstant>,
   pub closure_types: Vec<ClosureTypeDefinition>,
+  pub interface_types: Vec<InterfaceTypeDefinition>,
   pub type_definitions: Vec<TypeDefinition>,
   pub main_function_names: Vec<FunctionName>,
   pub functions: Vec<Function>,
@@ -1026,9 +1245,20 @@ impl Sources {
     for (i, v) in self.global_variables.iter().enumerate() {
       lines.push(format!("const GLOBAL_STRING_{} = '{}';\n", i, v.0.as_str(heap)));
     }
+    for g in &self.global_constants {
+      lines.push(format!(
+        "const {}: {} = {}();\n",
+        g.initializer.type_name.encoded_for_test(heap, &self.symbol_table),
+        g.type_.pretty_print(heap, &self.symbol_table),
+        g.initializer.encoded_for_test(heap, &self.symbol_table)
+      ));
+    }
     for d in &self.closure_types {
       lines.push(d.pretty_print(heap, &self.symbol_table));
     }
+    for d in &self.interface_types {
+      lines.push(d.pretty_print(heap, &self.symbol_table));
+    }
     for d in &self.type_definitions {
       lines.push(d.pretty_print(heap, &self.symbol_table));
     }
@@ -21,7 +21,24 @@ mod tests {
     assert!(Expression::Int31Literal(1) >= Expression::i32(100));
     assert!(Expression::Int31Literal(1) <= Expression::Int31Literal(2));
     assert!(Expression::Int31Literal(1) <= Expression::StringName(PStr::EMPTY));
+    assert!(Expression::Int31Literal(1) <= Expression::float(1.0));
+    assert!(Expression::float(1.0) <= Expression::float(2.0));
+    assert!(Expression::float(1.0) <= Expression::StringName(PStr::EMPTY));
+    assert!(Expression::StringName(PStr::EMPTY) >= Expression::float(1.0));
+    assert!(Expression::float(1.0).convert_to_callee().is_none());
     assert!(ZERO.as_int32_literal().is_some());
+    let global_constant = Expression::GlobalConstant(GlobalConstant {
+      initializer: FunctionName {
+        type_name: table.create_type_name_for_test(PStr::UPPER_A),
+        fn_name: PStr::INIT,
+      },
+      type_: INT_32_TYPE,
+    });
+    assert!(Expression::StringName(PStr::EMPTY) <= global_constant);
+    assert!(global_constant <= global_constant);
+    assert!(global_constant <= Expression::var_name(PStr::LOWER_A, INT_32_TYPE));
+    assert!(global_constant.convert_to_callee().is_none());
+    assert!(!format!("{global_constant:?}").is_empty());
     Type::Id(table.create_type_name_for_test(PStr::UPPER_A)).as_id();
     Type::Id(table.create_type_name_for_test(PStr::UPPER_A)).is_int32();
     Type::Id(table.create_type_name_for_test(PStr::UPPER_A)).is_int31();
@@ -90,6 +107,7 @@ mod tests {
     let mut hasher = DefaultHasher::new();
     ZERO.hash(&mut hasher);
     Expression::Int31Literal(1).hash(&mut hasher);
+    Expression::float(1.0).hash(&mut hasher);
     Expression::StringName(PStr::LOWER_A).hash(&mut hasher);
     Expression::var_name(PStr::LOWER_A, INT_32_TYPE).hash(&mut hasher);
     Expression::var_name(PStr::LOWER_A, Type::Id(table.create_type_name_for_test(PStr::UPPER_A)))
@@ -139,7 +157,9 @@ mod tests {
 
     assert_eq!("int", INT_32_TYPE.pretty_print(heap, table));
     assert_eq!("i31", INT_31_TYPE.pretty_print(heap, table));
+    assert_eq!("float", FLOAT_TYPE.pretty_print(heap, table));
     assert_eq!("0", ZERO.dupe().debug_print(heap, table));
+    assert_eq!("2.5", Expression::float(2.5).debug_print(heap, table));
     assert_eq!(
       "(a: int)",
       Expression::var_name(PStr::LOWER_A, INT_32_TYPE).debug_print(heap, table)
@@ -211,6 +231,7 @@ mod tests {
         Statement::binary(heap.alloc_str_for_test("dd"), BinaryOperator::LOR, ZERO, ZERO),
         Statement::binary(heap.alloc_str_for_test("dd"), BinaryOperator::SHL, ZERO, ZERO),
         Statement::binary(heap.alloc_str_for_test("dd"), BinaryOperator::SHR, ZERO, ZERO),
+        Statement::binary(heap.alloc_str_for_test("dd"), BinaryOperator::ASHR, ZERO, ZERO),
         Statement::binary(heap.alloc_str_for_test("dd"), BinaryOperator::XOR, ZERO, ZERO),
         Statement::Cast {
           name: heap.alloc_str_for_test("cast"),
@@ -303,6 +324,7 @@ mod tests {
           ),
           index: 0,
         },
+        Statement::Return(ZERO),
         Statement::Break(ZERO),
       ],
       final_assignments: vec![IfElseFinalAssignment {
@@ -327,6 +349,7 @@ if 0 {
   let dd = 0 | 0;
   let dd = 0 << 0;
   let dd = 0 >>> 0;
+  let dd = 0 >> 0;
   let dd = 0 ^ 0;
   let cast = 0 as int;
   let cast: int;
@@ -358,6 +381,7 @@ if 0 {
   __$stresso((d: int));
   (d: int)((d: int));
   let f: int = (big: _FooBar)[0];
+  return 0;
   undefined = 0;
   break;
   bar = (b2: int);
@@ -372,6 +396,7 @@ if 0 {
 
     let sources1 = Sources {
       global_variables: vec![GlobalString(heap.alloc_str_for_test("dev_meggo_vibez"))],
+      global_constants: Vec::new(),
       closure_types: vec![
         ClosureTypeDefinition {
           name: table.create_type_name_for_test(PStr::UPPER_A),
@@ -379,6 +404,7 @@ if 0 {
         }
         .clone(),
       ],
+      interface_types: Vec::new(),
       type_definitions: vec![
         TypeDefinition {
           name: table.create_type_name_for_test(heap.alloc_str_for_test("Foo")),
@@ -423,7 +449,9 @@ sources.mains = [__$ddd]"#;
     let table = SymbolTable::new();
     let sources2 = Sources {
       global_variables: Vec::new(),
+      global_constants: Vec::new(),
       closure_types: Vec::new(),
+      interface_types: Vec::new(),
       type_definitions: Vec::new(),
       main_function_names: Vec::new(),
       functions: vec![Function {
@@ -573,5 +601,36 @@ sources.mains = [__$ddd]"#;
       (BinaryOperator::DIV, ZERO, ONE),
       Statement::flexible_order_binary(BinaryOperator::DIV, ZERO, ONE)
     );
+
+    let f0 = Expression::float(0.0);
+    let f1 = Expression::float(1.0);
+    assert_eq!(
+      (BinaryOperator::FPLUS, f1, f0),
+      Statement::flexible_order_binary(BinaryOperator::FPLUS, f0, f1)
+    );
+    assert_eq!(
+      (BinaryOperator::FMINUS, f0, f1),
+      Statement::flexible_order_binary(BinaryOperator::FMINUS, f0, f1)
+    );
+    assert_eq!(
+      (BinaryOperator::FGT, f1, f0),
+      Statement::flexible_order_binary(BinaryOperator::FLT, f0, f1)
+    );
+    assert_eq!(
+      (BinaryOperator::FGE, f1, f0),
+      Statement::flexible_order_binary(BinaryOperator::FLE, f0, f1)
+    );
+    assert_eq!(
+      (BinaryOperator::FLT, f1, f0),
+      Statement::flexible_order_binary(BinaryOperator::FGT, f0, f1)
+    );
+    assert_eq!(
+      (BinaryOperator::FLE, f1, f0),
+      Statement::flexible_order_binary(BinaryOperator::FGE, f0, f1)
+    );
+    assert_eq!(
+      (BinaryOperator::FGE, f1, f0),
+      Statement::flexible_order_binary(BinaryOperator::FGE, f1, f0)
+    );
   }
 }
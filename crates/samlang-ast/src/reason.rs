@@ -1,4 +1,7 @@
-use super::loc::Location;
+use super::{
+  loc::Location,
+  source::{Literal, Visibility},
+};
 use dupe::Dupe;
 use itertools::Itertools;
 use samlang_heap::{Heap, PStr};
@@ -8,9 +11,9 @@ pub enum Description {
   UnitType,
   BoolType,
   IntType,
+  FloatType,
   AnyType,
-  PrivateMember,
-  PublicMember,
+  Member(Visibility),
   GeneralFunctionType,
   GeneralNominalType,
   GeneralInterfaceType,
@@ -20,10 +23,12 @@ pub enum Description {
   Class(PStr),
   NominalType { name: PStr, type_args: Vec<Description> },
   FunctionType(Vec<Description>, Box<Description>),
+  TupleType(Vec<Description>),
   TypeParameter(PStr, Option<Box<Description>>),
   WildcardPattern,
   TuplePattern(Vec<Description>),
   VariantPattern(PStr, Vec<Description>),
+  LiteralPattern(Literal),
   OrPattern(Vec<Description>),
 }
 
@@ -33,9 +38,9 @@ impl Description {
       Self::UnitType => "unit".to_string(),
       Self::BoolType => "bool".to_string(),
       Self::IntType => "int".to_string(),
+      Self::FloatType => "float".to_string(),
       Self::AnyType => "any".to_string(),
-      Self::PrivateMember => "private member".to_string(),
-      Self::PublicMember => "public member".to_string(),
+      Self::Member(visibility) => format!("{} member", visibility.as_str()),
       Self::GeneralFunctionType => "function type".to_string(),
       Self::GeneralNominalType => "nominal type".to_string(),
       Self::GeneralInterfaceType => "interface type".to_string(),
@@ -60,6 +65,9 @@ impl Description {
           return_type.pretty_print(heap)
         )
       }
+      Self::TupleType(element_types) => {
+        format!("({})", element_types.iter().map(|t| t.pretty_print(heap)).join(", "))
+      }
       Self::TypeParameter(name, None) => name.as_str(heap).to_string(),
       Self::TypeParameter(name, Some(bound)) => {
         format!("{} : {}", name.as_str(heap), bound.pretty_print(heap))
@@ -79,6 +87,7 @@ impl Description {
           )
         }
       }
+      Self::LiteralPattern(literal) => literal.pretty_print(heap),
       Self::OrPattern(elements) => {
         elements.iter().map(|e| e.pretty_print(heap)).join(" | ").to_string()
       }
@@ -136,9 +145,11 @@ mod tests {
     assert_eq!("unit", Description::UnitType.pretty_print(heap));
     assert_eq!("bool", Description::BoolType.pretty_print(heap));
     assert_eq!("int", Description::IntType.pretty_print(heap));
+    assert_eq!("float", Description::FloatType.pretty_print(heap));
     assert_eq!("any", Description::AnyType.pretty_print(heap));
-    assert_eq!("private member", Description::PrivateMember.pretty_print(heap));
-    assert_eq!("public member", Description::PublicMember.pretty_print(heap));
+    assert_eq!("private member", Description::Member(Visibility::Private).pretty_print(heap));
+    assert_eq!("internal member", Description::Member(Visibility::Internal).pretty_print(heap));
+    assert_eq!("public member", Description::Member(Visibility::Public).pretty_print(heap));
     assert_eq!("function type", Description::GeneralFunctionType.pretty_print(heap));
     assert_eq!("nominal type", Description::GeneralNominalType.pretty_print(heap));
     assert_eq!("interface type", Description::GeneralInterfaceType.pretty_print(heap));
@@ -166,6 +177,7 @@ mod tests {
       Description::TypeParameter(PStr::UPPER_A, Some(Box::new(Description::IntType)))
         .pretty_print(heap)
     );
+    assert_eq!("false", Description::LiteralPattern(Literal::Bool(false)).pretty_print(heap));
     assert_eq!(
       "A | B((_, _))",
       Description::OrPattern(vec![
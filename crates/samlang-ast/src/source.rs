@@ -1,4 +1,4 @@
-use super::loc::Location;
+use super::{Float64, loc::Location};
 use dupe::Dupe;
 use enum_as_inner::EnumAsInner;
 use samlang_heap::{Heap, ModuleReference, PStr};
@@ -83,11 +83,16 @@ impl Default for CommentStore {
   }
 }
 
-#[derive(Clone, Dupe, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Dupe, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum Literal {
   Bool(bool),
-  Int(i32),
-  String(PStr),
+  /// An int value, with its source spelling when it is not the plain decimal form of the value,
+  /// so that hex, binary and digit separators survive formatting.
+  Int(i32, Option<PStr>),
+  Float(Float64),
+  /// A string value with escape sequences other than `\"` kept as written, with its source spelling
+  /// when it is written as a raw or multiline string.
+  String(PStr, Option<PStr>),
 }
 
 impl Literal {
@@ -99,19 +104,26 @@ impl Literal {
   }
 
   pub fn int_literal(i: i32) -> Literal {
-    Literal::Int(i)
+    Literal::Int(i, None)
+  }
+
+  pub fn float_literal(f: f64) -> Literal {
+    Literal::Float(Float64::new(f))
   }
 
   pub fn string_literal(s: PStr) -> Literal {
-    Literal::String(s)
+    Literal::String(s, None)
   }
 
   pub fn pretty_print(&self, heap: &Heap) -> String {
     match self {
       Self::Bool(true) => "true".to_string(),
       Self::Bool(false) => "false".to_string(),
-      Self::Int(i) => i.to_string(),
-      Self::String(s) => format!("\"{}\"", s.as_str(heap)),
+      Self::Int(_, Some(spelling)) => spelling.as_str(heap).to_string(),
+      Self::Int(i, None) => i.to_string(),
+      Self::Float(f) => f.pretty_print(),
+      Self::String(_, Some(spelling)) => spelling.as_str(heap).to_string(),
+      Self::String(s, None) => format!("\"{}\"", s.as_str(heap).replace('"', "\\\"")),
     }
   }
 }
@@ -127,6 +139,7 @@ pub mod annotation {
     Unit,
     Bool,
     Int,
+    Float,
     Any,
   }
 
@@ -136,6 +149,7 @@ pub mod annotation {
         Self::Unit => "unit",
         Self::Bool => "bool",
         Self::Int => "int",
+        Self::Float => "float",
         Self::Any => "any",
       }
     }
@@ -185,6 +199,8 @@ pub mod annotation {
     Id(Id),
     Generic(Location, super::Id),
     Fn(Function),
+    /// `(T1, T2, ...)`. The start comments of the list are the comments before `(`.
+    Tuple(ParenthesizedAnnotationList),
   }
 
   impl T {
@@ -194,6 +210,7 @@ pub mod annotation {
         Self::Id(annot) => annot.location,
         Self::Generic(l, _) => *l,
         Self::Fn(annot) => annot.location,
+        Self::Tuple(annot) => annot.location,
       }
     }
 
@@ -203,6 +220,7 @@ pub mod annotation {
         Self::Id(annot) => annot.id.associated_comments,
         Self::Generic(_, id) => id.associated_comments,
         Self::Fn(annot) => annot.associated_comments,
+        Self::Tuple(annot) => annot.start_associated_comments,
       }
     }
   }
@@ -297,6 +315,11 @@ pub mod pattern {
       location: Location,
       associated_comments: super::CommentReference,
     },
+    Literal {
+      location: Location,
+      associated_comments: super::CommentReference,
+      literal: super::Literal,
+    },
     Or {
       location: Location,
       patterns: Vec<MatchingPattern<T>>,
@@ -311,6 +334,7 @@ pub mod pattern {
         | Self::Variant(VariantPattern { loc: location, .. })
         | Self::Id(Id { loc: location, .. }, _)
         | Self::Wildcard { location, .. }
+        | Self::Literal { location, .. }
         | Self::Or { location, .. } => location,
       }
     }
@@ -333,7 +357,7 @@ pub mod pattern {
           }
           true
         }
-        Self::Variant(_) => false,
+        Self::Variant(_) | Self::Literal { .. } => false,
         Self::Id(_, _) | Self::Wildcard { .. } => true,
         Self::Or { patterns, .. } => patterns.iter().any(|p| p.always_matching()),
       }
@@ -361,7 +385,7 @@ pub mod pattern {
         Self::Id(Id { name, .. }, t) => {
           collector.insert(*name, t);
         }
-        Self::Wildcard { .. } => {}
+        Self::Wildcard { .. } | Self::Literal { .. } => {}
         Self::Or { patterns, .. } => {
           if let Some(first) = patterns.first() {
             first.collect_bindings(collector)
@@ -384,6 +408,8 @@ pub struct AnnotatedId<T: Clone> {
   pub name: Id,
   pub type_: T,
   pub annotation: annotation::T,
+  /// The constant expression used when a call doesn't pass the parameter.
+  pub default_value: Option<expr::E<()>>,
 }
 
 pub mod expr {
@@ -445,6 +471,7 @@ pub mod expr {
   pub enum UnaryOperator {
     NOT,
     NEG,
+    BNOT,
   }
 
   impl UnaryOperator {
@@ -452,6 +479,7 @@ pub mod expr {
       match self {
         Self::NOT => "!",
         Self::NEG => "-",
+        Self::BNOT => "~",
       }
     }
   }
@@ -469,11 +497,34 @@ pub mod expr {
     pub argument: Box<E<T>>,
   }
 
+  /// How the arguments of a call bind to the parameters of the callee.
+  #[derive(Clone, PartialEq, Eq)]
+  pub struct ArgumentBinding<T: Clone> {
+    /// Names of the trailing `name = value` arguments, in the order they are written.
+    pub names: Vec<Id>,
+    /// Filled by the checker: the parameter index of every argument.
+    /// It stays empty when all arguments are passed positionally.
+    pub parameter_indices: Vec<usize>,
+    /// Filled by the checker: the default values of parameters without arguments.
+    pub default_arguments: Vec<(usize, E<T>)>,
+  }
+
+  impl<T: Clone> Default for ArgumentBinding<T> {
+    fn default() -> Self {
+      ArgumentBinding {
+        names: Vec::new(),
+        parameter_indices: Vec::new(),
+        default_arguments: Vec::new(),
+      }
+    }
+  }
+
   #[derive(Clone, PartialEq, Eq)]
   pub struct Call<T: Clone> {
     pub common: ExpressionCommon<T>,
     pub callee: Box<E<T>>,
     pub arguments: ParenthesizedExpressionList<T>,
+    pub binding: ArgumentBinding<T>,
   }
 
   #[derive(Copy, Clone, Dupe, PartialEq, Eq)]
@@ -483,6 +534,12 @@ pub mod expr {
     MOD,
     PLUS,
     MINUS,
+    SHL,
+    ASHR,
+    SHR,
+    LAND,
+    XOR,
+    LOR,
     LT,
     LE,
     GT,
@@ -492,6 +549,8 @@ pub mod expr {
     AND,
     OR,
     CONCAT,
+    /// `e |> f` calls `f(e)`, and `e |> f(a)` inserts `e` as the first argument: `f(e, a)`.
+    PIPE,
   }
 
   impl BinaryOperator {
@@ -503,6 +562,12 @@ pub mod expr {
         Self::PLUS => "+",
         Self::MINUS => "-",
         Self::CONCAT => "::",
+        Self::SHL => "<<",
+        Self::ASHR => ">>",
+        Self::SHR => ">>>",
+        Self::LAND => "&",
+        Self::XOR => "^",
+        Self::LOR => "|",
         Self::LT => "<",
         Self::LE => "<=",
         Self::GT => ">",
@@ -511,6 +576,7 @@ pub mod expr {
         Self::NE => "!=",
         Self::AND => "&&",
         Self::OR => "||",
+        Self::PIPE => "|>",
       }
     }
   }
@@ -530,14 +596,21 @@ pub mod expr {
         Self::PLUS => 1,
         Self::MINUS => 1,
         Self::CONCAT => 1,
-        Self::LT => 2,
-        Self::LE => 2,
-        Self::GT => 2,
-        Self::GE => 2,
-        Self::EQ => 2,
-        Self::NE => 2,
-        Self::AND => 3,
-        Self::OR => 4,
+        Self::SHL => 2,
+        Self::ASHR => 2,
+        Self::SHR => 2,
+        Self::LAND => 3,
+        Self::XOR => 4,
+        Self::LOR => 5,
+        Self::LT => 6,
+        Self::LE => 6,
+        Self::GT => 6,
+        Self::GE => 6,
+        Self::EQ => 6,
+        Self::NE => 6,
+        Self::AND => 7,
+        Self::OR => 8,
+        Self::PIPE => 9,
       }
     }
   }
@@ -575,6 +648,7 @@ pub mod expr {
   pub struct VariantPatternToExpression<T: Clone> {
     pub loc: Location,
     pub pattern: pattern::MatchingPattern<T>,
+    pub guard: Option<Box<E<T>>>,
     pub body: Box<E<T>>,
     pub ending_associated_comments: CommentReference,
   }
@@ -601,18 +675,106 @@ pub mod expr {
     pub body: Box<E<T>>,
   }
 
+  #[derive(Clone, PartialEq, Eq)]
+  pub struct Return<T: Clone> {
+    pub common: ExpressionCommon<T>,
+    pub argument: Box<E<T>>,
+  }
+
+  #[derive(Clone, PartialEq, Eq)]
+  pub struct While<T: Clone> {
+    pub common: ExpressionCommon<T>,
+    pub condition: Box<E<T>>,
+    pub body: Box<Block<T>>,
+  }
+
+  /// A counted loop `for (iterator in start..end) { ... }` over the half-open range.
+  #[derive(Clone, PartialEq, Eq)]
+  pub struct For<T: Clone> {
+    pub common: ExpressionCommon<T>,
+    pub iterator: Id,
+    pub start: Box<E<T>>,
+    pub end: Box<E<T>>,
+    pub body: Box<Block<T>>,
+  }
+
+  /// `assert(condition)` or `assert(condition, message)`.
+  #[derive(Clone, PartialEq, Eq)]
+  pub struct Assert<T: Clone> {
+    pub common: ExpressionCommon<T>,
+    pub arguments: ParenthesizedExpressionList<T>,
+  }
+
+  /// `"text ${hole} text"`. `holes[i]` sits between `parts[i]` and `parts[i + 1]`, so there is
+  /// always one more part than holes.
+  #[derive(Clone, PartialEq, Eq)]
+  pub struct TemplateString<T: Clone> {
+    pub common: ExpressionCommon<T>,
+    pub parts: Vec<PStr>,
+    pub holes: Vec<E<T>>,
+  }
+
+  /// `argument?`, which exits the enclosing function early on `None` or `Error`.
+  #[derive(Clone, PartialEq, Eq)]
+  pub struct Try<T: Clone> {
+    pub common: ExpressionCommon<T>,
+    pub argument: Box<E<T>>,
+  }
+
+  #[derive(Clone, PartialEq, Eq)]
+  pub struct FieldUpdate<T: Clone> {
+    pub loc: Location,
+    pub field_name: Id,
+    pub field_order: i32,
+    pub value: E<T>,
+  }
+
+  /// `{ ...base, field: value }`, which copies a struct with some of its fields replaced.
+  #[derive(Clone, PartialEq, Eq)]
+  pub struct StructUpdate<T: Clone> {
+    pub common: ExpressionCommon<T>,
+    pub base: Box<E<T>>,
+    pub fields: Vec<FieldUpdate<T>>,
+    pub ending_associated_comments: CommentReference,
+  }
+
   #[derive(Clone, PartialEq, Eq)]
   pub struct DeclarationStatement<T: Clone> {
     pub loc: Location,
     pub associated_comments: CommentReference,
+    /// Whether the declaration is introduced by `var` instead of `let`.
+    pub is_mutable: bool,
     pub pattern: super::pattern::MatchingPattern<T>,
     pub annotation: Option<annotation::T>,
     pub assigned_expression: Box<E<T>>,
+    /// The block of a `let ... else` declaration, which runs when the pattern doesn't match.
+    pub else_block: Option<Box<Block<T>>>,
+  }
+
+  #[derive(Clone, PartialEq, Eq)]
+  pub struct AssignmentStatement<T: Clone> {
+    pub loc: Location,
+    pub associated_comments: CommentReference,
+    pub name: Id,
+    pub assigned_expression: Box<E<T>>,
+  }
+
+  /// A block-local `function` declaration, whose name is also in scope in its own body.
+  #[derive(Clone, PartialEq, Eq)]
+  pub struct LocalFunctionStatement<T: Clone> {
+    pub loc: Location,
+    pub associated_comments: CommentReference,
+    pub name: Id,
+    pub return_annotation: annotation::T,
+    /// The function value. All of its parameters are annotated.
+    pub lambda: Lambda<T>,
   }
 
   #[derive(Clone, PartialEq, Eq)]
   pub enum Statement<T: Clone> {
     Declaration(Box<DeclarationStatement<T>>),
+    Assignment(Box<AssignmentStatement<T>>),
+    Function(Box<LocalFunctionStatement<T>>),
     Expression(Box<E<T>>),
   }
 
@@ -620,6 +782,8 @@ pub mod expr {
     pub fn loc(&self) -> Location {
       match self {
         Self::Declaration(s) => s.loc,
+        Self::Assignment(s) => s.loc,
+        Self::Function(s) => s.loc,
         Self::Expression(e) => e.loc(),
       }
     }
@@ -639,6 +803,7 @@ pub mod expr {
     LocalId(ExpressionCommon<T>, Id),
     ClassId(ExpressionCommon<T>, ModuleReference, Id),
     Tuple(ExpressionCommon<T>, ParenthesizedExpressionList<T>),
+    TemplateString(TemplateString<T>),
     FieldAccess(FieldAccess<T>),
     MethodAccess(MethodAccess<T>),
     Unary(Unary<T>),
@@ -647,6 +812,13 @@ pub mod expr {
     IfElse(IfElse<T>),
     Match(Match<T>),
     Lambda(Lambda<T>),
+    Return(Return<T>),
+    While(While<T>),
+    For(For<T>),
+    Break(ExpressionCommon<T>),
+    Assert(Assert<T>),
+    Try(Try<T>),
+    StructUpdate(StructUpdate<T>),
     Block(Block<T>),
   }
 
@@ -657,6 +829,7 @@ pub mod expr {
         | Self::LocalId(common, _)
         | Self::ClassId(common, _, _)
         | Self::Tuple(common, _)
+        | Self::TemplateString(TemplateString { common, .. })
         | Self::FieldAccess(FieldAccess { common, .. })
         | Self::MethodAccess(MethodAccess { common, .. })
         | Self::Unary(Unary { common, .. })
@@ -665,6 +838,13 @@ pub mod expr {
         | Self::IfElse(IfElse { common, .. })
         | Self::Match(Match { common, .. })
         | Self::Lambda(Lambda { common, .. })
+        | Self::Return(Return { common, .. })
+        | Self::While(While { common, .. })
+        | Self::For(For { common, .. })
+        | Self::Break(common)
+        | Self::Assert(Assert { common, .. })
+        | Self::Try(Try { common, .. })
+        | Self::StructUpdate(StructUpdate { common, .. })
         | Self::Block(Block { common, .. }) => common,
       }
     }
@@ -675,6 +855,7 @@ pub mod expr {
         | Self::LocalId(common, _)
         | Self::ClassId(common, _, _)
         | Self::Tuple(common, _)
+        | Self::TemplateString(TemplateString { common, .. })
         | Self::FieldAccess(FieldAccess { common, .. })
         | Self::MethodAccess(MethodAccess { common, .. })
         | Self::Unary(Unary { common, .. })
@@ -683,6 +864,13 @@ pub mod expr {
         | Self::IfElse(IfElse { common, .. })
         | Self::Match(Match { common, .. })
         | Self::Lambda(Lambda { common, .. })
+        | Self::Return(Return { common, .. })
+        | Self::While(While { common, .. })
+        | Self::For(For { common, .. })
+        | Self::Break(common)
+        | Self::Assert(Assert { common, .. })
+        | Self::Try(Try { common, .. })
+        | Self::StructUpdate(StructUpdate { common, .. })
         | Self::Block(Block { common, .. }) => common,
       }
     }
@@ -697,13 +885,25 @@ pub mod expr {
 
     pub fn precedence(&self) -> i32 {
       match self {
-        Self::Literal(_, _) | Self::LocalId(_, _) | Self::ClassId(_, _, _) | Self::Tuple(_, _) => 0,
-        Self::FieldAccess(_) | Self::MethodAccess(_) | Self::Call(_) | Self::Block(_) => 1,
+        Self::Literal(_, _)
+        | Self::LocalId(_, _)
+        | Self::ClassId(_, _, _)
+        | Self::Tuple(_, _)
+        | Self::TemplateString(_)
+        | Self::StructUpdate(_)
+        | Self::Break(_) => 0,
+        Self::FieldAccess(_)
+        | Self::MethodAccess(_)
+        | Self::Call(_)
+        | Self::Assert(_)
+        | Self::Try(_)
+        | Self::Block(_) => 1,
         Self::Unary(_) => 2,
         Self::Binary(b) => 4 + b.operator.precedence(),
-        Self::IfElse(_) => 10,
-        Self::Match(_) => 11,
-        Self::Lambda(_) => 12,
+        Self::IfElse(_) | Self::While(_) | Self::For(_) => 14,
+        Self::Match(_) => 15,
+        Self::Lambda(_) => 16,
+        Self::Return(_) => 17,
       }
     }
   }
@@ -717,11 +917,34 @@ pub struct FunctionParameters {
   pub parameters: Arc<Vec<AnnotatedId<()>>>,
 }
 
+/// Visibility of toplevels and class members, ordered from the most to the least restrictive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Visibility {
+  /// Only visible within the same class (members) or module (toplevels).
+  Private,
+  /// Only visible within the same class and its subtypes. Only applicable to members.
+  Protected,
+  /// Only visible to modules that share the parent module path of the declaring module.
+  Internal,
+  Public,
+}
+
+impl Visibility {
+  pub fn as_str(&self) -> &'static str {
+    match self {
+      Self::Private => "private",
+      Self::Protected => "protected",
+      Self::Internal => "internal",
+      Self::Public => "public",
+    }
+  }
+}
+
 #[derive(Clone, PartialEq, Eq)]
 pub struct ClassMemberDeclaration {
   pub loc: Location,
   pub associated_comments: CommentReference,
-  pub is_public: bool,
+  pub visibility: Visibility,
   pub is_method: bool,
   pub name: Id,
   pub type_parameters: Option<annotation::TypeParameters>,
@@ -743,6 +966,15 @@ pub struct ExtendsOrImplementsNodes {
   pub nodes: Vec<annotation::Id>,
 }
 
+/// `derives equals, compare, toString` on a class. The derived members are synthesized by the
+/// parser and located at the corresponding entry of this clause.
+#[derive(Clone, PartialEq, Eq)]
+pub struct DerivesClause {
+  pub location: Location,
+  pub associated_comments: CommentReference,
+  pub names: Vec<Id>,
+}
+
 #[derive(Clone, PartialEq, Eq)]
 pub struct InterfaceMembersCommon<M> {
   pub loc: Location,
@@ -754,15 +986,23 @@ pub struct InterfaceMembersCommon<M> {
 pub struct InterfaceDeclarationCommon<D, M> {
   pub loc: Location,
   pub associated_comments: CommentReference,
-  pub private: bool,
+  pub visibility: Visibility,
   pub name: Id,
   pub type_parameters: Option<annotation::TypeParameters>,
   pub extends_or_implements_nodes: Option<ExtendsOrImplementsNodes>,
+  pub derives: Option<DerivesClause>,
   pub type_definition: D,
   pub members: InterfaceMembersCommon<M>,
 }
 
-pub type InterfaceDeclaration = InterfaceDeclarationCommon<(), ClassMemberDeclaration>;
+/// An interface member. Methods may carry a default body that implementing classes inherit.
+#[derive(Clone, PartialEq, Eq)]
+pub struct InterfaceMemberDefinition<T: Clone> {
+  pub decl: ClassMemberDeclaration,
+  pub default_body: Option<expr::E<T>>,
+}
+
+pub type InterfaceDeclaration<T> = InterfaceDeclarationCommon<(), InterfaceMemberDefinition<T>>;
 
 #[derive(Clone, PartialEq, Eq)]
 pub struct FieldDefinition {
@@ -832,15 +1072,39 @@ impl TypeDefinition {
 pub type ClassDefinition<T> =
   InterfaceDeclarationCommon<Option<TypeDefinition>, ClassMemberDefinition<T>>;
 
+/// `type Name<T> = annotation;`
+#[derive(Clone, PartialEq, Eq)]
+pub struct TypeAliasDefinition {
+  pub loc: Location,
+  pub associated_comments: CommentReference,
+  pub visibility: Visibility,
+  pub name: Id,
+  pub type_parameters: Option<annotation::TypeParameters>,
+  pub annotation: annotation::T,
+}
+
+/// `const NAME: annotation = value;`
+#[derive(Clone, PartialEq, Eq)]
+pub struct ConstDefinition<T: Clone> {
+  pub loc: Location,
+  pub associated_comments: CommentReference,
+  pub visibility: Visibility,
+  pub name: Id,
+  pub annotation: annotation::T,
+  pub value: expr::E<T>,
+}
+
 #[derive(Clone, PartialEq, Eq)]
 pub enum Toplevel<T: Clone> {
-  Interface(InterfaceDeclaration),
+  Interface(InterfaceDeclaration<T>),
   Class(ClassDefinition<T>),
+  TypeAlias(TypeAliasDefinition),
+  Const(ConstDefinition<T>),
 }
 
 pub enum MemberDeclarationsIterator<'a, T: Clone> {
   Class(std::slice::Iter<'a, ClassMemberDefinition<T>>),
-  Interface(std::slice::Iter<'a, ClassMemberDeclaration>),
+  Interface(std::slice::Iter<'a, InterfaceMemberDefinition<T>>),
 }
 
 impl<'a, T: Clone> Iterator for MemberDeclarationsIterator<'a, T> {
@@ -849,7 +1113,7 @@ impl<'a, T: Clone> Iterator for MemberDeclarationsIterator<'a, T> {
   fn next(&mut self) -> Option<Self::Item> {
     match self {
       Self::Class(iter) => iter.next().map(|it| &it.decl),
-      Self::Interface(iter) => iter.next(),
+      Self::Interface(iter) => iter.next().map(|it| &it.decl),
     }
   }
 }
@@ -857,7 +1121,7 @@ impl<'a, T: Clone> Iterator for MemberDeclarationsIterator<'a, T> {
 impl<T: Clone> Toplevel<T> {
   pub fn is_class(&self) -> bool {
     match self {
-      Self::Interface(_) => false,
+      Self::Interface(_) | Self::TypeAlias(_) | Self::Const(_) => false,
       Self::Class(_) => true,
     }
   }
@@ -866,6 +1130,8 @@ impl<T: Clone> Toplevel<T> {
     match self {
       Self::Interface(i) => i.loc,
       Self::Class(c) => c.loc,
+      Self::TypeAlias(a) => a.loc,
+      Self::Const(c) => c.loc,
     }
   }
 
@@ -873,13 +1139,17 @@ impl<T: Clone> Toplevel<T> {
     match self {
       Self::Interface(i) => i.associated_comments,
       Self::Class(c) => c.associated_comments,
+      Self::TypeAlias(a) => a.associated_comments,
+      Self::Const(c) => c.associated_comments,
     }
   }
 
-  pub fn is_private(&self) -> bool {
+  pub fn visibility(&self) -> Visibility {
     match self {
-      Self::Interface(i) => i.private,
-      Self::Class(c) => c.private,
+      Self::Interface(i) => i.visibility,
+      Self::Class(c) => c.visibility,
+      Self::TypeAlias(a) => a.visibility,
+      Self::Const(c) => c.visibility,
     }
   }
 
@@ -887,6 +1157,8 @@ impl<T: Clone> Toplevel<T> {
     match self {
       Self::Interface(i) => &i.name,
       Self::Class(c) => &c.name,
+      Self::TypeAlias(a) => &a.name,
+      Self::Const(c) => &c.name,
     }
   }
 
@@ -894,6 +1166,8 @@ impl<T: Clone> Toplevel<T> {
     match self {
       Self::Interface(i) => i.type_parameters.as_ref(),
       Self::Class(c) => c.type_parameters.as_ref(),
+      Self::TypeAlias(a) => a.type_parameters.as_ref(),
+      Self::Const(_) => None,
     }
   }
 
@@ -901,12 +1175,13 @@ impl<T: Clone> Toplevel<T> {
     match self {
       Self::Interface(i) => i.extends_or_implements_nodes.as_ref(),
       Self::Class(c) => c.extends_or_implements_nodes.as_ref(),
+      Self::TypeAlias(_) | Self::Const(_) => None,
     }
   }
 
   pub fn type_definition(&self) -> Option<&TypeDefinition> {
     match self {
-      Self::Interface(_) => None,
+      Self::Interface(_) | Self::TypeAlias(_) | Self::Const(_) => None,
       Self::Class(c) => c.type_definition.as_ref(),
     }
   }
@@ -915,6 +1190,7 @@ impl<T: Clone> Toplevel<T> {
     match self {
       Self::Interface(i) => MemberDeclarationsIterator::Interface(i.members.members.iter()),
       Self::Class(c) => MemberDeclarationsIterator::Class(c.members.members.iter()),
+      Self::TypeAlias(_) | Self::Const(_) => MemberDeclarationsIterator::Interface([].iter()),
     }
   }
 }
@@ -1040,6 +1316,15 @@ pub mod test_builder {
     ) -> annotation::T {
       annotation::T::Fn(self.fn_annot_unwrapped(argument_types, return_type))
     }
+
+    pub fn tuple_annot(&self, element_types: Vec<annotation::T>) -> annotation::T {
+      annotation::T::Tuple(annotation::ParenthesizedAnnotationList {
+        location: Location::dummy(),
+        start_associated_comments: NO_COMMENT_REFERENCE,
+        ending_associated_comments: NO_COMMENT_REFERENCE,
+        annotations: element_types,
+      })
+    }
   }
 
   pub fn create() -> CustomizedAstBuilder {
@@ -36,6 +36,7 @@ mod tests {
 
     assert_eq!("!", expr::UnaryOperator::NOT.dupe().to_string());
     assert_eq!("-", expr::UnaryOperator::NEG.dupe().to_string());
+    assert_eq!("~", expr::UnaryOperator::BNOT.dupe().to_string());
 
     let list = [
       expr::BinaryOperator::MUL,
@@ -44,6 +45,12 @@ mod tests {
       expr::BinaryOperator::PLUS,
       expr::BinaryOperator::MINUS,
       expr::BinaryOperator::CONCAT,
+      expr::BinaryOperator::SHL,
+      expr::BinaryOperator::ASHR,
+      expr::BinaryOperator::SHR,
+      expr::BinaryOperator::LAND,
+      expr::BinaryOperator::XOR,
+      expr::BinaryOperator::LOR,
       expr::BinaryOperator::LT,
       expr::BinaryOperator::LE,
       expr::BinaryOperator::GT,
@@ -249,6 +256,14 @@ mod tests {
     matching_pattern.bindings();
     assert!(matching_pattern.always_matching());
     assert_eq!(*matching_pattern.loc(), Location::dummy());
+    matching_pattern = pattern::MatchingPattern::Literal {
+      location: Location::dummy(),
+      associated_comments: NO_COMMENT_REFERENCE,
+      literal: Literal::Int(1, None),
+    };
+    assert!(matching_pattern.bindings().is_empty());
+    assert_eq!(false, matching_pattern.always_matching());
+    assert_eq!(*matching_pattern.loc(), Location::dummy());
     assert!(
       pattern::MatchingPattern::Variant(pattern::VariantPattern {
         loc: Location::dummy(),
@@ -323,6 +338,7 @@ mod tests {
     assert_eq!("true", Literal::true_literal().pretty_print(&heap));
     assert_eq!("false", Literal::false_literal().pretty_print(&heap));
     assert_eq!("0", Literal::int_literal(0).dupe().pretty_print(&heap));
+    assert_eq!("1.5", Literal::float_literal(1.5).pretty_print(&heap));
     assert_eq!(
       "\"hi\"",
       Literal::string_literal(heap.alloc_str_for_test("hi")).dupe().pretty_print(&heap)
@@ -335,6 +351,7 @@ mod tests {
     assert_eq!("unit", annotation::PrimitiveTypeKind::Unit.to_string());
     assert_eq!("bool", annotation::PrimitiveTypeKind::Bool.to_string());
     assert_eq!("int", annotation::PrimitiveTypeKind::Int.to_string());
+    assert_eq!("float", annotation::PrimitiveTypeKind::Float.to_string());
   }
 
   #[test]
@@ -369,7 +386,7 @@ mod tests {
   fn precedence_boilerplate_tests() {
     let mut heap = Heap::new();
     let common = ExpressionCommon::dummy(());
-    let zero_expr = E::Literal(ExpressionCommon::dummy(()), Literal::Int(0));
+    let zero_expr = E::Literal(ExpressionCommon::dummy(()), Literal::Int(0, None));
 
     coverage_hack_for_expr(zero_expr.clone());
     coverage_hack_for_expr(E::LocalId(common.clone(), Id::from(heap.alloc_str_for_test("s"))));
@@ -411,6 +428,7 @@ mod tests {
         ending_associated_comments: NO_COMMENT_REFERENCE,
         expressions: Vec::new(),
       },
+      binding: Default::default(),
     }));
     coverage_hack_for_expr(E::Unary(Unary {
       common: common.clone(),
@@ -481,6 +499,7 @@ mod tests {
           location: Location::dummy(),
           associated_comments: NO_COMMENT_REFERENCE,
         },
+        guard: Some(Box::new(zero_expr.clone())),
         body: Box::new(zero_expr.clone()),
         ending_associated_comments: NO_COMMENT_REFERENCE,
       }],
@@ -499,12 +518,65 @@ mod tests {
       captured: HashMap::new(),
       body: Box::new(zero_expr.clone()),
     }));
+    coverage_hack_for_expr(E::Return(Return {
+      common: common.clone(),
+      argument: Box::new(zero_expr.clone()),
+    }));
+    let empty_block = Block {
+      common: common.clone(),
+      statements: vec![],
+      expression: None,
+      ending_associated_comments: NO_COMMENT_REFERENCE,
+    };
+    coverage_hack_for_expr(E::While(While {
+      common: common.clone(),
+      condition: Box::new(zero_expr.clone()),
+      body: Box::new(empty_block.clone()),
+    }));
+    coverage_hack_for_expr(E::For(For {
+      common: common.clone(),
+      iterator: Id::from(heap.alloc_str_for_test("i")),
+      start: Box::new(zero_expr.clone()),
+      end: Box::new(zero_expr.clone()),
+      body: Box::new(empty_block),
+    }));
+    coverage_hack_for_expr(E::Break(common.clone()));
+    coverage_hack_for_expr(E::Assert(Assert {
+      common: common.clone(),
+      arguments: ParenthesizedExpressionList {
+        loc: Location::dummy(),
+        start_associated_comments: NO_COMMENT_REFERENCE,
+        ending_associated_comments: NO_COMMENT_REFERENCE,
+        expressions: vec![zero_expr.clone()],
+      },
+    }));
+    coverage_hack_for_expr(E::TemplateString(TemplateString {
+      common: common.clone(),
+      parts: vec![PStr::EMPTY, PStr::EMPTY],
+      holes: vec![zero_expr.clone()],
+    }));
+    coverage_hack_for_expr(E::Try(Try {
+      common: common.clone(),
+      argument: Box::new(zero_expr.clone()),
+    }));
+    coverage_hack_for_expr(E::StructUpdate(StructUpdate {
+      common: common.clone(),
+      base: Box::new(zero_expr.clone()),
+      fields: vec![FieldUpdate {
+        loc: Location::dummy(),
+        field_name: Id::from(heap.alloc_str_for_test("name")),
+        field_order: 0,
+        value: zero_expr.clone(),
+      }],
+      ending_associated_comments: NO_COMMENT_REFERENCE,
+    }));
     coverage_hack_for_expr(E::Block(Block {
       common,
       statements: vec![
         expr::Statement::Declaration(Box::new(expr::DeclarationStatement {
           loc: Location::dummy(),
           associated_comments: NO_COMMENT_REFERENCE,
+          is_mutable: false,
           pattern: pattern::MatchingPattern::Object {
             location: Location::dummy(),
             start_associated_comments: NO_COMMENT_REFERENCE,
@@ -527,10 +599,12 @@ mod tests {
             annotation::PrimitiveTypeKind::Int,
           )),
           assigned_expression: Box::new(zero_expr.clone()),
+          else_block: None,
         })),
         expr::Statement::Declaration(Box::new(expr::DeclarationStatement {
           loc: Location::dummy(),
           associated_comments: NO_COMMENT_REFERENCE,
+          is_mutable: false,
           pattern: pattern::MatchingPattern::Tuple(pattern::TuplePattern {
             location: Location::dummy(),
             start_associated_comments: NO_COMMENT_REFERENCE,
@@ -549,10 +623,12 @@ mod tests {
             annotation::PrimitiveTypeKind::Int,
           )),
           assigned_expression: Box::new(zero_expr.clone()),
+          else_block: None,
         })),
         expr::Statement::Declaration(Box::new(expr::DeclarationStatement {
           loc: Location::dummy(),
           associated_comments: NO_COMMENT_REFERENCE,
+          is_mutable: false,
           pattern: pattern::MatchingPattern::Wildcard {
             location: Location::dummy(),
             associated_comments: NO_COMMENT_REFERENCE,
@@ -563,10 +639,12 @@ mod tests {
             annotation::PrimitiveTypeKind::Int,
           )),
           assigned_expression: Box::new(zero_expr.clone()),
+          else_block: None,
         })),
         expr::Statement::Declaration(Box::new(expr::DeclarationStatement {
           loc: Location::dummy(),
           associated_comments: NO_COMMENT_REFERENCE,
+          is_mutable: false,
           pattern: pattern::MatchingPattern::Id(Id::from(heap.alloc_str_for_test("s")), ()),
           annotation: Some(annotation::T::Fn(annotation::Function {
             location: Location::dummy(),
@@ -606,6 +684,7 @@ mod tests {
             )),
           })),
           assigned_expression: Box::new(zero_expr.clone()),
+          else_block: None,
         })),
         expr::Statement::Expression(Box::new(zero_expr.clone())),
       ],
@@ -617,14 +696,37 @@ mod tests {
     let decl_stmt = expr::Statement::Declaration(Box::new(expr::DeclarationStatement {
       loc: Location::dummy(),
       associated_comments: NO_COMMENT_REFERENCE,
+      is_mutable: false,
       pattern: pattern::MatchingPattern::Wildcard {
         location: Location::dummy(),
         associated_comments: NO_COMMENT_REFERENCE,
       },
       annotation: None,
-      assigned_expression: Box::new(E::Literal(ExpressionCommon::dummy(()), Literal::Int(0))),
+      assigned_expression: Box::new(E::Literal(ExpressionCommon::dummy(()), Literal::Int(0, None))),
+      else_block: None,
     }));
     let _ = decl_stmt.loc();
+    let function_stmt = expr::Statement::Function(Box::new(expr::LocalFunctionStatement {
+      loc: Location::dummy(),
+      associated_comments: NO_COMMENT_REFERENCE,
+      name: Id::from(heap.alloc_str_for_test("f")),
+      return_annotation: annotation::T::Primitive(
+        Location::dummy(),
+        NO_COMMENT_REFERENCE,
+        annotation::PrimitiveTypeKind::Int,
+      ),
+      lambda: expr::Lambda {
+        common: ExpressionCommon::dummy(()),
+        parameters: expr::LambdaParameters {
+          loc: Location::dummy(),
+          parameters: Vec::new(),
+          ending_associated_comments: NO_COMMENT_REFERENCE,
+        },
+        captured: HashMap::new(),
+        body: Box::new(E::Literal(ExpressionCommon::dummy(()), Literal::Int(0, None))),
+      },
+    }));
+    let _ = function_stmt.clone().loc();
   }
 
   #[test]
@@ -651,7 +753,8 @@ mod tests {
           Location::dummy(),
           NO_COMMENT_REFERENCE,
           annotation::PrimitiveTypeKind::Bool
-        )
+        ),
+        default_value: None,
       }
       .name
       .name
@@ -664,7 +767,7 @@ mod tests {
       InterfaceDeclaration {
         loc: Location::dummy(),
         associated_comments: NO_COMMENT_REFERENCE,
-        private: false,
+        visibility: Visibility::Public,
         name: Id::from(PStr::LOWER_A),
         type_parameters: None,
         extends_or_implements_nodes: Some(ExtendsOrImplementsNodes {
@@ -672,32 +775,40 @@ mod tests {
           associated_comments: NO_COMMENT_REFERENCE,
           nodes: Vec::new(),
         }),
+        derives: None,
         type_definition: (),
         members: InterfaceMembersCommon {
           loc: Location::dummy(),
-          members: vec![ClassMemberDeclaration {
-            loc: Location::dummy(),
-            associated_comments: NO_COMMENT_REFERENCE,
-            is_public: true,
-            is_method: true,
-            name: Id::from(PStr::LOWER_A),
-            type_parameters: Some(annotation::TypeParameters {
-              location: Location::dummy(),
-              start_associated_comments: NO_COMMENT_REFERENCE,
-              ending_associated_comments: NO_COMMENT_REFERENCE,
-              parameters: Vec::new()
-            }),
-            parameters: FunctionParameters {
-              location: Location::dummy(),
-              start_associated_comments: NO_COMMENT_REFERENCE,
-              ending_associated_comments: NO_COMMENT_REFERENCE,
-              parameters: Arc::new(vec![AnnotatedId {
-                name: Id::from(PStr::LOWER_A),
-                type_: (),
-                annotation: builder.int_annot()
-              }])
+          members: vec![InterfaceMemberDefinition {
+            decl: ClassMemberDeclaration {
+              loc: Location::dummy(),
+              associated_comments: NO_COMMENT_REFERENCE,
+              visibility: Visibility::Public,
+              is_method: true,
+              name: Id::from(PStr::LOWER_A),
+              type_parameters: Some(annotation::TypeParameters {
+                location: Location::dummy(),
+                start_associated_comments: NO_COMMENT_REFERENCE,
+                ending_associated_comments: NO_COMMENT_REFERENCE,
+                parameters: Vec::new()
+              }),
+              parameters: FunctionParameters {
+                location: Location::dummy(),
+                start_associated_comments: NO_COMMENT_REFERENCE,
+                ending_associated_comments: NO_COMMENT_REFERENCE,
+                parameters: Arc::new(vec![AnnotatedId {
+                  name: Id::from(PStr::LOWER_A),
+                  type_: (),
+                  annotation: builder.int_annot(),
+                  default_value: None,
+                }])
+              },
+              return_type: builder.int_annot(),
             },
-            return_type: builder.int_annot(),
+            default_body: Some(expr::E::Literal(
+              expr::ExpressionCommon::dummy(()),
+              Literal::Int(0, None)
+            )),
           }],
           ending_associated_comments: NO_COMMENT_REFERENCE
         }
@@ -744,9 +855,18 @@ mod tests {
     assert!(enum_type_def.eq(&enum_type_def));
 
     assert!(
-      AnnotatedId { name: Id::from(PStr::LOWER_A), type_: (), annotation: builder.int_annot() }.eq(
-        &AnnotatedId { name: Id::from(PStr::LOWER_A), type_: (), annotation: builder.int_annot() }
-      )
+      AnnotatedId {
+        name: Id::from(PStr::LOWER_A),
+        type_: (),
+        annotation: builder.int_annot(),
+        default_value: None
+      }
+      .eq(&AnnotatedId {
+        name: Id::from(PStr::LOWER_A),
+        type_: (),
+        annotation: builder.int_annot(),
+        default_value: None
+      })
     );
     assert!(
       annotation::TypeParameter {
@@ -764,7 +884,7 @@ mod tests {
     let class = Toplevel::Class(InterfaceDeclarationCommon {
       loc: Location::dummy(),
       associated_comments: NO_COMMENT_REFERENCE,
-      private: false,
+      visibility: Visibility::Public,
       name: Id::from(heap.alloc_str_for_test("name")),
       type_parameters: Some(annotation::TypeParameters {
         location: Location::dummy(),
@@ -777,6 +897,11 @@ mod tests {
         associated_comments: NO_COMMENT_REFERENCE,
         nodes: Vec::new(),
       }),
+      derives: Some(DerivesClause {
+        location: Location::dummy(),
+        associated_comments: NO_COMMENT_REFERENCE,
+        names: vec![Id::from(PStr::LOWER_A)],
+      }),
       type_definition: Some(TypeDefinition::Struct {
         loc: Location::dummy(),
         start_associated_comments: NO_COMMENT_REFERENCE,
@@ -793,7 +918,7 @@ mod tests {
           decl: ClassMemberDeclaration {
             loc: Location::dummy(),
             associated_comments: NO_COMMENT_REFERENCE,
-            is_public: true,
+            visibility: Visibility::Public,
             is_method: true,
             name: Id::from(PStr::LOWER_A),
             type_parameters: Some(annotation::TypeParameters {
@@ -810,7 +935,7 @@ mod tests {
             },
             return_type: builder.int_annot(),
           },
-          body: expr::E::Literal(expr::ExpressionCommon::dummy(()), Literal::Int(0)),
+          body: expr::E::Literal(expr::ExpressionCommon::dummy(()), Literal::Int(0, None)),
         }],
         ending_associated_comments: NO_COMMENT_REFERENCE,
       },
@@ -823,7 +948,7 @@ mod tests {
     let interface: Toplevel<()> = Toplevel::Interface(InterfaceDeclarationCommon {
       loc: Location::dummy(),
       associated_comments: NO_COMMENT_REFERENCE,
-      private: false,
+      visibility: Visibility::Public,
       name: Id::from(heap.alloc_str_for_test("name")),
       type_parameters: Some(annotation::TypeParameters {
         location: Location::dummy(),
@@ -836,28 +961,32 @@ mod tests {
         associated_comments: NO_COMMENT_REFERENCE,
         nodes: Vec::new(),
       }),
+      derives: None,
       type_definition: (),
       members: InterfaceMembersCommon {
         loc: Location::dummy(),
-        members: vec![ClassMemberDeclaration {
-          loc: Location::dummy(),
-          associated_comments: NO_COMMENT_REFERENCE,
-          is_public: true,
-          is_method: true,
-          name: Id::from(PStr::LOWER_A),
-          type_parameters: Some(annotation::TypeParameters {
-            location: Location::dummy(),
-            start_associated_comments: NO_COMMENT_REFERENCE,
-            ending_associated_comments: NO_COMMENT_REFERENCE,
-            parameters: Vec::new(),
-          }),
-          parameters: FunctionParameters {
-            location: Location::dummy(),
-            start_associated_comments: NO_COMMENT_REFERENCE,
-            ending_associated_comments: NO_COMMENT_REFERENCE,
-            parameters: Arc::new(Vec::new()),
+        members: vec![InterfaceMemberDefinition {
+          decl: ClassMemberDeclaration {
+            loc: Location::dummy(),
+            associated_comments: NO_COMMENT_REFERENCE,
+            visibility: Visibility::Public,
+            is_method: true,
+            name: Id::from(PStr::LOWER_A),
+            type_parameters: Some(annotation::TypeParameters {
+              location: Location::dummy(),
+              start_associated_comments: NO_COMMENT_REFERENCE,
+              ending_associated_comments: NO_COMMENT_REFERENCE,
+              parameters: Vec::new(),
+            }),
+            parameters: FunctionParameters {
+              location: Location::dummy(),
+              start_associated_comments: NO_COMMENT_REFERENCE,
+              ending_associated_comments: NO_COMMENT_REFERENCE,
+              parameters: Arc::new(Vec::new()),
+            },
+            return_type: builder.int_annot(),
           },
-          return_type: builder.int_annot(),
+          default_body: None,
         }],
         ending_associated_comments: NO_COMMENT_REFERENCE,
       },
@@ -1,4 +1,4 @@
-use super::{hir, lir, mir};
+use super::{Float64, hir, lir, mir};
 use dupe::Dupe;
 use enum_as_inner::EnumAsInner;
 use samlang_heap::{Heap, PStr};
@@ -7,6 +7,7 @@ use samlang_heap::{Heap, PStr};
 pub enum Type {
   Int32,
   Int31,
+  Float64,
   Eq,
   Reference(mir::TypeNameId),
 }
@@ -16,6 +17,7 @@ impl Type {
     match self {
       Type::Int32 => collector.push_str("i32"),
       Type::Int31 => collector.push_str("(ref i31)"),
+      Type::Float64 => collector.push_str("f64"),
       Type::Eq => collector.push_str("(ref eq)"),
       Type::Reference(id) => {
         collector.push_str("(ref $");
@@ -25,10 +27,25 @@ impl Type {
     }
   }
 
+  fn pretty_print_null_value(&self, collector: &mut String, heap: &Heap, table: &mir::SymbolTable) {
+    match self {
+      Type::Int32 => collector.push_str("(i32.const 0)"),
+      Type::Int31 => collector.push_str("(ref.null i31)"),
+      Type::Float64 => collector.push_str("(f64.const 0)"),
+      Type::Eq => collector.push_str("(ref.null eq)"),
+      Type::Reference(id) => {
+        collector.push_str("(ref.null $");
+        id.write_encoded(collector, heap, table);
+        collector.push(')');
+      }
+    }
+  }
+
   fn pretty_print_nullable(&self, collector: &mut String, heap: &Heap, table: &mir::SymbolTable) {
     match self {
       Type::Int32 => collector.push_str("i32"),
       Type::Int31 => collector.push_str("(ref null i31)"),
+      Type::Float64 => collector.push_str("f64"),
       Type::Eq => collector.push_str("(ref null eq)"),
       Type::Reference(id) => {
         collector.push_str("(ref null $");
@@ -75,10 +92,12 @@ pub struct TypeDefinition {
 
 pub enum InlineInstruction {
   Const(i32),
+  FloatConst(Float64),
   Drop(Box<InlineInstruction>),
   LocalGet(PStr),
   LocalSet(PStr, Box<InlineInstruction>),
   GlobalGet(PStr),
+  GlobalConstantGet(mir::TypeNameId),
   IsPointer {
     pointer_type: lir::Type,
     value: Box<InlineInstruction>,
@@ -128,6 +147,18 @@ impl InlineInstruction {
         collector.push_str(&i.to_string());
         collector.push(')');
       }
+      Self::FloatConst(f) => {
+        collector.push_str("(f64.const ");
+        let value = f.value();
+        if value.is_nan() {
+          collector.push_str("nan");
+        } else if value.is_infinite() {
+          collector.push_str(if value > 0.0 { "inf" } else { "-inf" });
+        } else {
+          collector.push_str(&format!("{value:?}"));
+        }
+        collector.push(')');
+      }
       Self::Drop(v) => {
         collector.push_str("(drop ");
         v.pretty_print(collector, heap, table);
@@ -152,6 +183,11 @@ impl InlineInstruction {
         collector.push_str(name.as_str(heap));
         collector.push_str("))");
       }
+      Self::GlobalConstantGet(name) => {
+        collector.push_str("(global.get $");
+        name.write_encoded(collector, heap, table);
+        collector.push(')');
+      }
       Self::IsPointer { pointer_type, value } => {
         collector.push_str("(ref.test (ref $");
         pointer_type.pretty_print(collector, heap, table);
@@ -185,24 +221,35 @@ impl InlineInstruction {
           }
         } else {
           let op_s = match op {
-            hir::BinaryOperator::MUL => "mul",
-            hir::BinaryOperator::DIV => "div_s",
-            hir::BinaryOperator::MOD => "rem_s",
-            hir::BinaryOperator::PLUS => "add",
-            hir::BinaryOperator::MINUS => "sub",
-            hir::BinaryOperator::LAND => "and",
-            hir::BinaryOperator::LOR => "or",
-            hir::BinaryOperator::SHL => "shl",
-            hir::BinaryOperator::SHR => "shr_u",
-            hir::BinaryOperator::XOR => "xor",
-            hir::BinaryOperator::LT => "lt_s",
-            hir::BinaryOperator::LE => "le_s",
-            hir::BinaryOperator::GT => "gt_s",
-            hir::BinaryOperator::GE => "ge_s",
-            hir::BinaryOperator::EQ => "eq",
-            hir::BinaryOperator::NE => "ne",
+            hir::BinaryOperator::MUL => "i32.mul",
+            hir::BinaryOperator::DIV => "i32.div_s",
+            hir::BinaryOperator::MOD => "i32.rem_s",
+            hir::BinaryOperator::PLUS => "i32.add",
+            hir::BinaryOperator::MINUS => "i32.sub",
+            hir::BinaryOperator::LAND => "i32.and",
+            hir::BinaryOperator::LOR => "i32.or",
+            hir::BinaryOperator::SHL => "i32.shl",
+            hir::BinaryOperator::SHR => "i32.shr_u",
+            hir::BinaryOperator::ASHR => "i32.shr_s",
+            hir::BinaryOperator::XOR => "i32.xor",
+            hir::BinaryOperator::LT => "i32.lt_s",
+            hir::BinaryOperator::LE => "i32.le_s",
+            hir::BinaryOperator::GT => "i32.gt_s",
+            hir::BinaryOperator::GE => "i32.ge_s",
+            hir::BinaryOperator::EQ => "i32.eq",
+            hir::BinaryOperator::NE => "i32.ne",
+            hir::BinaryOperator::FMUL => "f64.mul",
+            hir::BinaryOperator::FDIV => "f64.div",
+            hir::BinaryOperator::FPLUS => "f64.add",
+            hir::BinaryOperator::FMINUS => "f64.sub",
+            hir::BinaryOperator::FLT => "f64.lt",
+            hir::BinaryOperator::FLE => "f64.le",
+            hir::BinaryOperator::FGT => "f64.gt",
+            hir::BinaryOperator::FGE => "f64.ge",
+            hir::BinaryOperator::FEQ => "f64.eq",
+            hir::BinaryOperator::FNE => "f64.ne",
           };
-          collector.push_str("(i32.");
+          collector.push('(');
           collector.push_str(op_s);
           collector.push(' ');
           v1.pretty_print(collector, heap, table);
@@ -290,6 +337,7 @@ pub enum Instruction {
   Inline(InlineInstruction),
   IfElse { condition: InlineInstruction, s1: Vec<Instruction>, s2: Vec<Instruction> },
   UnconditionalJump(LabelId),
+  Return(InlineInstruction),
   Loop { continue_label: LabelId, exit_label: LabelId, instructions: Vec<Instruction> },
 }
 
@@ -337,6 +385,12 @@ impl Instruction {
         collector.push_str(&label.0.to_string());
         collector.push_str(")\n");
       }
+      Self::Return(i) => {
+        Self::append_spaces(collector, level);
+        collector.push_str("(return ");
+        i.pretty_print(collector, heap, table);
+        collector.push_str(")\n");
+      }
       Self::Loop { continue_label, exit_label, instructions } => {
         Self::append_spaces(collector, level);
         collector.push_str("(loop $l");
@@ -407,12 +461,20 @@ impl GlobalData {
   }
 }
 
+/// A module-level constant, stored in a global named after the constant and set by calling
+/// `initializer` in `__$init_globals`.
+pub struct GlobalConstant {
+  pub initializer: mir::FunctionName,
+  pub type_: Type,
+}
+
 pub struct Module {
   pub symbol_table: mir::SymbolTable,
   pub function_type_mapping: Vec<(mir::TypeNameId, FunctionType)>,
   pub type_definitions: Vec<TypeDefinition>,
   pub global_variables: Vec<GlobalData>,
   pub gc_string_globals: Vec<GlobalGcString>,
+  pub global_constants: Vec<GlobalConstant>,
   pub exported_functions: Vec<mir::FunctionName>,
   pub functions: Vec<Function>,
 }
@@ -424,6 +486,7 @@ impl Module {
     collector.push_str("(type $_Str (array (mut i8)))\n");
     collector.push_str("(type $_VecData (array (mut (ref null eq))))\n");
     collector.push_str("(type $_Vec (struct (field (mut (ref $_VecData))) (field (mut i32))))\n");
+    collector.push_str("(type $_Float (struct (field f64)))\n");
     for (type_name, fun_t) in &self.function_type_mapping {
       collector.push_str("(type $");
       type_name.write_encoded(&mut collector, heap, &self.symbol_table);
@@ -469,6 +532,15 @@ impl Module {
       collector.push_str(gc_string.name.as_str(heap));
       collector.push_str(" (mut (ref null $_Str)) (ref.null $_Str))\n");
     }
+    for GlobalConstant { initializer, type_ } in &self.global_constants {
+      collector.push_str("(global $");
+      initializer.type_name.write_encoded(&mut collector, heap, &self.symbol_table);
+      collector.push_str(" (mut ");
+      type_.pretty_print_nullable(&mut collector, heap, &self.symbol_table);
+      collector.push_str(") ");
+      type_.pretty_print_null_value(&mut collector, heap, &self.symbol_table);
+      collector.push_str(")\n");
+    }
     collector.push_str("(table $0 ");
     collector.push_str(&self.functions.len().to_string());
     collector.push_str(" funcref)\n(elem $0 (i32.const 0)");
@@ -511,8 +583,9 @@ impl Module {
       }
       collector.push_str(")\n");
     }
-    // Add init function and start section if there are GC string globals
-    if !self.gc_string_globals.is_empty() {
+    // Add init function and start section if there are GC string globals or constants.
+    // Strings are set first, since constant initializers might read them.
+    if !self.gc_string_globals.is_empty() || !self.global_constants.is_empty() {
       collector.push_str("(func $__$init_globals\n");
       for gc_string in &self.gc_string_globals {
         collector.push_str("  (global.set $");
@@ -525,6 +598,13 @@ impl Module {
         collector.push_str(&gc_string.length.to_string());
         collector.push_str(")))\n");
       }
+      for GlobalConstant { initializer, type_: _ } in &self.global_constants {
+        collector.push_str("  (global.set $");
+        initializer.type_name.write_encoded(&mut collector, heap, &self.symbol_table);
+        collector.push_str(" (call $");
+        initializer.write_encoded(&mut collector, heap, &self.symbol_table);
+        collector.push_str("))\n");
+      }
       collector.push_str(")\n");
       collector.push_str("(start $__$init_globals)\n");
     }
@@ -629,6 +709,7 @@ mod tests {
         offset: 0,
         length: 5,
       }],
+      global_constants: Vec::new(),
       exported_functions: vec![mir::FunctionName::new_for_test(PStr::MAIN_FN)],
       functions: vec![
         Function {
@@ -731,6 +812,12 @@ mod tests {
                   v2: Box::new(InlineInstruction::Const(0)),
                   is_ref_comparison: false,
                 }),
+                Instruction::Inline(InlineInstruction::Binary {
+                  v1: Box::new(InlineInstruction::Const(0)),
+                  op: hir::BinaryOperator::ASHR,
+                  v2: Box::new(InlineInstruction::Const(0)),
+                  is_ref_comparison: false,
+                }),
                 Instruction::Inline(InlineInstruction::Binary {
                   v1: Box::new(InlineInstruction::Const(0)),
                   op: hir::BinaryOperator::XOR,
@@ -776,6 +863,7 @@ mod tests {
               ],
             },
             Instruction::UnconditionalJump(LabelId(0)),
+            Instruction::Return(InlineInstruction::Const(0)),
             Instruction::Loop {
               continue_label: LabelId(1),
               exit_label: LabelId(2),
@@ -842,6 +930,7 @@ mod tests {
 (type $_Str (array (mut i8)))
 (type $_VecData (array (mut (ref null eq))))
 (type $_Vec (struct (field (mut (ref $_VecData))) (field (mut i32))))
+(type $_Float (struct (field f64)))
 (type $_FnType (func (param i32) (result i32)))
 (type $_F (struct (field i32) (field (ref $_F))))
 (type $_Parent (sub (struct (field i32))))
@@ -876,6 +965,7 @@ mod tests {
     (i32.or (i32.const 0) (i32.const 0))
     (i32.shl (i32.const 0) (i32.const 0))
     (i32.shr_u (i32.const 0) (i32.const 0))
+    (i32.shr_s (i32.const 0) (i32.const 0))
     (i32.xor (i32.const 0) (i32.const 0))
     (i32.lt_s (i32.const 0) (i32.const 0))
     (i32.le_s (i32.const 0) (i32.const 0))
@@ -885,6 +975,7 @@ mod tests {
     (i32.ne (i32.const 0) (i32.const 0))
   ))
   (br $l0)
+  (return (i32.const 0))
   (loop $l1
     (block $l2
       (call $__$main (i32.const 0))
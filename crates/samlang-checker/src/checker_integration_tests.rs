@@ -13,7 +13,8 @@ mod tests {
     let mut string_sources = HashMap::new();
     let mut parsed_sources = HashMap::new();
     for t in SOURCES.iter() {
-      let mod_ref = heap.alloc_module_reference_from_string_vec(vec![t.test_name.to_string()]);
+      let mod_ref = heap
+        .alloc_module_reference_from_string_vec(t.test_name.split('.').map(String::from).collect());
       string_sources.insert(mod_ref, t.source_code.to_string());
       parsed_sources.insert(
         mod_ref,
@@ -23,7 +24,7 @@ mod tests {
     for (mod_ref, parsed) in samlang_parser::builtin_parsed_std_sources_for_tests(heap) {
       parsed_sources.insert(mod_ref, parsed);
     }
-    super::super::type_check_sources(&parsed_sources, &mut error_set);
+    super::super::type_check_sources(&parsed_sources, heap, &mut error_set);
     assert_eq!(
       EXPECTED_ERRORS.trim(),
       error_set.pretty_print_error_messages(heap, &string_sources).trim()
@@ -35,7 +36,7 @@ mod tests {
     source_code: &'a str,
   }
 
-  static SOURCES: [CheckerTestSource; 72] = [
+  static SOURCES: [CheckerTestSource; 95] = [
     CheckerTestSource {
       test_name: "access-builtin",
       source_code: r#"
@@ -47,6 +48,21 @@ class Main {
     let d: Main = Process.panic("3");
   }
 }
+"#,
+    },
+    CheckerTestSource {
+      test_name: "access-builtin-str-methods",
+      source_code: r#"
+class Main {
+  function main(): unit = {
+    let s = " Hello ";
+    let a: int = s.length() + s.byteLength() + s.codePointAt(0) + s.indexOf("l");
+    let b: Str = s.charAt(1) :: s.substring(1, 3) :: s.trim().toUpper().toLower();
+    let c: Vec<Str> = s.split(" ");
+    let d: bool = s.startsWith(" ");
+    let e: int = s.compare("Hello");
+  }
+}
 "#,
     },
     CheckerTestSource {
@@ -129,8 +145,8 @@ class Pair<T: Comparable<T>>(val v1: T, val v2: T) {
   method relation3(): int = TwoItemCompare.compare<int>(this.v1, this.v2) // error typearg
 }
 class TestLimitedSubtyping {
-  function test(v: Comparable<BoxedInt>): unit = {} // error signature validation
-  function main(): unit = TestLimitedSubtyping.test(BoxedInt.init(1)) // error subtyping
+  function test(v: Comparable<BoxedInt>): unit = {} // ok: interface value type
+  function main(): unit = TestLimitedSubtyping.test(BoxedInt.init(1)) // ok: implicit upcast
 }
 interface Conflicting1 {
   method foo(): int
@@ -958,6 +974,395 @@ class ABC(A, B, C) {
       (A | B, A | B | C) -> true,
     }
 }
+"#,
+    },
+    CheckerTestSource {
+      test_name: "early-return-ok",
+      source_code: r#"
+class Option<T>(Some(T), None) {
+  function <T> firstSome(a: Option<T>, b: Option<T>): Option<T> = {
+    let _ = match a { Some(_) -> return a, None -> 0 };
+    let v = match b { Some(v) -> v, None -> return Option.None() };
+    Option.Some(v)
+  }
+}
+
+class Main {
+  function abs(n: int): int = {
+    if n >= 0 { return n } else {};
+    -n
+  }
+
+  function sign(n: int): int = if n > 0 { return 1 } else if n < 0 { -1 } else { 0 }
+
+  function <T> same(a: T, b: T): T = a
+
+  function negative(n: int): int = {
+    let v = Main.same(return n, -n);
+    v
+  }
+}
+"#,
+    },
+    CheckerTestSource {
+      test_name: "early-return-bad",
+      source_code: r#"
+class Main {
+  function wrongType(): int = {
+    if true { return "str" } else {};
+    1
+  }
+
+  function inLambda(): unit = {
+    let _ = (x: int) -> return x;
+  }
+}
+
+const C: int = return 1;
+"#,
+    },
+    CheckerTestSource {
+      test_name: "mutable-var-ok",
+      source_code: r#"
+class Main {
+  function sum(n: int): int = {
+    var acc = 0;
+    acc = acc + n;
+    if n > 1 { acc = acc + Main.sum(n - 1); } else {};
+    acc
+  }
+
+  function shadow(): Str = {
+    var s: Str = "a";
+    let f = (t: Str) -> t;
+    s = f("b");
+    s
+  }
+}
+"#,
+    },
+    CheckerTestSource {
+      test_name: "mutable-var-bad",
+      source_code: r#"
+class Main {
+  function assignImmutable(n: int): int = {
+    let a = 1;
+    a = 2;
+    n = 3;
+    a
+  }
+
+  function wrongType(): int = {
+    var a = 1;
+    a = "str";
+    a
+  }
+
+  function captured(): int = {
+    var a = 1;
+    let f = () -> a + 1;
+    let g = () -> { a = 2; };
+    a
+  }
+}
+"#,
+    },
+    CheckerTestSource {
+      test_name: "loops-ok",
+      source_code: r#"
+class Main {
+  function sumTo(n: int): int = {
+    var sum = 0;
+    for (i in 0..n + 1) { sum = sum + i; }
+    sum
+  }
+
+  function firstMultiple(k: int): int = {
+    var i = 1;
+    while i % k != 0 {
+      let next: int = if i > 100 { break } else { i + 1 };
+      i = next;
+    }
+    i
+  }
+}
+"#,
+    },
+    CheckerTestSource {
+      test_name: "loops-bad",
+      source_code: r#"
+class Main {
+  function badBounds(): unit = {
+    for (i in true.."a") {}
+    while 1 {}
+  }
+
+  function badBody(): unit = {
+    for (i in 0..1) { i }
+    while true { let f = () -> { break }; }
+  }
+
+  function outside(): int = {
+    i;
+    break
+  }
+}
+"#,
+    },
+    CheckerTestSource {
+      test_name: "assert-ok",
+      source_code: r#"
+class Main {
+  function check(n: int, message: Str): unit = {
+    assert(n > 0);
+    assert(n < 10, message);
+    assert(n != 5, "n is " :: Str.fromInt(n))
+  }
+
+  function loop(): unit = {
+    let _: unit = assert(true);
+    while true { assert(false); break; }
+  }
+}
+"#,
+    },
+    CheckerTestSource {
+      test_name: "assert-bad",
+      source_code: r#"
+class Main {
+  function bad(): unit = {
+    assert(1);
+    assert(true, 2);
+    let _: int = assert(true);
+  }
+}
+"#,
+    },
+    CheckerTestSource {
+      test_name: "type-alias-ok",
+      source_code: r#"
+type Pair2<T> = Pair<T, T>;
+type IntPair = Pair2<int>;
+type Mapper<A, B> = (A) -> B;
+
+class Pair<A, B>(val a: A, val b: B) {
+  function swap(p: IntPair): IntPair = Pair.init(p.b, p.a)
+
+  function <T> map(p: Pair2<T>, f: Mapper<T, int>): IntPair = Pair.init(f(p.a), f(p.b))
+
+  function sum(): int = {
+    let p: IntPair = Pair.swap(Pair.init(1, 2));
+    let { a, b } = p;
+    let f: Mapper<IntPair, int> = (q) -> q.a + q.b;
+    a + b + f(Pair.map(p, (x) -> x * 2))
+  }
+}
+"#,
+    },
+    CheckerTestSource {
+      test_name: "type-alias-bad",
+      source_code: r#"
+type Loop = Loop;
+type A1 = (A2) -> int;
+type A2 = (A1) -> int;
+type Box<T> = Holder<T>;
+type Missing = NotExist;
+type Abstract = I;
+
+interface I {}
+
+class Holder<T>(val v: T) {
+  function f1(b: Box<int, int>): int = 1
+  function f2(b: Box): int = 1
+  function f3(b: Box<int>): bool = b.v
+  function f4(a: Abstract): unit = {}
+}
+"#,
+    },
+    CheckerTestSource {
+      test_name: "const-ok",
+      source_code: r#"
+const Base: int = 40;
+const Answer: int = Base + 2 * 1 - -1;
+const Flag: bool = !(Answer > 10) || Answer == 42;
+const Greeting: Str = "Hello" :: " " :: "World";
+const Origin: Point = Point.init(0, Answer);
+private const Nothing: Opt<int> = Opt.None();
+const Something: Opt<Point> = Opt.Some(Origin);
+const Empty: Box = Box.init({});
+
+class Box(val v: unit) {}
+
+class Point(val x: int, val y: int) {
+  function main(): int = Origin.x + Origin.y + Answer
+}
+
+class Opt<T>(None, Some(T)) {
+  function value(): Str = if Flag { Greeting } else { "no" }
+}
+"#,
+    },
+    CheckerTestSource {
+      test_name: "const-bad",
+      source_code: r#"
+const Loop1: int = Loop2;
+const Loop2: int = Loop1;
+const Recursive: int = Recursive + 1;
+const WrongType: bool = 1;
+const Computed: int = Util.compute();
+const Lambda: (int) -> int = (x) -> x;
+const Cond: int = if true { 1 } else { 2 };
+const ClassRef: Util = Util;
+const Missing: NotExist = 1;
+
+class Util {
+  function compute(): int = 1
+}
+"#,
+    },
+    CheckerTestSource {
+      test_name: "literal-pattern-ok",
+      source_code: r#"
+class Opt(None, Some(int)) {}
+class Main {
+  function op(name: Str, a: int, b: int): int =
+    match name { "add" -> a + b, "sub" -> a - b, _ -> 0 }
+  function sign(i: int): int = match i { 0 -> 0, -1 -> -1, _ -> 1 }
+  function toInt(b: bool): int = match b { true -> 1, false -> 0 }
+  function nested(b: bool, o: Opt): int =
+    match (b, o) { (true, Some(0)) -> 0, (true, _) -> 1, (false, None) -> 2, (false, Some(i)) -> i }
+}
+"#,
+    },
+    CheckerTestSource {
+      test_name: "literal-pattern-bad",
+      source_code: r#"
+class Opt(None, Some(int)) {}
+class Main {
+  function missingWildcard(i: int): int = match i { 0 -> 0, 1 -> 1 }
+  function missingBool(b: bool): int = match b { true -> 1 }
+  function nestedMissing(o: Opt): int = match o { None -> 0, Some(1) -> 1 }
+  function wrongType(i: int): int = match i { "a" -> 0, true -> 1, _ -> 2 }
+  function uselessGuard(b: bool): int = if let _ = b { 1 } else { 0 }
+  function usefulGuard(i: int): int = if let 0 = i { 1 } else { 0 }
+}
+"#,
+    },
+    CheckerTestSource {
+      test_name: "match-guard-ok",
+      source_code: r#"
+class Opt(None, Some(int)) {}
+class Main {
+  function sign(o: Opt): int =
+    match o { Some(x) if x > 0 -> 1, Some(x) if x < 0 -> -1, Some(_) -> 0, None -> 0 }
+  function literal(i: int): int = match i { 0 if true -> 1, 0 -> 2, _ -> 3 }
+}
+"#,
+    },
+    CheckerTestSource {
+      test_name: "match-guard-bad",
+      source_code: r#"
+class Opt(None, Some(int)) {}
+class Main {
+  function notExhaustive(o: Opt): int = match o { Some(x) if x > 0 -> 1, None -> 0 }
+  function allGuarded(b: bool): int = match b { _ if b -> 1 }
+  function notBool(o: Opt): int = match o { Some(x) if x -> 1, _ -> 0 }
+  function covered(o: Opt): int = match o { Some(_) -> 1, Some(x) if x > 0 -> 2, None -> 0 }
+  function coveredAfterWildcard(o: Opt): int = match o { _ -> 1, None -> 0 }
+  function unboundInGuard(o: Opt): int = match o { Some(_) if y -> 1, _ -> 0 }
+}
+"#,
+    },
+    CheckerTestSource {
+      test_name: "visibility.core.lib",
+      source_code: r#"
+internal class Helper { internal function help(): int = 1 }
+private class Secret { function get(): int = 2 }
+interface Shape { protected method area(): int  method describe(): int }
+class Square(val size: int) : Shape {
+  protected method area(): int = this.size * this.size
+  method describe(): int = Secret.get() + Helper.help()
+  function <T: Shape> measure(shape: T): int = shape.area()
+}
+class Circle(val radius: int) : Shape {
+  method area(): int = this.radius
+  internal method describe(): int = 1
+}
+internal const LIMIT: int = 3;
+"#,
+    },
+    CheckerTestSource {
+      test_name: "visibility.core.user",
+      source_code: r#"
+import { Helper, LIMIT, Square } from visibility.core.lib
+class Main {
+  function ok(): int = Helper.help() + LIMIT
+  function bad(): int = Square.init(1).area()
+}
+"#,
+    },
+    CheckerTestSource {
+      test_name: "visibility.core.nested.user",
+      source_code: r#"
+import { Helper } from visibility.core.lib
+class Main { function ok(): int = Helper.help() }
+"#,
+    },
+    CheckerTestSource {
+      test_name: "visibility.outside",
+      source_code: r#"
+import { Helper, LIMIT, Secret, Square } from visibility.core.lib
+class Main {
+  function bad(): int = Helper.help() + LIMIT + Secret.get()
+  function ok(): int = Square.init(1).describe()
+}
+"#,
+    },
+    CheckerTestSource {
+      test_name: "interface-default-ok",
+      source_code: r#"
+interface Shape {
+  method area(): int
+  method describe(): Str = "area: " :: Str.fromInt(this.area())
+  method <T> tag(t: T): T = t
+}
+interface Ordered<T> : Shape {
+  method compare(other: T): int
+  method lessThan(other: T): bool = this.compare(other) < 0
+}
+class Square(val size: int) : Ordered<Square> {
+  method area(): int = this.size * this.size
+  method compare(other: Square): int = this.size - other.size
+}
+class Circle(val radius: int) : Shape {
+  method area(): int = this.radius
+  method describe(): Str = "circle"
+}
+class Main {
+  function <S: Shape> show(s: S): Str = s.describe()
+  function main(): Str = {
+    let s = Square.init(2);
+    let _: bool = s.lessThan(Square.init(3));
+    let _: int = s.tag(1);
+    Main.show(s) :: Circle.init(1).describe()
+  }
+}
+"#,
+    },
+    CheckerTestSource {
+      test_name: "interface-default-bad",
+      source_code: r#"
+interface Shape {
+  method area(): int
+  method describe(): Str = this.area()
+  method me(): Shape = this
+  method perimeter(): int = this.missing()
+}
+class NoArea : Shape {}
+class BadOverride(val i: int) : Shape {
+  method area(): int = this.i
+  method describe(): int = 1
+}
 "#,
     },
   ];
@@ -965,7 +1370,7 @@ class ABC(A, B, C) {
   const EXPECTED_ERRORS: &str = r#"
 Error ---------------- access-private-member.sam:12:15-12:16
 
-Cannot resolve member `b` on `A`.
+`b` is private and not visible here.
 
   12|     let _ = A.b();
                     ^
@@ -1069,32 +1474,6 @@ Error --------------------- bounded-generics.sam:15:66-15:73
                                                          ^^^
 
 
-Error --------------------- bounded-generics.sam:18:20-18:40
-
-`Comparable<BoxedInt>` is incompatible with `non-abstract type`.
-
-  18|   function test(v: Comparable<BoxedInt>): unit = {} // error signature validation
-                         ^^^^^^^^^^^^^^^^^^^^
-
-
-Error --------------------- bounded-generics.sam:19:53-19:69
-
-`BoxedInt` [1] is incompatible with `Comparable<BoxedInt>` [2].
-
-  19|   function main(): unit = TestLimitedSubtyping.test(BoxedInt.init(1)) // error subtyping
-                                                          ^^^^^^^^^^^^^^^^
-
-  [1] bounded-generics.sam:19:53-19:69
-  ------------------------------------
-  19|   function main(): unit = TestLimitedSubtyping.test(BoxedInt.init(1)) // error subtyping
-                                                          ^^^^^^^^^^^^^^^^
-
-  [2] bounded-generics.sam:18:20-18:40
-  ------------------------------------
-  18|   function test(v: Comparable<BoxedInt>): unit = {} // error signature validation
-                         ^^^^^^^^^^^^^^^^^^^^
-
-
 Error ---------------------- bounded-generics.sam:28:7-28:17
 
 Type `ImplItself` has a cyclic definition.
@@ -1127,14 +1506,6 @@ Function declarations are not allowed in interfaces.
                      ^^^^^^^^^^^^^^^^^^^
 
 
-Error ---------------- call-interface-function.sam:3:33-3:36
-
-`Foo` is incompatible with `non-abstract type`.
-
-  3| class Ouch { function call(foo: Foo): int = Foo.bar() }
-                                     ^^^
-
-
 Error ---------------- call-interface-function.sam:3:45-3:48
 
 Cannot resolve class `Foo`.
@@ -1992,6 +2363,672 @@ Here is an example of a non-matching value: `(C, _)`.
      ^^^^^
 
 
-Found 84 errors.
+Error ----------------------- early-return-bad.sam:4:22-4:27
+
+`Str` [1] is incompatible with `int` [2].
+
+  4|     if true { return "str" } else {};
+                          ^^^^^
+
+  [1] early-return-bad.sam:4:22-4:27
+  ----------------------------------
+  4|     if true { return "str" } else {};
+                          ^^^^^
+
+  [2] early-return-bad.sam:3:25-3:28
+  ----------------------------------
+  3|   function wrongType(): int = {
+                             ^^^
+
+
+Error ----------------------- early-return-bad.sam:9:25-9:33
+
+`return` is not allowed inside lambdas.
+
+  9|     let _ = (x: int) -> return x;
+                             ^^^^^^^^
+
+
+Error --------------------- early-return-bad.sam:13:16-13:24
+
+`return` is only allowed inside function bodies.
+
+  13| const C: int = return 1;
+                     ^^^^^^^^
+
+
+Error --------------------- early-return-bad.sam:13:16-13:24
+
+This expression cannot be evaluated at compile time.
+
+  13| const C: int = return 1;
+                     ^^^^^^^^
+
+
+Error -------------------------- mutable-var-bad.sam:5:5-5:6
+
+Cannot assign to `a`, since it is not declared with `var`.
+
+  5|     a = 2;
+         ^
+
+
+Error -------------------------- mutable-var-bad.sam:6:5-6:6
+
+Cannot assign to `n`, since it is not declared with `var`.
+
+  6|     n = 3;
+         ^
+
+
+Error ----------------------- mutable-var-bad.sam:12:5-12:15
+
+`Str` [1] is incompatible with `int` [2].
+
+  12|     a = "str";
+          ^^^^^^^^^^
+
+  [1] mutable-var-bad.sam:12:9-12:14
+  ----------------------------------
+  12|     a = "str";
+              ^^^^^
+
+  [2] mutable-var-bad.sam:12:5-12:6
+  ---------------------------------
+  12|     a = "str";
+          ^
+
+
+Error ---------------------- mutable-var-bad.sam:18:19-18:20
+
+Mutable variable `a` cannot be captured by a lambda.
+
+  18|     let f = () -> a + 1;
+                        ^
+
+
+Error ---------------------- mutable-var-bad.sam:19:21-19:22
+
+Mutable variable `a` cannot be captured by a lambda.
+
+  19|     let g = () -> { a = 2; };
+                          ^
+
+
+Error ------------------------------ loops-bad.sam:4:15-4:19
+
+`bool` [1] is incompatible with `int` [2].
+
+  4|     for (i in true.."a") {}
+                   ^^^^
+
+  [1] loops-bad.sam:4:15-4:19
+  ---------------------------
+  4|     for (i in true.."a") {}
+                   ^^^^
+
+  [2] loops-bad.sam:4:15-4:19
+  ---------------------------
+  4|     for (i in true.."a") {}
+                   ^^^^
+
+
+Error ------------------------------ loops-bad.sam:4:21-4:24
+
+`Str` [1] is incompatible with `int` [2].
+
+  4|     for (i in true.."a") {}
+                         ^^^
+
+  [1] loops-bad.sam:4:21-4:24
+  ---------------------------
+  4|     for (i in true.."a") {}
+                         ^^^
+
+  [2] loops-bad.sam:4:21-4:24
+  ---------------------------
+  4|     for (i in true.."a") {}
+                         ^^^
+
+
+Error ------------------------------ loops-bad.sam:5:11-5:12
+
+`int` [1] is incompatible with `bool` [2].
+
+  5|     while 1 {}
+               ^
+
+  [1] loops-bad.sam:5:11-5:12
+  ---------------------------
+  5|     while 1 {}
+               ^
+
+  [2] loops-bad.sam:5:11-5:12
+  ---------------------------
+  5|     while 1 {}
+               ^
+
+
+Error ------------------------------ loops-bad.sam:9:21-9:26
+
+`int` [1] is incompatible with `unit` [2].
+
+  9|     for (i in 0..1) { i }
+                         ^^^^^
+
+  [1] loops-bad.sam:9:21-9:26
+  ---------------------------
+  9|     for (i in 0..1) { i }
+                         ^^^^^
+
+  [2] loops-bad.sam:9:21-9:26
+  ---------------------------
+  9|     for (i in 0..1) { i }
+                         ^^^^^
+
+
+Error ---------------------------- loops-bad.sam:10:34-10:39
+
+`break` is only allowed inside loops.
+
+  10|     while true { let f = () -> { break }; }
+                                       ^^^^^
+
+
+Error ------------------------------ loops-bad.sam:14:5-14:6
+
+Cannot resolve name `i`.
+
+  14|     i;
+          ^
+
+
+Error ----------------------------- loops-bad.sam:15:5-15:10
+
+`break` is only allowed inside loops.
+
+  15|     break
+          ^^^^^
+
+
+Error ----------------------------- assert-bad.sam:4:12-4:13
+
+`int` [1] is incompatible with `bool` [2].
+
+  4|     assert(1);
+                ^
+
+  [1] assert-bad.sam:4:12-4:13
+  ----------------------------
+  4|     assert(1);
+                ^
+
+  [2] assert-bad.sam:4:12-4:13
+  ----------------------------
+  4|     assert(1);
+                ^
+
+
+Error ----------------------------- assert-bad.sam:5:18-5:19
+
+`int` [1] is incompatible with `Str` [2].
+
+  5|     assert(true, 2);
+                      ^
+
+  [1] assert-bad.sam:5:18-5:19
+  ----------------------------
+  5|     assert(true, 2);
+                      ^
+
+  [2] assert-bad.sam:5:18-5:19
+  ----------------------------
+  5|     assert(true, 2);
+                      ^
+
+
+Error ------------------------------ assert-bad.sam:6:5-6:31
+
+`unit` [1] is incompatible with `int` [2].
+
+  6|     let _: int = assert(true);
+         ^^^^^^^^^^^^^^^^^^^^^^^^^^
+
+  [1] assert-bad.sam:6:18-6:30
+  ----------------------------
+  6|     let _: int = assert(true);
+                      ^^^^^^^^^^^^
+
+  [2] assert-bad.sam:6:12-6:15
+  ----------------------------
+  6|     let _: int = assert(true);
+                ^^^
+
+
+Error -------------------------- type-alias-bad.sam:2:6-2:10
+
+Type `Loop` has a cyclic definition.
+
+  2| type Loop = Loop;
+          ^^^^
+
+
+Error --------------------------- type-alias-bad.sam:3:6-3:8
+
+Type `A1` has a cyclic definition.
+
+  3| type A1 = (A2) -> int;
+          ^^
+
+
+Error --------------------------- type-alias-bad.sam:4:6-4:8
+
+Type `A2` has a cyclic definition.
+
+  4| type A2 = (A1) -> int;
+          ^^
+
+
+Error ------------------------- type-alias-bad.sam:6:16-6:24
+
+Cannot resolve name `NotExist`.
+
+  6| type Missing = NotExist;
+                    ^^^^^^^^
+
+
+Error ----------------------- type-alias-bad.sam:12:18-12:31
+
+Type argument arity of 2 is incompatible with type argument arity of 1.
+
+  12|   function f1(b: Box<int, int>): int = 1
+                       ^^^^^^^^^^^^^
+
+
+Error ----------------------- type-alias-bad.sam:13:18-13:21
+
+Type argument arity of 0 is incompatible with type argument arity of 1.
+
+  13|   function f2(b: Box): int = 1
+                       ^^^
+
+
+Error ----------------------- type-alias-bad.sam:14:36-14:39
+
+`int` [1] is incompatible with `bool` [2].
+
+  14|   function f3(b: Box<int>): bool = b.v
+                                         ^^^
+
+  [1] type-alias-bad.sam:14:36-14:39
+  ----------------------------------
+  14|   function f3(b: Box<int>): bool = b.v
+                                         ^^^
+
+  [2] type-alias-bad.sam:14:29-14:33
+  ----------------------------------
+  14|   function f3(b: Box<int>): bool = b.v
+                                  ^^^^
+
+
+Error ------------------------------- const-bad.sam:2:7-2:12
+
+Constant `Loop1` has a cyclic definition.
+
+  2| const Loop1: int = Loop2;
+           ^^^^^
+
+
+Error ------------------------------- const-bad.sam:3:7-3:12
+
+Constant `Loop2` has a cyclic definition.
+
+  3| const Loop2: int = Loop1;
+           ^^^^^
+
+
+Error ------------------------------- const-bad.sam:4:7-4:16
+
+Constant `Recursive` has a cyclic definition.
+
+  4| const Recursive: int = Recursive + 1;
+           ^^^^^^^^^
+
+
+Error ------------------------------ const-bad.sam:5:25-5:26
+
+`int` [1] is incompatible with `bool` [2].
+
+  5| const WrongType: bool = 1;
+                             ^
+
+  [1] const-bad.sam:5:25-5:26
+  ---------------------------
+  5| const WrongType: bool = 1;
+                             ^
+
+  [2] const-bad.sam:5:18-5:22
+  ---------------------------
+  5| const WrongType: bool = 1;
+                      ^^^^
+
+
+Error ------------------------------ const-bad.sam:6:23-6:37
+
+This expression cannot be evaluated at compile time.
+
+  6| const Computed: int = Util.compute();
+                           ^^^^^^^^^^^^^^
+
+
+Error ------------------------------ const-bad.sam:7:30-7:38
+
+This expression cannot be evaluated at compile time.
+
+  7| const Lambda: (int) -> int = (x) -> x;
+                                  ^^^^^^^^
+
+
+Error ------------------------------ const-bad.sam:8:19-8:43
+
+This expression cannot be evaluated at compile time.
+
+  8| const Cond: int = if true { 1 } else { 2 };
+                       ^^^^^^^^^^^^^^^^^^^^^^^^
+
+
+Error ------------------------------ const-bad.sam:9:24-9:28
+
+This expression cannot be evaluated at compile time.
+
+  9| const ClassRef: Util = Util;
+                            ^^^^
+
+
+Error ------------------------------ const-bad.sam:9:24-9:28
+
+`class Util` [1] is incompatible with `Util` [2].
+
+  9| const ClassRef: Util = Util;
+                            ^^^^
+
+  [1] const-bad.sam:9:24-9:28
+  ---------------------------
+  9| const ClassRef: Util = Util;
+                            ^^^^
+
+  [2] const-bad.sam:9:17-9:21
+  ---------------------------
+  9| const ClassRef: Util = Util;
+                     ^^^^
+
+
+Error ---------------------------- const-bad.sam:10:16-10:24
+
+Cannot resolve name `NotExist`.
+
+  10| const Missing: NotExist = 1;
+                     ^^^^^^^^
+
+
+Error ---------------------------- const-bad.sam:10:27-10:28
+
+`int` [1] is incompatible with `NotExist` [2].
+
+  10| const Missing: NotExist = 1;
+                                ^
+
+  [1] const-bad.sam:10:27-10:28
+  -----------------------------
+  10| const Missing: NotExist = 1;
+                                ^
+
+  [2] const-bad.sam:10:16-10:24
+  -----------------------------
+  10| const Missing: NotExist = 1;
+                     ^^^^^^^^
+
+
+Error -------------------- literal-pattern-bad.sam:4:43-4:69
+
+This pattern-matching is not exhaustive.
+Here is an example of a non-matching value: `_`.
+
+  4|   function missingWildcard(i: int): int = match i { 0 -> 0, 1 -> 1 }
+                                               ^^^^^^^^^^^^^^^^^^^^^^^^^^
+
+
+Error -------------------- literal-pattern-bad.sam:5:40-5:61
+
+This pattern-matching is not exhaustive.
+Here is an example of a non-matching value: `false`.
+
+  5|   function missingBool(b: bool): int = match b { true -> 1 }
+                                            ^^^^^^^^^^^^^^^^^^^^^
+
+
+Error -------------------- literal-pattern-bad.sam:6:41-6:76
+
+This pattern-matching is not exhaustive.
+Here is an example of a non-matching value: `Some(_)`.
+
+  6|   function nestedMissing(o: Opt): int = match o { None -> 0, Some(1) -> 1 }
+                                             ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+
+
+Error -------------------- literal-pattern-bad.sam:7:47-7:50
+
+`Str` [1] is incompatible with `int` [2].
+
+  7|   function wrongType(i: int): int = match i { "a" -> 0, true -> 1, _ -> 2 }
+                                                   ^^^
+
+  [1] literal-pattern-bad.sam:7:47-7:50
+  -------------------------------------
+  7|   function wrongType(i: int): int = match i { "a" -> 0, true -> 1, _ -> 2 }
+                                                   ^^^
+
+  [2] literal-pattern-bad.sam:7:43-7:44
+  -------------------------------------
+  7|   function wrongType(i: int): int = match i { "a" -> 0, true -> 1, _ -> 2 }
+                                               ^
+
+
+Error -------------------- literal-pattern-bad.sam:7:57-7:61
+
+`bool` [1] is incompatible with `int` [2].
+
+  7|   function wrongType(i: int): int = match i { "a" -> 0, true -> 1, _ -> 2 }
+                                                             ^^^^
+
+  [1] literal-pattern-bad.sam:7:57-7:61
+  -------------------------------------
+  7|   function wrongType(i: int): int = match i { "a" -> 0, true -> 1, _ -> 2 }
+                                                             ^^^^
+
+  [2] literal-pattern-bad.sam:7:43-7:44
+  -------------------------------------
+  7|   function wrongType(i: int): int = match i { "a" -> 0, true -> 1, _ -> 2 }
+                                               ^
+
+
+Error -------------------- literal-pattern-bad.sam:8:48-8:49
+
+The pattern is irrefutable.
+
+  8|   function uselessGuard(b: bool): int = if let _ = b { 1 } else { 0 }
+                                                    ^
+
+
+Error ------------------------ match-guard-bad.sam:4:41-4:85
+
+This pattern-matching is not exhaustive.
+Here is an example of a non-matching value: `Some(_)`.
+
+  4|   function notExhaustive(o: Opt): int = match o { Some(x) if x > 0 -> 1, None -> 0 }
+                                             ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+
+
+Error ------------------------ match-guard-bad.sam:5:39-5:62
+
+This pattern-matching is not exhaustive.
+Here is an example of a non-matching value: `_`.
+
+  5|   function allGuarded(b: bool): int = match b { _ if b -> 1 }
+                                           ^^^^^^^^^^^^^^^^^^^^^^^
+
+
+Error ------------------------ match-guard-bad.sam:6:56-6:57
+
+`int` [1] is incompatible with `bool` [2].
+
+  6|   function notBool(o: Opt): int = match o { Some(x) if x -> 1, _ -> 0 }
+                                                            ^
+
+  [1] match-guard-bad.sam:6:56-6:57
+  ---------------------------------
+  6|   function notBool(o: Opt): int = match o { Some(x) if x -> 1, _ -> 0 }
+                                                            ^
+
+  [2] match-guard-bad.sam:6:56-6:57
+  ---------------------------------
+  6|   function notBool(o: Opt): int = match o { Some(x) if x -> 1, _ -> 0 }
+                                                            ^
+
+
+Error ------------------------ match-guard-bad.sam:7:59-7:66
+
+The pattern is already covered by previous cases.
+
+  7|   function covered(o: Opt): int = match o { Some(_) -> 1, Some(x) if x > 0 -> 2, None -> 0 }
+                                                               ^^^^^^^
+
+
+Error ------------------------ match-guard-bad.sam:8:66-8:70
+
+The pattern is already covered by previous cases.
+
+  8|   function coveredAfterWildcard(o: Opt): int = match o { _ -> 1, None -> 0 }
+                                                                      ^^^^
+
+
+Error ------------------------ match-guard-bad.sam:9:63-9:64
+
+Cannot resolve name `y`.
+
+  9|   function unboundInGuard(o: Opt): int = match o { Some(_) if y -> 1, _ -> 0 }
+                                                                   ^
+
+
+Error ------------------ visibility/core/lib.sam:12:12-12:38
+
+`internal member` is incompatible with `public member`.
+
+  12|   internal method describe(): int = 1
+                 ^^^^^^^^^^^^^^^^^^^^^^^^^^
+
+
+Error ------------------- visibility/core/user.sam:5:40-5:44
+
+`area` is protected and not visible here.
+
+  5|   function bad(): int = Square.init(1).area()
+                                            ^^^^
+
+
+Error --------------------- visibility/outside.sam:2:10-2:16
+
+`Helper` is internal and not visible here.
+
+  2| import { Helper, LIMIT, Secret, Square } from visibility.core.lib
+              ^^^^^^
+
+
+Error --------------------- visibility/outside.sam:2:18-2:23
+
+`LIMIT` is internal and not visible here.
+
+  2| import { Helper, LIMIT, Secret, Square } from visibility.core.lib
+                      ^^^^^
+
+
+Error --------------------- visibility/outside.sam:2:25-2:31
+
+`Secret` is private and not visible here.
+
+  2| import { Helper, LIMIT, Secret, Square } from visibility.core.lib
+                             ^^^^^^
+
+
+Error --------------------- visibility/outside.sam:4:32-4:36
+
+`Helper` is internal and not visible here.
+
+  4|   function bad(): int = Helper.help() + LIMIT + Secret.get()
+                                    ^^^^
+
+
+Error --------------------- visibility/outside.sam:4:56-4:59
+
+`Secret` is private and not visible here.
+
+  4|   function bad(): int = Helper.help() + LIMIT + Secret.get()
+                                                            ^^^
+
+
+Error ------------------ interface-default-bad.sam:4:28-4:39
+
+`int` [1] is incompatible with `Str` [2].
+
+  4|   method describe(): Str = this.area()
+                                ^^^^^^^^^^^
+
+  [1] interface-default-bad.sam:4:28-4:39
+  ---------------------------------------
+  4|   method describe(): Str = this.area()
+                                ^^^^^^^^^^^
+
+  [2] interface-default-bad.sam:4:22-4:25
+  ---------------------------------------
+  4|   method describe(): Str = this.area()
+                          ^^^
+
+
+Error ------------------ interface-default-bad.sam:6:34-6:41
+
+Cannot resolve member `missing` on `Shape`.
+
+  6|   method perimeter(): int = this.missing()
+                                      ^^^^^^^
+
+
+Error ------------------- interface-default-bad.sam:8:7-8:13
+
+The following members must be implemented for the class:
+- `area`
+
+  8| class NoArea : Shape {}
+           ^^^^^^
+
+
+Error ---------------- interface-default-bad.sam:11:10-11:18
+
+`() -> int` [1] is incompatible with `() -> Str` [2].
+
+  11|   method describe(): int = 1
+               ^^^^^^^^
+
+  [1] interface-default-bad.sam:11:3-11:29
+  ----------------------------------------
+  11|   method describe(): int = 1
+        ^^^^^^^^^^^^^^^^^^^^^^^^^^
+
+  [2] interface-default-bad.sam:4:3-4:39
+  --------------------------------------
+  4|   method describe(): Str = this.area()
+       ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+
+
+Found 141 errors.
 "#;
 }
@@ -15,7 +15,7 @@ mod tests {
   use pretty_assertions::assert_eq;
   use samlang_ast::{
     Location, Reason,
-    source::{Id, Literal, NO_COMMENT_REFERENCE, expr},
+    source::{Id, Literal, NO_COMMENT_REFERENCE, Visibility, expr},
   };
   use samlang_errors::ErrorSet;
   use samlang_heap::{Heap, ModuleReference, PStr};
@@ -119,11 +119,14 @@ mod tests {
       (
         ModuleReference::DUMMY,
         ModuleSignature {
+          package: Vec::new(),
+          type_aliases: HashMap::new(),
+          constants: HashMap::new(),
           interfaces: HashMap::from([
             (
               heap.alloc_str_for_test("Test"),
               InterfaceSignature {
-                private: false,
+                visibility: Visibility::Public,
                 type_definition: Some(TypeDefinitionSignature::Struct(vec![
                   StructItemDefinitionSignature {
                     name: heap.alloc_str_for_test("foo"),
@@ -145,31 +148,36 @@ mod tests {
                   (
                     PStr::INIT,
                     MemberSignature {
-                      is_public: true,
+                      visibility: Visibility::Public,
+                      has_default: false,
                       type_parameters: Vec::new(),
                       type_: FunctionType {
                         reason: Reason::dummy(),
                         argument_types: vec![builder.bool_type(), builder.int_type()],
                         return_type: builder.simple_nominal_type(heap.alloc_str_for_test("Test")),
                       },
+                      parameters: Vec::new(),
                     },
                   ),
                   (
                     heap.alloc_str_for_test("helloWorld"),
                     MemberSignature {
-                      is_public: false,
+                      visibility: Visibility::Private,
+                      has_default: false,
                       type_parameters: Vec::new(),
                       type_: FunctionType {
                         reason: Reason::dummy(),
                         argument_types: vec![builder.string_type()],
                         return_type: builder.unit_type(),
                       },
+                      parameters: Vec::new(),
                     },
                   ),
                   (
                     heap.alloc_str_for_test("helloWorldWithTypeParameters"),
                     MemberSignature {
-                      is_public: false,
+                      visibility: Visibility::Private,
+                      has_default: false,
                       type_parameters: vec![TypeParameterSignature {
                         name: PStr::UPPER_A,
                         bound: None,
@@ -179,12 +187,14 @@ mod tests {
                         argument_types: vec![builder.generic_type(PStr::UPPER_A)],
                         return_type: builder.unit_type(),
                       },
+                      parameters: Vec::new(),
                     },
                   ),
                   (
                     heap.alloc_str_for_test("generic1"),
                     MemberSignature {
-                      is_public: false,
+                      visibility: Visibility::Private,
+                      has_default: false,
                       type_parameters: vec![
                         TypeParameterSignature { name: PStr::UPPER_A, bound: None },
                         TypeParameterSignature { name: PStr::UPPER_B, bound: None },
@@ -200,12 +210,14 @@ mod tests {
                         ],
                         return_type: builder.generic_type(PStr::UPPER_D),
                       },
+                      parameters: Vec::new(),
                     },
                   ),
                   (
                     heap.alloc_str_for_test("generic2"),
                     MemberSignature {
-                      is_public: false,
+                      visibility: Visibility::Private,
+                      has_default: false,
                       type_parameters: vec![TypeParameterSignature {
                         name: heap.alloc_str_for_test("T"),
                         bound: None,
@@ -218,12 +230,14 @@ mod tests {
                         ],
                         return_type: builder.bool_type(),
                       },
+                      parameters: Vec::new(),
                     },
                   ),
                   (
                     heap.alloc_str_for_test("generic3"),
                     MemberSignature {
-                      is_public: false,
+                      visibility: Visibility::Private,
+                      has_default: false,
                       type_parameters: vec![
                         TypeParameterSignature { name: PStr::UPPER_A, bound: None },
                         TypeParameterSignature { name: PStr::UPPER_B, bound: None },
@@ -236,12 +250,14 @@ mod tests {
                         )],
                         return_type: builder.bool_type(),
                       },
+                      parameters: Vec::new(),
                     },
                   ),
                   (
                     heap.alloc_str_for_test("generic4"),
                     MemberSignature {
-                      is_public: false,
+                      visibility: Visibility::Private,
+                      has_default: false,
                       type_parameters: Vec::new(),
                       type_: FunctionType {
                         reason: Reason::dummy(),
@@ -251,6 +267,7 @@ mod tests {
                         )],
                         return_type: builder.bool_type(),
                       },
+                      parameters: Vec::new(),
                     },
                   ),
                 ]),
@@ -258,19 +275,22 @@ mod tests {
                   (
                     heap.alloc_str_for_test("baz"),
                     MemberSignature {
-                      is_public: false,
+                      visibility: Visibility::Private,
+                      has_default: false,
                       type_parameters: Vec::new(),
                       type_: FunctionType {
                         reason: Reason::dummy(),
                         argument_types: vec![builder.int_type()],
                         return_type: builder.bool_type(),
                       },
+                      parameters: Vec::new(),
                     },
                   ),
                   (
                     heap.alloc_str_for_test("bazWithTypeParam"),
                     MemberSignature {
-                      is_public: false,
+                      visibility: Visibility::Private,
+                      has_default: false,
                       type_parameters: vec![TypeParameterSignature {
                         name: PStr::UPPER_A,
                         bound: None,
@@ -280,12 +300,14 @@ mod tests {
                         argument_types: vec![builder.int_type()],
                         return_type: builder.bool_type(),
                       },
+                      parameters: Vec::new(),
                     },
                   ),
                   (
                     heap.alloc_str_for_test("bazWithUsefulTypeParam"),
                     MemberSignature {
-                      is_public: false,
+                      visibility: Visibility::Private,
+                      has_default: false,
                       type_parameters: vec![TypeParameterSignature {
                         name: PStr::UPPER_A,
                         bound: None,
@@ -295,6 +317,7 @@ mod tests {
                         argument_types: vec![builder.generic_type(PStr::UPPER_A)],
                         return_type: builder.bool_type(),
                       },
+                      parameters: Vec::new(),
                     },
                   ),
                 ]),
@@ -305,7 +328,7 @@ mod tests {
             (
               heap.alloc_str_for_test("Test2"),
               InterfaceSignature {
-                private: false,
+                visibility: Visibility::Public,
                 type_definition: Some(TypeDefinitionSignature::Enum(vec![
                   EnumVariantDefinitionSignature {
                     name: heap.alloc_str_for_test("Foo"),
@@ -320,25 +343,29 @@ mod tests {
                   (
                     heap.alloc_str_for_test("Foo"),
                     MemberSignature {
-                      is_public: true,
+                      visibility: Visibility::Public,
+                      has_default: false,
                       type_parameters: Vec::new(),
                       type_: FunctionType {
                         reason: Reason::dummy(),
                         argument_types: vec![builder.bool_type()],
                         return_type: builder.simple_nominal_type(heap.alloc_str_for_test("Test2")),
                       },
+                      parameters: Vec::new(),
                     },
                   ),
                   (
                     heap.alloc_str_for_test("Bar"),
                     MemberSignature {
-                      is_public: true,
+                      visibility: Visibility::Public,
+                      has_default: false,
                       type_parameters: Vec::new(),
                       type_: FunctionType {
                         reason: Reason::dummy(),
                         argument_types: vec![builder.int_type()],
                         return_type: builder.simple_nominal_type(heap.alloc_str_for_test("Test2")),
                       },
+                      parameters: Vec::new(),
                     },
                   ),
                 ]),
@@ -350,7 +377,7 @@ mod tests {
             (
               heap.alloc_str_for_test("Test3"),
               InterfaceSignature {
-                private: false,
+                visibility: Visibility::Public,
                 type_parameters: vec![TypeParameterSignature { name: PStr::UPPER_E, bound: None }],
                 type_definition: Some(TypeDefinitionSignature::Struct(vec![
                   StructItemDefinitionSignature {
@@ -372,7 +399,7 @@ mod tests {
             (
               heap.alloc_str_for_test("Test4"),
               InterfaceSignature {
-                private: false,
+                visibility: Visibility::Public,
                 type_parameters: vec![TypeParameterSignature { name: PStr::UPPER_E, bound: None }],
                 type_definition: Some(TypeDefinitionSignature::Enum(vec![
                   EnumVariantDefinitionSignature {
@@ -388,7 +415,8 @@ mod tests {
                   (
                     heap.alloc_str_for_test("Foo"),
                     MemberSignature {
-                      is_public: true,
+                      visibility: Visibility::Public,
+                      has_default: false,
                       type_parameters: vec![TypeParameterSignature {
                         name: PStr::UPPER_E,
                         bound: None,
@@ -401,12 +429,14 @@ mod tests {
                           vec![builder.generic_type(PStr::UPPER_E)],
                         ),
                       },
+                      parameters: Vec::new(),
                     },
                   ),
                   (
                     heap.alloc_str_for_test("Bar"),
                     MemberSignature {
-                      is_public: true,
+                      visibility: Visibility::Public,
+                      has_default: false,
                       type_parameters: vec![TypeParameterSignature {
                         name: PStr::UPPER_E,
                         bound: None,
@@ -419,6 +449,7 @@ mod tests {
                           vec![builder.generic_type(PStr::UPPER_E)],
                         ),
                       },
+                      parameters: Vec::new(),
                     },
                   ),
                 ]),
@@ -429,7 +460,7 @@ mod tests {
             (
               PStr::UPPER_A,
               InterfaceSignature {
-                private: false,
+                visibility: Visibility::Public,
                 type_definition: Some(TypeDefinitionSignature::Struct(vec![
                   StructItemDefinitionSignature {
                     name: PStr::LOWER_A,
@@ -445,13 +476,15 @@ mod tests {
                 functions: HashMap::from([(
                   PStr::INIT,
                   MemberSignature {
-                    is_public: true,
+                    visibility: Visibility::Public,
+                    has_default: false,
                     type_parameters: Vec::new(),
                     type_: FunctionType {
                       reason: Reason::dummy(),
                       argument_types: Vec::new(),
                       return_type: builder.simple_nominal_type(PStr::UPPER_A),
                     },
+                    parameters: Vec::new(),
                   },
                 )]),
                 methods: HashMap::new(),
@@ -462,7 +495,7 @@ mod tests {
             (
               PStr::UPPER_B,
               InterfaceSignature {
-                private: false,
+                visibility: Visibility::Public,
                 type_definition: Some(TypeDefinitionSignature::Struct(vec![
                   StructItemDefinitionSignature {
                     name: PStr::LOWER_A,
@@ -478,13 +511,15 @@ mod tests {
                 functions: HashMap::from([(
                   PStr::INIT,
                   MemberSignature {
-                    is_public: true,
+                    visibility: Visibility::Public,
+                    has_default: false,
                     type_parameters: Vec::new(),
                     type_: FunctionType {
                       reason: Reason::dummy(),
                       argument_types: Vec::new(),
                       return_type: builder.simple_nominal_type(PStr::UPPER_B),
                     },
+                    parameters: Vec::new(),
                   },
                 )]),
                 methods: HashMap::new(),
@@ -495,7 +530,7 @@ mod tests {
             (
               PStr::UPPER_C,
               InterfaceSignature {
-                private: false,
+                visibility: Visibility::Public,
                 type_definition: Some(TypeDefinitionSignature::Enum(vec![
                   EnumVariantDefinitionSignature {
                     name: PStr::LOWER_A,
@@ -509,13 +544,15 @@ mod tests {
                 functions: HashMap::from([(
                   PStr::INIT,
                   MemberSignature {
-                    is_public: true,
+                    visibility: Visibility::Public,
+                    has_default: false,
                     type_parameters: Vec::new(),
                     type_: FunctionType {
                       reason: Reason::dummy(),
                       argument_types: Vec::new(),
                       return_type: builder.simple_nominal_type(PStr::UPPER_C),
                     },
+                    parameters: Vec::new(),
                   },
                 )]),
                 methods: HashMap::new(),
@@ -609,6 +646,7 @@ mod tests {
     assert_checks(heap, "true", &builder.bool_type());
     assert_checks(heap, "false", &builder.bool_type());
     assert_checks(heap, "42", &builder.int_type());
+    assert_checks(heap, "4.2", &builder.float_type());
     assert_checks(heap, "\"a\"", &builder.string_type());
     assert_errors(
       heap,
@@ -771,6 +809,189 @@ Found 1 error.
     );
   }
 
+  #[test]
+  fn tuple_class_conversion_tests() {
+    let tuples = r#"class Pair<E0, E1>(val e0: E0, val e1: E1) {}
+class Triple<E0, E1, E2>(val e0: E0, val e1: E1, val e2: E2) {}"#;
+    let option = "class Option<T>(None, Some(T)) {}";
+    let source = r#"import { Pair, Triple } from std.tuples;
+import { Option } from std.option;
+interface Shape { method area(): int }
+class Square(val side: int) : Shape { method area(): int = this.side * this.side }
+class Test {
+  function pair(): Pair<int, Str> = { let p: Pair<int, Str> = (1, ""); p }
+  function nested(): Pair<Triple<int, int, bool>, Str> = ((1, 2, true), "")
+  function generic(): Option<Pair<bool, int>> = Option.Some((true, 42))
+  function upcast(): Pair<Shape, int> = (Square.init(1), 1)
+  function converted(t: (int, (int, bool))): Pair<int, Pair<int, bool>> = t
+  function destructure(p: Pair<int, Str>): int = { let (a, _) = p; a + p.e0 }
+  function structural(): (int, Str) = (1, "")
+  function wrongElement(): Pair<int, Str> = (1, 2)
+  function wrongArity(): Triple<int, int, int> = (1, 2)
+  function noReverse(p: Pair<int, Str>): (int, Str) = p
+}
+"#;
+    assert_module_errors(
+      vec![("std.tuples", tuples), ("std.option", option), ("conversion", source)],
+      r#"Error --------------------------- conversion.sam:13:49-13:50
+
+`int` [1] is incompatible with `Str` [2].
+
+  13|   function wrongElement(): Pair<int, Str> = (1, 2)
+                                                      ^
+
+  [1] conversion.sam:13:49-13:50
+  ------------------------------
+  13|   function wrongElement(): Pair<int, Str> = (1, 2)
+                                                      ^
+
+  [2] conversion.sam:13:38-13:41
+  ------------------------------
+  13|   function wrongElement(): Pair<int, Str> = (1, 2)
+                                           ^^^
+
+
+Error --------------------------- conversion.sam:14:50-14:56
+
+`(int, int)` [1] is incompatible with `Triple<int, int, int>` [2].
+
+  14|   function wrongArity(): Triple<int, int, int> = (1, 2)
+                                                       ^^^^^^
+
+  [1] conversion.sam:14:50-14:56
+  ------------------------------
+  14|   function wrongArity(): Triple<int, int, int> = (1, 2)
+                                                       ^^^^^^
+
+  [2] conversion.sam:14:26-14:47
+  ------------------------------
+  14|   function wrongArity(): Triple<int, int, int> = (1, 2)
+                               ^^^^^^^^^^^^^^^^^^^^^
+
+
+Error --------------------------- conversion.sam:15:55-15:56
+
+`Pair<int, Str>` [1] is incompatible with `(int, Str)` [2].
+
+  15|   function noReverse(p: Pair<int, Str>): (int, Str) = p
+                                                            ^
+
+  [1] conversion.sam:15:55-15:56
+  ------------------------------
+  15|   function noReverse(p: Pair<int, Str>): (int, Str) = p
+                                                            ^
+
+  [2] conversion.sam:15:42-15:52
+  ------------------------------
+  15|   function noReverse(p: Pair<int, Str>): (int, Str) = p
+                                               ^^^^^^^^^^
+
+
+Found 3 errors."#,
+    );
+  }
+
+  #[test]
+  fn structural_tuple_checker_test() {
+    let heap = &mut Heap::new();
+    let builder = test_type_builder::create();
+
+    assert_errors_full_customization(
+      heap,
+      r#"{
+  let _: int = (1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1).e19;
+  let _: (int, (bool, Str)) = (1, (true, ""));
+  let _: ((int) -> int, int) = ((x) -> x + 1, 1);
+  let (a, (b, _), { e0 as c, e1 }) = (1, (true, 2), (3, 4));
+  let _: int = a + c + e1;
+  let _: bool = b;
+  let _: (int, int) = { ...(1, 2), e1: 3 };
+  let _: (int, int) = (1, 2, 3);
+  let _: (int, bool) = (1, 2);
+  let _: int = (1, 2).e2;
+  let _: int = (1, 2).foo;
+  let (_, _, _) = (1, 2);
+  let { e2 } = (1, 2);
+}"#,
+      &builder.unit_type(),
+      r#"
+Error ----------------------------------- DUMMY.sam:9:3-9:33
+
+`(int, int, int)` is incompatible with `(int, int)`.
+- Tuple arity of 3 is incompatible with tuple arity of 2.
+
+  9|   let _: (int, int) = (1, 2, 3);
+       ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+
+
+Error --------------------------------- DUMMY.sam:10:3-10:31
+
+`(int, int)` is incompatible with `(int, bool)`.
+- `int` [1] is incompatible with `bool` [2].
+
+  10|   let _: (int, bool) = (1, 2);
+        ^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+
+  [1] DUMMY.sam:10:28-10:29
+  -------------------------
+  10|   let _: (int, bool) = (1, 2);
+                                 ^
+
+  [2] DUMMY.sam:10:16-10:20
+  -------------------------
+  10|   let _: (int, bool) = (1, 2);
+                     ^^^^
+
+
+Error -------------------------------- DUMMY.sam:11:23-11:25
+
+Cannot resolve member `e2` on `(int, int)`.
+
+  11|   let _: int = (1, 2).e2;
+                            ^^
+
+
+Error -------------------------------- DUMMY.sam:12:23-12:26
+
+Cannot resolve member `foo` on `(int, int)`.
+
+  12|   let _: int = (1, 2).foo;
+                            ^^^
+
+
+Error -------------------------------- DUMMY.sam:13:14-13:15
+
+Cannot access member of `(int, int)` at index 2.
+
+  13|   let (_, _, _) = (1, 2);
+                   ^
+
+
+Error --------------------------------- DUMMY.sam:14:7-14:13
+
+The pattern does not bind all fields. The following names have not been mentioned:
+- `e0`
+- `e1`
+
+  14|   let { e2 } = (1, 2);
+            ^^^^^^
+
+
+Error --------------------------------- DUMMY.sam:14:9-14:11
+
+Cannot resolve member `e2` on `(int, int)`.
+
+  14|   let { e2 } = (1, 2);
+              ^^
+
+
+Found 7 errors.
+"#,
+      "Test",
+      true,
+    );
+  }
+
   #[test]
   fn class_members_checker_test() {
     let heap = &mut Heap::new();
@@ -1706,6 +1927,22 @@ Found 1 error.
     assert_checks(heap, "false != true", &builder.bool_type());
     assert_checks(heap, "\"\" != \"3\"", &builder.bool_type());
     assert_checks(heap, "{ let _ = (t: Str, f: Str) -> t == f; }", &builder.unit_type());
+    assert_checks(heap, "~1", &builder.int_type());
+    assert_checks(heap, "1 & 1", &builder.int_type());
+    assert_checks(heap, "1 | 1", &builder.int_type());
+    assert_checks(heap, "1 ^ 1", &builder.int_type());
+    assert_checks(heap, "1 << 1", &builder.int_type());
+    assert_checks(heap, "1 >> 1", &builder.int_type());
+    assert_checks(heap, "1 >>> 1", &builder.int_type());
+    assert_checks(heap, "1 & 1 == 0", &builder.bool_type());
+    assert_checks(heap, "-1.5", &builder.float_type());
+    assert_checks(heap, "1.5 * 2.0", &builder.float_type());
+    assert_checks(heap, "1.5 / 2.0", &builder.float_type());
+    assert_checks(heap, "1.5 + 2.0", &builder.float_type());
+    assert_checks(heap, "1.5 - 2.0", &builder.float_type());
+    assert_checks(heap, "1.5 < 2.0", &builder.bool_type());
+    assert_checks(heap, "1.5 >= 2.0", &builder.bool_type());
+    assert_checks(heap, "1.5 == 2.0", &builder.bool_type());
 
     assert_errors(
       heap,
@@ -1731,6 +1968,128 @@ Error ------------------------------------ DUMMY.sam:1:3-1:8
 
 
 Found 1 error.
+"#,
+    );
+    assert_errors(
+      heap,
+      "~true",
+      &builder.int_type(),
+      r#"
+Error ------------------------------------ DUMMY.sam:1:2-1:6
+
+`bool` [1] is incompatible with `int` [2].
+
+  1| ~true
+      ^^^^
+
+  [1] DUMMY.sam:1:2-1:6
+  ---------------------
+  1| ~true
+      ^^^^
+
+  [2] DUMMY.sam:1:1-1:6
+  ---------------------
+  1| ~true
+     ^^^^^
+
+
+Found 1 error.
+"#,
+    );
+    assert_errors(
+      heap,
+      "true | 1",
+      &builder.int_type(),
+      r#"
+Error ------------------------------------ DUMMY.sam:1:1-1:5
+
+`bool` [1] is incompatible with `int` [2].
+
+  1| true | 1
+     ^^^^
+
+  [1] DUMMY.sam:1:1-1:5
+  ---------------------
+  1| true | 1
+     ^^^^
+
+  [2] DUMMY.sam:1:1-1:9
+  ---------------------
+  1| true | 1
+     ^^^^^^^^
+
+
+Found 1 error.
+"#,
+    );
+    assert_errors(
+      heap,
+      "1.0 + 1",
+      &builder.float_type(),
+      r#"
+Error ------------------------------------ DUMMY.sam:1:7-1:8
+
+`int` [1] is incompatible with `float` [2].
+
+  1| 1.0 + 1
+           ^
+
+  [1] DUMMY.sam:1:7-1:8
+  ---------------------
+  1| 1.0 + 1
+           ^
+
+  [2] DUMMY.sam:1:1-1:8
+  ---------------------
+  1| 1.0 + 1
+     ^^^^^^^
+
+
+Found 1 error.
+"#,
+    );
+    assert_errors(
+      heap,
+      "1.0 % 1.0",
+      &builder.int_type(),
+      r#"
+Error ------------------------------------ DUMMY.sam:1:1-1:4
+
+`float` [1] is incompatible with `int` [2].
+
+  1| 1.0 % 1.0
+     ^^^
+
+  [1] DUMMY.sam:1:1-1:4
+  ---------------------
+  1| 1.0 % 1.0
+     ^^^
+
+  [2] DUMMY.sam:1:1-1:10
+  ----------------------
+  1| 1.0 % 1.0
+     ^^^^^^^^^
+
+
+Error ----------------------------------- DUMMY.sam:1:7-1:10
+
+`float` [1] is incompatible with `int` [2].
+
+  1| 1.0 % 1.0
+           ^^^
+
+  [1] DUMMY.sam:1:7-1:10
+  ----------------------
+  1| 1.0 % 1.0
+           ^^^
+
+  [2] DUMMY.sam:1:1-1:10
+  ----------------------
+  1| 1.0 % 1.0
+     ^^^^^^^^^
+
+
+Found 2 errors.
 "#,
     );
     assert_errors(
@@ -2804,7 +3163,7 @@ The pattern is irrefutable.
 
 Error ---------------------------------- DUMMY.sam:1:38-1:39
 
-Cannot access member of `Pair<int, int>` at index 2.
+Cannot access member of `(int, int)` at index 2.
 
   1| { let _ = (t: Test) -> if let (a, b, _) = (1, 2) {1} else {2}; }
                                           ^
@@ -3382,13 +3741,15 @@ Found 6 errors.
     let mut string_sources = HashMap::new();
     let mut unchecked_sources = HashMap::new();
     for (mod_ref_str, source) in sources {
-      let mod_ref = heap.alloc_module_reference_from_string_vec(vec![mod_ref_str.to_string()]);
+      let mod_ref = heap.alloc_module_reference_from_string_vec(
+        mod_ref_str.split('.').map(|part| part.to_string()).collect(),
+      );
       let parsed =
         samlang_parser::parse_source_module_from_text(source, mod_ref, &mut heap, &mut error_set);
       string_sources.insert(mod_ref, source.to_string());
       unchecked_sources.insert(mod_ref, parsed);
     }
-    type_check_sources(&unchecked_sources, &mut error_set);
+    type_check_sources(&unchecked_sources, &heap, &mut error_set);
     assert_eq!(
       expected_errors.trim(),
       error_set.pretty_print_error_messages(&heap, &string_sources).trim()
@@ -3594,7 +3955,7 @@ Type argument arity of 0 is incompatible with type argument arity of 2.
 
 Error -------------------------------------- D.sam:2:15-2:16
 
-There is no `D` export in `B`.
+`D` is private and not visible here.
 
   2|   import { B, D } from B
                    ^
@@ -3913,8 +4274,8 @@ class Pair<T: Comparable<T>>(val v1: T, val v2: T) {
   method relation3(): int = TwoItemCompare.compare<int>(this.v1, this.v2) // error typearg
 }
 class TestLimitedSubtyping {
-  function test(v: Comparable<BoxedInt>): unit = {} // error signature validation
-  function main(): unit = TestLimitedSubtyping.test(BoxedInt.init(1)) // error subtyping
+  function test(v: Comparable<BoxedInt>): unit = {} // ok: interface value type
+  function main(): unit = TestLimitedSubtyping.test(BoxedInt.init(1)) // ok: implicit upcast
 }
 interface Conflicting1 {
   method foo(): int
@@ -3974,32 +4335,6 @@ Error --------------------- bounded-generics.sam:15:66-15:73
                                                          ^^^
 
 
-Error --------------------- bounded-generics.sam:18:20-18:40
-
-`Comparable<BoxedInt>` is incompatible with `non-abstract type`.
-
-  18|   function test(v: Comparable<BoxedInt>): unit = {} // error signature validation
-                         ^^^^^^^^^^^^^^^^^^^^
-
-
-Error --------------------- bounded-generics.sam:19:53-19:69
-
-`BoxedInt` [1] is incompatible with `Comparable<BoxedInt>` [2].
-
-  19|   function main(): unit = TestLimitedSubtyping.test(BoxedInt.init(1)) // error subtyping
-                                                          ^^^^^^^^^^^^^^^^
-
-  [1] bounded-generics.sam:19:53-19:69
-  ------------------------------------
-  19|   function main(): unit = TestLimitedSubtyping.test(BoxedInt.init(1)) // error subtyping
-                                                          ^^^^^^^^^^^^^^^^
-
-  [2] bounded-generics.sam:18:20-18:40
-  ------------------------------------
-  18|   function test(v: Comparable<BoxedInt>): unit = {} // error signature validation
-                         ^^^^^^^^^^^^^^^^^^^^
-
-
 Error ---------------------- bounded-generics.sam:28:7-28:17
 
 Type `ImplItself` has a cyclic definition.
@@ -4032,27 +4367,914 @@ Error --------------------- bounded-generics.sam:31:34-31:35
                                        ^
 
 
-Found 9 errors.
+Found 7 errors.
 "#;
     assert_module_errors(vec![("bounded-generics", source)], expected_errors);
   }
 
   #[test]
-  fn type_checker_identifier_resolution_tests() {
-    let source_a = r#"class SameName(val a: int) {
-    function create(): SameName = SameName.init(0)
-  }"#;
-    let source_b = r#"import { SameName } from A
-  class Producer {
-    function produce(): SameName = SameName.create()
-  }"#;
-    let source_c = r#"import { Producer } from B
+  fn interface_value_tests() {
+    let source = r#"
+interface Shape {
+  method area(): int
+}
+interface Tagger {
+  method <T> tag(t: T): T
+}
+class Square(val side: int) : Shape, Tagger {
+  method area(): int = this.side * this.side
+  method <T> tag(t: T): T = t
+}
+class Circle(val radius: int) : Shape {
+  method area(): int = this.radius
+}
+class Choice(Big(int), Small) {}
+class Test {
+  function total(shapes: Vec<Shape>, s: Shape): int = shapes.length() + s.area()
+  function pick(b: bool): Shape = if b { Square.init(1) } else { Circle.init(2) }
+  function pickMatch(c: Choice): Shape = match c { Big(v) -> Square.init(v), Small -> Circle.init(0) }
+  function early(b: bool): Shape = { if b { return Square.init(1); } else {}; Circle.init(2) }
+  function <S: Shape> bound(s: S): Shape = s
+  function main(): unit = {
+    let shapes: Vec<Shape> = Vec.empty();
+    shapes.push(Square.init(1));
+    let s: Shape = Circle.init(1);
+    var t: Shape = s;
+    t = Square.init(2);
+    let _ = Test.total(shapes, Circle.init(3));
+    let _: bool = s == Circle.init(1); // error: no upcast in equality
+    let _: Tagger = Square.init(1); // error: generic methods cannot be dispatched
+    let _: Square = s; // error: no downcast
+  }
+}
+"#;
+    let expected_errors = r#"
+Error ---------------------- interface-value.sam:29:24-29:38
 
-  class SameName(val b: int) {
-    // Here, Producer.produce() produces a SameName class from module a, so the field a should exist.
-    function create(): SameName = SameName.init(Producer.produce().a)
-  }"#;
+`Circle` [1] is incompatible with `Shape` [2].
 
-    assert_module_errors(vec![("A", source_a), ("B", source_b), ("C", source_c)], "");
+  29|     let _: bool = s == Circle.init(1); // error: no upcast in equality
+                             ^^^^^^^^^^^^^^
+
+  [1] interface-value.sam:29:24-29:38
+  -----------------------------------
+  29|     let _: bool = s == Circle.init(1); // error: no upcast in equality
+                             ^^^^^^^^^^^^^^
+
+  [2] interface-value.sam:29:19-29:20
+  -----------------------------------
+  29|     let _: bool = s == Circle.init(1); // error: no upcast in equality
+                        ^
+
+
+Error ----------------------- interface-value.sam:30:5-30:36
+
+`Square` [1] is incompatible with `Tagger` [2].
+
+  30|     let _: Tagger = Square.init(1); // error: generic methods cannot be dispatched
+          ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+
+  [1] interface-value.sam:30:21-30:35
+  -----------------------------------
+  30|     let _: Tagger = Square.init(1); // error: generic methods cannot be dispatched
+                          ^^^^^^^^^^^^^^
+
+  [2] interface-value.sam:30:12-30:18
+  -----------------------------------
+  30|     let _: Tagger = Square.init(1); // error: generic methods cannot be dispatched
+                 ^^^^^^
+
+
+Error ---------------------- interface-value.sam:30:12-30:18
+
+`Tagger` cannot be used as a value type, since its method `tag` is generic.
+
+  30|     let _: Tagger = Square.init(1); // error: generic methods cannot be dispatched
+                 ^^^^^^
+
+
+Error ----------------------- interface-value.sam:31:5-31:23
+
+`Shape` [1] is incompatible with `Square` [2].
+
+  31|     let _: Square = s; // error: no downcast
+          ^^^^^^^^^^^^^^^^^^
+
+  [1] interface-value.sam:31:21-31:22
+  -----------------------------------
+  31|     let _: Square = s; // error: no downcast
+                          ^
+
+  [2] interface-value.sam:31:12-31:18
+  -----------------------------------
+  31|     let _: Square = s; // error: no downcast
+                 ^^^^^^
+
+
+Found 4 errors.
+"#;
+    assert_module_errors(vec![("interface-value", source)], expected_errors);
+  }
+
+  #[test]
+  fn try_tests() {
+    let option_source = "class Option<T>(None, Some(T)) {}";
+    let result_source = "class Result<T, E>(Ok(T), Error(E)) {}";
+    let source = r#"
+import { Option } from std.option;
+import { Result } from std.result;
+class Test {
+  function opt(o: Option<int>): Option<bool> = { let v = o?; Option.Some(v > 0) }
+  function res(r: Result<int, Str>): Result<bool, Str> = Result.Ok(r? > 0)
+  function notOption(o: Option<int>): int = o?
+  function mixed(r: Result<int, Str>): Option<int> = Option.Some(r?)
+  function errorType(r: Result<int, int>): Result<int, Str> = Result.Ok(r?)
+  function operand(i: int): Option<int> = Option.Some(i?)
+  function lambda(o: Option<int>): Option<int> = { let _ = () -> o?; Option.None() }
+}
+"#;
+    let expected_errors = r#"
+Error ------------------------------------ try.sam:7:45-7:47
+
+`?` on `Option` is only allowed in functions that return `Option`.
+
+  7|   function notOption(o: Option<int>): int = o?
+                                                 ^^
+
+
+Error ------------------------------------ try.sam:8:66-8:68
+
+`?` on `Result` is only allowed in functions that return `Result`.
+
+  8|   function mixed(r: Result<int, Str>): Option<int> = Option.Some(r?)
+                                                                      ^^
+
+
+Error ------------------------------------ try.sam:9:73-9:74
+
+`int` [1] is incompatible with `Str` [2].
+
+  9|   function errorType(r: Result<int, int>): Result<int, Str> = Result.Ok(r?)
+                                                                             ^
+
+  [1] try.sam:9:37-9:40
+  ---------------------
+  9|   function errorType(r: Result<int, int>): Result<int, Str> = Result.Ok(r?)
+                                         ^^^
+
+  [2] try.sam:9:56-9:59
+  ---------------------
+  9|   function errorType(r: Result<int, int>): Result<int, Str> = Result.Ok(r?)
+                                                            ^^^
+
+
+Error ---------------------------------- try.sam:10:55-10:56
+
+`?` can only be applied to `Option` or `Result`, but got `int`.
+
+  10|   function operand(i: int): Option<int> = Option.Some(i?)
+                                                            ^
+
+
+Error ---------------------------------- try.sam:11:66-11:68
+
+`?` on `Option` is only allowed in functions that return `Option`.
+
+  11|   function lambda(o: Option<int>): Option<int> = { let _ = () -> o?; Option.None() }
+                                                                       ^^
+
+
+Found 5 errors.
+"#;
+    assert_module_errors(
+      vec![("std.option", option_source), ("std.result", result_source), ("try", source)],
+      expected_errors,
+    );
+  }
+
+  #[test]
+  fn template_string_tests() {
+    let source = r#"
+interface Show { method toString(): Str }
+class Boxed(val v: int) : Show { method toString(): Str = "${this.v}" }
+class Named(val n: Str) { private method toString(): Str = this.n }
+class WrongReturn(val v: int) { method toString(): int = this.v }
+class WithArgument(val v: int) { method toString(radix: int): Str = "" }
+class Test {
+  function <T: Show> good(t: T, b: Boxed, i: int, s: Str): Str = "${t} ${b} ${i} ${s}"
+  function bad(n: Named, w: WrongReturn, a: WithArgument, f: (int) -> int): Str =
+    "${n} ${w} ${a} ${f} ${true} ${Test}"
+  function inferred(): Str = "${(x) -> x}"
+}
+"#;
+    let expected_errors = r#"
+Error ------------------------------- template.sam:10:8-10:9
+
+`Named` cannot be used in a template string. Expected `Str`, `int` or a type with a `toString(): Str` method.
+
+  10|     "${n} ${w} ${a} ${f} ${true} ${Test}"
+             ^
+
+
+Error ----------------------------- template.sam:10:13-10:14
+
+`WrongReturn` cannot be used in a template string. Expected `Str`, `int` or a type with a `toString(): Str` method.
+
+  10|     "${n} ${w} ${a} ${f} ${true} ${Test}"
+                  ^
+
+
+Error ----------------------------- template.sam:10:18-10:19
+
+`WithArgument` cannot be used in a template string. Expected `Str`, `int` or a type with a `toString(): Str` method.
+
+  10|     "${n} ${w} ${a} ${f} ${true} ${Test}"
+                       ^
+
+
+Error ----------------------------- template.sam:10:23-10:24
+
+`(int) -> int` cannot be used in a template string. Expected `Str`, `int` or a type with a `toString(): Str` method.
+
+  10|     "${n} ${w} ${a} ${f} ${true} ${Test}"
+                            ^
+
+
+Error ----------------------------- template.sam:10:28-10:32
+
+`bool` cannot be used in a template string. Expected `Str`, `int` or a type with a `toString(): Str` method.
+
+  10|     "${n} ${w} ${a} ${f} ${true} ${Test}"
+                                 ^^^^
+
+
+Error ----------------------------- template.sam:10:36-10:40
+
+`class Test` cannot be used in a template string. Expected `Str`, `int` or a type with a `toString(): Str` method.
+
+  10|     "${n} ${w} ${a} ${f} ${true} ${Test}"
+                                         ^^^^
+
+
+Error ----------------------------- template.sam:11:33-11:41
+
+`(any) -> any` cannot be used in a template string. Expected `Str`, `int` or a type with a `toString(): Str` method.
+
+  11|   function inferred(): Str = "${(x) -> x}"
+                                      ^^^^^^^^
+
+
+Error ----------------------------- template.sam:11:34-11:35
+
+There is not enough context information to decide the type of this expression.
+
+  11|   function inferred(): Str = "${(x) -> x}"
+                                       ^
+
+
+Found 8 errors.
+"#;
+    assert_module_errors(vec![("template", source)], expected_errors);
+  }
+
+  #[test]
+  fn pipe_tests() {
+    let source = r#"
+class Test {
+  function <T> apply(t: T, f: (T) -> T): T = f(t)
+  function add(a: int, b: int): int = a + b
+  function good(): int = {
+    let a = 1 |> (n) -> n + 1;
+    let b = a |> Test.add(2) |> Test.apply((n) -> n * 2);
+    let c: Str = b |> Str.fromInt;
+    b
+  }
+  function bad(): int = {
+    let _ = 1 |> Test.add;
+    let _ = 1 |> 2;
+    let _ = true |> (n: int) -> n;
+    let _ = "" |> Test.add(1);
+    1 |> (n) -> n |> Test.add(1)
+  }
+}
+"#;
+    let expected_errors = r#"
+Error --------------------------------- pipe.sam:12:13-12:26
+
+Function parameter arity of 1 is incompatible with function parameter arity of 2.
+
+  12|     let _ = 1 |> Test.add;
+                  ^^^^^^^^^^^^^
+
+
+Error --------------------------------- pipe.sam:13:18-13:19
+
+`int` is incompatible with `function type`.
+
+  13|     let _ = 1 |> 2;
+                       ^
+
+
+Error --------------------------------- pipe.sam:14:13-14:17
+
+`bool` [1] is incompatible with `int` [2].
+
+  14|     let _ = true |> (n: int) -> n;
+                  ^^^^
+
+  [1] pipe.sam:14:13-14:17
+  ------------------------
+  14|     let _ = true |> (n: int) -> n;
+                  ^^^^
+
+  [2] pipe.sam:14:25-14:28
+  ------------------------
+  14|     let _ = true |> (n: int) -> n;
+                              ^^^
+
+
+Error --------------------------------- pipe.sam:15:13-15:15
+
+`Str` [1] is incompatible with `int` [2].
+
+  15|     let _ = "" |> Test.add(1);
+                  ^^
+
+  [1] pipe.sam:15:13-15:15
+  ------------------------
+  15|     let _ = "" |> Test.add(1);
+                  ^^
+
+  [2] pipe.sam:4:19-4:22
+  ----------------------
+  4|   function add(a: int, b: int): int = a + b
+                       ^^^
+
+
+Found 4 errors.
+"#;
+    assert_module_errors(vec![("pipe", source)], expected_errors);
+  }
+
+  #[test]
+  fn let_else_tests() {
+    let source = r#"
+class Opt(None, Some(int)) {
+  function good(o: Opt): int = {
+    let Some(a) = o else { return 0 };
+    let Some(b) = o else { Process.panic("b") };
+    let Some(c) = o else { if a > 0 { return 1 } else { Process.panic("c") } };
+    var i = 0;
+    while true {
+      let Some(d) = o else { break };
+      i = i + d;
+    }
+    let j = if i > 0 { Process.panic("j") } else { 1 };
+    a + b + c + i + j
+  }
+  function bad(o: Opt): int = {
+    let Some(a) = o else { 1 };
+    let Some(b) = o else { };
+    let c = o else { return 1 };
+    let Some(d) = o else { return d };
+    let Some(e) = o;
+    a + b + d + e
+  }
+  function goodStatements(o: Opt): int = {
+    let Some(a) = o else { return -1; };
+    let Some(b) = o else { let _: int = Process.panic("b"); };
+    var i = 0;
+    while true {
+      let Some(c) = o else { i = i + 1; break; };
+      i = i + c;
+    }
+    a + b + i
+  }
+}
+"#;
+    let expected_errors = r#"
+Error ------------------------------ letElse.sam:16:26-16:31
+
+The `else` block of `let ... else` must end with `return`, `break` or `Process.panic`.
+
+  16|     let Some(a) = o else { 1 };
+                               ^^^^^
+
+
+Error ------------------------------ letElse.sam:17:26-17:29
+
+The `else` block of `let ... else` must end with `return`, `break` or `Process.panic`.
+
+  17|     let Some(b) = o else { };
+                               ^^^
+
+
+Error ------------------------------- letElse.sam:18:9-18:10
+
+The pattern is irrefutable.
+
+  18|     let c = o else { return 1 };
+              ^
+
+
+Error ------------------------------ letElse.sam:19:35-19:36
+
+Cannot resolve name `d`.
+
+  19|     let Some(d) = o else { return d };
+                                        ^
+
+
+Error ------------------------------- letElse.sam:20:9-20:16
+
+This pattern-matching is not exhaustive.
+Here is an example of a non-matching value: `None`.
+
+  20|     let Some(e) = o;
+              ^^^^^^^
+
+
+Found 5 errors.
+"#;
+    assert_module_errors(vec![("letElse", source)], expected_errors);
+  }
+
+  #[test]
+  fn struct_update_tests() {
+    let source = r#"
+class Point(val x: int, val y: int, private val label: Str) {
+  function good(p: Point): Point = { ...p, y: 2, x: 1 }
+  function unchanged(p: Point): Point = { ...p }
+  method relabel(): Point = { ...this, label: "new" }
+}
+class Box<T>(val value: T) {
+  function replace(b: Box<int>): Box<int> = { ...b, value: 3 }
+}
+class Opt(None, Some(int)) {}
+class Main {
+  function bad(p: Point, b: Box<Str>, o: Opt): unit = {
+    let _ = { ...p, z: 1 };
+    let _ = { ...p, label: "a" };
+    let _ = { ...p, x: 1, x: 2 };
+    let _ = { ...p, y: "a" };
+    let _ = { ...b, value: 1 };
+    let _ = { ...o, x: 1 };
+    let _ = { ...1 };
+    let _: int = { ...p };
+  }
+}
+"#;
+    let expected_errors = r#"
+Error ------------------------- structUpdate.sam:13:21-13:22
+
+Cannot resolve member `z` on `Point`.
+
+  13|     let _ = { ...p, z: 1 };
+                          ^
+
+
+Error ------------------------- structUpdate.sam:14:21-14:26
+
+`label` is private and not visible here.
+
+  14|     let _ = { ...p, label: "a" };
+                          ^^^^^
+
+
+Error ------------------------- structUpdate.sam:15:27-15:28
+
+Name `x` collides with a previously defined name at [1].
+
+  15|     let _ = { ...p, x: 1, x: 2 };
+                                ^
+
+  [1] structUpdate.sam:15:21-15:22
+  --------------------------------
+  15|     let _ = { ...p, x: 1, x: 2 };
+                          ^
+
+
+Error ------------------------- structUpdate.sam:16:24-16:27
+
+`Str` [1] is incompatible with `int` [2].
+
+  16|     let _ = { ...p, y: "a" };
+                             ^^^
+
+  [1] structUpdate.sam:16:24-16:27
+  --------------------------------
+  16|     let _ = { ...p, y: "a" };
+                             ^^^
+
+  [2] structUpdate.sam:2:32-2:35
+  ------------------------------
+  2| class Point(val x: int, val y: int, private val label: Str) {
+                                    ^^^
+
+
+Error ------------------------- structUpdate.sam:17:28-17:29
+
+`int` [1] is incompatible with `Str` [2].
+
+  17|     let _ = { ...b, value: 1 };
+                                 ^
+
+  [1] structUpdate.sam:17:28-17:29
+  --------------------------------
+  17|     let _ = { ...b, value: 1 };
+                                 ^
+
+  [2] structUpdate.sam:12:33-12:36
+  --------------------------------
+  12|   function bad(p: Point, b: Box<Str>, o: Opt): unit = {
+                                      ^^^
+
+
+Error ------------------------- structUpdate.sam:18:18-18:19
+
+`Opt` is not an instance of a struct class.
+
+  18|     let _ = { ...o, x: 1 };
+                       ^
+
+
+Error ------------------------- structUpdate.sam:19:18-19:19
+
+`int` is not an instance of a struct class.
+
+  19|     let _ = { ...1 };
+                       ^
+
+
+Error -------------------------- structUpdate.sam:20:5-20:27
+
+`Point` [1] is incompatible with `int` [2].
+
+  20|     let _: int = { ...p };
+          ^^^^^^^^^^^^^^^^^^^^^^
+
+  [1] structUpdate.sam:20:18-20:26
+  --------------------------------
+  20|     let _: int = { ...p };
+                       ^^^^^^^^
+
+  [2] structUpdate.sam:20:12-20:15
+  --------------------------------
+  20|     let _: int = { ...p };
+                 ^^^
+
+
+Found 8 errors.
+"#;
+    assert_module_errors(vec![("structUpdate", source)], expected_errors);
+  }
+
+  #[test]
+  fn named_argument_tests() {
+    let source = r#"
+const LIMIT: int = 3;
+class Point(val x: int, val y: int) {
+  function make(x: int, y: int = LIMIT, label: Str = "p"): Point = Point.init(x, y)
+  method shift(by: int = 1): Point = Point.init(y = this.y, x = this.x + by)
+  function good(): unit = {
+    let _ = Point.make(1);
+    let _ = Point.make(y = 2, x = 1);
+    let _ = Point.make(1, label = "q");
+    let _ = Point.init(1, 2).shift();
+    let _ = Point.init(y = 1, x = 2).shift(by = 3);
+  }
+  function bad(): unit = {
+    let _ = Point.make(1, x = 2);
+    let _ = Point.make(1, z = 2);
+    let _ = Point.make(label = "q");
+    let _ = Point.make(1, y = "q");
+    let _ = Point.init(x = 1);
+    let f = (a: int) -> a;
+    let _ = f(a = 1);
+  }
+  function nonConstant(a: int, b: int = Point.make(1).x, c: bool = 1): unit = {}
+}
+"#;
+    let expected_errors = r#"
+Error -------------------------------- named.sam:14:27-14:28
+
+Parameter `x` is already given an argument.
+
+  14|     let _ = Point.make(1, x = 2);
+                                ^
+
+
+Error -------------------------------- named.sam:15:27-15:28
+
+There is no parameter named `z`.
+
+  15|     let _ = Point.make(1, z = 2);
+                                ^
+
+
+Error -------------------------------- named.sam:16:13-16:36
+
+Missing an argument for parameter `x`.
+
+  16|     let _ = Point.make(label = "q");
+                  ^^^^^^^^^^^^^^^^^^^^^^^
+
+
+Error -------------------------------- named.sam:17:31-17:34
+
+`Str` [1] is incompatible with `int` [2].
+
+  17|     let _ = Point.make(1, y = "q");
+                                    ^^^
+
+  [1] named.sam:17:31-17:34
+  -------------------------
+  17|     let _ = Point.make(1, y = "q");
+                                    ^^^
+
+  [2] named.sam:4:28-4:31
+  -----------------------
+  4|   function make(x: int, y: int = LIMIT, label: Str = "p"): Point = Point.init(x, y)
+                                ^^^
+
+
+Error -------------------------------- named.sam:18:13-18:30
+
+Missing an argument for parameter `y`.
+
+  18|     let _ = Point.init(x = 1);
+                  ^^^^^^^^^^^^^^^^^
+
+
+Error -------------------------------- named.sam:20:15-20:16
+
+There is no parameter named `a`.
+
+  20|     let _ = f(a = 1);
+                    ^
+
+
+Error -------------------------------- named.sam:22:41-22:56
+
+This expression cannot be evaluated at compile time.
+
+  22|   function nonConstant(a: int, b: int = Point.make(1).x, c: bool = 1): unit = {}
+                                              ^^^^^^^^^^^^^^^
+
+
+Error -------------------------------- named.sam:22:68-22:69
+
+`int` [1] is incompatible with `bool` [2].
+
+  22|   function nonConstant(a: int, b: int = Point.make(1).x, c: bool = 1): unit = {}
+                                                                         ^
+
+  [1] named.sam:22:68-22:69
+  -------------------------
+  22|   function nonConstant(a: int, b: int = Point.make(1).x, c: bool = 1): unit = {}
+                                                                         ^
+
+  [2] named.sam:22:61-22:65
+  -------------------------
+  22|   function nonConstant(a: int, b: int = Point.make(1).x, c: bool = 1): unit = {}
+                                                                  ^^^^
+
+
+Found 8 errors.
+"#;
+    assert_module_errors(vec![("named", source)], expected_errors);
+  }
+
+  #[test]
+  fn local_function_tests() {
+    let source = r#"
+class Main {
+  function good(base: int): int = {
+    function fact(n: int): int = if n <= 1 { base } else { n * fact(n - 1) };
+    function first(n: int): bool = {
+      let _ = if n > 10 { return true } else { 0 };
+      first(n + 1)
+    };
+    let f: (int) -> int = fact;
+    if first(0) { f(3) } else { 0 }
+  }
+  function bad(): int = {
+    var v = 1;
+    function a(n: int): Str = n;
+    function b(n: int): int = { v };
+    function c(n: int): int = c(true);
+    function d(n: int): int = { break };
+    function b(n: int): int = n;
+    0
+  }
+}
+"#;
+    let expected_errors = r#"
+Error ------------------------ localFunction.sam:14:31-14:32
+
+`int` [1] is incompatible with `Str` [2].
+
+  14|     function a(n: int): Str = n;
+                                    ^
+
+  [1] localFunction.sam:14:31-14:32
+  ---------------------------------
+  14|     function a(n: int): Str = n;
+                                    ^
+
+  [2] localFunction.sam:14:25-14:28
+  ---------------------------------
+  14|     function a(n: int): Str = n;
+                              ^^^
+
+
+Error ------------------------ localFunction.sam:15:33-15:34
+
+Mutable variable `v` cannot be captured by a lambda.
+
+  15|     function b(n: int): int = { v };
+                                      ^
+
+
+Error ------------------------ localFunction.sam:16:33-16:37
+
+`bool` [1] is incompatible with `int` [2].
+
+  16|     function c(n: int): int = c(true);
+                                      ^^^^
+
+  [1] localFunction.sam:16:33-16:37
+  ---------------------------------
+  16|     function c(n: int): int = c(true);
+                                      ^^^^
+
+  [2] localFunction.sam:16:19-16:22
+  ---------------------------------
+  16|     function c(n: int): int = c(true);
+                        ^^^
+
+
+Error ------------------------ localFunction.sam:17:33-17:38
+
+`break` is only allowed inside loops.
+
+  17|     function d(n: int): int = { break };
+                                      ^^^^^
+
+
+Error ------------------------ localFunction.sam:18:14-18:15
+
+Name `b` collides with a previously defined name at [1].
+
+  18|     function b(n: int): int = n;
+                   ^
+
+  [1] localFunction.sam:15:14-15:15
+  ---------------------------------
+  15|     function b(n: int): int = { v };
+                   ^
+
+
+Found 5 errors.
+"#;
+    assert_module_errors(vec![("localFunction", source)], expected_errors);
+  }
+
+  #[test]
+  fn type_checker_identifier_resolution_tests() {
+    let source_a = r#"class SameName(val a: int) {
+    function create(): SameName = SameName.init(0)
+  }"#;
+    let source_b = r#"import { SameName } from A
+  class Producer {
+    function produce(): SameName = SameName.create()
+  }"#;
+    let source_c = r#"import { Producer } from B
+
+  class SameName(val b: int) {
+    // Here, Producer.produce() produces a SameName class from module a, so the field a should exist.
+    function create(): SameName = SameName.init(Producer.produce().a)
+  }"#;
+
+    assert_module_errors(vec![("A", source_a), ("B", source_b), ("C", source_c)], "");
+  }
+
+  #[test]
+  fn derives_tests() {
+    let interfaces = "interface Comparable<T> { method compare(other: T): int }";
+    let source = r#"
+import { Comparable } from std.interfaces;
+class P(val x: int, val f: float, val b: bool, val u: unit) derives equals, compare, toString {}
+class E(A(int, P), B) derives equals, compare, toString {}
+class Box<T: Comparable<T>>(val v: T) derives compare {}
+class NoMethods(val v: int) {}
+class Bad(val s: Str, val n: NoMethods) derives equals, compare, toString {}
+class Test {
+  function <T: Comparable<T>> max(a: T, b: T): T = if a.compare(b) > 0 { a } else { b }
+  function good(p: P, e: E): Str = {
+    let _: bool = p.equals(p) && e.equals(E.B());
+    let _: int = Test.max(p, p).compare(p) + Test.max(e, e).compare(e);
+    let _: int = Test.max(Box.init(p), Box.init(p)).compare(Box.init(p));
+    p.toString() :: "${e}"
+  }
+  function named(n: Named): int = Test.max(n, n).compare(n)
+}
+class Named(val name: Str, val rank: int) derives equals, compare {}
+"#;
+    assert_module_errors(
+      vec![("std.interfaces", interfaces), ("derives", source)],
+      r#"Error -------------------------------- derives.sam:7:49-7:55
+
+Field `n: NoMethods` does not support derive `equals`.
+
+  7| class Bad(val s: Str, val n: NoMethods) derives equals, compare, toString {}
+                                                     ^^^^^^
+
+
+Error -------------------------------- derives.sam:7:57-7:64
+
+Field `n: NoMethods` does not support derive `compare`.
+
+  7| class Bad(val s: Str, val n: NoMethods) derives equals, compare, toString {}
+                                                             ^^^^^^^
+
+
+Error -------------------------------- derives.sam:7:66-7:74
+
+Field `n: NoMethods` does not support derive `toString`.
+
+  7| class Bad(val s: Str, val n: NoMethods) derives equals, compare, toString {}
+                                                                      ^^^^^^^^
+
+
+Found 3 errors."#,
+    );
+
+    let source = r#"
+class P(val x: int, val s: Str) derives compare {}
+class Test {
+  function f(p: P): int = p.compare(p)
+}
+"#;
+    assert_module_errors(vec![("std.interfaces", interfaces), ("derives", source)], "");
+
+    let source = r#"
+class Fns(val i: int, val f: (int) -> int) derives equals, compare, toString {}
+class Vecs(val v: Vec<int>) derives toString {}
+class Generic<T>(val v: T) derives compare {}
+class Variants(A(int), B((int) -> int)) derives equals {}
+"#;
+    assert_module_errors(
+      vec![("std.interfaces", interfaces), ("derives", source)],
+      r#"Error -------------------------------- derives.sam:2:52-2:58
+
+Field `f: (int) -> int` does not support derive `equals`.
+
+  2| class Fns(val i: int, val f: (int) -> int) derives equals, compare, toString {}
+                                                        ^^^^^^
+
+
+Error -------------------------------- derives.sam:2:60-2:67
+
+Field `f: (int) -> int` does not support derive `compare`.
+
+  2| class Fns(val i: int, val f: (int) -> int) derives equals, compare, toString {}
+                                                                ^^^^^^^
+
+
+Error -------------------------------- derives.sam:2:69-2:77
+
+Field `f: (int) -> int` does not support derive `toString`.
+
+  2| class Fns(val i: int, val f: (int) -> int) derives equals, compare, toString {}
+                                                                         ^^^^^^^^
+
+
+Error -------------------------------- derives.sam:3:37-3:45
+
+Field `v: Vec<int>` does not support derive `toString`.
+
+  3| class Vecs(val v: Vec<int>) derives toString {}
+                                         ^^^^^^^^
+
+
+Error -------------------------------- derives.sam:4:36-4:43
+
+Field `v: T` does not support derive `compare`.
+
+  4| class Generic<T>(val v: T) derives compare {}
+                                        ^^^^^^^
+
+
+Error -------------------------------- derives.sam:5:49-5:55
+
+Data `(int) -> int` of variant `B` does not support derive `equals`.
+
+  5| class Variants(A(int), B((int) -> int)) derives equals {}
+                                                     ^^^^^^
+
+
+Found 6 errors."#,
+    );
   }
 }
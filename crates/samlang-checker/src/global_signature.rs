@@ -1,8 +1,9 @@
 use super::{
   type_::{
-    EnumVariantDefinitionSignature, FunctionType, GlobalSignature, InterfaceSignature,
-    MemberSignature, ModuleSignature, NominalType, StructItemDefinitionSignature, Type,
-    TypeDefinitionSignature, TypeParameterSignature,
+    ConstSignature, EnumVariantDefinitionSignature, FunctionType, GlobalSignature,
+    InterfaceSignature, MemberSignature, ModuleSignature, NominalType, ParameterSignature,
+    StructItemDefinitionSignature, Type, TypeAliasSignature, TypeDefinitionSignature,
+    TypeParameterSignature,
   },
   type_system,
 };
@@ -11,30 +12,69 @@ use itertools::Itertools;
 use rayon::prelude::*;
 use samlang_ast::{
   Reason,
-  source::{Module, Toplevel, TypeDefinition},
+  source::{Module, Toplevel, TypeDefinition, Visibility, expr},
 };
-use samlang_heap::{ModuleReference, PStr};
+use samlang_heap::{Heap, ModuleReference, PStr};
 use std::{
   collections::{HashMap, HashSet},
   sync::Arc,
 };
 
-pub fn build_module_signature(
+pub fn build_module_signature<T: Clone>(
   module_reference: ModuleReference,
-  module: &Module<()>,
+  module: &Module<T>,
+  heap: &Heap,
 ) -> ModuleSignature {
   let mut interfaces = HashMap::new();
+  let mut type_aliases = HashMap::new();
+  let mut constants = HashMap::new();
   for toplevel in &module.toplevels {
+    if let Toplevel::Const(const_def) = toplevel {
+      let mut dependencies = Vec::new();
+      collect_const_dependencies(&const_def.value, &mut dependencies);
+      constants.insert(
+        const_def.name.name,
+        ConstSignature {
+          visibility: const_def.visibility,
+          type_: Arc::new(Type::from_annotation(&const_def.annotation)),
+          dependencies,
+        },
+      );
+      continue;
+    }
+    if let Toplevel::TypeAlias(alias) = toplevel {
+      type_aliases.insert(
+        alias.name.name,
+        TypeAliasSignature {
+          visibility: alias.visibility,
+          type_parameters: TypeParameterSignature::from_list(alias.type_parameters.as_ref()),
+          type_: Arc::new(Type::from_annotation(&alias.annotation)),
+        },
+      );
+      continue;
+    }
     let is_class = toplevel.is_class();
-    let private = toplevel.is_private();
+    let visibility = toplevel.visibility();
     let name = toplevel.name().name;
     let mut functions = HashMap::new();
     let mut methods = HashMap::new();
+    let defaulted_methods: HashSet<PStr> = match toplevel {
+      Toplevel::Interface(interface) => interface
+        .members
+        .members
+        .iter()
+        .filter(|it| it.decl.is_method && it.default_body.is_some())
+        .map(|it| it.decl.name.name)
+        .collect(),
+      Toplevel::Class(_) | Toplevel::TypeAlias(_) | Toplevel::Const(_) => HashSet::new(),
+    };
     for member in toplevel.members_iter() {
       let type_info = MemberSignature {
-        is_public: member.is_public,
+        visibility: member.visibility,
+        has_default: defaulted_methods.contains(&member.name.name),
         type_parameters: TypeParameterSignature::from_list(member.type_parameters.as_ref()),
         type_: FunctionType::from_function(member),
+        parameters: ParameterSignature::from_function(module_reference, name, member),
       };
       if member.is_method {
         methods.insert(member.name.name, type_info);
@@ -65,7 +105,8 @@ pub fn build_module_signature(
         }) => {
           let type_def_reason = Reason::new(*loc, Some(*loc));
           let ctor_fn = MemberSignature {
-            is_public: true,
+            visibility: Visibility::Public,
+            has_default: false,
             type_parameters: toplevel_tparams_sig.clone(),
             type_: FunctionType {
               reason: type_def_reason,
@@ -75,6 +116,10 @@ pub fn build_module_signature(
                 .collect_vec(),
               return_type: class_type,
             },
+            parameters: fields
+              .iter()
+              .map(|it| ParameterSignature { name: it.name.name, default_value: None })
+              .collect(),
           };
           functions.insert(
             // init string should be pre-allocated during builtin_cx init
@@ -101,7 +146,8 @@ pub fn build_module_signature(
           let type_def_reason = Reason::new(*loc, Some(*loc));
           for variant in variants {
             let ctor_fn = MemberSignature {
-              is_public: true,
+              visibility: Visibility::Public,
+              has_default: false,
               type_parameters: toplevel_tparams_sig.clone(),
               type_: FunctionType {
                 reason: type_def_reason,
@@ -113,6 +159,7 @@ pub fn build_module_signature(
                   .collect(),
                 return_type: class_type.dupe(),
               },
+              parameters: Vec::new(),
             };
             functions.insert(variant.name.name, ctor_fn);
           }
@@ -139,7 +186,7 @@ pub fn build_module_signature(
     interfaces.insert(
       name,
       InterfaceSignature {
-        private,
+        visibility,
         type_definition,
         functions,
         methods,
@@ -153,7 +200,40 @@ pub fn build_module_signature(
       },
     );
   }
-  ModuleSignature { interfaces }
+  let package = match module_reference.get_parts(heap).split_last() {
+    Some((_, package)) => package.to_vec(),
+    None => Vec::new(),
+  };
+  ModuleSignature { package, interfaces, type_aliases, constants }
+}
+
+fn collect_const_dependencies<T: Clone>(
+  expression: &expr::E<T>,
+  collector: &mut Vec<(ModuleReference, PStr)>,
+) {
+  match expression {
+    expr::E::ClassId(_, module_reference, id) => collector.push((*module_reference, id.name)),
+    expr::E::Tuple(_, expressions) => {
+      for e in &expressions.expressions {
+        collect_const_dependencies(e, collector);
+      }
+    }
+    expr::E::FieldAccess(e) => collect_const_dependencies(&e.object, collector),
+    expr::E::MethodAccess(e) => collect_const_dependencies(&e.object, collector),
+    expr::E::Unary(e) => collect_const_dependencies(&e.argument, collector),
+    expr::E::Call(e) => {
+      collect_const_dependencies(&e.callee, collector);
+      for e in &e.arguments.expressions {
+        collect_const_dependencies(e, collector);
+      }
+    }
+    expr::E::Binary(e) => {
+      collect_const_dependencies(&e.e1, collector);
+      collect_const_dependencies(&e.e2, collector);
+    }
+    // Other expressions are not allowed in constants, and are reported by the checker.
+    _ => {}
+  }
 }
 
 #[cfg(test)]
@@ -162,24 +242,316 @@ pub(super) fn create_std_signatures_for_test(
 ) -> HashMap<ModuleReference, ModuleSignature> {
   samlang_parser::builtin_parsed_std_sources_for_tests(heap)
     .into_iter()
-    .map(|(mod_ref, parsed)| (mod_ref, build_module_signature(mod_ref, &parsed)))
+    .map(|(mod_ref, parsed)| (mod_ref, build_module_signature(mod_ref, &parsed, heap)))
     .collect()
 }
 
-pub fn build_global_signature(
-  sources: &HashMap<ModuleReference, Module<()>>,
+pub fn build_global_signature<T: Clone + Sync>(
+  sources: &HashMap<ModuleReference, Module<T>>,
   builtin_module_types: ModuleSignature,
+  heap: &Heap,
 ) -> GlobalSignature {
   let mut global_cx: HashMap<_, _> = sources
     .par_iter()
     .map(|(module_reference, module)| {
-      (*module_reference, build_module_signature(*module_reference, module))
+      (*module_reference, build_module_signature(*module_reference, module, heap))
     })
     .collect();
   global_cx.insert(ModuleReference::ROOT, builtin_module_types);
+  let module_references = sources.keys().copied().collect_vec();
+  expand_type_aliases_in_module_signatures(&mut global_cx, &module_references);
   global_cx
 }
 
+/// Rebuilds the signatures of the given modules in place.
+/// Signatures of other modules are assumed to be unaffected by the change.
+pub fn update_global_signature(
+  global_cx: &mut GlobalSignature,
+  sources: &HashMap<ModuleReference, Module<()>>,
+  module_references: &HashSet<ModuleReference>,
+  heap: &Heap,
+) {
+  let mut rebuilt = Vec::new();
+  for module_reference in module_references {
+    if let Some(module) = sources.get(module_reference) {
+      global_cx.insert(*module_reference, build_module_signature(*module_reference, module, heap));
+      rebuilt.push(*module_reference);
+    }
+  }
+  expand_type_aliases_in_module_signatures(global_cx, &rebuilt);
+}
+
+fn expand_type_aliases_in_module_signatures(
+  global_cx: &mut GlobalSignature,
+  module_references: &[ModuleReference],
+) {
+  let expanded = module_references
+    .par_iter()
+    .filter_map(|module_reference| {
+      let module_cx = global_cx.get(module_reference)?;
+      let interfaces = module_cx
+        .interfaces
+        .iter()
+        .map(|(name, interface)| (*name, expand_type_aliases_in_interface(global_cx, interface)))
+        .collect::<HashMap<_, _>>();
+      let constants = module_cx
+        .constants
+        .iter()
+        .map(|(name, c)| {
+          (
+            *name,
+            ConstSignature {
+              visibility: c.visibility,
+              type_: expand_type_aliases(global_cx, &c.type_),
+              dependencies: c.dependencies.clone(),
+            },
+          )
+        })
+        .collect::<HashMap<_, _>>();
+      Some((*module_reference, interfaces, constants))
+    })
+    .collect::<Vec<_>>();
+  for (module_reference, interfaces, constants) in expanded {
+    let module_cx = global_cx.get_mut(&module_reference).unwrap();
+    module_cx.interfaces = interfaces;
+    module_cx.constants = constants;
+  }
+}
+
+fn expand_type_aliases_in_members(
+  global_cx: &GlobalSignature,
+  members: &HashMap<PStr, MemberSignature>,
+) -> HashMap<PStr, MemberSignature> {
+  members
+    .iter()
+    .map(|(name, member)| {
+      (
+        *name,
+        MemberSignature {
+          visibility: member.visibility,
+          has_default: member.has_default,
+          type_parameters: member.type_parameters.clone(),
+          type_: expand_type_aliases_in_fn_type(global_cx, &member.type_),
+          parameters: member.parameters.clone(),
+        },
+      )
+    })
+    .collect()
+}
+
+fn expand_type_aliases_in_interface(
+  global_cx: &GlobalSignature,
+  interface: &InterfaceSignature,
+) -> InterfaceSignature {
+  let type_definition = interface.type_definition.as_ref().map(|type_def| match type_def {
+    TypeDefinitionSignature::Struct(items) => TypeDefinitionSignature::Struct(
+      items
+        .iter()
+        .map(|item| StructItemDefinitionSignature {
+          name: item.name,
+          type_: expand_type_aliases(global_cx, &item.type_),
+          is_public: item.is_public,
+        })
+        .collect(),
+    ),
+    TypeDefinitionSignature::Enum(variants) => TypeDefinitionSignature::Enum(
+      variants
+        .iter()
+        .map(|variant| EnumVariantDefinitionSignature {
+          name: variant.name,
+          types: variant.types.iter().map(|t| expand_type_aliases(global_cx, t)).collect(),
+        })
+        .collect(),
+    ),
+  });
+  InterfaceSignature {
+    visibility: interface.visibility,
+    type_definition,
+    functions: expand_type_aliases_in_members(global_cx, &interface.functions),
+    methods: expand_type_aliases_in_members(global_cx, &interface.methods),
+    type_parameters: interface.type_parameters.clone(),
+    super_types: interface.super_types.clone(),
+  }
+}
+
+pub(super) fn resolve_type_alias_cx(
+  global_cx: &GlobalSignature,
+  module_reference: ModuleReference,
+  alias_name: PStr,
+) -> Option<&TypeAliasSignature> {
+  global_cx.get(&module_reference)?.type_aliases.get(&alias_name)
+}
+
+/// Expands all type aliases within the type.
+/// Aliases that are cyclic or applied with the wrong number of type arguments are kept as is.
+pub fn expand_type_aliases(global_cx: &GlobalSignature, type_: &Type) -> Arc<Type> {
+  expand_type_aliases_recursive(global_cx, type_, &mut Vec::new())
+}
+
+pub(super) fn expand_type_aliases_in_fn_type(
+  global_cx: &GlobalSignature,
+  fn_type: &FunctionType,
+) -> FunctionType {
+  expand_type_aliases_in_fn_type_recursive(global_cx, fn_type, &mut Vec::new())
+}
+
+fn expand_type_aliases_in_fn_type_recursive(
+  global_cx: &GlobalSignature,
+  fn_type: &FunctionType,
+  expanding: &mut Vec<(ModuleReference, PStr)>,
+) -> FunctionType {
+  FunctionType {
+    reason: fn_type.reason,
+    argument_types: fn_type
+      .argument_types
+      .iter()
+      .map(|t| expand_type_aliases_recursive(global_cx, t, expanding))
+      .collect(),
+    return_type: expand_type_aliases_recursive(global_cx, &fn_type.return_type, expanding),
+  }
+}
+
+fn expand_type_aliases_recursive(
+  global_cx: &GlobalSignature,
+  type_: &Type,
+  expanding: &mut Vec<(ModuleReference, PStr)>,
+) -> Arc<Type> {
+  let nominal_type = match type_ {
+    Type::Any(_, _) | Type::Primitive(_, _) | Type::Generic(_, _) => {
+      return Arc::new(type_.clone());
+    }
+    Type::Fn(f) => {
+      return Arc::new(Type::Fn(expand_type_aliases_in_fn_type_recursive(global_cx, f, expanding)));
+    }
+    Type::Tuple(reason, element_types) => {
+      return Arc::new(Type::Tuple(
+        *reason,
+        element_types
+          .iter()
+          .map(|t| expand_type_aliases_recursive(global_cx, t, expanding))
+          .collect(),
+      ));
+    }
+    Type::Nominal(nominal_type) => nominal_type,
+  };
+  let type_arguments = nominal_type
+    .type_arguments
+    .iter()
+    .map(|t| expand_type_aliases_recursive(global_cx, t, expanding))
+    .collect_vec();
+  let key = (nominal_type.module_reference, nominal_type.id);
+  if let Some(alias) = resolve_type_alias_cx(global_cx, key.0, key.1)
+    && alias.type_parameters.len() == type_arguments.len()
+    && !expanding.contains(&key)
+  {
+    let subst_mapping =
+      alias.type_parameters.iter().map(|it| it.name).zip(type_arguments).collect::<HashMap<_, _>>();
+    expanding.push(key);
+    let expanded = expand_type_aliases_recursive(
+      global_cx,
+      &type_system::subst_type(&alias.type_, &subst_mapping),
+      expanding,
+    );
+    expanding.pop();
+    return Arc::new(expanded.reposition(nominal_type.reason.use_loc));
+  }
+  Arc::new(Type::Nominal(NominalType {
+    reason: nominal_type.reason,
+    is_class_statics: nominal_type.is_class_statics,
+    module_reference: nominal_type.module_reference,
+    id: nominal_type.id,
+    type_arguments,
+  }))
+}
+
+fn collect_referenced_type_aliases(
+  global_cx: &GlobalSignature,
+  type_: &Type,
+  collector: &mut Vec<(ModuleReference, PStr)>,
+) {
+  match type_ {
+    Type::Any(_, _) | Type::Primitive(_, _) | Type::Generic(_, _) => {}
+    Type::Fn(f) => {
+      for t in &f.argument_types {
+        collect_referenced_type_aliases(global_cx, t, collector);
+      }
+      collect_referenced_type_aliases(global_cx, &f.return_type, collector);
+    }
+    Type::Tuple(_, element_types) => {
+      for t in element_types {
+        collect_referenced_type_aliases(global_cx, t, collector);
+      }
+    }
+    Type::Nominal(nominal_type) => {
+      if resolve_type_alias_cx(global_cx, nominal_type.module_reference, nominal_type.id).is_some()
+      {
+        collector.push((nominal_type.module_reference, nominal_type.id));
+      }
+      for t in &nominal_type.type_arguments {
+        collect_referenced_type_aliases(global_cx, t, collector);
+      }
+    }
+  }
+}
+
+/// Returns whether the type alias refers back to itself, either directly or through other aliases.
+pub(super) fn is_cyclic_type_alias(
+  global_cx: &GlobalSignature,
+  module_reference: ModuleReference,
+  alias_name: PStr,
+) -> bool {
+  let start = (module_reference, alias_name);
+  let mut visited = HashSet::new();
+  let mut stack = vec![start];
+  while let Some((mod_ref, name)) = stack.pop() {
+    if let Some(alias) = resolve_type_alias_cx(global_cx, mod_ref, name) {
+      let mut referenced = Vec::new();
+      collect_referenced_type_aliases(global_cx, &alias.type_, &mut referenced);
+      for key in referenced {
+        if key == start {
+          return true;
+        }
+        if visited.insert(key) {
+          stack.push(key);
+        }
+      }
+    }
+  }
+  false
+}
+
+pub(super) fn resolve_const_cx(
+  global_cx: &GlobalSignature,
+  module_reference: ModuleReference,
+  const_name: PStr,
+) -> Option<&ConstSignature> {
+  global_cx.get(&module_reference)?.constants.get(&const_name)
+}
+
+/// Returns whether the constant depends on itself, either directly or through other constants.
+pub(super) fn is_cyclic_const(
+  global_cx: &GlobalSignature,
+  module_reference: ModuleReference,
+  const_name: PStr,
+) -> bool {
+  let start = (module_reference, const_name);
+  let mut visited = HashSet::new();
+  let mut stack = vec![start];
+  while let Some((mod_ref, name)) = stack.pop() {
+    if let Some(const_cx) = resolve_const_cx(global_cx, mod_ref, name) {
+      for key in &const_cx.dependencies {
+        if *key == start {
+          return true;
+        }
+        if visited.insert(*key) {
+          stack.push(*key);
+        }
+      }
+    }
+  }
+  false
+}
+
 pub(super) fn resolve_interface_cx(
   global_cx: &GlobalSignature,
   module_reference: ModuleReference,
@@ -188,6 +560,78 @@ pub(super) fn resolve_interface_cx(
   global_cx.get(&module_reference)?.interfaces.get(&toplevel_name)
 }
 
+fn is_in_same_package(
+  global_cx: &GlobalSignature,
+  current_module_reference: ModuleReference,
+  declaring_module_reference: ModuleReference,
+) -> bool {
+  if current_module_reference == declaring_module_reference {
+    return true;
+  }
+  match (global_cx.get(&current_module_reference), global_cx.get(&declaring_module_reference)) {
+    (Some(current), Some(declaring)) => current.package.starts_with(&declaring.package),
+    _ => false,
+  }
+}
+
+fn is_transitive_sub_type(
+  global_cx: &GlobalSignature,
+  sub_type: (ModuleReference, PStr),
+  super_type: (ModuleReference, PStr),
+) -> bool {
+  let mut lookup_candidates = vec![sub_type];
+  let mut visited = HashSet::new();
+  while let Some(candidate) = lookup_candidates.pop() {
+    if candidate == super_type {
+      return true;
+    }
+    if !visited.insert(candidate) {
+      continue;
+    }
+    if let Some(interface_cx) = resolve_interface_cx(global_cx, candidate.0, candidate.1) {
+      lookup_candidates.extend(interface_cx.super_types.iter().map(|t| (t.module_reference, t.id)));
+    }
+  }
+  false
+}
+
+/// Returns whether a toplevel with the given visibility in the declaring module can be referenced
+/// from the current module.
+pub fn is_toplevel_visible(
+  global_cx: &GlobalSignature,
+  current_module_reference: ModuleReference,
+  declaring_module_reference: ModuleReference,
+  visibility: Visibility,
+) -> bool {
+  match visibility {
+    Visibility::Private | Visibility::Protected => {
+      current_module_reference == declaring_module_reference
+    }
+    Visibility::Internal => {
+      is_in_same_package(global_cx, current_module_reference, declaring_module_reference)
+    }
+    Visibility::Public => true,
+  }
+}
+
+/// Returns whether a member with the given visibility in the declaring toplevel can be accessed
+/// from the current toplevel.
+pub fn is_member_visible(
+  global_cx: &GlobalSignature,
+  current_toplevel: (ModuleReference, PStr),
+  declaring_toplevel: (ModuleReference, PStr),
+  visibility: Visibility,
+) -> bool {
+  match visibility {
+    Visibility::Private => current_toplevel == declaring_toplevel,
+    Visibility::Protected => {
+      is_transitive_sub_type(global_cx, current_toplevel, declaring_toplevel)
+    }
+    Visibility::Internal => is_in_same_package(global_cx, current_toplevel.0, declaring_toplevel.0),
+    Visibility::Public => true,
+  }
+}
+
 pub(super) struct SuperTypesResolutionResult {
   pub(super) types: Vec<NominalType>,
   pub(super) is_cyclic: bool,
@@ -212,6 +656,7 @@ pub(super) fn resolve_all_member_names(
   method: bool,
 ) -> HashSet<PStr> {
   let mut collector = HashSet::new();
+  let mut defaulted = HashSet::new();
   let mut lookup_candidates =
     interface_types.iter().map(|t| (t.module_reference, t.id)).collect_vec();
   let mut visited = HashSet::new();
@@ -223,15 +668,21 @@ pub(super) fn resolve_all_member_names(
         continue;
       }
       if let Some(interface_cx) = resolve_interface_cx(global_cx, mod_ref, toplevel_name) {
-        for n in if method { interface_cx.methods.keys() } else { interface_cx.functions.keys() } {
-          collector.insert(*n);
+        for (n, info) in if method { &interface_cx.methods } else { &interface_cx.functions }.iter()
+        {
+          if info.has_default {
+            defaulted.insert(*n);
+          } else {
+            collector.insert(*n);
+          }
         }
         for super_type in interface_cx.super_types.iter() {
           lookup_candidates.push((super_type.module_reference, super_type.id));
         }
       }
     } else {
-      return collector;
+      // Members with a default implementation in any super interface are inherited.
+      return collector.difference(&defaulted).copied().collect();
     }
   }
 }
@@ -350,7 +801,8 @@ fn resolve_method_signature_recursive(
     }
     if let Some(info) = interface_cx.methods.get(&method_name) {
       collector.push(MemberSignature {
-        is_public: info.is_public,
+        visibility: info.visibility,
+        has_default: info.has_default,
         type_parameters: info
           .type_parameters
           .iter()
@@ -361,6 +813,7 @@ fn resolve_method_signature_recursive(
           })
           .collect(),
         type_: type_system::subst_fn_type(&info.type_, &subst_mapping),
+        parameters: info.parameters.clone(),
       });
     }
     for super_type in &interface_cx.super_types {
@@ -415,14 +868,99 @@ pub(super) fn resolve_all_method_signatures(
   collector
 }
 
+/// Resolves the default interface methods that a class inherits without overriding them, paired
+/// with the interface type providing each default and the method signature instantiated for the
+/// class.
+pub fn resolve_inherited_default_methods(
+  global_cx: &GlobalSignature,
+  class_type: &NominalType,
+) -> Vec<(PStr, NominalType, MemberSignature)> {
+  let Some(class_cx) = resolve_interface_cx(global_cx, class_type.module_reference, class_type.id)
+  else {
+    return Vec::new();
+  };
+  let mut resolved_names: HashSet<PStr> = class_cx.methods.keys().copied().collect();
+  let mut collector = Vec::new();
+  // Transitive super types are collected in post-order, so nearer interfaces are visited first.
+  for super_type in resolve_all_transitive_super_types(global_cx, class_type).types.iter().rev() {
+    let Some(interface_cx) =
+      resolve_interface_cx(global_cx, super_type.module_reference, super_type.id)
+    else {
+      continue;
+    };
+    for (name, info) in interface_cx.methods.iter().sorted_by_key(|(name, _)| **name) {
+      if info.has_default && resolved_names.insert(*name) {
+        let signature = resolve_method_signature(global_cx, super_type, *name).swap_remove(0);
+        collector.push((*name, super_type.clone(), signature));
+      }
+    }
+  }
+  collector
+}
+
+/// Resolves all methods of the given interface type, sorted by name and instantiated for the type.
+/// Returns None when the type is not an interface.
+fn resolve_all_interface_methods(
+  global_cx: &GlobalSignature,
+  interface_type: &NominalType,
+) -> Option<Vec<(PStr, MemberSignature)>> {
+  let interface_cx =
+    resolve_interface_cx(global_cx, interface_type.module_reference, interface_type.id)?;
+  if interface_cx.type_definition.is_some() {
+    return None;
+  }
+  let mut names: HashSet<PStr> = interface_cx.methods.keys().copied().collect();
+  for super_type in resolve_all_transitive_super_types(global_cx, interface_type).types {
+    if let Some(super_cx) =
+      resolve_interface_cx(global_cx, super_type.module_reference, super_type.id)
+    {
+      names.extend(super_cx.methods.keys().copied());
+    }
+  }
+  Some(
+    names
+      .into_iter()
+      .sorted()
+      .map(|name| (name, resolve_method_signature(global_cx, interface_type, name).swap_remove(0)))
+      .collect(),
+  )
+}
+
+/// Resolves the methods callable on a value of the given interface type, sorted by name and
+/// instantiated for the type. Returns None when the type is not an interface or when one of the
+/// methods is generic, since generic methods cannot be dispatched through a vtable.
+pub fn resolve_interface_object_methods(
+  global_cx: &GlobalSignature,
+  interface_type: &NominalType,
+) -> Option<Vec<(PStr, MemberSignature)>> {
+  let methods = resolve_all_interface_methods(global_cx, interface_type)?;
+  if methods.iter().any(|(_, signature)| !signature.type_parameters.is_empty()) {
+    return None;
+  }
+  Some(methods)
+}
+
+/// Finds the first generic method of the given interface type, which prevents the interface from
+/// being used as a value type.
+pub(super) fn find_generic_interface_method(
+  global_cx: &GlobalSignature,
+  interface_type: &NominalType,
+) -> Option<PStr> {
+  resolve_all_interface_methods(global_cx, interface_type)?
+    .into_iter()
+    .find(|(_, signature)| !signature.type_parameters.is_empty())
+    .map(|(name, _)| name)
+}
+
 #[cfg(test)]
 mod tests {
   use super::super::type_::{
-    GlobalSignature, NominalType, create_builtin_module_signature, test_type_builder,
+    GlobalSignature, ISourceType, NominalType, create_builtin_module_signature, test_type_builder,
   };
   use super::{
-    resolve_all_member_names, resolve_all_method_signatures, resolve_all_transitive_super_types,
-    resolve_function_signature, resolve_method_signature,
+    find_generic_interface_method, resolve_all_member_names, resolve_all_method_signatures,
+    resolve_all_transitive_super_types, resolve_function_signature,
+    resolve_inherited_default_methods, resolve_interface_object_methods, resolve_method_signature,
   };
   use itertools::Itertools;
   use pretty_assertions::assert_eq;
@@ -450,6 +988,10 @@ class Foo2(A(Str), B(int)): Bar {
 }
 
 interface Hiya {}
+
+private type Pair<T> = (T, Foo2) -> Foo1<T>;
+
+const Answer: Foo1<Str> = Foo1.init(1, "");
 "#;
     let module = samlang_parser::parse_source_module_from_text(
       source_code,
@@ -459,8 +1001,11 @@ interface Hiya {}
     );
     assert_eq!("", error_set.pretty_print_error_messages_no_frame_for_test(heap));
     let builtin_cx = create_builtin_module_signature();
-    let global_cx =
-      super::build_global_signature(&HashMap::from([(ModuleReference::DUMMY, module)]), builtin_cx);
+    let global_cx = super::build_global_signature(
+      &HashMap::from([(ModuleReference::DUMMY, module)]),
+      builtin_cx,
+      heap,
+    );
     assert_eq!(2, global_cx.len());
     let module_cx = global_cx.get(&ModuleReference::DUMMY).unwrap();
     assert_eq!(
@@ -481,7 +1026,13 @@ methods:
 foo2: public <T>(int) -> int
 Hiya: interface  : []
 functions:
-methods:"#,
+methods:
+
+type aliases:
+Pair: private type<T> = (T, Foo2) -> Foo1<T>
+
+constants:
+Answer: const Foo1<Str>"#,
       module_cx.to_string(heap)
     );
   }
@@ -529,7 +1080,11 @@ interface UsingConflictingExtends : ConflictExtends1, ConflictExtends2 {}
     );
     assert_eq!("", error_set.pretty_print_error_messages_no_frame_for_test(heap));
     let builtin_cx = create_builtin_module_signature();
-    super::build_global_signature(&HashMap::from([(ModuleReference::DUMMY, module)]), builtin_cx)
+    super::build_global_signature(
+      &HashMap::from([(ModuleReference::DUMMY, module)]),
+      builtin_cx,
+      heap,
+    )
   }
 
   #[test]
@@ -834,4 +1389,146 @@ public () -> bool"#
       .join("\n")
     );
   }
+
+  #[test]
+  fn default_method_resolution_tests() {
+    let heap = &mut Heap::new();
+    let mut error_set = ErrorSet::new();
+    let source_code = r#"
+interface IBase<A> {
+  method m1(a: A): int
+  method m2(a: A): A = a
+}
+interface ILevel1 : IBase<int> {
+  method m3(): int = 1
+  method m4(): int = 2
+}
+class Impl : ILevel1 {
+  method m1(a: int): int = a
+  method m4(): int = 3
+}
+"#;
+    let module = samlang_parser::parse_source_module_from_text(
+      source_code,
+      ModuleReference::DUMMY,
+      heap,
+      &mut error_set,
+    );
+    assert_eq!("", error_set.pretty_print_error_messages_no_frame_for_test(heap));
+    let global_cx = super::build_global_signature(
+      &HashMap::from([(ModuleReference::DUMMY, module)]),
+      create_builtin_module_signature(),
+      heap,
+    );
+
+    assert_eq!(
+      vec!["m1"],
+      resolve_all_member_names(
+        &global_cx,
+        &[NominalType {
+          reason: Reason::dummy(),
+          is_class_statics: false,
+          module_reference: ModuleReference::DUMMY,
+          id: heap.alloc_str_for_test("ILevel1"),
+          type_arguments: Vec::new(),
+        }],
+        true,
+      )
+      .into_iter()
+      .map(|p| p.as_str(heap).to_string())
+      .collect_vec()
+    );
+    assert_eq!(
+      vec!["m3 from ILevel1: public () -> int", "m2 from IBase<int>: public (int) -> int"],
+      resolve_inherited_default_methods(
+        &global_cx,
+        &NominalType {
+          reason: Reason::dummy(),
+          is_class_statics: false,
+          module_reference: ModuleReference::DUMMY,
+          id: heap.alloc_str_for_test("Impl"),
+          type_arguments: Vec::new(),
+        },
+      )
+      .iter()
+      .map(|(name, interface_type, signature)| format!(
+        "{} from {}: {}",
+        name.as_str(heap),
+        interface_type.pretty_print(heap),
+        signature.to_string(heap)
+      ))
+      .collect_vec()
+    );
+    assert!(
+      resolve_inherited_default_methods(
+        &global_cx,
+        &NominalType {
+          reason: Reason::dummy(),
+          is_class_statics: false,
+          module_reference: ModuleReference::DUMMY,
+          id: heap.alloc_str_for_test("NotExist"),
+          type_arguments: Vec::new(),
+        },
+      )
+      .is_empty()
+    );
+  }
+
+  #[test]
+  fn interface_object_methods_resolution_tests() {
+    let heap = &mut Heap::new();
+    let mut error_set = ErrorSet::new();
+    let source_code = r#"
+interface IBase<A> {
+  method m1(a: A): int
+  method m2(a: A): A = a
+}
+interface ILevel1 : IBase<int> {
+  method m3(): int
+}
+interface IGeneric : ILevel1 {
+  method <T> m4(t: T): T
+}
+class Impl : ILevel1 {
+  method m1(a: int): int = a
+  method m3(): int = 3
+}
+"#;
+    let module = samlang_parser::parse_source_module_from_text(
+      source_code,
+      ModuleReference::DUMMY,
+      heap,
+      &mut error_set,
+    );
+    assert_eq!("", error_set.pretty_print_error_messages_no_frame_for_test(heap));
+    let global_cx = super::build_global_signature(
+      &HashMap::from([(ModuleReference::DUMMY, module)]),
+      create_builtin_module_signature(),
+      heap,
+    );
+    let mut nominal_type = |name: &'static str| NominalType {
+      reason: Reason::dummy(),
+      is_class_statics: false,
+      module_reference: ModuleReference::DUMMY,
+      id: heap.alloc_str_for_test(name),
+      type_arguments: Vec::new(),
+    };
+    let level1 = nominal_type("ILevel1");
+    let generic = nominal_type("IGeneric");
+    let class = nominal_type("Impl");
+
+    assert_eq!(
+      vec!["m1: public (int) -> int", "m2: public (int) -> int", "m3: public () -> int"],
+      resolve_interface_object_methods(&global_cx, &level1)
+        .unwrap()
+        .iter()
+        .map(|(name, signature)| format!("{}: {}", name.as_str(heap), signature.to_string(heap)))
+        .collect_vec()
+    );
+    assert!(resolve_interface_object_methods(&global_cx, &generic).is_none());
+    assert!(resolve_interface_object_methods(&global_cx, &class).is_none());
+    assert_eq!(None, find_generic_interface_method(&global_cx, &level1));
+    assert_eq!("m4", find_generic_interface_method(&global_cx, &generic).unwrap().as_str(heap));
+    assert_eq!(None, find_generic_interface_method(&global_cx, &class));
+  }
 }
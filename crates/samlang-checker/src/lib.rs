@@ -1,7 +1,7 @@
 use rayon::prelude::*;
 use samlang_ast::source::Module;
 use samlang_errors::ErrorSet;
-use samlang_heap::ModuleReference;
+use samlang_heap::{Heap, ModuleReference};
 use std::{collections::HashMap, sync::Arc};
 
 mod checker_integration_tests;
@@ -23,16 +23,21 @@ mod type_system;
 mod typing_context;
 mod typing_context_tests;
 
-pub use global_signature::build_module_signature;
+pub use global_signature::{
+  build_global_signature, build_module_signature, expand_type_aliases, is_member_visible,
+  is_toplevel_visible, resolve_inherited_default_methods, resolve_interface_object_methods,
+  update_global_signature,
+};
 pub use main_checker::type_check_module;
 pub use ssa_analysis::{SsaAnalysisResult, perform_ssa_analysis_on_module};
 
 pub fn type_check_sources(
   sources: &HashMap<ModuleReference, Module<()>>,
+  heap: &Heap,
   error_set: &mut ErrorSet,
 ) -> (HashMap<ModuleReference, Module<Arc<type_::Type>>>, type_::GlobalSignature) {
   let builtin_cx = type_::create_builtin_module_signature();
-  let global_cx = global_signature::build_global_signature(sources, builtin_cx);
+  let global_cx = global_signature::build_global_signature(sources, builtin_cx, heap);
   let results: Vec<_> = sources
     .par_iter()
     .map(|(module_reference, module)| {
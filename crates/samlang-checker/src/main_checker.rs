@@ -2,26 +2,26 @@ use super::{
   global_signature, pattern_matching,
   ssa_analysis::perform_ssa_analysis_on_module,
   type_::{
-    FunctionType, GlobalSignature, ISourceType, MemberSignature, NominalType, PrimitiveTypeKind,
-    Type, TypeParameterSignature,
+    FunctionType, GlobalSignature, ISourceType, MemberSignature, NominalType, ParameterSignature,
+    PrimitiveTypeKind, StructItemDefinitionSignature, Type, TypeParameterSignature,
   },
   type_system,
-  typing_context::{LocalTypingContext, TypingContext},
+  typing_context::{LocalTypingContext, ReturnTarget, TypingContext},
 };
 use dupe::Dupe;
 use itertools::Itertools;
 use samlang_ast::{
   Description, Location, Reason,
   source::{
-    ClassMemberDeclaration, ClassMemberDefinition, Id, InterfaceDeclarationCommon,
-    InterfaceMembersCommon, Literal, Module, OptionallyAnnotatedId, Toplevel, TypeDefinition,
-    annotation, expr, pattern,
+    ClassMemberDeclaration, ClassMemberDefinition, ConstDefinition, DerivesClause, Id,
+    InterfaceDeclarationCommon, InterfaceMemberDefinition, InterfaceMembersCommon, Literal, Module,
+    OptionallyAnnotatedId, Toplevel, TypeDefinition, Visibility, annotation, expr, pattern,
   },
 };
 use samlang_errors::{ErrorSet, StackableError};
 use samlang_heap::{ModuleReference, PStr};
 use std::{
-  collections::{BTreeMap, BTreeSet, HashMap},
+  collections::{BTreeMap, BTreeSet, HashMap, HashSet},
   ops::Deref,
   sync::Arc,
 };
@@ -64,6 +64,15 @@ mod type_hint {
         _ => MISSING,
       }
     }
+
+    pub(super) fn transform_to_nth_element(&'a self, n: usize) -> Hint<'a> {
+      match &self.0 {
+        Some(Type::Tuple(_, element_types)) if n < element_types.len() => {
+          available(&element_types[n])
+        }
+        _ => MISSING,
+      }
+    }
   }
 
   #[cfg(test)]
@@ -103,11 +112,13 @@ fn arguments_should_be_checked_without_hint(e: &expr::E<()>) -> bool {
     expr::E::Literal(_, _)
     | expr::E::LocalId(_, _)
     | expr::E::ClassId(_, _, _)
-    | expr::E::Tuple(_, _)
+    | expr::E::TemplateString(_)
     | expr::E::FieldAccess(_)
     | expr::E::MethodAccess(_)
     | expr::E::Unary(_)
-    | expr::E::Binary(_) => true,
+    | expr::E::StructUpdate(_) => true,
+    expr::E::Tuple(_, es) => es.expressions.iter().all(arguments_should_be_checked_without_hint),
+    expr::E::Binary(e) => e.operator != expr::BinaryOperator::PIPE,
     expr::E::Call(_) => false,
     expr::E::IfElse(e) => if_else_should_be_checked_without_hint(e),
     expr::E::Match(expr::Match { common: _, matched: _, cases }) => {
@@ -126,10 +137,91 @@ fn arguments_should_be_checked_without_hint(e: &expr::E<()>) -> bool {
       }
       arguments_should_be_checked_without_hint(body)
     }
+    expr::E::Return(_) | expr::E::Break(_) => false,
+    expr::E::While(_) | expr::E::For(_) | expr::E::Assert(_) | expr::E::Try(_) => true,
     expr::E::Block(b) => block_should_be_checked_without_hint(b),
   }
 }
 
+/// An expression that always returns from the enclosing function, breaks out of the enclosing
+/// loop or panics has no useful type on its own, so its type can be decided by the surrounding
+/// context instead.
+fn expression_always_returns(e: &expr::E<()>) -> bool {
+  match e {
+    expr::E::Return(_) | expr::E::Break(_) => true,
+    expr::E::Call(call) => is_panic_call(call),
+    expr::E::IfElse(e) => if_else_always_returns(e),
+    expr::E::Match(e) => e.cases.iter().all(|case| expression_always_returns(&case.body)),
+    expr::E::Block(b) => block_always_returns(b),
+    _ => false,
+  }
+}
+
+fn if_else_always_returns(if_else: &expr::IfElse<()>) -> bool {
+  block_always_returns(&if_else.e1)
+    && match if_else.e2.as_ref() {
+      expr::IfElseOrBlock::IfElse(nested) => if_else_always_returns(nested),
+      expr::IfElseOrBlock::Block(block) => block_always_returns(block),
+    }
+}
+
+fn block_always_returns(block: &expr::Block<()>) -> bool {
+  block.expression.as_ref().is_some_and(|e| expression_always_returns(e))
+}
+
+/// Unlike [block_always_returns], a block also never finishes when one of its statements always
+/// returns, as in `{ return -1; }`. Such a block still has type `unit`, so this is only used where
+/// the block produces no value.
+fn block_never_finishes(block: &expr::Block<()>) -> bool {
+  block.statements.iter().any(|statement| match statement {
+    expr::Statement::Declaration(s) => expression_always_returns(&s.assigned_expression),
+    expr::Statement::Assignment(s) => expression_always_returns(&s.assigned_expression),
+    expr::Statement::Expression(e) => expression_always_returns(e),
+    expr::Statement::Function(_) => false,
+  }) || block_always_returns(block)
+}
+
+fn is_panic_call(call: &expr::Call<()>) -> bool {
+  if let expr::E::FieldAccess(access) = call.callee.as_ref()
+    && let expr::E::ClassId(_, ModuleReference::ROOT, class_name) = access.object.as_ref()
+  {
+    class_name.name == PStr::PROCESS_TYPE && access.field_name.name == PStr::PANIC
+  } else {
+    false
+  }
+}
+
+/// A type argument that the return type hint solves to an interface takes priority over the one
+/// solved from the arguments, so that the arguments are upcast to the interface. The same goes for
+/// a `std.tuples` class that the tuple solved from the arguments converts to.
+fn solve_call_type_constraints(
+  cx: &TypingContext,
+  constraints: &Vec<type_system::TypeConstraint>,
+  type_parameter_signatures: &Vec<TypeParameterSignature>,
+  generic_return_type: &Type,
+  valid_return_type_hint: Option<&Type>,
+) -> HashMap<PStr, Arc<Type>> {
+  let mut substitution =
+    type_system::solve_multiple_type_constrains(constraints, type_parameter_signatures);
+  if let Some(return_hint) = valid_return_type_hint {
+    let hint_substitution = type_system::solve_multiple_type_constrains(
+      &vec![type_system::TypeConstraint {
+        concrete_type: return_hint,
+        generic_type: generic_return_type,
+      }],
+      type_parameter_signatures,
+    );
+    for (name, solved) in hint_substitution {
+      if cx.is_interface_value_type(&solved)
+        || substitution.get(&name).is_some_and(|t| cx.is_tuple_class_conversion(t, &solved))
+      {
+        substitution.insert(name, solved);
+      }
+    }
+  }
+  substitution
+}
+
 fn solve_type_arguments(
   cx: &mut TypingContext,
   function_call_reason: &Reason,
@@ -150,8 +242,13 @@ fn solve_type_arguments(
       generic_type: &generic_function_type.return_type,
     })
   }
-  let mut partially_solved_substitution =
-    type_system::solve_multiple_type_constrains(&constraints, type_parameter_signatures);
+  let mut partially_solved_substitution = solve_call_type_constraints(
+    cx,
+    &constraints,
+    type_parameter_signatures,
+    &generic_function_type.return_type,
+    valid_return_type_hint,
+  );
   for type_parameter in type_parameter_signatures {
     partially_solved_substitution
       .entry(type_parameter.name)
@@ -202,6 +299,9 @@ fn validate_type_arguments(
 }
 
 fn assignability_check(cx: &mut TypingContext, use_loc: Location, lower: &Type, upper: &Type) {
+  if cx.is_implicit_conversion(lower, upper) {
+    return;
+  }
   if let Some(e) = type_system::assignability_check(lower, upper) {
     cx.error_set.report_stackable_error(use_loc, e);
   }
@@ -216,15 +316,23 @@ fn type_check_expression(
     expr::E::Literal(common, literal) => check_literal(common, literal),
     expr::E::LocalId(common, id) => check_local_variable(cx, common, id),
     expr::E::ClassId(common, mod_ref, id) => check_class_id(cx, common, *mod_ref, id),
-    expr::E::Tuple(common, expressions) => check_tuple(cx, common, expressions),
+    expr::E::Tuple(common, expressions) => check_tuple(cx, common, expressions, hint),
+    expr::E::TemplateString(e) => check_template_string(cx, e),
     expr::E::FieldAccess(e) => check_field_access(cx, e, hint),
     expr::E::MethodAccess(_) => panic!("Raw parsed expression does not contain MethodAccess!"),
     expr::E::Unary(e) => check_unary(cx, e),
     expr::E::Call(e) => check_function_call(cx, e, hint),
-    expr::E::Binary(e) => check_binary(cx, e),
+    expr::E::Binary(e) => check_binary(cx, e, hint),
     expr::E::IfElse(e) => expr::E::IfElse(check_if_else(cx, e, hint)),
     expr::E::Match(e) => check_match(cx, e, hint),
     expr::E::Lambda(e) => check_lambda(cx, e, hint),
+    expr::E::Return(e) => check_return(cx, e, hint),
+    expr::E::While(e) => check_while(cx, e),
+    expr::E::For(e) => check_for(cx, e),
+    expr::E::Break(common) => check_break(cx, common, hint),
+    expr::E::Assert(e) => check_assert(cx, e),
+    expr::E::Try(e) => check_try(cx, e),
+    expr::E::StructUpdate(e) => check_struct_update(cx, e, hint),
     expr::E::Block(e) => expr::E::Block(check_block(cx, e, hint)),
   }
 }
@@ -242,8 +350,9 @@ fn check_literal(common: &expr::ExpressionCommon<()>, literal: &Literal) -> expr
   let reason = Reason::new(common.loc, Some(common.loc));
   let type_ = match &literal {
     Literal::Bool(_) => Arc::new(Type::Primitive(reason, PrimitiveTypeKind::Bool)),
-    Literal::Int(_) => Arc::new(Type::Primitive(reason, PrimitiveTypeKind::Int)),
-    Literal::String(_) => Arc::new(Type::Nominal(NominalType {
+    Literal::Int(_, _) => Arc::new(Type::Primitive(reason, PrimitiveTypeKind::Int)),
+    Literal::Float(_) => Arc::new(Type::Primitive(reason, PrimitiveTypeKind::Float)),
+    Literal::String(_, _) => Arc::new(Type::Nominal(NominalType {
       reason,
       is_class_statics: false,
       module_reference: ModuleReference::ROOT,
@@ -254,6 +363,58 @@ fn check_literal(common: &expr::ExpressionCommon<()>, literal: &Literal) -> expr
   expr::E::Literal(common.with_new_type(type_), *literal)
 }
 
+fn is_str_type(type_: &Type) -> bool {
+  matches!(
+    type_,
+    Type::Nominal(NominalType { is_class_statics: false, module_reference, id, .. })
+    if *module_reference == ModuleReference::ROOT && *id == PStr::STR_TYPE
+  )
+}
+
+/// Holes of a template string must be `Str`, `int`, or have a `toString(): Str` method.
+fn is_valid_template_string_hole_type(cx: &TypingContext, type_: &Type, use_loc: Location) -> bool {
+  match type_ {
+    Type::Any(_, _) | Type::Primitive(_, PrimitiveTypeKind::Int) => true,
+    _ if is_str_type(type_) => true,
+    _ => cx
+      .nominal_type_upper_bound(type_)
+      .filter(|nominal_type| !nominal_type.is_class_statics)
+      .and_then(|nominal_type| cx.get_method_type(nominal_type, PStr::TO_STRING, use_loc))
+      .is_some_and(|method| {
+        method.type_parameters.is_empty()
+          && method.type_.argument_types.is_empty()
+          && is_str_type(&method.type_.return_type)
+      }),
+  }
+}
+
+fn check_template_string(
+  cx: &mut TypingContext,
+  expression: &expr::TemplateString<()>,
+) -> expr::E<Arc<Type>> {
+  let mut holes = Vec::with_capacity(expression.holes.len());
+  for hole in &expression.holes {
+    let checked = type_check_expression(cx, hole, type_hint::MISSING);
+    if !is_valid_template_string_hole_type(cx, checked.type_(), checked.loc()) {
+      cx.error_set
+        .report_illegal_template_string_hole(checked.loc(), checked.type_().to_description());
+    }
+    holes.push(checked);
+  }
+  let type_ = Arc::new(Type::Nominal(NominalType {
+    reason: Reason::new(expression.common.loc, Some(expression.common.loc)),
+    is_class_statics: false,
+    module_reference: ModuleReference::ROOT,
+    id: PStr::STR_TYPE,
+    type_arguments: Vec::new(),
+  }));
+  expr::E::TemplateString(expr::TemplateString {
+    common: expression.common.with_new_type(type_),
+    parts: expression.parts.clone(),
+    holes,
+  })
+}
+
 fn check_local_variable(
   cx: &mut TypingContext,
   common: &expr::ExpressionCommon<()>,
@@ -270,7 +431,10 @@ fn check_class_id(
   id: &Id,
 ) -> expr::E<Arc<Type>> {
   let reason = Reason::new(common.loc, Some(common.loc));
-  if cx.class_exists(module_reference, id.name) {
+  if let Some(type_) = cx.get_const_type(module_reference, id.name) {
+    let type_ = Arc::new(type_.reposition(common.loc));
+    expr::E::ClassId(common.with_new_type(type_), module_reference, *id)
+  } else if cx.class_exists(module_reference, id.name) {
     let type_ = Arc::new(Type::Nominal(NominalType {
       reason,
       is_class_statics: true,
@@ -293,39 +457,32 @@ fn check_tuple(
   cx: &mut TypingContext,
   common: &expr::ExpressionCommon<()>,
   expressions: &expr::ParenthesizedExpressionList<()>,
+  hint: type_hint::Hint,
 ) -> expr::E<Arc<Type>> {
-  let mut type_arguments = Vec::with_capacity(expressions.expressions.len());
-  let mut checked_expressions = Vec::with_capacity(expressions.expressions.len());
-  for e in &expressions.expressions {
-    let checked = type_check_expression(cx, e, type_hint::MISSING);
-    type_arguments.push(checked.type_().dupe());
+  let arity = expressions.expressions.len();
+  assert!(arity >= 2, "Invalid tuple length {arity}");
+  // Against a hint like `Pair<A, B>`, the tuple constructs the `std.tuples` class instead.
+  let class_hint = hint
+    .get_valid_hint()
+    .filter(|t| t.std_tuple_class_elements().is_some_and(|elements| elements.len() == arity));
+  let class_elements = class_hint.and_then(Type::std_tuple_class_elements);
+  let mut element_types = Vec::with_capacity(arity);
+  let mut checked_expressions = Vec::with_capacity(arity);
+  for (i, e) in expressions.expressions.iter().enumerate() {
+    let checked = if let Some(class_elements) = class_elements {
+      let checked = type_check_expression(cx, e, type_hint::available(&class_elements[i]));
+      assignability_check(cx, checked.loc(), checked.type_(), &class_elements[i]);
+      checked
+    } else {
+      type_check_expression(cx, e, hint.transform_to_nth_element(i))
+    };
+    element_types.push(checked.type_().dupe());
     checked_expressions.push(checked);
   }
-  let id = match expressions.expressions.len() {
-    2 => PStr::PAIR,
-    3 => PStr::TRIPLE,
-    4 => PStr::TUPLE_4,
-    5 => PStr::TUPLE_5,
-    6 => PStr::TUPLE_6,
-    7 => PStr::TUPLE_7,
-    8 => PStr::TUPLE_8,
-    9 => PStr::TUPLE_9,
-    10 => PStr::TUPLE_10,
-    11 => PStr::TUPLE_11,
-    12 => PStr::TUPLE_12,
-    13 => PStr::TUPLE_13,
-    14 => PStr::TUPLE_14,
-    15 => PStr::TUPLE_15,
-    16 => PStr::TUPLE_16,
-    l => panic!("Invalid tuple length {l}"),
+  let type_ = match class_hint {
+    Some(class_type) => Arc::new(class_type.reposition(common.loc)),
+    None => Arc::new(Type::Tuple(Reason::new(common.loc, None), element_types)),
   };
-  let type_ = Arc::new(Type::Nominal(NominalType {
-    reason: Reason::new(common.loc, None),
-    is_class_statics: false,
-    module_reference: ModuleReference::STD_TUPLES,
-    id,
-    type_arguments,
-  }));
   expr::E::Tuple(
     common.with_new_type(type_),
     expr::ParenthesizedExpressionList {
@@ -377,12 +534,49 @@ fn replace_undecided_tparam_with_unknown_and_update_type(
   }
 }
 
+/// The fields of a structural tuple, named `e0`, `e1`, ... like the classes in `std.tuples`.
+fn tuple_struct_items(element_types: &[Arc<Type>]) -> Vec<StructItemDefinitionSignature> {
+  element_types
+    .iter()
+    .enumerate()
+    .map(|(i, type_)| StructItemDefinitionSignature {
+      name: PStr::create_inline_opt(&format!("e{i}")).unwrap(),
+      type_: type_.dupe(),
+      is_public: true,
+    })
+    .collect()
+}
+
 fn check_member_with_unresolved_tparams(
   cx: &mut TypingContext,
   expression: &expr::FieldAccess<()>,
   hint: type_hint::Hint,
 ) -> (FieldOrMethodAccesss, Vec<TypeParameterSignature>) {
   let checked_expression = type_check_expression(cx, &expression.object, type_hint::MISSING);
+  if let Type::Tuple(_, elements) = checked_expression.type_().as_ref() {
+    let element_order = tuple_struct_items(elements)
+      .into_iter()
+      .position(|item| item.name == expression.field_name.name);
+    let type_ = if let Some(order) = element_order {
+      Arc::new(elements[order].reposition(expression.common.loc))
+    } else {
+      cx.error_set.report_cannot_resolve_member_error(
+        expression.field_name.loc,
+        checked_expression.type_().to_description(),
+        expression.field_name.name,
+      );
+      Arc::new(Type::Any(Reason::new(expression.common.loc, None), false))
+    };
+    let partially_checked_expr = FieldOrMethodAccesss::Field(expr::FieldAccess {
+      common: expression.common.with_new_type(type_),
+      explicit_type_arguments: expression.explicit_type_arguments.clone(),
+      inferred_type_arguments: Vec::new(),
+      object: Box::new(checked_expression),
+      field_name: expression.field_name,
+      field_order: element_order.map(|order| order as i32).unwrap_or(expression.field_order),
+    });
+    return (partially_checked_expr, Vec::new());
+  }
   let obj_type = match cx.nominal_type_upper_bound(checked_expression.type_()) {
     Some(t) => t,
     None => {
@@ -411,7 +605,7 @@ fn check_member_with_unresolved_tparams(
   {
     // This is a valid method. We will now type check it as a method access
     for targ in expression.explicit_type_arguments.iter().flat_map(|it| &it.arguments) {
-      cx.validate_type_instantiation_strictly(&Type::from_annotation(targ))
+      cx.validate_type_instantiation_strictly(&cx.type_from_annotation(targ))
     }
     if let Some(annotation::TypeArguments { arguments: explicit_type_arguments, .. }) =
       &expression.explicit_type_arguments
@@ -419,13 +613,13 @@ fn check_member_with_unresolved_tparams(
       if explicit_type_arguments.len() == method_type_info.type_parameters.len() {
         let mut subst_map = HashMap::new();
         for (tparam, targ) in method_type_info.type_parameters.iter().zip(explicit_type_arguments) {
-          subst_map.insert(tparam.name, Arc::new(Type::from_annotation(targ)));
+          subst_map.insert(tparam.name, cx.type_from_annotation(targ));
         }
         validate_type_arguments(cx, &method_type_info.type_parameters, &subst_map);
         let type_ =
           Arc::new(Type::Fn(type_system::subst_fn_type(&method_type_info.type_, &subst_map)));
         let inferred_type_arguments =
-          explicit_type_arguments.iter().map(|a| Arc::new(Type::from_annotation(a))).collect_vec();
+          explicit_type_arguments.iter().map(|a| cx.type_from_annotation(a)).collect_vec();
         let partially_checked_expr = FieldOrMethodAccesss::Method(expr::MethodAccess {
           common: expression.common.with_new_type(type_),
           explicit_type_arguments: expression.explicit_type_arguments.clone(),
@@ -497,6 +691,7 @@ fn check_member_with_unresolved_tparams(
     });
     (partially_checked_expr, method_type_info.type_parameters.clone())
   } else {
+    let hidden_member = cx.get_hidden_method_visibility(obj_type, expression.field_name.name);
     // Now it should be checked as field access.
     if let Some(explicit_type_arguments) = &expression.explicit_type_arguments {
       let mut error = StackableError::new();
@@ -525,11 +720,15 @@ fn check_member_with_unresolved_tparams(
       });
       (partially_checked_expr, Vec::new())
     } else {
-      cx.error_set.report_cannot_resolve_member_error(
-        expression.field_name.loc,
-        Description::NominalType { name: class_id, type_args: Vec::new() },
-        expression.field_name.name,
-      );
+      if let Some((name, visibility)) = hidden_member {
+        cx.error_set.report_not_visible_error(expression.field_name.loc, name, visibility);
+      } else {
+        cx.error_set.report_cannot_resolve_member_error(
+          expression.field_name.loc,
+          Description::NominalType { name: class_id, type_args: Vec::new() },
+          expression.field_name.name,
+        );
+      }
       let any_type = Arc::new(Type::Any(Reason::new(expression.common.loc, None), false));
       let partially_checked_expr = FieldOrMethodAccesss::Field(expr::FieldAccess {
         common: expression.common.with_new_type(any_type),
@@ -566,14 +765,15 @@ fn check_field_access(
 }
 
 fn check_unary(cx: &mut TypingContext, expression: &expr::Unary<()>) -> expr::E<Arc<Type>> {
+  let argument = Box::new(type_check_expression(cx, &expression.argument, type_hint::MISSING));
   let expected_type = Arc::new(Type::Primitive(
     Reason::new(expression.common.loc, Some(expression.common.loc)),
     match expression.operator {
       expr::UnaryOperator::NOT => PrimitiveTypeKind::Bool,
-      expr::UnaryOperator::NEG => PrimitiveTypeKind::Int,
+      expr::UnaryOperator::NEG if is_float_type(argument.type_()) => PrimitiveTypeKind::Float,
+      expr::UnaryOperator::NEG | expr::UnaryOperator::BNOT => PrimitiveTypeKind::Int,
     },
   ));
-  let argument = Box::new(type_check_expression(cx, &expression.argument, type_hint::MISSING));
   assignability_check(cx, argument.loc(), argument.type_(), &expected_type);
   expr::E::Unary(expr::Unary {
     common: expression.common.with_new_type(expected_type),
@@ -678,9 +878,12 @@ fn check_function_call_implicit_instantiation(
       generic_type: &generic_function_type.return_type,
     })
   }
-  let mut fully_solved_substitution = type_system::solve_multiple_type_constrains(
+  let mut fully_solved_substitution = solve_call_type_constraints(
+    cx,
     &final_phase_arguments_constraints,
     type_parameters,
+    &generic_function_type.return_type,
+    valid_return_type_hint,
   );
   let still_unresolved_type_parameters = type_parameters
     .iter()
@@ -712,6 +915,129 @@ fn check_function_call_implicit_instantiation(
   }
 }
 
+/// Returns the names and default values of the parameters of the function or method that the
+/// callee refers to. It's empty when the callee is an arbitrary function value.
+fn callee_parameters(cx: &TypingContext, callee: &expr::E<Arc<Type>>) -> Vec<ParameterSignature> {
+  let expr::E::MethodAccess(method_access) = callee else {
+    return Vec::new();
+  };
+  cx.nominal_type_upper_bound(method_access.object.type_())
+    .and_then(|nominal_type| {
+      cx.get_method_type(nominal_type, method_access.method_name.name, method_access.common.loc)
+    })
+    .map(|member| member.parameters)
+    .unwrap_or_default()
+}
+
+/// Binds the positional arguments and then the named arguments of a call to parameters.
+/// Returns the parameter index of each argument and the parameters that fall back to their
+/// default values, or None after reporting why the arguments cannot be bound.
+fn bind_call_arguments(
+  cx: &mut TypingContext,
+  expression: &expr::Call<()>,
+  parameters: &[ParameterSignature],
+  parameter_count: usize,
+) -> Option<(Vec<usize>, Vec<usize>)> {
+  let names = &expression.binding.names;
+  let arguments_count = expression.arguments.expressions.len();
+  let positional_count = arguments_count - names.len();
+  let report_arity_error = |cx: &mut TypingContext| {
+    let mut stackable = StackableError::new();
+    stackable.add_fn_param_arity_error(arguments_count, parameter_count);
+    cx.error_set.report_stackable_error(expression.common.loc, stackable);
+  };
+  if positional_count > parameter_count {
+    report_arity_error(cx);
+    return None;
+  }
+  let mut is_bound = vec![false; parameter_count];
+  let mut parameter_indices = (0..positional_count).collect_vec();
+  for bound in is_bound.iter_mut().take(positional_count) {
+    *bound = true;
+  }
+  let mut has_error = false;
+  for name in names {
+    match parameters.iter().position(|parameter| parameter.name == name.name) {
+      Some(index) if is_bound[index] => {
+        cx.error_set.report_duplicate_named_argument_error(name.loc, name.name);
+        has_error = true;
+      }
+      Some(index) => {
+        is_bound[index] = true;
+        parameter_indices.push(index);
+      }
+      None => {
+        cx.error_set.report_unknown_named_argument_error(name.loc, name.name);
+        has_error = true;
+      }
+    }
+  }
+  let mut defaulted_parameters = Vec::new();
+  for (index, bound) in is_bound.into_iter().enumerate() {
+    if bound {
+      continue;
+    }
+    match parameters.get(index) {
+      Some(parameter) if parameter.default_value.is_some() => defaulted_parameters.push(index),
+      _ if names.is_empty() => {
+        report_arity_error(cx);
+        return None;
+      }
+      Some(parameter) => {
+        cx.error_set.report_missing_argument_error(expression.common.loc, parameter.name);
+        has_error = true;
+      }
+      None => {}
+    }
+  }
+  if has_error { None } else { Some((parameter_indices, defaulted_parameters)) }
+}
+
+/// Checks the default value of a parameter in the class where it's written.
+fn check_default_argument(
+  cx: &TypingContext,
+  (module_reference, class_name, value): &(ModuleReference, PStr, expr::E<()>),
+  parameter_type: &Type,
+) -> expr::E<Arc<Type>> {
+  cx.run_in_class_scope((*module_reference, *class_name), value, |cx| {
+    type_check_expression(cx, value, type_hint::available(parameter_type))
+  })
+}
+
+/// The call is checked as well as possible when the callee or its arguments are invalid.
+fn check_function_call_with_invalid_callee(
+  cx: &mut TypingContext,
+  expression: &expr::Call<()>,
+  partially_checked_callee: expr::E<Arc<Type>>,
+  unresolved_tparams: Vec<TypeParameterSignature>,
+) -> expr::E<Arc<Type>> {
+  let loc = expression.common.loc;
+  let type_ = Arc::new(Type::Any(Reason::new(loc, None), false));
+  expr::E::Call(expr::Call {
+    common: expression.common.with_new_type(type_),
+    callee: Box::new(replace_undecided_tparam_with_unknown_and_update_type(
+      cx,
+      partially_checked_callee,
+      unresolved_tparams,
+    )),
+    arguments: expr::ParenthesizedExpressionList {
+      loc: expression.arguments.loc,
+      start_associated_comments: expression.arguments.start_associated_comments,
+      ending_associated_comments: expression.arguments.ending_associated_comments,
+      expressions: expression
+        .arguments
+        .expressions
+        .iter()
+        .map(|e| type_check_expression(cx, e, type_hint::MISSING))
+        .collect(),
+    },
+    binding: expr::ArgumentBinding {
+      names: expression.binding.names.clone(),
+      ..Default::default()
+    },
+  })
+}
+
 fn check_function_call(
   cx: &mut TypingContext,
   expression: &expr::Call<()>,
@@ -729,9 +1055,8 @@ fn check_function_call(
     }
     e => (type_check_expression(cx, e, type_hint::MISSING), Vec::new()),
   };
-  let partially_checked_callee_type = partially_checked_callee.type_().deref();
-  let callee_function_type = match partially_checked_callee_type {
-    Type::Fn(fn_type) => fn_type,
+  let callee_function_type = match partially_checked_callee.type_().deref() {
+    Type::Fn(fn_type) => fn_type.clone(),
     t => {
       if !t.is_any() {
         cx.error_set.report_incompatible_type_kind_error(
@@ -740,58 +1065,56 @@ fn check_function_call(
           Description::GeneralNominalType,
         );
       }
-      let loc = expression.common.loc;
-      let type_ = Arc::new(Type::Any(Reason::new(loc, None), false));
-      return expr::E::Call(expr::Call {
-        common: expression.common.with_new_type(type_),
-        callee: Box::new(replace_undecided_tparam_with_unknown_and_update_type(
-          cx,
-          partially_checked_callee,
-          unresolved_tparams,
-        )),
-        arguments: expr::ParenthesizedExpressionList {
-          loc: expression.arguments.loc,
-          start_associated_comments: expression.arguments.start_associated_comments,
-          ending_associated_comments: expression.arguments.ending_associated_comments,
-          expressions: expression
-            .arguments
-            .expressions
-            .iter()
-            .map(|e| type_check_expression(cx, e, type_hint::MISSING))
-            .collect(),
-        },
-      });
+      return check_function_call_with_invalid_callee(
+        cx,
+        expression,
+        partially_checked_callee,
+        unresolved_tparams,
+      );
     }
   };
-  if callee_function_type.argument_types.len() != expression.arguments.expressions.len() {
-    let mut stackable = StackableError::new();
-    stackable.add_fn_param_arity_error(
-      expression.arguments.expressions.len(),
-      callee_function_type.argument_types.len(),
-    );
-    cx.error_set.report_stackable_error(expression.common.loc, stackable);
-    return expr::E::Call(expr::Call {
-      common: expression
-        .common
-        .with_new_type(Arc::new(Type::Any(Reason::new(expression.common.loc, None), false))),
-      callee: Box::new(replace_undecided_tparam_with_unknown_and_update_type(
+  let parameters = callee_parameters(cx, &partially_checked_callee);
+  let is_positional = expression.binding.names.is_empty()
+    && (expression.arguments.expressions.len() == callee_function_type.argument_types.len()
+      || !parameters.iter().any(|it| it.default_value.is_some()));
+  let (bound_function_type, parameter_indices, defaulted_parameters) = if is_positional {
+    if callee_function_type.argument_types.len() != expression.arguments.expressions.len() {
+      let mut stackable = StackableError::new();
+      stackable.add_fn_param_arity_error(
+        expression.arguments.expressions.len(),
+        callee_function_type.argument_types.len(),
+      );
+      cx.error_set.report_stackable_error(expression.common.loc, stackable);
+      return check_function_call_with_invalid_callee(
         cx,
+        expression,
         partially_checked_callee,
         unresolved_tparams,
-      )),
-      arguments: expr::ParenthesizedExpressionList {
-        loc: expression.arguments.loc,
-        start_associated_comments: expression.arguments.start_associated_comments,
-        ending_associated_comments: expression.arguments.ending_associated_comments,
-        expressions: expression
-          .arguments
-          .expressions
-          .iter()
-          .map(|e| type_check_expression(cx, e, type_hint::MISSING))
-          .collect(),
-      },
-    });
-  }
+      );
+    }
+    (callee_function_type.clone(), Vec::new(), Vec::new())
+  } else {
+    let Some((parameter_indices, defaulted_parameters)) =
+      bind_call_arguments(cx, expression, &parameters, callee_function_type.argument_types.len())
+    else {
+      return check_function_call_with_invalid_callee(
+        cx,
+        expression,
+        partially_checked_callee,
+        unresolved_tparams,
+      );
+    };
+    // Arguments are checked in the order they are written, against the parameters they bind to.
+    let bound_function_type = FunctionType {
+      reason: callee_function_type.reason,
+      argument_types: parameter_indices
+        .iter()
+        .map(|i| callee_function_type.argument_types[*i].dupe())
+        .collect(),
+      return_type: callee_function_type.return_type.dupe(),
+    };
+    (bound_function_type, parameter_indices, defaulted_parameters)
+  };
   let FunctionCallTypeCheckingResult {
     solved_generic_type,
     solved_return_type,
@@ -799,12 +1122,24 @@ fn check_function_call(
     checked_arguments,
   } = check_function_call_implicit_instantiation(
     cx,
-    callee_function_type,
+    &bound_function_type,
     &unresolved_tparams,
     &Reason::new(expression.common.loc, None),
     &expression.arguments.expressions,
     hint.get_valid_hint(),
   );
+  let solved_generic_type = if is_positional {
+    solved_generic_type
+  } else {
+    type_system::subst_fn_type(&callee_function_type, &solved_substitution)
+  };
+  let default_arguments = defaulted_parameters
+    .into_iter()
+    .map(|i| {
+      let default_value = parameters[i].default_value.as_ref().unwrap();
+      (i, check_default_argument(cx, default_value, &solved_generic_type.argument_types[i]))
+    })
+    .collect();
   let fully_resolved_checked_callee =
     mod_type(partially_checked_callee, Arc::new(Type::Fn(solved_generic_type)));
   let callee_with_patched_targs = match fully_resolved_checked_callee {
@@ -838,47 +1173,137 @@ fn check_function_call(
       ending_associated_comments: expression.arguments.ending_associated_comments,
       expressions: checked_arguments,
     },
+    binding: expr::ArgumentBinding {
+      names: expression.binding.names.clone(),
+      parameter_indices,
+      default_arguments,
+    },
   })
 }
 
-fn check_binary(cx: &mut TypingContext, expression: &expr::Binary<()>) -> expr::E<Arc<Type>> {
-  let expected_type = Arc::new(match expression.operator {
-    expr::BinaryOperator::MUL
-    | expr::BinaryOperator::DIV
-    | expr::BinaryOperator::MOD
-    | expr::BinaryOperator::PLUS
-    | expr::BinaryOperator::MINUS => Type::Primitive(
-      Reason::new(expression.common.loc, Some(expression.common.loc)),
-      PrimitiveTypeKind::Int,
-    ),
-    expr::BinaryOperator::LT
-    | expr::BinaryOperator::LE
-    | expr::BinaryOperator::GT
-    | expr::BinaryOperator::GE
-    | expr::BinaryOperator::EQ
-    | expr::BinaryOperator::NE
-    | expr::BinaryOperator::AND
-    | expr::BinaryOperator::OR => Type::Primitive(
-      Reason::new(expression.common.loc, Some(expression.common.loc)),
-      PrimitiveTypeKind::Bool,
-    ),
-    expr::BinaryOperator::CONCAT => Type::Nominal(NominalType {
-      reason: Reason::new(expression.common.loc, Some(expression.common.loc)),
-      is_class_statics: false,
-      module_reference: ModuleReference::ROOT,
-      id: PStr::STR_TYPE,
-      type_arguments: Vec::new(),
-    }),
+/// `e |> f(a)` is checked as `f(e, a)`, so that `e` participates in type argument inference.
+/// `e |> f` is checked as `f(e)`, with `e` checked first to infer lambda parameter types.
+fn check_pipe(
+  cx: &mut TypingContext,
+  expression: &expr::Binary<()>,
+  hint: type_hint::Hint,
+) -> expr::E<Arc<Type>> {
+  if let expr::E::Call(call) = expression.e2.as_ref() {
+    let mut arguments = call.arguments.clone();
+    arguments.expressions.insert(0, expression.e1.as_ref().clone());
+    let desugared = expr::Call {
+      common: call.common.clone(),
+      callee: call.callee.clone(),
+      arguments,
+      binding: call.binding.clone(),
+    };
+    let expr::E::Call(mut checked_call) = check_function_call(cx, &desugared, hint) else {
+      panic!("Function call must be checked into a call.")
+    };
+    let e1 = checked_call.arguments.expressions.remove(0);
+    let type_ = checked_call.common.type_.dupe();
+    return expr::E::Binary(expr::Binary {
+      common: expression.common.with_new_type(type_),
+      operator_preceding_comments: expression.operator_preceding_comments,
+      operator: expression.operator,
+      e1: Box::new(e1),
+      e2: Box::new(expr::E::Call(checked_call)),
+    });
+  }
+  let e1 = type_check_expression(cx, &expression.e1, type_hint::MISSING);
+  let reason = Reason::new(expression.common.loc, None);
+  let callee_hint = Type::Fn(FunctionType {
+    reason,
+    argument_types: vec![e1.type_().dupe()],
+    return_type: Arc::new(hint.get_valid_hint().cloned().unwrap_or(Type::Any(reason, true))),
   });
+  let e2 = type_check_expression(cx, &expression.e2, type_hint::available(&callee_hint));
+  let type_ = match e2.type_().deref() {
+    Type::Fn(fn_type) if fn_type.argument_types.len() == 1 => {
+      assignability_check(cx, e1.loc(), e1.type_(), &fn_type.argument_types[0]);
+      fn_type.return_type.reposition(expression.common.loc)
+    }
+    Type::Fn(fn_type) => {
+      let mut stackable = StackableError::new();
+      stackable.add_fn_param_arity_error(1, fn_type.argument_types.len());
+      cx.error_set.report_stackable_error(expression.common.loc, stackable);
+      Type::Any(reason, false)
+    }
+    t => {
+      if !t.is_any() {
+        cx.error_set.report_incompatible_type_kind_error(
+          e2.loc(),
+          t.to_description(),
+          Description::GeneralFunctionType,
+        );
+      }
+      Type::Any(reason, false)
+    }
+  };
+  expr::E::Binary(expr::Binary {
+    common: expression.common.with_new_type(Arc::new(type_)),
+    operator_preceding_comments: expression.operator_preceding_comments,
+    operator: expression.operator,
+    e1: Box::new(e1),
+    e2: Box::new(e2),
+  })
+}
+
+fn is_float_type(type_: &Type) -> bool {
+  matches!(type_, Type::Primitive(_, PrimitiveTypeKind::Float))
+}
+
+fn check_binary(
+  cx: &mut TypingContext,
+  expression: &expr::Binary<()>,
+  hint: type_hint::Hint,
+) -> expr::E<Arc<Type>> {
+  let reason = Reason::new(expression.common.loc, Some(expression.common.loc));
   match expression.operator {
+    expr::BinaryOperator::PIPE => check_pipe(cx, expression, hint),
     expr::BinaryOperator::MUL
     | expr::BinaryOperator::DIV
-    | expr::BinaryOperator::MOD
     | expr::BinaryOperator::PLUS
-    | expr::BinaryOperator::MINUS
+    | expr::BinaryOperator::MINUS => {
+      // Arithmetic operators work on both int and float, decided by the left operand.
+      let e1 = Box::new(type_check_expression(cx, &expression.e1, type_hint::MISSING));
+      let kind =
+        if is_float_type(e1.type_()) { PrimitiveTypeKind::Float } else { PrimitiveTypeKind::Int };
+      let expected_type = Arc::new(Type::Primitive(reason, kind));
+      assignability_check(cx, e1.loc(), e1.type_(), &expected_type);
+      let e2 = Box::new(type_check_expression(cx, &expression.e2, type_hint::MISSING));
+      assignability_check(cx, e2.loc(), e2.type_(), &expected_type);
+      expr::E::Binary(expr::Binary {
+        common: expression.common.with_new_type(expected_type),
+        operator_preceding_comments: expression.operator_preceding_comments,
+        operator: expression.operator,
+        e1,
+        e2,
+      })
+    }
+    expr::BinaryOperator::MOD
+    | expr::BinaryOperator::SHL
+    | expr::BinaryOperator::ASHR
+    | expr::BinaryOperator::SHR
+    | expr::BinaryOperator::LAND
+    | expr::BinaryOperator::XOR
+    | expr::BinaryOperator::LOR
     | expr::BinaryOperator::AND
     | expr::BinaryOperator::OR
     | expr::BinaryOperator::CONCAT => {
+      let expected_type = Arc::new(match expression.operator {
+        expr::BinaryOperator::AND | expr::BinaryOperator::OR => {
+          Type::Primitive(reason, PrimitiveTypeKind::Bool)
+        }
+        expr::BinaryOperator::CONCAT => Type::Nominal(NominalType {
+          reason,
+          is_class_statics: false,
+          module_reference: ModuleReference::ROOT,
+          id: PStr::STR_TYPE,
+          type_arguments: Vec::new(),
+        }),
+        _ => Type::Primitive(reason, PrimitiveTypeKind::Int),
+      });
       let e1 = Box::new(type_check_expression(cx, &expression.e1, type_hint::MISSING));
       assignability_check(cx, e1.loc(), e1.type_(), &expected_type);
       let e2 = Box::new(type_check_expression(cx, &expression.e2, type_hint::MISSING));
@@ -895,14 +1320,18 @@ fn check_binary(cx: &mut TypingContext, expression: &expr::Binary<()>) -> expr::
     | expr::BinaryOperator::LE
     | expr::BinaryOperator::GT
     | expr::BinaryOperator::GE => {
-      let child_type_hint =
-        Type::Primitive(Reason::new(expression.common.loc, None), PrimitiveTypeKind::Int);
       let e1 = Box::new(type_check_expression(cx, &expression.e1, type_hint::MISSING));
+      let child_type_hint = Type::Primitive(
+        Reason::new(expression.common.loc, None),
+        if is_float_type(e1.type_()) { PrimitiveTypeKind::Float } else { PrimitiveTypeKind::Int },
+      );
       assignability_check(cx, e1.loc(), e1.type_(), &child_type_hint);
       let e2 = Box::new(type_check_expression(cx, &expression.e2, type_hint::MISSING));
       assignability_check(cx, e2.loc(), e2.type_(), &child_type_hint);
       expr::E::Binary(expr::Binary {
-        common: expression.common.with_new_type(expected_type),
+        common: expression
+          .common
+          .with_new_type(Arc::new(Type::Primitive(reason, PrimitiveTypeKind::Bool))),
         operator_preceding_comments: expression.operator_preceding_comments,
         operator: expression.operator,
         e1,
@@ -913,9 +1342,14 @@ fn check_binary(cx: &mut TypingContext, expression: &expr::Binary<()>) -> expr::
       let e1 = Box::new(type_check_expression(cx, &expression.e1, type_hint::MISSING));
       let e2 =
         Box::new(type_check_expression(cx, &expression.e2, type_hint::available(e1.type_())));
-      assignability_check(cx, e2.loc(), e2.type_(), e1.type_());
+      // Equality compares identities, so it never upcasts to an interface.
+      if let Some(e) = type_system::assignability_check(e2.type_(), e1.type_()) {
+        cx.error_set.report_stackable_error(e2.loc(), e);
+      }
       expr::E::Binary(expr::Binary {
-        common: expression.common.with_new_type(expected_type),
+        common: expression
+          .common
+          .with_new_type(Arc::new(Type::Primitive(reason, PrimitiveTypeKind::Bool))),
         operator_preceding_comments: expression.operator_preceding_comments,
         operator: expression.operator,
         e1,
@@ -947,20 +1381,51 @@ fn check_if_else(
       expr::IfElseCondition::Guard(pattern, expr)
     }
   });
+  if hint.get_valid_hint().is_none() && block_always_returns(&expression.e1) {
+    // The type is decided by the else branch, since the then branch never produces a value.
+    let e2 = Box::new(match expression.e2.as_ref() {
+      expr::IfElseOrBlock::IfElse(e2) => {
+        expr::IfElseOrBlock::IfElse(check_if_else(cx, e2, type_hint::MISSING))
+      }
+      expr::IfElseOrBlock::Block(e2) => {
+        expr::IfElseOrBlock::Block(check_block(cx, e2, type_hint::MISSING))
+      }
+    });
+    let e2_type = match e2.as_ref() {
+      expr::IfElseOrBlock::IfElse(e) => &e.common.type_,
+      expr::IfElseOrBlock::Block(e) => &e.common.type_,
+    };
+    let e1 = Box::new(check_block(cx, &expression.e1, type_hint::available(e2_type)));
+    let type_ = e2_type.reposition(expression.common.loc);
+    return expr::IfElse {
+      common: expression.common.with_new_type(Arc::new(type_)),
+      condition,
+      e1,
+      e2,
+    };
+  }
   let e1 = Box::new(check_block(cx, &expression.e1, hint));
+  // When an interface value is expected, both branches are upcast to it.
+  let join_type = match hint.get_valid_hint() {
+    Some(hint_type) if cx.is_interface_value_type(hint_type) => {
+      assignability_check(cx, e1.common.loc, &e1.common.type_, hint_type);
+      Arc::new(hint_type.clone())
+    }
+    _ => e1.common.type_.dupe(),
+  };
   let e2 = Box::new(match expression.e2.as_ref() {
     expr::IfElseOrBlock::IfElse(e2) => {
-      let checked = check_if_else(cx, e2, type_hint::available(&e1.common.type_));
-      assignability_check(cx, e2.common.loc, &checked.common.type_, &e1.common.type_);
+      let checked = check_if_else(cx, e2, type_hint::available(&join_type));
+      assignability_check(cx, e2.common.loc, &checked.common.type_, &join_type);
       expr::IfElseOrBlock::IfElse(checked)
     }
     expr::IfElseOrBlock::Block(e2) => {
-      let checked = check_block(cx, e2, type_hint::available(&e1.common.type_));
-      assignability_check(cx, e2.common.loc, &checked.common.type_, &e1.common.type_);
+      let checked = check_block(cx, e2, type_hint::available(&join_type));
+      assignability_check(cx, e2.common.loc, &checked.common.type_, &join_type);
       expr::IfElseOrBlock::Block(checked)
     }
   });
-  let type_ = e1.common.type_.reposition(expression.common.loc);
+  let type_ = join_type.reposition(expression.common.loc);
   expr::IfElse { common: expression.common.with_new_type(Arc::new(type_)), condition, e1, e2 }
 }
 
@@ -971,27 +1436,78 @@ fn check_match(
 ) -> expr::E<Arc<Type>> {
   let checked_matched = type_check_expression(cx, &expression.matched, type_hint::MISSING);
   let checked_matched_type = checked_matched.type_();
-  let mut checked_cases = Vec::new();
-  let mut matching_list_type: Option<Arc<Type>> = None;
+  let mut checked_patterns = Vec::with_capacity(expression.cases.len());
+  let mut checked_guards = Vec::with_capacity(expression.cases.len());
   let mut abstract_pattern_nodes = Vec::with_capacity(expression.cases.len());
-  for expr::VariantPatternToExpression { loc, pattern, body, ending_associated_comments } in
-    &expression.cases
-  {
+  // Bad patterns are treated as wildcards for exhaustiveness, but they shouldn't make later
+  // cases look redundant.
+  let mut well_formed_abstract_pattern_nodes = Vec::with_capacity(expression.cases.len());
+  for case in &expression.cases {
+    let error_count_before_pattern = cx.error_set.error_count();
     let (pattern, abstract_pattern_node) =
-      check_matching_pattern(cx, pattern, true, checked_matched_type);
-    abstract_pattern_nodes.push(abstract_pattern_node);
-    let checked_body = type_check_expression(cx, body, hint);
-    match &matching_list_type {
-      Some(expected) => assignability_check(cx, *loc, checked_body.type_(), expected),
-      None => matching_list_type = Some(checked_body.type_().dupe()),
-    }
-    checked_cases.push(expr::VariantPatternToExpression {
-      loc: *loc,
-      pattern,
-      body: Box::new(checked_body),
-      ending_associated_comments: *ending_associated_comments,
+      check_matching_pattern(cx, &case.pattern, true, checked_matched_type);
+    let is_well_formed = cx.error_set.error_count() == error_count_before_pattern;
+    if is_well_formed
+      && !pattern_matching::is_additional_pattern_useful(
+        cx,
+        &well_formed_abstract_pattern_nodes,
+        abstract_pattern_node.dupe(),
+      )
+    {
+      cx.error_set.report_useless_pattern_error(*case.pattern.loc(), false);
+    }
+    let guard = case.guard.as_ref().map(|guard| {
+      let bool_type = Type::Primitive(Reason::new(guard.loc(), None), PrimitiveTypeKind::Bool);
+      let checked_guard = type_check_expression(cx, guard, type_hint::available(&bool_type));
+      assignability_check(cx, checked_guard.loc(), checked_guard.type_(), &bool_type);
+      Box::new(checked_guard)
     });
+    // A guard can fail at runtime, so a guarded case never counts towards coverage.
+    if guard.is_none() {
+      if is_well_formed {
+        well_formed_abstract_pattern_nodes.push(abstract_pattern_node.dupe());
+      }
+      abstract_pattern_nodes.push(abstract_pattern_node);
+    }
+    checked_patterns.push(pattern);
+    checked_guards.push(guard);
   }
+  // Without a hint, cases that always return are checked last,
+  // so that they can be typed by the other cases.
+  let defer_returning_cases = hint.get_valid_hint().is_none();
+  // When an interface value is expected, every case is upcast to it.
+  let mut matching_list_type: Option<Arc<Type>> =
+    hint.get_valid_hint().filter(|t| cx.is_interface_value_type(t)).map(|t| Arc::new(t.clone()));
+  let mut checked_bodies = expression.cases.iter().map(|_| None).collect_vec();
+  for deferred_pass in [false, true] {
+    for (case, checked_body_slot) in expression.cases.iter().zip(&mut checked_bodies) {
+      if deferred_pass != (defer_returning_cases && expression_always_returns(&case.body)) {
+        continue;
+      }
+      let case_hint =
+        if deferred_pass { type_hint::from_option(matching_list_type.as_deref()) } else { hint };
+      let checked_body = type_check_expression(cx, &case.body, case_hint);
+      match &matching_list_type {
+        Some(expected) => assignability_check(cx, case.loc, checked_body.type_(), expected),
+        None => matching_list_type = Some(checked_body.type_().dupe()),
+      }
+      *checked_body_slot = Some(checked_body);
+    }
+  }
+  let checked_cases = expression
+    .cases
+    .iter()
+    .zip(checked_patterns)
+    .zip(checked_guards)
+    .zip(checked_bodies)
+    .map(|(((case, pattern), guard), checked_body)| expr::VariantPatternToExpression {
+      loc: case.loc,
+      pattern,
+      guard,
+      body: Box::new(checked_body.unwrap()),
+      ending_associated_comments: case.ending_associated_comments,
+    })
+    .collect_vec();
   if let Some(description) =
     pattern_matching::incomplete_counterexample(cx, &abstract_pattern_nodes)
   {
@@ -1020,7 +1536,7 @@ fn infer_lambda_parameter_types(
     expression.parameters.parameters.iter().enumerate()
   {
     let type_ = if let Some(annot) = annotation {
-      Arc::new(Type::from_annotation(annot))
+      cx.type_from_annotation(annot)
     } else if let Some(param_hint) = hint.transform_to_nth_param(i).get_valid_hint() {
       Arc::new(param_hint.reposition(name.loc))
     } else {
@@ -1063,7 +1579,11 @@ fn check_lambda(
       Literal::Bool(false),
     )
   } else {
-    type_check_expression(cx, &expression.body, hint.transform_to_return_type())
+    cx.run_with_return_target(ReturnTarget::Lambda, |cx| {
+      cx.run_with_in_loop(false, |cx| {
+        type_check_expression(cx, &expression.body, hint.transform_to_return_type())
+      })
+    })
   };
   let captured = cx.local_typing_context.get_captured(&expression.common.loc);
   let type_ = Type::Fn(FunctionType {
@@ -1079,6 +1599,242 @@ fn check_lambda(
   })
 }
 
+/// Like `Process.panic`, `return` and `break` never produce a value, so they take the type that
+/// the context expects. Without one, the type is left for inference to decide, just like an
+/// unsolved type argument of `Process.panic`. Outside of inference, the value can only be discarded.
+fn diverging_expression_type(cx: &mut TypingContext, loc: Location, hint: type_hint::Hint) -> Type {
+  if let Some(t) = hint.get_valid_hint() {
+    t.reposition(loc)
+  } else if cx.in_synthesis_mode() {
+    cx.mk_placeholder_type(Reason::new(loc, None))
+  } else {
+    Type::Primitive(Reason::new(loc, None), PrimitiveTypeKind::Unit)
+  }
+}
+
+fn check_return(
+  cx: &mut TypingContext,
+  expression: &expr::Return<()>,
+  hint: type_hint::Hint,
+) -> expr::E<Arc<Type>> {
+  let argument = match cx.return_target() {
+    ReturnTarget::Function(return_type) => {
+      let checked =
+        type_check_expression(cx, &expression.argument, type_hint::available(&return_type));
+      assignability_check(cx, checked.loc(), checked.type_(), &return_type);
+      checked
+    }
+    ReturnTarget::Lambda => {
+      cx.error_set.report_illegal_return_in_lambda(expression.common.loc);
+      type_check_expression(cx, &expression.argument, type_hint::MISSING)
+    }
+    ReturnTarget::OutsideFunction => {
+      cx.error_set.report_illegal_return_outside_function(expression.common.loc);
+      type_check_expression(cx, &expression.argument, type_hint::MISSING)
+    }
+  };
+  let type_ = diverging_expression_type(cx, expression.common.loc, hint);
+  expr::E::Return(expr::Return {
+    common: expression.common.with_new_type(Arc::new(type_)),
+    argument: Box::new(argument),
+  })
+}
+
+fn check_while(cx: &mut TypingContext, expression: &expr::While<()>) -> expr::E<Arc<Type>> {
+  let bool_type =
+    Type::Primitive(Reason::new(expression.condition.loc(), None), PrimitiveTypeKind::Bool);
+  let condition =
+    type_check_expression(cx, &expression.condition, type_hint::available(&bool_type));
+  assignability_check(cx, condition.loc(), condition.type_(), &bool_type);
+  let body = cx.run_with_in_loop(true, |cx| check_loop_body(cx, &expression.body));
+  expr::E::While(expr::While {
+    common: expression.common.with_new_type(Arc::new(Type::Primitive(
+      Reason::new(expression.common.loc, None),
+      PrimitiveTypeKind::Unit,
+    ))),
+    condition: Box::new(condition),
+    body: Box::new(body),
+  })
+}
+
+fn check_for(cx: &mut TypingContext, expression: &expr::For<()>) -> expr::E<Arc<Type>> {
+  let check_bound = |cx: &mut TypingContext, bound: &expr::E<()>| {
+    let int_type = Type::Primitive(Reason::new(bound.loc(), None), PrimitiveTypeKind::Int);
+    let checked = type_check_expression(cx, bound, type_hint::available(&int_type));
+    assignability_check(cx, checked.loc(), checked.type_(), &int_type);
+    checked
+  };
+  let start = check_bound(cx, &expression.start);
+  let end = check_bound(cx, &expression.end);
+  let iterator_loc = expression.iterator.loc;
+  cx.local_typing_context.write(
+    iterator_loc,
+    Arc::new(Type::Primitive(
+      Reason::new(iterator_loc, Some(iterator_loc)),
+      PrimitiveTypeKind::Int,
+    )),
+  );
+  let body = cx.run_with_in_loop(true, |cx| check_loop_body(cx, &expression.body));
+  expr::E::For(expr::For {
+    common: expression.common.with_new_type(Arc::new(Type::Primitive(
+      Reason::new(expression.common.loc, None),
+      PrimitiveTypeKind::Unit,
+    ))),
+    iterator: expression.iterator,
+    start: Box::new(start),
+    end: Box::new(end),
+    body: Box::new(body),
+  })
+}
+
+fn check_loop_body(cx: &mut TypingContext, body: &expr::Block<()>) -> expr::Block<Arc<Type>> {
+  let unit_type = Type::Primitive(Reason::new(body.common.loc, None), PrimitiveTypeKind::Unit);
+  let checked = check_block(cx, body, type_hint::available(&unit_type));
+  assignability_check(cx, body.common.loc, &checked.common.type_, &unit_type);
+  checked
+}
+
+fn check_break(
+  cx: &mut TypingContext,
+  common: &expr::ExpressionCommon<()>,
+  hint: type_hint::Hint,
+) -> expr::E<Arc<Type>> {
+  if !cx.in_loop() {
+    cx.error_set.report_illegal_break_outside_loop(common.loc);
+  }
+  let type_ = diverging_expression_type(cx, common.loc, hint);
+  expr::E::Break(common.with_new_type(Arc::new(type_)))
+}
+
+fn check_assert(cx: &mut TypingContext, expression: &expr::Assert<()>) -> expr::E<Arc<Type>> {
+  let mut checked_expressions = Vec::with_capacity(expression.arguments.expressions.len());
+  for (i, e) in expression.arguments.expressions.iter().enumerate() {
+    // The first argument is the condition, and the optional second argument is the message.
+    let expected_type = if i == 0 {
+      Type::bool_type(Reason::new(e.loc(), None))
+    } else {
+      Type::Nominal(NominalType {
+        reason: Reason::new(e.loc(), None),
+        is_class_statics: false,
+        module_reference: ModuleReference::ROOT,
+        id: PStr::STR_TYPE,
+        type_arguments: Vec::new(),
+      })
+    };
+    let checked = type_check_expression(cx, e, type_hint::available(&expected_type));
+    assignability_check(cx, checked.loc(), checked.type_(), &expected_type);
+    checked_expressions.push(checked);
+  }
+  expr::E::Assert(expr::Assert {
+    common: expression.common.with_new_type(Arc::new(Type::unit_type(Reason::new(
+      expression.common.loc,
+      Some(expression.common.loc),
+    )))),
+    arguments: expr::ParenthesizedExpressionList {
+      loc: expression.arguments.loc,
+      start_associated_comments: expression.arguments.start_associated_comments,
+      ending_associated_comments: expression.arguments.ending_associated_comments,
+      expressions: checked_expressions,
+    },
+  })
+}
+
+fn is_try_operand_type(nominal: &NominalType) -> bool {
+  !nominal.is_class_statics
+    && ((nominal.module_reference == ModuleReference::STD_OPTION
+      && nominal.id == PStr::OPTION_TYPE
+      && nominal.type_arguments.len() == 1)
+      || (nominal.module_reference == ModuleReference::STD_RESULT
+        && nominal.id == PStr::RESULT_TYPE
+        && nominal.type_arguments.len() == 2))
+}
+
+fn check_try(cx: &mut TypingContext, expression: &expr::Try<()>) -> expr::E<Arc<Type>> {
+  let argument = type_check_expression(cx, &expression.argument, type_hint::MISSING);
+  let type_ = match argument.type_().as_ref() {
+    Type::Nominal(nominal) if is_try_operand_type(nominal) => {
+      // The none or error case is returned as is, so the enclosing function must return the same
+      // kind of value, with a compatible error type for `Result`.
+      match cx.return_type().as_deref() {
+        Some(Type::Nominal(return_type))
+          if return_type.module_reference == nominal.module_reference
+            && return_type.id == nominal.id
+            && is_try_operand_type(return_type) =>
+        {
+          if let (Some(error_type), Some(return_error_type)) =
+            (nominal.type_arguments.get(1), return_type.type_arguments.get(1))
+          {
+            assignability_check(cx, argument.loc(), error_type, return_error_type);
+          }
+        }
+        _ => cx.error_set.report_illegal_try_in_function(expression.common.loc, nominal.id),
+      }
+      nominal.type_arguments[0].reposition(expression.common.loc)
+    }
+    Type::Any(_, _) => Type::Any(Reason::new(expression.common.loc, None), false),
+    t => {
+      cx.error_set.report_illegal_try_operand(argument.loc(), t.to_description());
+      Type::Any(Reason::new(expression.common.loc, None), false)
+    }
+  };
+  expr::E::Try(expr::Try {
+    common: expression.common.with_new_type(Arc::new(type_)),
+    argument: Box::new(argument),
+  })
+}
+
+fn check_struct_update(
+  cx: &mut TypingContext,
+  expression: &expr::StructUpdate<()>,
+  hint: type_hint::Hint,
+) -> expr::E<Arc<Type>> {
+  let base = type_check_expression(cx, &expression.base, hint);
+  let struct_fields = if let Type::Tuple(_, element_types) = base.type_().as_ref() {
+    Some(tuple_struct_items(element_types))
+  } else {
+    cx.resolve_detailed_struct_definitions_opt(base.type_()).map(|(_, _, f)| f)
+  };
+  if struct_fields.is_none() && !base.type_().is_any() {
+    cx.error_set.report_not_a_struct_error(base.loc(), base.type_().to_description());
+  }
+  let mut updated_field_locations = HashMap::new();
+  let mut fields = Vec::with_capacity(expression.fields.len());
+  for field in &expression.fields {
+    let field_name = field.field_name;
+    if let Some(old_loc) = updated_field_locations.insert(field_name.name, field_name.loc) {
+      cx.error_set.report_name_already_bound_error(field_name.loc, field_name.name, old_loc);
+    }
+    let resolved = struct_fields.as_ref().and_then(|struct_fields| {
+      struct_fields.iter().enumerate().find(|(_, it)| it.name == field_name.name)
+    });
+    let (field_order, value) = if let Some((field_order, resolved)) = resolved {
+      if !resolved.is_public {
+        cx.error_set.report_not_visible_error(field_name.loc, field_name.name, Visibility::Private);
+      }
+      let value = type_check_expression(cx, &field.value, type_hint::available(&resolved.type_));
+      assignability_check(cx, value.loc(), value.type_(), &resolved.type_);
+      (field_order as i32, value)
+    } else {
+      if struct_fields.is_some() {
+        cx.error_set.report_cannot_resolve_member_error(
+          field_name.loc,
+          base.type_().to_description(),
+          field_name.name,
+        );
+      }
+      (field.field_order, type_check_expression(cx, &field.value, type_hint::MISSING))
+    };
+    fields.push(expr::FieldUpdate { loc: field.loc, field_name, field_order, value });
+  }
+  let type_ = base.type_().reposition(expression.common.loc);
+  expr::E::StructUpdate(expr::StructUpdate {
+    common: expression.common.with_new_type(Arc::new(type_)),
+    base: Box::new(base),
+    fields,
+    ending_associated_comments: expression.ending_associated_comments,
+  })
+}
+
 fn bad_pattern_default(wildcard_on_bad_pattern: bool) -> pattern_matching::AbstractPatternNode {
   if wildcard_on_bad_pattern {
     pattern_matching::AbstractPatternNode::wildcard()
@@ -1176,6 +1932,13 @@ fn any_typed_invalid_matching_pattern(
         associated_comments: *associated_comments,
       }
     }
+    pattern::MatchingPattern::Literal { location, associated_comments, literal } => {
+      pattern::MatchingPattern::Literal {
+        location: *location,
+        associated_comments: *associated_comments,
+        literal: *literal,
+      }
+    }
     pattern::MatchingPattern::Or { location, patterns } => {
       let checked_patterns =
         patterns.iter().map(|p| any_typed_invalid_matching_pattern(cx, p)).collect();
@@ -1197,7 +1960,12 @@ fn check_matching_pattern(
       ending_associated_comments,
       elements: destructured_names,
     }) => {
-      let Some((_, _, fields)) = cx.resolve_detailed_struct_definitions_opt(pattern_type) else {
+      let fields = if let Type::Tuple(_, element_types) = pattern_type.as_ref() {
+        tuple_struct_items(element_types)
+      } else if let Some((_, _, fields)) = cx.resolve_detailed_struct_definitions_opt(pattern_type)
+      {
+        fields
+      } else {
         cx.error_set.report_not_a_struct_error(*pattern_loc, pattern_type.to_description());
         return (
           any_typed_invalid_matching_pattern(cx, pattern),
@@ -1257,7 +2025,12 @@ fn check_matching_pattern(
       ending_associated_comments,
       elements: destructured_names,
     } => {
-      let Some((_, _, fields)) = cx.resolve_detailed_struct_definitions_opt(pattern_type) else {
+      let fields = if let Type::Tuple(_, element_types) = pattern_type.as_ref() {
+        tuple_struct_items(element_types)
+      } else if let Some((_, _, fields)) = cx.resolve_detailed_struct_definitions_opt(pattern_type)
+      {
+        fields
+      } else {
         cx.error_set.report_not_a_struct_error(*pattern_loc, pattern_type.to_description());
         return (
           any_typed_invalid_matching_pattern(cx, pattern),
@@ -1463,6 +2236,29 @@ fn check_matching_pattern(
       },
       pattern_matching::AbstractPatternNode::wildcard(),
     ),
+    pattern::MatchingPattern::Literal { location, associated_comments, literal } => {
+      let common = expr::ExpressionCommon {
+        loc: *location,
+        associated_comments: *associated_comments,
+        type_: (),
+      };
+      let literal_type = check_literal(&common, literal).type_().dupe();
+      let abstract_pattern =
+        if let Some(e) = type_system::assignability_check(&literal_type, pattern_type) {
+          cx.error_set.report_stackable_error(*location, e);
+          bad_pattern_default(wildcard_on_bad_pattern)
+        } else {
+          pattern_matching::AbstractPatternNode::literal(*literal)
+        };
+      (
+        pattern::MatchingPattern::Literal {
+          location: *location,
+          associated_comments: *associated_comments,
+          literal: *literal,
+        },
+        abstract_pattern,
+      )
+    }
     pattern::MatchingPattern::Or { location, patterns } => {
       let first_pattern = patterns.first().unwrap();
       let (first_checked, first_abstract) =
@@ -1518,36 +2314,128 @@ fn check_declaration_statement(
   let expr::DeclarationStatement {
     loc,
     associated_comments,
+    is_mutable,
     pattern,
     annotation,
     assigned_expression,
+    else_block,
   } = statement;
   let hint = if let Some(annot) = &annotation {
-    let t = Type::from_annotation(annot);
+    let t = cx.type_from_annotation(annot);
     cx.validate_type_instantiation_strictly(&t);
     Some(t)
   } else {
     None
   };
   let checked_assigned_expr =
-    type_check_expression(cx, assigned_expression, type_hint::from_option(hint.as_ref()));
-  let checked_assigned_expr_type = checked_assigned_expr.type_();
+    type_check_expression(cx, assigned_expression, type_hint::from_option(hint.as_deref()));
+  let mut checked_assigned_expr_type = checked_assigned_expr.type_();
   if let Some(hint) = &hint {
     assignability_check(cx, *loc, checked_assigned_expr_type, hint);
+    // An upcast or converted value is bound with the annotated type.
+    if cx.is_implicit_conversion(checked_assigned_expr_type, hint) {
+      checked_assigned_expr_type = hint;
+    }
   }
   let (checked_pattern, abstract_pattern_node) =
-    check_matching_pattern(cx, pattern, true, checked_assigned_expr_type);
-  if let Some(description) =
-    pattern_matching::incomplete_counterexample(cx, &[abstract_pattern_node])
-  {
-    cx.error_set.report_non_exhaustive_match_error(*checked_pattern.loc(), description);
-  }
+    check_matching_pattern(cx, pattern, else_block.is_none(), checked_assigned_expr_type);
+  let checked_else_block = if let Some(else_block) = else_block {
+    // Like an `if let` guard, the pattern is refutable, and the else block handles the rest.
+    if !pattern_matching::is_additional_pattern_useful(
+      cx,
+      &[abstract_pattern_node],
+      pattern_matching::AbstractPatternNode::wildcard(),
+    ) {
+      cx.error_set.report_useless_pattern_error(*pattern.loc(), true);
+    }
+    if !block_never_finishes(else_block) {
+      cx.error_set.report_illegal_let_else_block(else_block.common.loc);
+    }
+    let unit_type = Type::unit_type(Reason::new(else_block.common.loc, None));
+    Some(Box::new(check_block(cx, else_block, type_hint::available(&unit_type))))
+  } else {
+    if let Some(description) =
+      pattern_matching::incomplete_counterexample(cx, &[abstract_pattern_node])
+    {
+      cx.error_set.report_non_exhaustive_match_error(*checked_pattern.loc(), description);
+    }
+    None
+  };
   expr::DeclarationStatement {
     loc: *loc,
     associated_comments: *associated_comments,
+    is_mutable: *is_mutable,
     pattern: checked_pattern,
     annotation: annotation.clone(),
     assigned_expression: Box::new(checked_assigned_expr),
+    else_block: checked_else_block,
+  }
+}
+
+fn check_assignment_statement(
+  cx: &mut TypingContext,
+  statement: &expr::AssignmentStatement<()>,
+) -> expr::AssignmentStatement<Arc<Type>> {
+  let variable_type = cx.local_typing_context.read(&statement.name.loc);
+  let checked_assigned_expr =
+    type_check_expression(cx, &statement.assigned_expression, type_hint::available(&variable_type));
+  assignability_check(cx, statement.loc, checked_assigned_expr.type_(), &variable_type);
+  expr::AssignmentStatement {
+    loc: statement.loc,
+    associated_comments: statement.associated_comments,
+    name: statement.name,
+    assigned_expression: Box::new(checked_assigned_expr),
+  }
+}
+
+/// The name of a local function is typed from its annotations before its body is checked, so
+/// that the body can call the function recursively.
+fn check_local_function_statement(
+  cx: &mut TypingContext,
+  statement: &expr::LocalFunctionStatement<()>,
+) -> expr::LocalFunctionStatement<Arc<Type>> {
+  let lambda = &statement.lambda;
+  let (argument_types, _) = infer_lambda_parameter_types(cx, lambda, type_hint::MISSING);
+  let return_type = cx.type_from_annotation(&statement.return_annotation);
+  cx.validate_type_instantiation_strictly(&return_type);
+  let fn_type = Arc::new(Type::Fn(FunctionType {
+    reason: Reason::new(lambda.common.loc, None),
+    argument_types: argument_types.clone(),
+    return_type: return_type.dupe(),
+  }));
+  cx.local_typing_context.write(statement.name.loc, fn_type.dupe());
+  let body = cx.run_with_return_target(ReturnTarget::Function(return_type.dupe()), |cx| {
+    cx.run_with_in_loop(false, |cx| {
+      type_check_expression(cx, &lambda.body, type_hint::available(&return_type))
+    })
+  });
+  assignability_check(cx, body.loc(), body.type_(), &return_type);
+  let parameters = expr::LambdaParameters {
+    loc: lambda.parameters.loc,
+    parameters: lambda
+      .parameters
+      .parameters
+      .iter()
+      .zip(argument_types)
+      .map(|(param, type_)| OptionallyAnnotatedId {
+        name: param.name,
+        type_,
+        annotation: param.annotation.clone(),
+      })
+      .collect_vec(),
+    ending_associated_comments: lambda.parameters.ending_associated_comments,
+  };
+  expr::LocalFunctionStatement {
+    loc: statement.loc,
+    associated_comments: statement.associated_comments,
+    name: statement.name,
+    return_annotation: statement.return_annotation.clone(),
+    lambda: expr::Lambda {
+      common: lambda.common.with_new_type(fn_type),
+      parameters,
+      captured: cx.local_typing_context.get_captured(&lambda.common.loc),
+      body: Box::new(body),
+    },
   }
 }
 
@@ -1559,6 +2447,12 @@ fn check_statement(
     expr::Statement::Declaration(decl_stmt) => {
       expr::Statement::Declaration(Box::new(check_declaration_statement(cx, decl_stmt)))
     }
+    expr::Statement::Assignment(assign_stmt) => {
+      expr::Statement::Assignment(Box::new(check_assignment_statement(cx, assign_stmt)))
+    }
+    expr::Statement::Function(function_stmt) => {
+      expr::Statement::Function(Box::new(check_local_function_statement(cx, function_stmt)))
+    }
     expr::Statement::Expression(expr) => {
       let checked_expr = type_check_expression(cx, expr, type_hint::MISSING);
       expr::Statement::Expression(Box::new(checked_expr))
@@ -1599,6 +2493,7 @@ fn validate_tparams_signature_type_instantiation(
 }
 
 fn check_class_member_conformance_with_signature(
+  global_cx: &GlobalSignature,
   error_set: &mut ErrorSet,
   expected: &MemberSignature,
   actual: &ClassMemberDeclaration,
@@ -1639,7 +2534,10 @@ fn check_class_member_conformance_with_signature(
       expected.type_parameters.iter().map(TypeParameterSignature::to_description).collect(),
     );
   } else {
-    let actual_fn_type = FunctionType::from_function(actual);
+    let actual_fn_type = global_signature::expand_type_aliases_in_fn_type(
+      global_cx,
+      &FunctionType::from_function(actual),
+    );
     if !expected.type_.is_the_same_type(&actual_fn_type) {
       let mut error = StackableError::new();
       error.add_type_incompatibility_error(
@@ -1653,6 +2551,114 @@ fn check_class_member_conformance_with_signature(
   }
 }
 
+fn is_constructor_callee(cx: &TypingContext, callee: &expr::E<Arc<Type>>) -> bool {
+  if let expr::E::MethodAccess(method_access) = callee
+    && let expr::E::ClassId(_, mod_ref, class_id) = method_access.object.as_ref()
+  {
+    cx.is_constructor_function(*mod_ref, class_id.name, method_access.method_name.name)
+  } else {
+    false
+  }
+}
+
+/// Constants are restricted to literals, other constants, operators over them, and
+/// struct or enum construction, so that they can be emitted as globals.
+fn check_compile_time_evaluable(cx: &mut TypingContext, expression: &expr::E<Arc<Type>>) {
+  match expression {
+    expr::E::Literal(_, _) => {}
+    // `{}` is the unit value.
+    expr::E::Block(e) if e.statements.is_empty() && e.expression.is_none() => {}
+    // Class references are typed as class statics, while constant references are not.
+    expr::E::ClassId(common, _, _)
+      if !common.type_.as_nominal().is_some_and(|n| n.is_class_statics) => {}
+    expr::E::Tuple(_, expressions) => {
+      for e in &expressions.expressions {
+        check_compile_time_evaluable(cx, e);
+      }
+    }
+    expr::E::Unary(e) => check_compile_time_evaluable(cx, &e.argument),
+    expr::E::Binary(e) if e.operator != expr::BinaryOperator::PIPE => {
+      check_compile_time_evaluable(cx, &e.e1);
+      check_compile_time_evaluable(cx, &e.e2);
+    }
+    expr::E::Call(e) if is_constructor_callee(cx, &e.callee) => {
+      for e in &e.arguments.expressions {
+        check_compile_time_evaluable(cx, e);
+      }
+    }
+    _ => cx.error_set.report_not_compile_time_evaluable_error(expression.loc()),
+  }
+}
+
+/// Whether the member synthesized for `derive` can handle a field of type `type_`.
+fn is_derivable_field_type(
+  cx: &TypingContext,
+  type_: &Type,
+  derive: PStr,
+  use_loc: Location,
+) -> bool {
+  match type_ {
+    Type::Any(_, _) | Type::Primitive(_, _) => true,
+    Type::Fn(_) => false,
+    _ if is_str_type(type_) => true,
+    _ if derive == PStr::TO_STRING => is_valid_template_string_hole_type(cx, type_, use_loc),
+    // Tuples are compared with `==`.
+    Type::Tuple(_, _) if derive == PStr::EQUALS => true,
+    _ => cx
+      .nominal_type_upper_bound(type_)
+      .filter(|nominal_type| !nominal_type.is_class_statics)
+      .and_then(|nominal_type| cx.get_method_type(nominal_type, derive, use_loc))
+      .is_some_and(|method| {
+        method.type_parameters.is_empty() && method.type_.argument_types.len() == 1
+      }),
+  }
+}
+
+/// Reports each derive that cannot handle some field of the class once at the derive, and returns
+/// their locations, which are also the locations of the synthesized members.
+fn check_derived_field_types(
+  cx: &mut TypingContext,
+  clause: &DerivesClause,
+  type_definition: &TypeDefinition,
+) -> HashSet<Location> {
+  let fields = match type_definition {
+    TypeDefinition::Struct { fields, .. } => {
+      fields.iter().map(|field| (field.name.name, false, &field.annotation)).collect_vec()
+    }
+    TypeDefinition::Enum { variants, .. } => variants
+      .iter()
+      .flat_map(|variant| {
+        variant
+          .associated_data_types
+          .iter()
+          .flat_map(|list| &list.annotations)
+          .map(|annotation| (variant.name.name, true, annotation))
+      })
+      .collect_vec(),
+  };
+  let mut unsupported_derives = HashSet::new();
+  for derive in &clause.names {
+    if ![PStr::EQUALS, PStr::COMPARE, PStr::TO_STRING].contains(&derive.name) {
+      continue;
+    }
+    for (name, is_variant, annotation) in &fields {
+      let type_ = cx.type_from_annotation(annotation);
+      if !is_derivable_field_type(cx, &type_, derive.name, derive.loc) {
+        cx.error_set.report_unsupported_derive_error(
+          derive.loc,
+          derive.name,
+          *name,
+          *is_variant,
+          type_.to_description(),
+        );
+        unsupported_derives.insert(derive.loc);
+        break;
+      }
+    }
+  }
+  unsupported_derives
+}
+
 pub fn type_check_module(
   module_reference: ModuleReference,
   module: &Module<()>,
@@ -1665,9 +2671,27 @@ pub fn type_check_module(
   for one_import in module.imports.iter() {
     if let Some(module_cx) = global_cx.get(&one_import.imported_module) {
       for id in one_import.imported_members.iter() {
-        if module_cx.interfaces.get(&id.name).filter(|interface_cx| !interface_cx.private).is_none()
-        {
-          error_set.report_missing_export_error(id.loc, one_import.imported_module, id.name);
+        let visibility = module_cx
+          .interfaces
+          .get(&id.name)
+          .map(|interface_cx| interface_cx.visibility)
+          .or_else(|| module_cx.type_aliases.get(&id.name).map(|alias_cx| alias_cx.visibility))
+          .or_else(|| module_cx.constants.get(&id.name).map(|const_cx| const_cx.visibility));
+        match visibility {
+          None => {
+            error_set.report_missing_export_error(id.loc, one_import.imported_module, id.name)
+          }
+          Some(visibility)
+            if !global_signature::is_toplevel_visible(
+              global_cx,
+              module_reference,
+              one_import.imported_module,
+              visibility,
+            ) =>
+          {
+            error_set.report_not_visible_error(id.loc, id.name, visibility)
+          }
+          Some(_) => {}
         }
       }
     } else {
@@ -1735,7 +2759,7 @@ pub fn type_check_module(
           fields,
         } => {
           for field in fields {
-            cx.validate_type_instantiation_strictly(&Type::from_annotation(&field.annotation))
+            cx.validate_type_instantiation_strictly(&cx.type_from_annotation(&field.annotation))
           }
         }
         TypeDefinition::Enum {
@@ -1748,7 +2772,7 @@ pub fn type_check_module(
             .iter()
             .flat_map(|it| it.associated_data_types.iter().flat_map(|it| &it.annotations))
           {
-            cx.validate_type_instantiation_strictly(&Type::from_annotation(t))
+            cx.validate_type_instantiation_strictly(&cx.type_from_annotation(t))
           }
         }
       }
@@ -1764,24 +2788,26 @@ pub fn type_check_module(
         }
         TypeParameterSignature::from_list(member.type_parameters.as_ref())
       };
-      let has_interface_def = if member.is_method {
+      let required_visibility = if member.is_method {
         let resolved = global_signature::resolve_all_method_signatures(
           global_cx,
           &resolved_super_types,
           member.name.name,
         );
         for expected in &resolved {
-          check_class_member_conformance_with_signature(error_set, expected, member);
+          check_class_member_conformance_with_signature(global_cx, error_set, expected, member);
         }
-        !resolved.is_empty()
+        resolved.iter().map(|it| it.visibility).max()
       } else {
-        false
+        None
       };
-      if !member.is_public && has_interface_def {
+      if let Some(required_visibility) = required_visibility
+        && member.visibility < required_visibility
+      {
         error_set.report_incompatible_type_kind_error(
           member.loc,
-          Description::PrivateMember,
-          Description::PublicMember,
+          Description::Member(member.visibility),
+          Description::Member(required_visibility),
         );
       }
 
@@ -1800,15 +2826,131 @@ pub fn type_check_module(
           ));
         }
       }
-      member_cx
-        .validate_type_instantiation_strictly(&Type::Fn(FunctionType::from_function(member)));
+      member_cx.validate_type_instantiation_strictly(&Type::Fn(
+        global_signature::expand_type_aliases_in_fn_type(
+          global_cx,
+          &FunctionType::from_function(member),
+        ),
+      ));
+      for param in member.parameters.parameters.iter() {
+        if let Some(default_value) = &param.default_value {
+          let param_type = member_cx.type_from_annotation(&param.annotation);
+          let checked =
+            type_check_expression(&mut member_cx, default_value, type_hint::available(&param_type));
+          assignability_check(&mut member_cx, checked.loc(), checked.type_(), &param_type);
+          check_compile_time_evaluable(&mut member_cx, &checked);
+        }
+      }
       for param in member.parameters.parameters.iter() {
-        local_cx.write(param.name.loc, Arc::new(Type::from_annotation(&param.annotation)));
+        local_cx.write(
+          param.name.loc,
+          global_signature::expand_type_aliases(
+            global_cx,
+            &Type::from_annotation(&param.annotation),
+          ),
+        );
       }
     }
 
     let checked = match toplevel {
-      Toplevel::Interface(i) => Toplevel::Interface(i.clone()),
+      Toplevel::Interface(i) => {
+        // Default bodies are checked against an abstract `this` bounded by the interface itself,
+        // so they can only rely on members that every implementing class provides.
+        local_cx.write(
+          i.loc,
+          Arc::new(Type::Generic(Reason::new(i.name.loc, Some(i.name.loc)), PStr::UNDERSCORE_SELF)),
+        );
+        let mut checked_members = Vec::new();
+        for member in &i.members.members {
+          let default_body = if let Some(default_body) = &member.default_body {
+            let mut tparam_sigs = vec![TypeParameterSignature {
+              name: PStr::UNDERSCORE_SELF,
+              bound: Some(nominal_type.clone()),
+            }];
+            tparam_sigs.append(&mut TypeParameterSignature::from_list(toplevel.type_parameters()));
+            tparam_sigs
+              .append(&mut TypeParameterSignature::from_list(member.decl.type_parameters.as_ref()));
+            let mut cx = TypingContext::new(
+              global_cx,
+              &mut local_cx,
+              error_set,
+              module_reference,
+              i.name.name,
+              tparam_sigs,
+            );
+            let body_type_hint = cx.type_from_annotation(&member.decl.return_type);
+            let body = cx
+              .run_with_return_target(ReturnTarget::Function(body_type_hint.dupe()), |cx| {
+                type_check_expression(cx, default_body, type_hint::available(&body_type_hint))
+              });
+            assignability_check(&mut cx, body.loc(), body.type_(), &body_type_hint);
+            Some(body)
+          } else {
+            None
+          };
+          checked_members
+            .push(InterfaceMemberDefinition { decl: member.decl.clone(), default_body });
+        }
+        Toplevel::Interface(InterfaceDeclarationCommon {
+          loc: i.loc,
+          associated_comments: i.associated_comments,
+          visibility: i.visibility,
+          name: i.name,
+          type_parameters: i.type_parameters.clone(),
+          extends_or_implements_nodes: i.extends_or_implements_nodes.clone(),
+          derives: None,
+          type_definition: (),
+          members: InterfaceMembersCommon {
+            loc: i.members.loc,
+            members: checked_members,
+            ending_associated_comments: i.members.ending_associated_comments,
+          },
+        })
+      }
+      Toplevel::TypeAlias(alias) => {
+        if global_signature::is_cyclic_type_alias(global_cx, module_reference, alias.name.name) {
+          error_set
+            .report_cyclic_type_definition_error(alias.name.loc, nominal_type.to_description());
+        }
+        let mut cx = TypingContext::new(
+          global_cx,
+          &mut local_cx,
+          error_set,
+          module_reference,
+          alias.name.name,
+          toplevel_tparams_sig.clone(),
+        );
+        cx.validate_type_instantiation_allow_abstract_types(
+          &cx.type_from_annotation(&alias.annotation),
+        );
+        Toplevel::TypeAlias(alias.clone())
+      }
+      Toplevel::Const(c) => {
+        if global_signature::is_cyclic_const(global_cx, module_reference, c.name.name) {
+          error_set.report_cyclic_const_definition_error(c.name.loc, c.name.name);
+        }
+        let mut cx = TypingContext::new(
+          global_cx,
+          &mut local_cx,
+          error_set,
+          module_reference,
+          c.name.name,
+          Vec::new(),
+        );
+        let type_ = cx.type_from_annotation(&c.annotation);
+        cx.validate_type_instantiation_strictly(&type_);
+        let value = type_check_expression(&mut cx, &c.value, type_hint::available(&type_));
+        assignability_check(&mut cx, value.loc(), value.type_(), &type_);
+        check_compile_time_evaluable(&mut cx, &value);
+        Toplevel::Const(ConstDefinition {
+          loc: c.loc,
+          associated_comments: c.associated_comments,
+          visibility: c.visibility,
+          name: c.name,
+          annotation: c.annotation.clone(),
+          value,
+        })
+      }
       Toplevel::Class(c) => {
         let mut missing_function_members =
           global_signature::resolve_all_member_names(global_cx, &resolved_super_types, false);
@@ -1847,6 +2989,20 @@ pub fn type_check_module(
         }
         local_cx.write(c.loc, Arc::new(Type::Nominal(nominal_type)));
 
+        let unsupported_derives =
+          if let (Some(clause), Some(type_definition)) = (&c.derives, &c.type_definition) {
+            let mut cx = TypingContext::new(
+              global_cx,
+              &mut local_cx,
+              error_set,
+              module_reference,
+              c.name.name,
+              TypeParameterSignature::from_list(toplevel.type_parameters()),
+            );
+            check_derived_field_types(&mut cx, clause, type_definition)
+          } else {
+            HashSet::new()
+          };
         let mut checked_members = Vec::new();
         for member in &c.members.members {
           let tparam_sigs = if member.decl.is_method {
@@ -1858,27 +3014,38 @@ pub fn type_check_module(
           } else {
             TypeParameterSignature::from_list(member.decl.type_parameters.as_ref())
           };
+          // Errors in a derived member that has an unsupported field are already reported at the
+          // derive, and the rest would only point at its synthesized source.
+          let mut discarded_error_set = ErrorSet::new();
+          let member_error_set = if unsupported_derives.contains(&member.decl.name.loc) {
+            &mut discarded_error_set
+          } else {
+            &mut *error_set
+          };
           let mut cx = TypingContext::new(
             global_cx,
             &mut local_cx,
-            error_set,
+            member_error_set,
             module_reference,
             c.name.name,
             tparam_sigs,
           );
-          let body_type_hint = Type::from_annotation(&member.decl.return_type);
-          let body =
-            type_check_expression(&mut cx, &member.body, type_hint::available(&body_type_hint));
+          let body_type_hint = cx.type_from_annotation(&member.decl.return_type);
+          let body = cx
+            .run_with_return_target(ReturnTarget::Function(body_type_hint.dupe()), |cx| {
+              type_check_expression(cx, &member.body, type_hint::available(&body_type_hint))
+            });
           assignability_check(&mut cx, body.loc(), body.type_(), &body_type_hint);
           checked_members.push(ClassMemberDefinition { decl: member.decl.clone(), body });
         }
         Toplevel::Class(InterfaceDeclarationCommon {
           loc: c.loc,
           associated_comments: c.associated_comments,
-          private: c.private,
+          visibility: c.visibility,
           name: c.name,
           type_parameters: c.type_parameters.clone(),
           extends_or_implements_nodes: c.extends_or_implements_nodes.clone(),
+          derives: c.derives.clone(),
           type_definition: c.type_definition.clone(),
           members: InterfaceMembersCommon {
             loc: c.members.loc,
@@ -1,6 +1,6 @@
 use dupe::Dupe;
 use itertools::Itertools;
-use samlang_ast::Description;
+use samlang_ast::{Description, source::Literal};
 use samlang_collections::list::{PersistentList, cons, list, one};
 use samlang_heap::{ModuleReference, PStr};
 use std::{
@@ -16,13 +16,19 @@ pub(super) struct VariantPatternConstructor {
   pub(super) variant_name: PStr,
 }
 
+/// Literals are constructors without elements. Bool literals form a finite domain, while int and
+/// string literals can never cover all values, so matching on them always needs a wildcard.
+#[derive(Debug, Copy, Clone, Dupe, PartialOrd, Ord, PartialEq, Eq, Hash)]
+enum PatternConstructor {
+  Tuple,
+  Variant(VariantPatternConstructor),
+  Literal(Literal),
+}
+
 #[derive(Debug, PartialEq, Eq)]
 enum AbstractPatternNodeInner {
   // We assume the elements are normalized to add wildcard to not-mentioned fields.
-  StructLike {
-    variant: Option<VariantPatternConstructor>,
-    elements: PersistentList<AbstractPatternNode>,
-  },
+  StructLike { constructor: PatternConstructor, elements: PersistentList<AbstractPatternNode> },
   Wildcard,
   Or(Vec<AbstractPatternNode>),
 }
@@ -30,17 +36,26 @@ enum AbstractPatternNodeInner {
 impl AbstractPatternNodeInner {
   fn to_description(&self) -> Description {
     match self {
-      Self::StructLike { variant: None, elements } => Description::TuplePattern(
-        elements.iter().map(AbstractPatternNode::to_description).collect(),
-      ),
+      Self::StructLike { constructor: PatternConstructor::Tuple, elements } => {
+        Description::TuplePattern(
+          elements.iter().map(AbstractPatternNode::to_description).collect(),
+        )
+      }
       Self::StructLike {
-        variant:
-          Some(VariantPatternConstructor { module_reference: _, class_name: _, variant_name }),
+        constructor:
+          PatternConstructor::Variant(VariantPatternConstructor {
+            module_reference: _,
+            class_name: _,
+            variant_name,
+          }),
         elements,
       } => Description::VariantPattern(
         *variant_name,
         elements.iter().map(AbstractPatternNode::to_description).collect(),
       ),
+      Self::StructLike { constructor: PatternConstructor::Literal(literal), elements: _ } => {
+        Description::LiteralPattern(*literal)
+      }
       Self::Wildcard => Description::WildcardPattern,
       Self::Or(choices) => {
         Description::OrPattern(choices.iter().map(AbstractPatternNode::to_description).collect())
@@ -67,7 +82,8 @@ impl AbstractPatternNode {
     if elements.len() == 1 {
       elements.first().unwrap().to_description()
     } else {
-      AbstractPatternNodeInner::StructLike { variant: None, elements }.to_description()
+      AbstractPatternNodeInner::StructLike { constructor: PatternConstructor::Tuple, elements }
+        .to_description()
     }
   }
 }
@@ -78,24 +94,31 @@ impl AbstractPatternNode {
   }
 
   pub(super) fn tuple(elements: Vec<AbstractPatternNode>) -> Self {
-    Self::tuple_or_variant(None, elements)
+    Self::struct_like(PatternConstructor::Tuple, elements)
   }
 
   pub(super) fn variant(c: VariantPatternConstructor, elements: Vec<AbstractPatternNode>) -> Self {
-    Self::tuple_or_variant(Some(c), elements)
+    Self::struct_like(PatternConstructor::Variant(c), elements)
+  }
+
+  pub(super) fn literal(literal: Literal) -> Self {
+    // Literals that only differ in their source spelling are the same constructor.
+    let literal = match literal {
+      Literal::Int(i, Some(_)) => Literal::Int(i, None),
+      Literal::String(s, Some(_)) => Literal::String(s, None),
+      literal => literal,
+    };
+    Self::struct_like(PatternConstructor::Literal(literal), Vec::with_capacity(0))
   }
 
-  pub(super) fn tuple_or_variant(
-    variant: Option<VariantPatternConstructor>,
-    elements: Vec<AbstractPatternNode>,
-  ) -> Self {
-    Self(Rc::new(AbstractPatternNodeInner::StructLike { variant, elements: list(elements) }))
+  fn struct_like(constructor: PatternConstructor, elements: Vec<AbstractPatternNode>) -> Self {
+    Self(Rc::new(AbstractPatternNodeInner::StructLike { constructor, elements: list(elements) }))
   }
 
   #[cfg(test)]
   pub(super) fn enum_(c: VariantPatternConstructor) -> Self {
     Self(Rc::new(AbstractPatternNodeInner::StructLike {
-      variant: Some(c),
+      constructor: PatternConstructor::Variant(c),
       elements: PersistentList::NULL,
     }))
   }
@@ -172,25 +195,25 @@ fn useful_internal<CX: PatternMatchingContext>(
     return p.0.is_empty();
   };
   match q_first.0.as_ref() {
-    AbstractPatternNodeInner::StructLike { variant, elements: rs } => {
+    AbstractPatternNodeInner::StructLike { constructor, elements: rs } => {
       let rs_len = rs.len();
       useful_internal(
         cx,
-        &convert_into_specialized_matrix(p, *variant, rs_len),
+        &convert_into_specialized_matrix(p, *constructor, rs_len),
         PatternVector(rs.clone().append(q_rest)),
       )
     }
     AbstractPatternNodeInner::Wildcard => {
       let root_constructors = find_roots_constructors(p);
       if signature_incomplete_names(cx, &root_constructors).is_none() {
-        for (variant, rs_len) in root_constructors {
+        for (constructor, rs_len) in root_constructors {
           let mut new_q = q_rest.clone();
           for _ in 0..rs_len {
             new_q = cons(AbstractPatternNode::wildcard(), new_q);
           }
           if useful_internal(
             cx,
-            &convert_into_specialized_matrix(p, variant, rs_len),
+            &convert_into_specialized_matrix(p, constructor, rs_len),
             PatternVector(new_q),
           ) {
             return true;
@@ -211,14 +234,14 @@ fn useful_internal<CX: PatternMatchingContext>(
 fn convert_into_specialized_matrix_row(
   new_rows: &mut Vec<PatternVector>,
   PatternVector(p_row): &PatternVector,
-  variant: Option<VariantPatternConstructor>,
+  constructor: PatternConstructor,
   rs_len: usize,
 ) {
   let p_first = p_row.first().unwrap();
   match p_first.0.as_ref() {
-    AbstractPatternNodeInner::StructLike { variant: p_last_variant, elements: rs } => {
-      match (p_last_variant.as_ref(), variant) {
-        (Some(a), Some(b)) if a != &b => {
+    AbstractPatternNodeInner::StructLike { constructor: p_first_constructor, elements: rs } => {
+      match (p_first_constructor, constructor) {
+        (a, b) if *a != b && *a != PatternConstructor::Tuple && b != PatternConstructor::Tuple => {
           // Different constructors. Skip
         }
         _ => {
@@ -238,7 +261,7 @@ fn convert_into_specialized_matrix_row(
         convert_into_specialized_matrix_row(
           new_rows,
           &PatternVector(cons(r.dupe(), p_row.rest())),
-          variant,
+          constructor,
           rs_len,
         )
       }
@@ -248,25 +271,25 @@ fn convert_into_specialized_matrix_row(
 
 fn convert_into_specialized_matrix(
   p: &PatternMatrix,
-  variant: Option<VariantPatternConstructor>,
+  constructor: PatternConstructor,
   rs_len: usize,
 ) -> PatternMatrix {
   let mut new_rows = Vec::new();
   for p_row in &p.0 {
-    convert_into_specialized_matrix_row(&mut new_rows, p_row, variant, rs_len)
+    convert_into_specialized_matrix_row(&mut new_rows, p_row, constructor, rs_len)
   }
   PatternMatrix(new_rows)
 }
 
-fn find_roots_constructors(p: &PatternMatrix) -> HashMap<Option<VariantPatternConstructor>, usize> {
+fn find_roots_constructors(p: &PatternMatrix) -> HashMap<PatternConstructor, usize> {
   let mut root_constructors = HashMap::new();
   let mut find_constructor_pattern_queue =
     p.0.iter().filter_map(|r| r.0.first()).collect::<VecDeque<_>>();
   while let Some(pattern) = find_constructor_pattern_queue.pop_front() {
     match pattern.0.as_ref() {
       AbstractPatternNodeInner::Wildcard => {}
-      AbstractPatternNodeInner::StructLike { variant, elements } => {
-        root_constructors.insert(*variant, elements.len());
+      AbstractPatternNodeInner::StructLike { constructor, elements } => {
+        root_constructors.insert(*constructor, elements.len());
       }
       AbstractPatternNodeInner::Or(possibilities) => {
         find_constructor_pattern_queue.extend(possibilities.iter())
@@ -298,17 +321,34 @@ fn default_matrix(p: &PatternMatrix) -> PatternMatrix {
 
 fn signature_incomplete_names<CX: PatternMatchingContext>(
   cx: &CX,
-  root_constructors: &HashMap<Option<VariantPatternConstructor>, usize>,
-) -> Option<Vec<(VariantPatternConstructor, usize)>> {
-  if root_constructors.contains_key(&None) {
+  root_constructors: &HashMap<PatternConstructor, usize>,
+) -> Option<Vec<(PatternConstructor, usize)>> {
+  if root_constructors.contains_key(&PatternConstructor::Tuple) {
     return None;
   }
   if root_constructors.is_empty() {
     return Some(Vec::with_capacity(0));
   }
+  let literals = root_constructors
+    .keys()
+    .filter_map(|c| if let PatternConstructor::Literal(l) = c { Some(*l) } else { None })
+    .collect_vec();
+  if !literals.is_empty() {
+    if !literals.iter().all(|l| matches!(l, Literal::Bool(_))) {
+      return Some(Vec::with_capacity(0));
+    }
+    let missing = [Literal::Bool(false), Literal::Bool(true)]
+      .into_iter()
+      .filter(|b| !literals.contains(b))
+      .map(|b| (PatternConstructor::Literal(b), 0))
+      .collect_vec();
+    return if missing.is_empty() { None } else { Some(missing) };
+  }
   let mut variants_grouped = Vec::new();
-  for (key, group) in
-    &root_constructors.keys().filter_map(|c| *c).chunk_by(|c| (c.module_reference, c.class_name))
+  for (key, group) in &root_constructors
+    .keys()
+    .filter_map(|c| if let PatternConstructor::Variant(v) = c { Some(*v) } else { None })
+    .chunk_by(|c| (c.module_reference, c.class_name))
   {
     variants_grouped.push((key, group.map(|g| g.variant_name).collect_vec()));
   }
@@ -319,7 +359,9 @@ fn signature_incomplete_names<CX: PatternMatchingContext>(
     .variant_signature_incomplete_names(mod_ref, class_name, &variants)
     .into_iter()
     .map(|(n, size)| {
-      (VariantPatternConstructor { module_reference: mod_ref, class_name, variant_name: n }, size)
+      let variant =
+        VariantPatternConstructor { module_reference: mod_ref, class_name, variant_name: n };
+      (PatternConstructor::Variant(variant), size)
     })
     .collect_vec();
   if result.is_empty() { None } else { Some(result) }
@@ -341,9 +383,9 @@ fn incomplete_counterexample_internal<CX: PatternMatchingContext>(
   let root_constructors = find_roots_constructors(p);
   if let Some(incomplete_names) = signature_incomplete_names(cx, &root_constructors) {
     let incomplete_vector = incomplete_counterexample_internal(cx, &default_matrix(p), n - 1)?;
-    let head = if let Some((variant, size)) = incomplete_names.into_iter().min() {
-      AbstractPatternNode::variant(
-        variant,
+    let head = if let Some((constructor, size)) = incomplete_names.into_iter().min() {
+      AbstractPatternNode::struct_like(
+        constructor,
         (0..size).map(|_| AbstractPatternNode::wildcard()).collect(),
       )
     } else {
@@ -351,10 +393,10 @@ fn incomplete_counterexample_internal<CX: PatternMatchingContext>(
     };
     Some(PatternVector(cons(head, incomplete_vector.0)))
   } else {
-    for (variant, a_k) in root_constructors.into_iter().sorted_by_key(|(k, _)| *k) {
+    for (constructor, a_k) in root_constructors.into_iter().sorted_by_key(|(k, _)| *k) {
       if let Some(incomplete_vector) = incomplete_counterexample_internal(
         cx,
-        &convert_into_specialized_matrix(p, variant, a_k),
+        &convert_into_specialized_matrix(p, constructor, a_k),
         a_k + n - 1,
       ) {
         let mut split_remaining_count = a_k;
@@ -367,7 +409,7 @@ fn incomplete_counterexample_internal<CX: PatternMatchingContext>(
           split_remaining_count -= 1;
         }
         return Some(PatternVector(cons(
-          AbstractPatternNode::tuple_or_variant(variant, first_part_vec),
+          AbstractPatternNode::struct_like(constructor, first_part_vec),
           split_remaining,
         )));
       }
@@ -383,6 +425,7 @@ mod tests {
   };
   use dupe::Dupe;
   use pretty_assertions::assert_eq;
+  use samlang_ast::source::Literal;
   use samlang_heap::{ModuleReference, PStr};
 
   const OPTION: PStr = PStr::six_letter_literal(b"Option");
@@ -708,6 +751,31 @@ mod tests {
     );
   }
 
+  #[test]
+  fn literal_patterns_test() {
+    let int = |i| P::literal(Literal::Int(i, None));
+    let bool_ = |b| P::literal(Literal::Bool(b));
+    assert_eq!(
+      (true, Some("_".to_string())),
+      useful_and_counter_example(&[&[int(0)], &[int(1)]], &[P::wildcard()])
+    );
+    assert_eq!(false, useful(&[&[int(0)], &[int(1)]], &[int(1)]));
+    assert_eq!(true, useful(&[&[int(0)], &[int(1)]], &[int(2)]));
+    assert_eq!(false, useful(&[&[int(0)], &[P::wildcard()]], &[int(2)]));
+    assert_eq!(
+      (true, Some("false".to_string())),
+      useful_and_counter_example(&[&[bool_(true)]], &[P::wildcard()])
+    );
+    assert_eq!(false, useful(&[&[bool_(true)], &[bool_(false)]], &[P::wildcard()]));
+    assert_eq!(
+      (true, Some("(true, false)".to_string())),
+      useful_and_counter_example(
+        &[&[bool_(false), P::wildcard()], &[P::wildcard(), bool_(true)]],
+        &[P::wildcard(), P::wildcard()]
+      )
+    );
+  }
+
   #[test]
   fn or_pattern_edge_cases_test() {
     let single = P::wildcard();
@@ -67,6 +67,9 @@ struct SsaAnalysisState<'a> {
   def_locs: HashSet<Location>,
   local_scoped_def_locs: HashMap<Location, HashMap<PStr, Location>>,
   lambda_captures: HashMap<Location, HashMap<PStr, Location>>,
+  /// Definition locations of `var` declarations, mapped to the lambda depth they are defined at.
+  mutable_defs: HashMap<Location, usize>,
+  lambda_depth: usize,
   context: SsaLocalStackedContext,
   error_set: &'a mut ErrorSet,
 }
@@ -81,6 +84,8 @@ impl<'a> SsaAnalysisState<'a> {
       def_locs: HashSet::new(),
       local_scoped_def_locs: HashMap::new(),
       lambda_captures: HashMap::new(),
+      mutable_defs: HashMap::new(),
+      lambda_depth: 0,
       context: SsaLocalStackedContext::new(),
       error_set,
     }
@@ -104,7 +109,9 @@ impl<'a> SsaAnalysisState<'a> {
       let type_definition = toplevel.type_definition();
 
       for t in toplevel.extends_or_implements_nodes().iter().flat_map(|it| &it.nodes) {
-        self.use_id(&t.id.name, t.id.loc, true);
+        if self.module_reference.eq(&t.module_reference) {
+          self.use_id(&t.id.name, t.id.loc, true);
+        }
       }
 
       self.context.push_scope();
@@ -153,6 +160,11 @@ impl<'a> SsaAnalysisState<'a> {
               self.define_id(name.name, name.loc);
             }
           }
+          match toplevel {
+            Toplevel::TypeAlias(alias) => self.visit_annot(&alias.annotation),
+            Toplevel::Const(c) => self.visit_annot(&c.annotation),
+            Toplevel::Interface(_) | Toplevel::Class(_) => {}
+          }
         }
         self.context.pop_scope();
 
@@ -166,8 +178,11 @@ impl<'a> SsaAnalysisState<'a> {
         self.context.pop_scope();
         // Visit instance methods
         self.context.push_scope();
-        if toplevel.is_class() {
-          self.define_id(PStr::THIS, toplevel.loc());
+        match toplevel {
+          Toplevel::Interface(_) | Toplevel::Class(_) => {
+            self.define_id(PStr::THIS, toplevel.loc());
+          }
+          Toplevel::TypeAlias(_) | Toplevel::Const(_) => {}
         }
         for tparam in type_parameters.iter().flat_map(|it| &it.parameters) {
           let id = &tparam.name;
@@ -195,11 +210,17 @@ impl<'a> SsaAnalysisState<'a> {
       }
       Toplevel::Interface(d) => {
         for m in &d.members.members {
-          if m.is_method == is_method {
-            self.visit_member_declaration(m, None);
+          if m.decl.is_method == is_method {
+            self.visit_member_declaration(&m.decl, m.default_body.as_ref());
           }
         }
       }
+      Toplevel::TypeAlias(_) => {}
+      Toplevel::Const(c) => {
+        if !is_method {
+          self.visit_expression(&c.value);
+        }
+      }
     }
   }
 
@@ -212,6 +233,9 @@ impl<'a> SsaAnalysisState<'a> {
     self.visit_type_parameters_with_bounds(member.type_parameters.as_ref());
     for param in member.parameters.parameters.iter() {
       self.visit_annot(&param.annotation);
+      if let Some(default_value) = &param.default_value {
+        self.visit_expression(default_value);
+      }
     }
     self.visit_annot(&member.return_type);
     self.context.push_scope();
@@ -266,6 +290,17 @@ impl<'a> SsaAnalysisState<'a> {
           self.visit_expression(e);
         }
       }
+      expr::E::TemplateString(e) => {
+        for hole in &e.holes {
+          self.visit_expression(hole);
+        }
+      }
+      expr::E::StructUpdate(e) => {
+        self.visit_expression(&e.base);
+        for field in &e.fields {
+          self.visit_expression(&field.value);
+        }
+      }
       expr::E::FieldAccess(e) => {
         self.visit_expression(&e.object);
         for targ in e.explicit_type_arguments.iter().flat_map(|it| &it.arguments) {
@@ -295,30 +330,57 @@ impl<'a> SsaAnalysisState<'a> {
         for case in &e.cases {
           self.context.push_scope();
           self.visit_matching_pattern(&case.pattern);
+          if let Some(guard) = &case.guard {
+            self.visit_expression(guard);
+          }
           self.visit_expression(&case.body);
           let (local_defs, _) = self.context.pop_scope();
           self.local_scoped_def_locs.insert(case.loc, local_defs);
         }
       }
-      expr::E::Lambda(e) => {
+      expr::E::Lambda(e) => self.visit_lambda(e),
+      expr::E::Return(e) => self.visit_expression(&e.argument),
+      expr::E::While(e) => {
+        self.visit_expression(&e.condition);
+        self.visit_block(&e.body);
+      }
+      expr::E::For(e) => {
+        self.visit_expression(&e.start);
+        self.visit_expression(&e.end);
         self.context.push_scope();
-        for OptionallyAnnotatedId { name, type_: _, annotation } in &e.parameters.parameters {
-          self.define_id(name.name, name.loc);
-          if let Some(annot) = annotation {
-            self.visit_annot(annot)
-          }
+        self.define_id(e.iterator.name, e.iterator.loc);
+        self.visit_block(&e.body);
+        self.context.pop_scope();
+      }
+      expr::E::Break(_) => {}
+      expr::E::Assert(e) => {
+        for arg in &e.arguments.expressions {
+          self.visit_expression(arg);
         }
-        self.visit_expression(&e.body);
-        let (local_defs, captured) = self.context.pop_scope();
-        self.local_scoped_def_locs.insert(e.common.loc, local_defs);
-        self.lambda_captures.insert(e.common.loc, captured);
       }
+      expr::E::Try(e) => self.visit_expression(&e.argument),
       expr::E::Block(e) => {
         self.visit_block(e);
       }
     }
   }
 
+  fn visit_lambda(&mut self, lambda: &expr::Lambda<()>) {
+    self.lambda_depth += 1;
+    self.context.push_scope();
+    for OptionallyAnnotatedId { name, type_: _, annotation } in &lambda.parameters.parameters {
+      self.define_id(name.name, name.loc);
+      if let Some(annot) = annotation {
+        self.visit_annot(annot)
+      }
+    }
+    self.visit_expression(&lambda.body);
+    self.lambda_depth -= 1;
+    let (local_defs, captured) = self.context.pop_scope();
+    self.local_scoped_def_locs.insert(lambda.common.loc, local_defs);
+    self.lambda_captures.insert(lambda.common.loc, captured);
+  }
+
   fn visit_if_else(&mut self, if_else: &expr::IfElse<()>) {
     match if_else.condition.as_ref() {
       expr::IfElseCondition::Expression(guard) => {
@@ -353,7 +415,30 @@ impl<'a> SsaAnalysisState<'a> {
           if let Some(annot) = &decl.annotation {
             self.visit_annot(annot);
           }
+          // The bindings of a `let ... else` declaration are not in scope in its else block.
+          if let Some(else_block) = &decl.else_block {
+            self.visit_block(else_block);
+          }
           self.visit_matching_pattern(&decl.pattern);
+          if let (true, pattern::MatchingPattern::Id(id, ())) = (decl.is_mutable, &decl.pattern) {
+            self.mutable_defs.insert(id.loc, self.lambda_depth);
+          }
+        }
+        expr::Statement::Assignment(assign) => {
+          self.visit_expression(&assign.assigned_expression);
+          let name = &assign.name;
+          self.use_id(&name.name, name.loc, false);
+          if let Some(def_loc) = self.use_define_map.get(&name.loc)
+            && !self.mutable_defs.contains_key(def_loc)
+          {
+            self.error_set.report_cannot_assign_to_immutable_error(name.loc, name.name);
+          }
+        }
+        expr::Statement::Function(function) => {
+          // The name is defined first, so that the body can capture it to call itself.
+          self.define_id(function.name.name, function.name.loc);
+          self.visit_annot(&function.return_annotation);
+          self.visit_lambda(&function.lambda);
         }
         expr::Statement::Expression(expr) => {
           self.visit_expression(expr);
@@ -387,7 +472,7 @@ impl<'a> SsaAnalysisState<'a> {
         }
       }
       pattern::MatchingPattern::Id(id, ()) => self.define_id(id.name, id.loc),
-      pattern::MatchingPattern::Wildcard { .. } => {}
+      pattern::MatchingPattern::Wildcard { .. } | pattern::MatchingPattern::Literal { .. } => {}
       pattern::MatchingPattern::Or { patterns, .. } => {
         let mut iter = patterns.iter();
         self.visit_matching_pattern(iter.next().unwrap());
@@ -421,7 +506,9 @@ impl<'a> SsaAnalysisState<'a> {
         }
       }
       pattern::MatchingPattern::Id(id, ()) => self.use_id(&id.name, id.loc, false),
-      pattern::MatchingPattern::Wildcard { .. } | pattern::MatchingPattern::Or { .. } => {}
+      pattern::MatchingPattern::Wildcard { .. }
+      | pattern::MatchingPattern::Literal { .. }
+      | pattern::MatchingPattern::Or { .. } => {}
     }
   }
 
@@ -459,6 +546,11 @@ impl<'a> SsaAnalysisState<'a> {
         }
         self.visit_annot(return_type);
       }
+      annotation::T::Tuple(annot) => {
+        for element in &annot.annotations {
+          self.visit_annot(element);
+        }
+      }
     }
   }
 
@@ -475,6 +567,11 @@ impl<'a> SsaAnalysisState<'a> {
 
   fn use_id(&mut self, name: &PStr, loc: Location, for_type: bool) {
     if let Some(definition) = self.context.get(name, for_type) {
+      if let Some(def_lambda_depth) = self.mutable_defs.get(definition)
+        && *def_lambda_depth < self.lambda_depth
+      {
+        self.error_set.report_cannot_capture_mutable_variable_error(loc, *name);
+      }
       self.use_define_map.insert(loc, *definition);
     } else {
       self.unbound_names.insert(*name);
@@ -546,7 +643,6 @@ impl SsaAnalysisResult {
   }
 }
 
-#[cfg(test)]
 pub(super) fn perform_ssa_analysis_on_expression(
   module_reference: ModuleReference,
   expression: &expr::E<()>,
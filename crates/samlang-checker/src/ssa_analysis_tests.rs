@@ -5,6 +5,7 @@ mod tests {
     type_::{ISourceType, test_type_builder},
     typing_context::LocalTypingContext,
   };
+  use itertools::Itertools;
   use pretty_assertions::assert_eq;
   use samlang_ast::{
     Location,
@@ -207,7 +208,9 @@ def_to_use_map:
 23:26-23:27 -> [23:26-23:27]
 23:7-23:22 -> [23:7-23:22]
 2:10-2:14 -> [2:10-2:14]
+4:1-4:21 -> [4:1-4:21]
 4:11-4:18 -> [10:52-10:59, 4:11-4:18, 6:27-6:34]
+6:1-8:2 -> [6:1-8:2]
 6:11-6:21 -> [10:30-10:40, 14:15-14:25, 15:16-15:26, 16:16-16:26, 6:11-6:21]
 6:22-6:23 -> [6:22-6:23, 7:25-7:26]
 7:10-7:17 -> [7:10-7:17]
@@ -224,6 +227,27 @@ def_to_use_map:
     assert!(cx.get_captured(&Location::from_pos(17, 39, 17, 54)).is_empty());
   }
 
+  #[test]
+  fn local_function_test() {
+    let mut heap = Heap::new();
+    let mut error_set = ErrorSet::new();
+    let (_, expr) = samlang_parser::parse_source_expression_from_text(
+      "{ let a = 1; function f(n: int): int = f(n + a); f(1) }",
+      ModuleReference::DUMMY,
+      &mut heap,
+      &mut error_set,
+    );
+    assert_eq!(false, error_set.has_errors());
+    let analysis_result = ssa_analysis::perform_ssa_analysis_on_expression(
+      ModuleReference::DUMMY,
+      &expr,
+      &mut error_set,
+    );
+    assert!(analysis_result.unbound_names.is_empty());
+    let captured = analysis_result.lambda_captures.values().exactly_one().unwrap();
+    assert_eq!(vec!["a", "f"], captured.keys().map(|n| n.as_str(&heap)).sorted().collect_vec());
+  }
+
   #[test]
   fn or_pattern_test() {
     let mut heap = Heap::new();
@@ -3,7 +3,7 @@ use enum_as_inner::EnumAsInner;
 use itertools::Itertools;
 use samlang_ast::{
   Description, Location, Reason,
-  source::{ClassMemberDeclaration, annotation},
+  source::{ClassMemberDeclaration, Visibility, annotation, expr},
 };
 use samlang_heap::{Heap, ModuleReference, PStr};
 use std::{collections::HashMap, sync::Arc};
@@ -13,6 +13,7 @@ pub enum PrimitiveTypeKind {
   Unit,
   Bool,
   Int,
+  Float,
 }
 
 impl std::fmt::Display for PrimitiveTypeKind {
@@ -21,6 +22,7 @@ impl std::fmt::Display for PrimitiveTypeKind {
       Self::Unit => write!(f, "unit"),
       Self::Bool => write!(f, "bool"),
       Self::Int => write!(f, "int"),
+      Self::Float => write!(f, "float"),
     }
   }
 }
@@ -31,6 +33,7 @@ impl PrimitiveTypeKind {
       PrimitiveTypeKind::Unit => Description::UnitType,
       PrimitiveTypeKind::Bool => Description::BoolType,
       PrimitiveTypeKind::Int => Description::IntType,
+      PrimitiveTypeKind::Float => Description::FloatType,
     }
   }
 }
@@ -190,6 +193,8 @@ pub enum Type {
   Nominal(NominalType),
   Generic(Reason, PStr),
   Fn(FunctionType),
+  /// A structural tuple type of any arity.
+  Tuple(Reason, Vec<Arc<Type>>),
 }
 
 impl ISourceType for Type {
@@ -202,6 +207,9 @@ impl ISourceType for Type {
       Self::Nominal(t) => t.pretty_print(heap),
       Self::Generic(_, s) => s.as_str(heap).to_string(),
       Self::Fn(t) => t.pretty_print(heap),
+      Self::Tuple(_, element_types) => {
+        format!("({})", element_types.iter().map(|t| t.pretty_print(heap)).join(", "))
+      }
     }
   }
 
@@ -212,6 +220,9 @@ impl ISourceType for Type {
       Self::Nominal(t) => t.to_description(),
       Self::Generic(_, s) => Description::GenericType(*s),
       Self::Fn(t) => t.to_description(),
+      Self::Tuple(_, element_types) => {
+        Description::TupleType(element_types.iter().map(|t| t.to_description()).collect())
+      }
     }
   }
 
@@ -222,6 +233,10 @@ impl ISourceType for Type {
       (Self::Nominal(n1), Self::Nominal(n2)) => n1.is_the_same_type(n2),
       (Self::Generic(_, s1), Self::Generic(_, s2)) => s1 == s2,
       (Self::Fn(f1), Self::Fn(f2)) => f1.is_the_same_type(f2),
+      (Self::Tuple(_, elements1), Self::Tuple(_, elements2)) => {
+        elements1.len() == elements2.len()
+          && elements1.iter().zip(elements2.iter()).all(|(a, b)| a.is_the_same_type(b))
+      }
       _ => false,
     }
   }
@@ -238,6 +253,39 @@ impl Type {
     Self::Primitive(reason, PrimitiveTypeKind::Int)
   }
 
+  /// The element types of a class in `std.tuples` like `Pair<A, B>`, which a tuple of the same
+  /// arity converts to.
+  pub fn std_tuple_class_elements(&self) -> Option<&[Arc<Type>]> {
+    const STD_TUPLE_CLASSES: [PStr; 15] = [
+      PStr::PAIR,
+      PStr::TRIPLE,
+      PStr::TUPLE_4,
+      PStr::TUPLE_5,
+      PStr::TUPLE_6,
+      PStr::TUPLE_7,
+      PStr::TUPLE_8,
+      PStr::TUPLE_9,
+      PStr::TUPLE_10,
+      PStr::TUPLE_11,
+      PStr::TUPLE_12,
+      PStr::TUPLE_13,
+      PStr::TUPLE_14,
+      PStr::TUPLE_15,
+      PStr::TUPLE_16,
+    ];
+    let nominal_type = self.as_nominal()?;
+    let arity = nominal_type.type_arguments.len();
+    if nominal_type.module_reference == ModuleReference::STD_TUPLES
+      && !nominal_type.is_class_statics
+      && arity >= 2
+      && STD_TUPLE_CLASSES.get(arity - 2) == Some(&nominal_type.id)
+    {
+      Some(&nominal_type.type_arguments)
+    } else {
+      None
+    }
+  }
+
   pub fn get_reason(&self) -> &Reason {
     match self {
       Self::Any(reason, _) => reason,
@@ -245,6 +293,7 @@ impl Type {
       Self::Nominal(NominalType { reason, .. }) => reason,
       Self::Generic(reason, _) => reason,
       Self::Fn(FunctionType { reason, .. }) => reason,
+      Self::Tuple(reason, _) => reason,
     }
   }
 
@@ -273,6 +322,9 @@ impl Type {
         argument_types: argument_types.clone(),
         return_type: return_type.dupe(),
       }),
+      Self::Tuple(reason, element_types) => {
+        Self::Tuple(reason.to_use_reason(use_loc), element_types.clone())
+      }
     }
   }
 
@@ -287,12 +339,19 @@ impl Type {
       annotation::T::Primitive(loc, _, annotation::PrimitiveTypeKind::Int) => {
         Self::Primitive(Reason::new(*loc, Some(*loc)), PrimitiveTypeKind::Int)
       }
+      annotation::T::Primitive(loc, _, annotation::PrimitiveTypeKind::Float) => {
+        Self::Primitive(Reason::new(*loc, Some(*loc)), PrimitiveTypeKind::Float)
+      }
       annotation::T::Primitive(loc, _, annotation::PrimitiveTypeKind::Any) => {
         Self::Any(Reason::new(*loc, Some(*loc)), false)
       }
       annotation::T::Id(annot) => Self::Nominal(NominalType::from_annotation(annot)),
       annotation::T::Generic(loc, id) => Self::Generic(Reason::new(*loc, Some(*loc)), id.name),
       annotation::T::Fn(annot) => Self::Fn(FunctionType::from_annotation(annot)),
+      annotation::T::Tuple(annot) => Self::Tuple(
+        Reason::new(annot.location, Some(annot.location)),
+        annot.annotations.iter().map(|annot| Arc::new(Type::from_annotation(annot))).collect(),
+      ),
     }
   }
 }
@@ -352,6 +411,9 @@ pub mod test_type_builder {
     pub fn int_type(&self) -> Arc<Type> {
       Arc::new(Type::int_type(self.reason))
     }
+    pub fn float_type(&self) -> Arc<Type> {
+      Arc::new(Type::Primitive(self.reason, PrimitiveTypeKind::Float))
+    }
 
     pub fn string_type(&self) -> Arc<Type> {
       Arc::new(Type::Nominal(NominalType {
@@ -402,6 +464,10 @@ pub mod test_type_builder {
     pub fn fun_type(&self, argument_types: Vec<Arc<Type>>, return_type: Arc<Type>) -> Arc<Type> {
       Arc::new(Type::Fn(FunctionType { reason: self.reason, argument_types, return_type }))
     }
+
+    pub fn tuple_type(&self, element_types: Vec<Arc<Type>>) -> Arc<Type> {
+      Arc::new(Type::Tuple(self.reason, element_types))
+    }
   }
 
   pub fn create() -> CustomizedTypeBuilder {
@@ -409,25 +475,53 @@ pub mod test_type_builder {
   }
 }
 
+#[derive(Clone)]
+pub struct ParameterSignature {
+  pub name: PStr,
+  /// The constant default value, with the module and class in whose scope it's checked.
+  pub default_value: Option<(ModuleReference, PStr, expr::E<()>)>,
+}
+
+impl ParameterSignature {
+  pub fn from_function(
+    module_reference: ModuleReference,
+    class_name: PStr,
+    f: &ClassMemberDeclaration,
+  ) -> Vec<ParameterSignature> {
+    f.parameters
+      .parameters
+      .iter()
+      .map(|id| ParameterSignature {
+        name: id.name.name,
+        default_value: id.default_value.clone().map(|e| (module_reference, class_name, e)),
+      })
+      .collect()
+  }
+}
+
 pub struct MemberSignature {
-  pub is_public: bool,
+  pub visibility: Visibility,
+  /// Whether an interface provides a default implementation that implementing classes inherit.
+  pub has_default: bool,
   pub type_parameters: Vec<TypeParameterSignature>,
   pub type_: FunctionType,
+  /// Names and default values of the parameters.
+  /// It's empty for functions whose parameters can only be passed positionally.
+  pub parameters: Vec<ParameterSignature>,
 }
 
 impl MemberSignature {
   #[cfg(test)]
   pub fn to_string(&self, heap: &Heap) -> String {
-    let access_str = if self.is_public { "public" } else { "private" };
     let tparam_str = TypeParameterSignature::pretty_print_list(&self.type_parameters, heap);
-    format!("{} {}{}", access_str, tparam_str, self.type_.pretty_print(heap))
+    format!("{} {}{}", self.visibility.as_str(), tparam_str, self.type_.pretty_print(heap))
   }
 }
 
 impl MemberSignature {
   fn create_custom_builtin_function(
     name: PStr,
-    is_public: bool,
+    visibility: Visibility,
     argument_types: Vec<Arc<Type>>,
     return_type: Arc<Type>,
     type_parameters: Vec<PStr>,
@@ -435,12 +529,14 @@ impl MemberSignature {
     (
       name,
       MemberSignature {
-        is_public,
+        visibility,
+        has_default: false,
         type_parameters: type_parameters
           .into_iter()
           .map(|name| TypeParameterSignature { name, bound: None })
           .collect_vec(),
         type_: FunctionType { reason: Reason::builtin(), argument_types, return_type },
+        parameters: Vec::new(),
       },
     )
   }
@@ -453,7 +549,7 @@ impl MemberSignature {
   ) -> (PStr, MemberSignature) {
     MemberSignature::create_custom_builtin_function(
       name,
-      true,
+      Visibility::Public,
       argument_types,
       return_type,
       type_parameters,
@@ -469,7 +565,7 @@ impl MemberSignature {
   ) -> (PStr, MemberSignature) {
     MemberSignature::create_custom_builtin_function(
       name,
-      false,
+      Visibility::Private,
       argument_types,
       return_type,
       type_parameters,
@@ -477,22 +573,32 @@ impl MemberSignature {
   }
 
   pub fn pretty_print(&self, name: &str, heap: &Heap) -> String {
-    let access_str = if self.is_public { "public" } else { "private" };
     let tparam_str = TypeParameterSignature::pretty_print_list(&self.type_parameters, heap);
-    format!("{} {}{}{}", access_str, name, tparam_str, self.type_.pretty_print(heap))
+    format!("{} {}{}{}", self.visibility.as_str(), name, tparam_str, self.type_.pretty_print(heap))
   }
 
   pub(super) fn reposition(&self, use_loc: Location) -> MemberSignature {
     MemberSignature {
-      is_public: self.is_public,
+      visibility: self.visibility,
+      has_default: self.has_default,
       type_parameters: self.type_parameters.clone(),
       type_: self.type_.clone().reposition(use_loc),
+      parameters: self.parameters.clone(),
     }
   }
 }
 
+fn visibility_prefix(visibility: Visibility) -> &'static str {
+  match visibility {
+    Visibility::Private => "private ",
+    Visibility::Protected => "protected ",
+    Visibility::Internal => "internal ",
+    Visibility::Public => "",
+  }
+}
+
 pub struct InterfaceSignature {
-  pub private: bool,
+  pub visibility: Visibility,
   pub type_definition: Option<TypeDefinitionSignature>,
   pub functions: HashMap<PStr, MemberSignature>,
   pub methods: HashMap<PStr, MemberSignature>,
@@ -506,7 +612,7 @@ impl InterfaceSignature {
     let mut lines = Vec::new();
     lines.push(format!(
       "{}{} {} : [{}]",
-      if self.private { "private " } else { "" },
+      visibility_prefix(self.visibility),
       if let Some(type_def) = &self.type_definition {
         format!("class({})", type_def.to_string(heap))
       } else {
@@ -579,8 +685,43 @@ impl TypeDefinitionSignature {
   }
 }
 
+pub struct TypeAliasSignature {
+  pub visibility: Visibility,
+  pub type_parameters: Vec<TypeParameterSignature>,
+  pub type_: Arc<Type>,
+}
+
+impl TypeAliasSignature {
+  pub fn to_string(&self, heap: &Heap) -> String {
+    format!(
+      "{}type{} = {}",
+      visibility_prefix(self.visibility),
+      TypeParameterSignature::pretty_print_list(&self.type_parameters, heap),
+      self.type_.pretty_print(heap)
+    )
+  }
+}
+
+pub struct ConstSignature {
+  pub visibility: Visibility,
+  pub type_: Arc<Type>,
+  /// Toplevel names referenced by the value. Not all of them are necessarily constants.
+  pub dependencies: Vec<(ModuleReference, PStr)>,
+}
+
+impl ConstSignature {
+  pub fn to_string(&self, heap: &Heap) -> String {
+    format!("{}const {}", visibility_prefix(self.visibility), self.type_.pretty_print(heap))
+  }
+}
+
 pub struct ModuleSignature {
+  /// Module path without the last part.
+  /// Internal toplevels and members are visible to all modules under this path.
+  pub package: Vec<PStr>,
   pub interfaces: HashMap<PStr, InterfaceSignature>,
+  pub type_aliases: HashMap<PStr, TypeAliasSignature>,
+  pub constants: HashMap<PStr, ConstSignature>,
 }
 
 impl ModuleSignature {
@@ -591,6 +732,18 @@ impl ModuleSignature {
     for (name, i) in self.interfaces.iter().sorted_by(|p1, p2| p1.0.cmp(p2.0)) {
       lines.push(format!("{}: {}", name.as_str(heap), i.to_string(heap)));
     }
+    if !self.type_aliases.is_empty() {
+      lines.push("\ntype aliases:".to_string());
+      for (name, a) in self.type_aliases.iter().sorted_by(|p1, p2| p1.0.cmp(p2.0)) {
+        lines.push(format!("{}: {}", name.as_str(heap), a.to_string(heap)));
+      }
+    }
+    if !self.constants.is_empty() {
+      lines.push("\nconstants:".to_string());
+      for (name, c) in self.constants.iter().sorted_by(|p1, p2| p1.0.cmp(p2.0)) {
+        lines.push(format!("{}: {}", name.as_str(heap), c.to_string(heap)));
+      }
+    }
     lines.join("\n")
   }
 }
@@ -599,6 +752,7 @@ pub fn create_builtin_module_signature() -> ModuleSignature {
   let int_t = || Arc::new(Type::Primitive(Reason::builtin(), PrimitiveTypeKind::Int));
   let unit_t = || Arc::new(Type::Primitive(Reason::builtin(), PrimitiveTypeKind::Unit));
   let bool_t = || Arc::new(Type::Primitive(Reason::builtin(), PrimitiveTypeKind::Bool));
+  let float_t = || Arc::new(Type::Primitive(Reason::builtin(), PrimitiveTypeKind::Float));
   let str_t = || {
     Arc::new(Type::Nominal(NominalType {
       reason: Reason::builtin(),
@@ -619,11 +773,14 @@ pub fn create_builtin_module_signature() -> ModuleSignature {
     }))
   };
   ModuleSignature {
+    package: Vec::new(),
+    type_aliases: HashMap::new(),
+    constants: HashMap::new(),
     interfaces: HashMap::from([
       (
         PStr::PROCESS_TYPE,
         InterfaceSignature {
-          private: false,
+          visibility: Visibility::Public,
           type_definition: Some(TypeDefinitionSignature::Enum(Vec::new())),
           type_parameters: Vec::new(),
           super_types: Vec::new(),
@@ -647,20 +804,111 @@ pub fn create_builtin_module_signature() -> ModuleSignature {
       (
         PStr::STR_TYPE,
         InterfaceSignature {
-          private: false,
+          visibility: Visibility::Public,
+          type_definition: Some(TypeDefinitionSignature::Enum(Vec::new())),
+          functions: HashMap::from([
+            MemberSignature::create_builtin_function(
+              PStr::FROM_INT,
+              vec![int_t()],
+              str_t(),
+              Vec::new(),
+            ),
+            MemberSignature::create_builtin_function(
+              PStr::FROM_FLOAT,
+              vec![float_t()],
+              str_t(),
+              Vec::new(),
+            ),
+          ]),
+          methods: HashMap::from([
+            MemberSignature::create_builtin_function(PStr::TO_INT, Vec::new(), int_t(), Vec::new()),
+            MemberSignature::create_builtin_function(PStr::LENGTH, Vec::new(), int_t(), Vec::new()),
+            MemberSignature::create_builtin_function(
+              PStr::BYTE_LENGTH,
+              Vec::new(),
+              int_t(),
+              Vec::new(),
+            ),
+            MemberSignature::create_builtin_function(
+              PStr::CHAR_AT,
+              vec![int_t()],
+              str_t(),
+              Vec::new(),
+            ),
+            MemberSignature::create_builtin_function(
+              PStr::CODE_POINT_AT,
+              vec![int_t()],
+              int_t(),
+              Vec::new(),
+            ),
+            MemberSignature::create_builtin_function(
+              PStr::SUBSTRING,
+              vec![int_t(), int_t()],
+              str_t(),
+              Vec::new(),
+            ),
+            MemberSignature::create_builtin_function(
+              PStr::INDEX_OF,
+              vec![str_t()],
+              int_t(),
+              Vec::new(),
+            ),
+            MemberSignature::create_builtin_function(
+              PStr::SPLIT,
+              vec![str_t()],
+              vec_of(str_t()),
+              Vec::new(),
+            ),
+            MemberSignature::create_builtin_function(PStr::TRIM, Vec::new(), str_t(), Vec::new()),
+            MemberSignature::create_builtin_function(
+              PStr::STARTS_WITH,
+              vec![str_t()],
+              bool_t(),
+              Vec::new(),
+            ),
+            MemberSignature::create_builtin_function(
+              PStr::COMPARE,
+              vec![str_t()],
+              int_t(),
+              Vec::new(),
+            ),
+            MemberSignature::create_builtin_function(
+              PStr::TO_UPPER,
+              Vec::new(),
+              str_t(),
+              Vec::new(),
+            ),
+            MemberSignature::create_builtin_function(
+              PStr::TO_LOWER,
+              Vec::new(),
+              str_t(),
+              Vec::new(),
+            ),
+          ]),
+          type_parameters: Vec::new(),
+          super_types: Vec::new(),
+        },
+      ),
+      (
+        PStr::FLOAT_TYPE,
+        InterfaceSignature {
+          visibility: Visibility::Public,
           type_definition: Some(TypeDefinitionSignature::Enum(Vec::new())),
-          functions: HashMap::from([MemberSignature::create_builtin_function(
-            PStr::FROM_INT,
-            vec![int_t()],
-            str_t(),
-            Vec::new(),
-          )]),
-          methods: HashMap::from([MemberSignature::create_builtin_function(
-            PStr::TO_INT,
-            Vec::new(),
-            int_t(),
-            Vec::new(),
-          )]),
+          functions: HashMap::from([
+            MemberSignature::create_builtin_function(
+              PStr::FROM_INT,
+              vec![int_t()],
+              float_t(),
+              Vec::new(),
+            ),
+            MemberSignature::create_builtin_function(
+              PStr::TO_INT,
+              vec![float_t()],
+              int_t(),
+              Vec::new(),
+            ),
+          ]),
+          methods: HashMap::new(),
           type_parameters: Vec::new(),
           super_types: Vec::new(),
         },
@@ -668,7 +916,7 @@ pub fn create_builtin_module_signature() -> ModuleSignature {
       (
         PStr::VEC_TYPE,
         InterfaceSignature {
-          private: false,
+          visibility: Visibility::Public,
           type_definition: Some(TypeDefinitionSignature::Enum(Vec::new())),
           type_parameters: vec![TypeParameterSignature { name: PStr::UPPER_T, bound: None }],
           super_types: Vec::new(),
@@ -911,7 +1159,7 @@ m2: public () -> any
 "#
       .trim(),
       InterfaceSignature {
-        private: true,
+        visibility: Visibility::Private,
         type_definition: Some(TypeDefinitionSignature::Struct(vec![
           StructItemDefinitionSignature {
             name: PStr::LOWER_A,
@@ -931,25 +1179,29 @@ m2: public () -> any
           (
             heap.alloc_str_for_test("m1"),
             MemberSignature {
-              is_public: true,
+              visibility: Visibility::Public,
+              has_default: false,
               type_parameters: Vec::new(),
               type_: FunctionType {
                 reason: Reason::dummy(),
                 argument_types: Vec::new(),
                 return_type: Arc::new(Type::Any(Reason::dummy(), false))
-              }
+              },
+              parameters: Vec::new(),
             }
           ),
           (
             heap.alloc_str_for_test("m2"),
             MemberSignature {
-              is_public: true,
+              visibility: Visibility::Public,
+              has_default: false,
               type_parameters: Vec::new(),
               type_: FunctionType {
                 reason: Reason::dummy(),
                 argument_types: Vec::new(),
                 return_type: Arc::new(Type::Any(Reason::dummy(), false))
-              }
+              },
+              parameters: Vec::new(),
             }
           )
         ]),
@@ -1131,7 +1383,7 @@ m2: public () -> any
       FunctionType::from_function(&ClassMemberDeclaration {
         loc: Location::dummy(),
         associated_comments: NO_COMMENT_REFERENCE,
-        is_public: false,
+        visibility: Visibility::Private,
         is_method: false,
         name: Id::from(PStr::LOWER_A),
         type_parameters: None,
@@ -1142,7 +1394,8 @@ m2: public () -> any
           parameters: Arc::new(vec![AnnotatedId {
             name: Id::from(PStr::LOWER_A),
             type_: (),
-            annotation: builder.bool_annot()
+            annotation: builder.bool_annot(),
+            default_value: None,
           }])
         },
         return_type: builder.general_id_annot(
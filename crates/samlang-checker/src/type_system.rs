@@ -17,6 +17,7 @@ pub(super) fn contains_placeholder(type_: &Type) -> bool {
       f.argument_types.iter().any(|t| contains_placeholder(t))
         || contains_placeholder(&f.return_type)
     }
+    Type::Tuple(_, element_types) => element_types.iter().any(|t| contains_placeholder(t)),
   }
 }
 
@@ -62,6 +63,19 @@ fn assignability_check_visit(lower: &Type, upper: &Type, error_stack: &mut Stack
           .add_fn_param_arity_error(lower_f.argument_types.len(), upper_f.argument_types.len());
       }
     }
+    (Type::Tuple(_, lower_elements), Type::Tuple(_, upper_elements)) => {
+      if lower_elements.len() == upper_elements.len() {
+        if lower_elements
+          .iter()
+          .zip(upper_elements)
+          .all(|(l, u)| assignability_check_visit(l, u, error_stack))
+        {
+          return true;
+        }
+      } else {
+        error_stack.add_tuple_arity_error(lower_elements.len(), upper_elements.len());
+      }
+    }
     (_, _) => {}
   }
   error_stack.add_type_incompatibility_error(
@@ -156,6 +170,25 @@ fn type_meet_visit(lower: &Type, upper: &Type, error_stack: &mut StackableError)
           .add_fn_param_arity_error(lower_f.argument_types.len(), upper_f.argument_types.len());
       }
     }
+    (Type::Tuple(lower_r, lower_elements), Type::Tuple(_, upper_elements)) => {
+      if lower_elements.len() == upper_elements.len() {
+        let mut element_types = Vec::with_capacity(lower_elements.len());
+        let mut passing = true;
+        for (l, u) in lower_elements.iter().zip(upper_elements) {
+          if let Some(t) = type_meet_visit(l, u, error_stack) {
+            element_types.push(Arc::new(t));
+          } else {
+            passing = false;
+            break;
+          }
+        }
+        if passing {
+          return Some(Type::Tuple(*lower_r, element_types));
+        }
+      } else {
+        error_stack.add_tuple_arity_error(lower_elements.len(), upper_elements.len());
+      }
+    }
     (_, _) => {}
   }
   error_stack.add_type_incompatibility_error(
@@ -227,6 +260,13 @@ fn solve_type_constraints_internal(
         )
       }
     }
+    Type::Tuple(_, g_elements) => {
+      if let Type::Tuple(_, c_elements) = concrete {
+        for (g_element, c_element) in g_elements.iter().zip(c_elements) {
+          solve_type_constraints_internal(c_element, g_element, type_parameters, partially_solved);
+        }
+      }
+    }
   }
 }
 
@@ -313,6 +353,10 @@ pub(super) fn subst_type(t: &Type, mapping: &HashMap<PStr, Arc<Type>>) -> Arc<Ty
       }
     }
     Type::Fn(f) => Arc::new(Type::Fn(subst_fn_type(f, mapping))),
+    Type::Tuple(reason, element_types) => Arc::new(Type::Tuple(
+      *reason,
+      element_types.iter().map(|it| subst_type(it, mapping)).collect(),
+    )),
   }
 }
 
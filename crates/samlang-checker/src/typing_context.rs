@@ -7,7 +7,10 @@ use super::{
   type_system,
 };
 use dupe::Dupe;
-use samlang_ast::{Description, Location, Position, Reason};
+use samlang_ast::{
+  Location, Position, Reason,
+  source::{Visibility, annotation, expr},
+};
 use samlang_errors::{ErrorSet, StackableError};
 use samlang_heap::{ModuleReference, PStr};
 use std::{collections::HashMap, sync::Arc};
@@ -59,6 +62,17 @@ impl LocalTypingContext {
   }
 }
 
+/// What a `return` expression in the expression being checked returns from.
+#[derive(Clone)]
+pub(crate) enum ReturnTarget {
+  /// The body of a function or method, with its declared return type.
+  Function(Arc<Type>),
+  /// The body of a lambda, where `return` is not allowed.
+  Lambda,
+  /// Code outside of any function body, e.g. a constant initializer or a default value.
+  OutsideFunction,
+}
+
 pub(crate) struct TypingContext<'a> {
   global_signature: &'a GlobalSignature,
   pub(crate) local_typing_context: &'a mut LocalTypingContext,
@@ -66,6 +80,9 @@ pub(crate) struct TypingContext<'a> {
   pub(crate) current_module_reference: ModuleReference,
   current_class: PStr,
   available_type_parameters: Vec<TypeParameterSignature>,
+  return_target: ReturnTarget,
+  /// Whether the expression being checked is inside a loop body, where `break` is allowed.
+  in_loop: bool,
   in_synthesis_mode: bool,
   produced_placeholders: bool,
 }
@@ -86,11 +103,76 @@ impl<'a> TypingContext<'a> {
       current_module_reference,
       current_class,
       available_type_parameters,
+      return_target: ReturnTarget::OutsideFunction,
+      in_loop: false,
       in_synthesis_mode: false,
       produced_placeholders: false,
     }
   }
 
+  /// Runs `f` on an expression written in another class, e.g. the default value of a parameter.
+  /// Errors are discarded, since they are already reported where the expression is written.
+  pub(super) fn run_in_class_scope<R>(
+    &self,
+    (module_reference, class_name): (ModuleReference, PStr),
+    expression: &expr::E<()>,
+    f: impl FnOnce(&mut TypingContext) -> R,
+  ) -> R {
+    let mut error_set = ErrorSet::new();
+    let mut local_cx = LocalTypingContext::new(ssa_analysis::perform_ssa_analysis_on_expression(
+      module_reference,
+      expression,
+      &mut error_set,
+    ));
+    let mut cx = TypingContext::new(
+      self.global_signature,
+      &mut local_cx,
+      &mut error_set,
+      module_reference,
+      class_name,
+      Vec::new(),
+    );
+    f(&mut cx)
+  }
+
+  pub(super) fn return_target(&self) -> ReturnTarget {
+    self.return_target.clone()
+  }
+
+  /// The declared return type of the function whose body is being checked.
+  pub(super) fn return_type(&self) -> Option<Arc<Type>> {
+    match &self.return_target {
+      ReturnTarget::Function(return_type) => Some(return_type.dupe()),
+      ReturnTarget::Lambda | ReturnTarget::OutsideFunction => None,
+    }
+  }
+
+  pub(super) fn run_with_return_target<R>(
+    &mut self,
+    return_target: ReturnTarget,
+    f: impl FnOnce(&mut TypingContext<'a>) -> R,
+  ) -> R {
+    let saved_return_target = std::mem::replace(&mut self.return_target, return_target);
+    let result = f(self);
+    self.return_target = saved_return_target;
+    result
+  }
+
+  pub(super) fn in_loop(&self) -> bool {
+    self.in_loop
+  }
+
+  pub(super) fn run_with_in_loop<R>(
+    &mut self,
+    in_loop: bool,
+    f: impl FnOnce(&mut TypingContext<'a>) -> R,
+  ) -> R {
+    let saved_in_loop = std::mem::replace(&mut self.in_loop, in_loop);
+    let result = f(self);
+    self.in_loop = saved_in_loop;
+    result
+  }
+
   pub(super) fn in_synthesis_mode(&self) -> bool {
     self.in_synthesis_mode
   }
@@ -132,7 +214,7 @@ impl<'a> TypingContext<'a> {
 
   pub(crate) fn nominal_type_upper_bound(&'a self, type_: &'a Type) -> Option<&'a NominalType> {
     match type_ {
-      Type::Any(_, _) | Type::Primitive(_, _) | Type::Fn(_) => None,
+      Type::Any(_, _) | Type::Primitive(_, _) | Type::Fn(_) | Type::Tuple(_, _) => None,
       Type::Nominal(t) => Some(t),
       Type::Generic(_, id) => self.resolve_to_potentially_in_scope_type_parameter_bound(*id),
     }
@@ -158,6 +240,47 @@ impl<'a> TypingContext<'a> {
     upper.as_nominal().map(|u| self.is_subtype_with_id_upper(lower, u)).unwrap_or(false)
   }
 
+  /// Whether the type is an interface that can be used as a value type.
+  pub(super) fn is_interface_value_type(&self, t: &Type) -> bool {
+    t.as_nominal().is_some_and(|nominal_type| {
+      !nominal_type.is_class_statics
+        && global_signature::resolve_interface_object_methods(self.global_signature, nominal_type)
+          .is_some()
+    })
+  }
+
+  /// Whether a value of the lower type can be implicitly upcast to the interface value type.
+  pub(super) fn is_interface_upcast(&self, lower: &Type, upper: &Type) -> bool {
+    self.is_interface_value_type(upper)
+      && !lower.as_nominal().is_some_and(|it| it.is_class_statics)
+      && !lower.is_the_same_type(upper)
+      && self.is_subtype(lower, upper)
+  }
+
+  /// Whether a tuple can be implicitly converted to the `std.tuples` class of the same arity.
+  pub(super) fn is_tuple_class_conversion(&self, lower: &Type, upper: &Type) -> bool {
+    let (Type::Tuple(_, lower_elements), Some(upper_elements)) =
+      (lower, upper.std_tuple_class_elements())
+    else {
+      return false;
+    };
+    lower_elements.len() == upper_elements.len()
+      && lower_elements
+        .iter()
+        .zip(upper_elements)
+        .all(|(l, u)| l.is_the_same_type(u) || self.is_implicit_conversion(l, u))
+  }
+
+  /// Whether a value of the lower type flows into the upper type through an interface upcast or
+  /// a tuple conversion.
+  pub(super) fn is_implicit_conversion(&self, lower: &Type, upper: &Type) -> bool {
+    self.is_interface_upcast(lower, upper) || self.is_tuple_class_conversion(lower, upper)
+  }
+
+  pub(super) fn type_from_annotation(&self, annotation: &annotation::T) -> Arc<Type> {
+    global_signature::expand_type_aliases(self.global_signature, &Type::from_annotation(annotation))
+  }
+
   pub(super) fn validate_type_instantiation_allow_abstract_types(&mut self, t: &Type) {
     self.validate_type_instantiation_customized(t, false)
   }
@@ -176,6 +299,12 @@ impl<'a> TypingContext<'a> {
         self.validate_type_instantiation_customized(&f.return_type, true);
         return;
       }
+      Type::Tuple(_, element_types) => {
+        for element_type in element_types {
+          self.validate_type_instantiation_customized(element_type, true)
+        }
+        return;
+      }
       Type::Nominal(t) => t,
     };
     for targ in &nominal_type.type_arguments {
@@ -187,11 +316,15 @@ impl<'a> TypingContext<'a> {
       nominal_type.id,
     ) {
       let interface_type_parameters = interface_info.type_parameters.clone();
-      if interface_info.type_definition.is_none() && enforce_concrete_types {
-        self.error_set.report_incompatible_type_kind_error(
+      if interface_info.type_definition.is_none()
+        && enforce_concrete_types
+        && let Some(method_name) =
+          global_signature::find_generic_interface_method(self.global_signature, nominal_type)
+      {
+        self.error_set.report_generic_method_in_interface_value_error(
           nominal_type.reason.use_loc,
-          nominal_type.to_description(),
-          Description::GeneralNonAbstractType,
+          nominal_type.id,
+          method_name,
         )
       }
       if interface_type_parameters.len() != nominal_type.type_arguments.len() {
@@ -221,6 +354,20 @@ impl<'a> TypingContext<'a> {
           }
         }
       }
+    } else if let Some(alias_info) = global_signature::resolve_type_alias_cx(
+      self.global_signature,
+      nominal_type.module_reference,
+      nominal_type.id,
+    ) {
+      // Well-formed aliases are already expanded, so only arity errors can show up here.
+      if alias_info.type_parameters.len() != nominal_type.type_arguments.len() {
+        let mut error = StackableError::new();
+        error.add_type_args_arity_error(
+          nominal_type.type_arguments.len(),
+          alias_info.type_parameters.len(),
+        );
+        self.error_set.report_stackable_error(nominal_type.reason.use_loc, error);
+      }
     }
   }
 
@@ -238,47 +385,117 @@ impl<'a> TypingContext<'a> {
       .unwrap_or(false)
   }
 
-  pub(super) fn get_method_type(
+  pub(super) fn get_const_type(
+    &self,
+    module_reference: ModuleReference,
+    const_name: PStr,
+  ) -> Option<Arc<Type>> {
+    global_signature::resolve_const_cx(self.global_signature, module_reference, const_name)
+      .map(|it| it.type_.dupe())
+  }
+
+  /// Returns whether the function is the generated constructor of a struct or an enum variant.
+  pub(super) fn is_constructor_function(
+    &self,
+    module_reference: ModuleReference,
+    class_name: PStr,
+    function_name: PStr,
+  ) -> bool {
+    match global_signature::resolve_interface_cx(
+      self.global_signature,
+      module_reference,
+      class_name,
+    )
+    .and_then(|it| it.type_definition.as_ref())
+    {
+      Some(TypeDefinitionSignature::Struct(_)) => function_name == PStr::INIT,
+      Some(TypeDefinitionSignature::Enum(variants)) => {
+        variants.iter().any(|it| it.name == function_name)
+      }
+      None => false,
+    }
+  }
+
+  /// Resolves the member together with the visibility of its toplevel, regardless of whether
+  /// they are visible from the current class.
+  fn resolve_member_signature(
     &self,
     nominal_type: &NominalType,
     method_name: PStr,
     use_loc: Location,
-  ) -> Option<MemberSignature> {
-    global_signature::resolve_interface_cx(
+  ) -> Option<(Visibility, MemberSignature)> {
+    let toplevel_visibility = global_signature::resolve_interface_cx(
       self.global_signature,
       nominal_type.module_reference,
       nominal_type.id,
-    )
-    .filter(|it| !it.private || nominal_type.module_reference == self.current_module_reference)?;
-    if nominal_type.is_class_statics {
-      let resolved = global_signature::resolve_function_signature(
+    )?
+    .visibility;
+    let type_info = if nominal_type.is_class_statics {
+      global_signature::resolve_function_signature(
         self.global_signature,
         (nominal_type.module_reference, nominal_type.id),
         method_name,
-      );
-      let type_info = resolved.first()?;
-      if type_info.is_public || self.in_same_class(nominal_type.module_reference, nominal_type.id) {
-        Some(type_info.reposition(use_loc))
-      } else {
-        None
-      }
+      )
+      .first()?
+      .reposition(use_loc)
     } else {
-      let resolved = global_signature::resolve_method_signature(
-        self.global_signature,
-        nominal_type,
-        method_name,
-      );
-      let type_info = resolved.first()?;
-      if type_info.is_public || self.in_same_class(nominal_type.module_reference, nominal_type.id) {
-        Some(type_info.reposition(use_loc))
-      } else {
-        None
-      }
+      global_signature::resolve_method_signature(self.global_signature, nominal_type, method_name)
+        .first()?
+        .reposition(use_loc)
+    };
+    Some((toplevel_visibility, type_info))
+  }
+
+  pub(super) fn get_method_type(
+    &self,
+    nominal_type: &NominalType,
+    method_name: PStr,
+    use_loc: Location,
+  ) -> Option<MemberSignature> {
+    let (toplevel_visibility, type_info) =
+      self.resolve_member_signature(nominal_type, method_name, use_loc)?;
+    if self.is_toplevel_visible(nominal_type.module_reference, toplevel_visibility)
+      && self.is_member_visible(nominal_type, type_info.visibility)
+    {
+      Some(type_info)
+    } else {
+      None
+    }
+  }
+
+  /// Returns the name and visibility of whatever hides an existing member from the current class.
+  pub(super) fn get_hidden_method_visibility(
+    &self,
+    nominal_type: &NominalType,
+    method_name: PStr,
+  ) -> Option<(PStr, Visibility)> {
+    let (toplevel_visibility, type_info) =
+      self.resolve_member_signature(nominal_type, method_name, Location::dummy())?;
+    if !self.is_toplevel_visible(nominal_type.module_reference, toplevel_visibility) {
+      Some((nominal_type.id, toplevel_visibility))
+    } else if !self.is_member_visible(nominal_type, type_info.visibility) {
+      Some((method_name, type_info.visibility))
+    } else {
+      None
     }
   }
 
-  fn in_same_class(&self, module_reference: ModuleReference, class_name: PStr) -> bool {
-    self.current_module_reference == module_reference && class_name == self.current_class
+  fn is_toplevel_visible(&self, module_reference: ModuleReference, visibility: Visibility) -> bool {
+    global_signature::is_toplevel_visible(
+      self.global_signature,
+      self.current_module_reference,
+      module_reference,
+      visibility,
+    )
+  }
+
+  fn is_member_visible(&self, nominal_type: &NominalType, visibility: Visibility) -> bool {
+    global_signature::is_member_visible(
+      self.global_signature,
+      (self.current_module_reference, self.current_class),
+      (nominal_type.module_reference, nominal_type.id),
+      visibility,
+    )
   }
 
   pub(super) fn resolve_detailed_struct_definitions_opt(
@@ -325,7 +542,7 @@ impl<'a> TypingContext<'a> {
       nominal_type.id,
     )
     .filter(|toplevel_cx| {
-      !toplevel_cx.private || nominal_type.module_reference == self.current_module_reference
+      self.is_toplevel_visible(nominal_type.module_reference, toplevel_cx.visibility)
     })
     .and_then(|toplevel_cx| toplevel_cx.type_definition.as_ref())?;
     let mut subst_map = HashMap::new();
@@ -11,7 +11,7 @@ mod tests {
   };
   use itertools::Itertools;
   use pretty_assertions::assert_eq;
-  use samlang_ast::{Location, Position, Reason};
+  use samlang_ast::{Location, Position, Reason, source::Visibility};
   use samlang_errors::ErrorSet;
   use samlang_heap::{Heap, ModuleReference, PStr};
   use std::{
@@ -116,10 +116,13 @@ mod tests {
     let global_cx = HashMap::from([(
       ModuleReference::DUMMY,
       ModuleSignature {
+        package: Vec::new(),
+        type_aliases: HashMap::new(),
+        constants: HashMap::new(),
         interfaces: HashMap::from([(
           PStr::UPPER_A,
           InterfaceSignature {
-            private: false,
+            visibility: Visibility::Public,
             type_definition: Some(TypeDefinitionSignature::Enum(Vec::new())),
             type_parameters: vec![TypeParameterSignature {
               name: heap.alloc_str_for_test("T"),
@@ -202,11 +205,14 @@ mod tests {
     let global_cx = HashMap::from([(
       ModuleReference::DUMMY,
       ModuleSignature {
+        package: Vec::new(),
+        type_aliases: HashMap::new(),
+        constants: HashMap::new(),
         interfaces: HashMap::from([
           (
             PStr::UPPER_A,
             InterfaceSignature {
-              private: false,
+              visibility: Visibility::Public,
               type_definition: Some(TypeDefinitionSignature::Enum(Vec::new())),
               type_parameters: vec![
                 TypeParameterSignature { name: heap.alloc_str_for_test("T1"), bound: None },
@@ -223,12 +229,17 @@ mod tests {
           (
             PStr::UPPER_B,
             InterfaceSignature {
-              private: false,
+              visibility: Visibility::Public,
               type_definition: None,
               type_parameters: Vec::new(),
               super_types: vec![builder.simple_nominal_type_unwrapped(PStr::UPPER_B)],
               functions: HashMap::new(),
-              methods: HashMap::new(),
+              methods: HashMap::from([MemberSignature::create_builtin_function(
+                heap.alloc_str_for_test("m"),
+                Vec::new(),
+                builder.int_type(),
+                vec![PStr::UPPER_C],
+              )]),
             },
           ),
         ]),
@@ -276,12 +287,12 @@ mod tests {
     let expected_errors = r#"
 Error -------------------------------------- DUMMY.sam:DUMMY
 
-`int` is not a subtype of `B`.
+`B` cannot be used as a value type, since its method `m` is generic.
 
 
 Error -------------------------------------- DUMMY.sam:DUMMY
 
-`B` is incompatible with `non-abstract type`.
+`int` is not a subtype of `B`.
 
 
 Error -------------------------------------- DUMMY.sam:DUMMY
@@ -308,10 +319,13 @@ Found 3 errors.
       (
         mod_ref_w,
         ModuleSignature {
+          package: Vec::new(),
+          type_aliases: HashMap::new(),
+          constants: HashMap::new(),
           interfaces: HashMap::from([(
             PStr::UPPER_A,
             InterfaceSignature {
-              private: true,
+              visibility: Visibility::Private,
               type_definition: Some(TypeDefinitionSignature::Enum(Vec::new())),
               type_parameters: Vec::new(),
               super_types: Vec::new(),
@@ -329,11 +343,14 @@ Found 3 errors.
       (
         ModuleReference::DUMMY,
         ModuleSignature {
+          package: Vec::new(),
+          type_aliases: HashMap::new(),
+          constants: HashMap::new(),
           interfaces: HashMap::from([
             (
               PStr::UPPER_A,
               InterfaceSignature {
-                private: false,
+                visibility: Visibility::Public,
                 type_definition: Some(TypeDefinitionSignature::Enum(Vec::new())),
                 type_parameters: vec![
                   TypeParameterSignature { name: PStr::UPPER_A, bound: None },
@@ -373,7 +390,7 @@ Found 3 errors.
             (
               PStr::UPPER_B,
               InterfaceSignature {
-                private: false,
+                visibility: Visibility::Public,
                 type_definition: None,
                 type_parameters: vec![
                   TypeParameterSignature { name: PStr::UPPER_E, bound: None },
@@ -773,11 +790,14 @@ Found 3 errors.
       (
         ModuleReference::DUMMY,
         ModuleSignature {
+          package: Vec::new(),
+          type_aliases: HashMap::new(),
+          constants: HashMap::new(),
           interfaces: HashMap::from([
             (
               PStr::UPPER_A,
               InterfaceSignature {
-                private: false,
+                visibility: Visibility::Public,
                 type_definition: Some(TypeDefinitionSignature::Enum(vec![
                   EnumVariantDefinitionSignature {
                     name: PStr::LOWER_A,
@@ -800,7 +820,7 @@ Found 3 errors.
             (
               PStr::UPPER_B,
               InterfaceSignature {
-                private: false,
+                visibility: Visibility::Public,
                 type_definition: Some(TypeDefinitionSignature::Struct(Vec::new())),
                 type_parameters: vec![
                   TypeParameterSignature { name: PStr::UPPER_E, bound: None },
@@ -817,10 +837,13 @@ Found 3 errors.
       (
         mod_ref_w,
         ModuleSignature {
+          package: Vec::new(),
+          type_aliases: HashMap::new(),
+          constants: HashMap::new(),
           interfaces: HashMap::from([(
             PStr::UPPER_A,
             InterfaceSignature {
-              private: true,
+              visibility: Visibility::Private,
               type_definition: Some(TypeDefinitionSignature::Enum(vec![
                 EnumVariantDefinitionSignature {
                   name: PStr::LOWER_A,
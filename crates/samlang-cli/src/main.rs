@@ -749,6 +749,7 @@ mod runners {
         heap,
         collected_sources,
         entry_module_references,
+        configuration.strip_asserts,
         enable_profiling,
       ) {
         Ok(samlang_compiler::SourcesCompilationResult { text_code_results, wasm_file }) => {
@@ -10,11 +10,14 @@ use super::{
 use dupe::{Dupe, OptionDupedExt};
 use itertools::Itertools;
 use ordermap::OrderSet;
-use samlang_ast::{hir, mir, source};
+use samlang_ast::{Reason, hir, mir, source};
 use samlang_checker::type_;
 use samlang_collections::local_stacked_context::LocalStackedContext;
 use samlang_heap::{Heap, ModuleReference, PStr};
-use std::{collections::HashMap, sync::Arc};
+use std::{
+  collections::{BTreeMap, HashMap, HashSet},
+  sync::Arc,
+};
 
 struct LoweringResult {
   statements: Vec<hir::Statement>,
@@ -29,9 +32,46 @@ struct LoweringResultWithSyntheticFunctions {
 
 type LoweringContext = LocalStackedContext<PStr, hir::Expression>;
 
+/// Lowered names of `var` declarations in scope, mapped to their types and current values.
+type MutableVariables = BTreeMap<PStr, (hir::Type, hir::Expression)>;
+
+fn is_float_type(type_: &type_::Type) -> bool {
+  matches!(type_, type_::Type::Primitive(_, type_::PrimitiveTypeKind::Float))
+}
+
+/// The value of an `int` expression made of literals and operators that cannot overflow, so that
+/// a template string hole like `${1 + 2}` can be folded into the surrounding text.
+fn constant_int_value(expression: &source::expr::E<Arc<type_::Type>>) -> Option<i32> {
+  use source::expr::{BinaryOperator, E, UnaryOperator};
+  match expression {
+    E::Literal(_, source::Literal::Int(i, _)) => Some(*i),
+    E::Unary(e) if e.operator == UnaryOperator::NEG => {
+      constant_int_value(&e.argument)?.checked_neg()
+    }
+    E::Unary(e) if e.operator == UnaryOperator::BNOT => Some(!constant_int_value(&e.argument)?),
+    E::Binary(e) => {
+      let (v1, v2) = (constant_int_value(&e.e1)?, constant_int_value(&e.e2)?);
+      match e.operator {
+        BinaryOperator::PLUS => v1.checked_add(v2),
+        BinaryOperator::MINUS => v1.checked_sub(v2),
+        BinaryOperator::MUL => v1.checked_mul(v2),
+        BinaryOperator::LAND => Some(v1 & v2),
+        BinaryOperator::LOR => Some(v1 | v2),
+        BinaryOperator::XOR => Some(v1 ^ v2),
+        _ => None,
+      }
+    }
+    _ => None,
+  }
+}
+
 fn bind_value(cx: &mut LoweringContext, name: PStr, value: hir::Expression) {
   match &value {
-    hir::Expression::IntLiteral(_) | hir::Expression::Int31Zero | hir::Expression::Variable(_) => {
+    hir::Expression::IntLiteral(_)
+    | hir::Expression::Int31Zero
+    | hir::Expression::FloatLiteral(_)
+    | hir::Expression::GlobalConstant(_)
+    | hir::Expression::Variable(_) => {
       cx.insert(name, value);
     }
     hir::Expression::StringName(n) => {
@@ -60,6 +100,130 @@ mod lowering_cx_boilterplate_tests {
   }
 }
 
+/// Collects the names of all variables that might be reassigned while evaluating the expression.
+/// Lambdas are skipped, since they cannot capture `var` declarations.
+fn collect_assigned_variables(
+  expression: &source::expr::E<Arc<type_::Type>>,
+  collector: &mut HashSet<PStr>,
+) {
+  match expression {
+    source::expr::E::Literal(_, _)
+    | source::expr::E::LocalId(_, _)
+    | source::expr::E::ClassId(_, _, _)
+    | source::expr::E::Lambda(_)
+    | source::expr::E::Break(_) => {}
+    source::expr::E::Tuple(_, es) => {
+      for e in &es.expressions {
+        collect_assigned_variables(e, collector);
+      }
+    }
+    source::expr::E::TemplateString(e) => {
+      for hole in &e.holes {
+        collect_assigned_variables(hole, collector);
+      }
+    }
+    source::expr::E::StructUpdate(e) => {
+      collect_assigned_variables(&e.base, collector);
+      for field in &e.fields {
+        collect_assigned_variables(&field.value, collector);
+      }
+    }
+    source::expr::E::FieldAccess(e) => collect_assigned_variables(&e.object, collector),
+    source::expr::E::MethodAccess(e) => collect_assigned_variables(&e.object, collector),
+    source::expr::E::Unary(e) => collect_assigned_variables(&e.argument, collector),
+    source::expr::E::Call(e) => {
+      collect_assigned_variables(&e.callee, collector);
+      for e in &e.arguments.expressions {
+        collect_assigned_variables(e, collector);
+      }
+    }
+    source::expr::E::Binary(e) => {
+      collect_assigned_variables(&e.e1, collector);
+      collect_assigned_variables(&e.e2, collector);
+    }
+    source::expr::E::IfElse(e) => collect_assigned_variables_in_if_else(e, collector),
+    source::expr::E::Match(e) => {
+      collect_assigned_variables(&e.matched, collector);
+      for case in &e.cases {
+        if let Some(guard) = &case.guard {
+          collect_assigned_variables(guard, collector);
+        }
+        collect_assigned_variables(&case.body, collector);
+      }
+    }
+    source::expr::E::Return(e) => collect_assigned_variables(&e.argument, collector),
+    source::expr::E::While(e) => {
+      collect_assigned_variables(&e.condition, collector);
+      collect_assigned_variables_in_block(&e.body, collector);
+    }
+    source::expr::E::For(e) => {
+      collect_assigned_variables(&e.start, collector);
+      collect_assigned_variables(&e.end, collector);
+      collect_assigned_variables_in_block(&e.body, collector);
+    }
+    source::expr::E::Assert(e) => {
+      for e in &e.arguments.expressions {
+        collect_assigned_variables(e, collector);
+      }
+    }
+    source::expr::E::Try(e) => collect_assigned_variables(&e.argument, collector),
+    source::expr::E::Block(e) => collect_assigned_variables_in_block(e, collector),
+  }
+}
+
+fn collect_assigned_variables_in_if_else(
+  expression: &source::expr::IfElse<Arc<type_::Type>>,
+  collector: &mut HashSet<PStr>,
+) {
+  match expression.condition.as_ref() {
+    source::expr::IfElseCondition::Expression(e) | source::expr::IfElseCondition::Guard(_, e) => {
+      collect_assigned_variables(e, collector)
+    }
+  }
+  collect_assigned_variables_in_block(&expression.e1, collector);
+  match expression.e2.as_ref() {
+    source::expr::IfElseOrBlock::IfElse(e) => collect_assigned_variables_in_if_else(e, collector),
+    source::expr::IfElseOrBlock::Block(e) => collect_assigned_variables_in_block(e, collector),
+  }
+}
+
+fn collect_assigned_variables_in_block(
+  block: &source::expr::Block<Arc<type_::Type>>,
+  collector: &mut HashSet<PStr>,
+) {
+  for statement in &block.statements {
+    match statement {
+      source::expr::Statement::Declaration(decl) => {
+        collect_assigned_variables(&decl.assigned_expression, collector);
+        if let Some(else_block) = &decl.else_block {
+          collect_assigned_variables_in_block(else_block, collector);
+        }
+      }
+      source::expr::Statement::Assignment(assign) => {
+        collector.insert(assign.name.name);
+        collect_assigned_variables(&assign.assigned_expression, collector);
+      }
+      // Like lambdas, local functions cannot capture `var` declarations.
+      source::expr::Statement::Function(_) => {}
+      source::expr::Statement::Expression(e) => collect_assigned_variables(e, collector),
+    }
+  }
+  if let Some(e) = &block.expression {
+    collect_assigned_variables(e, collector);
+  }
+}
+
+/// A `var` declaration that is reassigned inside a loop, which is promoted to a loop variable.
+struct LoopCarriedVariable {
+  /// The lowered name of the `var` declaration.
+  name: PStr,
+  type_: hir::Type,
+  loop_variable: PStr,
+  initial_value: hir::Expression,
+  /// Holds the value of the variable when the loop exits.
+  exit_slot: PStr,
+}
+
 struct NextSyntheticFnIdManager {
   id: i32,
 }
@@ -72,9 +236,17 @@ struct ExpressionLoweringManager<'a> {
   type_definition_mapping: &'a HashMap<hir::TypeName, hir::TypeDefinition>,
   type_lowering_manager: &'a mut TypeLoweringManager,
   string_manager: &'a mut StringManager,
+  /// Whether `assert` expressions should be dropped instead of checked at runtime.
+  strip_asserts: bool,
   // Mutable states
   next_synthetic_fn_id_manager: &'a mut NextSyntheticFnIdManager,
   variable_cx: LoweringContext,
+  /// `var` declarations are kept in SSA form by tracking the value each one currently holds.
+  mutable_variables: MutableVariables,
+  /// `var` declarations promoted to loop variables, for each enclosing loop from the outermost.
+  loop_carried_variables: Vec<Vec<LoopCarriedVariable>>,
+  /// The lowered return type of the enclosing function, which returned values are upcast to.
+  return_type: Option<hir::Type>,
   synthetic_functions: Vec<hir::Function>,
 }
 
@@ -87,6 +259,7 @@ impl<'a> ExpressionLoweringManager<'a> {
     heap: &'a mut Heap,
     type_lowering_manager: &'a mut TypeLoweringManager,
     string_manager: &'a mut StringManager,
+    strip_asserts: bool,
     next_synthetic_fn_id_manager: &'a mut NextSyntheticFnIdManager,
   ) -> ExpressionLoweringManager<'a> {
     let mut variable_cx = LoweringContext::new();
@@ -100,8 +273,12 @@ impl<'a> ExpressionLoweringManager<'a> {
       type_definition_mapping,
       type_lowering_manager,
       string_manager,
+      strip_asserts,
       next_synthetic_fn_id_manager,
       variable_cx,
+      mutable_variables: BTreeMap::new(),
+      loop_carried_variables: Vec::new(),
+      return_type: None,
       synthetic_functions: Vec::new(),
     }
   }
@@ -133,6 +310,102 @@ impl<'a> ExpressionLoweringManager<'a> {
     e
   }
 
+  fn is_interface_type(&self, type_: &hir::Type) -> bool {
+    let Some(hir::IdType {
+      name: hir::TypeName { module_reference: Some(module_reference), type_name },
+      type_arguments: _,
+    }) = type_.as_id()
+    else {
+      return false;
+    };
+    self
+      .type_lowering_manager
+      .global_cx
+      .get(module_reference)
+      .and_then(|module_cx| module_cx.interfaces.get(type_name))
+      .is_some_and(|interface_cx| interface_cx.type_definition.is_none())
+  }
+
+  fn is_tuple_to_class_conversion(
+    &self,
+    value_type: &hir::Type,
+    expected_type: &hir::Type,
+  ) -> bool {
+    value_type.as_id().is_some_and(|id_type| {
+      self
+        .type_lowering_manager
+        .type_synthesizer
+        .synthesized_tuple_types
+        .contains_key(&id_type.name.type_name)
+    }) && expected_type
+      .as_id()
+      .is_some_and(|id_type| id_type.name.module_reference == Some(ModuleReference::STD_TUPLES))
+  }
+
+  /// The checker allows a value to flow into a position typed by an interface it implements.
+  /// Such a value is wrapped into an interface value, so that methods can be dispatched
+  /// dynamically. A tuple flowing into a position typed by a `std.tuples` class is rebuilt as that
+  /// class.
+  fn upcast(
+    &mut self,
+    value: hir::Expression,
+    expected_type: &hir::Type,
+    statements: &mut Vec<hir::Statement>,
+  ) -> hir::Expression {
+    if value.type_() == expected_type {
+      return value;
+    }
+    if self.is_tuple_to_class_conversion(value.type_(), expected_type) {
+      let tuple_mappings = self.resolve_struct_mapping_of_id_type(value.type_().as_id().unwrap());
+      let class_type = expected_type.as_id().unwrap().clone();
+      let class_mappings = self.resolve_struct_mapping_of_id_type(&class_type);
+      let mut expression_list = Vec::with_capacity(class_mappings.len());
+      for (index, (element_type, class_element_type)) in
+        tuple_mappings.into_iter().zip(&class_mappings).enumerate()
+      {
+        let name = self.allocate_temp_variable();
+        statements.push(hir::Statement::IndexedAccess {
+          name,
+          type_: element_type.dupe(),
+          pointer_expression: value.dupe(),
+          index,
+        });
+        let element = hir::Expression::var_name(name, element_type);
+        expression_list.push(self.upcast(element, class_element_type, statements));
+      }
+      let struct_variable_name = self.allocate_temp_variable();
+      statements.push(hir::Statement::StructInit {
+        struct_variable_name,
+        type_: class_type,
+        expression_list,
+      });
+      let class_value = hir::Expression::var_name(struct_variable_name, expected_type.dupe());
+      bind_value(&mut self.variable_cx, struct_variable_name, class_value.dupe());
+      return class_value;
+    }
+    if !self.is_interface_type(expected_type) {
+      return value;
+    }
+    let interface_variable_name = self.allocate_temp_variable();
+    statements.push(hir::Statement::InterfaceInit {
+      interface_variable_name,
+      interface_type: expected_type.as_id().unwrap().clone(),
+      context: value,
+    });
+    let interface_value = hir::Expression::var_name(interface_variable_name, expected_type.dupe());
+    bind_value(&mut self.variable_cx, interface_variable_name, interface_value.dupe());
+    interface_value
+  }
+
+  fn upcast_lowering_result(
+    &mut self,
+    LoweringResult { mut statements, expression }: LoweringResult,
+    expected_type: &hir::Type,
+  ) -> LoweringResult {
+    let expression = self.upcast(expression, expected_type, &mut statements);
+    LoweringResult { statements, expression }
+  }
+
   fn get_synthetic_identifier_type_from_tuple(&mut self, mappings: Vec<hir::Type>) -> hir::IdType {
     let type_parameters = collect_used_generic_types(
       &hir::Type::new_fn_unwrapped(mappings.clone(), hir::INT_TYPE),
@@ -174,8 +447,44 @@ impl<'a> ExpressionLoweringManager<'a> {
     self.variable_cx.get(variable_name).unwrap().dupe()
   }
 
+  /// Merges the `var` values coming out of each branch of a conditional. Every `var` whose value
+  /// differs between the branches gets a slot, which is assigned at the end of each branch. The
+  /// returned slot declarations must be placed before the conditional.
+  fn join_mutable_variables(
+    &mut self,
+    before: MutableVariables,
+    mut branches: Vec<(&mut Vec<hir::Statement>, MutableVariables)>,
+  ) -> Vec<hir::Statement> {
+    let mut declarations = Vec::new();
+    let mut joined = before;
+    for (name, (type_, value)) in &mut joined {
+      if branches.iter().all(|(_, after)| after.get(name).is_none_or(|(_, v)| v == value)) {
+        continue;
+      }
+      let slot = self.heap.alloc_temp_str();
+      declarations.push(hir::Statement::LateInitDeclaration { name: slot, type_: type_.dupe() });
+      for (statements, after) in &mut branches {
+        let branch_value = after.get(name).map_or(value.dupe(), |(_, v)| v.dupe());
+        statements.push(hir::Statement::LateInitAssignment {
+          name: slot,
+          assigned_expression: branch_value,
+        });
+      }
+      *value = hir::Expression::var_name(slot, type_.dupe());
+    }
+    self.mutable_variables = joined;
+    declarations
+  }
+
   fn resolve_struct_mapping_of_id_type(&mut self, hir_id_type: &hir::IdType) -> Vec<hir::Type> {
-    let type_def = self.type_definition_mapping.get(&hir_id_type.name).unwrap();
+    let type_def = self.type_definition_mapping.get(&hir_id_type.name).unwrap_or_else(|| {
+      self
+        .type_lowering_manager
+        .type_synthesizer
+        .synthesized_tuple_types
+        .get(&hir_id_type.name.type_name)
+        .unwrap()
+    });
     let replacement_map: HashMap<_, _> = type_def
       .type_parameters
       .iter()
@@ -207,25 +516,37 @@ impl<'a> ExpressionLoweringManager<'a> {
       source::expr::E::Literal(_, source::Literal::Bool(b)) => {
         LoweringResult { statements: Vec::new(), expression: if *b { hir::ONE } else { hir::ZERO } }
       }
-      source::expr::E::Literal(_, source::Literal::Int(i)) => {
+      source::expr::E::Literal(_, source::Literal::Int(i, _)) => {
         LoweringResult { statements: Vec::new(), expression: hir::Expression::int(*i) }
       }
-      source::expr::E::Literal(_, source::Literal::String(s)) => LoweringResult {
+      source::expr::E::Literal(_, source::Literal::Float(f)) => {
+        LoweringResult { statements: Vec::new(), expression: hir::Expression::FloatLiteral(*f) }
+      }
+      source::expr::E::Literal(_, source::Literal::String(s, _)) => LoweringResult {
         statements: Vec::new(),
         expression: hir::Expression::StringName(self.string_manager.allocate(*s).0),
       },
-      source::expr::E::LocalId(_, id) => LoweringResult {
+      source::expr::E::LocalId(_, id) if id.name == PStr::THIS => LoweringResult {
         statements: Vec::new(),
-        expression: if id.name == PStr::THIS {
-          self.resolve_variable(&PStr::UNDERSCORE_THIS)
-        } else {
-          self.resolve_variable(&id.name)
-        },
+        expression: self.resolve_variable(&PStr::UNDERSCORE_THIS),
       },
-      source::expr::E::ClassId(_, _, _) => {
-        LoweringResult { statements: Vec::new(), expression: hir::Expression::Int31Zero }
+      source::expr::E::LocalId(_, id) => self.lower_local_id(id.name),
+      source::expr::E::ClassId(common, mod_ref, id) => {
+        let expression = if let Some(module_cx) = self.type_lowering_manager.global_cx.get(mod_ref)
+          && module_cx.constants.contains_key(&id.name)
+        {
+          hir::Expression::GlobalConstant(hir::GlobalConstant {
+            initializer: const_initializer_name(*mod_ref, id.name),
+            type_: self.type_lowering_manager.lower_source_type(self.heap, &common.type_),
+          })
+        } else {
+          hir::Expression::Int31Zero
+        };
+        LoweringResult { statements: Vec::new(), expression }
       }
       source::expr::E::Tuple(common, es) => self.lower_tuple(common, &es.expressions),
+      source::expr::E::TemplateString(e) => self.lower_template_string(e),
+      source::expr::E::StructUpdate(e) => self.lower_struct_update(e),
       source::expr::E::FieldAccess(e) => self.lower_field_access(e),
       source::expr::E::MethodAccess(e) => self.lower_method_access(e),
       source::expr::E::Unary(e) => self.lower_unary(e),
@@ -233,7 +554,16 @@ impl<'a> ExpressionLoweringManager<'a> {
       source::expr::E::Binary(_) => self.lower_binary(expression),
       source::expr::E::IfElse(e) => self.lower_if_else(e),
       source::expr::E::Match(e) => self.lower_match(e),
-      source::expr::E::Lambda(e) => self.lower_lambda(e),
+      source::expr::E::Lambda(e) => self.lower_lambda(e, None),
+      source::expr::E::Return(e) => self.lower_return(e),
+      source::expr::E::While(e) => self.lower_while(e),
+      source::expr::E::For(e) => self.lower_for(e),
+      source::expr::E::Break(common) => {
+        let statements = self.lower_break();
+        self.lower_diverging_value(statements, &common.type_)
+      }
+      source::expr::E::Assert(e) => self.lower_assert(e),
+      source::expr::E::Try(e) => self.lower_try(e),
       source::expr::E::Block(e) => self.lower_block(e),
     }
   }
@@ -247,6 +577,16 @@ impl<'a> ExpressionLoweringManager<'a> {
     hir::FunctionName { type_name, fn_name }
   }
 
+  fn lower_local_id(&mut self, name: PStr) -> LoweringResult {
+    let resolved = self.resolve_variable(&name);
+    let hir::Expression::Variable(v) = &resolved else {
+      return LoweringResult { statements: Vec::new(), expression: resolved };
+    };
+    let expression =
+      self.mutable_variables.get(&v.name).map_or(resolved.dupe(), |(_, value)| value.dupe());
+    LoweringResult { statements: Vec::new(), expression }
+  }
+
   fn lower_field_access(
     &mut self,
     expression: &source::expr::FieldAccess<Arc<type_::Type>>,
@@ -319,6 +659,21 @@ impl<'a> ExpressionLoweringManager<'a> {
     let LoweringResult { mut statements, expression: result_expr } =
       self.lower(&expression.argument);
     let value_name = self.allocate_temp_variable();
+    if expression.operator == source::expr::UnaryOperator::NEG
+      && is_float_type(&expression.common.type_)
+    {
+      // Multiplying by -1.0 instead of subtracting from 0.0 preserves the sign of zero.
+      statements.push(hir::Statement::Binary {
+        name: value_name,
+        operator: hir::BinaryOperator::FMUL,
+        e1: result_expr,
+        e2: hir::Expression::float(-1.0),
+      });
+      return LoweringResult {
+        statements,
+        expression: hir::Expression::var_name(value_name, hir::FLOAT_TYPE),
+      };
+    }
     statements.push(match expression.operator {
       source::expr::UnaryOperator::NOT => {
         hir::Statement::Not { name: value_name, operand: result_expr }
@@ -329,6 +684,12 @@ impl<'a> ExpressionLoweringManager<'a> {
         e1: hir::ZERO,
         e2: result_expr,
       },
+      source::expr::UnaryOperator::BNOT => hir::Statement::Binary {
+        name: value_name,
+        operator: hir::BinaryOperator::XOR,
+        e1: result_expr,
+        e2: hir::Expression::IntLiteral(-1),
+      },
     });
     LoweringResult { statements, expression: hir::Expression::var_name(value_name, hir::INT_TYPE) }
   }
@@ -339,38 +700,215 @@ impl<'a> ExpressionLoweringManager<'a> {
     expressions: &[source::expr::E<Arc<type_::Type>>],
   ) -> LoweringResult {
     let mut lowered_stmts = Vec::new();
-    let return_collector_name = self.allocate_temp_variable();
-    let fn_name = self.create_hir_function_name(&common.type_, PStr::INIT);
-    let return_type = self.type_lowering_manager.lower_source_type(self.heap, &common.type_);
-
-    let mut lowered_arguments = Vec::with_capacity(1 + expressions.len());
-    let mut parameter_types = Vec::with_capacity(1 + expressions.len());
-    let mut type_arguments = Vec::with_capacity(expressions.len());
-    lowered_arguments.push(hir::ZERO);
-    parameter_types.push(hir::INT_TYPE);
-    for e in expressions {
+    let tuple_type = self.type_lowering_manager.lower_source_type(self.heap, &common.type_);
+    // The tuple can also be typed by a `std.tuples` class, whose fields might need upcasts.
+    let mappings = self.resolve_struct_mapping_of_id_type(tuple_type.as_id().unwrap());
+    let mut expression_list = Vec::with_capacity(expressions.len());
+    for (e, element_type) in expressions.iter().zip(&mappings) {
       let lowered = self.lowered_and_add_statements(e, &mut lowered_stmts);
-      parameter_types.push(lowered.type_().dupe());
-      type_arguments.push(lowered.type_().dupe());
-      lowered_arguments.push(lowered);
+      expression_list.push(self.upcast(lowered, element_type, &mut lowered_stmts));
+    }
+    let struct_variable_name = self.allocate_temp_variable();
+    lowered_stmts.push(hir::Statement::StructInit {
+      struct_variable_name,
+      type_: tuple_type.as_id().unwrap().clone(),
+      expression_list,
+    });
+    let value = hir::Expression::var_name(struct_variable_name, tuple_type);
+    bind_value(&mut self.variable_cx, struct_variable_name, value.dupe());
+    LoweringResult { statements: lowered_stmts, expression: value }
+  }
+
+  /// Lowers `{ ...base, field: value }` into a single struct initialization, which reads the
+  /// fields that are not updated from the base.
+  fn lower_struct_update(
+    &mut self,
+    expression: &source::expr::StructUpdate<Arc<type_::Type>>,
+  ) -> LoweringResult {
+    let mut lowered_stmts = Vec::new();
+    let base = self.lowered_and_add_statements(&expression.base, &mut lowered_stmts);
+    let id_type = base.type_().as_id().unwrap().clone();
+    let mappings_for_id_type = self.resolve_struct_mapping_of_id_type(&id_type);
+    let mut updated_values = HashMap::new();
+    for field in &expression.fields {
+      let lowered = self.lowered_and_add_statements(&field.value, &mut lowered_stmts);
+      let index = usize::try_from(field.field_order).unwrap();
+      let lowered = self.upcast(lowered, &mappings_for_id_type[index], &mut lowered_stmts);
+      updated_values.insert(index, lowered);
+    }
+    let mut expression_list = Vec::with_capacity(mappings_for_id_type.len());
+    for (index, field_type) in mappings_for_id_type.iter().enumerate() {
+      if let Some(value) = updated_values.remove(&index) {
+        expression_list.push(value);
+        continue;
+      }
+      let value_name = self.allocate_temp_variable();
+      lowered_stmts.push(hir::Statement::IndexedAccess {
+        name: value_name,
+        type_: field_type.dupe(),
+        pointer_expression: base.dupe(),
+        index,
+      });
+      let value = hir::Expression::var_name(value_name, field_type.dupe());
+      bind_value(&mut self.variable_cx, value_name, value.dupe());
+      expression_list.push(value);
     }
-    lowered_stmts.push(hir::Statement::Call {
+    let struct_variable_name = self.allocate_temp_variable();
+    lowered_stmts.push(hir::Statement::StructInit {
+      struct_variable_name,
+      type_: id_type.clone(),
+      expression_list,
+    });
+    let value = hir::Expression::var_name(struct_variable_name, hir::Type::Id(id_type));
+    bind_value(&mut self.variable_cx, struct_variable_name, value.dupe());
+    LoweringResult { statements: lowered_stmts, expression: value }
+  }
+
+  /// Call a builtin function of `Str` that returns `Str`.
+  fn call_str_builtin(
+    &mut self,
+    fn_name: PStr,
+    arguments: Vec<hir::Expression>,
+    statements: &mut Vec<hir::Statement>,
+  ) -> hir::Expression {
+    let return_collector_name = self.allocate_temp_variable();
+    statements.push(hir::Statement::Call {
       callee: hir::Callee::FunctionName(hir::FunctionNameExpression {
-        name: fn_name,
-        type_: hir::FunctionType {
-          argument_types: parameter_types,
-          return_type: Box::new(return_type.dupe()),
+        name: hir::FunctionName {
+          type_name: hir::TypeName {
+            module_reference: Some(ModuleReference::ROOT),
+            type_name: PStr::STR_TYPE,
+          },
+          fn_name,
         },
-        type_arguments,
+        type_: hir::Type::new_fn_unwrapped(
+          arguments.iter().map(|it| it.type_().dupe()).collect(),
+          hir::STRING_TYPE.dupe(),
+        ),
+        type_arguments: Vec::new(),
       }),
-      arguments: lowered_arguments,
-      return_type: return_type.dupe(),
+      arguments,
+      return_type: hir::STRING_TYPE.dupe(),
       return_collector: Some(return_collector_name),
     });
-    LoweringResult {
-      statements: lowered_stmts,
-      expression: hir::Expression::var_name(return_collector_name, return_type),
+    hir::Expression::var_name(return_collector_name, hir::STRING_TYPE.dupe())
+  }
+
+  fn lower_template_string(
+    &mut self,
+    expression: &source::expr::TemplateString<Arc<type_::Type>>,
+  ) -> LoweringResult {
+    enum Piece {
+      Constant(String),
+      Dynamic(hir::Expression),
+    }
+
+    let mut statements = Vec::new();
+    let mut pieces = vec![Piece::Constant(expression.parts[0].as_str(self.heap).to_string())];
+    for (hole, part) in expression.holes.iter().zip(&expression.parts[1..]) {
+      let piece = match hole {
+        source::expr::E::Literal(_, source::Literal::String(s, _)) => {
+          Piece::Constant(s.as_str(self.heap).to_string())
+        }
+        _ if let Some(i) = constant_int_value(hole) => Piece::Constant(i.to_string()),
+        _ if hole.type_().as_primitive().is_some() => {
+          let lowered = self.lowered_and_add_statements(hole, &mut statements);
+          // Like other class functions, `Str.fromInt` takes the class value as its first argument.
+          let arguments = vec![hir::Expression::Int31Zero, lowered];
+          Piece::Dynamic(self.call_str_builtin(PStr::FROM_INT, arguments, &mut statements))
+        }
+        _ if hole.type_().as_nominal().is_some_and(|t| {
+          t.module_reference == ModuleReference::ROOT && t.id == PStr::STR_TYPE
+        }) =>
+        {
+          Piece::Dynamic(self.lowered_and_add_statements(hole, &mut statements))
+        }
+        _ => {
+          // Everything else is converted with its `toString(): Str` method.
+          let str_type = expression.common.type_.dupe();
+          let method_type = Arc::new(type_::Type::Fn(type_::FunctionType {
+            reason: Reason::new(hole.loc(), None),
+            argument_types: Vec::new(),
+            return_type: str_type.dupe(),
+          }));
+          let to_string_call = source::expr::Call {
+            common: hole.common().with_new_type(str_type),
+            callee: Box::new(source::expr::E::MethodAccess(source::expr::MethodAccess {
+              common: hole.common().with_new_type(method_type),
+              explicit_type_arguments: None,
+              inferred_type_arguments: Vec::new(),
+              object: Box::new(hole.clone()),
+              method_name: source::Id::from(PStr::TO_STRING),
+            })),
+            arguments: source::expr::ParenthesizedExpressionList {
+              loc: hole.loc(),
+              start_associated_comments: source::NO_COMMENT_REFERENCE,
+              ending_associated_comments: source::NO_COMMENT_REFERENCE,
+              expressions: Vec::new(),
+            },
+            binding: Default::default(),
+          };
+          let LoweringResult { statements: mut call_statements, expression } =
+            self.lower_fn_call(&to_string_call);
+          statements.append(&mut call_statements);
+          Piece::Dynamic(expression)
+        }
+      };
+      pieces.push(piece);
+      pieces.push(Piece::Constant(part.as_str(self.heap).to_string()));
+    }
+
+    // Fold adjacent constant pieces together, and drop the empty ones.
+    let mut folded_pieces: Vec<Piece> = Vec::new();
+    for piece in pieces {
+      match (folded_pieces.last_mut(), piece) {
+        (_, Piece::Constant(s)) if s.is_empty() => {}
+        (Some(Piece::Constant(previous)), Piece::Constant(s)) => previous.push_str(&s),
+        (_, piece) => folded_pieces.push(piece),
+      }
+    }
+    if folded_pieces.is_empty() {
+      folded_pieces.push(Piece::Constant(String::new()));
+    }
+    let mut result = None;
+    for piece in folded_pieces {
+      let lowered = match piece {
+        Piece::Constant(s) => {
+          let p_str = self.heap.alloc_string(s);
+          hir::Expression::StringName(self.string_manager.allocate(p_str).0)
+        }
+        Piece::Dynamic(e) => e,
+      };
+      result = Some(match result {
+        None => lowered,
+        Some(previous) => {
+          self.call_str_builtin(PStr::CONCAT, vec![previous, lowered], &mut statements)
+        }
+      });
+    }
+    LoweringResult { statements, expression: result.unwrap() }
+  }
+
+  /// Lowers the arguments in the order they are written, followed by the default values of the
+  /// parameters without arguments, and returns them in the order of parameters.
+  fn lower_call_arguments(
+    &mut self,
+    expression: &source::expr::Call<Arc<type_::Type>>,
+    lowered_stmts: &mut Vec<hir::Statement>,
+  ) -> Vec<hir::Expression> {
+    let binding = &expression.binding;
+    let mut lowered_args = Vec::new();
+    for (i, argument) in expression.arguments.expressions.iter().enumerate() {
+      let parameter_index = binding.parameter_indices.get(i).copied().unwrap_or(i);
+      lowered_args
+        .push((parameter_index, self.lowered_and_add_statements(argument, lowered_stmts)));
     }
+    for (parameter_index, default_value) in &binding.default_arguments {
+      lowered_args
+        .push((*parameter_index, self.lowered_and_add_statements(default_value, lowered_stmts)));
+    }
+    lowered_args.sort_by_key(|(parameter_index, _)| *parameter_index);
+    lowered_args.into_iter().map(|(_, lowered)| lowered).collect()
   }
 
   fn lower_fn_call(&mut self, expression: &source::expr::Call<Arc<type_::Type>>) -> LoweringResult {
@@ -389,6 +927,7 @@ impl<'a> ExpressionLoweringManager<'a> {
         let fn_type_without_cx =
           self.get_function_type_without_context(&source_callee.common.type_);
         let hir_target = self.lowered_and_add_statements(&source_callee.object, &mut lowered_stmts);
+        let lowered_args = self.lower_call_arguments(expression, &mut lowered_stmts);
         let hir_target_type = hir_target.type_();
         let inferred_targs = self
           .type_lowering_manager
@@ -414,13 +953,12 @@ impl<'a> ExpressionLoweringManager<'a> {
             }),
             arguments: vec![hir_target]
               .into_iter()
-              .chain(
-                expression
-                  .arguments
-                  .expressions
-                  .iter()
-                  .map(|a| self.lowered_and_add_statements(a, &mut lowered_stmts)),
-              )
+              .chain(lowered_args.into_iter().enumerate().map(|(i, lowered)| {
+                match fn_type_without_cx.argument_types.get(i) {
+                  Some(t) => self.upcast(lowered, t, &mut lowered_stmts),
+                  None => lowered,
+                }
+              }))
               .collect_vec(),
             return_type: fn_type_without_cx.return_type.as_ref().dupe(),
             return_collector: if is_void_return { None } else { Some(return_collector_name) },
@@ -438,11 +976,17 @@ impl<'a> ExpressionLoweringManager<'a> {
         let return_type = self
           .type_lowering_manager
           .lower_source_type(self.heap, &source_callee_fn_type.return_type);
-        let lowered_args = expression
-          .arguments
-          .expressions
-          .iter()
-          .map(|a| self.lowered_and_add_statements(a, &mut lowered_stmts))
+        let lowered_args = self
+          .lower_call_arguments(expression, &mut lowered_stmts)
+          .into_iter()
+          .enumerate()
+          .map(|(i, lowered)| {
+            let Some(t) = source_callee_fn_type.argument_types.get(i) else {
+              return lowered;
+            };
+            let expected_type = self.type_lowering_manager.lower_source_type(self.heap, t);
+            self.upcast(lowered, &expected_type, &mut lowered_stmts)
+          })
           .collect_vec();
         (
           return_type.dupe(),
@@ -473,22 +1017,60 @@ impl<'a> ExpressionLoweringManager<'a> {
       _ => return self.lower(expression),
     };
     let operator = match expression.operator {
+      source::expr::BinaryOperator::PIPE => {
+        // `e |> f(a)` is lowered as `f(e, a)`, and `e |> f` as `f(e)`.
+        let (callee, mut arguments, binding) = match expression.e2.as_ref() {
+          source::expr::E::Call(call) => {
+            (call.callee.clone(), call.arguments.clone(), call.binding.clone())
+          }
+          e2 => (
+            Box::new(e2.clone()),
+            source::expr::ParenthesizedExpressionList {
+              loc: e2.loc(),
+              start_associated_comments: source::NO_COMMENT_REFERENCE,
+              ending_associated_comments: source::NO_COMMENT_REFERENCE,
+              expressions: Vec::new(),
+            },
+            Default::default(),
+          ),
+        };
+        arguments.expressions.insert(0, expression.e1.as_ref().clone());
+        return self.lower_fn_call(&source::expr::Call {
+          common: expression.common.clone(),
+          callee,
+          arguments,
+          binding,
+        });
+      }
       source::expr::BinaryOperator::AND => {
         let temp = self.allocate_temp_variable();
         let LoweringResult { statements: s1, expression: e1 } = self.lower_binary(&expression.e1);
-        let LoweringResult { statements: s2, expression: e2 } = self.lower_binary(&expression.e2);
+        let before = self.mutable_variables.clone();
+        let LoweringResult { statements: mut s2, expression: e2 } =
+          self.lower_binary(&expression.e2);
         if let hir::Expression::IntLiteral(v) = &e1 {
           return if *v != 0 {
             LoweringResult { statements: s1.into_iter().chain(s2).collect_vec(), expression: e2 }
           } else {
+            self.mutable_variables = before;
             LoweringResult { statements: s1, expression: hir::ZERO }
           };
         }
+        let after_e2 = std::mem::replace(&mut self.mutable_variables, before.clone());
+        let mut short_circuit_statements = Vec::new();
+        let mut declarations = self.join_mutable_variables(
+          before,
+          vec![
+            (&mut s2, after_e2),
+            (&mut short_circuit_statements, self.mutable_variables.clone()),
+          ],
+        );
         let mut statements = s1;
+        statements.append(&mut declarations);
         statements.push(hir::Statement::IfElse {
           condition: e1,
           s1: s2,
-          s2: Vec::new(),
+          s2: short_circuit_statements,
           final_assignments: vec![(temp, hir::INT_TYPE, e2, hir::ZERO)],
         });
         return LoweringResult {
@@ -499,18 +1081,31 @@ impl<'a> ExpressionLoweringManager<'a> {
       source::expr::BinaryOperator::OR => {
         let temp = self.allocate_temp_variable();
         let LoweringResult { statements: s1, expression: e1 } = self.lower_binary(&expression.e1);
-        let LoweringResult { statements: s2, expression: e2 } = self.lower_binary(&expression.e2);
+        let before = self.mutable_variables.clone();
+        let LoweringResult { statements: mut s2, expression: e2 } =
+          self.lower_binary(&expression.e2);
         if let hir::Expression::IntLiteral(v) = &e1 {
           return if *v != 0 {
+            self.mutable_variables = before;
             LoweringResult { statements: s1, expression: hir::ONE }
           } else {
             LoweringResult { statements: s1.into_iter().chain(s2).collect_vec(), expression: e2 }
           };
         }
+        let after_e2 = std::mem::replace(&mut self.mutable_variables, before.clone());
+        let mut short_circuit_statements = Vec::new();
+        let mut declarations = self.join_mutable_variables(
+          before,
+          vec![
+            (&mut short_circuit_statements, self.mutable_variables.clone()),
+            (&mut s2, after_e2),
+          ],
+        );
         let mut statements = s1;
+        statements.append(&mut declarations);
         statements.push(hir::Statement::IfElse {
           condition: e1,
-          s1: Vec::new(),
+          s1: short_circuit_statements,
           s2,
           final_assignments: vec![(temp, hir::INT_TYPE, hir::ONE, e2)],
         });
@@ -521,8 +1116,8 @@ impl<'a> ExpressionLoweringManager<'a> {
       }
       source::expr::BinaryOperator::CONCAT => {
         if let (
-          source::expr::E::Literal(_, source::Literal::String(s1)),
-          source::expr::E::Literal(_, source::Literal::String(s2)),
+          source::expr::E::Literal(_, source::Literal::String(s1, _)),
+          source::expr::E::Literal(_, source::Literal::String(s2, _)),
         ) = (expression.e1.as_ref(), expression.e2.as_ref())
         {
           let concat_string = format!("{}{}", s1.as_str(self.heap), s2.as_str(self.heap));
@@ -535,36 +1130,20 @@ impl<'a> ExpressionLoweringManager<'a> {
         let mut lowered_stmts = Vec::new();
         let e1 = self.lowered_and_add_statements(&expression.e1, &mut lowered_stmts);
         let e2 = self.lowered_and_add_statements(&expression.e2, &mut lowered_stmts);
-        let return_collector_name = self.allocate_temp_variable();
-        lowered_stmts.push(hir::Statement::Call {
-          callee: hir::Callee::FunctionName(hir::FunctionNameExpression {
-            name: hir::FunctionName {
-              type_name: hir::TypeName {
-                module_reference: Some(ModuleReference::ROOT),
-                type_name: PStr::STR_TYPE,
-              },
-              fn_name: PStr::CONCAT,
-            },
-            type_: hir::Type::new_fn_unwrapped(
-              vec![hir::STRING_TYPE.dupe(), hir::STRING_TYPE.dupe()],
-              hir::STRING_TYPE.dupe(),
-            ),
-            type_arguments: Vec::new(),
-          }),
-          arguments: vec![e1, e2],
-          return_type: hir::STRING_TYPE.dupe(),
-          return_collector: Some(return_collector_name),
-        });
-        return LoweringResult {
-          statements: lowered_stmts,
-          expression: hir::Expression::var_name(return_collector_name, hir::STRING_TYPE.dupe()),
-        };
+        let expression = self.call_str_builtin(PStr::CONCAT, vec![e1, e2], &mut lowered_stmts);
+        return LoweringResult { statements: lowered_stmts, expression };
       }
       source::expr::BinaryOperator::MUL => hir::BinaryOperator::MUL,
       source::expr::BinaryOperator::DIV => hir::BinaryOperator::DIV,
       source::expr::BinaryOperator::MOD => hir::BinaryOperator::MOD,
       source::expr::BinaryOperator::PLUS => hir::BinaryOperator::PLUS,
       source::expr::BinaryOperator::MINUS => hir::BinaryOperator::MINUS,
+      source::expr::BinaryOperator::SHL => hir::BinaryOperator::SHL,
+      source::expr::BinaryOperator::ASHR => hir::BinaryOperator::ASHR,
+      source::expr::BinaryOperator::SHR => hir::BinaryOperator::SHR,
+      source::expr::BinaryOperator::LAND => hir::BinaryOperator::LAND,
+      source::expr::BinaryOperator::XOR => hir::BinaryOperator::XOR,
+      source::expr::BinaryOperator::LOR => hir::BinaryOperator::LOR,
       source::expr::BinaryOperator::LT => hir::BinaryOperator::LT,
       source::expr::BinaryOperator::LE => hir::BinaryOperator::LE,
       source::expr::BinaryOperator::GT => hir::BinaryOperator::GT,
@@ -572,6 +1151,23 @@ impl<'a> ExpressionLoweringManager<'a> {
       source::expr::BinaryOperator::EQ => hir::BinaryOperator::EQ,
       source::expr::BinaryOperator::NE => hir::BinaryOperator::NE,
     };
+    let (operator, result_type) = if is_float_type(expression.e1.type_()) {
+      match operator {
+        hir::BinaryOperator::MUL => (hir::BinaryOperator::FMUL, hir::FLOAT_TYPE),
+        hir::BinaryOperator::DIV => (hir::BinaryOperator::FDIV, hir::FLOAT_TYPE),
+        hir::BinaryOperator::PLUS => (hir::BinaryOperator::FPLUS, hir::FLOAT_TYPE),
+        hir::BinaryOperator::MINUS => (hir::BinaryOperator::FMINUS, hir::FLOAT_TYPE),
+        hir::BinaryOperator::LT => (hir::BinaryOperator::FLT, hir::INT_TYPE),
+        hir::BinaryOperator::LE => (hir::BinaryOperator::FLE, hir::INT_TYPE),
+        hir::BinaryOperator::GT => (hir::BinaryOperator::FGT, hir::INT_TYPE),
+        hir::BinaryOperator::GE => (hir::BinaryOperator::FGE, hir::INT_TYPE),
+        hir::BinaryOperator::EQ => (hir::BinaryOperator::FEQ, hir::INT_TYPE),
+        hir::BinaryOperator::NE => (hir::BinaryOperator::FNE, hir::INT_TYPE),
+        op => (op, hir::INT_TYPE),
+      }
+    } else {
+      (operator, hir::INT_TYPE)
+    };
     let mut lowered_stmts = Vec::new();
     let e1 = self.lowered_and_add_statements(&expression.e1, &mut lowered_stmts);
     let e2 = self.lowered_and_add_statements(&expression.e2, &mut lowered_stmts);
@@ -579,7 +1175,7 @@ impl<'a> ExpressionLoweringManager<'a> {
     lowered_stmts.push(hir::Statement::Binary { name: value_temp, operator, e1, e2 });
     LoweringResult {
       statements: lowered_stmts,
-      expression: hir::Expression::var_name(value_temp, hir::INT_TYPE),
+      expression: hir::Expression::var_name(value_temp, result_type),
     }
   }
 
@@ -609,21 +1205,34 @@ impl<'a> ExpressionLoweringManager<'a> {
         condition
       }
     };
+    let expected_type =
+      self.type_lowering_manager.lower_source_type(self.heap, &expression.common.type_);
     if condition == hir::ONE {
+      let lowered_e1 = self.lower_block(&expression.e1);
       let LoweringResult { statements: mut to_append, expression } =
-        self.lower_block(&expression.e1);
+        self.upcast_lowering_result(lowered_e1, &expected_type);
       lowered_stmts.append(&mut to_append);
       return LoweringResult { statements: lowered_stmts, expression };
     } else if condition == hir::ZERO {
+      let lowered_e2 = self.lower_if_else_or_block(&expression.e2);
       let LoweringResult { statements: mut to_append, expression } =
-        self.lower_if_else_or_block(&expression.e2);
+        self.upcast_lowering_result(lowered_e2, &expected_type);
       lowered_stmts.append(&mut to_append);
       return LoweringResult { statements: lowered_stmts, expression };
     }
     let final_var_name = self.allocate_temp_variable();
-    let LoweringResult { statements: s1, expression: e1 } = self.lower_block(&expression.e1);
-    let LoweringResult { statements: s2, expression: e2 } =
-      self.lower_if_else_or_block(&expression.e2);
+    let before = self.mutable_variables.clone();
+    let lowered_e1 = self.lower_block(&expression.e1);
+    let LoweringResult { statements: mut s1, expression: e1 } =
+      self.upcast_lowering_result(lowered_e1, &expected_type);
+    let after_e1 = std::mem::replace(&mut self.mutable_variables, before.clone());
+    let lowered_e2 = self.lower_if_else_or_block(&expression.e2);
+    let LoweringResult { statements: mut s2, expression: e2 } =
+      self.upcast_lowering_result(lowered_e2, &expected_type);
+    let after_e2 = self.mutable_variables.clone();
+    let mut declarations =
+      self.join_mutable_variables(before, vec![(&mut s1, after_e1), (&mut s2, after_e2)]);
+    lowered_stmts.append(&mut declarations);
     let lowered_return_type = e1.type_().dupe();
     lowered_stmts.push(hir::Statement::IfElse {
       condition,
@@ -712,7 +1321,8 @@ impl<'a> ExpressionLoweringManager<'a> {
         let id_type = lowered_expression.type_().as_id().unwrap();
         let resolved_struct_mappings = self.resolve_struct_mapping_of_id_type(id_type);
         let mut acc = LoweringResult { statements: Vec::new(), expression: hir::ONE };
-        for (index, nested) in elements.iter().enumerate().rev() {
+        for nested in elements.iter().rev() {
+          let index = nested.field_order;
           let field_type = &resolved_struct_mappings[index];
           let name = self.allocate_temp_variable();
           let LoweringResult {
@@ -840,6 +1450,44 @@ impl<'a> ExpressionLoweringManager<'a> {
       source::pattern::MatchingPattern::Wildcard { .. } => {
         LoweringResult { statements: Vec::with_capacity(0), expression: hir::ONE }
       }
+      source::pattern::MatchingPattern::Literal {
+        literal: source::Literal::Bool(true), ..
+      } => LoweringResult { statements: Vec::with_capacity(0), expression: lowered_expression },
+      source::pattern::MatchingPattern::Literal {
+        literal: source::Literal::Bool(false), ..
+      } => {
+        let name = self.allocate_temp_variable();
+        LoweringResult {
+          statements: vec![hir::Statement::Not { name, operand: lowered_expression }],
+          expression: hir::Expression::var_name(name, hir::INT_TYPE),
+        }
+      }
+      source::pattern::MatchingPattern::Literal { literal, .. } => {
+        // A single equality test per case, so a chain of int cases stays a flat sequence of
+        // comparisons against constants. Str equality is resolved to a content comparison by
+        // the backends.
+        let (operator, constant) = match literal {
+          source::Literal::Bool(_) => unreachable!("Handled above."),
+          source::Literal::Int(i, _) => (hir::BinaryOperator::EQ, hir::Expression::int(*i)),
+          source::Literal::Float(f) => {
+            (hir::BinaryOperator::FEQ, hir::Expression::FloatLiteral(*f))
+          }
+          source::Literal::String(s, _) => (
+            hir::BinaryOperator::EQ,
+            hir::Expression::StringName(self.string_manager.allocate(*s).0),
+          ),
+        };
+        let name = self.allocate_temp_variable();
+        LoweringResult {
+          statements: vec![hir::Statement::Binary {
+            name,
+            operator,
+            e1: lowered_expression,
+            e2: constant,
+          }],
+          expression: hir::Expression::var_name(name, hir::INT_TYPE),
+        }
+      }
       source::pattern::MatchingPattern::Or { patterns, .. } => {
         if patterns.is_empty() {
           return LoweringResult { statements: Vec::new(), expression: hir::ZERO };
@@ -875,46 +1523,23 @@ impl<'a> ExpressionLoweringManager<'a> {
   fn lower_match(&mut self, expression: &source::expr::Match<Arc<type_::Type>>) -> LoweringResult {
     let mut lowered_stmts = Vec::new();
     let matched_expr = self.lowered_and_add_statements(&expression.matched, &mut lowered_stmts);
-
-    let unreachable_branch_collector = self.allocate_temp_variable();
     let final_return_type =
       self.type_lowering_manager.lower_source_type(self.heap, &expression.common.type_);
-    let mut acc = (
-      vec![hir::Statement::Call {
-        callee: hir::Callee::FunctionName(hir::FunctionNameExpression {
-          name: hir::FunctionName {
-            type_name: hir::TypeName {
-              module_reference: Some(ModuleReference::ROOT),
-              type_name: PStr::PROCESS_TYPE,
-            },
-            fn_name: PStr::PANIC,
-          },
-          type_: hir::FunctionType {
-            argument_types: vec![hir::INT_TYPE, hir::STRING_TYPE.dupe()],
-            return_type: Box::new(final_return_type.dupe()),
-          },
-          type_arguments: vec![final_return_type.dupe()],
-        }),
-        arguments: vec![
-          hir::ZERO,
-          hir::Expression::StringName(self.string_manager.allocate(PStr::EMPTY).0),
-        ],
-        return_type: final_return_type.dupe(),
-        return_collector: Some(unreachable_branch_collector),
-      }],
-      hir::Expression::var_name(unreachable_branch_collector, final_return_type),
-    );
+
+    // Cases are lowered in evaluation order, so that `var` values flow from one case test to the
+    // next. They are nested into a chain of if-else afterwards.
+    let before = self.mutable_variables.clone();
+    let mut lowered_cases = Vec::new();
     for source::expr::VariantPatternToExpression {
       loc: _,
       pattern,
+      guard,
       body,
       ending_associated_comments: _,
-    } in expression.cases.iter().rev()
+    } in &expression.cases
     {
       let final_assignment_temp = self.allocate_temp_variable();
-      let lowered_return_type = acc.1.type_().dupe();
-      let (acc_stmts, acc_e) = acc;
-      let mut new_stmts = Vec::new();
+      let mut test_stmts = Vec::new();
       self.variable_cx.push_scope();
       let mut binding_names = HashMap::new();
       for (n, t) in pattern.bindings() {
@@ -922,34 +1547,115 @@ impl<'a> ExpressionLoweringManager<'a> {
         binding_names.insert(n, name);
         let type_ = self.type_lowering_manager.lower_source_type(self.heap, t);
         bind_value(&mut self.variable_cx, n, hir::Expression::var_name(name, type_.dupe()));
-        new_stmts.push(hir::Statement::LateInitDeclaration { name, type_ });
+        test_stmts.push(hir::Statement::LateInitDeclaration { name, type_ });
       }
       let LoweringResult { statements: mut binding_stmts, expression: match_success_condition } =
         self.lower_matching_pattern(pattern, &binding_names, matched_expr.dupe());
-      new_stmts.append(&mut binding_stmts);
-      let body_lowering_result = self.lower(body);
+      test_stmts.append(&mut binding_stmts);
+      // The guard only runs after the pattern matches. When it fails, we fall through to the
+      // remaining cases, which are shared with the pattern mismatch branch.
+      let match_success_condition = if let Some(guard) = guard {
+        let before_guard = self.mutable_variables.clone();
+        let LoweringResult { statements: mut guard_stmts, expression: guard_expr } =
+          self.lower(guard);
+        let after_guard = std::mem::replace(&mut self.mutable_variables, before_guard.clone());
+        let mut skipped_guard_stmts = Vec::new();
+        let mut declarations = self.join_mutable_variables(
+          before_guard.clone(),
+          vec![(&mut guard_stmts, after_guard), (&mut skipped_guard_stmts, before_guard)],
+        );
+        test_stmts.append(&mut declarations);
+        let guarded_condition_temp = self.allocate_temp_variable();
+        test_stmts.push(hir::Statement::IfElse {
+          condition: match_success_condition,
+          s1: guard_stmts,
+          s2: skipped_guard_stmts,
+          final_assignments: vec![(guarded_condition_temp, hir::INT_TYPE, guard_expr, hir::ZERO)],
+        });
+        hir::Expression::var_name(guarded_condition_temp, hir::INT_TYPE)
+      } else {
+        match_success_condition
+      };
+      let state_after_test = self.mutable_variables.clone();
+      let lowered_body = self.lower(body);
+      let body_lowering_result = self.upcast_lowering_result(lowered_body, &final_return_type);
       self.variable_cx.pop_scope();
-      new_stmts.push(hir::Statement::IfElse {
+      let state_after_body = std::mem::replace(&mut self.mutable_variables, state_after_test);
+      lowered_cases.push((
+        final_assignment_temp,
+        test_stmts,
+        match_success_condition,
+        body_lowering_result,
+        state_after_body,
+      ));
+    }
+
+    let unreachable_branch_collector = self.allocate_temp_variable();
+    let mut unreachable_stmts = vec![hir::Statement::Call {
+      callee: hir::Callee::FunctionName(hir::FunctionNameExpression {
+        name: hir::FunctionName {
+          type_name: hir::TypeName {
+            module_reference: Some(ModuleReference::ROOT),
+            type_name: PStr::PROCESS_TYPE,
+          },
+          fn_name: PStr::PANIC,
+        },
+        type_: hir::FunctionType {
+          argument_types: vec![hir::INT_TYPE, hir::STRING_TYPE.dupe()],
+          return_type: Box::new(final_return_type.dupe()),
+        },
+        type_arguments: vec![final_return_type.dupe()],
+      }),
+      arguments: vec![
+        hir::ZERO,
+        hir::Expression::StringName(self.string_manager.allocate(PStr::EMPTY).0),
+      ],
+      return_type: final_return_type.dupe(),
+      return_collector: Some(unreachable_branch_collector),
+    }];
+    let state_after_unreachable = self.mutable_variables.clone();
+    let mut declarations = self.join_mutable_variables(
+      before,
+      lowered_cases
+        .iter_mut()
+        .map(|(_, _, _, LoweringResult { statements, .. }, after)| (statements, after.clone()))
+        .chain(std::iter::once((&mut unreachable_stmts, state_after_unreachable)))
+        .collect_vec(),
+    );
+    lowered_stmts.append(&mut declarations);
+
+    let mut acc = (
+      unreachable_stmts,
+      hir::Expression::var_name(unreachable_branch_collector, final_return_type.dupe()),
+    );
+    for (final_assignment_temp, mut test_stmts, match_success_condition, body, _) in
+      lowered_cases.into_iter().rev()
+    {
+      let (acc_stmts, acc_e) = acc;
+      test_stmts.push(hir::Statement::IfElse {
         condition: match_success_condition,
-        s1: body_lowering_result.statements,
+        s1: body.statements,
         s2: acc_stmts,
         final_assignments: vec![(
           final_assignment_temp,
-          lowered_return_type.dupe(),
-          body_lowering_result.expression,
+          final_return_type.dupe(),
+          body.expression,
           acc_e,
         )],
       });
-      acc = (new_stmts, hir::Expression::var_name(final_assignment_temp, lowered_return_type))
+      acc = (test_stmts, hir::Expression::var_name(final_assignment_temp, final_return_type.dupe()))
     }
 
     lowered_stmts.append(&mut acc.0);
     LoweringResult { statements: lowered_stmts, expression: acc.1 }
   }
 
+  /// When the lambda is a local function, `recursive_name` is its name, which is bound in the body
+  /// to a closure rebuilt from the same function and context.
   fn create_synthetic_lambda_function(
     &mut self,
     expression: &source::expr::Lambda<Arc<type_::Type>>,
+    recursive_name: Option<PStr>,
     captured: &[(PStr, hir::Expression)],
     context_type: &hir::Type,
   ) -> hir::Function {
@@ -991,6 +1697,34 @@ impl<'a> ExpressionLoweringManager<'a> {
       &source_fn_type.return_type,
     );
     let fn_name = self.allocate_synthetic_fn_name();
+    let fn_type = hir::FunctionType {
+      argument_types: vec![context_type.dupe()]
+        .into_iter()
+        .chain(fun_type_without_cx_argument_types.iter().cloned())
+        .collect_vec(),
+      return_type: fun_type_without_cx_return_type.clone(),
+    };
+    let recursive_binding = recursive_name.map(|name| {
+      let closure_type = self.get_synthetic_identifier_type_from_closure(hir::FunctionType {
+        argument_types: fun_type_without_cx_argument_types.clone(),
+        return_type: fun_type_without_cx_return_type.clone(),
+      });
+      lambda_stmts.push(hir::Statement::ClosureInit {
+        closure_variable_name: name,
+        closure_type: closure_type.dupe(),
+        function_name: hir::FunctionNameExpression {
+          name: fn_name,
+          type_: fn_type.clone(),
+          type_arguments: type_parameters
+            .iter()
+            .copied()
+            .map(hir::Type::new_generic_type)
+            .collect_vec(),
+        },
+        context: hir::Expression::var_name(PStr::UNDERSCORE_THIS, context_type.dupe()),
+      });
+      (name, hir::Type::Id(closure_type))
+    });
     let mut manager = ExpressionLoweringManager::new(
       self.module_reference,
       parameters
@@ -998,13 +1732,16 @@ impl<'a> ExpressionLoweringManager<'a> {
         .zip(fun_type_without_cx_argument_types.iter().cloned())
         .chain(self.defined_variables.iter().cloned())
         .chain(captured_renamed.iter().map(|(_orig, body_name, t)| (*body_name, t.dupe())))
+        .chain(recursive_binding)
         .collect_vec(),
       self.type_definition_mapping,
       self.heap,
       self.type_lowering_manager,
       self.string_manager,
+      self.strip_asserts,
       self.next_synthetic_fn_id_manager,
     );
+    manager.return_type = Some(fun_type_without_cx_return_type.as_ref().dupe());
     for (orig, body_name, t) in &captured_renamed {
       if *orig != *body_name {
         bind_value(
@@ -1029,13 +1766,7 @@ impl<'a> ExpressionLoweringManager<'a> {
         .chain(expression.parameters.parameters.iter().map(|it| it.name.name))
         .collect_vec(),
       type_parameters,
-      type_: hir::FunctionType {
-        argument_types: vec![context_type.dupe()]
-          .into_iter()
-          .chain(fun_type_without_cx_argument_types)
-          .collect_vec(),
-        return_type: fun_type_without_cx_return_type,
-      },
+      type_: fn_type,
       body: lambda_stmts,
       return_value: lowered_e,
     }
@@ -1044,10 +1775,13 @@ impl<'a> ExpressionLoweringManager<'a> {
   fn lower_lambda(
     &mut self,
     expression: &source::expr::Lambda<Arc<type_::Type>>,
+    recursive_name: Option<PStr>,
   ) -> LoweringResult {
     let captured = expression
       .captured
       .keys()
+      // A local function captures itself, but it is rebuilt from its own context instead.
+      .filter(|k| Some(**k) != recursive_name)
       .map(|k| {
         let resolved_name = if *k == PStr::THIS { PStr::UNDERSCORE_THIS } else { *k };
         (resolved_name, self.resolve_variable(&resolved_name))
@@ -1076,7 +1810,7 @@ impl<'a> ExpressionLoweringManager<'a> {
       hir::Expression::var_name(context_name, hir::Type::Id(context_type))
     };
     let synthetic_lambda =
-      self.create_synthetic_lambda_function(expression, &captured, context.type_());
+      self.create_synthetic_lambda_function(expression, recursive_name, &captured, context.type_());
     let closure_type = self.get_synthetic_identifier_type_from_closure(hir::FunctionType {
       argument_types: synthetic_lambda.type_.argument_types.iter().skip(1).cloned().collect_vec(),
       return_type: synthetic_lambda.type_.return_type.clone(),
@@ -1108,25 +1842,416 @@ impl<'a> ExpressionLoweringManager<'a> {
     }
   }
 
-  fn lower_block(&mut self, expression: &source::expr::Block<Arc<type_::Type>>) -> LoweringResult {
-    let mut lowered_stmts = Vec::new();
-    self.variable_cx.push_scope();
-    for s in &expression.statements {
-      match s {
-        source::expr::Statement::Declaration(decl_stmt) => {
-          let assigned_expr =
-            self.lowered_and_add_statements(&decl_stmt.assigned_expression, &mut lowered_stmts);
-          let mut binding_names = HashMap::new();
-          for (n, t) in decl_stmt.pattern.bindings() {
-            let name = self.allocate_temp_variable();
-            binding_names.insert(n, name);
+  fn lower_return(
+    &mut self,
+    expression: &source::expr::Return<Arc<type_::Type>>,
+  ) -> LoweringResult {
+    let LoweringResult { mut statements, expression: argument } = self.lower(&expression.argument);
+    let argument = if let Some(return_type) = self.return_type.clone() {
+      self.upcast(argument, &return_type, &mut statements)
+    } else {
+      argument
+    };
+    statements.push(hir::Statement::Return(argument));
+    self.lower_diverging_value(statements, &expression.common.type_)
+  }
+
+  /// Control never reaches past a return or a break, but the expression still needs a well-typed
+  /// value.
+  fn lower_diverging_value(
+    &mut self,
+    mut statements: Vec<hir::Statement>,
+    source_type: &type_::Type,
+  ) -> LoweringResult {
+    let type_ = self.type_lowering_manager.lower_source_type(self.heap, source_type);
+    match &type_ {
+      hir::Type::Int32 => return LoweringResult { statements, expression: hir::ZERO },
+      hir::Type::Int31 => {
+        return LoweringResult { statements, expression: hir::Expression::Int31Zero };
+      }
+      hir::Type::Float => {
+        return LoweringResult { statements, expression: hir::Expression::float(0.0) };
+      }
+      hir::Type::Id(_) => {}
+    }
+    let name = self.allocate_temp_variable();
+    statements.push(hir::Statement::LateInitDeclaration { name, type_: type_.dupe() });
+    LoweringResult { statements, expression: hir::Expression::var_name(name, type_) }
+  }
+
+  /// Promotes every `var` in scope that is reassigned by the loop to a loop variable. Each one
+  /// also gets an exit slot, which holds its value whenever control leaves the loop by a break.
+  fn enter_loop(
+    &mut self,
+    assigned_variables: HashSet<PStr>,
+    statements: &mut Vec<hir::Statement>,
+  ) {
+    let lowered_names = assigned_variables
+      .into_iter()
+      .filter_map(|n| match self.variable_cx.get(&n) {
+        Some(hir::Expression::Variable(v)) if self.mutable_variables.contains_key(&v.name) => {
+          Some(v.name)
+        }
+        _ => None,
+      })
+      .sorted()
+      .collect_vec();
+    let mut loop_carried_variables = Vec::new();
+    for name in lowered_names {
+      let loop_variable = self.allocate_temp_variable();
+      let exit_slot = self.allocate_temp_variable();
+      let (type_, value) = self.mutable_variables.get_mut(&name).unwrap();
+      let initial_value =
+        std::mem::replace(value, hir::Expression::var_name(loop_variable, type_.dupe()));
+      statements.push(hir::Statement::LateInitDeclaration { name: exit_slot, type_: type_.dupe() });
+      statements.push(hir::Statement::LateInitAssignment {
+        name: exit_slot,
+        assigned_expression: initial_value.dupe(),
+      });
+      loop_carried_variables.push(LoopCarriedVariable {
+        name,
+        type_: type_.dupe(),
+        loop_variable,
+        initial_value,
+        exit_slot,
+      });
+    }
+    self.loop_carried_variables.push(loop_carried_variables);
+  }
+
+  /// Leaves the innermost loop with a break.
+  fn lower_break(&mut self) -> Vec<hir::Statement> {
+    let mut statements = Vec::new();
+    for v in self.loop_carried_variables.last().into_iter().flatten() {
+      let (_, value) = self.mutable_variables.get(&v.name).unwrap();
+      if *value != hir::Expression::var_name(v.loop_variable, v.type_.dupe()) {
+        statements.push(hir::Statement::LateInitAssignment {
+          name: v.exit_slot,
+          assigned_expression: value.dupe(),
+        });
+      }
+    }
+    statements.push(hir::Statement::Break);
+    statements
+  }
+
+  /// Ends an iteration of the innermost loop, and returns its `var` loop variables.
+  fn exit_loop(
+    &mut self,
+    loop_statements: &mut Vec<hir::Statement>,
+  ) -> Vec<(PStr, hir::Type, hir::Expression, hir::Expression)> {
+    let mut loop_variables = Vec::new();
+    for v in self.loop_carried_variables.pop().unwrap() {
+      let (_, value) = self.mutable_variables.get_mut(&v.name).unwrap();
+      let next_value =
+        std::mem::replace(value, hir::Expression::var_name(v.exit_slot, v.type_.dupe()));
+      loop_statements.push(hir::Statement::LateInitAssignment {
+        name: v.exit_slot,
+        assigned_expression: next_value.dupe(),
+      });
+      loop_variables.push((v.loop_variable, v.type_, v.initial_value, next_value));
+    }
+    loop_variables
+  }
+
+  fn lower_while(&mut self, expression: &source::expr::While<Arc<type_::Type>>) -> LoweringResult {
+    let mut statements = Vec::new();
+    let mut assigned_variables = HashSet::new();
+    collect_assigned_variables(&expression.condition, &mut assigned_variables);
+    collect_assigned_variables_in_block(&expression.body, &mut assigned_variables);
+    self.enter_loop(assigned_variables, &mut statements);
+    let mut loop_statements = Vec::new();
+    let condition = self.lowered_and_add_statements(&expression.condition, &mut loop_statements);
+    let break_statements = self.lower_break();
+    loop_statements.push(hir::Statement::SingleIf {
+      condition,
+      invert_condition: true,
+      statements: break_statements,
+    });
+    let LoweringResult { statements: mut body_statements, expression: _ } =
+      self.lower_block(&expression.body);
+    loop_statements.append(&mut body_statements);
+    let loop_variables = self.exit_loop(&mut loop_statements);
+    statements.push(hir::Statement::While { loop_variables, statements: loop_statements });
+    LoweringResult { statements, expression: hir::ZERO }
+  }
+
+  fn lower_for(&mut self, expression: &source::expr::For<Arc<type_::Type>>) -> LoweringResult {
+    let mut statements = Vec::new();
+    // Both bounds are evaluated exactly once before the loop starts.
+    let start = self.lowered_and_add_statements(&expression.start, &mut statements);
+    let end = self.lowered_and_add_statements(&expression.end, &mut statements);
+    let mut assigned_variables = HashSet::new();
+    collect_assigned_variables_in_block(&expression.body, &mut assigned_variables);
+    self.enter_loop(assigned_variables, &mut statements);
+    let iterator_name = self.allocate_temp_variable();
+    let condition_name = self.allocate_temp_variable();
+    let next_iterator_name = self.allocate_temp_variable();
+    let iterator = hir::Expression::var_name(iterator_name, hir::INT_TYPE);
+    // The loop is shaped as `while (true) { if (i >= end) break; body; i = i + 1; }`,
+    // so that the loop optimizations can recognize the iterator as an induction variable.
+    let mut loop_statements = vec![
+      hir::Statement::Binary {
+        name: condition_name,
+        operator: hir::BinaryOperator::GE,
+        e1: iterator.clone(),
+        e2: end,
+      },
+      hir::Statement::SingleIf {
+        condition: hir::Expression::var_name(condition_name, hir::INT_TYPE),
+        invert_condition: false,
+        statements: vec![hir::Statement::Break],
+      },
+    ];
+    self.variable_cx.push_scope();
+    bind_value(&mut self.variable_cx, expression.iterator.name, iterator.clone());
+    let LoweringResult { statements: mut body_statements, expression: _ } =
+      self.lower_block(&expression.body);
+    self.variable_cx.pop_scope();
+    loop_statements.append(&mut body_statements);
+    loop_statements.push(hir::Statement::Binary {
+      name: next_iterator_name,
+      operator: hir::BinaryOperator::PLUS,
+      e1: iterator,
+      e2: hir::ONE,
+    });
+    let mut loop_variables = vec![(
+      iterator_name,
+      hir::INT_TYPE,
+      start,
+      hir::Expression::var_name(next_iterator_name, hir::INT_TYPE),
+    )];
+    loop_variables.append(&mut self.exit_loop(&mut loop_statements));
+    statements.push(hir::Statement::While { loop_variables, statements: loop_statements });
+    LoweringResult { statements, expression: hir::ZERO }
+  }
+
+  fn lower_assert(
+    &mut self,
+    expression: &source::expr::Assert<Arc<type_::Type>>,
+  ) -> LoweringResult {
+    if self.strip_asserts {
+      return LoweringResult { statements: Vec::new(), expression: hir::ZERO };
+    }
+    let mut statements = Vec::new();
+    let condition =
+      self.lowered_and_add_statements(&expression.arguments.expressions[0], &mut statements);
+    let loc = expression.common.loc;
+    let location_message = format!(
+      "Assertion failed at {}:{}:{}",
+      loc.module_reference.pretty_print(self.heap),
+      loc.start.0 as u64 + 1,
+      loc.start.1 as u64 + 1
+    );
+    // The message is only evaluated when the assertion fails.
+    let before = self.mutable_variables.clone();
+    let LoweringResult { statements: mut failure_statements, expression: message } =
+      if let Some(custom_message) = expression.arguments.expressions.get(1) {
+        let prefix = self.heap.alloc_string(format!("{location_message}: "));
+        self.lower_binary(&source::expr::E::Binary(source::expr::Binary {
+          common: custom_message.common().clone(),
+          operator_preceding_comments: source::NO_COMMENT_REFERENCE,
+          operator: source::expr::BinaryOperator::CONCAT,
+          e1: Box::new(source::expr::E::Literal(
+            custom_message.common().clone(),
+            source::Literal::String(prefix, None),
+          )),
+          e2: Box::new(custom_message.clone()),
+        }))
+      } else {
+        let message = self.heap.alloc_string(location_message);
+        LoweringResult {
+          statements: Vec::new(),
+          expression: hir::Expression::StringName(self.string_manager.allocate(message).0),
+        }
+      };
+    // The failure branch never returns, so it cannot affect the values of `var` declarations.
+    self.mutable_variables = before;
+    failure_statements.push(hir::Statement::Call {
+      callee: hir::Callee::FunctionName(hir::FunctionNameExpression {
+        name: hir::FunctionName {
+          type_name: hir::TypeName {
+            module_reference: Some(ModuleReference::ROOT),
+            type_name: PStr::PROCESS_TYPE,
+          },
+          fn_name: PStr::PANIC,
+        },
+        type_: hir::FunctionType {
+          argument_types: vec![hir::INT_TYPE, hir::STRING_TYPE.dupe()],
+          return_type: Box::new(hir::INT_TYPE),
+        },
+        type_arguments: vec![hir::INT_TYPE],
+      }),
+      arguments: vec![hir::ZERO, message],
+      return_type: hir::INT_TYPE,
+      return_collector: None,
+    });
+    statements.push(hir::Statement::IfElse {
+      condition,
+      s1: Vec::new(),
+      s2: failure_statements,
+      final_assignments: Vec::new(),
+    });
+    LoweringResult { statements, expression: hir::ZERO }
+  }
+
+  fn lower_try(&mut self, expression: &source::expr::Try<Arc<type_::Type>>) -> LoweringResult {
+    let mut statements = Vec::new();
+    let argument = self.lowered_and_add_statements(&expression.argument, &mut statements);
+    let argument_type = expression.argument.type_().as_nominal().unwrap();
+    let (success_tag_name, failure_tag_name) = if argument_type.id == PStr::OPTION_TYPE {
+      (PStr::SOME, PStr::NONE)
+    } else {
+      (PStr::OK, PStr::ERROR)
+    };
+    let variants = self
+      .type_definition_mapping
+      .get(&hir::TypeName {
+        module_reference: Some(argument_type.module_reference),
+        type_name: argument_type.id,
+      })
+      .unwrap()
+      .mappings
+      .as_enum()
+      .unwrap();
+    let success_tag = variants.iter().position(|(name, _)| *name == success_tag_name).unwrap();
+    let failure_tag = variants.iter().position(|(name, _)| *name == failure_tag_name).unwrap();
+    let hir::Type::Id(return_type) = self.return_type.clone().unwrap() else {
+      panic!("The checker ensures that the function returns an Option or a Result.")
+    };
+
+    // The none or error case is rebuilt with the return type and returned immediately.
+    let return_variable = self.allocate_temp_variable();
+    let return_failure = |associated_data_list| {
+      vec![
+        hir::Statement::EnumInit {
+          enum_variable_name: return_variable,
+          enum_type: return_type.clone(),
+          tag: failure_tag,
+          associated_data_list,
+        },
+        hir::Statement::Return(hir::Expression::var_name(
+          return_variable,
+          hir::Type::Id(return_type.clone()),
+        )),
+      ]
+    };
+    let failure_statements = if let Some(error_type) = argument_type.type_arguments.get(1) {
+      let error_type = self.type_lowering_manager.lower_source_type(self.heap, error_type);
+      let error_variable = self.allocate_temp_variable();
+      let mut s1 = Vec::new();
+      let error_value = self.upcast(
+        hir::Expression::var_name(error_variable, error_type.dupe()),
+        &return_type.type_arguments[1],
+        &mut s1,
+      );
+      s1.append(&mut return_failure(vec![error_value]));
+      vec![hir::Statement::ConditionalDestructure {
+        test_expr: argument.dupe(),
+        tag: failure_tag,
+        bindings: vec![Some((error_variable, error_type))],
+        s1,
+        s2: Vec::new(),
+        final_assignments: Vec::new(),
+      }]
+    } else {
+      return_failure(Vec::new())
+    };
+    let LoweringResult { statements: failure_statements, expression: diverging_value } =
+      self.lower_diverging_value(failure_statements, &expression.common.type_);
+
+    let value_type =
+      self.type_lowering_manager.lower_source_type(self.heap, &expression.common.type_);
+    let value_variable = self.allocate_temp_variable();
+    let final_variable = self.allocate_temp_variable();
+    statements.push(hir::Statement::ConditionalDestructure {
+      test_expr: argument,
+      tag: success_tag,
+      bindings: vec![Some((value_variable, value_type.dupe()))],
+      s1: Vec::new(),
+      s2: failure_statements,
+      final_assignments: vec![(
+        final_variable,
+        value_type.dupe(),
+        hir::Expression::var_name(value_variable, value_type.dupe()),
+        diverging_value,
+      )],
+    });
+    LoweringResult { statements, expression: hir::Expression::var_name(final_variable, value_type) }
+  }
+
+  fn lower_block(&mut self, expression: &source::expr::Block<Arc<type_::Type>>) -> LoweringResult {
+    let mut lowered_stmts = Vec::new();
+    let mut declared_mutable_variables = Vec::new();
+    self.variable_cx.push_scope();
+    for s in &expression.statements {
+      match s {
+        source::expr::Statement::Declaration(decl_stmt) => {
+          let mut assigned_expr =
+            self.lowered_and_add_statements(&decl_stmt.assigned_expression, &mut lowered_stmts);
+          if let Some(annotation) = &decl_stmt.annotation {
+            let annotated_type = self
+              .type_lowering_manager
+              .lower_source_type(self.heap, &type_::Type::from_annotation(annotation));
+            assigned_expr = self.upcast(assigned_expr, &annotated_type, &mut lowered_stmts);
+          }
+          if decl_stmt.is_mutable {
+            // The parser only allows a single name to be bound by a `var` declaration.
+            let (n, t) = decl_stmt.pattern.bindings().into_iter().exactly_one().ok().unwrap();
+            let name = self.allocate_temp_variable();
+            let type_ = self.type_lowering_manager.lower_source_type(self.heap, t);
+            bind_value(&mut self.variable_cx, n, hir::Expression::var_name(name, type_.dupe()));
+            self.mutable_variables.insert(name, (type_, assigned_expr));
+            declared_mutable_variables.push(name);
+            continue;
+          }
+          // The else block is lowered before the bindings are in scope, since it cannot use them.
+          let lowered_else_block = decl_stmt.else_block.as_ref().map(|else_block| {
+            let before = self.mutable_variables.clone();
+            let LoweringResult { statements, expression: _ } = self.lower_block(else_block);
+            // The else block never completes, so the values assigned in it are never observed.
+            self.mutable_variables = before;
+            statements
+          });
+          let mut binding_names = HashMap::new();
+          for (n, t) in decl_stmt.pattern.bindings() {
+            let name = self.allocate_temp_variable();
+            binding_names.insert(n, name);
             let type_ = self.type_lowering_manager.lower_source_type(self.heap, t);
             bind_value(&mut self.variable_cx, n, hir::Expression::var_name(name, type_.dupe()));
             lowered_stmts.push(hir::Statement::LateInitDeclaration { name, type_ });
           }
-          let LoweringResult { statements: mut stmts, expression: _ } =
+          let LoweringResult { statements: mut stmts, expression: condition } =
             self.lower_matching_pattern(&decl_stmt.pattern, &binding_names, assigned_expr);
           lowered_stmts.append(&mut stmts);
+          if let Some(s2) = lowered_else_block
+            && condition != hir::ONE
+          {
+            lowered_stmts.push(hir::Statement::IfElse {
+              condition,
+              s1: Vec::new(),
+              s2,
+              final_assignments: Vec::new(),
+            });
+          }
+        }
+        source::expr::Statement::Assignment(assign_stmt) => {
+          let assigned_expr =
+            self.lowered_and_add_statements(&assign_stmt.assigned_expression, &mut lowered_stmts);
+          let hir::Expression::Variable(hir::VariableName { name, type_ }) =
+            self.resolve_variable(&assign_stmt.name.name)
+          else {
+            panic!("Mutable variables are always lowered into variables.")
+          };
+          let assigned_expr = self.upcast(assigned_expr, &type_, &mut lowered_stmts);
+          self.mutable_variables.get_mut(&name).unwrap().1 = assigned_expr;
+        }
+        source::expr::Statement::Function(function_stmt) => {
+          let name = function_stmt.name.name;
+          let LoweringResult { statements: mut stmts, expression: closure } =
+            self.lower_lambda(&function_stmt.lambda, Some(name));
+          lowered_stmts.append(&mut stmts);
+          bind_value(&mut self.variable_cx, name, closure);
         }
         source::expr::Statement::Expression(expr) => {
           self.lowered_and_add_statements(expr, &mut lowered_stmts);
@@ -1139,6 +2264,9 @@ impl<'a> ExpressionLoweringManager<'a> {
       hir::ZERO
     };
     self.variable_cx.pop_scope();
+    for name in declared_mutable_variables {
+      self.mutable_variables.remove(&name);
+    }
     LoweringResult { statements: lowered_stmts, expression: final_expr }
   }
 }
@@ -1147,7 +2275,11 @@ fn lower_source_expression(
   mut manager: ExpressionLoweringManager,
   expression: &source::expr::E<Arc<type_::Type>>,
 ) -> LoweringResultWithSyntheticFunctions {
-  let LoweringResult { statements, expression } = manager.lower(expression);
+  let mut lowering_result = manager.lower(expression);
+  if let Some(return_type) = manager.return_type.clone() {
+    lowering_result = manager.upcast_lowering_result(lowering_result, &return_type);
+  }
+  let LoweringResult { statements, expression } = lowering_result;
   LoweringResultWithSyntheticFunctions {
     synthetic_functions: manager.synthetic_functions,
     statements,
@@ -1245,6 +2377,107 @@ fn lower_constructors(
   functions
 }
 
+fn const_initializer_name(module_reference: ModuleReference, name: PStr) -> hir::FunctionName {
+  hir::FunctionName {
+    type_name: hir::TypeName { module_reference: Some(module_reference), type_name: name },
+    fn_name: PStr::INIT,
+  }
+}
+
+/// Orders constants so that each one is initialized after all the constants it reads.
+fn sort_global_constants(
+  global_cx: &type_::GlobalSignature,
+  mut const_types: HashMap<(ModuleReference, PStr), hir::Type>,
+) -> Vec<hir::GlobalConstant> {
+  fn visit(
+    global_cx: &type_::GlobalSignature,
+    const_types: &mut HashMap<(ModuleReference, PStr), hir::Type>,
+    key: (ModuleReference, PStr),
+    collector: &mut Vec<hir::GlobalConstant>,
+  ) {
+    let Some(type_) = const_types.remove(&key) else {
+      return;
+    };
+    for dependency in &global_cx[&key.0].constants[&key.1].dependencies {
+      visit(global_cx, const_types, *dependency, collector);
+    }
+    collector
+      .push(hir::GlobalConstant { initializer: const_initializer_name(key.0, key.1), type_ });
+  }
+
+  let mut collector = Vec::new();
+  for key in const_types.keys().copied().sorted() {
+    visit(global_cx, &mut const_types, key, &mut collector);
+  }
+  collector
+}
+
+/// Lowers a method that a class inherits from an interface default. It forwards to the default
+/// body with the class as the implementing type, so that the body is specialized per class.
+fn lower_inherited_default_method(
+  heap: &mut Heap,
+  type_lowering_manager: &mut TypeLoweringManager,
+  class_type: &type_::NominalType,
+  class_tparams: &[PStr],
+  method_name: PStr,
+  interface_type: &type_::NominalType,
+  signature: &type_::MemberSignature,
+) -> hir::Function {
+  let method_tparams = signature.type_parameters.iter().map(|it| it.name).collect_vec();
+  let tparams: OrderSet<_> = class_tparams.iter().chain(&method_tparams).copied().collect();
+  type_lowering_manager.generic_types = tparams.clone();
+  let this_type =
+    type_lowering_manager.lower_source_type(heap, &type_::Type::Nominal(class_type.clone()));
+  let parameters_with_types = vec![(PStr::UNDERSCORE_THIS, this_type.dupe())]
+    .into_iter()
+    .chain(signature.type_.argument_types.iter().enumerate().map(|(i, t)| {
+      (heap.alloc_string(format!("_p{i}")), type_lowering_manager.lower_source_type(heap, t))
+    }))
+    .collect_vec();
+  let return_type = type_lowering_manager.lower_source_type(heap, &signature.type_.return_type);
+  let fn_type = hir::Type::new_fn_unwrapped(
+    parameters_with_types.iter().map(|(_, t)| t.dupe()).collect_vec(),
+    return_type.dupe(),
+  );
+  let type_arguments = vec![this_type]
+    .into_iter()
+    .chain(type_lowering_manager.lower_source_types(heap, &interface_type.type_arguments))
+    .chain(method_tparams.iter().map(|it| hir::Type::new_generic_type(*it)))
+    .collect_vec();
+  hir::Function {
+    name: hir::FunctionName {
+      type_name: hir::TypeName {
+        module_reference: Some(class_type.module_reference),
+        type_name: class_type.id,
+      },
+      fn_name: method_name,
+    },
+    parameters: parameters_with_types.iter().map(|(n, _)| *n).collect_vec(),
+    type_parameters: tparams.into_iter().collect(),
+    type_: fn_type.clone(),
+    body: vec![hir::Statement::Call {
+      callee: hir::Callee::FunctionName(hir::FunctionNameExpression {
+        name: hir::FunctionName {
+          type_name: hir::TypeName {
+            module_reference: Some(interface_type.module_reference),
+            type_name: interface_type.id,
+          },
+          fn_name: method_name,
+        },
+        type_: fn_type,
+        type_arguments,
+      }),
+      arguments: parameters_with_types
+        .into_iter()
+        .map(|(n, t)| hir::Expression::var_name(n, t))
+        .collect_vec(),
+      return_type: return_type.dupe(),
+      return_collector: Some(PStr::UNDERSCORE_TMP),
+    }],
+    return_value: hir::Expression::var_name(PStr::UNDERSCORE_TMP, return_type),
+  }
+}
+
 fn lower_tparams(type_parameters: Option<&source::annotation::TypeParameters>) -> Vec<PStr> {
   type_parameters.iter().flat_map(|it| &it.parameters).map(|it| it.name.name).collect_vec()
 }
@@ -1252,15 +2485,58 @@ fn lower_tparams(type_parameters: Option<&source::annotation::TypeParameters>) -
 fn compile_sources_with_generics_preserved(
   heap: &mut Heap,
   sources: &HashMap<ModuleReference, source::Module<Arc<type_::Type>>>,
+  strip_asserts: bool,
 ) -> hir::Sources {
   let mut type_lowering_manager = TypeLoweringManager {
     generic_types: OrderSet::new(),
     type_synthesizer: TypeSynthesizer::new(),
+    global_cx: samlang_checker::build_global_signature(
+      sources,
+      type_::create_builtin_module_signature(),
+      heap,
+    ),
   };
   let mut compiled_type_defs = Vec::new();
+  let mut compiled_interface_defs = Vec::new();
   let mut main_function_names = Vec::new();
   for (mod_ref, source_module) in sources.iter() {
     for toplevel in &source_module.toplevels {
+      if let source::Toplevel::Interface(i) = &toplevel {
+        let type_parameters = lower_tparams(i.type_parameters.as_ref());
+        let interface_type = type_::NominalType {
+          reason: Reason::dummy(),
+          is_class_statics: false,
+          module_reference: *mod_ref,
+          id: i.name.name,
+          type_arguments: type_parameters
+            .iter()
+            .map(|it| Arc::new(type_::Type::Generic(Reason::dummy(), *it)))
+            .collect(),
+        };
+        // Interfaces with generic methods cannot be used as values, so they need no vtables.
+        if let Some(methods) = samlang_checker::resolve_interface_object_methods(
+          &type_lowering_manager.global_cx,
+          &interface_type,
+        ) {
+          type_lowering_manager.generic_types = type_parameters.iter().copied().collect();
+          let methods = methods
+            .into_iter()
+            .map(|(name, signature)| {
+              let (_, method_type) = type_lowering_manager.lower_source_function_type_for_toplevel(
+                heap,
+                &signature.type_.argument_types,
+                &signature.type_.return_type,
+              );
+              (name, method_type)
+            })
+            .collect();
+          compiled_interface_defs.push(hir::InterfaceTypeDefinition {
+            name: hir::TypeName { module_reference: Some(*mod_ref), type_name: i.name.name },
+            type_parameters,
+            methods,
+          });
+        }
+      }
       if let source::Toplevel::Class(c) = &toplevel {
         type_lowering_manager.generic_types =
           c.type_parameters.iter().flat_map(|it| &it.parameters).map(|it| it.name.name).collect();
@@ -1294,8 +2570,106 @@ fn compile_sources_with_generics_preserved(
   let mut string_manager = StringManager::new();
   let mut next_synthetic_fn_id_manager = NextSyntheticFnIdManager { id: 0 };
   let mut compiled_functions = Vec::new();
+  let mut const_types = HashMap::new();
   for (module_reference, source_module) in sources.iter() {
     for toplevel in &source_module.toplevels {
+      if let source::Toplevel::Const(c) = &toplevel {
+        type_lowering_manager.generic_types = OrderSet::new();
+        let type_ = type_lowering_manager
+          .lower_source_type(heap, &type_::Type::from_annotation(&c.annotation));
+        let mut manager = ExpressionLoweringManager::new(
+          module_reference,
+          Vec::new(),
+          &type_def_mappings,
+          heap,
+          &mut type_lowering_manager,
+          &mut string_manager,
+          strip_asserts,
+          &mut next_synthetic_fn_id_manager,
+        );
+        manager.return_type = Some(type_.dupe());
+        let LoweringResultWithSyntheticFunctions {
+          statements,
+          expression,
+          synthetic_functions: mut compiled_functions_to_add,
+        } = lower_source_expression(manager, &c.value);
+        compiled_functions_to_add.push(hir::Function {
+          name: const_initializer_name(*module_reference, c.name.name),
+          parameters: Vec::new(),
+          type_parameters: Vec::new(),
+          type_: hir::Type::new_fn_unwrapped(Vec::new(), type_.dupe()),
+          body: statements,
+          return_value: expression,
+        });
+        compiled_functions.append(&mut compiled_functions_to_add);
+        const_types.insert((*module_reference, c.name.name), type_);
+      }
+      if let source::Toplevel::Interface(i) = &toplevel {
+        let interface_tparams = lower_tparams(i.type_parameters.as_ref());
+        for member in &i.members.members {
+          let Some(default_body) = &member.default_body else {
+            continue;
+          };
+          // The implementing class is the leading type parameter of a default body.
+          let tparams: OrderSet<_> = vec![PStr::UNDERSCORE_SELF]
+            .into_iter()
+            .chain(interface_tparams.iter().copied())
+            .chain(lower_tparams(member.decl.type_parameters.as_ref()))
+            .collect();
+          type_lowering_manager.generic_types = tparams.clone();
+          let main_function_parameter_with_types =
+            vec![(PStr::UNDERSCORE_THIS, hir::Type::new_generic_type(PStr::UNDERSCORE_SELF))]
+              .into_iter()
+              .chain(member.decl.parameters.parameters.iter().map(|id| {
+                (
+                  id.name.name,
+                  type_lowering_manager
+                    .lower_source_type(heap, &type_::Type::from_annotation(&id.annotation)),
+                )
+              }))
+              .collect_vec();
+          let return_type = type_lowering_manager
+            .lower_source_type(heap, &type_::Type::from_annotation(&member.decl.return_type));
+          let mut manager = ExpressionLoweringManager::new(
+            module_reference,
+            main_function_parameter_with_types.clone(),
+            &type_def_mappings,
+            heap,
+            &mut type_lowering_manager,
+            &mut string_manager,
+            strip_asserts,
+            &mut next_synthetic_fn_id_manager,
+          );
+          manager.return_type = Some(return_type.dupe());
+          let LoweringResultWithSyntheticFunctions {
+            statements,
+            expression,
+            synthetic_functions: mut compiled_functions_to_add,
+          } = lower_source_expression(manager, default_body);
+          let main_fn_type = hir::Type::new_fn_unwrapped(
+            main_function_parameter_with_types.iter().map(|(_, t)| t.dupe()).collect_vec(),
+            return_type,
+          );
+          compiled_functions_to_add.push(hir::Function {
+            name: hir::FunctionName {
+              type_name: hir::TypeName {
+                module_reference: Some(*module_reference),
+                type_name: i.name.name,
+              },
+              fn_name: member.decl.name.name,
+            },
+            parameters: main_function_parameter_with_types
+              .into_iter()
+              .map(|(n, _)| n)
+              .collect_vec(),
+            type_parameters: tparams.into_iter().collect(),
+            type_: main_fn_type,
+            body: statements,
+            return_value: expression,
+          });
+          compiled_functions.append(&mut compiled_functions_to_add);
+        }
+      }
       if let source::Toplevel::Class(c) = &toplevel {
         compiled_functions.append(&mut lower_constructors(
           heap,
@@ -1303,6 +2677,33 @@ fn compile_sources_with_generics_preserved(
           c.name.name,
           &type_def_mappings,
         ));
+        let class_tparams = lower_tparams(c.type_parameters.as_ref());
+        let class_type = type_::NominalType {
+          reason: Reason::dummy(),
+          is_class_statics: false,
+          module_reference: *module_reference,
+          id: c.name.name,
+          type_arguments: class_tparams
+            .iter()
+            .map(|it| Arc::new(type_::Type::Generic(Reason::dummy(), *it)))
+            .collect(),
+        };
+        for (method_name, interface_type, signature) in
+          samlang_checker::resolve_inherited_default_methods(
+            &type_lowering_manager.global_cx,
+            &class_type,
+          )
+        {
+          compiled_functions.push(lower_inherited_default_method(
+            heap,
+            &mut type_lowering_manager,
+            &class_type,
+            &class_tparams,
+            method_name,
+            &interface_type,
+            &signature,
+          ));
+        }
         for member in &c.members.members {
           let function_name = hir::FunctionName {
             type_name: hir::TypeName {
@@ -1341,15 +2742,19 @@ fn compile_sources_with_generics_preserved(
               )
             }))
             .collect_vec();
-            let manager = ExpressionLoweringManager::new(
+            let return_type = type_lowering_manager
+              .lower_source_type(heap, &type_::Type::from_annotation(&member.decl.return_type));
+            let mut manager = ExpressionLoweringManager::new(
               module_reference,
               main_function_parameter_with_types.clone(),
               &type_def_mappings,
               heap,
               &mut type_lowering_manager,
               &mut string_manager,
+              strip_asserts,
               &mut next_synthetic_fn_id_manager,
             );
+            manager.return_type = Some(return_type.dupe());
             let LoweringResultWithSyntheticFunctions {
               statements,
               expression,
@@ -1357,8 +2762,7 @@ fn compile_sources_with_generics_preserved(
             } = lower_source_expression(manager, &member.body);
             let main_fn_type = hir::Type::new_fn_unwrapped(
               main_function_parameter_with_types.iter().map(|(_, t)| t.dupe()).collect_vec(),
-              type_lowering_manager
-                .lower_source_type(heap, &type_::Type::from_annotation(&member.decl.return_type)),
+              return_type,
             );
             compiled_functions_to_add.push(hir::Function {
               name: function_name,
@@ -1386,15 +2790,19 @@ fn compile_sources_with_generics_preserved(
                 )
               }))
               .collect_vec();
-            let manager = ExpressionLoweringManager::new(
+            let return_type = type_lowering_manager
+              .lower_source_type(heap, &type_::Type::from_annotation(&member.decl.return_type));
+            let mut manager = ExpressionLoweringManager::new(
               module_reference,
               main_function_parameter_with_types.clone(),
               &type_def_mappings,
               heap,
               &mut type_lowering_manager,
               &mut string_manager,
+              strip_asserts,
               &mut next_synthetic_fn_id_manager,
             );
+            manager.return_type = Some(return_type.dupe());
             let LoweringResultWithSyntheticFunctions {
               statements,
               expression,
@@ -1402,8 +2810,7 @@ fn compile_sources_with_generics_preserved(
             } = lower_source_expression(manager, &member.body);
             let main_fn_type = hir::Type::new_fn_unwrapped(
               main_function_parameter_with_types.iter().map(|(_, t)| t.dupe()).collect_vec(),
-              type_lowering_manager
-                .lower_source_type(heap, &type_::Type::from_annotation(&member.decl.return_type)),
+              return_type,
             );
             let original_f = hir::Function {
               name: function_name,
@@ -1438,7 +2845,9 @@ fn compile_sources_with_generics_preserved(
 
   hir::Sources {
     global_variables: string_manager.all_global_variables(),
+    global_constants: sort_global_constants(&type_lowering_manager.global_cx, const_types),
     closure_types,
+    interface_types: compiled_interface_defs,
     type_definitions: compiled_type_defs,
     main_function_names,
     functions: compiled_functions,
@@ -1449,7 +2858,9 @@ fn optimize_by_tail_rec_rewrite(heap: &mut Heap, sources: mir::Sources) -> mir::
   let mir::Sources {
     symbol_table,
     global_variables,
+    global_constants,
     closure_types,
+    interface_types,
     type_definitions,
     main_function_names,
     functions,
@@ -1457,7 +2868,9 @@ fn optimize_by_tail_rec_rewrite(heap: &mut Heap, sources: mir::Sources) -> mir::
   mir::Sources {
     symbol_table,
     global_variables,
+    global_constants,
     closure_types,
+    interface_types,
     type_definitions,
     main_function_names,
     functions: functions
@@ -1470,8 +2883,9 @@ fn optimize_by_tail_rec_rewrite(heap: &mut Heap, sources: mir::Sources) -> mir::
 pub fn compile_sources_to_mir(
   heap: &mut Heap,
   sources: &HashMap<ModuleReference, source::Module<Arc<type_::Type>>>,
+  strip_asserts: bool,
 ) -> mir::Sources {
-  let sources = compile_sources_with_generics_preserved(heap, sources);
+  let sources = compile_sources_with_generics_preserved(heap, sources, strip_asserts);
   let mut sources = mir_generics_specialization::perform_generics_specialization(heap, sources);
   sources = mir_type_deduplication::deduplicate(sources);
   sources = mir_constant_param_elimination::rewrite_sources(sources);
@@ -1489,7 +2903,7 @@ mod tests {
   use itertools::Itertools;
   use ordermap::OrderSet;
   use pretty_assertions::assert_eq;
-  use samlang_ast::{Location, Reason, hir, source};
+  use samlang_ast::{Location, Position, Reason, hir, source};
   use samlang_checker::type_;
   use samlang_heap::{Heap, ModuleReference, PStr};
   use std::{collections::HashMap, sync::Arc};
@@ -1498,10 +2912,45 @@ mod tests {
     source_expr: &source::expr::E<Arc<type_::Type>>,
     heap: &mut Heap,
     expected_str: &str,
+  ) {
+    assert_expr_correctly_lowered_customized(source_expr, heap, false, None, expected_str)
+  }
+
+  fn assert_expr_correctly_lowered_with_strip_asserts(
+    source_expr: &source::expr::E<Arc<type_::Type>>,
+    heap: &mut Heap,
+    strip_asserts: bool,
+    expected_str: &str,
+  ) {
+    assert_expr_correctly_lowered_customized(source_expr, heap, strip_asserts, None, expected_str)
+  }
+
+  fn assert_expr_correctly_lowered_with_return_type(
+    source_expr: &source::expr::E<Arc<type_::Type>>,
+    heap: &mut Heap,
+    return_type: hir::Type,
+    expected_str: &str,
+  ) {
+    assert_expr_correctly_lowered_customized(
+      source_expr,
+      heap,
+      false,
+      Some(return_type),
+      expected_str,
+    )
+  }
+
+  fn assert_expr_correctly_lowered_customized(
+    source_expr: &source::expr::E<Arc<type_::Type>>,
+    heap: &mut Heap,
+    strip_asserts: bool,
+    return_type: Option<hir::Type>,
+    expected_str: &str,
   ) {
     let mut type_lowering_manager = TypeLoweringManager {
       generic_types: OrderSet::from([heap.alloc_str_for_test("GENERIC_TYPE")]),
       type_synthesizer: TypeSynthesizer::new(),
+      global_cx: HashMap::new(),
     };
     let mut string_manager = StringManager::new();
     let mut next_synthetic_fn_id_manager = super::NextSyntheticFnIdManager { id: 0 };
@@ -1535,8 +2984,42 @@ mod tests {
           mappings: hir::TypeDefinitionMappings::Struct(vec![hir::INT_TYPE, hir::INT_TYPE]),
         },
       ),
+      (
+        hir::TypeName {
+          module_reference: Some(ModuleReference::STD_OPTION),
+          type_name: PStr::OPTION_TYPE,
+        },
+        hir::TypeDefinition {
+          name: hir::TypeName {
+            module_reference: Some(ModuleReference::STD_OPTION),
+            type_name: PStr::OPTION_TYPE,
+          },
+          type_parameters: vec![PStr::UPPER_T],
+          mappings: hir::TypeDefinitionMappings::Enum(vec![
+            (PStr::NONE, Vec::new()),
+            (PStr::SOME, vec![hir::Type::new_generic_type(PStr::UPPER_T)]),
+          ]),
+        },
+      ),
+      (
+        hir::TypeName {
+          module_reference: Some(ModuleReference::STD_RESULT),
+          type_name: PStr::RESULT_TYPE,
+        },
+        hir::TypeDefinition {
+          name: hir::TypeName {
+            module_reference: Some(ModuleReference::STD_RESULT),
+            type_name: PStr::RESULT_TYPE,
+          },
+          type_parameters: vec![PStr::UPPER_T, PStr::UPPER_E],
+          mappings: hir::TypeDefinitionMappings::Enum(vec![
+            (PStr::OK, vec![hir::Type::new_generic_type(PStr::UPPER_T)]),
+            (PStr::ERROR, vec![hir::Type::new_generic_type(PStr::UPPER_E)]),
+          ]),
+        },
+      ),
     ]);
-    let manager = ExpressionLoweringManager::new(
+    let mut manager = ExpressionLoweringManager::new(
       &mod_ref,
       vec![
         (
@@ -1577,8 +3060,10 @@ mod tests {
       heap,
       &mut type_lowering_manager,
       &mut string_manager,
+      strip_asserts,
       &mut next_synthetic_fn_id_manager,
     );
+    manager.return_type = return_type;
     let super::LoweringResultWithSyntheticFunctions { statements, expression, synthetic_functions } =
       super::lower_source_expression(manager, source_expr);
     let SynthesizedTypes { tuple_types, closure_types } =
@@ -1586,7 +3071,9 @@ mod tests {
     let global_variables = string_manager.all_global_variables();
     let synthetic_module = hir::Sources {
       global_variables,
+      global_constants: Vec::new(),
       closure_types,
+      interface_types: Vec::new(),
       type_definitions: tuple_types,
       main_function_names: Vec::new(),
       functions: synthetic_functions,
@@ -1649,15 +3136,23 @@ mod tests {
     assert_expr_correctly_lowered(
       &source::expr::E::Literal(
         source::expr::ExpressionCommon::dummy(builder.int_type()),
-        source::Literal::Int(0),
+        source::Literal::Int(0, None),
       ),
       heap,
       "return 0;",
     );
+    assert_expr_correctly_lowered(
+      &source::expr::E::Literal(
+        source::expr::ExpressionCommon::dummy(builder.float_type()),
+        source::Literal::float_literal(1.5),
+      ),
+      heap,
+      "return 1.5;",
+    );
     assert_expr_correctly_lowered(
       &source::expr::E::Literal(
         source::expr::ExpressionCommon::dummy(builder.string_type()),
-        source::Literal::String(heap.alloc_str_for_test("foo")),
+        source::Literal::String(heap.alloc_str_for_test("foo"), None),
       ),
       heap,
       "const GLOBAL_STRING_0 = 'foo';\n\n\nreturn \"foo\";",
@@ -1680,7 +3175,9 @@ mod tests {
     let heap = &mut Heap::new();
     assert_expr_correctly_lowered(
       &source::expr::E::Tuple(
-        source::expr::ExpressionCommon::dummy(Arc::new(dummy_source_id_type(heap))),
+        source::expr::ExpressionCommon::dummy(
+          builder.tuple_type(vec![builder.int_type(), builder.int_type()]),
+        ),
         source::expr::ParenthesizedExpressionList {
           loc: Location::dummy(),
           start_associated_comments: source::NO_COMMENT_REFERENCE,
@@ -1688,17 +3185,19 @@ mod tests {
           expressions: vec![
             source::expr::E::Literal(
               source::expr::ExpressionCommon::dummy(builder.int_type()),
-              source::Literal::Int(0),
+              source::Literal::Int(0, None),
             ),
             source::expr::E::Literal(
               source::expr::ExpressionCommon::dummy(builder.int_type()),
-              source::Literal::Int(0),
+              source::Literal::Int(0, None),
             ),
           ],
         },
       ),
       heap,
-      "let _t1: DUMMY_Dummy = DUMMY_Dummy$init<int, int>(0, 0, 0);\nreturn (_t1: DUMMY_Dummy);",
+      r#"object type _$SyntheticIDType0 = [int, int]
+let _t2: _$SyntheticIDType0 = [0, 0];
+return (_t2: _$SyntheticIDType0);"#,
     );
   }
 
@@ -1721,6 +3220,29 @@ mod tests {
       "let _t1: int = (_this: DUMMY_Dummy)[0];\nreturn (_t1: int);",
     );
 
+    // StructUpdate lowering works.
+    let heap = &mut Heap::new();
+    assert_expr_correctly_lowered(
+      &source::expr::E::StructUpdate(source::expr::StructUpdate {
+        common: source::expr::ExpressionCommon::dummy(Arc::new(dummy_source_id_type(heap))),
+        base: Box::new(dummy_source_this(heap)),
+        fields: vec![source::expr::FieldUpdate {
+          loc: Location::dummy(),
+          field_name: source::Id::from(heap.alloc_str_for_test("bar")),
+          field_order: 1,
+          value: source::expr::E::Literal(
+            source::expr::ExpressionCommon::dummy(builder.int_type()),
+            source::Literal::Int(3, None),
+          ),
+        }],
+        ending_associated_comments: source::NO_COMMENT_REFERENCE,
+      }),
+      heap,
+      r#"let _t1: int = (_this: DUMMY_Dummy)[0];
+let _t2: DUMMY_Dummy = [(_t1: int), 3];
+return (_t2: DUMMY_Dummy);"#,
+    );
+
     // MethodAccess lowering works.
     let heap = &mut Heap::new();
     assert_expr_correctly_lowered(
@@ -1765,6 +3287,7 @@ return (_t2: _$SyntheticIDType0);"#,
           ending_associated_comments: source::NO_COMMENT_REFERENCE,
           expressions: vec![dummy_source_this(heap), dummy_source_this(heap)],
         },
+        binding: Default::default(),
       }),
       heap,
       r#"let _t1: int = DUMMY_Dummy$fooBar((_this: DUMMY_Dummy), (_this: DUMMY_Dummy), (_this: DUMMY_Dummy));
@@ -1788,6 +3311,7 @@ return (_t1: int);"#,
             source::Literal::Bool(true),
           )],
         },
+        binding: Default::default(),
       }),
       heap,
       r#"let _t1: int = (closure: DUMMY_Closure)(1);
@@ -1811,24 +3335,112 @@ return (_t1: int);"#,
             source::Literal::Bool(true),
           )],
         },
+        binding: Default::default(),
       }),
       heap,
       r#"(closure_unit_return: DUMMY_Closure)(1);
 return 0;"#,
     );
-  }
-
-  #[test]
-  fn op_lowering_tests() {
-    let builder = type_::test_type_builder::create();
-
-    // Unary lowering works.
+    // Function call 4/n: pipe into a closure
     let heap = &mut Heap::new();
     assert_expr_correctly_lowered(
-      &source::expr::E::Unary(source::expr::Unary {
-        common: source::expr::ExpressionCommon::dummy(builder.unit_type()),
-        operator: source::expr::UnaryOperator::NOT,
-        argument: Box::new(dummy_source_this(heap)),
+      &source::expr::E::Binary(source::expr::Binary {
+        common: source::expr::ExpressionCommon::dummy(builder.int_type()),
+        operator_preceding_comments: source::NO_COMMENT_REFERENCE,
+        operator: source::expr::BinaryOperator::PIPE,
+        e1: Box::new(source::expr::E::Literal(
+          source::expr::ExpressionCommon::dummy(builder.bool_type()),
+          source::Literal::Bool(true),
+        )),
+        e2: Box::new(id_expr(
+          heap.alloc_str_for_test("closure"),
+          builder.fun_type(vec![builder.bool_type()], builder.int_type()),
+        )),
+      }),
+      heap,
+      r#"let _t1: int = (closure: DUMMY_Closure)(1);
+return (_t1: int);"#,
+    );
+    // Function call 5/n: pipe inserts the piped value as the first argument
+    let heap = &mut Heap::new();
+    assert_expr_correctly_lowered(
+      &source::expr::E::Binary(source::expr::Binary {
+        common: source::expr::ExpressionCommon::dummy(builder.int_type()),
+        operator_preceding_comments: source::NO_COMMENT_REFERENCE,
+        operator: source::expr::BinaryOperator::PIPE,
+        e1: Box::new(source::expr::E::Literal(
+          source::expr::ExpressionCommon::dummy(builder.bool_type()),
+          source::Literal::Bool(true),
+        )),
+        e2: Box::new(source::expr::E::Call(source::expr::Call {
+          common: source::expr::ExpressionCommon::dummy(builder.int_type()),
+          callee: Box::new(id_expr(
+            heap.alloc_str_for_test("closure"),
+            builder.fun_type(vec![builder.bool_type(), builder.bool_type()], builder.int_type()),
+          )),
+          arguments: source::expr::ParenthesizedExpressionList {
+            loc: Location::dummy(),
+            start_associated_comments: source::NO_COMMENT_REFERENCE,
+            ending_associated_comments: source::NO_COMMENT_REFERENCE,
+            expressions: vec![source::expr::E::Literal(
+              source::expr::ExpressionCommon::dummy(builder.bool_type()),
+              source::Literal::Bool(false),
+            )],
+          },
+          binding: Default::default(),
+        })),
+      }),
+      heap,
+      r#"let _t1: int = (closure: DUMMY_Closure)(1, 0);
+return (_t1: int);"#,
+    );
+    // Function call 6/n: named and default arguments are passed in parameter order
+    let heap = &mut Heap::new();
+    let int_literal = |i| {
+      source::expr::E::Literal(
+        source::expr::ExpressionCommon::dummy(builder.int_type()),
+        source::Literal::Int(i, None),
+      )
+    };
+    assert_expr_correctly_lowered(
+      &source::expr::E::Call(source::expr::Call {
+        common: source::expr::ExpressionCommon::dummy(builder.int_type()),
+        callee: Box::new(id_expr(
+          heap.alloc_str_for_test("closure"),
+          builder.fun_type(
+            vec![builder.int_type(), builder.int_type(), builder.int_type()],
+            builder.int_type(),
+          ),
+        )),
+        arguments: source::expr::ParenthesizedExpressionList {
+          loc: Location::dummy(),
+          start_associated_comments: source::NO_COMMENT_REFERENCE,
+          ending_associated_comments: source::NO_COMMENT_REFERENCE,
+          expressions: vec![int_literal(1), int_literal(2)],
+        },
+        binding: source::expr::ArgumentBinding {
+          names: Vec::new(),
+          parameter_indices: vec![2, 0],
+          default_arguments: vec![(1, int_literal(3))],
+        },
+      }),
+      heap,
+      r#"let _t1: int = (closure: DUMMY_Closure)(2, 3, 1);
+return (_t1: int);"#,
+    );
+  }
+
+  #[test]
+  fn op_lowering_tests() {
+    let builder = type_::test_type_builder::create();
+
+    // Unary lowering works.
+    let heap = &mut Heap::new();
+    assert_expr_correctly_lowered(
+      &source::expr::E::Unary(source::expr::Unary {
+        common: source::expr::ExpressionCommon::dummy(builder.unit_type()),
+        operator: source::expr::UnaryOperator::NOT,
+        argument: Box::new(dummy_source_this(heap)),
       }),
       heap,
       "let _t1 = !(_this: DUMMY_Dummy);\nreturn (_t1: int);",
@@ -1843,6 +3455,65 @@ return 0;"#,
       heap,
       "let _t1 = 0 - (_this: DUMMY_Dummy);\nreturn (_t1: int);",
     );
+    let heap = &mut Heap::new();
+    assert_expr_correctly_lowered(
+      &source::expr::E::Unary(source::expr::Unary {
+        common: source::expr::ExpressionCommon::dummy(builder.int_type()),
+        operator: source::expr::UnaryOperator::BNOT,
+        argument: Box::new(dummy_source_this(heap)),
+      }),
+      heap,
+      "let _t1 = (_this: DUMMY_Dummy) ^ -1;\nreturn (_t1: int);",
+    );
+    let heap = &mut Heap::new();
+    assert_expr_correctly_lowered(
+      &source::expr::E::Unary(source::expr::Unary {
+        common: source::expr::ExpressionCommon::dummy(builder.float_type()),
+        operator: source::expr::UnaryOperator::NEG,
+        argument: Box::new(source::expr::E::Literal(
+          source::expr::ExpressionCommon::dummy(builder.float_type()),
+          source::Literal::float_literal(2.0),
+        )),
+      }),
+      heap,
+      "let _t1 = 2.0 * -1.0;\nreturn (_t1: float);",
+    );
+    let heap = &mut Heap::new();
+    assert_expr_correctly_lowered(
+      &source::expr::E::Binary(source::expr::Binary {
+        common: source::expr::ExpressionCommon::dummy(builder.float_type()),
+        operator_preceding_comments: source::NO_COMMENT_REFERENCE,
+        operator: source::expr::BinaryOperator::MINUS,
+        e1: Box::new(source::expr::E::Literal(
+          source::expr::ExpressionCommon::dummy(builder.float_type()),
+          source::Literal::float_literal(2.0),
+        )),
+        e2: Box::new(source::expr::E::Literal(
+          source::expr::ExpressionCommon::dummy(builder.float_type()),
+          source::Literal::float_literal(0.5),
+        )),
+      }),
+      heap,
+      "let _t1 = 2.0 - 0.5;\nreturn (_t1: float);",
+    );
+    let heap = &mut Heap::new();
+    assert_expr_correctly_lowered(
+      &source::expr::E::Binary(source::expr::Binary {
+        common: source::expr::ExpressionCommon::dummy(builder.bool_type()),
+        operator_preceding_comments: source::NO_COMMENT_REFERENCE,
+        operator: source::expr::BinaryOperator::LT,
+        e1: Box::new(source::expr::E::Literal(
+          source::expr::ExpressionCommon::dummy(builder.float_type()),
+          source::Literal::float_literal(2.0),
+        )),
+        e2: Box::new(source::expr::E::Literal(
+          source::expr::ExpressionCommon::dummy(builder.float_type()),
+          source::Literal::float_literal(0.5),
+        )),
+      }),
+      heap,
+      "let _t1 = 2.0 < 0.5;\nreturn (_t1: int);",
+    );
 
     // Binary Lowering: normal
     let heap = &mut Heap::new();
@@ -1906,6 +3577,78 @@ return 0;"#,
       "let _t1 = (_this: DUMMY_Dummy) % (_this: DUMMY_Dummy);\nreturn (_t1: int);",
     );
     let heap = &mut Heap::new();
+    assert_expr_correctly_lowered(
+      &source::expr::E::Binary(source::expr::Binary {
+        common: source::expr::ExpressionCommon::dummy(builder.int_type()),
+        operator_preceding_comments: source::NO_COMMENT_REFERENCE,
+        operator: source::expr::BinaryOperator::SHL,
+        e1: Box::new(dummy_source_this(heap)),
+        e2: Box::new(dummy_source_this(heap)),
+      }),
+      heap,
+      "let _t1 = (_this: DUMMY_Dummy) << (_this: DUMMY_Dummy);\nreturn (_t1: int);",
+    );
+    let heap = &mut Heap::new();
+    assert_expr_correctly_lowered(
+      &source::expr::E::Binary(source::expr::Binary {
+        common: source::expr::ExpressionCommon::dummy(builder.int_type()),
+        operator_preceding_comments: source::NO_COMMENT_REFERENCE,
+        operator: source::expr::BinaryOperator::ASHR,
+        e1: Box::new(dummy_source_this(heap)),
+        e2: Box::new(dummy_source_this(heap)),
+      }),
+      heap,
+      "let _t1 = (_this: DUMMY_Dummy) >> (_this: DUMMY_Dummy);\nreturn (_t1: int);",
+    );
+    let heap = &mut Heap::new();
+    assert_expr_correctly_lowered(
+      &source::expr::E::Binary(source::expr::Binary {
+        common: source::expr::ExpressionCommon::dummy(builder.int_type()),
+        operator_preceding_comments: source::NO_COMMENT_REFERENCE,
+        operator: source::expr::BinaryOperator::SHR,
+        e1: Box::new(dummy_source_this(heap)),
+        e2: Box::new(dummy_source_this(heap)),
+      }),
+      heap,
+      "let _t1 = (_this: DUMMY_Dummy) >>> (_this: DUMMY_Dummy);\nreturn (_t1: int);",
+    );
+    let heap = &mut Heap::new();
+    assert_expr_correctly_lowered(
+      &source::expr::E::Binary(source::expr::Binary {
+        common: source::expr::ExpressionCommon::dummy(builder.int_type()),
+        operator_preceding_comments: source::NO_COMMENT_REFERENCE,
+        operator: source::expr::BinaryOperator::LAND,
+        e1: Box::new(dummy_source_this(heap)),
+        e2: Box::new(dummy_source_this(heap)),
+      }),
+      heap,
+      "let _t1 = (_this: DUMMY_Dummy) & (_this: DUMMY_Dummy);\nreturn (_t1: int);",
+    );
+    let heap = &mut Heap::new();
+    assert_expr_correctly_lowered(
+      &source::expr::E::Binary(source::expr::Binary {
+        common: source::expr::ExpressionCommon::dummy(builder.int_type()),
+        operator_preceding_comments: source::NO_COMMENT_REFERENCE,
+        operator: source::expr::BinaryOperator::XOR,
+        e1: Box::new(dummy_source_this(heap)),
+        e2: Box::new(dummy_source_this(heap)),
+      }),
+      heap,
+      "let _t1 = (_this: DUMMY_Dummy) ^ (_this: DUMMY_Dummy);\nreturn (_t1: int);",
+    );
+    let heap = &mut Heap::new();
+    assert_expr_correctly_lowered(
+      &source::expr::E::Binary(source::expr::Binary {
+        common: source::expr::ExpressionCommon::dummy(builder.int_type()),
+        operator_preceding_comments: source::NO_COMMENT_REFERENCE,
+        operator: source::expr::BinaryOperator::LOR,
+        e1: Box::new(dummy_source_this(heap)),
+        e2: Box::new(dummy_source_this(heap)),
+      }),
+      heap,
+      "let _t1 = (_this: DUMMY_Dummy) | (_this: DUMMY_Dummy);\nreturn (_t1: int);",
+    );
+    let heap = &mut Heap::new();
     assert_expr_correctly_lowered(
       &source::expr::E::Binary(source::expr::Binary {
         common: source::expr::ExpressionCommon::dummy(builder.bool_type()),
@@ -2039,7 +3782,7 @@ return (_t1: int);"#,
         )),
         e2: Box::new(source::expr::E::Literal(
           source::expr::ExpressionCommon::dummy(builder.int_type()),
-          source::Literal::Int(65536),
+          source::Literal::Int(65536, None),
         )),
       }),
       heap,
@@ -2057,7 +3800,7 @@ return (_t1: int);"#,
         )),
         e2: Box::new(source::expr::E::Literal(
           source::expr::ExpressionCommon::dummy(builder.int_type()),
-          source::Literal::Int(65536),
+          source::Literal::Int(65536, None),
         )),
       }),
       heap,
@@ -2103,11 +3846,11 @@ return (_t1: _Str);"#,
         operator: source::expr::BinaryOperator::CONCAT,
         e1: Box::new(source::expr::E::Literal(
           source::expr::ExpressionCommon::dummy(builder.string_type()),
-          source::Literal::String(heap.alloc_str_for_test("hello ")),
+          source::Literal::String(heap.alloc_str_for_test("hello "), None),
         )),
         e2: Box::new(source::expr::E::Literal(
           source::expr::ExpressionCommon::dummy(builder.string_type()),
-          source::Literal::String(heap.alloc_str_for_test("world")),
+          source::Literal::String(heap.alloc_str_for_test("world"), None),
         )),
       }),
       heap,
@@ -2115,6 +3858,113 @@ return (_t1: _Str);"#,
     );
   }
 
+  #[test]
+  fn template_string_lowering_tests() {
+    let builder = type_::test_type_builder::create();
+
+    let heap = &mut Heap::new();
+    let parts = [heap.alloc_str_for_test("a"), heap.alloc_str_for_test("c")];
+    let b = heap.alloc_str_for_test("b");
+    assert_expr_correctly_lowered(
+      &source::expr::E::TemplateString(source::expr::TemplateString {
+        common: source::expr::ExpressionCommon::dummy(builder.string_type()),
+        parts: vec![parts[0], PStr::EMPTY, parts[1]],
+        holes: vec![
+          source::expr::E::Literal(
+            source::expr::ExpressionCommon::dummy(builder.int_type()),
+            source::Literal::Int(1, None),
+          ),
+          source::expr::E::Literal(
+            source::expr::ExpressionCommon::dummy(builder.string_type()),
+            source::Literal::String(b, None),
+          ),
+        ],
+      }),
+      heap,
+      "const GLOBAL_STRING_0 = 'a1bc';\n\n\nreturn \"a1bc\";",
+    );
+
+    let heap = &mut Heap::new();
+    assert_expr_correctly_lowered(
+      &source::expr::E::TemplateString(source::expr::TemplateString {
+        common: source::expr::ExpressionCommon::dummy(builder.string_type()),
+        parts: vec![PStr::EMPTY, PStr::EMPTY],
+        holes: vec![source::expr::E::Literal(
+          source::expr::ExpressionCommon::dummy(builder.string_type()),
+          source::Literal::String(PStr::EMPTY, None),
+        )],
+      }),
+      heap,
+      "const GLOBAL_STRING_0 = '';\n\n\nreturn \"\";",
+    );
+
+    // "a${"b"}c${1 + 2}d"
+    let heap = &mut Heap::new();
+    let parts =
+      [heap.alloc_str_for_test("a"), heap.alloc_str_for_test("c"), heap.alloc_str_for_test("d")];
+    let b = heap.alloc_str_for_test("b");
+    assert_expr_correctly_lowered(
+      &source::expr::E::TemplateString(source::expr::TemplateString {
+        common: source::expr::ExpressionCommon::dummy(builder.string_type()),
+        parts: parts.to_vec(),
+        holes: vec![
+          source::expr::E::Literal(
+            source::expr::ExpressionCommon::dummy(builder.string_type()),
+            source::Literal::String(b, None),
+          ),
+          source::expr::E::Binary(source::expr::Binary {
+            common: source::expr::ExpressionCommon::dummy(builder.int_type()),
+            operator_preceding_comments: source::NO_COMMENT_REFERENCE,
+            operator: source::expr::BinaryOperator::PLUS,
+            e1: Box::new(source::expr::E::Literal(
+              source::expr::ExpressionCommon::dummy(builder.int_type()),
+              source::Literal::Int(1, None),
+            )),
+            e2: Box::new(source::expr::E::Literal(
+              source::expr::ExpressionCommon::dummy(builder.int_type()),
+              source::Literal::Int(2, None),
+            )),
+          }),
+        ],
+      }),
+      heap,
+      "const GLOBAL_STRING_0 = 'abc3d';\n\n\nreturn \"abc3d\";",
+    );
+
+    let heap = &mut Heap::new();
+    let parts = [heap.alloc_str_for_test("x="), heap.alloc_str_for_test(", this=")];
+    assert_expr_correctly_lowered(
+      &source::expr::E::TemplateString(source::expr::TemplateString {
+        common: source::expr::ExpressionCommon::dummy(builder.string_type()),
+        parts: vec![parts[0], parts[1], PStr::EMPTY, PStr::EMPTY],
+        holes: vec![
+          id_expr(heap.alloc_str_for_test("foo"), builder.int_type()),
+          dummy_source_this(heap),
+          source::expr::E::Binary(source::expr::Binary {
+            common: source::expr::ExpressionCommon::dummy(builder.string_type()),
+            operator_preceding_comments: source::NO_COMMENT_REFERENCE,
+            operator: source::expr::BinaryOperator::CONCAT,
+            e1: Box::new(dummy_source_this(heap)),
+            e2: Box::new(dummy_source_this(heap)),
+          }),
+        ],
+      }),
+      heap,
+      r#"const GLOBAL_STRING_0 = ', this=';
+
+const GLOBAL_STRING_1 = 'x=';
+
+let _t1: _Str = _Str$fromInt(0 as i31, (foo: int));
+let _t2: _Str = DUMMY_Dummy$toString((_this: DUMMY_Dummy));
+let _t3: _Str = _Str$concat((_this: DUMMY_Dummy), (_this: DUMMY_Dummy));
+let _t4: _Str = _Str$concat("x=", (_t1: _Str));
+let _t5: _Str = _Str$concat((_t4: _Str), ", this=");
+let _t6: _Str = _Str$concat((_t5: _Str), (_t2: _Str));
+let _t7: _Str = _Str$concat((_t6: _Str), (_t3: _Str));
+return (_t7: _Str);"#,
+    );
+  }
+
   #[test]
   fn lambda_lowering_tests() {
     let annot_builder = source::test_builder::create();
@@ -2350,6 +4200,7 @@ return (_t1: DUMMY_Dummy);"#,
               }),
               type_: Arc::new(dummy_source_id_type(heap)),
             }),
+            guard: None,
             body: Box::new(source::expr::E::LocalId(
               source::expr::ExpressionCommon::dummy(Arc::new(dummy_source_id_type(heap))),
               source::Id::from(heap.alloc_str_for_test("bar")),
@@ -2376,6 +4227,7 @@ return (_t1: DUMMY_Dummy);"#,
               }),
               type_: Arc::new(dummy_source_id_type(heap)),
             }),
+            guard: None,
             body: Box::new(dummy_source_this(heap)),
             ending_associated_comments: source::NO_COMMENT_REFERENCE,
           },
@@ -2384,32 +4236,32 @@ return (_t1: DUMMY_Dummy);"#,
       heap,
       r#"const GLOBAL_STRING_0 = '';
 
-let _t6: int;
-let [_t7: int] if tagof((_this: DUMMY_Dummy))==0 {
-  _t6 = (_t7: int);
-  _t8 = 1;
+let _t2: int;
+let [_t3: int] if tagof((_this: DUMMY_Dummy))==0 {
+  _t2 = (_t3: int);
+  _t4 = 1;
 } else {
-  _t8 = 0;
+  _t4 = 0;
 }
-let _t5: DUMMY_Dummy;
-if (_t8: int) {
-  _t5 = (_t6: int);
+let _t1: DUMMY_Dummy;
+if (_t4: int) {
+  _t1 = (_t2: int);
 } else {
-  let [_t3: int] if tagof((_this: DUMMY_Dummy))==1 {
-    _t4 = 1;
+  let [_t6: int] if tagof((_this: DUMMY_Dummy))==1 {
+    _t7 = 1;
   } else {
-    _t4 = 0;
+    _t7 = 0;
   }
-  let _t2: DUMMY_Dummy;
-  if (_t4: int) {
-    _t2 = (_this: DUMMY_Dummy);
+  let _t5: DUMMY_Dummy;
+  if (_t7: int) {
+    _t5 = (_this: DUMMY_Dummy);
   } else {
-    let _t1: DUMMY_Dummy = _Process$panic<DUMMY_Dummy>(0, "");
-    _t2 = (_t1: DUMMY_Dummy);
+    let _t8: DUMMY_Dummy = _Process$panic<DUMMY_Dummy>(0, "");
+    _t5 = (_t8: DUMMY_Dummy);
   }
-  _t5 = (_t2: DUMMY_Dummy);
+  _t1 = (_t5: DUMMY_Dummy);
 }
-return (_t5: DUMMY_Dummy);"#,
+return (_t1: DUMMY_Dummy);"#,
     );
 
     let heap = &mut Heap::new();
@@ -2438,6 +4290,7 @@ return (_t5: DUMMY_Dummy);"#,
               }),
               type_: Arc::new(dummy_source_id_type(heap)),
             }),
+            guard: None,
             body: Box::new(dummy_source_this(heap)),
             ending_associated_comments: source::NO_COMMENT_REFERENCE,
           },
@@ -2461,6 +4314,7 @@ return (_t5: DUMMY_Dummy);"#,
               }),
               type_: Arc::new(dummy_source_id_type(heap)),
             }),
+            guard: None,
             body: Box::new(id_expr(
               heap.alloc_str_for_test("bar"),
               Arc::new(dummy_source_id_type(heap)),
@@ -2487,6 +4341,7 @@ return (_t5: DUMMY_Dummy);"#,
               }),
               type_: Arc::new(dummy_source_id_type(heap)),
             }),
+            guard: None,
             body: Box::new(dummy_source_this(heap)),
             ending_associated_comments: source::NO_COMMENT_REFERENCE,
           },
@@ -2495,43 +4350,43 @@ return (_t5: DUMMY_Dummy);"#,
       heap,
       r#"const GLOBAL_STRING_0 = '';
 
-let [_t10: int] if tagof((_this: DUMMY_Dummy))==0 {
-  _t11 = 1;
+let [_t2: int] if tagof((_this: DUMMY_Dummy))==0 {
+  _t3 = 1;
 } else {
-  _t11 = 0;
+  _t3 = 0;
 }
-let _t9: DUMMY_Dummy;
-if (_t11: int) {
-  _t9 = (_this: DUMMY_Dummy);
+let _t1: DUMMY_Dummy;
+if (_t3: int) {
+  _t1 = (_this: DUMMY_Dummy);
 } else {
-  let _t6: int;
-  let [_t7: int] if tagof((_this: DUMMY_Dummy))==1 {
-    _t6 = (_t7: int);
-    _t8 = 1;
+  let _t5: int;
+  let [_t6: int] if tagof((_this: DUMMY_Dummy))==1 {
+    _t5 = (_t6: int);
+    _t7 = 1;
   } else {
-    _t8 = 0;
+    _t7 = 0;
   }
-  let _t5: DUMMY_Dummy;
-  if (_t8: int) {
-    _t5 = (_t6: int);
+  let _t4: DUMMY_Dummy;
+  if (_t7: int) {
+    _t4 = (_t5: int);
   } else {
-    let [_t3: int] if tagof((_this: DUMMY_Dummy))==2 {
-      _t4 = 1;
+    let [_t9: int] if tagof((_this: DUMMY_Dummy))==2 {
+      _t10 = 1;
     } else {
-      _t4 = 0;
+      _t10 = 0;
     }
-    let _t2: DUMMY_Dummy;
-    if (_t4: int) {
-      _t2 = (_this: DUMMY_Dummy);
+    let _t8: DUMMY_Dummy;
+    if (_t10: int) {
+      _t8 = (_this: DUMMY_Dummy);
     } else {
-      let _t1: DUMMY_Dummy = _Process$panic<DUMMY_Dummy>(0, "");
-      _t2 = (_t1: DUMMY_Dummy);
+      let _t11: DUMMY_Dummy = _Process$panic<DUMMY_Dummy>(0, "");
+      _t8 = (_t11: DUMMY_Dummy);
     }
-    _t5 = (_t2: DUMMY_Dummy);
+    _t4 = (_t8: DUMMY_Dummy);
   }
-  _t9 = (_t5: DUMMY_Dummy);
+  _t1 = (_t4: DUMMY_Dummy);
 }
-return (_t9: DUMMY_Dummy);"#,
+return (_t1: DUMMY_Dummy);"#,
     );
   }
 
@@ -3245,6 +5100,7 @@ return (_t10: int);"#,
                 }),
               ],
             },
+            guard: None,
             body: Box::new(source::expr::E::LocalId(
               source::expr::ExpressionCommon::dummy(dummy_type.clone()),
               source::Id::from(heap.alloc_str_for_test("x")),
@@ -3260,6 +5116,7 @@ return (_t10: int);"#,
               data_variables: None,
               type_: dummy_type.clone(),
             }),
+            guard: None,
             body: Box::new(dummy_source_this(heap)),
             ending_associated_comments: source::NO_COMMENT_REFERENCE,
           },
@@ -3268,326 +5125,1033 @@ return (_t10: int);"#,
       heap,
       r#"const GLOBAL_STRING_0 = '';
 
-let _t5: int;
-let [_t8: int] if tagof((_this: DUMMY_Dummy))==0 {
-  _t5 = (_t8: int);
-  _t9 = 1;
+let _t2: int;
+let [_t5: int] if tagof((_this: DUMMY_Dummy))==0 {
+  _t2 = (_t5: int);
+  _t6 = 1;
 } else {
-  _t9 = 0;
+  _t6 = 0;
 }
-let _t10: int;
-if (_t9: int) {
-  _t10 = 1;
-} else {
-  let [_t6: int] if tagof((_this: DUMMY_Dummy))==1 {
-    _t5 = (_t6: int);
-    _t7 = 1;
+let _t7: int;
+if (_t6: int) {
+  _t7 = 1;
+} else {
+  let [_t3: int] if tagof((_this: DUMMY_Dummy))==1 {
+    _t2 = (_t3: int);
+    _t4 = 1;
   } else {
-    _t7 = 0;
+    _t4 = 0;
   }
-  _t10 = (_t7: int);
+  _t7 = (_t4: int);
 }
-let _t4: DUMMY_Dummy;
-if (_t10: int) {
-  _t4 = (_t5: int);
+let _t1: DUMMY_Dummy;
+if (_t7: int) {
+  _t1 = (_t2: int);
 } else {
   let [] if tagof((_this: DUMMY_Dummy))==2 {
+    _t9 = 1;
+  } else {
+    _t9 = 0;
+  }
+  let _t8: DUMMY_Dummy;
+  if (_t9: int) {
+    _t8 = (_this: DUMMY_Dummy);
+  } else {
+    let _t10: DUMMY_Dummy = _Process$panic<DUMMY_Dummy>(0, "");
+    _t8 = (_t10: DUMMY_Dummy);
+  }
+  _t1 = (_t8: DUMMY_Dummy);
+}
+return (_t1: DUMMY_Dummy);"#,
+    );
+  }
+
+  #[test]
+  fn literal_pattern_lowering_test() {
+    let builder = type_::test_type_builder::create();
+    let heap = &mut Heap::new();
+
+    let literal_case = |literal, body: i32| source::expr::VariantPatternToExpression {
+      loc: Location::dummy(),
+      pattern: source::pattern::MatchingPattern::Literal {
+        location: Location::dummy(),
+        associated_comments: source::NO_COMMENT_REFERENCE,
+        literal,
+      },
+      guard: None,
+      body: Box::new(source::expr::E::Literal(
+        source::expr::ExpressionCommon::dummy(builder.int_type()),
+        source::Literal::Int(body, None),
+      )),
+      ending_associated_comments: source::NO_COMMENT_REFERENCE,
+    };
+    let cases = vec![
+      literal_case(source::Literal::Int(1, None), 1),
+      literal_case(source::Literal::String(heap.alloc_str_for_test("a"), None), 2),
+      literal_case(source::Literal::Bool(true), 3),
+      literal_case(source::Literal::Bool(false), 4),
+    ];
+    assert_expr_correctly_lowered(
+      &source::expr::E::Match(source::expr::Match {
+        common: source::expr::ExpressionCommon::dummy(builder.int_type()),
+        matched: Box::new(dummy_source_this(heap)),
+        cases,
+      }),
+      heap,
+      r#"const GLOBAL_STRING_0 = '';
+
+const GLOBAL_STRING_1 = 'a';
+
+let _t2 = (_this: DUMMY_Dummy) == 1;
+let _t1: int;
+if (_t2: int) {
+  _t1 = 1;
+} else {
+  let _t4 = (_this: DUMMY_Dummy) == "a";
+  let _t3: int;
+  if (_t4: int) {
+    _t3 = 2;
+  } else {
+    let _t5: int;
+    if (_this: DUMMY_Dummy) {
+      _t5 = 3;
+    } else {
+      let _t7 = !(_this: DUMMY_Dummy);
+      let _t6: int;
+      if (_t7: int) {
+        _t6 = 4;
+      } else {
+        let _t8: int = _Process$panic<int>(0, "");
+        _t6 = (_t8: int);
+      }
+      _t5 = (_t6: int);
+    }
+    _t3 = (_t5: int);
+  }
+  _t1 = (_t3: int);
+}
+return (_t1: int);"#,
+    );
+  }
+
+  #[test]
+  fn match_guard_lowering_test() {
+    let builder = type_::test_type_builder::create();
+    let heap = &mut Heap::new();
+
+    let x = heap.alloc_str_for_test("x");
+    assert_expr_correctly_lowered(
+      &source::expr::E::Match(source::expr::Match {
+        common: source::expr::ExpressionCommon::dummy(builder.int_type()),
+        matched: Box::new(dummy_source_this(heap)),
+        cases: vec![
+          source::expr::VariantPatternToExpression {
+            loc: Location::dummy(),
+            pattern: source::pattern::MatchingPattern::Id(source::Id::from(x), builder.bool_type()),
+            guard: Some(Box::new(source::expr::E::LocalId(
+              source::expr::ExpressionCommon::dummy(builder.bool_type()),
+              source::Id::from(x),
+            ))),
+            body: Box::new(source::expr::E::Literal(
+              source::expr::ExpressionCommon::dummy(builder.int_type()),
+              source::Literal::Int(1, None),
+            )),
+            ending_associated_comments: source::NO_COMMENT_REFERENCE,
+          },
+          source::expr::VariantPatternToExpression {
+            loc: Location::dummy(),
+            pattern: source::pattern::MatchingPattern::Wildcard {
+              location: Location::dummy(),
+              associated_comments: source::NO_COMMENT_REFERENCE,
+            },
+            guard: None,
+            body: Box::new(source::expr::E::Literal(
+              source::expr::ExpressionCommon::dummy(builder.int_type()),
+              source::Literal::Int(2, None),
+            )),
+            ending_associated_comments: source::NO_COMMENT_REFERENCE,
+          },
+        ],
+      }),
+      heap,
+      r#"const GLOBAL_STRING_0 = '';
+
+let _t2: int;
+_t2 = (_this: DUMMY_Dummy);
+let _t3: int;
+if 1 {
+  _t3 = (_t2: int);
+} else {
+  _t3 = 0;
+}
+let _t1: int;
+if (_t3: int) {
+  _t1 = 1;
+} else {
+  let _t4: int;
+  if 1 {
+    _t4 = 2;
+  } else {
+    let _t5: int = _Process$panic<int>(0, "");
+    _t4 = (_t5: int);
+  }
+  _t1 = (_t4: int);
+}
+return (_t1: int);"#,
+    );
+  }
+
+  #[test]
+  fn or_pattern_with_three_alternatives_test() {
+    let _builder = type_::test_type_builder::create();
+    let heap = &mut Heap::new();
+
+    let dummy_type = Arc::new(dummy_source_id_type(heap));
+
+    assert_expr_correctly_lowered(
+      &source::expr::E::Match(source::expr::Match {
+        common: source::expr::ExpressionCommon::dummy(dummy_type.clone()),
+        matched: Box::new(dummy_source_this(heap)),
+        cases: vec![source::expr::VariantPatternToExpression {
+          loc: Location::dummy(),
+          pattern: source::pattern::MatchingPattern::Or {
+            location: Location::dummy(),
+            patterns: vec![
+              source::pattern::MatchingPattern::Variant(source::pattern::VariantPattern {
+                loc: Location::dummy(),
+                tag_order: 0,
+                tag: source::Id::from(heap.alloc_str_for_test("A")),
+                data_variables: None,
+                type_: dummy_type.clone(),
+              }),
+              source::pattern::MatchingPattern::Variant(source::pattern::VariantPattern {
+                loc: Location::dummy(),
+                tag_order: 1,
+                tag: source::Id::from(heap.alloc_str_for_test("B")),
+                data_variables: None,
+                type_: dummy_type.clone(),
+              }),
+              source::pattern::MatchingPattern::Variant(source::pattern::VariantPattern {
+                loc: Location::dummy(),
+                tag_order: 2,
+                tag: source::Id::from(heap.alloc_str_for_test("C")),
+                data_variables: None,
+                type_: dummy_type.clone(),
+              }),
+            ],
+          },
+          guard: None,
+          body: Box::new(dummy_source_this(heap)),
+          ending_associated_comments: source::NO_COMMENT_REFERENCE,
+        }],
+      }),
+      heap,
+      r#"const GLOBAL_STRING_0 = '';
+
+let [] if tagof((_this: DUMMY_Dummy))==0 {
+  _t5 = 1;
+} else {
+  _t5 = 0;
+}
+let _t6: int;
+if (_t5: int) {
+  _t6 = 1;
+} else {
+  let [] if tagof((_this: DUMMY_Dummy))==1 {
     _t3 = 1;
   } else {
     _t3 = 0;
   }
-  let _t2: DUMMY_Dummy;
+  let _t4: int;
   if (_t3: int) {
-    _t2 = (_this: DUMMY_Dummy);
+    _t4 = 1;
   } else {
-    let _t1: DUMMY_Dummy = _Process$panic<DUMMY_Dummy>(0, "");
-    _t2 = (_t1: DUMMY_Dummy);
+    let [] if tagof((_this: DUMMY_Dummy))==2 {
+      _t2 = 1;
+    } else {
+      _t2 = 0;
+    }
+    _t4 = (_t2: int);
+  }
+  _t6 = (_t4: int);
+}
+let _t1: DUMMY_Dummy;
+if (_t6: int) {
+  _t1 = (_this: DUMMY_Dummy);
+} else {
+  let _t7: DUMMY_Dummy = _Process$panic<DUMMY_Dummy>(0, "");
+  _t1 = (_t7: DUMMY_Dummy);
+}
+return (_t1: DUMMY_Dummy);"#,
+    );
+  }
+
+  #[test]
+  fn or_pattern_empty_test() {
+    let heap = &mut Heap::new();
+    let dummy_type = Arc::new(dummy_source_id_type(heap));
+
+    assert_expr_correctly_lowered(
+      &source::expr::E::Match(source::expr::Match {
+        common: source::expr::ExpressionCommon::dummy(dummy_type.clone()),
+        matched: Box::new(dummy_source_this(heap)),
+        cases: vec![source::expr::VariantPatternToExpression {
+          loc: Location::dummy(),
+          pattern: source::pattern::MatchingPattern::Or {
+            location: Location::dummy(),
+            patterns: vec![],
+          },
+          guard: None,
+          body: Box::new(dummy_source_this(heap)),
+          ending_associated_comments: source::NO_COMMENT_REFERENCE,
+        }],
+      }),
+      heap,
+      r#"const GLOBAL_STRING_0 = '';
+
+let _t1: DUMMY_Dummy;
+if 0 {
+  _t1 = (_this: DUMMY_Dummy);
+} else {
+  let _t2: DUMMY_Dummy = _Process$panic<DUMMY_Dummy>(0, "");
+  _t1 = (_t2: DUMMY_Dummy);
+}
+return (_t1: DUMMY_Dummy);"#,
+    );
+  }
+
+  #[test]
+  fn or_pattern_single_test() {
+    let heap = &mut Heap::new();
+    let dummy_type = Arc::new(dummy_source_id_type(heap));
+
+    assert_expr_correctly_lowered(
+      &source::expr::E::Match(source::expr::Match {
+        common: source::expr::ExpressionCommon::dummy(dummy_type.clone()),
+        matched: Box::new(dummy_source_this(heap)),
+        cases: vec![source::expr::VariantPatternToExpression {
+          loc: Location::dummy(),
+          pattern: source::pattern::MatchingPattern::Or {
+            location: Location::dummy(),
+            patterns: vec![source::pattern::MatchingPattern::Variant(
+              source::pattern::VariantPattern {
+                loc: Location::dummy(),
+                tag_order: 0,
+                tag: source::Id::from(heap.alloc_str_for_test("Foo")),
+                data_variables: None,
+                type_: dummy_type.clone(),
+              },
+            )],
+          },
+          guard: None,
+          body: Box::new(dummy_source_this(heap)),
+          ending_associated_comments: source::NO_COMMENT_REFERENCE,
+        }],
+      }),
+      heap,
+      r#"const GLOBAL_STRING_0 = '';
+
+let [] if tagof((_this: DUMMY_Dummy))==0 {
+  _t2 = 1;
+} else {
+  _t2 = 0;
+}
+let _t1: DUMMY_Dummy;
+if (_t2: int) {
+  _t1 = (_this: DUMMY_Dummy);
+} else {
+  let _t3: DUMMY_Dummy = _Process$panic<DUMMY_Dummy>(0, "");
+  _t1 = (_t3: DUMMY_Dummy);
+}
+return (_t1: DUMMY_Dummy);"#,
+    );
+  }
+
+  #[test]
+  fn or_pattern_nested_in_variant_test() {
+    let builder = type_::test_type_builder::create();
+    let heap = &mut Heap::new();
+    let dummy_type = Arc::new(dummy_source_id_type(heap));
+
+    // match this { Wrap(Foo(x) | Bar(x)) -> x }
+    assert_expr_correctly_lowered(
+      &source::expr::E::Match(source::expr::Match {
+        common: source::expr::ExpressionCommon::dummy(dummy_type.clone()),
+        matched: Box::new(dummy_source_this(heap)),
+        cases: vec![source::expr::VariantPatternToExpression {
+          loc: Location::dummy(),
+          pattern: source::pattern::MatchingPattern::Variant(source::pattern::VariantPattern {
+            loc: Location::dummy(),
+            tag_order: 0,
+            tag: source::Id::from(heap.alloc_str_for_test("Wrap")),
+            data_variables: Some(source::pattern::TuplePattern {
+              location: Location::dummy(),
+              start_associated_comments: source::NO_COMMENT_REFERENCE,
+              ending_associated_comments: source::NO_COMMENT_REFERENCE,
+              elements: vec![source::pattern::TuplePatternElement {
+                pattern: Box::new(source::pattern::MatchingPattern::Or {
+                  location: Location::dummy(),
+                  patterns: vec![
+                    source::pattern::MatchingPattern::Variant(source::pattern::VariantPattern {
+                      loc: Location::dummy(),
+                      tag_order: 0,
+                      tag: source::Id::from(heap.alloc_str_for_test("Foo")),
+                      data_variables: Some(source::pattern::TuplePattern {
+                        location: Location::dummy(),
+                        start_associated_comments: source::NO_COMMENT_REFERENCE,
+                        ending_associated_comments: source::NO_COMMENT_REFERENCE,
+                        elements: vec![source::pattern::TuplePatternElement {
+                          pattern: Box::new(source::pattern::MatchingPattern::Id(
+                            source::Id::from(heap.alloc_str_for_test("x")),
+                            builder.int_type(),
+                          )),
+                          type_: builder.int_type(),
+                        }],
+                      }),
+                      type_: dummy_type.clone(),
+                    }),
+                    source::pattern::MatchingPattern::Variant(source::pattern::VariantPattern {
+                      loc: Location::dummy(),
+                      tag_order: 1,
+                      tag: source::Id::from(heap.alloc_str_for_test("Bar")),
+                      data_variables: Some(source::pattern::TuplePattern {
+                        location: Location::dummy(),
+                        start_associated_comments: source::NO_COMMENT_REFERENCE,
+                        ending_associated_comments: source::NO_COMMENT_REFERENCE,
+                        elements: vec![source::pattern::TuplePatternElement {
+                          pattern: Box::new(source::pattern::MatchingPattern::Id(
+                            source::Id::from(heap.alloc_str_for_test("x")),
+                            builder.int_type(),
+                          )),
+                          type_: builder.int_type(),
+                        }],
+                      }),
+                      type_: dummy_type.clone(),
+                    }),
+                  ],
+                }),
+                type_: dummy_type.clone(),
+              }],
+            }),
+            type_: dummy_type.clone(),
+          }),
+          guard: None,
+          body: Box::new(source::expr::E::LocalId(
+            source::expr::ExpressionCommon::dummy(dummy_type.clone()),
+            source::Id::from(heap.alloc_str_for_test("x")),
+          )),
+          ending_associated_comments: source::NO_COMMENT_REFERENCE,
+        }],
+      }),
+      heap,
+      r#"const GLOBAL_STRING_0 = '';
+
+let _t2: int;
+let [_t3: DUMMY_Dummy] if tagof((_this: DUMMY_Dummy))==0 {
+  let [_t6: int] if tagof((_t3: DUMMY_Dummy))==0 {
+    _t2 = (_t6: int);
+    _t7 = 1;
+  } else {
+    _t7 = 0;
+  }
+  let _t8: int;
+  if (_t7: int) {
+    _t8 = 1;
+  } else {
+    let [_t4: int] if tagof((_t3: DUMMY_Dummy))==1 {
+      _t2 = (_t4: int);
+      _t5 = 1;
+    } else {
+      _t5 = 0;
+    }
+    _t8 = (_t5: int);
+  }
+  let _t9: int;
+  if (_t8: int) {
+    _t9 = 1;
+  } else {
+    _t9 = 0;
+  }
+  _t10 = (_t9: int);
+} else {
+  _t10 = 0;
+}
+let _t1: DUMMY_Dummy;
+if (_t10: int) {
+  _t1 = (_t2: int);
+} else {
+  let _t11: DUMMY_Dummy = _Process$panic<DUMMY_Dummy>(0, "");
+  _t1 = (_t11: DUMMY_Dummy);
+}
+return (_t1: DUMMY_Dummy);"#,
+    );
+  }
+
+  #[test]
+  fn return_lowering_tests() {
+    let builder = type_::test_type_builder::create();
+
+    let heap = &mut Heap::new();
+    assert_expr_correctly_lowered(
+      &source::expr::E::Return(source::expr::Return {
+        common: source::expr::ExpressionCommon::dummy(builder.int_type()),
+        argument: Box::new(dummy_source_this(heap)),
+      }),
+      heap,
+      "return (_this: DUMMY_Dummy);\nreturn 0;",
+    );
+
+    let heap = &mut Heap::new();
+    assert_expr_correctly_lowered(
+      &source::expr::E::Return(source::expr::Return {
+        common: source::expr::ExpressionCommon::dummy(Arc::new(dummy_source_id_type(heap))),
+        argument: Box::new(dummy_source_this(heap)),
+      }),
+      heap,
+      "return (_this: DUMMY_Dummy);\nlet _t1: DUMMY_Dummy;\nreturn (_t1: DUMMY_Dummy);",
+    );
+  }
+
+  #[test]
+  fn let_else_lowering_tests() {
+    let builder = type_::test_type_builder::create();
+
+    let heap = &mut Heap::new();
+    assert_expr_correctly_lowered(
+      &source::expr::E::Block(source::expr::Block {
+        common: source::expr::ExpressionCommon::dummy(builder.int_type()),
+        statements: vec![source::expr::Statement::Declaration(Box::new(
+          source::expr::DeclarationStatement {
+            loc: Location::dummy(),
+            associated_comments: source::NO_COMMENT_REFERENCE,
+            is_mutable: false,
+            pattern: source::pattern::MatchingPattern::Variant(source::pattern::VariantPattern {
+              loc: Location::dummy(),
+              tag_order: 1,
+              tag: source::Id::from(heap.alloc_str_for_test("Some")),
+              data_variables: Some(source::pattern::TuplePattern {
+                location: Location::dummy(),
+                start_associated_comments: source::NO_COMMENT_REFERENCE,
+                ending_associated_comments: source::NO_COMMENT_REFERENCE,
+                elements: vec![source::pattern::TuplePatternElement {
+                  pattern: Box::new(source::pattern::MatchingPattern::Id(
+                    source::Id::from(PStr::LOWER_A),
+                    builder.int_type(),
+                  )),
+                  type_: builder.int_type(),
+                }],
+              }),
+              type_: Arc::new(dummy_source_id_type(heap)),
+            }),
+            annotation: None,
+            assigned_expression: Box::new(dummy_source_this(heap)),
+            else_block: Some(Box::new(source::expr::Block {
+              common: source::expr::ExpressionCommon::dummy(builder.unit_type()),
+              statements: Vec::new(),
+              expression: Some(Box::new(source::expr::E::Return(source::expr::Return {
+                common: source::expr::ExpressionCommon::dummy(builder.unit_type()),
+                argument: Box::new(source::expr::E::Literal(
+                  source::expr::ExpressionCommon::dummy(builder.int_type()),
+                  source::Literal::Int(0, None),
+                )),
+              }))),
+              ending_associated_comments: source::NO_COMMENT_REFERENCE,
+            })),
+          },
+        ))],
+        expression: Some(Box::new(id_expr(PStr::LOWER_A, builder.int_type()))),
+        ending_associated_comments: source::NO_COMMENT_REFERENCE,
+      }),
+      heap,
+      r#"let _t1: int;
+let [_t2: int] if tagof((_this: DUMMY_Dummy))==1 {
+  _t1 = (_t2: int);
+  _t3 = 1;
+} else {
+  _t3 = 0;
+}
+if (_t3: int) {
+} else {
+  return 0;
+}
+return (_t1: int);"#,
+    );
+  }
+
+  #[test]
+  fn local_function_lowering_tests() {
+    let annot_builder = source::test_builder::create();
+    let builder = type_::test_type_builder::create();
+
+    let heap = &mut Heap::new();
+    let fn_type = builder.fun_type(vec![builder.int_type()], builder.int_type());
+    let name = heap.alloc_str_for_test("f");
+    assert_expr_correctly_lowered(
+      &source::expr::E::Block(source::expr::Block {
+        common: source::expr::ExpressionCommon::dummy(fn_type.clone()),
+        statements: vec![source::expr::Statement::Function(Box::new(
+          source::expr::LocalFunctionStatement {
+            loc: Location::dummy(),
+            associated_comments: source::NO_COMMENT_REFERENCE,
+            name: source::Id::from(name),
+            return_annotation: annot_builder.int_annot(),
+            lambda: source::expr::Lambda {
+              common: source::expr::ExpressionCommon::dummy(fn_type.clone()),
+              parameters: source::expr::LambdaParameters {
+                loc: Location::dummy(),
+                ending_associated_comments: source::NO_COMMENT_REFERENCE,
+                parameters: vec![source::OptionallyAnnotatedId {
+                  name: source::Id::from(PStr::LOWER_A),
+                  type_: builder.int_type(),
+                  annotation: Some(annot_builder.int_annot()),
+                }],
+              },
+              captured: HashMap::from([
+                (name, fn_type.clone()),
+                (heap.alloc_str_for_test("captured_a"), builder.int_type()),
+              ]),
+              body: Box::new(source::expr::E::Call(source::expr::Call {
+                common: source::expr::ExpressionCommon::dummy(builder.int_type()),
+                callee: Box::new(id_expr(name, fn_type.clone())),
+                arguments: source::expr::ParenthesizedExpressionList {
+                  loc: Location::dummy(),
+                  start_associated_comments: source::NO_COMMENT_REFERENCE,
+                  ending_associated_comments: source::NO_COMMENT_REFERENCE,
+                  expressions: vec![id_expr(PStr::LOWER_A, builder.int_type())],
+                },
+                binding: Default::default(),
+              })),
+            },
+          },
+        ))],
+        expression: Some(Box::new(id_expr(name, fn_type))),
+        ending_associated_comments: source::NO_COMMENT_REFERENCE,
+      }),
+      heap,
+      r#"closure type _$SyntheticIDType1 = (int) -> int
+object type _$SyntheticIDType0 = [int]
+function __GenFn$0(_this: _$SyntheticIDType0, a: int): int {
+  let captured_a: int = (_this: _$SyntheticIDType0)[0];
+  let f: _$SyntheticIDType1 = Closure { fun: (__GenFn$0: (_$SyntheticIDType0, int) -> int), context: (_this: _$SyntheticIDType0) };
+  let _t5: int = (f: _$SyntheticIDType1)((a: int));
+  return (_t5: int);
+}
+
+let _t2: _$SyntheticIDType0 = [(captured_a: int)];
+let _t1: _$SyntheticIDType1 = Closure { fun: (__GenFn$0: (_$SyntheticIDType0, int) -> int), context: (_t2: _$SyntheticIDType0) };
+return (_t1: _$SyntheticIDType1);"#,
+    );
+  }
+
+  #[test]
+  fn mutable_variable_lowering_tests() {
+    let builder = type_::test_type_builder::create();
+
+    let heap = &mut Heap::new();
+    assert_expr_correctly_lowered(
+      &source::expr::E::Block(source::expr::Block {
+        common: source::expr::ExpressionCommon::dummy(builder.int_type()),
+        statements: vec![
+          source::expr::Statement::Declaration(Box::new(source::expr::DeclarationStatement {
+            loc: Location::dummy(),
+            associated_comments: source::NO_COMMENT_REFERENCE,
+            is_mutable: true,
+            pattern: source::pattern::MatchingPattern::Id(
+              source::Id::from(PStr::LOWER_A),
+              builder.int_type(),
+            ),
+            annotation: None,
+            assigned_expression: Box::new(source::expr::E::Literal(
+              source::expr::ExpressionCommon::dummy(builder.int_type()),
+              source::Literal::Int(1, None),
+            )),
+            else_block: None,
+          })),
+          source::expr::Statement::Assignment(Box::new(source::expr::AssignmentStatement {
+            loc: Location::dummy(),
+            associated_comments: source::NO_COMMENT_REFERENCE,
+            name: source::Id::from(PStr::LOWER_A),
+            assigned_expression: Box::new(source::expr::E::Binary(source::expr::Binary {
+              common: source::expr::ExpressionCommon::dummy(builder.int_type()),
+              operator_preceding_comments: source::NO_COMMENT_REFERENCE,
+              operator: source::expr::BinaryOperator::PLUS,
+              e1: Box::new(id_expr(PStr::LOWER_A, builder.int_type())),
+              e2: Box::new(source::expr::E::Literal(
+                source::expr::ExpressionCommon::dummy(builder.int_type()),
+                source::Literal::Int(2, None),
+              )),
+            })),
+          })),
+        ],
+        expression: Some(Box::new(id_expr(PStr::LOWER_A, builder.int_type()))),
+        ending_associated_comments: source::NO_COMMENT_REFERENCE,
+      }),
+      heap,
+      r#"let _t2 = 1 + 2;
+return (_t2: int);"#,
+    );
+
+    let heap = &mut Heap::new();
+    let assign_a = |value: i32| {
+      source::expr::Statement::Assignment(Box::new(source::expr::AssignmentStatement {
+        loc: Location::dummy(),
+        associated_comments: source::NO_COMMENT_REFERENCE,
+        name: source::Id::from(PStr::LOWER_A),
+        assigned_expression: Box::new(source::expr::E::Literal(
+          source::expr::ExpressionCommon::dummy(builder.int_type()),
+          source::Literal::Int(value, None),
+        )),
+      }))
+    };
+    let unit_block = |statements| source::expr::Block {
+      common: source::expr::ExpressionCommon::dummy(builder.unit_type()),
+      statements,
+      expression: None,
+      ending_associated_comments: source::NO_COMMENT_REFERENCE,
+    };
+    assert_expr_correctly_lowered(
+      &source::expr::E::Block(source::expr::Block {
+        common: source::expr::ExpressionCommon::dummy(builder.int_type()),
+        statements: vec![
+          source::expr::Statement::Declaration(Box::new(source::expr::DeclarationStatement {
+            loc: Location::dummy(),
+            associated_comments: source::NO_COMMENT_REFERENCE,
+            is_mutable: true,
+            pattern: source::pattern::MatchingPattern::Id(
+              source::Id::from(PStr::LOWER_A),
+              builder.int_type(),
+            ),
+            annotation: None,
+            assigned_expression: Box::new(source::expr::E::Literal(
+              source::expr::ExpressionCommon::dummy(builder.int_type()),
+              source::Literal::Int(1, None),
+            )),
+            else_block: None,
+          })),
+          source::expr::Statement::Expression(Box::new(source::expr::E::IfElse(
+            source::expr::IfElse {
+              common: source::expr::ExpressionCommon::dummy(builder.unit_type()),
+              condition: Box::new(source::expr::IfElseCondition::Expression(id_expr(
+                heap.alloc_str_for_test("foo"),
+                builder.bool_type(),
+              ))),
+              e1: Box::new(unit_block(vec![assign_a(2)])),
+              e2: Box::new(source::expr::IfElseOrBlock::Block(unit_block(Vec::new()))),
+            },
+          ))),
+        ],
+        expression: Some(Box::new(id_expr(PStr::LOWER_A, builder.int_type()))),
+        ending_associated_comments: source::NO_COMMENT_REFERENCE,
+      }),
+      heap,
+      r#"let _t3: int;
+let _t2: int;
+if (foo: int) {
+  _t3 = 2;
+  _t2 = 0;
+} else {
+  _t3 = 1;
+  _t2 = 0;
+}
+return (_t3: int);"#,
+    );
+  }
+
+  #[test]
+  fn loop_lowering_tests() {
+    let builder = type_::test_type_builder::create();
+
+    let heap = &mut Heap::new();
+    assert_expr_correctly_lowered(
+      &source::expr::E::While(source::expr::While {
+        common: source::expr::ExpressionCommon::dummy(builder.unit_type()),
+        condition: Box::new(id_expr(heap.alloc_str_for_test("foo"), builder.bool_type())),
+        body: Box::new(source::expr::Block {
+          common: source::expr::ExpressionCommon::dummy(builder.unit_type()),
+          statements: Vec::new(),
+          expression: Some(Box::new(source::expr::E::Break(
+            source::expr::ExpressionCommon::dummy(builder.unit_type()),
+          ))),
+          ending_associated_comments: source::NO_COMMENT_REFERENCE,
+        }),
+      }),
+      heap,
+      r#"while (true) {
+  if !(foo: int) {
+    break;
+  }
+  break;
+}
+return 0;"#,
+    );
+
+    let heap = &mut Heap::new();
+    let dummy_type = Arc::new(dummy_source_id_type(heap));
+    assert_expr_correctly_lowered(
+      &source::expr::E::For(source::expr::For {
+        common: source::expr::ExpressionCommon::dummy(builder.unit_type()),
+        iterator: source::Id::from(PStr::LOWER_I),
+        start: Box::new(source::expr::E::Literal(
+          source::expr::ExpressionCommon::dummy(builder.int_type()),
+          source::Literal::Int(0, None),
+        )),
+        end: Box::new(id_expr(heap.alloc_str_for_test("bar"), builder.int_type())),
+        body: Box::new(source::expr::Block {
+          common: source::expr::ExpressionCommon::dummy(builder.unit_type()),
+          statements: vec![
+            source::expr::Statement::Expression(Box::new(id_expr(
+              PStr::LOWER_I,
+              builder.int_type(),
+            ))),
+            source::expr::Statement::Expression(Box::new(source::expr::E::Break(
+              source::expr::ExpressionCommon::dummy(dummy_type),
+            ))),
+          ],
+          expression: None,
+          ending_associated_comments: source::NO_COMMENT_REFERENCE,
+        }),
+      }),
+      heap,
+      r#"let _t1: int = 0;
+while (true) {
+  let _t2 = (_t1: int) >= (bar: int);
+  if (_t2: int) {
+    break;
+  }
+  break;
+  let _t4: DUMMY_Dummy;
+  let _t3 = (_t1: int) + 1;
+  _t1 = (_t3: int);
+}
+return 0;"#,
+    );
+
+    let heap = &mut Heap::new();
+    assert_expr_correctly_lowered(
+      &source::expr::E::Block(source::expr::Block {
+        common: source::expr::ExpressionCommon::dummy(builder.int_type()),
+        statements: vec![
+          source::expr::Statement::Declaration(Box::new(source::expr::DeclarationStatement {
+            loc: Location::dummy(),
+            associated_comments: source::NO_COMMENT_REFERENCE,
+            is_mutable: true,
+            pattern: source::pattern::MatchingPattern::Id(
+              source::Id::from(PStr::LOWER_A),
+              builder.int_type(),
+            ),
+            annotation: None,
+            assigned_expression: Box::new(source::expr::E::Literal(
+              source::expr::ExpressionCommon::dummy(builder.int_type()),
+              source::Literal::Int(0, None),
+            )),
+            else_block: None,
+          })),
+          source::expr::Statement::Expression(Box::new(source::expr::E::While(
+            source::expr::While {
+              common: source::expr::ExpressionCommon::dummy(builder.unit_type()),
+              condition: Box::new(id_expr(heap.alloc_str_for_test("foo"), builder.bool_type())),
+              body: Box::new(source::expr::Block {
+                common: source::expr::ExpressionCommon::dummy(builder.unit_type()),
+                statements: vec![source::expr::Statement::Assignment(Box::new(
+                  source::expr::AssignmentStatement {
+                    loc: Location::dummy(),
+                    associated_comments: source::NO_COMMENT_REFERENCE,
+                    name: source::Id::from(PStr::LOWER_A),
+                    assigned_expression: Box::new(source::expr::E::Binary(source::expr::Binary {
+                      common: source::expr::ExpressionCommon::dummy(builder.int_type()),
+                      operator_preceding_comments: source::NO_COMMENT_REFERENCE,
+                      operator: source::expr::BinaryOperator::PLUS,
+                      e1: Box::new(id_expr(PStr::LOWER_A, builder.int_type())),
+                      e2: Box::new(source::expr::E::Literal(
+                        source::expr::ExpressionCommon::dummy(builder.int_type()),
+                        source::Literal::Int(1, None),
+                      )),
+                    })),
+                  },
+                ))],
+                expression: Some(Box::new(source::expr::E::Break(
+                  source::expr::ExpressionCommon::dummy(builder.unit_type()),
+                ))),
+                ending_associated_comments: source::NO_COMMENT_REFERENCE,
+              }),
+            },
+          ))),
+        ],
+        expression: Some(Box::new(id_expr(PStr::LOWER_A, builder.int_type()))),
+        ending_associated_comments: source::NO_COMMENT_REFERENCE,
+      }),
+      heap,
+      r#"let _t3: int;
+_t3 = 0;
+let _t2: int = 0;
+while (true) {
+  if !(foo: int) {
+    break;
   }
-  _t4 = (_t2: DUMMY_Dummy);
+  let _t4 = (_t2: int) + 1;
+  _t3 = (_t4: int);
+  break;
+  _t3 = (_t4: int);
+  _t2 = (_t4: int);
 }
-return (_t4: DUMMY_Dummy);"#,
+return (_t3: int);"#,
     );
   }
 
   #[test]
-  fn or_pattern_with_three_alternatives_test() {
-    let _builder = type_::test_type_builder::create();
-    let heap = &mut Heap::new();
-
-    let dummy_type = Arc::new(dummy_source_id_type(heap));
-
-    assert_expr_correctly_lowered(
-      &source::expr::E::Match(source::expr::Match {
-        common: source::expr::ExpressionCommon::dummy(dummy_type.clone()),
-        matched: Box::new(dummy_source_this(heap)),
-        cases: vec![source::expr::VariantPatternToExpression {
-          loc: Location::dummy(),
-          pattern: source::pattern::MatchingPattern::Or {
-            location: Location::dummy(),
-            patterns: vec![
-              source::pattern::MatchingPattern::Variant(source::pattern::VariantPattern {
-                loc: Location::dummy(),
-                tag_order: 0,
-                tag: source::Id::from(heap.alloc_str_for_test("A")),
-                data_variables: None,
-                type_: dummy_type.clone(),
-              }),
-              source::pattern::MatchingPattern::Variant(source::pattern::VariantPattern {
-                loc: Location::dummy(),
-                tag_order: 1,
-                tag: source::Id::from(heap.alloc_str_for_test("B")),
-                data_variables: None,
-                type_: dummy_type.clone(),
-              }),
-              source::pattern::MatchingPattern::Variant(source::pattern::VariantPattern {
-                loc: Location::dummy(),
-                tag_order: 2,
-                tag: source::Id::from(heap.alloc_str_for_test("C")),
-                data_variables: None,
-                type_: dummy_type.clone(),
-              }),
-            ],
+  fn assert_lowering_tests() {
+    let builder = type_::test_type_builder::create();
+    let assert_expr = |heap: &mut Heap, message: Option<source::expr::E<Arc<type_::Type>>>| {
+      let mut expressions = vec![id_expr(heap.alloc_str_for_test("foo"), builder.bool_type())];
+      expressions.extend(message);
+      source::expr::E::Assert(source::expr::Assert {
+        common: source::expr::ExpressionCommon {
+          loc: Location {
+            module_reference: ModuleReference::ROOT,
+            start: Position(2, 4),
+            end: Position(2, 20),
           },
-          body: Box::new(dummy_source_this(heap)),
+          associated_comments: source::NO_COMMENT_REFERENCE,
+          type_: builder.unit_type(),
+        },
+        arguments: source::expr::ParenthesizedExpressionList {
+          loc: Location::dummy(),
+          start_associated_comments: source::NO_COMMENT_REFERENCE,
           ending_associated_comments: source::NO_COMMENT_REFERENCE,
-        }],
-      }),
+          expressions,
+        },
+      })
+    };
+
+    let heap = &mut Heap::new();
+    let e = assert_expr(heap, None);
+    assert_expr_correctly_lowered(
+      &e,
       heap,
-      r#"const GLOBAL_STRING_0 = '';
+      r#"const GLOBAL_STRING_0 = 'Assertion failed at :3:5';
 
-let [] if tagof((_this: DUMMY_Dummy))==0 {
-  _t6 = 1;
-} else {
-  _t6 = 0;
-}
-let _t7: int;
-if (_t6: int) {
-  _t7 = 1;
-} else {
-  let [] if tagof((_this: DUMMY_Dummy))==1 {
-    _t4 = 1;
-  } else {
-    _t4 = 0;
-  }
-  let _t5: int;
-  if (_t4: int) {
-    _t5 = 1;
-  } else {
-    let [] if tagof((_this: DUMMY_Dummy))==2 {
-      _t3 = 1;
-    } else {
-      _t3 = 0;
-    }
-    _t5 = (_t3: int);
-  }
-  _t7 = (_t5: int);
-}
-let _t2: DUMMY_Dummy;
-if (_t7: int) {
-  _t2 = (_this: DUMMY_Dummy);
+if (foo: int) {
 } else {
-  let _t1: DUMMY_Dummy = _Process$panic<DUMMY_Dummy>(0, "");
-  _t2 = (_t1: DUMMY_Dummy);
+  _Process$panic<int>(0, "Assertion failed at :3:5");
 }
-return (_t2: DUMMY_Dummy);"#,
+return 0;"#,
     );
-  }
 
-  #[test]
-  fn or_pattern_empty_test() {
     let heap = &mut Heap::new();
-    let dummy_type = Arc::new(dummy_source_id_type(heap));
-
+    let message = source::expr::E::Literal(
+      source::expr::ExpressionCommon::dummy(builder.string_type()),
+      source::Literal::String(heap.alloc_str_for_test("bad"), None),
+    );
+    let e = assert_expr(heap, Some(message));
     assert_expr_correctly_lowered(
-      &source::expr::E::Match(source::expr::Match {
-        common: source::expr::ExpressionCommon::dummy(dummy_type.clone()),
-        matched: Box::new(dummy_source_this(heap)),
-        cases: vec![source::expr::VariantPatternToExpression {
-          loc: Location::dummy(),
-          pattern: source::pattern::MatchingPattern::Or {
-            location: Location::dummy(),
-            patterns: vec![],
-          },
-          body: Box::new(dummy_source_this(heap)),
-          ending_associated_comments: source::NO_COMMENT_REFERENCE,
-        }],
-      }),
+      &e,
       heap,
-      r#"const GLOBAL_STRING_0 = '';
+      r#"const GLOBAL_STRING_0 = 'Assertion failed at :3:5: bad';
 
-let _t2: DUMMY_Dummy;
-if 0 {
-  _t2 = (_this: DUMMY_Dummy);
+if (foo: int) {
 } else {
-  let _t1: DUMMY_Dummy = _Process$panic<DUMMY_Dummy>(0, "");
-  _t2 = (_t1: DUMMY_Dummy);
+  _Process$panic<int>(0, "Assertion failed at :3:5: bad");
 }
-return (_t2: DUMMY_Dummy);"#,
+return 0;"#,
     );
-  }
 
-  #[test]
-  fn or_pattern_single_test() {
     let heap = &mut Heap::new();
-    let dummy_type = Arc::new(dummy_source_id_type(heap));
-
+    let message = id_expr(heap.alloc_str_for_test("bar"), builder.string_type());
+    let e = assert_expr(heap, Some(message));
     assert_expr_correctly_lowered(
-      &source::expr::E::Match(source::expr::Match {
-        common: source::expr::ExpressionCommon::dummy(dummy_type.clone()),
-        matched: Box::new(dummy_source_this(heap)),
-        cases: vec![source::expr::VariantPatternToExpression {
-          loc: Location::dummy(),
-          pattern: source::pattern::MatchingPattern::Or {
-            location: Location::dummy(),
-            patterns: vec![source::pattern::MatchingPattern::Variant(
-              source::pattern::VariantPattern {
-                loc: Location::dummy(),
-                tag_order: 0,
-                tag: source::Id::from(heap.alloc_str_for_test("Foo")),
-                data_variables: None,
-                type_: dummy_type.clone(),
-              },
-            )],
-          },
-          body: Box::new(dummy_source_this(heap)),
-          ending_associated_comments: source::NO_COMMENT_REFERENCE,
-        }],
-      }),
+      &e,
       heap,
-      r#"const GLOBAL_STRING_0 = '';
+      r#"const GLOBAL_STRING_0 = 'Assertion failed at :3:5: ';
 
-let [] if tagof((_this: DUMMY_Dummy))==0 {
-  _t3 = 1;
-} else {
-  _t3 = 0;
-}
-let _t2: DUMMY_Dummy;
-if (_t3: int) {
-  _t2 = (_this: DUMMY_Dummy);
+if (foo: int) {
 } else {
-  let _t1: DUMMY_Dummy = _Process$panic<DUMMY_Dummy>(0, "");
-  _t2 = (_t1: DUMMY_Dummy);
+  let _t2: _Str = _Str$concat("Assertion failed at :3:5: ", (bar: int));
+  _Process$panic<int>(0, (_t2: _Str));
 }
-return (_t2: DUMMY_Dummy);"#,
+return 0;"#,
     );
+
+    let heap = &mut Heap::new();
+    let e = assert_expr(heap, None);
+    assert_expr_correctly_lowered_with_strip_asserts(&e, heap, true, "return 0;");
   }
 
   #[test]
-  fn or_pattern_nested_in_variant_test() {
+  fn try_lowering_tests() {
     let builder = type_::test_type_builder::create();
-    let heap = &mut Heap::new();
-    let dummy_type = Arc::new(dummy_source_id_type(heap));
+    let std_type = |module_reference, id, type_arguments| {
+      Arc::new(type_::Type::Nominal(type_::NominalType {
+        reason: Reason::dummy(),
+        is_class_statics: false,
+        module_reference,
+        id,
+        type_arguments,
+      }))
+    };
+    let hir_std_type = |module_reference, type_name, type_arguments: Vec<hir::Type>| {
+      hir::Type::Id(hir::IdType {
+        name: hir::TypeName { module_reference: Some(module_reference), type_name },
+        type_arguments: Arc::from(type_arguments),
+      })
+    };
+    let try_expr = |heap: &mut Heap, argument_type: Arc<type_::Type>, type_| {
+      source::expr::E::Try(source::expr::Try {
+        common: source::expr::ExpressionCommon::dummy(type_),
+        argument: Box::new(id_expr(heap.alloc_str_for_test("foo"), argument_type)),
+      })
+    };
 
-    // match this { Wrap(Foo(x) | Bar(x)) -> x }
-    assert_expr_correctly_lowered(
-      &source::expr::E::Match(source::expr::Match {
-        common: source::expr::ExpressionCommon::dummy(dummy_type.clone()),
-        matched: Box::new(dummy_source_this(heap)),
-        cases: vec![source::expr::VariantPatternToExpression {
-          loc: Location::dummy(),
-          pattern: source::pattern::MatchingPattern::Variant(source::pattern::VariantPattern {
-            loc: Location::dummy(),
-            tag_order: 0,
-            tag: source::Id::from(heap.alloc_str_for_test("Wrap")),
-            data_variables: Some(source::pattern::TuplePattern {
-              location: Location::dummy(),
-              start_associated_comments: source::NO_COMMENT_REFERENCE,
-              ending_associated_comments: source::NO_COMMENT_REFERENCE,
-              elements: vec![source::pattern::TuplePatternElement {
-                pattern: Box::new(source::pattern::MatchingPattern::Or {
-                  location: Location::dummy(),
-                  patterns: vec![
-                    source::pattern::MatchingPattern::Variant(source::pattern::VariantPattern {
-                      loc: Location::dummy(),
-                      tag_order: 0,
-                      tag: source::Id::from(heap.alloc_str_for_test("Foo")),
-                      data_variables: Some(source::pattern::TuplePattern {
-                        location: Location::dummy(),
-                        start_associated_comments: source::NO_COMMENT_REFERENCE,
-                        ending_associated_comments: source::NO_COMMENT_REFERENCE,
-                        elements: vec![source::pattern::TuplePatternElement {
-                          pattern: Box::new(source::pattern::MatchingPattern::Id(
-                            source::Id::from(heap.alloc_str_for_test("x")),
-                            builder.int_type(),
-                          )),
-                          type_: builder.int_type(),
-                        }],
-                      }),
-                      type_: dummy_type.clone(),
-                    }),
-                    source::pattern::MatchingPattern::Variant(source::pattern::VariantPattern {
-                      loc: Location::dummy(),
-                      tag_order: 1,
-                      tag: source::Id::from(heap.alloc_str_for_test("Bar")),
-                      data_variables: Some(source::pattern::TuplePattern {
-                        location: Location::dummy(),
-                        start_associated_comments: source::NO_COMMENT_REFERENCE,
-                        ending_associated_comments: source::NO_COMMENT_REFERENCE,
-                        elements: vec![source::pattern::TuplePatternElement {
-                          pattern: Box::new(source::pattern::MatchingPattern::Id(
-                            source::Id::from(heap.alloc_str_for_test("x")),
-                            builder.int_type(),
-                          )),
-                          type_: builder.int_type(),
-                        }],
-                      }),
-                      type_: dummy_type.clone(),
-                    }),
-                  ],
-                }),
-                type_: dummy_type.clone(),
-              }],
-            }),
-            type_: dummy_type.clone(),
-          }),
-          body: Box::new(source::expr::E::LocalId(
-            source::expr::ExpressionCommon::dummy(dummy_type.clone()),
-            source::Id::from(heap.alloc_str_for_test("x")),
-          )),
-          ending_associated_comments: source::NO_COMMENT_REFERENCE,
-        }],
-      }),
+    let heap = &mut Heap::new();
+    let e = try_expr(
       heap,
-      r#"const GLOBAL_STRING_0 = '';
-
-let _t3: int;
-let [_t4: DUMMY_Dummy] if tagof((_this: DUMMY_Dummy))==0 {
-  let [_t7: int] if tagof((_t4: DUMMY_Dummy))==0 {
-    _t3 = (_t7: int);
-    _t8 = 1;
-  } else {
-    _t8 = 0;
-  }
-  let _t9: int;
-  if (_t8: int) {
-    _t9 = 1;
-  } else {
-    let [_t5: int] if tagof((_t4: DUMMY_Dummy))==1 {
-      _t3 = (_t5: int);
-      _t6 = 1;
-    } else {
-      _t6 = 0;
-    }
-    _t9 = (_t6: int);
-  }
-  let _t10: int;
-  if (_t9: int) {
-    _t10 = 1;
-  } else {
-    _t10 = 0;
-  }
-  _t11 = (_t10: int);
+      std_type(ModuleReference::STD_OPTION, PStr::OPTION_TYPE, vec![builder.int_type()]),
+      builder.int_type(),
+    );
+    assert_expr_correctly_lowered_with_return_type(
+      &e,
+      heap,
+      hir_std_type(ModuleReference::STD_OPTION, PStr::OPTION_TYPE, vec![hir::INT_TYPE]),
+      r#"let [_t2: int] if tagof((foo: int))==1 {
+  _t3 = (_t2: int);
 } else {
-  _t11 = 0;
+  let _t1: std$option_Option<int> = [0, ];
+  return (_t1: std$option_Option<int>);
+  _t3 = 0;
 }
-let _t2: DUMMY_Dummy;
-if (_t11: int) {
-  _t2 = (_t3: int);
+return (_t3: int);"#,
+    );
+
+    let heap = &mut Heap::new();
+    let dummy_type = Arc::new(dummy_source_id_type(heap));
+    let e = try_expr(
+      heap,
+      std_type(
+        ModuleReference::STD_RESULT,
+        PStr::RESULT_TYPE,
+        vec![dummy_type.clone(), builder.string_type()],
+      ),
+      dummy_type,
+    );
+    assert_expr_correctly_lowered_with_return_type(
+      &e,
+      heap,
+      hir_std_type(
+        ModuleReference::STD_RESULT,
+        PStr::RESULT_TYPE,
+        vec![hir::INT_TYPE, hir::STRING_TYPE.clone()],
+      ),
+      r#"let [_t4: DUMMY_Dummy] if tagof((foo: int))==0 {
+  _t5 = (_t4: DUMMY_Dummy);
 } else {
-  let _t1: DUMMY_Dummy = _Process$panic<DUMMY_Dummy>(0, "");
-  _t2 = (_t1: DUMMY_Dummy);
+  let [_t2: _Str] if tagof((foo: int))==1 {
+    let _t1: std$result_Result<int, _Str> = [1, (_t2: _Str)];
+    return (_t1: std$result_Result<int, _Str>);
+  } else {
+  }
+  let _t3: DUMMY_Dummy;
+  _t5 = (_t3: DUMMY_Dummy);
 }
-return (_t2: DUMMY_Dummy);"#,
+return (_t5: DUMMY_Dummy);"#,
     );
   }
 
@@ -3604,6 +6168,7 @@ return (_t2: DUMMY_Dummy);"#,
           source::expr::DeclarationStatement {
             loc: Location::dummy(),
             associated_comments: source::NO_COMMENT_REFERENCE,
+            is_mutable: false,
             pattern: source::pattern::MatchingPattern::Id(
               source::Id::from(PStr::LOWER_A),
               builder.unit_type(),
@@ -3616,6 +6181,7 @@ return (_t2: DUMMY_Dummy);"#,
                   source::expr::DeclarationStatement {
                     loc: Location::dummy(),
                     associated_comments: source::NO_COMMENT_REFERENCE,
+                    is_mutable: false,
                     pattern: source::pattern::MatchingPattern::Object {
                       location: Location::dummy(),
                       start_associated_comments: source::NO_COMMENT_REFERENCE,
@@ -3647,24 +6213,28 @@ return (_t2: DUMMY_Dummy);"#,
                     },
                     annotation: Some(dummy_source_id_annot(heap)),
                     assigned_expression: Box::new(dummy_source_this(heap)),
+                    else_block: None,
                   },
                 )),
                 source::expr::Statement::Declaration(Box::new(
                   source::expr::DeclarationStatement {
                     loc: Location::dummy(),
                     associated_comments: source::NO_COMMENT_REFERENCE,
+                    is_mutable: false,
                     pattern: source::pattern::MatchingPattern::Wildcard {
                       location: Location::dummy(),
                       associated_comments: source::NO_COMMENT_REFERENCE,
                     },
                     annotation: Some(dummy_source_id_annot(heap)),
                     assigned_expression: Box::new(dummy_source_this(heap)),
+                    else_block: None,
                   },
                 )),
               ],
               expression: None,
               ending_associated_comments: source::NO_COMMENT_REFERENCE,
             })),
+            else_block: None,
           },
         ))],
         expression: None,
@@ -3690,6 +6260,7 @@ return 0;"#,
           source::expr::Statement::Declaration(Box::new(source::expr::DeclarationStatement {
             loc: Location::dummy(),
             associated_comments: source::NO_COMMENT_REFERENCE,
+            is_mutable: false,
             pattern: source::pattern::MatchingPattern::Object {
               location: Location::dummy(),
               start_associated_comments: source::NO_COMMENT_REFERENCE,
@@ -3721,10 +6292,12 @@ return 0;"#,
             },
             annotation: Some(dummy_source_id_annot(heap)),
             assigned_expression: Box::new(dummy_source_this(heap)),
+            else_block: None,
           })),
           source::expr::Statement::Declaration(Box::new(source::expr::DeclarationStatement {
             loc: Location::dummy(),
             associated_comments: source::NO_COMMENT_REFERENCE,
+            is_mutable: false,
             pattern: source::pattern::MatchingPattern::Tuple(source::pattern::TuplePattern {
               location: Location::dummy(),
               start_associated_comments: source::NO_COMMENT_REFERENCE,
@@ -3748,16 +6321,19 @@ return 0;"#,
             }),
             annotation: Some(dummy_source_id_annot(heap)),
             assigned_expression: Box::new(dummy_source_this(heap)),
+            else_block: None,
           })),
           source::expr::Statement::Declaration(Box::new(source::expr::DeclarationStatement {
             loc: Location::dummy(),
             associated_comments: source::NO_COMMENT_REFERENCE,
+            is_mutable: false,
             pattern: source::pattern::MatchingPattern::Wildcard {
               location: Location::dummy(),
               associated_comments: source::NO_COMMENT_REFERENCE,
             },
             annotation: Some(dummy_source_id_annot(heap)),
             assigned_expression: Box::new(dummy_source_this(heap)),
+            else_block: None,
           })),
         ],
         expression: None,
@@ -3785,6 +6361,7 @@ return 0;"#,
           source::expr::DeclarationStatement {
             loc: Location::dummy(),
             associated_comments: source::NO_COMMENT_REFERENCE,
+            is_mutable: false,
             pattern: source::pattern::MatchingPattern::Id(
               source::Id::from(PStr::LOWER_A),
               builder.int_type(),
@@ -3820,7 +6397,9 @@ return 0;"#,
                 ending_associated_comments: source::NO_COMMENT_REFERENCE,
                 expressions: vec![dummy_source_this(heap), dummy_source_this(heap)],
               },
+              binding: Default::default(),
             })),
+            else_block: None,
           },
         ))],
         expression: Some(Box::new(id_expr(PStr::LOWER_A, builder.string_type()))),
@@ -3841,6 +6420,7 @@ return (_t2: int);"#,
           source::expr::Statement::Declaration(Box::new(source::expr::DeclarationStatement {
             loc: Location::dummy(),
             associated_comments: source::NO_COMMENT_REFERENCE,
+            is_mutable: false,
             pattern: source::pattern::MatchingPattern::Id(
               source::Id::from(PStr::LOWER_A),
               builder.unit_type(),
@@ -3848,18 +6428,21 @@ return (_t2: int);"#,
             annotation: Some(annot_builder.unit_annot()),
             assigned_expression: Box::new(source::expr::E::Literal(
               source::expr::ExpressionCommon::dummy(builder.string_type()),
-              source::Literal::String(heap.alloc_str_for_test("foo")),
+              source::Literal::String(heap.alloc_str_for_test("foo"), None),
             )),
+            else_block: None,
           })),
           source::expr::Statement::Declaration(Box::new(source::expr::DeclarationStatement {
             loc: Location::dummy(),
             associated_comments: source::NO_COMMENT_REFERENCE,
+            is_mutable: false,
             pattern: source::pattern::MatchingPattern::Id(
               source::Id::from(PStr::LOWER_B),
               builder.unit_type(),
             ),
             annotation: Some(annot_builder.unit_annot()),
             assigned_expression: Box::new(id_expr(PStr::LOWER_A, builder.string_type())),
+            else_block: None,
           })),
         ],
         expression: Some(Box::new(id_expr(PStr::LOWER_B, builder.string_type()))),
@@ -3883,6 +6466,7 @@ return (_t2: int);"#,
           source::expr::DeclarationStatement {
             loc: Location::dummy(),
             associated_comments: source::NO_COMMENT_REFERENCE,
+            is_mutable: false,
             pattern: source::pattern::MatchingPattern::Id(
               source::Id::from(PStr::LOWER_A),
               builder.unit_type(),
@@ -3894,17 +6478,20 @@ return (_t2: int);"#,
                 source::expr::DeclarationStatement {
                   loc: Location::dummy(),
                   associated_comments: source::NO_COMMENT_REFERENCE,
+                  is_mutable: false,
                   pattern: source::pattern::MatchingPattern::Id(
                     source::Id::from(PStr::LOWER_A),
                     builder.unit_type(),
                   ),
                   annotation: Some(annot_builder.int_annot()),
                   assigned_expression: Box::new(dummy_source_this(heap)),
+                  else_block: None,
                 },
               ))],
               expression: Some(Box::new(id_expr(PStr::LOWER_A, builder.string_type()))),
               ending_associated_comments: source::NO_COMMENT_REFERENCE,
             })),
+            else_block: None,
           },
         ))],
         expression: Some(Box::new(id_expr(PStr::LOWER_A, builder.string_type()))),
@@ -3955,10 +6542,11 @@ return (_t2: int);"#,
         source::Toplevel::Interface(source::InterfaceDeclarationCommon {
           loc: Location::dummy(),
           associated_comments: source::NO_COMMENT_REFERENCE,
-          private: false,
+          visibility: source::Visibility::Public,
           name: source::Id::from(heap.alloc_str_for_test("I")),
           type_parameters: None,
           extends_or_implements_nodes: None,
+          derives: None,
           type_definition: (),
           members: source::InterfaceMembersCommon {
             loc: Location::dummy(),
@@ -3969,10 +6557,11 @@ return (_t2: int);"#,
         source::Toplevel::Class(source::InterfaceDeclarationCommon {
           loc: Location::dummy(),
           associated_comments: source::NO_COMMENT_REFERENCE,
-          private: false,
+          visibility: source::Visibility::Public,
           name: source::Id::from(PStr::MAIN_TYPE),
           type_parameters: None,
           extends_or_implements_nodes: None,
+          derives: None,
           type_definition: None,
           members: source::InterfaceMembersCommon {
             loc: Location::dummy(),
@@ -3981,7 +6570,7 @@ return (_t2: int);"#,
                 decl: source::ClassMemberDeclaration {
                   loc: Location::dummy(),
                   associated_comments: source::NO_COMMENT_REFERENCE,
-                  is_public: true,
+                  visibility: source::Visibility::Public,
                   is_method: false,
                   name: source::Id::from(PStr::MAIN_FN),
                   type_parameters: None,
@@ -4022,13 +6611,14 @@ return (_t2: int);"#,
                     ending_associated_comments: source::NO_COMMENT_REFERENCE,
                     expressions: Vec::new(),
                   },
+                  binding: Default::default(),
                 }),
               },
               source::ClassMemberDefinition {
                 decl: source::ClassMemberDeclaration {
                   loc: Location::dummy(),
                   associated_comments: source::NO_COMMENT_REFERENCE,
-                  is_public: true,
+                  visibility: source::Visibility::Public,
                   is_method: false,
                   name: source::Id::from(heap.alloc_str_for_test("loopy")),
                   type_parameters: Some(source::annotation::TypeParameters {
@@ -4078,6 +6668,7 @@ return (_t2: int);"#,
                     ending_associated_comments: source::NO_COMMENT_REFERENCE,
                     expressions: Vec::new(),
                   },
+                  binding: Default::default(),
                 }),
               },
             ],
@@ -4087,10 +6678,11 @@ return (_t2: int);"#,
         source::Toplevel::Class(source::InterfaceDeclarationCommon {
           loc: Location::dummy(),
           associated_comments: source::NO_COMMENT_REFERENCE,
-          private: false,
+          visibility: source::Visibility::Public,
           name: source::Id::from(heap.alloc_str_for_test("Class1")),
           type_parameters: None,
           extends_or_implements_nodes: None,
+          derives: None,
           type_definition: Some(source::TypeDefinition::Struct {
             loc: Location::dummy(),
             start_associated_comments: source::NO_COMMENT_REFERENCE,
@@ -4108,7 +6700,7 @@ return (_t2: int);"#,
                 decl: source::ClassMemberDeclaration {
                   loc: Location::dummy(),
                   associated_comments: source::NO_COMMENT_REFERENCE,
-                  is_public: true,
+                  visibility: source::Visibility::Public,
                   is_method: true,
                   name: source::Id::from(heap.alloc_str_for_test("foo")),
                   type_parameters: None,
@@ -4120,6 +6712,7 @@ return (_t2: int);"#,
                       name: source::Id::from(PStr::LOWER_A),
                       type_: (), // builder.int_type(),
                       annotation: annot_builder.int_annot(),
+                      default_value: None,
                     }]),
                   },
                   return_type: annot_builder.int_annot(),
@@ -4130,7 +6723,7 @@ return (_t2: int);"#,
                 decl: source::ClassMemberDeclaration {
                   loc: Location::dummy(),
                   associated_comments: source::NO_COMMENT_REFERENCE,
-                  is_public: true,
+                  visibility: source::Visibility::Public,
                   is_method: false,
                   name: source::Id::from(heap.alloc_str_for_test("infiniteLoop")),
                   type_parameters: None,
@@ -4171,13 +6764,14 @@ return (_t2: int);"#,
                     ending_associated_comments: source::NO_COMMENT_REFERENCE,
                     expressions: Vec::new(),
                   },
+                  binding: Default::default(),
                 }),
               },
               source::ClassMemberDefinition {
                 decl: source::ClassMemberDeclaration {
                   loc: Location::dummy(),
                   associated_comments: source::NO_COMMENT_REFERENCE,
-                  is_public: true,
+                  visibility: source::Visibility::Public,
                   is_method: false,
                   name: source::Id::from(heap.alloc_str_for_test("factorial")),
                   type_parameters: None,
@@ -4190,11 +6784,13 @@ return (_t2: int);"#,
                         name: source::Id::from(heap.alloc_str_for_test("n")),
                         type_: (), // builder.int_type(),
                         annotation: annot_builder.int_annot(),
+                        default_value: None,
                       },
                       source::AnnotatedId {
                         name: source::Id::from(heap.alloc_str_for_test("acc")),
                         type_: (), // builder.int_type(),
                         annotation: annot_builder.int_annot(),
+                        default_value: None,
                       },
                     ]),
                   },
@@ -4210,7 +6806,7 @@ return (_t2: int);"#,
                       e1: Box::new(id_expr(heap.alloc_str_for_test("n"), builder.int_type())),
                       e2: Box::new(source::expr::E::Literal(
                         source::expr::ExpressionCommon::dummy(builder.int_type()),
-                        source::Literal::Int(0),
+                        source::Literal::Int(0, None),
                       )),
                     }),
                   )),
@@ -4219,7 +6815,7 @@ return (_t2: int);"#,
                     statements: Vec::new(),
                     expression: Some(Box::new(source::expr::E::Literal(
                       source::expr::ExpressionCommon::dummy(builder.int_type()),
-                      source::Literal::Int(1),
+                      source::Literal::Int(1, None),
                     ))),
                     ending_associated_comments: source::NO_COMMENT_REFERENCE,
                   }),
@@ -4262,7 +6858,7 @@ return (_t2: int);"#,
                             e1: Box::new(id_expr(heap.alloc_str_for_test("n"), builder.int_type())),
                             e2: Box::new(source::expr::E::Literal(
                               source::expr::ExpressionCommon::dummy(builder.int_type()),
-                              source::Literal::Int(1),
+                              source::Literal::Int(1, None),
                             )),
                           }),
                           source::expr::E::Binary(source::expr::Binary {
@@ -4277,6 +6873,7 @@ return (_t2: int);"#,
                           }),
                         ],
                       },
+                      binding: Default::default(),
                     }))),
                     ending_associated_comments: source::NO_COMMENT_REFERENCE,
                   })),
@@ -4289,10 +6886,11 @@ return (_t2: int);"#,
         source::Toplevel::Class(source::InterfaceDeclarationCommon {
           loc: Location::dummy(),
           associated_comments: source::NO_COMMENT_REFERENCE,
-          private: false,
+          visibility: source::Visibility::Public,
           name: source::Id::from(heap.alloc_str_for_test("Class2")),
           type_parameters: None,
           extends_or_implements_nodes: None,
+          derives: None,
           type_definition: Some(source::TypeDefinition::Enum {
             loc: Location::dummy(),
             start_associated_comments: source::NO_COMMENT_REFERENCE,
@@ -4316,7 +6914,7 @@ return (_t2: int);"#,
         source::Toplevel::Class(source::InterfaceDeclarationCommon {
           loc: Location::dummy(),
           associated_comments: source::NO_COMMENT_REFERENCE,
-          private: false,
+          visibility: source::Visibility::Public,
           name: source::Id::from(heap.alloc_str_for_test("Class3")),
           type_parameters: Some(source::annotation::TypeParameters {
             location: Location::dummy(),
@@ -4329,6 +6927,7 @@ return (_t2: int);"#,
             }],
           }),
           extends_or_implements_nodes: None,
+          derives: None,
           type_definition: Some(source::TypeDefinition::Struct {
             loc: Location::dummy(),
             start_associated_comments: source::NO_COMMENT_REFERENCE,
@@ -4368,6 +6967,7 @@ return (_t2: int);"#,
     ]);
 
     let generics_preserved_expected = r#"closure type _$SyntheticIDType0<T> = (DUMMY_A<int>, T) -> int
+interface type DUMMY_I = []
 variant type DUMMY_Main = []
 object type DUMMY_Class1 = [int]
 variant type DUMMY_Class2 = [(Tag: [int])]
@@ -4438,8 +7038,11 @@ sources.mains = [_DUMMY_Main$main]"#;
 
     assert_eq!(
       generics_preserved_expected,
-      super::compile_sources_with_generics_preserved(heap, &sources).debug_print(heap)
+      super::compile_sources_with_generics_preserved(heap, &sources, false).debug_print(heap)
+    );
+    assert_eq!(
+      optimized_expected,
+      super::compile_sources_to_mir(heap, &sources, false).debug_print(heap)
     );
-    assert_eq!(optimized_expected, super::compile_sources_to_mir(heap, &sources).debug_print(heap));
   }
 }
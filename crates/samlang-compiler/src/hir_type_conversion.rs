@@ -93,7 +93,7 @@ fn collect_used_generic_types_visitor(
   collector: &mut OrderSet<PStr>,
 ) {
   match type_ {
-    Type::Int32 | Type::Int31 => {}
+    Type::Int32 | Type::Int31 | Type::Float => {}
     Type::Id(IdType { name, type_arguments }) => {
       if name.module_reference.is_none() && generic_types.contains(&name.type_name) {
         collector.insert(name.type_name);
@@ -121,6 +121,7 @@ pub(super) fn type_application(type_: &Type, replacement_map: &HashMap<PStr, Typ
   match type_ {
     Type::Int32 => Type::Int32,
     Type::Int31 => Type::Int31,
+    Type::Float => Type::Float,
     Type::Id(id) => {
       if id.name.module_reference.is_none() {
         replacement_map.get(&id.name.type_name).duped().unwrap()
@@ -156,6 +157,8 @@ fn fn_type_application(
 pub(super) struct TypeLoweringManager {
   pub(super) generic_types: OrderSet<PStr>,
   pub(super) type_synthesizer: TypeSynthesizer,
+  /// Used to expand type aliases that are still present in annotations.
+  pub(super) global_cx: type_::GlobalSignature,
 }
 
 impl TypeLoweringManager {
@@ -164,8 +167,17 @@ impl TypeLoweringManager {
       type_::Type::Any(reason, placeholder) => {
         panic!("any(placeholder={placeholder}) at {reason:?}")
       }
+      type_::Type::Primitive(_, type_::PrimitiveTypeKind::Float) => Type::Float,
       type_::Type::Primitive(_, _) => Type::Int32,
       type_::Type::Nominal(id) => {
+        if self
+          .global_cx
+          .get(&id.module_reference)
+          .is_some_and(|module_cx| module_cx.type_aliases.contains_key(&id.id))
+        {
+          let expanded = samlang_checker::expand_type_aliases(&self.global_cx, type_);
+          return self.lower_source_type(heap, &expanded);
+        }
         let id_string = id.id;
         Type::Id(IdType {
           name: TypeName { module_reference: Some(id.module_reference), type_name: id_string },
@@ -199,6 +211,23 @@ impl TypeLoweringManager {
         );
         Type::Id(IdType { name: closure_type_definition.name, type_arguments: type_args })
       }
+      type_::Type::Tuple(_, element_types) => {
+        let mappings =
+          element_types.iter().map(|it| self.lower_source_type(heap, it)).collect_vec();
+        let type_parameters = Vec::from_iter(
+          collect_used_generic_types(
+            &Type::new_fn_unwrapped(mappings.clone(), Type::Int32),
+            &self.generic_types,
+          )
+          .into_iter()
+          .sorted(),
+        );
+        let type_args: Arc<[_]> =
+          type_parameters.iter().map(|it| Type::new_generic_type(*it)).collect();
+        let tuple_type_definition =
+          self.type_synthesizer.synthesize_tuple_type(heap, mappings, type_parameters);
+        Type::Id(IdType { name: tuple_type_definition.name, type_arguments: type_args })
+      }
     }
   }
 
@@ -493,6 +522,7 @@ mod tests {
     TypeLoweringManager {
       generic_types: OrderSet::new(),
       type_synthesizer: TypeSynthesizer::new(),
+      global_cx: HashMap::new(),
     }
     .lower_source_type(heap, &type_::Type::Any(Reason::dummy(), true));
   }
@@ -503,6 +533,7 @@ mod tests {
     let mut manager = TypeLoweringManager {
       generic_types: OrderSet::new(),
       type_synthesizer: TypeSynthesizer::new(),
+      global_cx: HashMap::new(),
     };
     let builder = test_type_builder::create();
 
@@ -527,6 +558,7 @@ mod tests {
     let mut manager2 = TypeLoweringManager {
       generic_types: OrderSet::from([heap.alloc_str_for_test("T")]),
       type_synthesizer: manager.type_synthesizer,
+      global_cx: HashMap::new(),
     };
     assert_eq!("_$SyntheticIDType0<T>", {
       let t = builder.fun_type(
@@ -551,6 +583,7 @@ mod tests {
     let mut manager = TypeLoweringManager {
       generic_types: OrderSet::from([PStr::UPPER_A]),
       type_synthesizer: TypeSynthesizer::new(),
+      global_cx: HashMap::new(),
     };
     let annot_builder = test_builder::create();
 
@@ -610,6 +643,7 @@ mod tests {
     let mut manager = TypeLoweringManager {
       generic_types: OrderSet::from([PStr::UPPER_A]),
       type_synthesizer: TypeSynthesizer::new(),
+      global_cx: HashMap::new(),
     };
     let builder = test_type_builder::create();
 
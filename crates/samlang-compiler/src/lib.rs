@@ -37,6 +37,7 @@ pub fn compile_sources(
   heap: &mut samlang_heap::Heap,
   source_handles: std::collections::HashMap<samlang_heap::ModuleReference, String>,
   entry_module_references: Vec<samlang_heap::ModuleReference>,
+  strip_asserts: bool,
   enable_profiling: bool,
 ) -> Result<SourcesCompilationResult, String> {
   let mut error_set = samlang_errors::ErrorSet::new();
@@ -61,7 +62,7 @@ pub fn compile_sources(
     }
   }
   let checked_sources = samlang_profiling::measure_time(enable_profiling, "Type checking", || {
-    samlang_checker::type_check_sources(&parsed_sources, &mut error_set).0
+    samlang_checker::type_check_sources(&parsed_sources, heap, &mut error_set).0
   });
   let errors = error_set.pretty_print_error_messages(heap, &source_handles);
   if error_set.has_errors() {
@@ -70,7 +71,7 @@ pub fn compile_sources(
 
   let unoptimized_mir_sources =
     samlang_profiling::measure_time(enable_profiling, "Compile to MIR", || {
-      compile_sources_to_mir(heap, &checked_sources)
+      compile_sources_to_mir(heap, &checked_sources, strip_asserts)
     });
   let optimized_mir_sources =
     samlang_profiling::measure_time(enable_profiling, "Optimize MIR", || {
@@ -150,13 +151,318 @@ class HelloWorld {
     for (mod_ref, parsed) in samlang_parser::builtin_parsed_std_sources_for_tests(&mut heap) {
       sources.insert(mod_ref, parsed);
     }
-    let (checked_sources, _) = samlang_checker::type_check_sources(&sources, &mut error_set);
+    let (checked_sources, _) = samlang_checker::type_check_sources(&sources, &heap, &mut error_set);
     assert_eq!("", error_set.pretty_print_error_messages_no_frame_for_test(&heap));
-    let mir_sources = super::compile_sources_to_mir(&mut heap, &checked_sources);
+    let mir_sources = super::compile_sources_to_mir(&mut heap, &checked_sources, false);
     let lir_sources = super::compile_mir_to_lir(&mut heap, mir_sources);
     super::compile_lir_to_wasm(&mut heap, lir_sources);
   }
 
+  #[test]
+  fn global_constants_test() {
+    let mut heap = Heap::new();
+    let mut error_set = ErrorSet::new();
+    let mod_ref_lib = heap.alloc_module_reference_from_string_vec(vec!["Lib".to_string()]);
+    let lib_sources = r#"
+const Base: int = 40;
+const Answer: int = Base + 2;
+const Unused: Str = "unused";
+const Greeting: Str = "Hello " :: "World";
+"#;
+    let main_sources = r#"
+import { Answer, Greeting } from Lib;
+
+const Pair: Foo = Foo.init(Answer, Greeting);
+
+class Foo(val a: int, val b: Str) {}
+
+class Main {
+  function main(): unit = Process.println(Pair.b)
+}
+"#;
+    let mut sources = HashMap::from([
+      (
+        mod_ref_lib,
+        samlang_parser::parse_source_module_from_text(
+          lib_sources,
+          mod_ref_lib,
+          &mut heap,
+          &mut error_set,
+        ),
+      ),
+      (
+        ModuleReference::DUMMY,
+        samlang_parser::parse_source_module_from_text(
+          main_sources,
+          ModuleReference::DUMMY,
+          &mut heap,
+          &mut error_set,
+        ),
+      ),
+    ]);
+    for (mod_ref, parsed) in samlang_parser::builtin_parsed_std_sources_for_tests(&mut heap) {
+      sources.insert(mod_ref, parsed);
+    }
+    let (checked_sources, _) = samlang_checker::type_check_sources(&sources, &heap, &mut error_set);
+    assert_eq!("", error_set.pretty_print_error_messages_no_frame_for_test(&heap));
+    let mir_sources = super::compile_sources_to_mir(&mut heap, &checked_sources, false);
+    let global_lines = |code: &str, pattern: &str| {
+      code.lines().filter(|line| line.contains(pattern)).collect::<Vec<_>>().join("\n")
+    };
+    assert_eq!(
+      r#"const Lib_Base: int = _Lib_Base$init();
+const Lib_Answer: int = _Lib_Answer$init();
+const Lib_Greeting: _Str = _Lib_Greeting$init();
+const DUMMY_Pair: DUMMY_Foo = _DUMMY_Pair$init();"#,
+      global_lines(&mir_sources.debug_print(&heap), "$init();")
+    );
+    let lir_sources = super::compile_mir_to_lir(&mut heap, mir_sources);
+    let ts_code = lir_sources.pretty_print(&heap);
+    assert_eq!(
+      r#"const Lib_Base: number = _Lib_Base$init();
+const Lib_Answer: number = _Lib_Answer$init();
+const Lib_Greeting: _Str = _Lib_Greeting$init();
+const DUMMY_Pair: DUMMY_Foo = _DUMMY_Pair$init();"#,
+      global_lines(&ts_code, "$init();")
+    );
+    let (wat_code, _) = super::compile_lir_to_wasm(&mut heap, lir_sources);
+    assert_eq!(
+      r#"(global $Lib_Base (mut i32) (i32.const 0))
+(global $Lib_Answer (mut i32) (i32.const 0))
+(global $Lib_Greeting (mut (ref null $_Str)) (ref.null $_Str))"#,
+      global_lines(&wat_code, "(global $Lib_")
+    );
+    assert_eq!(
+      r#"  (global.set $Lib_Base (call $_Lib_Base$init))
+  (global.set $Lib_Answer (call $_Lib_Answer$init))
+  (global.set $Lib_Greeting (call $_Lib_Greeting$init))
+  (global.set $DUMMY_Pair (call $_DUMMY_Pair$init))"#,
+      global_lines(&wat_code, "$init))")
+    );
+  }
+
+  #[test]
+  fn default_interface_methods_test() {
+    let mut heap = Heap::new();
+    let mut error_set = ErrorSet::new();
+    let mut sources = HashMap::from([(
+      ModuleReference::DUMMY,
+      samlang_parser::parse_source_module_from_text(
+        r#"
+interface Shape {
+  method area(): int
+  method describe(): Str = Str.fromInt(this.area())
+}
+class Square(val size: int) : Shape {
+  method area(): int = this.size * this.size
+}
+class Labeled<T>(val label: T, val size: int) : Shape {
+  method area(): int = this.size
+}
+class Circle(val radius: int) : Shape {
+  method area(): int = this.radius
+  method describe(): Str = "circle"
+}
+class Main {
+  function main(): unit = {
+    Process.println(Square.init(2).describe());
+    Process.println(Labeled.init(true, 3).describe());
+    Process.println(Circle.init(1).describe());
+  }
+}
+"#,
+        ModuleReference::DUMMY,
+        &mut heap,
+        &mut error_set,
+      ),
+    )]);
+    for (mod_ref, parsed) in samlang_parser::builtin_parsed_std_sources_for_tests(&mut heap) {
+      sources.insert(mod_ref, parsed);
+    }
+    let (checked_sources, _) = samlang_checker::type_check_sources(&sources, &heap, &mut error_set);
+    assert_eq!("", error_set.pretty_print_error_messages_no_frame_for_test(&heap));
+    let mir_sources = super::compile_sources_to_mir(&mut heap, &checked_sources, false);
+    assert_eq!(
+      "function _DUMMY_Square$area(_this: DUMMY_Square): int {
+function _DUMMY_Square$describe(_this: DUMMY_Square): _Str {
+function _DUMMY_Square$init(): DUMMY_Square {
+function _DUMMY_Circle$describe(): _Str {
+function _DUMMY_Circle$init(): DUMMY_Square {
+function _DUMMY_Main$main(): int {
+function _DUMMY_Shape__DUMMY_Square$describe(_this: DUMMY_Square): _Str {
+function _DUMMY_Labeled__int$area(_this: DUMMY_Labeled__int): int {
+function _DUMMY_Labeled__int$describe(_this: DUMMY_Labeled__int): _Str {
+function _DUMMY_Labeled__int$init(): DUMMY_Labeled__int {
+function _DUMMY_Shape__DUMMY_Labeled__int$describe(_this: DUMMY_Labeled__int): _Str {",
+      mir_sources
+        .debug_print(&heap)
+        .lines()
+        .filter(|line| line.starts_with("function "))
+        .collect::<Vec<_>>()
+        .join("\n")
+    );
+    let lir_sources = super::compile_mir_to_lir(&mut heap, mir_sources);
+    super::compile_lir_to_wasm(&mut heap, lir_sources);
+  }
+
+  #[test]
+  fn interface_values_test() {
+    let mut heap = Heap::new();
+    let mut error_set = ErrorSet::new();
+    let mut sources = HashMap::from([(
+      ModuleReference::DUMMY,
+      samlang_parser::parse_source_module_from_text(
+        r#"
+interface Shape {
+  method area(): int
+  method describe(): Str = Str.fromInt(this.area())
+}
+class Square(val size: int) : Shape {
+  method area(): int = this.size * this.size
+}
+class Labeled<T>(val label: T, val size: int) : Shape {
+  method area(): int = this.size
+}
+class Main {
+  function pick(b: bool): Shape = if b { Square.init(2) } else { Labeled.init(true, 3) }
+  function main(): unit = {
+    let shapes: Vec<Shape> = Vec.empty();
+    shapes.push(Square.init(1));
+    shapes.push(Main.pick(false));
+    Process.println(shapes.get(1).describe());
+  }
+}
+"#,
+        ModuleReference::DUMMY,
+        &mut heap,
+        &mut error_set,
+      ),
+    )]);
+    for (mod_ref, parsed) in samlang_parser::builtin_parsed_std_sources_for_tests(&mut heap) {
+      sources.insert(mod_ref, parsed);
+    }
+    let (checked_sources, _) = samlang_checker::type_check_sources(&sources, &heap, &mut error_set);
+    assert_eq!("", error_set.pretty_print_error_messages_no_frame_for_test(&heap));
+    let mir_sources = super::compile_sources_to_mir(&mut heap, &checked_sources, false);
+    assert_eq!(
+      "interface type DUMMY_Shape = [() -> int, () -> _Str]
+function _DUMMY_Shape$describe(_this: DUMMY_Shape): _Str {
+function _DUMMY_Square$area(_this: DUMMY_Square): int {
+function _DUMMY_Square$describe(_this: DUMMY_Square): _Str {
+function _DUMMY_Square$init(_f0: int): DUMMY_Square {
+function _DUMMY_Main$main(): int {
+function _DUMMY_Main$pick(): DUMMY_Shape {
+function _DUMMY_Shape__DUMMY_Square$describe(_this: DUMMY_Square): _Str {
+function _DUMMY_Labeled__int$area(_this: DUMMY_Labeled__int): int {
+function _DUMMY_Labeled__int$describe(_this: DUMMY_Labeled__int): _Str {
+function _DUMMY_Labeled__int$init(): DUMMY_Labeled__int {
+function _DUMMY_Shape__DUMMY_Labeled__int$describe(_this: DUMMY_Labeled__int): _Str {",
+      mir_sources
+        .debug_print(&heap)
+        .lines()
+        .filter(|line| line.starts_with("interface type ") || line.starts_with("function "))
+        .collect::<Vec<_>>()
+        .join("\n")
+    );
+    let lir_sources = super::compile_mir_to_lir(&mut heap, mir_sources);
+    super::compile_lir_to_wasm(&mut heap, lir_sources);
+  }
+
+  #[test]
+  fn mutable_variables_test() {
+    let mut heap = Heap::new();
+    let mut error_set = ErrorSet::new();
+    let mut sources = HashMap::from([(
+      ModuleReference::DUMMY,
+      samlang_parser::parse_source_module_from_text(
+        r#"
+class Main {
+  function main(): unit = {
+    var x = 1;
+    x = x + 2;
+    x = x * 2;
+    Process.println(Str.fromInt(x));
+  }
+}
+"#,
+        ModuleReference::DUMMY,
+        &mut heap,
+        &mut error_set,
+      ),
+    )]);
+    for (mod_ref, parsed) in samlang_parser::builtin_parsed_std_sources_for_tests(&mut heap) {
+      sources.insert(mod_ref, parsed);
+    }
+    let (checked_sources, _) = samlang_checker::type_check_sources(&sources, &heap, &mut error_set);
+    assert_eq!("", error_set.pretty_print_error_messages_no_frame_for_test(&heap));
+    let mir_sources = super::compile_sources_to_mir(&mut heap, &checked_sources, false);
+    let mir_sources = samlang_optimization::optimize_sources(
+      &mut heap,
+      mir_sources,
+      &samlang_optimization::ALL_ENABLED_CONFIGURATION,
+    );
+    // Reads of `var` declarations are plain SSA values, so they can be constant folded.
+    let mir_code = mir_sources.debug_print(&heap);
+    let main_function = mir_code
+      .lines()
+      .skip_while(|line| !line.starts_with("function _DUMMY_Main$main"))
+      .take_while(|line| !line.is_empty())
+      .collect::<Vec<_>>()
+      .join("\n");
+    assert!(main_function.contains("__Str$fromInt(0 as i31, 6)"), "{main_function}");
+  }
+
+  #[test]
+  fn loop_optimization_test() {
+    let mut heap = Heap::new();
+    let mut error_set = ErrorSet::new();
+    let mut sources = HashMap::from([(
+      ModuleReference::DUMMY,
+      samlang_parser::parse_source_module_from_text(
+        r#"
+class Main {
+  function sum(n: int): int = {
+    var i = 0;
+    var s = 0;
+    while i < n {
+      s = s + i * 4;
+      i = i + 1;
+    }
+    s
+  }
+  function main(): unit = Process.println(Str.fromInt(Main.sum(10)))
+}
+"#,
+        ModuleReference::DUMMY,
+        &mut heap,
+        &mut error_set,
+      ),
+    )]);
+    for (mod_ref, parsed) in samlang_parser::builtin_parsed_std_sources_for_tests(&mut heap) {
+      sources.insert(mod_ref, parsed);
+    }
+    let (checked_sources, _) = samlang_checker::type_check_sources(&sources, &heap, &mut error_set);
+    assert_eq!("", error_set.pretty_print_error_messages_no_frame_for_test(&heap));
+    let mir_sources = super::compile_sources_to_mir(&mut heap, &checked_sources, false);
+    let mir_sources = samlang_optimization::optimize_sources(
+      &mut heap,
+      mir_sources,
+      &samlang_optimization::ALL_ENABLED_CONFIGURATION,
+    );
+    // `sum` is inlined into `main`. Both `var` declarations become loop variables, so `i * 4` is
+    // strength reduced into a derived induction variable that is incremented by 4.
+    let mir_code = mir_sources.debug_print(&heap);
+    let main_function = mir_code
+      .lines()
+      .skip_while(|line| !line.starts_with("function _DUMMY_Main$main"))
+      .take_while(|line| !line.is_empty())
+      .collect::<Vec<_>>()
+      .join("\n");
+    assert!(main_function.contains("while (true) {"), "{main_function}");
+    assert!(main_function.contains(": int) + 4;"), "{main_function}");
+    assert!(!main_function.contains("* 4"), "{main_function}");
+  }
+
   #[test]
   fn full_integration_test() {
     let heap = &mut Heap::new();
@@ -165,7 +471,7 @@ class HelloWorld {
 
     assert_eq!(
       "Invalid entry point: A does not exist.",
-      super::compile_sources(heap, std::collections::HashMap::new(), vec![mod_ref_a], false)
+      super::compile_sources(heap, std::collections::HashMap::new(), vec![mod_ref_a], false, false)
         .err()
         .unwrap()
     );
@@ -179,6 +485,7 @@ class HelloWorld {
         )]),
         vec![mod_ref_demo],
         false,
+        false,
       )
       .is_err()
     );
@@ -251,6 +558,32 @@ class Main {
     opt // 3
   }
 
+  function earlyReturn(n: int): Str = {
+    if n < 0 { return "negative" } else {};
+    let v = match Option.Some(n) { None -> return "none", Some(v) -> v };
+    Str.fromInt(v)
+  }
+
+  function counter(n: int): int = {
+    var count = 0;
+    count = count + n;
+    if count > 10 { count = count * 2; } else { count = count - 1; };
+    count
+  }
+
+  function loops(n: int): int = {
+    var sum = 0;
+    for (i in 0..n) {
+      if i > 5 { break } else {};
+      sum = sum + i;
+    }
+    var k = n;
+    while k > 0 { k = k - 2; }
+    assert(k <= 0);
+    assert(sum >= 0, "negative sum " :: Str.fromInt(sum));
+    sum + k
+  }
+
   function main(): unit = {
     let _ = Process.println(Str.fromInt(
       39 + Main.nestedVal() + FooOrFoo.intValue() - FooOrFoo.intValue()
@@ -259,6 +592,9 @@ class Main {
     let _ = Process.println(if ("hello" == "hello") { "yes" } else { "no" });
     let _ = Maybe.wrap(Maybe.None());
     let _ = Foo.init(42).getClosure()();
+    let _ = Process.println(Main.earlyReturn(-1));
+    let _ = Process.println(Str.fromInt(Main.counter(20)));
+    let _ = Process.println(Str.fromInt(Main.loops(10)));
   }
 }
 "#
@@ -266,6 +602,7 @@ class Main {
         )]),
         vec![mod_ref_demo],
         false,
+        false,
       )
       .is_ok()
     );
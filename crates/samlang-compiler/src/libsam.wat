@@ -1,11 +1,12 @@
 (import "builtins" "__Process$println" (func $__Process$println (param (ref eq)) (param (ref $_Str)) (result i32)))
 (import "builtins" "__Process$panic" (func $__Process$panic (param (ref eq)) (param (ref $_Str)) (result i32)))
+(import "builtins" "__Str$fromFloat" (func $__Str$fromFloat (param (ref eq)) (param f64) (result (ref $_Str))))
 ;; Export helper functions for JavaScript to read GC string arrays
 (func $__$strLen (export "__strLen") (param $str (ref $_Str)) (result i32)
   (array.len (local.get $str))
 )
 (func $__$strGet (export "__strGet") (param $str (ref $_Str)) (param $idx i32) (result i32)
-  (array.get_s $_Str (local.get $str) (local.get $idx))
+  (array.get_u $_Str (local.get $str) (local.get $idx))
 )
 (func $__Str$eq (param $a (ref $_Str)) (param $b (ref $_Str)) (result i32)
   (local $len i32) (local $i i32)
@@ -150,6 +151,19 @@
   )
   (ref.cast (ref $_Str) (local.get $conversion_result))
 )
+;; Export helpers for JavaScript to build GC string arrays (used by Str.fromFloat)
+(func $__$strNew (export "__strNew") (param $len i32) (result (ref $_Str))
+  (array.new $_Str (i32.const 0) (local.get $len))
+)
+(func $__$strSet (export "__strSet") (param $str (ref $_Str)) (param $idx i32) (param $c i32)
+  (array.set $_Str (local.get $str) (local.get $idx) (local.get $c))
+)
+(func $__Float$fromInt (param $this (ref eq)) (param $p0 i32) (result f64)
+  (f64.convert_i32_s (local.get $p0))
+)
+(func $__Float$toInt (param $this (ref eq)) (param $p0 f64) (result i32)
+  (i32.trunc_sat_f64_s (local.get $p0))
+)
 (func $__Str$toInt (param $p0 (ref $_Str)) (result i32)
   (local $len i32) (local $neg i32) (local $num i32) (local $character i32) (local $i i32)
   (local $l1 i32) (local $l2 i32) (local $l3 i32) (local $l4 i32) (local $l5 i32)
@@ -240,6 +254,304 @@
   (ref.as_non_null (local.get $new_array))
 )
 
+;; -----------------------------------------------------------------------------
+;; Str runtime
+;;
+;; A Str is an array of UTF-8 bytes. Indices taken and returned by Str methods
+;; count code points, so they are mapped to byte offsets by skipping UTF-8
+;; continuation bytes. trim, toUpper and toLower only look at ASCII bytes.
+;; Out of bounds indices trap, like Vec.get. compare is byte-wise, which orders
+;; UTF-8 strings by code point.
+;; -----------------------------------------------------------------------------
+
+;; Whether the byte at $i starts a code point, i.e. is not a 0b10xxxxxx byte.
+(func $__$strIsCharStart (param $s (ref $_Str)) (param $i i32) (result i32)
+  (i32.ne
+    (i32.and (array.get_u $_Str (local.get $s) (local.get $i)) (i32.const 0xC0))
+    (i32.const 0x80))
+)
+
+;; Number of code points in the first $end bytes.
+(func $__$strCountChars (param $s (ref $_Str)) (param $end i32) (result i32)
+  (local $i i32) (local $count i32)
+  (block $done
+    (loop $loop
+      (br_if $done (i32.ge_u (local.get $i) (local.get $end)))
+      (local.set $count
+        (i32.add (local.get $count) (call $__$strIsCharStart (local.get $s) (local.get $i))))
+      (local.set $i (i32.add (local.get $i) (i32.const 1)))
+      (br $loop)
+    )
+  )
+  (local.get $count)
+)
+
+;; Byte offset of the code point at $index, where the length maps to the byte
+;; length. Returns -1 when $index is out of range.
+(func $__$strByteOffset (param $s (ref $_Str)) (param $index i32) (result i32)
+  (local $i i32) (local $len i32) (local $count i32)
+  (if (i32.lt_s (local.get $index) (i32.const 0)) (then (return (i32.const -1))))
+  (local.set $len (array.len (local.get $s)))
+  (block $done
+    (loop $loop
+      (br_if $done (i32.ge_u (local.get $i) (local.get $len)))
+      (if (call $__$strIsCharStart (local.get $s) (local.get $i))
+        (then
+          (if (i32.eq (local.get $count) (local.get $index)) (then (return (local.get $i))))
+          (local.set $count (i32.add (local.get $count) (i32.const 1)))))
+      (local.set $i (i32.add (local.get $i) (i32.const 1)))
+      (br $loop)
+    )
+  )
+  (select (local.get $len) (i32.const -1) (i32.eq (local.get $count) (local.get $index)))
+)
+
+;; New Str with the bytes in [$start, $end).
+(func $__$strSlice (param $s (ref $_Str)) (param $start i32) (param $end i32) (result (ref $_Str))
+  (local $result (ref $_Str))
+  (local.set $result
+    (array.new $_Str (i32.const 0) (i32.sub (local.get $end) (local.get $start))))
+  (array.copy $_Str $_Str
+    (local.get $result) (i32.const 0)
+    (local.get $s) (local.get $start)
+    (i32.sub (local.get $end) (local.get $start)))
+  (local.get $result)
+)
+
+;; Whether $needle occurs in $s at byte offset $at.
+(func $__$strMatchesAt (param $s (ref $_Str)) (param $needle (ref $_Str)) (param $at i32) (result i32)
+  (local $i i32) (local $len i32)
+  (local.set $len (array.len (local.get $needle)))
+  (if (i32.gt_u (i32.add (local.get $at) (local.get $len)) (array.len (local.get $s)))
+    (then (return (i32.const 0))))
+  (block $done
+    (loop $loop
+      (br_if $done (i32.ge_u (local.get $i) (local.get $len)))
+      (if (i32.ne
+        (array.get_u $_Str (local.get $s) (i32.add (local.get $at) (local.get $i)))
+        (array.get_u $_Str (local.get $needle) (local.get $i))
+      ) (then (return (i32.const 0))))
+      (local.set $i (i32.add (local.get $i) (i32.const 1)))
+      (br $loop)
+    )
+  )
+  (i32.const 1)
+)
+
+;; Byte offset of the first $needle at or after byte offset $from, or -1. A match
+;; of valid UTF-8 always starts on a code point boundary.
+(func $__$strFind (param $s (ref $_Str)) (param $needle (ref $_Str)) (param $from i32) (result i32)
+  (local $i i32) (local $last i32)
+  (local.set $i (local.get $from))
+  (local.set $last (i32.sub (array.len (local.get $s)) (array.len (local.get $needle))))
+  (block $done
+    (loop $loop
+      (br_if $done (i32.gt_s (local.get $i) (local.get $last)))
+      (if (call $__$strMatchesAt (local.get $s) (local.get $needle) (local.get $i))
+        (then (return (local.get $i))))
+      (local.set $i (i32.add (local.get $i) (i32.const 1)))
+      (br $loop)
+    )
+  )
+  (i32.const -1)
+)
+
+;; Space, \t, \n, \v, \f and \r.
+(func $__$isAsciiWhitespace (param $c i32) (result i32)
+  (i32.or
+    (i32.eq (local.get $c) (i32.const 32))
+    (i32.lt_u (i32.sub (local.get $c) (i32.const 9)) (i32.const 5)))
+)
+
+;; Copy of $s with $delta added to every byte in [$from, $to].
+(func $__$strMapAsciiRange (param $s (ref $_Str)) (param $from i32) (param $to i32) (param $delta i32) (result (ref $_Str))
+  (local $result (ref $_Str)) (local $i i32) (local $c i32)
+  (local.set $result (call $__$strSlice (local.get $s) (i32.const 0) (array.len (local.get $s))))
+  (block $done
+    (loop $loop
+      (br_if $done (i32.ge_u (local.get $i) (array.len (local.get $result))))
+      (local.set $c (array.get_u $_Str (local.get $result) (local.get $i)))
+      (if (i32.le_u
+        (i32.sub (local.get $c) (local.get $from))
+        (i32.sub (local.get $to) (local.get $from))
+      ) (then
+        (array.set $_Str (local.get $result) (local.get $i)
+          (i32.add (local.get $c) (local.get $delta)))))
+      (local.set $i (i32.add (local.get $i) (i32.const 1)))
+      (br $loop)
+    )
+  )
+  (local.get $result)
+)
+
+(func $__Str$length (param $this (ref $_Str)) (result i32)
+  (call $__$strCountChars (local.get $this) (array.len (local.get $this)))
+)
+
+(func $__Str$byteLength (param $this (ref $_Str)) (result i32)
+  (array.len (local.get $this))
+)
+
+(func $__Str$substring (param $this (ref $_Str)) (param $start i32) (param $end i32) (result (ref $_Str))
+  (local $start_offset i32) (local $end_offset i32)
+  (if (i32.gt_s (local.get $start) (local.get $end)) (then (unreachable)))
+  (local.set $start_offset (call $__$strByteOffset (local.get $this) (local.get $start)))
+  (local.set $end_offset (call $__$strByteOffset (local.get $this) (local.get $end)))
+  (if (i32.lt_s (local.get $start_offset) (i32.const 0)) (then (unreachable)))
+  (if (i32.lt_s (local.get $end_offset) (i32.const 0)) (then (unreachable)))
+  (call $__$strSlice (local.get $this) (local.get $start_offset) (local.get $end_offset))
+)
+
+(func $__Str$charAt (param $this (ref $_Str)) (param $index i32) (result (ref $_Str))
+  (call $__Str$substring
+    (local.get $this) (local.get $index) (i32.add (local.get $index) (i32.const 1)))
+)
+
+(func $__Str$codePointAt (param $this (ref $_Str)) (param $index i32) (result i32)
+  (local $offset i32) (local $lead i32) (local $count i32) (local $code_point i32)
+  (local.set $offset (call $__$strByteOffset (local.get $this) (local.get $index)))
+  (if (i32.lt_s (local.get $offset) (i32.const 0)) (then (unreachable)))
+  (if (i32.ge_s (local.get $offset) (array.len (local.get $this))) (then (unreachable)))
+  ;; The lead byte gives the number of continuation bytes and the highest bits.
+  (local.set $lead (array.get_u $_Str (local.get $this) (local.get $offset)))
+  (if (i32.lt_u (local.get $lead) (i32.const 0x80)) (then (return (local.get $lead))))
+  (if (i32.lt_u (local.get $lead) (i32.const 0xE0))
+    (then
+      (local.set $count (i32.const 1))
+      (local.set $code_point (i32.and (local.get $lead) (i32.const 0x1F))))
+    (else
+      (if (i32.lt_u (local.get $lead) (i32.const 0xF0))
+        (then
+          (local.set $count (i32.const 2))
+          (local.set $code_point (i32.and (local.get $lead) (i32.const 0x0F))))
+        (else
+          (local.set $count (i32.const 3))
+          (local.set $code_point (i32.and (local.get $lead) (i32.const 0x07)))))))
+  (block $done
+    (loop $loop
+      (br_if $done (i32.eqz (local.get $count)))
+      (local.set $offset (i32.add (local.get $offset) (i32.const 1)))
+      (local.set $code_point
+        (i32.or
+          (i32.shl (local.get $code_point) (i32.const 6))
+          (i32.and
+            (array.get_u $_Str (local.get $this) (local.get $offset))
+            (i32.const 0x3F))))
+      (local.set $count (i32.sub (local.get $count) (i32.const 1)))
+      (br $loop)
+    )
+  )
+  (local.get $code_point)
+)
+
+(func $__Str$indexOf (param $this (ref $_Str)) (param $needle (ref $_Str)) (result i32)
+  (local $offset i32)
+  (local.set $offset (call $__$strFind (local.get $this) (local.get $needle) (i32.const 0)))
+  (if (result i32) (i32.lt_s (local.get $offset) (i32.const 0))
+    (then (i32.const -1))
+    (else (call $__$strCountChars (local.get $this) (local.get $offset))))
+)
+
+;; An empty separator splits the string into its code points.
+(func $__Str$split (param $this (ref $_Str)) (param $separator (ref $_Str)) (result (ref $_Vec))
+  (local $result (ref $_Vec)) (local $len i32) (local $sep_len i32)
+  (local $start i32) (local $end i32)
+  (local.set $result (call $__Vec$empty (ref.i31 (i32.const 0))))
+  (local.set $len (array.len (local.get $this)))
+  (local.set $sep_len (array.len (local.get $separator)))
+  (if (i32.eqz (local.get $sep_len))
+    (then
+      (block $done
+        (loop $loop
+          (br_if $done (i32.ge_u (local.get $start) (local.get $len)))
+          (local.set $end (i32.add (local.get $start) (i32.const 1)))
+          (block $char_end
+            (loop $skip
+              (br_if $char_end (i32.ge_u (local.get $end) (local.get $len)))
+              (br_if $char_end (call $__$strIsCharStart (local.get $this) (local.get $end)))
+              (local.set $end (i32.add (local.get $end) (i32.const 1)))
+              (br $skip)
+            )
+          )
+          (drop (call $__Vec$push (local.get $result)
+            (call $__$strSlice (local.get $this) (local.get $start) (local.get $end))))
+          (local.set $start (local.get $end))
+          (br $loop)
+        )
+      )
+      (return (local.get $result))))
+  (block $done
+    (loop $loop
+      (local.set $end (call $__$strFind (local.get $this) (local.get $separator) (local.get $start)))
+      (br_if $done (i32.lt_s (local.get $end) (i32.const 0)))
+      (drop (call $__Vec$push (local.get $result)
+        (call $__$strSlice (local.get $this) (local.get $start) (local.get $end))))
+      (local.set $start (i32.add (local.get $end) (local.get $sep_len)))
+      (br $loop)
+    )
+  )
+  (drop (call $__Vec$push (local.get $result)
+    (call $__$strSlice (local.get $this) (local.get $start) (local.get $len))))
+  (local.get $result)
+)
+
+(func $__Str$trim (param $this (ref $_Str)) (result (ref $_Str))
+  (local $start i32) (local $end i32)
+  (local.set $end (array.len (local.get $this)))
+  (block $done
+    (loop $loop
+      (br_if $done (i32.ge_u (local.get $start) (local.get $end)))
+      (br_if $done (i32.eqz (call $__$isAsciiWhitespace
+        (array.get_u $_Str (local.get $this) (local.get $start)))))
+      (local.set $start (i32.add (local.get $start) (i32.const 1)))
+      (br $loop)
+    )
+  )
+  (block $done
+    (loop $loop
+      (br_if $done (i32.le_u (local.get $end) (local.get $start)))
+      (br_if $done (i32.eqz (call $__$isAsciiWhitespace
+        (array.get_u $_Str (local.get $this) (i32.sub (local.get $end) (i32.const 1))))))
+      (local.set $end (i32.sub (local.get $end) (i32.const 1)))
+      (br $loop)
+    )
+  )
+  (call $__$strSlice (local.get $this) (local.get $start) (local.get $end))
+)
+
+(func $__Str$startsWith (param $this (ref $_Str)) (param $prefix (ref $_Str)) (result i32)
+  (call $__$strMatchesAt (local.get $this) (local.get $prefix) (i32.const 0))
+)
+
+(func $__Str$compare (param $this (ref $_Str)) (param $other (ref $_Str)) (result i32)
+  (local $i i32) (local $len i32) (local $a i32) (local $b i32)
+  (local.set $len (array.len (local.get $this)))
+  (if (i32.lt_u (array.len (local.get $other)) (local.get $len))
+    (then (local.set $len (array.len (local.get $other)))))
+  (block $done
+    (loop $loop
+      (br_if $done (i32.ge_u (local.get $i) (local.get $len)))
+      (local.set $a (array.get_u $_Str (local.get $this) (local.get $i)))
+      (local.set $b (array.get_u $_Str (local.get $other) (local.get $i)))
+      (if (i32.ne (local.get $a) (local.get $b))
+        (then (return (select (i32.const -1) (i32.const 1) (i32.lt_u (local.get $a) (local.get $b))))))
+      (local.set $i (i32.add (local.get $i) (i32.const 1)))
+      (br $loop)
+    )
+  )
+  (i32.sub
+    (i32.gt_u (array.len (local.get $this)) (array.len (local.get $other)))
+    (i32.lt_u (array.len (local.get $this)) (array.len (local.get $other))))
+)
+
+(func $__Str$toUpper (param $this (ref $_Str)) (result (ref $_Str))
+  (call $__$strMapAsciiRange (local.get $this) (i32.const 97) (i32.const 122) (i32.const -32))
+)
+
+(func $__Str$toLower (param $this (ref $_Str)) (result (ref $_Str))
+  (call $__$strMapAsciiRange (local.get $this) (i32.const 65) (i32.const 90) (i32.const 32))
+)
+
 ;; -----------------------------------------------------------------------------
 ;; Vec<T> runtime
 ;;
@@ -257,6 +569,14 @@
   (i31.get_s (ref.cast (ref i31) (local.get $v)))
 )
 
+(func $__$wrapF64 (param $v f64) (result (ref eq))
+  (struct.new $_Float (local.get $v))
+)
+
+(func $__$unwrapF64 (param $v (ref eq)) (result f64)
+  (struct.get $_Float 0 (ref.cast (ref $_Float) (local.get $v)))
+)
+
 (func $__Vec$empty (param $_this (ref eq)) (result (ref $_Vec))
   (struct.new $_Vec (array.new $_VecData (ref.null eq) (i32.const 0)) (i32.const 0))
 )
@@ -11,6 +11,7 @@ fn lower_type(type_: mir::Type, types_needing_any_pointer: &TypesNeedingAnyPoint
   match type_ {
     mir::Type::Int32 => lir::Type::Int32,
     mir::Type::Int31 => lir::Type::Int31,
+    mir::Type::Float => lir::Type::Float,
     mir::Type::Id(name) => {
       // Enum types with Int31 variants need to use AnyPointer in WASM GC
       // because a variable can hold either a struct reference or ref.i31
@@ -36,35 +37,75 @@ fn lower_fn_type(
   }
 }
 
+fn lower_global_constant(
+  mir::GlobalConstant { initializer, type_ }: mir::GlobalConstant,
+  types_needing_any_pointer: &TypesNeedingAnyPointer,
+) -> lir::GlobalConstant {
+  lir::GlobalConstant { initializer, type_: lower_type(type_, types_needing_any_pointer) }
+}
+
+fn lower_expression_type(type_: mir::Type) -> lir::Type {
+  match type_ {
+    mir::Type::Int32 => lir::Type::Int32,
+    mir::Type::Int31 => lir::Type::Int31,
+    mir::Type::Float => lir::Type::Float,
+    mir::Type::Id(name) => lir::Type::Id(name),
+  }
+}
+
 fn lower_expression(expr: mir::Expression) -> lir::Expression {
   match expr {
     mir::Expression::Int32Literal(i) => lir::Expression::Int32Literal(i),
     mir::Expression::Int31Literal(i) => lir::Expression::Int31Literal(i),
+    mir::Expression::FloatLiteral(f) => lir::Expression::FloatLiteral(f),
     mir::Expression::StringName(n) => lir::Expression::StringName(n),
+    mir::Expression::GlobalConstant(mir::GlobalConstant { initializer, type_ }) => {
+      lir::Expression::GlobalConstant(lir::GlobalConstant {
+        initializer,
+        type_: lower_expression_type(type_),
+      })
+    }
     mir::Expression::Variable(mir::VariableName { name, type_ }) => {
-      let lir_type = match type_ {
-        mir::Type::Int32 => lir::Type::Int32,
-        mir::Type::Int31 => lir::Type::Int31,
-        mir::Type::Id(name) => lir::Type::Id(name),
-      };
-      lir::Expression::Variable(name, lir_type)
+      lir::Expression::Variable(name, lower_expression_type(type_))
     }
   }
 }
 
+/// An interface value is a `[vtable, context]` struct. The vtable holds type-erased methods.
+struct InterfaceLowering {
+  vtable_type_name: mir::TypeNameId,
+  method_types: Vec<lir::FunctionType>,
+}
+
+/// Vtables are shared global constants, keyed by the name of their initializer.
+type VTables = BTreeMap<mir::FunctionName, (mir::TypeNameId, Vec<mir::FunctionNameExpression>)>;
+
 struct LoweringManager<'a> {
   heap: &'a mut Heap,
+  symbol_table: &'a mut mir::SymbolTable,
   closure_defs: &'a BTreeMap<mir::TypeNameId, lir::FunctionType>,
+  interface_defs: &'a BTreeMap<mir::TypeNameId, InterfaceLowering>,
+  vtables: &'a mut VTables,
   types_needing_any_pointer: &'a TypesNeedingAnyPointer,
 }
 
 impl<'a> LoweringManager<'a> {
   fn new(
     heap: &'a mut Heap,
+    symbol_table: &'a mut mir::SymbolTable,
     closure_defs: &'a BTreeMap<mir::TypeNameId, lir::FunctionType>,
+    interface_defs: &'a BTreeMap<mir::TypeNameId, InterfaceLowering>,
+    vtables: &'a mut VTables,
     types_needing_any_pointer: &'a TypesNeedingAnyPointer,
   ) -> LoweringManager<'a> {
-    LoweringManager { heap, closure_defs, types_needing_any_pointer }
+    LoweringManager {
+      heap,
+      symbol_table,
+      closure_defs,
+      interface_defs,
+      vtables,
+      types_needing_any_pointer,
+    }
   }
 
   fn lower_type(&self, type_: mir::Type) -> lir::Type {
@@ -189,6 +230,44 @@ impl<'a> LoweringManager<'a> {
               return_collector,
             });
           }
+          mir::Callee::InterfaceMethod { receiver, index } => {
+            let temp_vtable = self.heap.alloc_temp_str();
+            let temp_fn = self.heap.alloc_temp_str();
+            let temp_cx = self.heap.alloc_temp_str();
+            let interface_type_name = receiver.type_().into_id().unwrap();
+            let InterfaceLowering { vtable_type_name, method_types } =
+              self.interface_defs.get(&interface_type_name).unwrap();
+            let vtable_type = lir::Type::Id(*vtable_type_name);
+            let fn_type = lir::Type::Fn(method_types[index].clone());
+            let pointer_expr = self.lower_expression(receiver);
+            statements.push(lir::Statement::IndexedAccess {
+              name: temp_vtable,
+              type_: vtable_type.clone(),
+              pointer_expression: pointer_expr.clone(),
+              index: 0,
+            });
+            statements.push(lir::Statement::IndexedAccess {
+              name: temp_fn,
+              type_: fn_type.clone(),
+              pointer_expression: lir::Expression::Variable(temp_vtable, vtable_type),
+              index,
+            });
+            statements.push(lir::Statement::IndexedAccess {
+              name: temp_cx,
+              type_: lir::ANY_POINTER_TYPE,
+              pointer_expression: pointer_expr,
+              index: 1,
+            });
+            statements.push(lir::Statement::Call {
+              callee: lir::Expression::Variable(temp_fn, fn_type),
+              arguments: vec![lir::Expression::Variable(temp_cx, lir::ANY_POINTER_TYPE)]
+                .into_iter()
+                .chain(arguments.into_iter().map(|e| self.lower_expression(e)))
+                .collect(),
+              return_type: lowered_return_type,
+              return_collector,
+            });
+          }
         }
         statements
       }
@@ -214,6 +293,7 @@ impl<'a> LoweringManager<'a> {
         }]
       }
       mir::Statement::Break(e) => vec![lir::Statement::Break(self.lower_expression(e))],
+      mir::Statement::Return(e) => vec![lir::Statement::Return(self.lower_expression(e))],
       mir::Statement::While { loop_variables, statements, break_collector } => {
         let loop_variables = loop_variables
           .into_iter()
@@ -301,10 +381,93 @@ impl<'a> LoweringManager<'a> {
         });
         statements
       }
+      mir::Statement::InterfaceInit {
+        interface_variable_name,
+        interface_type_name,
+        vtable,
+        context,
+      } => {
+        let vtable_type_name =
+          self.interface_defs.get(&interface_type_name).unwrap().vtable_type_name;
+        let (_, mut suffix) = self.symbol_table.split_type_name_suffix(vtable_type_name);
+        if let Some(first) = vtable.first() {
+          suffix.push(mir::Type::Id(first.name.type_name));
+        }
+        let initializer = mir::FunctionName {
+          type_name: self.symbol_table.derived_type_name_with_suffix(vtable_type_name, suffix),
+          fn_name: PStr::INIT,
+        };
+        self.vtables.entry(initializer).or_insert((vtable_type_name, vtable));
+        let mut statements = Vec::new();
+        let context = self.lower_expression(context);
+        let cx_slot = {
+          let temp = self.heap.alloc_temp_str();
+          statements.push(lir::Statement::Cast {
+            name: temp,
+            type_: lir::ANY_POINTER_TYPE,
+            assigned_expression: context,
+          });
+          lir::Expression::Variable(temp, lir::ANY_POINTER_TYPE)
+        };
+        let vtable_slot = lir::Expression::GlobalConstant(lir::GlobalConstant {
+          initializer,
+          type_: lir::Type::Id(vtable_type_name),
+        });
+        statements.push(lir::Statement::StructInit {
+          struct_variable_name: interface_variable_name,
+          type_: self.lower_type(mir::Type::Id(interface_type_name)),
+          expression_list: vec![vtable_slot, cx_slot],
+        });
+        statements
+      }
     }
   }
 }
 
+/// The initializer of a vtable global, which fills the vtable with its type-erased methods.
+fn lower_vtable_initializer(
+  heap: &mut Heap,
+  initializer: mir::FunctionName,
+  vtable_type_name: mir::TypeNameId,
+  method_types: &[lir::FunctionType],
+  vtable: Vec<mir::FunctionNameExpression>,
+  types_needing_any_pointer: &TypesNeedingAnyPointer,
+) -> lir::Function {
+  let mut body = Vec::new();
+  let mut expression_list = Vec::new();
+  for (mir::FunctionNameExpression { name, type_ }, method_type) in
+    vtable.into_iter().zip(method_types)
+  {
+    let temp = heap.alloc_temp_str();
+    body.push(lir::Statement::Cast {
+      name: temp,
+      type_: lir::Type::Fn(method_type.clone()),
+      assigned_expression: lir::Expression::FnName(
+        name,
+        lower_fn_type(type_, types_needing_any_pointer),
+      ),
+    });
+    expression_list.push(lir::Expression::Variable(temp, lir::Type::Fn(method_type.clone())));
+  }
+  let vtable_type = lir::Type::Id(vtable_type_name);
+  let vtable_variable = heap.alloc_temp_str();
+  body.push(lir::Statement::StructInit {
+    struct_variable_name: vtable_variable,
+    type_: vtable_type.clone(),
+    expression_list,
+  });
+  lir::Function {
+    name: initializer,
+    parameters: Vec::new(),
+    type_: lir::FunctionType {
+      argument_types: Vec::new(),
+      return_type: Box::new(vtable_type.clone()),
+    },
+    body,
+    return_value: lir::Expression::Variable(vtable_variable, vtable_type),
+  }
+}
+
 pub fn compile_mir_to_lir(heap: &mut Heap, sources: mir::Sources) -> lir::Sources {
   let mut type_defs = Vec::new();
   let mut closure_def_map = BTreeMap::new();
@@ -312,8 +475,10 @@ pub fn compile_mir_to_lir(heap: &mut Heap, sources: mir::Sources) -> lir::Source
   let mir::Sources {
     mut symbol_table,
     global_variables,
+    global_constants,
     type_definitions,
     closure_types,
+    interface_types,
     main_function_names,
     functions,
   } = sources;
@@ -346,6 +511,37 @@ pub fn compile_mir_to_lir(heap: &mut Heap, sources: mir::Sources) -> lir::Source
     });
     closure_def_map.insert(name, fn_type);
   }
+  let mut interface_def_map = BTreeMap::new();
+  for mir::InterfaceTypeDefinition { name, methods } in interface_types {
+    let vtable_type_name = symbol_table.derived_vtable_type_name(heap, name);
+    let method_types = methods
+      .into_iter()
+      .map(|method| {
+        let lir::FunctionType { argument_types, return_type } =
+          lower_fn_type(method, &types_needing_any_pointer);
+        lir::FunctionType {
+          argument_types: vec![lir::ANY_POINTER_TYPE]
+            .into_iter()
+            .chain(argument_types)
+            .collect_vec(),
+          return_type,
+        }
+      })
+      .collect_vec();
+    type_defs.push(lir::TypeDefinition {
+      name: vtable_type_name,
+      parent_type: None,
+      is_extensible: false,
+      mappings: method_types.iter().cloned().map(lir::Type::Fn).collect(),
+    });
+    type_defs.push(lir::TypeDefinition {
+      name,
+      parent_type: None,
+      is_extensible: false,
+      mappings: vec![lir::Type::Id(vtable_type_name), lir::ANY_POINTER_TYPE],
+    });
+    interface_def_map.insert(name, InterfaceLowering { vtable_type_name, method_types });
+  }
   for type_def in type_definitions {
     match &type_def.mappings {
       mir::TypeDefinitionMappings::Struct(types) => {
@@ -387,15 +583,44 @@ pub fn compile_mir_to_lir(heap: &mut Heap, sources: mir::Sources) -> lir::Source
       }
     }
   }
-  let functions = functions
+  let mut vtables = BTreeMap::new();
+  let mut functions = functions
     .into_iter()
     .map(|f| {
-      LoweringManager::new(heap, &closure_def_map, &types_needing_any_pointer).lower_function(f)
+      LoweringManager::new(
+        heap,
+        &mut symbol_table,
+        &closure_def_map,
+        &interface_def_map,
+        &mut vtables,
+        &types_needing_any_pointer,
+      )
+      .lower_function(f)
     })
     .collect_vec();
+  // Vtables come first, since user constants can be initialized with interface values.
+  let mut lir_global_constants = Vec::new();
+  for (initializer, (vtable_type_name, vtable)) in vtables {
+    let interface_lowering =
+      interface_def_map.values().find(|it| it.vtable_type_name == vtable_type_name).unwrap();
+    functions.push(lower_vtable_initializer(
+      heap,
+      initializer,
+      vtable_type_name,
+      &interface_lowering.method_types,
+      vtable,
+      &types_needing_any_pointer,
+    ));
+    lir_global_constants
+      .push(lir::GlobalConstant { initializer, type_: lir::Type::Id(vtable_type_name) });
+  }
+  lir_global_constants.extend(
+    global_constants.into_iter().map(|g| lower_global_constant(g, &types_needing_any_pointer)),
+  );
   lir_unused_name_elimination::optimize_lir_sources_by_eliminating_unused_ones(lir::Sources {
     symbol_table,
     global_variables,
+    global_constants: lir_global_constants,
     type_definitions: type_defs,
     main_function_names,
     functions,
@@ -410,8 +635,8 @@ mod tests {
     mir::{
       Callee, ClosureTypeDefinition, EnumTypeDefinition, Expression, Function, FunctionName,
       FunctionNameExpression, GenenalLoopVariable, INT_31_TYPE, INT_32_TYPE, IfElseFinalAssignment,
-      ONE, Sources, Statement, SymbolTable, Type, TypeDefinition, TypeDefinitionMappings,
-      TypeNameId, VariableName, ZERO,
+      InterfaceTypeDefinition, ONE, Sources, Statement, SymbolTable, Type, TypeDefinition,
+      TypeDefinitionMappings, TypeNameId, VariableName, ZERO,
     },
   };
   use samlang_heap::{Heap, PStr};
@@ -490,6 +715,7 @@ mod tests {
     let mut table = SymbolTable::new();
     let lir_sources = lir::Sources {
       global_variables: Vec::new(),
+      global_constants: Vec::new(),
       type_definitions: vec![
         lir::TypeDefinition {
           name: TypeNameId::STR,
@@ -523,7 +749,9 @@ mod tests {
       Sources {
         symbol_table: SymbolTable::new(),
         global_variables: Vec::new(),
+        global_constants: Vec::new(),
         closure_types: Vec::new(),
+        interface_types: Vec::new(),
         type_definitions: Vec::new(),
         main_function_names: Vec::new(),
         functions: Vec::new(),
@@ -544,10 +772,12 @@ mod tests {
       Type::Id(table.create_type_name_for_test(heap.alloc_str_for_test("Variant")));
     let sources = Sources {
       global_variables: vec![hir::GlobalString(heap.alloc_str_for_test("G1"))],
+      global_constants: Vec::new(),
       closure_types: vec![ClosureTypeDefinition {
         name: table.create_type_name_for_test(heap.alloc_str_for_test("CC")),
         function_type: Type::new_fn_unwrapped(vec![INT_32_TYPE], INT_32_TYPE),
       }],
+      interface_types: Vec::new(),
       type_definitions: vec![
         TypeDefinition {
           name: table.create_type_name_for_test(heap.alloc_str_for_test("Object")),
@@ -876,6 +1106,94 @@ function __$compiled_program_main(): number {{
   }}
   return 0;
 }}
+"#,
+      lir::ts_prolog(),
+    );
+    assert_lowered(sources, heap, &expected);
+  }
+
+  #[test]
+  fn interface_test() {
+    let heap = &mut Heap::new();
+    let mut table = SymbolTable::new();
+
+    let interface_type_name = table.create_type_name_for_test(PStr::UPPER_I);
+    let interface_type = Type::Id(interface_type_name);
+    let obj_type = Type::Id(table.create_type_name_for_test(PStr::UPPER_A));
+    let method_type = Type::new_fn_unwrapped(vec![obj_type, INT_32_TYPE], INT_32_TYPE);
+    let method_name = FunctionName::new_for_test(PStr::LOWER_M);
+    let sources = Sources {
+      global_variables: Vec::new(),
+      global_constants: Vec::new(),
+      closure_types: Vec::new(),
+      interface_types: vec![InterfaceTypeDefinition {
+        name: interface_type_name,
+        methods: vec![Type::new_fn_unwrapped(vec![INT_32_TYPE], INT_32_TYPE)],
+      }],
+      type_definitions: vec![TypeDefinition {
+        name: table.create_type_name_for_test(PStr::UPPER_A),
+        mappings: TypeDefinitionMappings::Struct(vec![INT_32_TYPE]),
+      }],
+      main_function_names: vec![FunctionName::new_for_test(PStr::MAIN_FN)],
+      functions: vec![
+        Function {
+          name: FunctionName::new_for_test(PStr::MAIN_FN),
+          parameters: vec![PStr::LOWER_A],
+          type_: Type::new_fn_unwrapped(vec![obj_type], INT_32_TYPE),
+          body: vec![
+            Statement::InterfaceInit {
+              interface_variable_name: PStr::LOWER_B,
+              interface_type_name,
+              vtable: vec![FunctionNameExpression {
+                name: method_name,
+                type_: method_type.clone(),
+              }],
+              context: Expression::var_name(PStr::LOWER_A, obj_type),
+            },
+            Statement::Call {
+              callee: Callee::InterfaceMethod {
+                receiver: Expression::var_name(PStr::LOWER_B, interface_type),
+                index: 0,
+              },
+              arguments: vec![ONE],
+              return_type: INT_32_TYPE,
+              return_collector: Some(PStr::LOWER_C),
+            },
+          ],
+          return_value: Expression::var_name(PStr::LOWER_C, INT_32_TYPE),
+        },
+        Function {
+          name: method_name,
+          parameters: vec![PStr::UNDERSCORE_THIS, PStr::LOWER_X],
+          type_: method_type,
+          body: Vec::new(),
+          return_value: Expression::var_name(PStr::LOWER_X, INT_32_TYPE),
+        },
+      ],
+      symbol_table: table,
+    };
+    let expected = format!(
+      r#"{}type _I$VTable = [(t0: any, t1: number) => number];
+type _I = [_I$VTable, any];
+type _A = [number];
+function __$main(a: _A): number {{
+  let _t0 = a as unknown as any;
+  let b: _I = [_I$VTable___, _t0];
+  let _t1: _I$VTable = b[0];
+  let _t2: (t0: any, t1: number) => number = _t1[0];
+  let _t3: any = b[1];
+  let c: number = _t2(_t3, 1);
+  return c;
+}}
+function __$m(_this: any, x: number): number {{
+  return x;
+}}
+function __I$VTable___$init(): _I$VTable {{
+  let _t4 = __$m as unknown as (t0: any, t1: number) => number;
+  let _t5: _I$VTable = [_t4];
+  return _t5;
+}}
+const _I$VTable___: _I$VTable = __I$VTable___$init();
 "#,
       lir::ts_prolog(),
     );
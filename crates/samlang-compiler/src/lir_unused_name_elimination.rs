@@ -12,7 +12,7 @@ fn collect_for_type_set(type_: &Type, type_set: &mut HashSet<TypeNameId>) {
       type_set.insert(*n);
     }
     Type::Fn(f) => collect_for_type_set_from_fn_type(f, type_set),
-    Type::Int32 | Type::Int31 | Type::AnyPointer => (),
+    Type::Int32 | Type::Int31 | Type::Float | Type::AnyPointer => (),
   }
 }
 fn collect_for_type_set_from_fn_type(type_: &FunctionType, type_set: &mut HashSet<TypeNameId>) {
@@ -29,11 +29,12 @@ fn collect_used_names_from_expression(
   expression: &Expression,
 ) {
   match expression {
-    Expression::Int32Literal(_) | Expression::Int31Literal(_) => {}
+    Expression::Int32Literal(_) | Expression::Int31Literal(_) | Expression::FloatLiteral(_) => {}
     Expression::Variable(_, t) => collect_for_type_set(t, type_set),
     Expression::StringName(n) => {
       str_name_set.insert(*n);
     }
+    Expression::GlobalConstant(g) => collect_for_type_set(&g.type_, type_set),
     Expression::FnName(n, t) => {
       fn_name_set.insert(*n);
       type_set.insert(n.type_name);
@@ -85,7 +86,7 @@ fn collect_used_names_from_statement(
       collect_used_names_from_expression(str_name_set, fn_name_set, type_set, condition);
       collect_used_names_from_statements(str_name_set, fn_name_set, type_set, statements);
     }
-    Statement::Break(e) => {
+    Statement::Break(e) | Statement::Return(e) => {
       collect_used_names_from_expression(str_name_set, fn_name_set, type_set, e)
     }
     Statement::While { loop_variables, statements, break_collector } => {
@@ -198,16 +199,30 @@ fn analyze_used_function_names_and_type_names(
 }
 
 pub(super) fn optimize_lir_sources_by_eliminating_unused_ones(
-  Sources { symbol_table, global_variables, type_definitions, main_function_names, functions }: Sources,
+  Sources {
+    symbol_table,
+    global_variables,
+    global_constants,
+    type_definitions,
+    main_function_names,
+    functions,
+  }: Sources,
 ) -> Sources {
+  // Global constants are initialized eagerly, so their initializers are entry points too.
+  let entry_points = main_function_names
+    .iter()
+    .copied()
+    .chain(global_constants.iter().map(|it| it.initializer))
+    .collect_vec();
   let (used_str_names, used_fn_names, used_types) =
-    analyze_used_function_names_and_type_names(&functions, &main_function_names);
+    analyze_used_function_names_and_type_names(&functions, &entry_points);
   Sources {
     symbol_table,
     global_variables: global_variables
       .into_iter()
       .filter(|it| used_str_names.contains(&it.0))
       .collect_vec(),
+    global_constants,
     type_definitions: type_definitions
       .into_iter()
       .filter(|it| used_types.contains(&it.name))
@@ -241,6 +256,7 @@ mod tests {
         hir::GlobalString(heap.alloc_str_for_test("bar")),
         hir::GlobalString(heap.alloc_str_for_test("fsdfsdf")),
       ],
+      global_constants: Vec::new(),
       type_definitions: vec![
         TypeDefinition {
           name: table.create_type_name_for_test(heap.alloc_str_for_test("Foo")),
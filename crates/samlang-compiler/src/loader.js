@@ -2,15 +2,25 @@ function samlangGeneratedWebAssemblyLoader(bytes, builtinsPatch = () => ({})) {
   const codeModule = new WebAssembly.Module(bytes);
   let instance = null;
 
-  // Convert a WASM GC string array to a JavaScript string using exported helpers
+  // Convert a WASM GC string array of UTF-8 bytes to a JavaScript string using exported helpers
   function gcArrayToString(arr) {
     if (!instance) throw new Error('Instance not initialized');
-    const len = instance.exports.__strLen(arr);
-    const codes = [];
-    for (let i = 0; i < len; i++) {
-      codes.push(instance.exports.__strGet(arr, i));
+    const bytes = new Uint8Array(instance.exports.__strLen(arr));
+    for (let i = 0; i < bytes.length; i++) {
+      bytes[i] = instance.exports.__strGet(arr, i);
     }
-    return String.fromCharCode(...codes);
+    return new TextDecoder().decode(bytes);
+  }
+
+  // Convert a JavaScript string to a WASM GC string array of UTF-8 bytes using exported helpers
+  function stringToGcArray(str) {
+    if (!instance) throw new Error('Instance not initialized');
+    const bytes = new TextEncoder().encode(str);
+    const arr = instance.exports.__strNew(bytes.length);
+    for (let i = 0; i < bytes.length; i++) {
+      instance.exports.__strSet(arr, i, bytes[i]);
+    }
+    return arr;
   }
 
   const builtins = {
@@ -21,6 +31,9 @@ function samlangGeneratedWebAssemblyLoader(bytes, builtinsPatch = () => ({})) {
     __Process$panic(_, strArr) {
       throw new Error(gcArrayToString(strArr));
     },
+    __Str$fromFloat(_, v) {
+      return stringToGcArray(String(v));
+    },
     ...builtinsPatch(gcArrayToString),
   };
 
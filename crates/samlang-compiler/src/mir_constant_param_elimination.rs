@@ -1,9 +1,12 @@
 use dupe::Dupe;
 use itertools::Itertools;
 use rayon::prelude::*;
-use samlang_ast::mir::{
-  Binary, Callee, Expression, Function, FunctionName, FunctionNameExpression, FunctionType,
-  IfElseFinalAssignment, Sources, Statement, VariableName,
+use samlang_ast::{
+  Float64,
+  mir::{
+    Binary, Callee, Expression, Function, FunctionName, FunctionNameExpression, FunctionType,
+    IfElseFinalAssignment, Sources, Statement, VariableName,
+  },
 };
 use samlang_heap::PStr;
 use std::collections::HashMap;
@@ -14,6 +17,7 @@ enum ParamUsageAnalysisState {
   Referenced,
   Int32Constant(i32),
   Int31Constant(i32),
+  FloatConstant(Float64),
   StrConstant(PStr),
   Unoptimizable,
 }
@@ -104,6 +108,9 @@ fn collect_def_function_usages_stmt(
         }
         Callee::FunctionName(_) => {}
         Callee::Variable(v) => collect_def_function_usages_var(state, v),
+        Callee::InterfaceMethod { receiver, index: _ } => {
+          collect_def_function_usages_expr(state, receiver)
+        }
       }
       for arg in arguments {
         collect_def_function_usages_expr(state, arg);
@@ -122,7 +129,7 @@ fn collect_def_function_usages_stmt(
       collect_def_function_usages_expr(state, condition);
       collect_def_function_usages_stmts(state, f, statements);
     }
-    Statement::Break(e) => collect_def_function_usages_expr(state, e),
+    Statement::Break(e) | Statement::Return(e) => collect_def_function_usages_expr(state, e),
     Statement::While { loop_variables, statements, break_collector: _ } => {
       for v in loop_variables {
         collect_def_function_usages_expr(state, &v.initial_value);
@@ -146,6 +153,12 @@ fn collect_def_function_usages_stmt(
       function_name: _,
       context,
     } => collect_def_function_usages_expr(state, context),
+    Statement::InterfaceInit {
+      interface_variable_name: _,
+      interface_type_name: _,
+      vtable: _,
+      context,
+    } => collect_def_function_usages_expr(state, context),
   }
 }
 
@@ -168,12 +181,13 @@ fn collect_global_usages_stmt(
     | Statement::Binary(_)
     | Statement::IndexedAccess { .. }
     | Statement::Break(_)
+    | Statement::Return(_)
     | Statement::Cast { .. }
     | Statement::LateInitDeclaration { .. }
     | Statement::LateInitAssignment { .. }
     | Statement::StructInit { .. }
     | Statement::Call {
-      callee: Callee::Variable(_),
+      callee: Callee::Variable(_) | Callee::InterfaceMethod { .. },
       arguments: _,
       return_type: _,
       return_collector: _,
@@ -194,6 +208,16 @@ fn collect_global_usages_stmt(
     } => {
       state.insert(function_name.name, FunctionAnalysisState::Unoptimizable);
     }
+    Statement::InterfaceInit {
+      interface_variable_name: _,
+      interface_type_name: _,
+      vtable,
+      context: _,
+    } => {
+      for function_name in vtable {
+        state.insert(function_name.name, FunctionAnalysisState::Unoptimizable);
+      }
+    }
     Statement::Call {
       callee: Callee::FunctionName(FunctionNameExpression { name: fn_name, type_: _ }),
       arguments,
@@ -207,8 +231,11 @@ fn collect_global_usages_stmt(
             match arg {
               Expression::Int32Literal(n) => ParamUsageAnalysisState::Int32Constant(*n),
               Expression::Int31Literal(n) => ParamUsageAnalysisState::Int31Constant(*n),
+              Expression::FloatLiteral(f) => ParamUsageAnalysisState::FloatConstant(*f),
               Expression::StringName(p) => ParamUsageAnalysisState::StrConstant(*p),
-              Expression::Variable(_) => ParamUsageAnalysisState::Unoptimizable,
+              Expression::GlobalConstant(_) | Expression::Variable(_) => {
+                ParamUsageAnalysisState::Unoptimizable
+              }
             },
           )
         }
@@ -243,6 +270,7 @@ fn collect_all_usages(sources: &Sources) -> HashMap<FunctionName, FunctionAnalys
 enum VariableRewriteInstruction {
   Int32(i32),
   Int31(i32),
+  Float(Float64),
   StrConstant(PStr),
 }
 
@@ -253,11 +281,16 @@ struct RewriteState<'a> {
 
 fn rewrite_expr(state: &RewriteState, expr: &mut Expression) {
   match &expr {
-    Expression::Int32Literal(_) | Expression::Int31Literal(_) | Expression::StringName(_) => {}
+    Expression::Int32Literal(_)
+    | Expression::Int31Literal(_)
+    | Expression::FloatLiteral(_)
+    | Expression::StringName(_)
+    | Expression::GlobalConstant(_) => {}
     Expression::Variable(v) => match state.local_rewrite.get(&v.name) {
       None => {}
       Some(VariableRewriteInstruction::Int32(n)) => *expr = Expression::Int32Literal(*n),
       Some(VariableRewriteInstruction::Int31(n)) => *expr = Expression::Int31Literal(*n),
+      Some(VariableRewriteInstruction::Float(f)) => *expr = Expression::FloatLiteral(*f),
       Some(VariableRewriteInstruction::StrConstant(s)) => *expr = Expression::StringName(*s),
     },
   }
@@ -302,6 +335,9 @@ fn rewrite_stmt(state: &RewriteState, stmt: &mut Statement) {
           keep
         });
       } else {
+        if let Callee::InterfaceMethod { receiver, index: _ } = callee {
+          rewrite_expr(state, receiver);
+        }
         for e in arguments {
           rewrite_expr(state, e);
         }
@@ -327,7 +363,7 @@ fn rewrite_stmt(state: &RewriteState, stmt: &mut Statement) {
       }
       rewrite_stmts(state, statements);
     }
-    Statement::Break(e) => rewrite_expr(state, e),
+    Statement::Break(e) | Statement::Return(e) => rewrite_expr(state, e),
     Statement::Cast { name: _, type_: _, assigned_expression }
     | Statement::LateInitAssignment { name: _, assigned_expression } => {
       rewrite_expr(state, assigned_expression)
@@ -346,6 +382,14 @@ fn rewrite_stmt(state: &RewriteState, stmt: &mut Statement) {
     } => {
       rewrite_expr(state, context);
     }
+    Statement::InterfaceInit {
+      interface_variable_name: _,
+      interface_type_name: _,
+      vtable: _,
+      context,
+    } => {
+      rewrite_expr(state, context);
+    }
   }
 }
 
@@ -388,6 +432,10 @@ pub(super) fn rewrite_sources(mut sources: Sources) -> Sources {
             local_rewrite.insert(*name, VariableRewriteInstruction::Int31(i));
             false
           }
+          ParamUsageAnalysisState::FloatConstant(f) => {
+            local_rewrite.insert(*name, VariableRewriteInstruction::Float(f));
+            false
+          }
           ParamUsageAnalysisState::StrConstant(s) => {
             local_rewrite.insert(*name, VariableRewriteInstruction::StrConstant(s));
             false
@@ -418,7 +466,7 @@ mod tests {
   use samlang_ast::{
     hir::BinaryOperator,
     mir::{
-      Callee, Expression, Function, FunctionName, FunctionNameExpression, FunctionType,
+      Callee, Expression, FLOAT_TYPE, Function, FunctionName, FunctionNameExpression, FunctionType,
       GenenalLoopVariable, INT_31_TYPE, INT_32_TYPE, IfElseFinalAssignment, Sources, Statement,
       SymbolTable, Type, VariableName, ZERO,
     },
@@ -445,7 +493,9 @@ mod tests {
 
     let input = Sources {
       global_variables: Vec::new(),
+      global_constants: Vec::new(),
       closure_types: Vec::new(),
+      interface_types: Vec::new(),
       type_definitions: Vec::new(),
       main_function_names: Vec::new(),
       functions: vec![
@@ -469,6 +519,16 @@ mod tests {
           body: vec![Statement::Break(Expression::var_name(PStr::LOWER_A, INT_32_TYPE))],
           return_value: ZERO,
         },
+        Function {
+          name: FunctionName::new_for_test(heap.alloc_str_for_test("float_const")),
+          parameters: vec![PStr::LOWER_A],
+          type_: FunctionType {
+            argument_types: vec![FLOAT_TYPE],
+            return_type: Box::new(INT_32_TYPE),
+          },
+          body: vec![Statement::Break(Expression::var_name(PStr::LOWER_A, FLOAT_TYPE))],
+          return_value: ZERO,
+        },
         Function {
           name: FunctionName::new_for_test(heap.alloc_str_for_test("func_with_consts")),
           parameters: vec![
@@ -600,6 +660,18 @@ mod tests {
               return_type: INT_32_TYPE,
               return_collector: None,
             },
+            Statement::Call {
+              callee: Callee::FunctionName(FunctionNameExpression {
+                name: FunctionName::new_for_test(heap.alloc_str_for_test("float_const")),
+                type_: FunctionType {
+                  argument_types: vec![FLOAT_TYPE],
+                  return_type: Box::new(INT_32_TYPE),
+                },
+              }),
+              arguments: vec![Expression::float(1.5)],
+              return_type: INT_32_TYPE,
+              return_collector: None,
+            },
             Statement::Call {
               callee: Callee::FunctionName(FunctionNameExpression {
                 name: FunctionName::new_for_test(heap.alloc_str_for_test("otherwise_optimizable")),
@@ -668,6 +740,12 @@ function __$str_const(): int {
   return 0;
 }
 
+function __$float_const(): int {
+  undefined = 1.5;
+  break;
+  return 0;
+}
+
 function __$func_with_consts(c: _C, e: _E): int {
   let _ = !0;
   let _ = 0 + (c: int);
@@ -678,6 +756,7 @@ function __$func_with_consts(c: _C, e: _E): int {
   __$func_with_consts(1, (e: int));
   __$func_with_consts(3, (e: int));
   __$str_const();
+  __$float_const();
   __$otherwise_optimizable(0, 0);
   let _: int;
   if (e: int) {
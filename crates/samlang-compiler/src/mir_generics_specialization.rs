@@ -5,12 +5,15 @@ use std::collections::{HashMap, HashSet};
 
 struct Rewriter {
   original_closure_defs: HashMap<hir::TypeName, hir::ClosureTypeDefinition>,
+  original_interface_defs: HashMap<mir::TypeNameId, hir::InterfaceTypeDefinition>,
   original_type_defs: HashMap<hir::TypeName, hir::TypeDefinition>,
   original_functions: HashMap<mir::FunctionName, hir::Function>,
   used_string_names: HashSet<PStr>,
+  used_global_constants: HashMap<mir::FunctionName, mir::GlobalConstant>,
   specialized_type_definition_names: HashSet<mir::TypeNameId>,
   specialized_function_names: HashSet<mir::FunctionName>,
   specialized_closure_definitions: Vec<mir::ClosureTypeDefinition>,
+  specialized_interface_definitions: Vec<mir::InterfaceTypeDefinition>,
   specialized_type_definitions: HashMap<mir::TypeNameId, mir::TypeDefinition>,
   specialized_functions: Vec<mir::Function>,
   symbol_table: mir::SymbolTable,
@@ -104,7 +107,11 @@ impl Rewriter {
         final_assignments,
       } => {
         let test_expr = self.rewrite_expr(heap, hir_test_expr, generics_replacement_map);
-        let enum_type = *test_expr.as_variable().unwrap().type_.as_id().unwrap();
+        let test_expr_type = match &test_expr {
+          mir::Expression::GlobalConstant(g) => g.type_,
+          e => e.as_variable().unwrap().type_,
+        };
+        let enum_type = *test_expr_type.as_id().unwrap();
         let has_int31_variants = self.enum_has_int31_variants(enum_type);
         match self.get_subtype(enum_type, *tag) {
           mir::EnumTypeDefinition::Boxed(_) => {
@@ -272,6 +279,30 @@ impl Rewriter {
           ),
         });
       }
+      hir::Statement::SingleIf { condition, invert_condition, statements } => {
+        collector.push(mir::Statement::SingleIf {
+          condition: self.rewrite_expr(heap, condition, generics_replacement_map),
+          invert_condition: *invert_condition,
+          statements: self.rewrite_stmts(heap, statements, generics_replacement_map),
+        });
+      }
+      hir::Statement::Break => collector.push(mir::Statement::Break(mir::ZERO)),
+      hir::Statement::While { loop_variables, statements } => {
+        let loop_variables = loop_variables
+          .iter()
+          .map(|(n, t, initial_value, loop_value)| mir::GenenalLoopVariable {
+            name: *n,
+            type_: self.rewrite_type(heap, t, generics_replacement_map),
+            initial_value: self.rewrite_expr(heap, initial_value, generics_replacement_map),
+            loop_value: self.rewrite_expr(heap, loop_value, generics_replacement_map),
+          })
+          .collect_vec();
+        collector.push(mir::Statement::While {
+          loop_variables,
+          statements: self.rewrite_stmts(heap, statements, generics_replacement_map),
+          break_collector: None,
+        });
+      }
       hir::Statement::LateInitDeclaration { name, type_ } => {
         collector.push(mir::Statement::LateInitDeclaration {
           name: *name,
@@ -372,6 +403,61 @@ impl Rewriter {
           context: self.rewrite_expr(heap, context, generics_replacement_map),
         });
       }
+      hir::Statement::InterfaceInit { interface_variable_name, interface_type, context } => {
+        let interface_type_name =
+          self.rewrite_id_type(heap, interface_type, generics_replacement_map).into_id().unwrap();
+        let context = self.rewrite_expr(heap, context, generics_replacement_map);
+        let context_type = context.type_();
+        // A generic value might turn out to be the interface value itself after specialization.
+        if context_type == mir::Type::Id(interface_type_name) {
+          collector.push(mir::Statement::Cast {
+            name: *interface_variable_name,
+            type_: context_type,
+            assigned_expression: context,
+          });
+          return;
+        }
+        let (interface_simple_name, interface_type_arguments) =
+          self.symbol_table.split_type_name_suffix(interface_type_name);
+        let interface_def =
+          self.original_interface_defs.get(&interface_simple_name).unwrap().clone();
+        let interface_replacement_map: HashMap<PStr, mir::Type> =
+          interface_def.type_parameters.iter().copied().zip(interface_type_arguments).collect();
+        let (implementor_simple_name, implementor_type_arguments) =
+          self.symbol_table.split_type_name_suffix(context_type.into_id().unwrap());
+        let vtable = interface_def
+          .methods
+          .iter()
+          .map(|(method_name, method_type)| {
+            let mir::FunctionType { argument_types, return_type } =
+              self.rewrite_fn_type(heap, method_type, &interface_replacement_map);
+            let type_ = mir::FunctionType {
+              argument_types: vec![context_type].into_iter().chain(argument_types).collect(),
+              return_type,
+            };
+            let name = self.rewrite_non_generic_fn_name(
+              heap,
+              mir::FunctionName { type_name: implementor_simple_name, fn_name: *method_name },
+              type_.clone(),
+              implementor_type_arguments.clone(),
+            );
+            mir::FunctionNameExpression { name, type_ }
+          })
+          .collect();
+        collector.push(mir::Statement::InterfaceInit {
+          interface_variable_name: *interface_variable_name,
+          interface_type_name,
+          vtable,
+          context,
+        });
+      }
+      hir::Statement::Return(e) => {
+        collector.push(mir::Statement::Return(self.rewrite_expr(
+          heap,
+          e,
+          generics_replacement_map,
+        )));
+      }
     }
   }
 
@@ -410,10 +496,24 @@ impl Rewriter {
     match expression {
       hir::Expression::IntLiteral(i) => mir::Expression::Int32Literal(*i),
       hir::Expression::Int31Zero => mir::Expression::Int31Literal(0),
+      hir::Expression::FloatLiteral(f) => mir::Expression::FloatLiteral(*f),
       hir::Expression::StringName(s) => {
         self.used_string_names.insert(*s);
         mir::Expression::StringName(*s)
       }
+      hir::Expression::GlobalConstant(hir::GlobalConstant { initializer, type_ }) => {
+        let type_ = self.rewrite_type(heap, type_, generics_replacement_map);
+        let initializer = self.rewrite_fn_name(
+          heap,
+          *initializer,
+          mir::FunctionType { argument_types: Vec::new(), return_type: Box::new(type_) },
+          Vec::new(),
+          generics_replacement_map,
+        );
+        let global_constant = mir::GlobalConstant { initializer, type_ };
+        self.used_global_constants.insert(initializer, global_constant);
+        mir::Expression::GlobalConstant(global_constant)
+      }
       hir::Expression::Variable(hir::VariableName { name, type_ }) => {
         mir::Expression::Variable(mir::VariableName {
           name: *name,
@@ -457,12 +557,15 @@ impl Rewriter {
       let fn_name = original_name.fn_name;
       let replacement_class =
         generics_replacement_map.get(&generic_class_name).unwrap().as_id().unwrap();
-      let rewritten_fn_name = mir::FunctionName { type_name: *replacement_class, fn_name };
+      // Methods of generic classes take the class type arguments before their own ones.
+      let (type_name, class_type_arguments) =
+        self.symbol_table.split_type_name_suffix(*replacement_class);
+      let rewritten_fn_name = mir::FunctionName { type_name, fn_name };
       self.rewrite_non_generic_fn_name(
         heap,
         rewritten_fn_name,
         function_type,
-        function_type_arguments,
+        class_type_arguments.into_iter().chain(function_type_arguments).collect(),
       )
     }
   }
@@ -474,6 +577,17 @@ impl Rewriter {
     function_type: mir::FunctionType,
     function_type_arguments: Vec<mir::Type>,
   ) -> mir::FunctionName {
+    if let Some(mir::Type::Id(receiver_type)) = function_type.argument_types.first()
+      && self.original_interface_defs.contains_key(&original_name.type_name)
+      && self.symbol_table.split_type_name_suffix(*receiver_type).0 == original_name.type_name
+    {
+      return self.rewrite_interface_method_dispatch(
+        heap,
+        *receiver_type,
+        original_name.fn_name,
+        function_type,
+      );
+    }
     if let Some(existing_fn) = self.original_functions.get(&original_name).cloned() {
       let encoded_specialized_fn_name = mir::FunctionName {
         type_name: self
@@ -498,6 +612,52 @@ impl Rewriter {
     }
   }
 
+  /// Methods called on an interface value are dispatched through its vtable by a thunk.
+  fn rewrite_interface_method_dispatch(
+    &mut self,
+    heap: &mut Heap,
+    interface_type_name: mir::TypeNameId,
+    method_name: PStr,
+    function_type: mir::FunctionType,
+  ) -> mir::FunctionName {
+    let thunk_name = mir::FunctionName { type_name: interface_type_name, fn_name: method_name };
+    if self.specialized_function_names.insert(thunk_name) {
+      let (interface_simple_name, _) =
+        self.symbol_table.split_type_name_suffix(interface_type_name);
+      let index = self.original_interface_defs[&interface_simple_name]
+        .methods
+        .iter()
+        .position(|(n, _)| *n == method_name)
+        .unwrap();
+      let receiver =
+        mir::Expression::var_name(PStr::UNDERSCORE_THIS, mir::Type::Id(interface_type_name));
+      let parameters =
+        function_type.argument_types[1..].iter().map(|_| heap.alloc_temp_str()).collect_vec();
+      let return_collector = heap.alloc_temp_str();
+      let return_type = *function_type.return_type;
+      self.specialized_functions.push(mir::Function {
+        name: thunk_name,
+        parameters: vec![PStr::UNDERSCORE_THIS]
+          .into_iter()
+          .chain(parameters.iter().copied())
+          .collect(),
+        body: vec![mir::Statement::Call {
+          callee: mir::Callee::InterfaceMethod { receiver, index },
+          arguments: parameters
+            .iter()
+            .zip(&function_type.argument_types[1..])
+            .map(|(n, t)| mir::Expression::var_name(*n, *t))
+            .collect(),
+          return_type,
+          return_collector: Some(return_collector),
+        }],
+        type_: function_type,
+        return_value: mir::Expression::var_name(return_collector, return_type),
+      });
+    }
+    thunk_name
+  }
+
   /// Invariant: enum type has already been specialized.
   /// Returns: rewritten type, whether the result is optimized
   fn get_subtype(
@@ -534,6 +694,7 @@ impl Rewriter {
     match type_ {
       hir::Type::Int32 => mir::Type::Int32,
       hir::Type::Int31 => mir::Type::Int31,
+      hir::Type::Float => mir::Type::Float,
       hir::Type::Id(id) => self.rewrite_id_type(heap, id, generics_replacement_map),
     }
   }
@@ -615,6 +776,22 @@ impl Rewriter {
           mir_type_name,
           mir::TypeDefinition { name: mir_type_name, mappings: rewritten_mappings },
         );
+      } else if let Some(interface_def) = self.original_interface_defs.get(
+        &self
+          .symbol_table
+          .create_simple_type_name(id_type.name.module_reference.unwrap(), id_type.name.type_name),
+      ) {
+        let interface_def = interface_def.clone();
+        let solved_targs_replacement_map: HashMap<PStr, mir::Type> =
+          interface_def.type_parameters.iter().copied().zip(concrete_type_mir_targs).collect();
+        let methods = interface_def
+          .methods
+          .iter()
+          .map(|(_, t)| self.rewrite_fn_type(heap, t, &solved_targs_replacement_map))
+          .collect();
+        self
+          .specialized_interface_definitions
+          .push(mir::InterfaceTypeDefinition { name: mir_type_name, methods });
       } else {
         let closure_def = self.original_closure_defs.get(&id_type.name).unwrap();
         let solved_targs_replacement_map: HashMap<PStr, mir::Type> =
@@ -635,6 +812,8 @@ impl Rewriter {
     match type_ {
       // We cannot distinguish unboxed int from tags
       mir::Type::Int32 | mir::Type::Int31 => false,
+      // Floats are not pointers either
+      mir::Type::Float => false,
       mir::Type::Id(type_id) => {
         let Some(type_def) = self.specialized_type_definitions.get(type_id) else {
           // Recursive type currently being processed - must be heap-allocated (pointer).
@@ -679,7 +858,9 @@ pub(super) fn perform_generics_specialization(
   heap: &mut Heap,
   hir::Sources {
     global_variables,
+    global_constants,
     closure_types,
+    interface_types,
     type_definitions,
     main_function_names,
     functions,
@@ -688,6 +869,16 @@ pub(super) fn perform_generics_specialization(
   let mut symbol_table = mir::SymbolTable::new();
   let mut rewriter = Rewriter {
     original_closure_defs: closure_types.into_iter().map(|it| (it.name, it)).collect(),
+    original_interface_defs: interface_types
+      .into_iter()
+      .map(|it| {
+        (
+          symbol_table
+            .create_simple_type_name(it.name.module_reference.unwrap(), it.name.type_name),
+          it,
+        )
+      })
+      .collect(),
     original_type_defs: type_definitions.into_iter().map(|it| (it.name, it)).collect(),
     original_functions: functions
       .into_iter()
@@ -705,9 +896,11 @@ pub(super) fn perform_generics_specialization(
       })
       .collect(),
     used_string_names: HashSet::new(),
+    used_global_constants: HashMap::new(),
     specialized_type_definition_names: HashSet::new(),
     specialized_function_names: HashSet::new(),
     specialized_closure_definitions: Vec::new(),
+    specialized_interface_definitions: Vec::new(),
     specialized_type_definitions: HashMap::new(),
     specialized_functions: Vec::new(),
     symbol_table,
@@ -741,9 +934,25 @@ pub(super) fn perform_generics_specialization(
     rewriter.specialized_functions.push(rewritten);
     mir_main_function_names.push(mir_main_fn_name);
   }
+  // Initializers can read other constants, so only now do we know all the used ones.
+  // The original order is kept, since it is already the initialization order.
+  let mir_global_constants = global_constants
+    .iter()
+    .filter_map(|hir::GlobalConstant { initializer, type_: _ }| {
+      let mir_initializer = mir::FunctionName {
+        type_name: rewriter.symbol_table.create_simple_type_name(
+          initializer.type_name.module_reference.unwrap(),
+          initializer.type_name.type_name,
+        ),
+        fn_name: initializer.fn_name,
+      };
+      rewriter.used_global_constants.get(&mir_initializer).copied()
+    })
+    .collect();
   let Rewriter {
     used_string_names,
     specialized_closure_definitions,
+    specialized_interface_definitions,
     specialized_type_definitions,
     specialized_functions,
     symbol_table,
@@ -755,7 +964,12 @@ pub(super) fn perform_generics_specialization(
       .into_iter()
       .filter(|it| used_string_names.contains(&it.0))
       .collect(),
+    global_constants: mir_global_constants,
     closure_types: specialized_closure_definitions.into_iter().sorted_by_key(|d| d.name).collect(),
+    interface_types: specialized_interface_definitions
+      .into_iter()
+      .sorted_by_key(|d| d.name)
+      .collect(),
     type_definitions: specialized_type_definitions
       .into_values()
       .sorted_by_key(|d| d.name)
@@ -782,7 +996,9 @@ mod tests {
     assert_specialized(
       hir::Sources {
         global_variables: Vec::new(),
+        global_constants: Vec::new(),
         closure_types: Vec::new(),
+        interface_types: Vec::new(),
         type_definitions: Vec::new(),
         main_function_names: Vec::new(),
         functions: Vec::new(),
@@ -799,7 +1015,9 @@ mod tests {
     assert_specialized(
       hir::Sources {
         global_variables: Vec::new(),
+        global_constants: Vec::new(),
         closure_types: Vec::new(),
+        interface_types: Vec::new(),
         type_definitions: Vec::new(),
         main_function_names: vec![hir::FunctionName {
           type_name: hir::TypeName::new_for_test(PStr::UPPER_I),
@@ -848,7 +1066,9 @@ sources.mains = [_DUMMY_I$main]
     assert_specialized(
       hir::Sources {
         global_variables: vec![GlobalString(heap.alloc_str_for_test("G1"))],
+        global_constants: Vec::new(),
         closure_types: Vec::new(),
+        interface_types: Vec::new(),
         type_definitions: vec![hir::TypeDefinition {
           name: hir::STRING_TYPE.dupe().as_id().unwrap().name,
           type_parameters: Vec::new(),
@@ -908,7 +1128,9 @@ sources.mains = [_DUMMY_I$main]
     assert_specialized(
       hir::Sources {
         global_variables: Vec::new(),
+        global_constants: Vec::new(),
         closure_types: Vec::new(),
+        interface_types: Vec::new(),
         type_definitions: Vec::new(),
         main_function_names: vec![hir::FunctionName {
           type_name: hir::TypeName::new_for_test(PStr::UPPER_I),
@@ -977,6 +1199,7 @@ sources.mains = [_DUMMY_I$main]
           GlobalString(heap.alloc_str_for_test("G1")),
           GlobalString(heap.alloc_str_for_test("G2")),
         ],
+        global_constants: Vec::new(),
         closure_types: vec![hir::ClosureTypeDefinition {
           name: hir::TypeName::new_for_test(heap.alloc_str_for_test("CC")),
           type_parameters: vec![PStr::UPPER_A, PStr::UPPER_B],
@@ -985,6 +1208,7 @@ sources.mains = [_DUMMY_I$main]
             hir::Type::new_generic_type(PStr::UPPER_B),
           ),
         }],
+        interface_types: Vec::new(),
         type_definitions: vec![
           hir::TypeDefinition {
             name: hir::TypeName::new_for_test(PStr::UPPER_A),
@@ -1321,6 +1545,7 @@ sources.mains = [_DUMMY_I$main]
                     },
                     context: g1,
                   },
+                  hir::Statement::Return(hir::ZERO),
                 ],
                 final_assignments: vec![(
                   heap.alloc_str_for_test("finalV"),
@@ -1446,6 +1671,7 @@ function _DUMMY_I$main(): int {
     let v2: int = (j: DUMMY_J)[0];
     let c1: DUMMY_CC___Str__Str = Closure { fun: (_DUMMY_I___Str$creatorIA: (_Str) -> DUMMY_I___Str__Str), context: "G1" };
     let c2: DUMMY_CC__int__Str = Closure { fun: (_DUMMY_I___Str$creatorIA: (_Str) -> DUMMY_I___Str__Str), context: "G1" };
+    return 0;
     finalV = (v2: int);
   }
   let b = 0 as DUMMY_Enum;
@@ -1511,7 +1737,7 @@ function _DUMMY_I___Str$creatorIB(b: _Str): DUMMY_I__int__Str {
 }
 
 function _DUMMY_I__DUMMY_I__int__Str$functor_fun(a: DUMMY_I__int__Str): int {
-  _DUMMY_I__int__Str$bar(0);
+  _DUMMY_I$bar(0);
   return 0;
 }
 
@@ -1524,6 +1750,252 @@ sources.mains = [_DUMMY_I$main]"#,
     );
   }
 
+  #[test]
+  fn default_method_per_class_specialization_test() {
+    let heap = &mut Heap::new();
+
+    let type_a = hir::Type::new_id_no_targs(PStr::UPPER_A);
+    let type_b_int = hir::Type::new_id(PStr::UPPER_B, vec![hir::INT_TYPE]);
+    let type_b_t =
+      hir::Type::new_id(PStr::UPPER_B, vec![hir::Type::new_generic_type(PStr::UPPER_T)]);
+    let type_self = hir::Type::new_generic_type(PStr::UNDERSCORE_SELF);
+    let area = heap.alloc_str_for_test("area");
+    let describe = heap.alloc_str_for_test("describe");
+    let call_describe = |type_: &hir::Type| hir::Statement::Call {
+      callee: hir::Callee::FunctionName(hir::FunctionNameExpression {
+        name: hir::FunctionName {
+          type_name: hir::TypeName::new_for_test(PStr::UPPER_I),
+          fn_name: describe,
+        },
+        type_: hir::Type::new_fn_unwrapped(vec![type_.dupe()], hir::INT_TYPE),
+        type_arguments: vec![type_.dupe()],
+      }),
+      arguments: vec![hir::ZERO],
+      return_type: hir::INT_TYPE,
+      return_collector: None,
+    };
+    assert_specialized(
+      hir::Sources {
+        global_variables: Vec::new(),
+        global_constants: Vec::new(),
+        closure_types: Vec::new(),
+        interface_types: Vec::new(),
+        type_definitions: vec![
+          hir::TypeDefinition {
+            name: hir::TypeName::new_for_test(PStr::UPPER_A),
+            type_parameters: Vec::new(),
+            mappings: hir::TypeDefinitionMappings::Struct(vec![hir::INT_TYPE]),
+          },
+          hir::TypeDefinition {
+            name: hir::TypeName::new_for_test(PStr::UPPER_B),
+            type_parameters: vec![PStr::UPPER_T],
+            mappings: hir::TypeDefinitionMappings::Struct(vec![hir::Type::new_generic_type(
+              PStr::UPPER_T,
+            )]),
+          },
+        ],
+        main_function_names: vec![hir::FunctionName {
+          type_name: hir::TypeName::new_for_test(PStr::UPPER_J),
+          fn_name: PStr::MAIN_FN,
+        }],
+        functions: vec![
+          hir::Function {
+            name: hir::FunctionName {
+              type_name: hir::TypeName::new_for_test(PStr::UPPER_J),
+              fn_name: PStr::MAIN_FN,
+            },
+            parameters: Vec::new(),
+            type_parameters: Vec::new(),
+            type_: hir::Type::new_fn_unwrapped(Vec::new(), hir::INT_TYPE),
+            body: vec![call_describe(&type_a), call_describe(&type_b_int)],
+            return_value: hir::ZERO,
+          },
+          // A default method body: the implementing class is its leading type parameter.
+          hir::Function {
+            name: hir::FunctionName {
+              type_name: hir::TypeName::new_for_test(PStr::UPPER_I),
+              fn_name: describe,
+            },
+            parameters: vec![PStr::UNDERSCORE_THIS],
+            type_parameters: vec![PStr::UNDERSCORE_SELF],
+            type_: hir::Type::new_fn_unwrapped(vec![type_self.dupe()], hir::INT_TYPE),
+            body: vec![hir::Statement::Call {
+              callee: hir::Callee::FunctionName(hir::FunctionNameExpression {
+                name: hir::FunctionName {
+                  type_name: hir::TypeName {
+                    module_reference: None,
+                    type_name: PStr::UNDERSCORE_SELF,
+                  },
+                  fn_name: area,
+                },
+                type_: hir::Type::new_fn_unwrapped(vec![type_self.dupe()], hir::INT_TYPE),
+                type_arguments: Vec::new(),
+              }),
+              arguments: vec![hir::Expression::var_name(PStr::UNDERSCORE_THIS, type_self.dupe())],
+              return_type: hir::INT_TYPE,
+              return_collector: None,
+            }],
+            return_value: hir::ZERO,
+          },
+          hir::Function {
+            name: hir::FunctionName {
+              type_name: hir::TypeName::new_for_test(PStr::UPPER_A),
+              fn_name: area,
+            },
+            parameters: vec![PStr::UNDERSCORE_THIS],
+            type_parameters: Vec::new(),
+            type_: hir::Type::new_fn_unwrapped(vec![type_a.dupe()], hir::INT_TYPE),
+            body: Vec::new(),
+            return_value: hir::ZERO,
+          },
+          hir::Function {
+            name: hir::FunctionName {
+              type_name: hir::TypeName::new_for_test(PStr::UPPER_B),
+              fn_name: area,
+            },
+            parameters: vec![PStr::UNDERSCORE_THIS],
+            type_parameters: vec![PStr::UPPER_T],
+            type_: hir::Type::new_fn_unwrapped(vec![type_b_t], hir::INT_TYPE),
+            body: Vec::new(),
+            return_value: hir::ZERO,
+          },
+        ],
+      },
+      heap,
+      r#"
+object type DUMMY_A = [int]
+object type DUMMY_B__int = [int]
+function _DUMMY_J$main(): int {
+  _DUMMY_I__DUMMY_A$describe(0);
+  _DUMMY_I__DUMMY_B__int$describe(0);
+  return 0;
+}
+
+function _DUMMY_A$area(_this: DUMMY_A): int {
+  return 0;
+}
+
+function _DUMMY_I__DUMMY_A$describe(_this: DUMMY_A): int {
+  _DUMMY_A$area((_this: DUMMY_A));
+  return 0;
+}
+
+function _DUMMY_B__int$area(_this: DUMMY_B__int): int {
+  return 0;
+}
+
+function _DUMMY_I__DUMMY_B__int$describe(_this: DUMMY_B__int): int {
+  _DUMMY_B__int$area((_this: DUMMY_B__int));
+  return 0;
+}
+
+sources.mains = [_DUMMY_J$main]
+"#,
+    );
+  }
+
+  #[test]
+  fn interface_value_specialization_test() {
+    let heap = &mut Heap::new();
+
+    let type_a = hir::Type::new_id_no_targs(PStr::UPPER_A);
+    let type_i = hir::Type::new_id_no_targs(PStr::UPPER_I);
+    let id_type_i = type_i.as_id().unwrap().clone();
+    let area = heap.alloc_str_for_test("area");
+    assert_specialized(
+      hir::Sources {
+        global_variables: Vec::new(),
+        global_constants: Vec::new(),
+        closure_types: Vec::new(),
+        interface_types: vec![hir::InterfaceTypeDefinition {
+          name: hir::TypeName::new_for_test(PStr::UPPER_I),
+          type_parameters: Vec::new(),
+          methods: vec![(area, hir::Type::new_fn_unwrapped(Vec::new(), hir::INT_TYPE))],
+        }],
+        type_definitions: vec![hir::TypeDefinition {
+          name: hir::TypeName::new_for_test(PStr::UPPER_A),
+          type_parameters: Vec::new(),
+          mappings: hir::TypeDefinitionMappings::Struct(vec![hir::INT_TYPE]),
+        }],
+        main_function_names: vec![hir::FunctionName {
+          type_name: hir::TypeName::new_for_test(PStr::UPPER_J),
+          fn_name: PStr::MAIN_FN,
+        }],
+        functions: vec![
+          hir::Function {
+            name: hir::FunctionName {
+              type_name: hir::TypeName::new_for_test(PStr::UPPER_J),
+              fn_name: PStr::MAIN_FN,
+            },
+            parameters: vec![PStr::LOWER_A],
+            type_parameters: Vec::new(),
+            type_: hir::Type::new_fn_unwrapped(vec![type_a.dupe()], hir::INT_TYPE),
+            body: vec![
+              hir::Statement::InterfaceInit {
+                interface_variable_name: PStr::LOWER_B,
+                interface_type: id_type_i.clone(),
+                context: hir::Expression::var_name(PStr::LOWER_A, type_a.dupe()),
+              },
+              // Upcasting a value that is already of the interface type is a no-op.
+              hir::Statement::InterfaceInit {
+                interface_variable_name: PStr::LOWER_C,
+                interface_type: id_type_i,
+                context: hir::Expression::var_name(PStr::LOWER_B, type_i.dupe()),
+              },
+              hir::Statement::Call {
+                callee: hir::Callee::FunctionName(hir::FunctionNameExpression {
+                  name: hir::FunctionName {
+                    type_name: hir::TypeName::new_for_test(PStr::UPPER_I),
+                    fn_name: area,
+                  },
+                  type_: hir::Type::new_fn_unwrapped(vec![type_i.dupe()], hir::INT_TYPE),
+                  type_arguments: Vec::new(),
+                }),
+                arguments: vec![hir::Expression::var_name(PStr::LOWER_C, type_i.dupe())],
+                return_type: hir::INT_TYPE,
+                return_collector: Some(PStr::LOWER_D),
+              },
+            ],
+            return_value: hir::Expression::var_name(PStr::LOWER_D, hir::INT_TYPE),
+          },
+          hir::Function {
+            name: hir::FunctionName {
+              type_name: hir::TypeName::new_for_test(PStr::UPPER_A),
+              fn_name: area,
+            },
+            parameters: vec![PStr::UNDERSCORE_THIS],
+            type_parameters: Vec::new(),
+            type_: hir::Type::new_fn_unwrapped(vec![type_a], hir::INT_TYPE),
+            body: Vec::new(),
+            return_value: hir::ZERO,
+          },
+        ],
+      },
+      heap,
+      r#"
+interface type DUMMY_I = [() -> int]
+object type DUMMY_A = [int]
+function _DUMMY_I$area(_this: DUMMY_I): int {
+  let _t0: int = (_this: DUMMY_I).vtable[0]();
+  return (_t0: int);
+}
+
+function _DUMMY_J$main(a: DUMMY_A): int {
+  let b: DUMMY_I = Interface { vtable: [_DUMMY_A$area: (DUMMY_A) -> int], context: (a: DUMMY_A) };
+  let c = (b: DUMMY_I) as DUMMY_I;
+  let d: int = _DUMMY_I$area((c: DUMMY_I));
+  return (d: int);
+}
+
+function _DUMMY_A$area(_this: DUMMY_A): int {
+  return 0;
+}
+
+sources.mains = [_DUMMY_J$main]
+"#,
+    );
+  }
+
   #[test]
   fn no_arg_function_type_def_test() {
     let heap = &mut Heap::new();
@@ -1531,7 +2003,9 @@ sources.mains = [_DUMMY_I$main]"#,
     assert_specialized(
       hir::Sources {
         global_variables: Vec::new(),
+        global_constants: Vec::new(),
         closure_types: Vec::new(),
+        interface_types: Vec::new(),
         type_definitions: vec![
           hir::TypeDefinition {
             name: hir::TypeName::new_for_test(PStr::UPPER_I),
@@ -158,9 +158,9 @@ fn optimize_function_by_tailrec_rewrite_aux(
   function: Function,
 ) -> (Function, bool) {
   let expected_return_collector = match &function.return_value {
-    Expression::Int32Literal(_) | Expression::Int31Literal(_) => None,
+    Expression::Int32Literal(_) | Expression::Int31Literal(_) | Expression::FloatLiteral(_) => None,
     Expression::Variable(v) => Some(v.name),
-    Expression::StringName(_) => return (function, false),
+    Expression::StringName(_) | Expression::GlobalConstant(_) => return (function, false),
   };
   let Function { name, parameters, type_, body, return_value } = function;
   let RewriteResult { stmts, args } = match try_rewrite_stmts_for_tailrec_without_using_return_value(
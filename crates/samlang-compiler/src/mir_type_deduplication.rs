@@ -1,8 +1,8 @@
 use itertools::Itertools;
 use samlang_ast::mir::{
   Binary, Callee, ClosureTypeDefinition, EnumTypeDefinition, Expression, Function,
-  FunctionNameExpression, FunctionType, IfElseFinalAssignment, Sources, Statement, Type,
-  TypeDefinition, TypeDefinitionMappings, TypeNameId, VariableName,
+  FunctionNameExpression, FunctionType, GenenalLoopVariable, IfElseFinalAssignment, Sources,
+  Statement, Type, TypeDefinition, TypeDefinitionMappings, TypeNameId, VariableName,
 };
 use std::collections::HashMap;
 
@@ -14,7 +14,7 @@ fn rewrite_id_type_name(state: &State, id: TypeNameId) -> TypeNameId {
 
 fn rewrite_type(state: &State, type_: &mut Type) {
   match type_ {
-    Type::Int32 | Type::Int31 => {}
+    Type::Int32 | Type::Int31 | Type::Float => {}
     Type::Id(id) => {
       *id = rewrite_id_type_name(state, *id);
     }
@@ -46,7 +46,11 @@ fn rewrite_fn_name(
 
 fn rewrite_expr(state: &State, expr: &mut Expression) {
   match expr {
-    Expression::Int32Literal(_) | Expression::Int31Literal(_) | Expression::StringName(_) => {}
+    Expression::Int32Literal(_)
+    | Expression::Int31Literal(_)
+    | Expression::FloatLiteral(_)
+    | Expression::StringName(_) => {}
+    Expression::GlobalConstant(g) => g.type_ = rewritten_type(state, g.type_),
     Expression::Variable(n) => rewrite_var_name(state, n),
   }
 }
@@ -94,6 +98,16 @@ fn rewrite_stmt(state: &State, stmt: &mut Statement) {
       rewrite_expressions(state, arguments);
       rewrite_type(state, return_type);
     }
+    Statement::Call {
+      callee: Callee::InterfaceMethod { receiver, index: _ },
+      arguments,
+      return_type,
+      return_collector: _,
+    } => {
+      rewrite_expr(state, receiver);
+      rewrite_expressions(state, arguments);
+      rewrite_type(state, return_type);
+    }
     Statement::IfElse { condition, s1, s2, final_assignments } => {
       rewrite_expr(state, condition);
       rewrite_stmts(state, s1);
@@ -104,15 +118,22 @@ fn rewrite_stmt(state: &State, stmt: &mut Statement) {
         rewrite_expr(state, e2);
       }
     }
-    Statement::SingleIf { .. } => {
-      panic!("SingleIf should not appear before tailrec optimization.")
-    }
-    Statement::Break(_) => {
-      panic!("Break should not appear before tailrec optimization.")
+    Statement::SingleIf { condition, invert_condition: _, statements } => {
+      rewrite_expr(state, condition);
+      rewrite_stmts(state, statements);
     }
-    Statement::While { .. } => {
-      panic!("While should not appear before tailrec optimization.")
+    Statement::While { loop_variables, statements, break_collector } => {
+      for GenenalLoopVariable { name: _, type_, initial_value, loop_value } in loop_variables {
+        rewrite_type(state, type_);
+        rewrite_expr(state, initial_value);
+        rewrite_expr(state, loop_value);
+      }
+      rewrite_stmts(state, statements);
+      if let Some(v) = break_collector {
+        rewrite_var_name(state, v);
+      }
     }
+    Statement::Break(e) | Statement::Return(e) => rewrite_expr(state, e),
     Statement::Cast { name: _, type_, assigned_expression } => {
       rewrite_type(state, type_);
       rewrite_expr(state, assigned_expression);
@@ -137,6 +158,17 @@ fn rewrite_stmt(state: &State, stmt: &mut Statement) {
       rewrite_fn_name(state, function_name);
       rewrite_expr(state, context);
     }
+    Statement::InterfaceInit {
+      interface_variable_name: _,
+      interface_type_name: _,
+      vtable,
+      context,
+    } => {
+      for function_name in vtable {
+        rewrite_fn_name(state, function_name);
+      }
+      rewrite_expr(state, context);
+    }
   }
 }
 
@@ -155,86 +187,102 @@ fn rewrite_function(
   rewrite_expr(state, return_value);
 }
 
+fn rewritten_mappings(state: &State, mappings: TypeDefinitionMappings) -> TypeDefinitionMappings {
+  match mappings {
+    TypeDefinitionMappings::Struct(types) => {
+      TypeDefinitionMappings::Struct(types.into_iter().map(|t| rewritten_type(state, t)).collect())
+    }
+    TypeDefinitionMappings::Enum(variants) => TypeDefinitionMappings::Enum(
+      variants
+        .into_iter()
+        .map(|v| match v {
+          EnumTypeDefinition::Boxed(types) => {
+            EnumTypeDefinition::Boxed(types.into_iter().map(|t| rewritten_type(state, t)).collect())
+          }
+          EnumTypeDefinition::Unboxed(t) => {
+            EnumTypeDefinition::Unboxed(rewrite_id_type_name(state, t))
+          }
+          EnumTypeDefinition::Int31 => EnumTypeDefinition::Int31,
+        })
+        .collect(),
+    ),
+  }
+}
+
 pub(super) fn deduplicate(
   Sources {
     mut symbol_table,
     global_variables,
+    mut global_constants,
     closure_types,
+    mut interface_types,
     type_definitions,
     main_function_names,
     mut functions,
   }: Sources,
 ) -> Sources {
   let mut state = HashMap::new();
-  let mut closure_type_def_mapping = HashMap::<FunctionType, TypeNameId>::new();
-  let mut type_def_mapping = HashMap::<TypeDefinitionMappings, TypeNameId>::new();
-  for closure_type in closure_types {
-    let original_name = closure_type.name;
-    let canonical_name = if let Some(id) = closure_type_def_mapping.get(&closure_type.function_type)
-    {
-      *id
-    } else {
-      closure_type_def_mapping.insert(closure_type.function_type, original_name);
-      original_name
-    };
-    state.insert(original_name, canonical_name);
-  }
-  for type_def in type_definitions {
-    let original_name = type_def.name;
-    let canonical_name = if let Some(id) = type_def_mapping.get(&type_def.mappings) {
-      *id
-    } else {
-      type_def_mapping.insert(type_def.mappings, original_name);
-      original_name
-    };
-    state.insert(original_name, canonical_name);
+  let mut closure_types = closure_types;
+  let mut type_definitions = type_definitions;
+  // Merging two types can make the types that refer to them identical (e.g. nested tuples), so we
+  // keep deduplicating with the rewritten definitions until nothing changes.
+  loop {
+    let mut merged = HashMap::new();
+    let mut closure_type_def_mapping = HashMap::<FunctionType, TypeNameId>::new();
+    let mut deduplicated_closure_types = Vec::with_capacity(closure_types.len());
+    for ClosureTypeDefinition { name, mut function_type } in closure_types {
+      rewrite_fn_type(&state, &mut function_type);
+      if let Some(id) = closure_type_def_mapping.get(&function_type) {
+        merged.insert(name, *id);
+      } else {
+        closure_type_def_mapping.insert(function_type.clone(), name);
+        deduplicated_closure_types.push(ClosureTypeDefinition { name, function_type });
+      }
+    }
+    let mut type_def_mapping = HashMap::<TypeDefinitionMappings, TypeNameId>::new();
+    let mut deduplicated_type_definitions = Vec::with_capacity(type_definitions.len());
+    for TypeDefinition { name, mappings } in type_definitions {
+      let mappings = rewritten_mappings(&state, mappings);
+      if let Some(id) = type_def_mapping.get(&mappings) {
+        merged.insert(name, *id);
+      } else {
+        type_def_mapping.insert(mappings.clone(), name);
+        deduplicated_type_definitions.push(TypeDefinition { name, mappings });
+      }
+    }
+    closure_types = deduplicated_closure_types;
+    type_definitions = deduplicated_type_definitions;
+    if merged.is_empty() {
+      break;
+    }
+    for canonical_name in state.values_mut() {
+      *canonical_name = rewrite_id_type_name(&merged, *canonical_name);
+    }
+    state.extend(merged);
   }
-
-  let closure_types = closure_type_def_mapping
-    .into_iter()
-    .map(|(mut t, name)| {
-      rewrite_fn_type(&state, &mut t);
-      ClosureTypeDefinition { name, function_type: t }
-    })
-    .sorted_by_key(|d| d.name)
-    .collect_vec();
-  let type_definitions = type_def_mapping
-    .into_iter()
-    .map(|(mappings, name)| TypeDefinition {
-      name,
-      mappings: match mappings {
-        TypeDefinitionMappings::Struct(types) => TypeDefinitionMappings::Struct(
-          types.into_iter().map(|t| rewritten_type(&state, t)).collect(),
-        ),
-        TypeDefinitionMappings::Enum(variants) => TypeDefinitionMappings::Enum(
-          variants
-            .into_iter()
-            .map(|v| match v {
-              EnumTypeDefinition::Boxed(types) => EnumTypeDefinition::Boxed(
-                types.into_iter().map(|t| rewritten_type(&state, t)).collect(),
-              ),
-              EnumTypeDefinition::Unboxed(t) => {
-                EnumTypeDefinition::Unboxed(rewrite_id_type_name(&state, t))
-              }
-              EnumTypeDefinition::Int31 => EnumTypeDefinition::Int31,
-            })
-            .collect(),
-        ),
-      },
-    })
-    .sorted_by_key(|d| d.name)
-    .collect_vec();
+  let closure_types = closure_types.into_iter().sorted_by_key(|d| d.name).collect_vec();
+  let type_definitions = type_definitions.into_iter().sorted_by_key(|d| d.name).collect_vec();
   let subtype_remap = symbol_table.remap_subtypes_for_deduplication(&state);
   for (old_id, new_id) in subtype_remap {
     state.insert(old_id, new_id);
   }
+  for d in &mut interface_types {
+    for method in &mut d.methods {
+      rewrite_fn_type(&state, method);
+    }
+  }
   for f in &mut functions {
     rewrite_function(&state, f);
   }
+  for g in &mut global_constants {
+    g.type_ = rewritten_type(&state, g.type_);
+  }
   Sources {
     symbol_table,
     global_variables,
+    global_constants,
     closure_types,
+    interface_types,
     type_definitions,
     main_function_names,
     functions,
@@ -248,32 +296,23 @@ mod tests {
   use samlang_ast::mir::{FunctionName, INT_31_TYPE, INT_32_TYPE, ONE, SymbolTable, ZERO};
   use samlang_heap::{Heap, ModuleReference, PStr};
 
-  #[should_panic]
-  #[test]
-  fn panic_test_1() {
-    rewrite_stmt(&HashMap::new(), &mut Statement::Break(ZERO));
-  }
-
-  #[should_panic]
   #[test]
-  fn panic_test_2() {
-    rewrite_stmt(
-      &HashMap::new(),
-      &mut Statement::SingleIf { condition: ZERO, invert_condition: false, statements: Vec::new() },
-    );
-  }
-
-  #[should_panic]
-  #[test]
-  fn panic_test_3() {
-    rewrite_stmt(
-      &HashMap::new(),
-      &mut Statement::While {
-        loop_variables: Vec::new(),
-        statements: Vec::new(),
-        break_collector: None,
-      },
-    );
+  fn loop_statements_test() {
+    let mut stmt = Statement::While {
+      loop_variables: vec![GenenalLoopVariable {
+        name: PStr::LOWER_I,
+        type_: INT_32_TYPE,
+        initial_value: ZERO,
+        loop_value: ZERO,
+      }],
+      statements: vec![Statement::SingleIf {
+        condition: ZERO,
+        invert_condition: false,
+        statements: vec![Statement::Break(ZERO)],
+      }],
+      break_collector: Some(VariableName::new(PStr::LOWER_B, INT_32_TYPE)),
+    };
+    rewrite_stmt(&HashMap::new(), &mut stmt);
   }
 
   #[test]
@@ -305,6 +344,7 @@ mod tests {
 
     let sources = Sources {
       global_variables: Vec::new(),
+      global_constants: Vec::new(),
       closure_types: vec![
         ClosureTypeDefinition {
           name: table.create_type_name_for_test(PStr::UPPER_A),
@@ -322,6 +362,7 @@ mod tests {
           ),
         },
       ],
+      interface_types: Vec::new(),
       type_definitions: vec![
         TypeDefinition {
           name: table.create_type_name_for_test(PStr::UPPER_C),
@@ -460,6 +501,74 @@ function __$main(): int {
   }
   return 0;
 }
+"#,
+      actual
+    );
+  }
+
+  #[test]
+  fn nested_struct_deduplication_test() {
+    let heap = &mut Heap::new();
+    let mut table = SymbolTable::new();
+    let a = table.create_type_name_for_test(PStr::UPPER_A);
+    let b = table.create_type_name_for_test(PStr::UPPER_B);
+    let c = table.create_type_name_for_test(PStr::UPPER_C);
+    let d = table.create_type_name_for_test(PStr::UPPER_D);
+    let sources = Sources {
+      global_variables: Vec::new(),
+      global_constants: Vec::new(),
+      closure_types: vec![
+        ClosureTypeDefinition {
+          name: table.create_type_name_for_test(PStr::UPPER_E),
+          function_type: Type::new_fn_unwrapped(vec![Type::Id(c)], INT_32_TYPE),
+        },
+        ClosureTypeDefinition {
+          name: table.create_type_name_for_test(PStr::UPPER_F),
+          function_type: Type::new_fn_unwrapped(vec![Type::Id(d)], INT_32_TYPE),
+        },
+      ],
+      interface_types: Vec::new(),
+      type_definitions: vec![
+        TypeDefinition {
+          name: a,
+          mappings: TypeDefinitionMappings::Struct(vec![INT_32_TYPE, INT_32_TYPE]),
+        },
+        TypeDefinition {
+          name: b,
+          mappings: TypeDefinitionMappings::Struct(vec![INT_32_TYPE, INT_32_TYPE]),
+        },
+        TypeDefinition {
+          name: c,
+          mappings: TypeDefinitionMappings::Struct(vec![Type::Id(a), Type::Id(a)]),
+        },
+        TypeDefinition {
+          name: d,
+          mappings: TypeDefinitionMappings::Struct(vec![Type::Id(a), Type::Id(b)]),
+        },
+      ],
+      main_function_names: Vec::new(),
+      functions: vec![Function {
+        name: FunctionName::new_for_test(PStr::MAIN_FN),
+        parameters: Vec::new(),
+        type_: Type::new_fn_unwrapped(Vec::new(), INT_32_TYPE),
+        body: vec![Statement::StructInit {
+          struct_variable_name: PStr::UNDERSCORE,
+          type_name: d,
+          expression_list: vec![ZERO, ZERO],
+        }],
+        return_value: ZERO,
+      }],
+      symbol_table: table,
+    };
+    let actual = deduplicate(sources).debug_print(heap);
+    assert_eq!(
+      r#"closure type _E = (_C) -> int
+object type _A = [int, int]
+object type _C = [_A, _A]
+function __$main(): int {
+  let _: _C = [0, 0];
+  return 0;
+}
 "#,
       actual
     );
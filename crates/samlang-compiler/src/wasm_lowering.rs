@@ -37,6 +37,7 @@ impl<'a> TypeLoweringContext<'a> {
     match t {
       lir::Type::Int32 => wasm::Type::Int32,
       lir::Type::Int31 => wasm::Type::Int31,
+      lir::Type::Float => wasm::Type::Float64,
       lir::Type::AnyPointer => wasm::Type::Eq,
       lir::Type::Id(type_name_id) => wasm::Type::Reference(*type_name_id),
       // Function types are represented as i32 (function table indices)
@@ -55,11 +56,13 @@ struct LoopContext {
 fn is_reference_expr(e: &lir::Expression) -> bool {
   match e {
     lir::Expression::Int31Literal(_) => true,
-    lir::Expression::Variable(_, t) => {
+    lir::Expression::Variable(_, t)
+    | lir::Expression::GlobalConstant(lir::GlobalConstant { type_: t, .. }) => {
       matches!(t, lir::Type::Int31 | lir::Type::AnyPointer | lir::Type::Id(_) | lir::Type::Fn(_))
     }
     lir::Expression::StringName(_)
     | lir::Expression::Int32Literal(_)
+    | lir::Expression::FloatLiteral(_)
     | lir::Expression::FnName(_, _) => false,
   }
 }
@@ -67,7 +70,10 @@ fn is_reference_expr(e: &lir::Expression) -> bool {
 fn is_string_expr(e: &lir::Expression) -> bool {
   match e {
     lir::Expression::StringName(_) => true,
-    lir::Expression::Variable(_, lir::Type::Id(id)) => *id == mir::TypeNameId::STR,
+    lir::Expression::Variable(_, lir::Type::Id(id))
+    | lir::Expression::GlobalConstant(lir::GlobalConstant { type_: lir::Type::Id(id), .. }) => {
+      *id == mir::TypeNameId::STR
+    }
     _ => false,
   }
 }
@@ -105,12 +111,32 @@ fn lir_expr_is_i32(e: &lir::Expression) -> bool {
   matches!(e, lir::Expression::Int32Literal(_) | lir::Expression::Variable(_, lir::Type::Int32))
 }
 
+/// True if the LIR expression has type f64. Such Vec element arguments are boxed into a
+/// `$_Float` struct, since f64 has no unboxed `(ref eq)` representation.
+fn lir_expr_is_float(e: &lir::Expression) -> bool {
+  matches!(e, lir::Expression::FloatLiteral(_) | lir::Expression::Variable(_, lir::Type::Float))
+}
+
+/// The WASM type of the result of a binary operator.
+fn binary_result_type(operator: hir::BinaryOperator) -> wasm::Type {
+  match operator {
+    hir::BinaryOperator::FMUL
+    | hir::BinaryOperator::FDIV
+    | hir::BinaryOperator::FPLUS
+    | hir::BinaryOperator::FMINUS => wasm::Type::Float64,
+    _ => wasm::Type::Int32,
+  }
+}
+
 struct LoweringManager<'a> {
   label_id: u32,
   type_cx: TypeLoweringContext<'a>,
   loop_cx: Option<LoopContext>,
   local_variables: BTreeMap<PStr, wasm::Type>,
+  return_type: wasm::Type,
   string_name_mapping: &'a HashMap<PStr, PStr>,
+  /// Maps a global constant's initializer to the declared type of the global.
+  global_constant_types: &'a HashMap<mir::FunctionName, wasm::Type>,
   function_index_mapping: &'a HashMap<mir::FunctionName, usize>,
   /// Maps type name ID to its field types (for StructInit lowering)
   type_field_mappings: &'a HashMap<mir::TypeNameId, Vec<wasm::Type>>,
@@ -120,6 +146,7 @@ impl<'a> LoweringManager<'a> {
   fn lower_fn(
     mut type_cx: TypeLoweringContext<'a>,
     string_name_mapping: &'a HashMap<PStr, PStr>,
+    global_constant_types: &'a HashMap<mir::FunctionName, wasm::Type>,
     function_index_mapping: &'a HashMap<mir::FunctionName, usize>,
     type_field_mappings: &'a HashMap<mir::TypeNameId, Vec<wasm::Type>>,
     function: &lir::Function,
@@ -130,26 +157,21 @@ impl<'a> LoweringManager<'a> {
     for (n, t) in function.parameters.iter().zip(&function.type_.argument_types) {
       param_types.insert(*n, type_cx.lower(t));
     }
+    let return_type = type_cx.lower(&function.type_.return_type);
     let mut instance = LoweringManager {
       label_id: 0,
       type_cx,
       loop_cx: None,
       local_variables: param_types,
+      return_type,
       string_name_mapping,
+      global_constant_types,
       function_index_mapping,
       type_field_mappings,
     };
     let mut instructions =
       function.body.iter().flat_map(|it| instance.lower_stmt(it)).collect_vec();
-    let return_value_expr = instance.lower_expr(&function.return_value);
-    // Wrap return value with ref.as_non_null for reference types since locals are nullable
-    let return_type = instance.type_cx.lower(&function.type_.return_type);
-    let return_value_expr =
-      if matches!(return_type, wasm::Type::Int31 | wasm::Type::Eq | wasm::Type::Reference(_)) {
-        wasm::InlineInstruction::RefAsNonNull(Box::new(return_value_expr))
-      } else {
-        return_value_expr
-      };
+    let return_value_expr = instance.lower_return_value(&function.return_value);
     instructions.push(wasm::Instruction::Inline(return_value_expr));
     let mut parameters = Vec::new();
     for (n, t) in function.parameters.iter().zip(&function.type_.argument_types) {
@@ -177,6 +199,16 @@ impl<'a> LoweringManager<'a> {
     (f, instance.type_cx)
   }
 
+  fn lower_return_value(&mut self, e: &lir::Expression) -> wasm::InlineInstruction {
+    let return_value_expr = self.lower_expr(e);
+    // Wrap return value with ref.as_non_null for reference types since locals are nullable
+    if matches!(self.return_type, wasm::Type::Int31 | wasm::Type::Eq | wasm::Type::Reference(_)) {
+      wasm::InlineInstruction::RefAsNonNull(Box::new(return_value_expr))
+    } else {
+      return_value_expr
+    }
+  }
+
   fn lower_stmt(&mut self, s: &lir::Statement) -> Vec<wasm::Instruction> {
     match s {
       lir::Statement::IsPointer { name, pointer_type, operand } => {
@@ -230,7 +262,7 @@ impl<'a> LoweringManager<'a> {
               && (is_reference_expr(e1) || is_reference_expr(e2));
           vec![wasm::Instruction::Inline(self.set(
             *name,
-            wasm::Type::Int32,
+            binary_result_type(*operator),
             wasm::InlineInstruction::Binary { v1: i1, op: *operator, v2: i2, is_ref_comparison },
           ))]
         }
@@ -253,10 +285,12 @@ impl<'a> LoweringManager<'a> {
         let (needs_ref_eq_this, is_panic, vec_element_arg, vec_returns_element) =
           if let lir::Expression::FnName(name, _) = callee {
             // PROCESS_PRINTLN and PROCESS_PANIC take (ref eq) as first arg.
-            // STR_FROM_INT takes (ref eq) as first arg.
+            // STR_FROM_INT, STR_FROM_FLOAT and Float statics take (ref eq) as first arg.
             // Vec static methods (empty, of, withCapacity) take (ref eq) as first arg.
             let needs_ref_eq = name.type_name == mir::TypeNameId::PROCESS
+              || name.type_name == mir::TypeNameId::FLOAT
               || (*name == mir::FunctionName::STR_FROM_INT)
+              || (*name == mir::FunctionName::STR_FROM_FLOAT)
               || vec_fn_is_static(*name);
             let is_panic = *name == mir::FunctionName::PROCESS_PANIC;
             (needs_ref_eq, is_panic, vec_fn_element_arg_index(*name), vec_fn_returns_element(*name))
@@ -284,14 +318,25 @@ impl<'a> LoweringManager<'a> {
             if Some(i) == vec_element_arg && lir_expr_is_i32(arg) {
               return wasm::InlineInstruction::I31New(Box::new(lowered));
             }
-            if let (Some(param_types), lir::Expression::Variable(var_name, _)) =
-              (callee_param_types, arg)
-            {
+            if Some(i) == vec_element_arg && lir_expr_is_float(arg) {
+              return wasm::InlineInstruction::DirectCall(
+                mir::FunctionName::WRAP_F64,
+                vec![lowered],
+              );
+            }
+            if let Some(param_types) = callee_param_types {
+              let stored_type = match arg {
+                lir::Expression::Variable(var_name, _) => self.local_variables.get(var_name),
+                lir::Expression::GlobalConstant(g) => {
+                  self.global_constant_types.get(&g.initializer)
+                }
+                _ => None,
+              };
               // For direct calls: if argument variable has WASM type (ref eq) but the target
               // function expects a concrete reference type, insert a ref.cast to downcast.
               // This occurs when _this is erased to AnyPointer for call_indirect compatibility
               // but is passed to a function that expects a concrete struct type.
-              if self.local_variables.get(var_name).copied() == Some(wasm::Type::Eq)
+              if stored_type.copied() == Some(wasm::Type::Eq)
                 && let Some(lir::Type::Id(_)) = param_types.get(i)
               {
                 return wasm::InlineInstruction::Cast {
@@ -332,6 +377,8 @@ impl<'a> LoweringManager<'a> {
           let call = if vec_returns_element {
             if return_type.is_int32() {
               wasm::InlineInstruction::DirectCall(mir::FunctionName::UNWRAP_I31, vec![call])
+            } else if return_type.is_float() {
+              wasm::InlineInstruction::DirectCall(mir::FunctionName::UNWRAP_F64, vec![call])
             } else {
               wasm::InlineInstruction::Cast {
                 pointer_type: return_type.clone(),
@@ -413,6 +460,7 @@ impl<'a> LoweringManager<'a> {
           vec![wasm::Instruction::UnconditionalJump(exit_label)]
         }
       }
+      lir::Statement::Return(e) => vec![wasm::Instruction::Return(self.lower_return_value(e))],
       lir::Statement::While { loop_variables, statements, break_collector } => {
         let saved_current_loop_cx = self.loop_cx;
         let continue_label = self.alloc_label_with_annot();
@@ -454,7 +502,10 @@ impl<'a> LoweringManager<'a> {
         // - Casting from AnyPointer (ref eq) to a specific struct type
         // - Casting from a parent enum type to one of its variant subtypes
         let source_type = match assigned_expression {
-          lir::Expression::Variable(_, src_t) => Some(src_t),
+          lir::Expression::Variable(_, src_t)
+          | lir::Expression::GlobalConstant(lir::GlobalConstant { type_: src_t, .. }) => {
+            Some(src_t)
+          }
           _ => None,
         };
         let needs_ref_cast = source_type.is_some_and(|src_t| {
@@ -528,6 +579,7 @@ impl<'a> LoweringManager<'a> {
   fn lower_expr(&mut self, e: &lir::Expression) -> wasm::InlineInstruction {
     match e {
       lir::Expression::Int32Literal(v) => wasm::InlineInstruction::Const(*v),
+      lir::Expression::FloatLiteral(v) => wasm::InlineInstruction::FloatConst(*v),
       lir::Expression::Int31Literal(v) => {
         wasm::InlineInstruction::I31New(Box::new(wasm::InlineInstruction::Const(*v)))
       }
@@ -544,6 +596,7 @@ impl<'a> LoweringManager<'a> {
         let global_name = self.string_name_mapping.get(n).unwrap();
         wasm::InlineInstruction::GlobalGet(*global_name)
       }
+      lir::Expression::GlobalConstant(g) => self.global_constant_get(g),
       lir::Expression::FnName(n, _) => {
         let index = self.function_index_mapping.get(n).unwrap();
         wasm::InlineInstruction::Const(i32::try_from(*index).unwrap())
@@ -562,6 +615,9 @@ impl<'a> LoweringManager<'a> {
       lir::Expression::Int31Literal(_) => {
         panic!("Int31Literal in place that expects struct typed values.")
       }
+      lir::Expression::FloatLiteral(_) => {
+        panic!("FloatLiteral in place that expects struct typed values.")
+      }
       lir::Expression::Variable(n, t) => {
         let lowered_type = self.type_cx.lower(t);
         let ref_type =
@@ -588,6 +644,24 @@ impl<'a> LoweringManager<'a> {
         let global_name = self.string_name_mapping.get(n).unwrap();
         (wasm::InlineInstruction::GlobalGet(*global_name), mir::TypeNameId::STR)
       }
+      lir::Expression::GlobalConstant(g) => {
+        let ref_type = self
+          .type_cx
+          .lower(&g.type_)
+          .into_reference()
+          .expect("The given expression doesn't have reference type.");
+        let global_get = self.global_constant_get(g);
+        // Same as variables: enums that can hold i31 are stored as eq and need a cast.
+        let instruction = if self.global_constant_types[&g.initializer] == wasm::Type::Eq {
+          wasm::InlineInstruction::Cast {
+            pointer_type: lir::Type::Id(ref_type),
+            value: Box::new(global_get),
+          }
+        } else {
+          global_get
+        };
+        (instruction, ref_type)
+      }
       lir::Expression::FnName(_, _) => {
         panic!("FnName in place that expects struct typed values.")
       }
@@ -607,7 +681,7 @@ impl<'a> LoweringManager<'a> {
       wasm::Type::Int31 | wasm::Type::Eq | wasm::Type::Reference(_) => {
         wasm::InlineInstruction::RefAsNonNull(Box::new(local_get))
       }
-      wasm::Type::Int32 => local_get,
+      wasm::Type::Int32 | wasm::Type::Float64 => local_get,
     }
   }
 
@@ -624,16 +698,57 @@ impl<'a> LoweringManager<'a> {
     local_get
   }
 
+  fn global_constant_get(&self, g: &lir::GlobalConstant) -> wasm::InlineInstruction {
+    let global_get = wasm::InlineInstruction::GlobalConstantGet(g.initializer.type_name);
+    // Reference typed globals are nullable, since they are only set in the start function.
+    if matches!(self.global_constant_types[&g.initializer], wasm::Type::Int32 | wasm::Type::Float64)
+    {
+      global_get
+    } else {
+      wasm::InlineInstruction::RefAsNonNull(Box::new(global_get))
+    }
+  }
+
   fn set(&mut self, n: PStr, t: wasm::Type, v: wasm::InlineInstruction) -> wasm::InlineInstruction {
     self.local_variables.insert(n, t);
     wasm::InlineInstruction::LocalSet(n, Box::new(v))
   }
 }
 
+/// Global strings keep samlang escape sequences, which are decoded into UTF-8 bytes here.
+fn string_literal_bytes(content: &str) -> Vec<u8> {
+  let mut decoded = String::with_capacity(content.len());
+  let mut chars = content.chars();
+  while let Some(c) = chars.next() {
+    if c != '\\' {
+      decoded.push(c);
+      continue;
+    }
+    match chars.next() {
+      Some('t') => decoded.push('\t'),
+      Some('v') => decoded.push('\u{0B}'),
+      Some('0') => decoded.push('\0'),
+      Some('b') => decoded.push('\u{08}'),
+      Some('f') => decoded.push('\u{0C}'),
+      Some('n') => decoded.push('\n'),
+      Some('r') => decoded.push('\r'),
+      Some('u') => {
+        let digits = chars.by_ref().skip(1).take_while(|c| *c != '}').collect::<String>();
+        let code_point = u32::from_str_radix(&digits, 16).ok().and_then(char::from_u32);
+        decoded.push(code_point.unwrap_or(char::REPLACEMENT_CHARACTER));
+      }
+      Some(escaped) => decoded.push(escaped),
+      None => decoded.push('\\'),
+    }
+  }
+  decoded.into_bytes()
+}
+
 pub(super) fn compile_lir_to_wasm(heap: &mut Heap, sources: lir::Sources) -> wasm::Module {
   let lir::Sources {
     symbol_table: source_symbol_table,
     global_variables: source_global_variables,
+    global_constants: source_global_constants,
     type_definitions: source_type_definitions,
     main_function_names: exported_functions,
     functions: source_functions,
@@ -648,10 +763,10 @@ pub(super) fn compile_lir_to_wasm(heap: &mut Heap, sources: lir::Sources) -> was
   // Collect all string bytes into a single data segment
   let mut data_segment_bytes = Vec::new();
   for (idx, hir::GlobalString(content)) in source_global_variables.iter().enumerate() {
-    let content_str = content.as_str(heap);
+    let bytes = string_literal_bytes(content.as_str(heap));
     let offset = data_segment_bytes.len();
-    let length = content_str.len();
-    data_segment_bytes.extend_from_slice(content_str.as_bytes());
+    let length = bytes.len();
+    data_segment_bytes.extend_from_slice(&bytes);
     // Create a unique global name for this string (GLOBAL_STRING_0, GLOBAL_STRING_1, ...)
     let global_name = heap.alloc_string(format!("GLOBAL_STRING_{idx}"));
     string_name_mapping.insert(*content, global_name);
@@ -690,11 +805,21 @@ pub(super) fn compile_lir_to_wasm(heap: &mut Heap, sources: lir::Sources) -> was
       mappings: wasm_mappings,
     });
   }
+  let global_constants = source_global_constants
+    .into_iter()
+    .map(|lir::GlobalConstant { initializer, type_ }| wasm::GlobalConstant {
+      initializer,
+      type_: type_cx.lower(&type_),
+    })
+    .collect_vec();
+  let global_constant_types: HashMap<_, _> =
+    global_constants.iter().map(|it| (it.initializer, it.type_)).collect();
   let mut functions = Vec::new();
   for f in &source_functions {
     let (f, new_type_cx) = LoweringManager::lower_fn(
       type_cx,
       &string_name_mapping,
+      &global_constant_types,
       &function_index_mapping,
       &type_field_mappings,
       f,
@@ -712,6 +837,7 @@ pub(super) fn compile_lir_to_wasm(heap: &mut Heap, sources: lir::Sources) -> was
     type_definitions,
     global_variables,
     gc_string_globals,
+    global_constants,
     exported_functions,
     functions,
   }
@@ -726,8 +852,8 @@ mod tests {
   use samlang_ast::{
     hir::{BinaryOperator, GlobalString},
     lir::{
-      self, Expression, Function, FunctionType, GenenalLoopVariable, INT_31_TYPE, INT_32_TYPE,
-      Sources, Statement, ZERO,
+      self, Expression, FLOAT_TYPE, Function, FunctionType, GenenalLoopVariable, INT_31_TYPE,
+      INT_32_TYPE, Sources, Statement, ZERO,
     },
     mir, wasm,
   };
@@ -765,6 +891,7 @@ mod tests {
         bytes: vec![0xDE, 0xAD, 0xBE, 0xEF],
       }],
       gc_string_globals: Vec::new(),
+      global_constants: Vec::new(),
       exported_functions: Vec::new(),
       functions: vec![wasm::Function {
         name: mir::FunctionName::PROCESS_PRINTLN,
@@ -817,6 +944,16 @@ mod tests {
     m.pretty_print(heap);
   }
 
+  #[test]
+  fn string_literal_bytes_tests() {
+    assert_eq!(b"abc".to_vec(), super::string_literal_bytes("abc"));
+    assert_eq!(b"a\tb\nc\\\"\0".to_vec(), super::string_literal_bytes(r#"a\tb\nc\\\"\0"#));
+    assert_eq!(vec![0x0b, 0x08, 0x0c, b'\r'], super::string_literal_bytes(r"\v\b\f\r"));
+    assert_eq!("é😀!".as_bytes().to_vec(), super::string_literal_bytes(r"\u{e9}\u{1F600}!"));
+    assert_eq!("日本".as_bytes().to_vec(), super::string_literal_bytes("日本"));
+    assert_eq!(b"${a} $3".to_vec(), super::string_literal_bytes(r"\${a} \$3"));
+  }
+
   #[should_panic]
   #[test]
   fn invalid_lower_expr_with_reference_type_test1() {
@@ -827,7 +964,9 @@ mod tests {
       type_cx,
       loop_cx: None,
       local_variables: BTreeMap::new(),
+      return_type: wasm::Type::Int32,
       string_name_mapping: &HashMap::new(),
+      global_constant_types: &HashMap::new(),
       function_index_mapping: &HashMap::new(),
       type_field_mappings: &HashMap::new(),
     }
@@ -844,7 +983,9 @@ mod tests {
       type_cx,
       loop_cx: None,
       local_variables: BTreeMap::new(),
+      return_type: wasm::Type::Int32,
       string_name_mapping: &HashMap::new(),
+      global_constant_types: &HashMap::new(),
       function_index_mapping: &HashMap::new(),
       type_field_mappings: &HashMap::new(),
     }
@@ -861,7 +1002,9 @@ mod tests {
       type_cx,
       loop_cx: None,
       local_variables: BTreeMap::new(),
+      return_type: wasm::Type::Int32,
       string_name_mapping: &HashMap::new(),
+      global_constant_types: &HashMap::new(),
       function_index_mapping: &HashMap::new(),
       type_field_mappings: &HashMap::new(),
     }
@@ -878,6 +1021,7 @@ mod tests {
 
     type_cx.lower(&lir::Type::new_fn(vec![INT_32_TYPE, INT_31_TYPE], INT_32_TYPE));
     assert_eq!(type_cx.lower(&INT_31_TYPE), wasm::Type::Int31);
+    assert_eq!(type_cx.lower(&FLOAT_TYPE), wasm::Type::Float64);
   }
 
   #[test]
@@ -890,6 +1034,7 @@ mod tests {
     let sources = Sources {
       symbol_table,
       global_variables: vec![],
+      global_constants: Vec::new(),
       type_definitions: vec![lir::TypeDefinition {
         name: test_struct_type,
         parent_type: None,
@@ -919,6 +1064,7 @@ mod tests {
     let sources = Sources {
       symbol_table: mir::SymbolTable::new(),
       global_variables: vec![GlobalString(heap.alloc_str_for_test("FOO"))],
+      global_constants: Vec::new(),
       type_definitions: vec![],
       main_function_names: vec![mir::FunctionName::new_for_test(PStr::MAIN_FN)],
       functions: vec![Function {
@@ -948,6 +1094,7 @@ mod tests {
     let sources = Sources {
       symbol_table,
       global_variables: vec![],
+      global_constants: Vec::new(),
       type_definitions: vec![],
       main_function_names: vec![mir::FunctionName::new_for_test(PStr::MAIN_FN)],
       functions: vec![Function {
@@ -972,6 +1119,7 @@ mod tests {
     let sources = Sources {
       symbol_table: mir::SymbolTable::new(),
       global_variables: vec![],
+      global_constants: Vec::new(),
       type_definitions: vec![],
       main_function_names: vec![mir::FunctionName::new_for_test(PStr::MAIN_FN)],
       functions: vec![Function {
@@ -992,6 +1140,7 @@ mod tests {
     let sources = Sources {
       symbol_table: mir::SymbolTable::new(),
       global_variables: vec![],
+      global_constants: Vec::new(),
       type_definitions: vec![],
       main_function_names: vec![mir::FunctionName::new_for_test(PStr::MAIN_FN)],
       functions: vec![Function {
@@ -1026,6 +1175,7 @@ mod tests {
     let sources = Sources {
       symbol_table,
       global_variables: vec![],
+      global_constants: Vec::new(),
       type_definitions: vec![lir::TypeDefinition {
         name: test_struct_type,
         parent_type: None,
@@ -1064,6 +1214,7 @@ mod tests {
     let sources = Sources {
       symbol_table,
       global_variables: vec![],
+      global_constants: Vec::new(),
       type_definitions: vec![lir::TypeDefinition {
         name: test_struct_type,
         parent_type: None,
@@ -1114,6 +1265,85 @@ mod tests {
     assert!(super::lir_expr_is_i32(&Expression::Variable(PStr::LOWER_A, INT_32_TYPE)));
     assert!(!super::lir_expr_is_i32(&Expression::Int31Literal(0)));
     assert!(!super::lir_expr_is_i32(&Expression::Variable(PStr::LOWER_A, INT_31_TYPE)));
+    assert!(super::lir_expr_is_float(&Expression::float(1.0)));
+    assert!(super::lir_expr_is_float(&Expression::Variable(PStr::LOWER_A, FLOAT_TYPE)));
+    assert!(!super::lir_expr_is_float(&Expression::Int32Literal(0)));
+
+    assert_eq!(wasm::Type::Float64, super::binary_result_type(BinaryOperator::FPLUS));
+    assert_eq!(wasm::Type::Int32, super::binary_result_type(BinaryOperator::FLT));
+    assert_eq!(wasm::Type::Int32, super::binary_result_type(BinaryOperator::PLUS));
+  }
+
+  #[test]
+  fn float_lowering_test() {
+    let heap = &mut Heap::new();
+    let v = heap.alloc_str_for_test("v");
+    let sources = Sources {
+      symbol_table: mir::SymbolTable::new(),
+      global_variables: Vec::new(),
+      global_constants: Vec::new(),
+      type_definitions: Vec::new(),
+      main_function_names: vec![mir::FunctionName::new_for_test(PStr::MAIN_FN)],
+      functions: vec![Function {
+        name: mir::FunctionName::new_for_test(PStr::MAIN_FN),
+        parameters: vec![v],
+        type_: lir::Type::new_fn_unwrapped(vec![lir::Type::Id(mir::TypeNameId::VEC)], FLOAT_TYPE),
+        body: vec![
+          // vec.push(1.5) — f64 element args are boxed.
+          Statement::Call {
+            callee: Expression::FnName(
+              mir::FunctionName::VEC_PUSH,
+              lir::Type::new_fn_unwrapped(
+                vec![lir::Type::Id(mir::TypeNameId::VEC), FLOAT_TYPE],
+                INT_32_TYPE,
+              ),
+            ),
+            arguments: vec![
+              Expression::Variable(v, lir::Type::Id(mir::TypeNameId::VEC)),
+              Expression::float(1.5),
+            ],
+            return_type: INT_32_TYPE,
+            return_collector: None,
+          },
+          // vec.get(0): float — unboxed on the way out.
+          Statement::Call {
+            callee: Expression::FnName(
+              mir::FunctionName::VEC_GET,
+              lir::Type::new_fn_unwrapped(
+                vec![lir::Type::Id(mir::TypeNameId::VEC), INT_32_TYPE],
+                FLOAT_TYPE,
+              ),
+            ),
+            arguments: vec![Expression::Variable(v, lir::Type::Id(mir::TypeNameId::VEC)), ZERO],
+            return_type: FLOAT_TYPE,
+            return_collector: Some(PStr::LOWER_A),
+          },
+          Statement::Binary {
+            name: PStr::LOWER_B,
+            operator: BinaryOperator::FMUL,
+            e1: Expression::Variable(PStr::LOWER_A, FLOAT_TYPE),
+            e2: Expression::float(2.0),
+          },
+          // Float.toInt(b) — Float statics take a (ref eq) placeholder receiver.
+          Statement::Call {
+            callee: Expression::FnName(
+              mir::FunctionName::FLOAT_TO_INT,
+              lir::Type::new_fn_unwrapped(vec![INT_32_TYPE, FLOAT_TYPE], INT_32_TYPE),
+            ),
+            arguments: vec![ZERO, Expression::Variable(PStr::LOWER_B, FLOAT_TYPE)],
+            return_type: INT_32_TYPE,
+            return_collector: Some(PStr::LOWER_C),
+          },
+        ],
+        return_value: Expression::Variable(PStr::LOWER_B, FLOAT_TYPE),
+      }],
+    };
+    let actual = super::compile_lir_to_wasm(heap, sources).pretty_print(heap);
+    assert!(actual.contains("(call $__$wrapF64 (f64.const 1.5))"));
+    assert!(actual.contains("(call $__$unwrapF64 (call $__Vec$get"));
+    assert!(actual.contains("(local $b f64)"));
+    assert!(actual.contains("(f64.mul (local.get $a) (f64.const 2.0))"));
+    assert!(actual.contains("(call $__Float$toInt (ref.i31 (i32.const 0)) (local.get $b))"));
   }
 
   #[test]
@@ -1128,6 +1358,7 @@ mod tests {
     let sources = Sources {
       symbol_table,
       global_variables: Vec::new(),
+      global_constants: Vec::new(),
       type_definitions: Vec::new(),
       main_function_names: vec![mir::FunctionName::new_for_test(PStr::MAIN_FN)],
       functions: vec![Function {
@@ -1251,6 +1482,7 @@ mod tests {
         GlobalString(heap.alloc_str_for_test("FOO")),
         GlobalString(heap.alloc_str_for_test("BAR")),
       ],
+      global_constants: Vec::new(),
       type_definitions: vec![
         lir::TypeDefinition {
           name: test_struct_type,
@@ -1410,6 +1642,12 @@ mod tests {
               Expression::Variable(PStr::LOWER_F, INT_32_TYPE),
               ZERO,
             ),
+            Statement::binary(
+              heap.alloc_str_for_test("bin_ashr"),
+              BinaryOperator::ASHR,
+              Expression::Variable(PStr::LOWER_F, INT_32_TYPE),
+              ZERO,
+            ),
             Statement::binary(
               heap.alloc_str_for_test("bin_lt"),
               BinaryOperator::LT,
@@ -1543,6 +1781,7 @@ mod tests {
 (type $_Str (array (mut i8)))
 (type $_VecData (array (mut (ref null eq))))
 (type $_Vec (struct (field (mut (ref $_VecData))) (field (mut i32))))
+(type $_Float (struct (field f64)))
 (type $__t0 (func (result i32)))
 (type $__t1 (func (param (ref eq)) (result i32)))
 (type $_TestStruct (struct (field i32) (field i32) (field i32) (field i32)))
@@ -1565,6 +1804,7 @@ mod tests {
   (local $bin7 i32)
   (local $bin8 i32)
   (local $bin9 i32)
+  (local $bin_ashr i32)
   (local $bin_gt i32)
   (local $bin_land i32)
   (local $bin_lor i32)
@@ -1628,6 +1868,7 @@ mod tests {
   (local.set $bin_lor (i32.or (local.get $f) (i32.const 0)))
   (local.set $bin_shl (i32.shl (local.get $f) (i32.const 0)))
   (local.set $bin_shr (i32.shr_u (local.get $f) (i32.const 0)))
+  (local.set $bin_ashr (i32.shr_s (local.get $f) (i32.const 0)))
   (local.set $bin_lt (i32.lt_s (local.get $f) (i32.const 0)))
   (local.set $bin_gt (i32.gt_s (local.get $f) (i32.const 0)))
   (local.set $bin7 (ref.eq (ref.i31 (i32.const 1)) (ref.i31 (i32.const 2))))
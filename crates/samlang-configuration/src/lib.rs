@@ -7,6 +7,8 @@ pub struct ProjectConfiguration {
   pub output_directory: String,
   pub entry_points: Vec<String>,
   pub ignores: Vec<String>,
+  /// Whether `assert` expressions are removed from the compiled output, as in release builds.
+  pub strip_asserts: bool,
   pub dangerously_allow_libdef_shadowing: bool,
 }
 
@@ -47,6 +49,10 @@ fn parse_configuration(configuration_string: &str) -> Option<ProjectConfiguratio
   let output_directory = parse_string_field(&parsed, "outputDirectory", "out")?;
   let entry_points = parse_string_array_field(&parsed, "entryPoints")?;
   let ignores = parse_string_array_field(&parsed, "ignores")?;
+  let strip_asserts = match parsed.get("stripAsserts") {
+    Some(v) => v.as_bool()?,
+    None => false,
+  };
   let dangerously_allow_libdef_shadowing =
     parsed.get("__dangerously_allow_libdef_shadowing__").and_then(|v| v.as_bool()).unwrap_or(false);
   Some(ProjectConfiguration {
@@ -54,6 +60,7 @@ fn parse_configuration(configuration_string: &str) -> Option<ProjectConfiguratio
     output_directory,
     entry_points,
     ignores,
+    strip_asserts,
     dangerously_allow_libdef_shadowing,
   })
 }
@@ -105,6 +112,7 @@ mod tests {
           output_directory: "out".to_string(),
           entry_points: Vec::new(),
           ignores: Vec::new(),
+          strip_asserts: false,
           dangerously_allow_libdef_shadowing: false,
         }
       )
@@ -116,6 +124,7 @@ mod tests {
         output_directory: "out".to_string(),
         entry_points: Vec::new(),
         ignores: Vec::new(),
+        strip_asserts: false,
         dangerously_allow_libdef_shadowing: false,
       },
       parse_configuration("{}").unwrap()
@@ -126,6 +135,7 @@ mod tests {
         output_directory: "out".to_string(),
         entry_points: Vec::new(),
         ignores: Vec::new(),
+        strip_asserts: false,
         dangerously_allow_libdef_shadowing: false,
       },
       parse_configuration("{\"sourceDirectory\": \"source\"}").unwrap()
@@ -136,6 +146,7 @@ mod tests {
         output_directory: "out-out".to_string(),
         entry_points: Vec::new(),
         ignores: Vec::new(),
+        strip_asserts: false,
         dangerously_allow_libdef_shadowing: false,
       },
       parse_configuration("{\"outputDirectory\": \"out-out\"}").unwrap()
@@ -146,6 +157,7 @@ mod tests {
         output_directory: "output".to_string(),
         entry_points: vec!["a".to_string(), "b".to_string()],
         ignores: vec!["c".to_string(), "d".to_string()],
+        strip_asserts: true,
         dangerously_allow_libdef_shadowing: true,
       },
       parse_configuration(
@@ -154,6 +166,7 @@ mod tests {
           "outputDirectory": "output",
           "entryPoints": ["a", "b"],
           "ignores": ["c", "d"],
+          "stripAsserts": true,
           "__dangerously_allow_libdef_shadowing__": true
         }"#
       )
@@ -175,6 +188,7 @@ mod tests {
     assert!(parse_configuration("{ \"entryPoints\": \"3\" }").is_none());
     assert!(parse_configuration("{ \"entryPoints\": [1, \"\"] }").is_none());
     assert!(parse_configuration("{ \"ignores\": [1, \"\"] }").is_none());
+    assert!(parse_configuration("{ \"stripAsserts\": \"true\" }").is_none());
   }
 
   #[test]
@@ -1,6 +1,6 @@
 use dupe::Dupe;
 use itertools::Itertools;
-use samlang_ast::{Description, Location, Reason};
+use samlang_ast::{Description, Location, Reason, source::Visibility};
 use samlang_heap::{Heap, ModuleReference, PStr};
 use std::collections::{BTreeSet, HashMap};
 
@@ -24,7 +24,7 @@ enum PrintableError<'a> {
 impl PrintableError<'_> {
   fn get_loc_reference_opt(&self) -> Option<Location> {
     match self {
-      PrintableError::LocationReference(l) => Some(**l),
+      PrintableError::LocationReference(l) => Some(l.resolve_synthesized()),
       _ => None,
     }
   }
@@ -213,6 +213,7 @@ mod printer {
     }
 
     pub(super) fn print_optional_ref(&mut self, heap: &Heap, location: &Location) {
+      let location = &location.resolve_synthesized();
       // In IDE state, the code frame should be dropped,
       // since it's right at the location of diagnostics.
       if self.style == super::ErrorPrinterStyle::IDE {
@@ -497,6 +498,7 @@ enum IncompatibilityNode {
   FunctionParametersArity(usize, usize),
   TypeArgumentsArity(usize, usize),
   TypeParametersArity(usize, usize),
+  TupleArity(usize, usize),
 }
 
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
@@ -545,6 +547,10 @@ impl StackableError {
   pub fn add_type_params_arity_error(&mut self, lower: usize, upper: usize) {
     self.rev_stack.push(IncompatibilityNode::TypeParametersArity(lower, upper));
   }
+
+  pub fn add_tuple_arity_error(&mut self, lower: usize, upper: usize) {
+    self.rev_stack.push(IncompatibilityNode::TupleArity(lower, upper));
+  }
 }
 
 #[cfg(test)]
@@ -575,16 +581,29 @@ mod stackable_error_tests {
 
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub enum ErrorDetail {
+  CannotAssignToImmutable { name: PStr },
+  CannotCaptureMutableVariable { name: PStr },
   CannotResolveClass { module_reference: ModuleReference, name: PStr },
   CannotResolveMember { parent: Description, member: PStr },
   CannotResolveModule { module_reference: ModuleReference },
   CannotResolveName { name: PStr },
+  CyclicConstDefinition { name: PStr },
   CyclicTypeDefinition { type_: Description },
+  DuplicateNamedArgument { name: PStr },
   ElementMissing { parent: Description, index: usize },
+  GenericMethodInInterfaceValue { interface_name: PStr, method_name: PStr },
+  IllegalBreakOutsideLoop,
   IllegalFunctionInInterface,
+  IllegalLetElseBlock,
+  IllegalReturnInLambda,
+  IllegalReturnOutsideFunction,
+  IllegalTemplateStringHole { type_: Description },
+  IllegalTryInFunction { expected: PStr },
+  IllegalTryOperand { type_: Description },
   IncompatibleSubType { lower: Description, upper: Description },
   IncompatibleTypeKind { lower: Description, upper: Description },
   InvalidSyntax(String),
+  MissingArgument { name: PStr },
   MissingClassMemberDefinitions { missing_definitions: Vec<PStr> },
   MissingExport { module_reference: ModuleReference, name: PStr },
   NameAlreadyBound { name: PStr, old_loc: Location },
@@ -593,16 +612,30 @@ pub enum ErrorDetail {
   NonExhaustiveMatch { counter_example: Description },
   NotAnEnum { description: Description },
   NotAStruct { description: Description },
+  NotCompileTimeEvaluable,
+  NotVisible { name: PStr, visibility: Visibility },
   OrPatternInconsistentBindings { expected: Vec<PStr>, actual: Vec<PStr> },
   Stacked(StackableError),
   TypeParameterNameMismatch { expected: Vec<Description> },
   Underconstrained,
+  UnknownNamedArgument { name: PStr },
+  UnsupportedDerive { derive: PStr, name: PStr, is_variant: bool, type_: Description },
   UselessPattern { only_pattern: bool },
 }
 
 impl ErrorDetail {
   fn push_to_printable_stream<'a>(&'a self, printable_stream: &mut PrintableStream<'a>) {
     match self {
+      ErrorDetail::CannotAssignToImmutable { name } => {
+        printable_stream.push_text("Cannot assign to `");
+        printable_stream.push_pstr(name);
+        printable_stream.push_text("`, since it is not declared with `var`.");
+      }
+      ErrorDetail::CannotCaptureMutableVariable { name } => {
+        printable_stream.push_text("Mutable variable `");
+        printable_stream.push_pstr(name);
+        printable_stream.push_text("` cannot be captured by a lambda.");
+      }
       ErrorDetail::CannotResolveClass { module_reference: _, name } => {
         printable_stream.push_text("Cannot resolve class `");
         printable_stream.push_pstr(name);
@@ -625,11 +658,21 @@ impl ErrorDetail {
         printable_stream.push_pstr(name);
         printable_stream.push_text("`.");
       }
+      ErrorDetail::CyclicConstDefinition { name } => {
+        printable_stream.push_text("Constant `");
+        printable_stream.push_pstr(name);
+        printable_stream.push_text("` has a cyclic definition.");
+      }
       ErrorDetail::CyclicTypeDefinition { type_ } => {
         printable_stream.push_text("Type `");
         printable_stream.push_description(type_);
         printable_stream.push_text("` has a cyclic definition.");
       }
+      ErrorDetail::DuplicateNamedArgument { name } => {
+        printable_stream.push_text("Parameter `");
+        printable_stream.push_pstr(name);
+        printable_stream.push_text("` is already given an argument.");
+      }
       ErrorDetail::ElementMissing { parent, index } => {
         printable_stream.push_text("Cannot access member of `");
         printable_stream.push_description(parent);
@@ -637,9 +680,50 @@ impl ErrorDetail {
         printable_stream.push_size(*index);
         printable_stream.push_text(".");
       }
+      ErrorDetail::GenericMethodInInterfaceValue { interface_name, method_name } => {
+        printable_stream.push_text("`");
+        printable_stream.push_pstr(interface_name);
+        printable_stream.push_text("` cannot be used as a value type, since its method `");
+        printable_stream.push_pstr(method_name);
+        printable_stream.push_text("` is generic.");
+      }
+      ErrorDetail::IllegalBreakOutsideLoop => {
+        printable_stream.push_text("`break` is only allowed inside loops.");
+      }
       ErrorDetail::IllegalFunctionInInterface => {
         printable_stream.push_text("Function declarations are not allowed in interfaces.");
       }
+      ErrorDetail::IllegalLetElseBlock => {
+        printable_stream.push_text(
+          "The `else` block of `let ... else` must end with `return`, `break` or `Process.panic`.",
+        );
+      }
+      ErrorDetail::IllegalReturnInLambda => {
+        printable_stream.push_text("`return` is not allowed inside lambdas.");
+      }
+      ErrorDetail::IllegalReturnOutsideFunction => {
+        printable_stream.push_text("`return` is only allowed inside function bodies.");
+      }
+      ErrorDetail::IllegalTemplateStringHole { type_ } => {
+        printable_stream.push_text("`");
+        printable_stream.push_description(type_);
+        printable_stream.push_text(
+          "` cannot be used in a template string. Expected `Str`, `int` or a type with ",
+        );
+        printable_stream.push_text("a `toString(): Str` method.");
+      }
+      ErrorDetail::IllegalTryInFunction { expected } => {
+        printable_stream.push_text("`?` on `");
+        printable_stream.push_pstr(expected);
+        printable_stream.push_text("` is only allowed in functions that return `");
+        printable_stream.push_pstr(expected);
+        printable_stream.push_text("`.");
+      }
+      ErrorDetail::IllegalTryOperand { type_ } => {
+        printable_stream.push_text("`?` can only be applied to `Option` or `Result`, but got `");
+        printable_stream.push_description(type_);
+        printable_stream.push_text("`.");
+      }
       ErrorDetail::IncompatibleTypeKind { lower, upper } => {
         printable_stream.push_text("`");
         printable_stream.push_description(lower);
@@ -659,6 +743,11 @@ impl ErrorDetail {
       ErrorDetail::InvalidSyntax(reason) => {
         printable_stream.push_text(reason);
       }
+      ErrorDetail::MissingArgument { name } => {
+        printable_stream.push_text("Missing an argument for parameter `");
+        printable_stream.push_pstr(name);
+        printable_stream.push_text("`.");
+      }
       ErrorDetail::MissingClassMemberDefinitions { missing_definitions } => {
         printable_stream.push_text("The following members must be implemented for the class:");
         for tag in missing_definitions {
@@ -716,6 +805,16 @@ impl ErrorDetail {
         printable_stream.push_description(description);
         printable_stream.push_text("` is not an instance of a struct class.");
       }
+      ErrorDetail::NotCompileTimeEvaluable => {
+        printable_stream.push_text("This expression cannot be evaluated at compile time.");
+      }
+      ErrorDetail::NotVisible { name, visibility } => {
+        printable_stream.push_text("`");
+        printable_stream.push_pstr(name);
+        printable_stream.push_text("` is ");
+        printable_stream.push_text(visibility.as_str());
+        printable_stream.push_text(" and not visible here.");
+      }
       ErrorDetail::OrPatternInconsistentBindings { expected, actual } => {
         printable_stream
           .push_text("Or-pattern alternatives must bind the same variables. Expected bindings: [");
@@ -777,6 +876,13 @@ impl ErrorDetail {
               printable_stream.push_size(*u);
               printable_stream.push_text(".");
             }
+            IncompatibilityNode::TupleArity(l, u) => {
+              printable_stream.push_text("Tuple arity of ");
+              printable_stream.push_size(*l);
+              printable_stream.push_text(" is incompatible with tuple arity of ");
+              printable_stream.push_size(*u);
+              printable_stream.push_text(".");
+            }
             IncompatibilityNode::TypeParametersArity(l, u) => {
               printable_stream.push_text("Type parameter arity of ");
               printable_stream.push_size(*l);
@@ -807,6 +913,29 @@ impl ErrorDetail {
           "There is not enough context information to decide the type of this expression.",
         );
       }
+      ErrorDetail::UnknownNamedArgument { name } => {
+        printable_stream.push_text("There is no parameter named `");
+        printable_stream.push_pstr(name);
+        printable_stream.push_text("`.");
+      }
+      ErrorDetail::UnsupportedDerive { derive, name, is_variant: false, type_ } => {
+        printable_stream.push_text("Field `");
+        printable_stream.push_pstr(name);
+        printable_stream.push_text(": ");
+        printable_stream.push_description(type_);
+        printable_stream.push_text("` does not support derive `");
+        printable_stream.push_pstr(derive);
+        printable_stream.push_text("`.");
+      }
+      ErrorDetail::UnsupportedDerive { derive, name, is_variant: true, type_ } => {
+        printable_stream.push_text("Data `");
+        printable_stream.push_description(type_);
+        printable_stream.push_text("` of variant `");
+        printable_stream.push_pstr(name);
+        printable_stream.push_text("` does not support derive `");
+        printable_stream.push_pstr(derive);
+        printable_stream.push_text("`.");
+      }
       ErrorDetail::UselessPattern { only_pattern: true } => {
         printable_stream.push_text("The pattern is irrefutable.");
       }
@@ -897,6 +1026,10 @@ impl ErrorSet {
     !self.errors.is_empty()
   }
 
+  pub fn error_count(&self) -> usize {
+    self.errors.len()
+  }
+
   pub fn errors(&self) -> Vec<&CompileTimeError> {
     self.errors.iter().collect()
   }
@@ -962,6 +1095,8 @@ impl ErrorSet {
   }
 
   fn report_error(&mut self, location: Location, detail: ErrorDetail) {
+    // Errors in code synthesized by the parser point at what it is synthesized for.
+    let location = location.resolve_synthesized();
     self.errors.insert(CompileTimeError { location, detail });
   }
 
@@ -991,22 +1126,76 @@ impl ErrorSet {
     self.report_error(loc, ErrorDetail::CannotResolveClass { module_reference, name })
   }
 
+  pub fn report_cannot_assign_to_immutable_error(&mut self, loc: Location, name: PStr) {
+    self.report_error(loc, ErrorDetail::CannotAssignToImmutable { name })
+  }
+
+  pub fn report_cannot_capture_mutable_variable_error(&mut self, loc: Location, name: PStr) {
+    self.report_error(loc, ErrorDetail::CannotCaptureMutableVariable { name })
+  }
+
   pub fn report_cannot_resolve_name_error(&mut self, loc: Location, name: PStr) {
     self.report_error(loc, ErrorDetail::CannotResolveName { name })
   }
 
+  pub fn report_cyclic_const_definition_error(&mut self, loc: Location, name: PStr) {
+    self.report_error(loc, ErrorDetail::CyclicConstDefinition { name });
+  }
+
   pub fn report_cyclic_type_definition_error(&mut self, type_loc: Location, type_: Description) {
     self.report_error(type_loc, ErrorDetail::CyclicTypeDefinition { type_ });
   }
 
+  pub fn report_duplicate_named_argument_error(&mut self, loc: Location, name: PStr) {
+    self.report_error(loc, ErrorDetail::DuplicateNamedArgument { name })
+  }
+
   pub fn report_element_missing_error(&mut self, loc: Location, parent: Description, index: usize) {
     self.report_error(loc, ErrorDetail::ElementMissing { parent, index })
   }
 
+  pub fn report_generic_method_in_interface_value_error(
+    &mut self,
+    loc: Location,
+    interface_name: PStr,
+    method_name: PStr,
+  ) {
+    self
+      .report_error(loc, ErrorDetail::GenericMethodInInterfaceValue { interface_name, method_name })
+  }
+
   pub fn report_illegal_function_in_interface(&mut self, loc: Location) {
     self.report_error(loc, ErrorDetail::IllegalFunctionInInterface);
   }
 
+  pub fn report_illegal_let_else_block(&mut self, loc: Location) {
+    self.report_error(loc, ErrorDetail::IllegalLetElseBlock);
+  }
+
+  pub fn report_illegal_break_outside_loop(&mut self, loc: Location) {
+    self.report_error(loc, ErrorDetail::IllegalBreakOutsideLoop);
+  }
+
+  pub fn report_illegal_return_in_lambda(&mut self, loc: Location) {
+    self.report_error(loc, ErrorDetail::IllegalReturnInLambda);
+  }
+
+  pub fn report_illegal_return_outside_function(&mut self, loc: Location) {
+    self.report_error(loc, ErrorDetail::IllegalReturnOutsideFunction);
+  }
+
+  pub fn report_illegal_template_string_hole(&mut self, loc: Location, type_: Description) {
+    self.report_error(loc, ErrorDetail::IllegalTemplateStringHole { type_ });
+  }
+
+  pub fn report_illegal_try_in_function(&mut self, loc: Location, expected: PStr) {
+    self.report_error(loc, ErrorDetail::IllegalTryInFunction { expected });
+  }
+
+  pub fn report_illegal_try_operand(&mut self, loc: Location, type_: Description) {
+    self.report_error(loc, ErrorDetail::IllegalTryOperand { type_ });
+  }
+
   pub fn report_incompatible_subtype_error(
     &mut self,
     loc: Location,
@@ -1029,6 +1218,10 @@ impl ErrorSet {
     self.report_error(loc, ErrorDetail::InvalidSyntax(reason))
   }
 
+  pub fn report_missing_argument_error(&mut self, loc: Location, name: PStr) {
+    self.report_error(loc, ErrorDetail::MissingArgument { name })
+  }
+
   pub fn report_missing_class_member_definition_error(
     &mut self,
     loc: Location,
@@ -1084,6 +1277,14 @@ impl ErrorSet {
     self.report_error(loc, ErrorDetail::NotAStruct { description })
   }
 
+  pub fn report_not_compile_time_evaluable_error(&mut self, loc: Location) {
+    self.report_error(loc, ErrorDetail::NotCompileTimeEvaluable)
+  }
+
+  pub fn report_not_visible_error(&mut self, loc: Location, name: PStr, visibility: Visibility) {
+    self.report_error(loc, ErrorDetail::NotVisible { name, visibility })
+  }
+
   pub fn report_or_pattern_inconsistent_bindings_error(
     &mut self,
     loc: Location,
@@ -1109,6 +1310,21 @@ impl ErrorSet {
     self.report_error(loc, ErrorDetail::Underconstrained)
   }
 
+  pub fn report_unknown_named_argument_error(&mut self, loc: Location, name: PStr) {
+    self.report_error(loc, ErrorDetail::UnknownNamedArgument { name })
+  }
+
+  pub fn report_unsupported_derive_error(
+    &mut self,
+    loc: Location,
+    derive: PStr,
+    name: PStr,
+    is_variant: bool,
+    type_: Description,
+  ) {
+    self.report_error(loc, ErrorDetail::UnsupportedDerive { derive, name, is_variant, type_ })
+  }
+
   pub fn report_useless_pattern_error(&mut self, loc: Location, only_pattern: bool) {
     self.report_error(loc, ErrorDetail::UselessPattern { only_pattern })
   }
@@ -1248,7 +1464,11 @@ Found 2 errors."#
       ModuleReference::DUMMY,
       heap.alloc_str_for_test("global"),
     );
+    error_set.report_cannot_assign_to_immutable_error(Location::dummy(), PStr::LOWER_A);
+    error_set.report_cannot_capture_mutable_variable_error(Location::dummy(), PStr::LOWER_B);
+    error_set.report_cyclic_const_definition_error(Location::dummy(), PStr::UPPER_A);
     error_set.report_cyclic_type_definition_error(Location::dummy(), Description::IntType);
+    error_set.report_duplicate_named_argument_error(Location::dummy(), PStr::LOWER_A);
     error_set.report_element_missing_error(Location::dummy(), Description::GeneralNominalType, 1);
     error_set.report_incompatible_type_kind_error(
       Location::dummy(),
@@ -1262,11 +1482,24 @@ Found 2 errors."#
     );
     error_set.report_invalid_syntax_error(Location::dummy(), "bad code".to_string());
     error_set.report_illegal_function_in_interface(Location::dummy());
+    error_set.report_generic_method_in_interface_value_error(
+      Location::dummy(),
+      PStr::UPPER_A,
+      PStr::LOWER_B,
+    );
+    error_set.report_illegal_break_outside_loop(Location::dummy());
+    error_set.report_illegal_let_else_block(Location::dummy());
+    error_set.report_illegal_return_in_lambda(Location::dummy());
+    error_set.report_illegal_return_outside_function(Location::dummy());
+    error_set.report_illegal_template_string_hole(Location::dummy(), Description::BoolType);
+    error_set.report_illegal_try_in_function(Location::dummy(), PStr::OPTION_TYPE);
+    error_set.report_illegal_try_operand(Location::dummy(), Description::IntType);
     error_set.report_cannot_resolve_member_error(
       Location::dummy(),
       Description::NominalType { name: heap.alloc_str_for_test("Foo"), type_args: Vec::new() },
       heap.alloc_str_for_test("bar"),
     );
+    error_set.report_missing_argument_error(Location::dummy(), PStr::LOWER_B);
     error_set.report_missing_class_member_definition_error(
       Location::dummy(),
       vec![heap.alloc_str_for_test("foo"), heap.alloc_str_for_test("bar")],
@@ -1285,6 +1518,8 @@ Found 2 errors."#
     error_set.report_non_exhaustive_match_error(Location::dummy(), Description::IntType);
     error_set.report_not_an_enum_error(Location::dummy(), Description::IntType);
     error_set.report_not_a_struct_error(Location::dummy(), Description::IntType);
+    error_set.report_not_compile_time_evaluable_error(Location::dummy());
+    error_set.report_not_visible_error(Location::dummy(), PStr::LOWER_A, Visibility::Internal);
     error_set.report_stackable_error(Location::dummy(), {
       let mut stacked = StackableError::new();
       stacked.add_type_incompatibility_error(
@@ -1299,8 +1534,9 @@ Found 2 errors."#
         Reason::dummy(),
         Description::AnyType,
         Reason::dummy(),
-        Description::AnyType,
+        Description::TupleType(vec![Description::IntType, Description::AnyType]),
       );
+      stacked.add_tuple_arity_error(2, 3);
       stacked.add_type_params_arity_error(1, 2);
       stacked
     });
@@ -1316,12 +1552,37 @@ Found 2 errors."#
       vec![heap.alloc_str_for_test("x"), heap.alloc_str_for_test("z")],
     );
     error_set.report_underconstrained_error(Location::dummy());
+    error_set.report_unknown_named_argument_error(Location::dummy(), PStr::LOWER_C);
+    error_set.report_unsupported_derive_error(
+      Location::dummy(),
+      PStr::COMPARE,
+      PStr::LOWER_F,
+      false,
+      Description::IntType,
+    );
+    error_set.report_unsupported_derive_error(
+      Location::dummy(),
+      PStr::TO_STRING,
+      PStr::UPPER_A,
+      true,
+      Description::BoolType,
+    );
     error_set.report_useless_pattern_error(Location::dummy(), false);
     error_set.report_useless_pattern_error(Location::dummy(), true);
 
     let expected_errors = r#"
 Error -------------------------------------- DUMMY.sam:DUMMY
 
+Cannot assign to `a`, since it is not declared with `var`.
+
+
+Error -------------------------------------- DUMMY.sam:DUMMY
+
+Mutable variable `b` cannot be captured by a lambda.
+
+
+Error -------------------------------------- DUMMY.sam:DUMMY
+
 Cannot resolve class `global`.
 
 
@@ -1335,21 +1596,71 @@ Error -------------------------------------- DUMMY.sam:DUMMY
 Cannot resolve module `DUMMY`.
 
 
+Error -------------------------------------- DUMMY.sam:DUMMY
+
+Constant `A` has a cyclic definition.
+
+
 Error -------------------------------------- DUMMY.sam:DUMMY
 
 Type `int` has a cyclic definition.
 
 
+Error -------------------------------------- DUMMY.sam:DUMMY
+
+Parameter `a` is already given an argument.
+
+
 Error -------------------------------------- DUMMY.sam:DUMMY
 
 Cannot access member of `nominal type` at index 1.
 
 
+Error -------------------------------------- DUMMY.sam:DUMMY
+
+`A` cannot be used as a value type, since its method `b` is generic.
+
+
+Error -------------------------------------- DUMMY.sam:DUMMY
+
+`break` is only allowed inside loops.
+
+
 Error -------------------------------------- DUMMY.sam:DUMMY
 
 Function declarations are not allowed in interfaces.
 
 
+Error -------------------------------------- DUMMY.sam:DUMMY
+
+The `else` block of `let ... else` must end with `return`, `break` or `Process.panic`.
+
+
+Error -------------------------------------- DUMMY.sam:DUMMY
+
+`return` is not allowed inside lambdas.
+
+
+Error -------------------------------------- DUMMY.sam:DUMMY
+
+`return` is only allowed inside function bodies.
+
+
+Error -------------------------------------- DUMMY.sam:DUMMY
+
+`bool` cannot be used in a template string. Expected `Str`, `int` or a type with a `toString(): Str` method.
+
+
+Error -------------------------------------- DUMMY.sam:DUMMY
+
+`?` on `Option` is only allowed in functions that return `Option`.
+
+
+Error -------------------------------------- DUMMY.sam:DUMMY
+
+`?` can only be applied to `Option` or `Result`, but got `int`.
+
+
 Error -------------------------------------- DUMMY.sam:DUMMY
 
 `int` is not a subtype of `bool`.
@@ -1365,6 +1676,11 @@ Error -------------------------------------- DUMMY.sam:DUMMY
 bad code
 
 
+Error -------------------------------------- DUMMY.sam:DUMMY
+
+Missing an argument for parameter `b`.
+
+
 Error -------------------------------------- DUMMY.sam:DUMMY
 
 The following members must be implemented for the class:
@@ -1410,6 +1726,16 @@ Error -------------------------------------- DUMMY.sam:DUMMY
 `int` is not an instance of a struct class.
 
 
+Error -------------------------------------- DUMMY.sam:DUMMY
+
+This expression cannot be evaluated at compile time.
+
+
+Error -------------------------------------- DUMMY.sam:DUMMY
+
+`a` is internal and not visible here.
+
+
 Error -------------------------------------- DUMMY.sam:DUMMY
 
 Or-pattern alternatives must bind the same variables. Expected bindings: [x, y], actual bindings: [x, z].
@@ -1418,10 +1744,11 @@ Or-pattern alternatives must bind the same variables. Expected bindings: [x, y],
 Error -------------------------------------- DUMMY.sam:DUMMY
 
 Type parameter arity of 1 is incompatible with type parameter arity of 2.
-- `any` is incompatible with `any`.
-  - Function parameter arity of 0 is incompatible with function parameter arity of 0.
-    - Type argument arity of 0 is incompatible with type argument arity of 0.
-      - `any`  is incompatible with `any` .
+- Tuple arity of 2 is incompatible with tuple arity of 3.
+  - `any` is incompatible with `(int, any)`.
+    - Function parameter arity of 0 is incompatible with function parameter arity of 0.
+      - Type argument arity of 0 is incompatible with type argument arity of 0.
+        - `any`  is incompatible with `any` .
 
 
 Error -------------------------------------- DUMMY.sam:DUMMY
@@ -1444,6 +1771,21 @@ Error -------------------------------------- DUMMY.sam:DUMMY
 There is not enough context information to decide the type of this expression.
 
 
+Error -------------------------------------- DUMMY.sam:DUMMY
+
+There is no parameter named `c`.
+
+
+Error -------------------------------------- DUMMY.sam:DUMMY
+
+Field `f: int` does not support derive `compare`.
+
+
+Error -------------------------------------- DUMMY.sam:DUMMY
+
+Data `bool` of variant `A` does not support derive `toString`.
+
+
 Error -------------------------------------- DUMMY.sam:DUMMY
 
 The pattern is already covered by previous cases.
@@ -1460,13 +1802,14 @@ Very/Very/Very/Very/Very/Very/Very/Very/Very/Very/Very/Very/Very/Very/Very/Long.
 Cannot resolve name `global`.
 
 
-Found 26 errors.
+Found 44 errors.
 "#;
     assert_eq!(
       expected_errors.trim(),
       error_set.pretty_print_error_messages_no_frame_for_test(&heap).trim()
     );
     assert!(error_set.has_errors());
+    assert_eq!(44, error_set.error_count());
     assert_eq!(2, error_set.group_errors().len());
   }
 }
@@ -124,7 +124,8 @@ impl PStr {
     self.0.as_inline_str().unwrap_or_else(|id| &heap.str_pointer_table[id as usize])
   }
 
-  fn create_inline_opt(s: &str) -> Option<PStr> {
+  /// Creates a string without the heap, if it's short enough to be stored inline.
+  pub fn create_inline_opt(s: &str) -> Option<PStr> {
     PStrPrivateRepr::from_str_opt(s).map(PStr)
   }
 
@@ -220,6 +221,30 @@ impl PStr {
     })
   }
 
+  pub const fn ten_letter_literal(bytes: &[u8; 10]) -> PStr {
+    PStr(PStrPrivateRepr {
+      inline: PStrPrivateReprInline {
+        size: 10,
+        storage: [
+          bytes[0], bytes[1], bytes[2], bytes[3], bytes[4], bytes[5], bytes[6], bytes[7], bytes[8],
+          bytes[9], 0, 0, 0, 0, 0,
+        ],
+      },
+    })
+  }
+
+  pub const fn eleven_letter_literal(bytes: &[u8; 11]) -> PStr {
+    PStr(PStrPrivateRepr {
+      inline: PStrPrivateReprInline {
+        size: 11,
+        storage: [
+          bytes[0], bytes[1], bytes[2], bytes[3], bytes[4], bytes[5], bytes[6], bytes[7], bytes[8],
+          bytes[9], bytes[10], 0, 0, 0, 0,
+        ],
+      },
+    })
+  }
+
   pub const fn twelve_letter_literal(bytes: &[u8; 12]) -> PStr {
     PStr(PStrPrivateRepr {
       inline: PStrPrivateReprInline {
@@ -242,10 +267,25 @@ impl PStr {
   pub const MAIN_FN: PStr = Self::four_letter_literal(b"main");
   pub const PROCESS_TYPE: PStr = Self::seven_letter_literal(b"Process");
   pub const VEC_TYPE: PStr = Self::three_letter_literal(b"Vec");
+  pub const FLOAT_TYPE: PStr = Self::five_letter_literal(b"Float");
   pub const CONCAT: PStr = Self::six_letter_literal(b"concat");
   pub const STR_EQ: PStr = Self::two_letter_literal(b"eq");
   pub const TO_INT: PStr = Self::five_letter_literal(b"toInt");
   pub const FROM_INT: PStr = Self::seven_letter_literal(b"fromInt");
+  pub const FROM_FLOAT: PStr = Self::nine_letter_literal(b"fromFloat");
+  pub const TO_STRING: PStr = Self::eight_letter_literal(b"toString");
+  pub const BYTE_LENGTH: PStr = Self::ten_letter_literal(b"byteLength");
+  pub const CHAR_AT: PStr = Self::six_letter_literal(b"charAt");
+  pub const CODE_POINT_AT: PStr = Self::eleven_letter_literal(b"codePointAt");
+  pub const SUBSTRING: PStr = Self::nine_letter_literal(b"substring");
+  pub const INDEX_OF: PStr = Self::seven_letter_literal(b"indexOf");
+  pub const SPLIT: PStr = Self::five_letter_literal(b"split");
+  pub const TRIM: PStr = Self::four_letter_literal(b"trim");
+  pub const STARTS_WITH: PStr = Self::ten_letter_literal(b"startsWith");
+  pub const COMPARE: PStr = Self::seven_letter_literal(b"compare");
+  pub const EQUALS: PStr = Self::six_letter_literal(b"equals");
+  pub const TO_UPPER: PStr = Self::seven_letter_literal(b"toUpper");
+  pub const TO_LOWER: PStr = Self::seven_letter_literal(b"toLower");
   pub const PRINTLN: PStr = Self::seven_letter_literal(b"println");
   pub const PANIC: PStr = Self::five_letter_literal(b"panic");
   pub const FREE_FN: PStr = Self::four_letter_literal(b"free");
@@ -268,9 +308,19 @@ impl PStr {
   pub const MAP: PStr = Self::three_letter_literal(b"map");
   pub const FOLD: PStr = Self::four_letter_literal(b"fold");
   pub const UNWRAP_I31: PStr = Self::nine_letter_literal(b"unwrapI31");
+  pub const WRAP_F64: PStr = Self::seven_letter_literal(b"wrapF64");
+  pub const UNWRAP_F64: PStr = Self::nine_letter_literal(b"unwrapF64");
 
   pub const STD: PStr = Self::three_letter_literal(b"std");
   pub const TUPLES: PStr = Self::six_letter_literal(b"tuples");
+  pub const OPTION: PStr = Self::six_letter_literal(b"option");
+  pub const RESULT: PStr = Self::six_letter_literal(b"result");
+  pub const OPTION_TYPE: PStr = Self::six_letter_literal(b"Option");
+  pub const RESULT_TYPE: PStr = Self::six_letter_literal(b"Result");
+  pub const NONE: PStr = Self::four_letter_literal(b"None");
+  pub const SOME: PStr = Self::four_letter_literal(b"Some");
+  pub const OK: PStr = Self::two_letter_literal(b"Ok");
+  pub const ERROR: PStr = Self::five_letter_literal(b"Error");
   pub const PAIR: PStr = Self::four_letter_literal(b"Pair");
   pub const TRIPLE: PStr = Self::six_letter_literal(b"Triple");
   pub const TUPLE_4: PStr = Self::six_letter_literal(b"Tuple4");
@@ -291,6 +341,7 @@ impl PStr {
   pub const UNDERSCORE_THIS: PStr = Self::five_letter_literal(b"_this");
   pub const UNDERSCORE_TMP: PStr = Self::four_letter_literal(b"_tmp");
   pub const UNDERSCORE_STR: PStr = Self::four_letter_literal(b"_Str");
+  pub const UNDERSCORE_SELF: PStr = Self::five_letter_literal(b"_Self");
   pub const UNDERSCORE_GENERATED_FN: PStr = Self::six_letter_literal(b"_GenFn");
   pub const UNDERSCORE_GENERATED_TYPE: PStr = Self::five_letter_literal(b"_GenT");
 
@@ -356,6 +407,8 @@ impl ModuleReference {
   pub const ROOT: ModuleReference = ModuleReference(0);
   pub const DUMMY: ModuleReference = ModuleReference(1);
   pub const STD_TUPLES: ModuleReference = ModuleReference(2);
+  pub const STD_OPTION: ModuleReference = ModuleReference(3);
+  pub const STD_RESULT: ModuleReference = ModuleReference(4);
 
   pub fn get_parts<'a>(&self, heap: &'a Heap) -> &'a [PStr] {
     heap.module_reference_pointer_table[self.0]
@@ -462,8 +515,12 @@ impl Heap {
     let dummy_parts = vec![PStr::DUMMY_MODULE];
     let allocated_dummy = heap.alloc_module_reference(dummy_parts);
     let allocated_std_tuples = heap.alloc_module_reference(vec![PStr::STD, PStr::TUPLES]);
+    let allocated_std_option = heap.alloc_module_reference(vec![PStr::STD, PStr::OPTION]);
+    let allocated_std_result = heap.alloc_module_reference(vec![PStr::STD, PStr::RESULT]);
     debug_assert!(ModuleReference::DUMMY == allocated_dummy); // Dummy
     debug_assert!(ModuleReference::STD_TUPLES == allocated_std_tuples); // Dummy
+    debug_assert!(ModuleReference::STD_OPTION == allocated_std_option);
+    debug_assert!(ModuleReference::STD_RESULT == allocated_std_result);
     heap
   }
 
@@ -758,6 +815,8 @@ mod tests {
     a1.as_str(&heap);
     a2.as_str(&heap);
     b.dupe().as_str(&heap);
+    assert_eq!(Some(b), PStr::create_inline_opt("b"));
+    assert_eq!(None, PStr::create_inline_opt("aaaaaaaaaaaaaaaaaaaaaaaaaaa"));
 
     let ma1 = heap.alloc_module_reference_from_string_vec(vec!["a".to_string()]);
     let mb = heap.alloc_module_reference_from_string_vec(vec!["b".to_string(), "d-c".to_string()]);
@@ -820,6 +879,8 @@ mod tests {
     assert_eq!("aaaaaaa", PStr::seven_letter_literal(b"aaaaaaa").as_str(heap));
     assert_eq!("aaaaaaaa", PStr::eight_letter_literal(b"aaaaaaaa").as_str(heap));
     assert_eq!("aaaaaaaaa", PStr::nine_letter_literal(b"aaaaaaaaa").as_str(heap));
+    assert_eq!("aaaaaaaaaa", PStr::ten_letter_literal(b"aaaaaaaaaa").as_str(heap));
+    assert_eq!("aaaaaaaaaaa", PStr::eleven_letter_literal(b"aaaaaaaaaaa").as_str(heap));
     assert_eq!("aaaaaaaaaaaa", PStr::twelve_letter_literal(b"aaaaaaaaaaaa").as_str(heap));
   }
 
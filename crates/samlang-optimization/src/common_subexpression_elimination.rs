@@ -21,13 +21,15 @@ fn optimize_stmts(
       // handle similar optimization in loop-invariant code motion for while
       Statement::Call { .. }
       | Statement::Break(_)
+      | Statement::Return(_)
       | Statement::SingleIf { .. }
       | Statement::While { .. }
       | Statement::Cast { .. }
       | Statement::LateInitDeclaration { .. }
       | Statement::LateInitAssignment { .. }
       | Statement::StructInit { .. }
-      | Statement::ClosureInit { .. } => collector.push(stmt),
+      | Statement::ClosureInit { .. }
+      | Statement::InterfaceInit { .. } => collector.push(stmt),
 
       Statement::IsPointer { name, pointer_type, operand } => {
         set.insert(BindedValue::IsPointer(pointer_type, operand));
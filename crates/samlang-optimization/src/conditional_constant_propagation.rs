@@ -8,29 +8,30 @@ use samlang_heap::PStr;
 
 fn evaluate_bin_op(operator: BinaryOperator, v1: i32, v2: i32) -> Option<i32> {
   match operator {
-    BinaryOperator::MUL => Some(v1 * v2),
+    BinaryOperator::MUL => Some(v1.wrapping_mul(v2)),
     BinaryOperator::DIV => {
       if v2 == 0 {
         None
       } else {
-        Some(v1 / v2)
+        Some(v1.wrapping_div(v2))
       }
     }
     BinaryOperator::MOD => {
       if v2 == 0 {
         None
       } else {
-        Some(v1 % v2)
+        Some(v1.wrapping_rem(v2))
       }
     }
-    BinaryOperator::PLUS => Some(v1 + v2),
-    BinaryOperator::MINUS => Some(v1 - v2),
+    BinaryOperator::PLUS => Some(v1.wrapping_add(v2)),
+    BinaryOperator::MINUS => Some(v1.wrapping_sub(v2)),
     BinaryOperator::LAND => Some(v1 & v2),
     BinaryOperator::LOR => Some(v1 | v2),
-    BinaryOperator::SHL => Some(v1 << v2),
-    BinaryOperator::SHR => {
-      Some(i32::from_be_bytes(((u32::from_be_bytes(v1.to_be_bytes())) >> v2).to_be_bytes()))
-    }
+    BinaryOperator::SHL => Some(v1.wrapping_shl(v2 as u32)),
+    BinaryOperator::SHR => Some(i32::from_be_bytes(
+      (u32::from_be_bytes(v1.to_be_bytes())).wrapping_shr(v2 as u32).to_be_bytes(),
+    )),
+    BinaryOperator::ASHR => Some(v1.wrapping_shr(v2 as u32)),
     BinaryOperator::XOR => Some(v1 ^ v2),
     BinaryOperator::LT => Some((v1 < v2) as i32),
     BinaryOperator::LE => Some((v1 <= v2) as i32),
@@ -38,9 +39,37 @@ fn evaluate_bin_op(operator: BinaryOperator, v1: i32, v2: i32) -> Option<i32> {
     BinaryOperator::GE => Some((v1 >= v2) as i32),
     BinaryOperator::EQ => Some((v1 == v2) as i32),
     BinaryOperator::NE => Some((v1 != v2) as i32),
+    BinaryOperator::FMUL
+    | BinaryOperator::FDIV
+    | BinaryOperator::FPLUS
+    | BinaryOperator::FMINUS
+    | BinaryOperator::FLT
+    | BinaryOperator::FLE
+    | BinaryOperator::FGT
+    | BinaryOperator::FGE
+    | BinaryOperator::FEQ
+    | BinaryOperator::FNE => None,
   }
 }
 
+fn evaluate_float_bin_op(operator: BinaryOperator, v1: f64, v2: f64) -> Option<Expression> {
+  let arithmetic_result = match operator {
+    BinaryOperator::FMUL => v1 * v2,
+    BinaryOperator::FDIV => v1 / v2,
+    BinaryOperator::FPLUS => v1 + v2,
+    BinaryOperator::FMINUS => v1 - v2,
+    BinaryOperator::FLT => return Some(Expression::i32((v1 < v2) as i32)),
+    BinaryOperator::FLE => return Some(Expression::i32((v1 <= v2) as i32)),
+    BinaryOperator::FGT => return Some(Expression::i32((v1 > v2) as i32)),
+    BinaryOperator::FGE => return Some(Expression::i32((v1 >= v2) as i32)),
+    BinaryOperator::FEQ => return Some(Expression::i32((v1 == v2) as i32)),
+    BinaryOperator::FNE => return Some(Expression::i32((v1 != v2) as i32)),
+    _ => return None,
+  };
+  // Keep NaN and infinities as runtime computations, so that they never need a literal form.
+  if arithmetic_result.is_finite() { Some(Expression::float(arithmetic_result)) } else { None }
+}
+
 #[derive(Clone)]
 struct BinaryExpression {
   operator: BinaryOperator,
@@ -59,7 +88,7 @@ fn merge_binary_expression(
         Some(BinaryExpression {
           operator: BinaryOperator::PLUS,
           e1: inner.e1,
-          e2: inner.e2 + outer_const,
+          e2: inner.e2.wrapping_add(outer_const),
         })
       } else {
         None
@@ -70,7 +99,7 @@ fn merge_binary_expression(
         Some(BinaryExpression {
           operator: BinaryOperator::MUL,
           e1: inner.e1,
-          e2: inner.e2 * outer_const,
+          e2: inner.e2.wrapping_mul(outer_const),
         })
       } else {
         None
@@ -86,7 +115,7 @@ fn merge_binary_expression(
         Some(BinaryExpression {
           operator: outer_operator,
           e1: inner.e1,
-          e2: outer_const - inner.e2,
+          e2: outer_const.checked_sub(inner.e2)?,
         })
       } else {
         None
@@ -111,7 +140,11 @@ fn optimize_variable_name(
 
 fn optimize_expr(value_cx: &mut LocalValueContextForOptimization, e: &Expression) -> Expression {
   match e {
-    Expression::Int32Literal(_) | Expression::Int31Literal(_) | Expression::StringName(_) => *e,
+    Expression::Int32Literal(_)
+    | Expression::Int31Literal(_)
+    | Expression::FloatLiteral(_)
+    | Expression::StringName(_)
+    | Expression::GlobalConstant(_) => *e,
     Expression::Variable(v) => optimize_variable_name(value_cx, v),
   }
 }
@@ -120,6 +153,9 @@ fn optimize_callee(value_cx: &mut LocalValueContextForOptimization, callee: &Cal
   match callee {
     Callee::FunctionName(n) => Callee::FunctionName(n.clone()),
     Callee::Variable(v) => optimize_variable_name(value_cx, v).convert_to_callee().unwrap(),
+    Callee::InterfaceMethod { receiver, index } => {
+      Callee::InterfaceMethod { receiver: optimize_expr(value_cx, receiver), index: *index }
+    }
   }
 }
 
@@ -207,6 +243,12 @@ fn optimize_stmt(
           return false;
         }
       }
+      if let (Expression::FloatLiteral(v1), Expression::FloatLiteral(v2)) = (&e1, &e2)
+        && let Some(value) = evaluate_float_bin_op(operator, v1.value(), v2.value())
+      {
+        value_cx.checked_bind(*name, value);
+        return false;
+      }
       match (&e1, &e2) {
         (Expression::Variable(v1), Expression::Variable(v2)) if v1.name.eq(&v2.name) => {
           if operator == BinaryOperator::MINUS || operator == BinaryOperator::MOD {
@@ -368,6 +410,11 @@ fn optimize_stmt(
       true
     }
 
+    Statement::Return(e) => {
+      collector.push(Statement::Return(optimize_expr(value_cx, e)));
+      true
+    }
+
     Statement::While { loop_variables, statements, break_collector } => {
       let mut filtered_loop_variables = Vec::new();
       for v in loop_variables.iter() {
@@ -479,6 +526,16 @@ fn optimize_stmt(
       });
       false
     }
+
+    Statement::InterfaceInit { interface_variable_name, interface_type_name, vtable, context } => {
+      collector.push(Statement::InterfaceInit {
+        interface_variable_name: *interface_variable_name,
+        interface_type_name: *interface_type_name,
+        vtable: vtable.clone(),
+        context: optimize_expr(value_cx, context),
+      });
+      false
+    }
   }
 }
 
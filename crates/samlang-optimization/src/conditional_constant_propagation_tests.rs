@@ -5,9 +5,9 @@ mod tests {
   use samlang_ast::{
     hir::BinaryOperator,
     mir::{
-      Callee, Expression, Function, FunctionName, FunctionNameExpression, GenenalLoopVariable,
-      INT_32_TYPE, IfElseFinalAssignment, ONE, Statement, SymbolTable, Type, TypeNameId,
-      VariableName, ZERO,
+      Callee, Expression, FLOAT_TYPE, Function, FunctionName, FunctionNameExpression,
+      GenenalLoopVariable, INT_32_TYPE, IfElseFinalAssignment, ONE, Statement, SymbolTable, Type,
+      TypeNameId, VariableName, ZERO,
     },
   };
   use samlang_heap::{Heap, PStr};
@@ -73,6 +73,18 @@ mod tests {
           Expression::i32(2),
           Expression::i32(1),
         ),
+        Statement::binary(
+          heap.alloc_str_for_test("c5"),
+          BinaryOperator::ASHR,
+          Expression::i32(-3),
+          Expression::i32(1),
+        ),
+        Statement::binary(
+          heap.alloc_str_for_test("c6"),
+          BinaryOperator::SHL,
+          Expression::i32(1),
+          Expression::i32(33),
+        ),
         Statement::StructInit {
           struct_variable_name: heap.alloc_str_for_test("c_o"),
           type_name: table.create_type_name_for_test(heap.alloc_str_for_test("Id")),
@@ -82,6 +94,8 @@ mod tests {
             Expression::var_name(heap.alloc_str_for_test("c2"), INT_32_TYPE),
             Expression::var_name(heap.alloc_str_for_test("c3"), INT_32_TYPE),
             Expression::var_name(heap.alloc_str_for_test("c4"), INT_32_TYPE),
+            Expression::var_name(heap.alloc_str_for_test("c5"), INT_32_TYPE),
+            Expression::var_name(heap.alloc_str_for_test("c6"), INT_32_TYPE),
           ],
         },
         Statement::binary(
@@ -312,7 +326,7 @@ mod tests {
       Expression::var_name(heap.alloc_str_for_test("a17"), INT_32_TYPE),
       heap,
       table,
-      r#"let c_o: _Id = [6, 1, 2147483646, 0, 3];
+      r#"let c_o: _Id = [6, 1, 2147483646, 0, 3, -2, 2];
 let i0: int = 6[2];
 let b8 = (i0: int) * (i0: int);
 let a6 = (i1: int) / 30;
@@ -334,6 +348,175 @@ return (a17: int);"#,
     );
   }
 
+  #[test]
+  fn overflow_sequence_test() {
+    let heap = &mut Heap::new();
+    let table = &mut SymbolTable::new();
+
+    assert_correctly_optimized(
+      vec![
+        Statement::binary(
+          heap.alloc_str_for_test("o0"),
+          BinaryOperator::MINUS,
+          Expression::i32(0),
+          Expression::i32(i32::MIN),
+        ),
+        Statement::binary(
+          heap.alloc_str_for_test("o1"),
+          BinaryOperator::PLUS,
+          Expression::i32(i32::MAX),
+          Expression::i32(1),
+        ),
+        Statement::binary(
+          heap.alloc_str_for_test("o2"),
+          BinaryOperator::MUL,
+          Expression::i32(i32::MAX),
+          Expression::i32(2),
+        ),
+        Statement::binary(
+          heap.alloc_str_for_test("o3"),
+          BinaryOperator::DIV,
+          Expression::i32(i32::MIN),
+          Expression::i32(-1),
+        ),
+        Statement::binary(
+          heap.alloc_str_for_test("o4"),
+          BinaryOperator::MOD,
+          Expression::i32(i32::MIN),
+          Expression::i32(-1),
+        ),
+        Statement::binary(
+          heap.alloc_str_for_test("a1"),
+          BinaryOperator::PLUS,
+          Expression::var_name(heap.alloc_str_for_test("a0"), INT_32_TYPE),
+          Expression::i32(i32::MAX),
+        ),
+        Statement::binary(
+          heap.alloc_str_for_test("a2"),
+          BinaryOperator::PLUS,
+          Expression::var_name(heap.alloc_str_for_test("a1"), INT_32_TYPE),
+          Expression::i32(1),
+        ),
+        Statement::binary(
+          heap.alloc_str_for_test("a3"),
+          BinaryOperator::LT,
+          Expression::var_name(heap.alloc_str_for_test("a1"), INT_32_TYPE),
+          Expression::i32(-2),
+        ),
+        Statement::StructInit {
+          struct_variable_name: heap.alloc_str_for_test("s"),
+          type_name: table.create_type_name_for_test(heap.alloc_str_for_test("Id")),
+          expression_list: vec![
+            Expression::var_name(heap.alloc_str_for_test("o0"), INT_32_TYPE),
+            Expression::var_name(heap.alloc_str_for_test("o1"), INT_32_TYPE),
+            Expression::var_name(heap.alloc_str_for_test("o2"), INT_32_TYPE),
+            Expression::var_name(heap.alloc_str_for_test("o3"), INT_32_TYPE),
+            Expression::var_name(heap.alloc_str_for_test("o4"), INT_32_TYPE),
+            Expression::var_name(heap.alloc_str_for_test("a2"), INT_32_TYPE),
+            Expression::var_name(heap.alloc_str_for_test("a3"), INT_32_TYPE),
+          ],
+        },
+      ],
+      ZERO,
+      heap,
+      table,
+      r#"let a1 = (a0: int) + 2147483647;
+let a2 = (a0: int) + -2147483648;
+let a3 = (a1: int) < -2;
+let s: _Id = [-2147483648, -2147483648, -2, -2147483648, 0, (a2: int), (a3: int)];
+return 0;"#,
+    );
+  }
+
+  #[test]
+  fn float_sequence_test() {
+    let heap = &mut Heap::new();
+    let table = &mut SymbolTable::new();
+
+    assert_correctly_optimized(
+      vec![
+        Statement::binary(
+          heap.alloc_str_for_test("f1"),
+          BinaryOperator::FMUL,
+          Expression::float(1.5),
+          Expression::float(2.0),
+        ),
+        Statement::binary(
+          heap.alloc_str_for_test("f2"),
+          BinaryOperator::FPLUS,
+          Expression::var_name(heap.alloc_str_for_test("f1"), FLOAT_TYPE),
+          Expression::float(0.25),
+        ),
+        Statement::binary(
+          heap.alloc_str_for_test("f3"),
+          BinaryOperator::FMINUS,
+          Expression::var_name(heap.alloc_str_for_test("f2"), FLOAT_TYPE),
+          Expression::float(4.0),
+        ),
+        Statement::binary(
+          heap.alloc_str_for_test("f4"),
+          BinaryOperator::FDIV,
+          Expression::var_name(heap.alloc_str_for_test("f3"), FLOAT_TYPE),
+          Expression::float(0.5),
+        ),
+        Statement::binary(
+          heap.alloc_str_for_test("f5"),
+          BinaryOperator::FDIV,
+          Expression::float(1.0),
+          Expression::float(0.0),
+        ),
+        Statement::binary(
+          heap.alloc_str_for_test("f6"),
+          BinaryOperator::FMINUS,
+          Expression::var_name(PStr::LOWER_X, FLOAT_TYPE),
+          Expression::var_name(PStr::LOWER_X, FLOAT_TYPE),
+        ),
+        Statement::binary(
+          heap.alloc_str_for_test("f7"),
+          BinaryOperator::FMUL,
+          Expression::var_name(PStr::LOWER_X, FLOAT_TYPE),
+          Expression::float(1.0),
+        ),
+        Statement::binary(
+          heap.alloc_str_for_test("b1"),
+          BinaryOperator::FLT,
+          Expression::var_name(heap.alloc_str_for_test("f4"), FLOAT_TYPE),
+          Expression::float(0.0),
+        ),
+        Statement::binary(
+          heap.alloc_str_for_test("b2"),
+          BinaryOperator::FGE,
+          Expression::var_name(heap.alloc_str_for_test("f2"), FLOAT_TYPE),
+          Expression::float(3.25),
+        ),
+        Statement::binary(
+          heap.alloc_str_for_test("b3"),
+          BinaryOperator::FNE,
+          Expression::float(1.0),
+          Expression::float(1.0),
+        ),
+        Statement::StructInit {
+          struct_variable_name: heap.alloc_str_for_test("s"),
+          type_name: table.create_type_name_for_test(heap.alloc_str_for_test("Id")),
+          expression_list: vec![
+            Expression::var_name(heap.alloc_str_for_test("f4"), FLOAT_TYPE),
+            Expression::var_name(heap.alloc_str_for_test("b1"), INT_32_TYPE),
+            Expression::var_name(heap.alloc_str_for_test("b2"), INT_32_TYPE),
+            Expression::var_name(heap.alloc_str_for_test("b3"), INT_32_TYPE),
+          ],
+        },
+      ],
+      Expression::var_name(heap.alloc_str_for_test("f7"), FLOAT_TYPE),
+      heap,
+      table,
+      r#"let f5 = 1.0 / 0.0;
+let f6 = (x: float) - (x: float);
+let f7 = (x: float) * 1.0;
+let s: _Id = [-1.5, 1, 1, 0];
+return (f7: float);"#,
+    );
+  }
+
   #[test]
   fn index_sequence_test() {
     let heap = &mut Heap::new();
@@ -994,6 +1177,57 @@ return 6;"#,
     );
   }
 
+  #[test]
+  fn return_tests() {
+    let heap = &mut Heap::new();
+    let table = &mut SymbolTable::new();
+
+    assert_correctly_optimized(
+      vec![
+        Statement::binary(heap.alloc_str_for_test("a"), BinaryOperator::PLUS, ONE, ONE),
+        Statement::Return(Expression::var_name(heap.alloc_str_for_test("a"), INT_32_TYPE)),
+        Statement::binary(heap.alloc_str_for_test("b"), BinaryOperator::PLUS, ONE, ONE),
+        Statement::Call {
+          callee: Callee::FunctionName(FunctionNameExpression {
+            name: FunctionName::new_for_test(heap.alloc_str_for_test("foo")),
+            type_: Type::new_fn_unwrapped(Vec::new(), INT_32_TYPE),
+          }),
+          arguments: vec![Expression::var_name(heap.alloc_str_for_test("b"), INT_32_TYPE)],
+          return_type: INT_32_TYPE,
+          return_collector: None,
+        },
+      ],
+      ZERO,
+      heap,
+      table,
+      "return 2;\nreturn 0;",
+    );
+
+    let heap = &mut Heap::new();
+    assert_correctly_optimized(
+      vec![
+        Statement::SingleIf {
+          condition: ONE,
+          invert_condition: false,
+          statements: vec![Statement::Return(ONE)],
+        },
+        Statement::Call {
+          callee: Callee::FunctionName(FunctionNameExpression {
+            name: FunctionName::new_for_test(heap.alloc_str_for_test("foo")),
+            type_: Type::new_fn_unwrapped(Vec::new(), INT_32_TYPE),
+          }),
+          arguments: Vec::new(),
+          return_type: INT_32_TYPE,
+          return_collector: None,
+        },
+      ],
+      ZERO,
+      heap,
+      table,
+      "return 1;\nreturn 0;",
+    );
+  }
+
   #[test]
   fn while_tests() {
     let heap = &mut Heap::new();
@@ -39,8 +39,14 @@ fn collect_use_from_stmt(stmt: &Statement, set: &mut HashSet<PStr>) {
       collect_use_from_expression(pointer_expression, set)
     }
     Statement::Call { callee, arguments, return_type: _, return_collector: _ } => {
-      if let Callee::Variable(v) = callee {
-        set.insert(v.name);
+      match callee {
+        Callee::FunctionName(_) => {}
+        Callee::Variable(v) => {
+          set.insert(v.name);
+        }
+        Callee::InterfaceMethod { receiver, index: _ } => {
+          collect_use_from_expression(receiver, set)
+        }
       }
       for e in arguments {
         collect_use_from_expression(e, set)
@@ -59,7 +65,7 @@ fn collect_use_from_stmt(stmt: &Statement, set: &mut HashSet<PStr>) {
       collect_use_from_expression(condition, set);
       collect_use_from_stmts(statements, set);
     }
-    Statement::Break(e) => collect_use_from_expression(e, set),
+    Statement::Break(e) | Statement::Return(e) => collect_use_from_expression(e, set),
     Statement::While { loop_variables, statements, break_collector: _ } => {
       collect_use_from_while_parts(loop_variables, statements, set);
     }
@@ -81,6 +87,14 @@ fn collect_use_from_stmt(stmt: &Statement, set: &mut HashSet<PStr>) {
     } => {
       collect_use_from_expression(context, set);
     }
+    Statement::InterfaceInit {
+      interface_variable_name: _,
+      interface_type_name: _,
+      vtable: _,
+      context,
+    } => {
+      collect_use_from_expression(context, set);
+    }
   }
 }
 
@@ -90,6 +104,27 @@ pub(super) fn collect_use_from_stmts(stmts: &[Statement], set: &mut HashSet<PStr
   }
 }
 
+/// Collects variables that are reassigned by the statements. Inside a loop, such an assignment can
+/// be read by the next iteration, so it cannot be judged by the uses after it alone.
+pub(super) fn collect_late_init_assignment_targets(stmts: &[Statement], set: &mut HashSet<PStr>) {
+  for stmt in stmts {
+    match stmt {
+      Statement::LateInitAssignment { name, assigned_expression: _ } => {
+        set.insert(*name);
+      }
+      Statement::IfElse { condition: _, s1, s2, final_assignments: _ } => {
+        collect_late_init_assignment_targets(s1, set);
+        collect_late_init_assignment_targets(s2, set);
+      }
+      Statement::SingleIf { condition: _, invert_condition: _, statements }
+      | Statement::While { loop_variables: _, statements, break_collector: _ } => {
+        collect_late_init_assignment_targets(statements, set);
+      }
+      _ => {}
+    }
+  }
+}
+
 fn optimize_stmt(stmt: &mut Statement, set: &mut HashSet<PStr>) -> bool {
   match stmt {
     Statement::IsPointer { name, pointer_type: _, operand } | Statement::Not { name, operand } => {
@@ -125,8 +160,14 @@ fn optimize_stmt(stmt: &mut Statement, set: &mut HashSet<PStr>) -> bool {
         Some(n) if set.contains(n) => Some(*n),
         _ => None,
       };
-      if let Callee::Variable(v) = &callee {
-        set.insert(v.name);
+      match &callee {
+        Callee::FunctionName(_) => {}
+        Callee::Variable(v) => {
+          set.insert(v.name);
+        }
+        Callee::InterfaceMethod { receiver, index: _ } => {
+          collect_use_from_expression(receiver, set)
+        }
       }
       for e in arguments {
         collect_use_from_expression(e, set);
@@ -161,7 +202,7 @@ fn optimize_stmt(stmt: &mut Statement, set: &mut HashSet<PStr>) -> bool {
         true
       }
     }
-    Statement::Break(e) => {
+    Statement::Break(e) | Statement::Return(e) => {
       collect_use_from_expression(e, set);
       true
     }
@@ -176,6 +217,9 @@ fn optimize_stmt(stmt: &mut Statement, set: &mut HashSet<PStr>) -> bool {
       for v in loop_variables.iter() {
         collect_use_from_expression(&v.loop_value, set);
       }
+      let mut reassigned = HashSet::new();
+      collect_late_init_assignment_targets(statements, &mut reassigned);
+      set.extend(reassigned.into_iter().filter(|name| used_inside_loop.contains(name)));
       optimize_stmts(statements, set);
       loop_variables.retain(|variable| {
         if set.contains(&variable.name) {
@@ -220,6 +264,19 @@ fn optimize_stmt(stmt: &mut Statement, set: &mut HashSet<PStr>) -> bool {
         true
       }
     }
+    Statement::InterfaceInit {
+      interface_variable_name,
+      interface_type_name: _,
+      vtable: _,
+      context,
+    } => {
+      if !set.contains(interface_variable_name) {
+        false
+      } else {
+        collect_use_from_expression(context, set);
+        true
+      }
+    }
   }
 }
 
@@ -702,4 +702,82 @@ return (v: int);"#,
 return (v: int);"#,
     );
   }
+
+  #[test]
+  fn while_test_5() {
+    let heap = &mut Heap::new();
+    let table = &mut SymbolTable::new();
+
+    // A variable reassigned inside the loop is read by the next iteration.
+    assert_correctly_optimized(
+      vec![
+        Statement::LateInitDeclaration { name: PStr::LOWER_K, type_: INT_32_TYPE },
+        Statement::LateInitAssignment {
+          name: PStr::LOWER_K,
+          assigned_expression: Expression::i32(10),
+        },
+        Statement::While {
+          loop_variables: Vec::new(),
+          statements: vec![
+            Statement::LateInitDeclaration { name: PStr::LOWER_T, type_: INT_32_TYPE },
+            Statement::LateInitAssignment {
+              name: PStr::LOWER_T,
+              assigned_expression: Expression::var_name(PStr::LOWER_K, INT_32_TYPE),
+            },
+            Statement::binary(
+              PStr::LOWER_C,
+              BinaryOperator::GT,
+              Expression::var_name(PStr::LOWER_T, INT_32_TYPE),
+              ZERO,
+            ),
+            Statement::SingleIf {
+              condition: Expression::var_name(PStr::LOWER_C, INT_32_TYPE),
+              invert_condition: true,
+              statements: vec![Statement::Break(ZERO)],
+            },
+            Statement::binary(
+              heap.alloc_str_for_test("t2"),
+              BinaryOperator::MINUS,
+              Expression::var_name(PStr::LOWER_T, INT_32_TYPE),
+              ONE,
+            ),
+            Statement::IfElse {
+              condition: ONE,
+              s1: vec![Statement::LateInitAssignment {
+                name: PStr::LOWER_K,
+                assigned_expression: Expression::var_name(
+                  heap.alloc_str_for_test("t2"),
+                  INT_32_TYPE,
+                ),
+              }],
+              s2: Vec::new(),
+              final_assignments: Vec::new(),
+            },
+            Statement::LateInitAssignment { name: PStr::LOWER_U, assigned_expression: ZERO },
+          ],
+          break_collector: None,
+        },
+      ],
+      ZERO,
+      heap,
+      table,
+      r#"let k: int;
+k = 10;
+while (true) {
+  let t: int;
+  t = (k: int);
+  let c = (t: int) > 0;
+  if !(c: int) {
+    undefined = 0;
+    break;
+  }
+  let t2 = (t: int) + -1;
+  if 1 {
+    k = (t2: int);
+  } else {
+  }
+}
+return 0;"#,
+    );
+  }
 }
@@ -36,12 +36,12 @@ mod estimator {
       Statement::SingleIf { condition: _, invert_condition: _, statements } => {
         1 + estimate_stmts_inline_cost(statements)
       }
-      Statement::Break(_) => 1,
+      Statement::Break(_) | Statement::Return(_) => 1,
       Statement::While { loop_variables, statements, break_collector: _ } => {
         1 + loop_variables.len() * 2 + estimate_stmts_inline_cost(statements)
       }
       Statement::StructInit { expression_list, .. } => 1 + expression_list.len(),
-      Statement::ClosureInit { .. } => 3,
+      Statement::ClosureInit { .. } | Statement::InterfaceInit { .. } => 3,
     }
   }
 
@@ -61,6 +61,20 @@ mod estimator {
     sum
   }
 
+  fn stmts_contain_return(stmts: &[Statement]) -> bool {
+    stmts.iter().any(|stmt| match stmt {
+      Statement::Return(_) => true,
+      Statement::IfElse { condition: _, s1, s2, final_assignments: _ } => {
+        stmts_contain_return(s1) || stmts_contain_return(s2)
+      }
+      Statement::SingleIf { condition: _, invert_condition: _, statements }
+      | Statement::While { loop_variables: _, statements, break_collector: _ } => {
+        stmts_contain_return(statements)
+      }
+      _ => false,
+    })
+  }
+
   pub(super) struct FunctionsToInline {
     pub(super) functions_that_can_be_inlined: HashSet<FunctionName>,
     pub(super) functions_that_can_perform_inlining: HashSet<FunctionName>,
@@ -71,7 +85,8 @@ mod estimator {
     let mut functions_that_can_perform_inlining = HashSet::new();
     for f in functions {
       let cost = estimate_fn_inline_cost(f);
-      if cost <= INLINE_THRESHOLD {
+      // An early return would exit the caller instead of the inlined callee.
+      if cost <= INLINE_THRESHOLD && !stmts_contain_return(&f.body) {
         functions_that_can_be_inlined.insert(f.name);
       }
       if cost <= PERFORM_INLINE_THRESHOLD {
@@ -166,10 +181,11 @@ mod estimator {
           Statement::Cast { name: PStr::EMPTY, type_: INT_32_TYPE, assigned_expression: ZERO },
           Statement::LateInitDeclaration { name: PStr::EMPTY, type_: INT_32_TYPE },
           Statement::LateInitAssignment { name: PStr::EMPTY, assigned_expression: ZERO },
+          Statement::Return(ZERO),
         ],
         return_value: ZERO,
       });
-      assert_eq!(34, actual);
+      assert_eq!(35, actual);
     }
   }
 }
@@ -200,6 +216,9 @@ fn inline_rewrite_callee(callee: &Callee, cx: &mut LocalValueContextForOptimizat
   match callee {
     Callee::FunctionName(n) => Callee::FunctionName(n.clone()),
     Callee::Variable(v) => inline_rewrite_variable(v, cx).convert_to_callee().unwrap(),
+    Callee::InterfaceMethod { receiver, index } => {
+      Callee::InterfaceMethod { receiver: inline_rewrite_expr(receiver, cx), index: *index }
+    }
   }
 }
 
@@ -285,6 +304,7 @@ fn inline_rewrite_stmt(
       Statement::SingleIf { condition, invert_condition: *invert_condition, statements }
     }
     Statement::Break(e) => Statement::Break(inline_rewrite_expr(e, cx)),
+    Statement::Return(_) => panic!("Functions with early returns should not be inlined."),
     Statement::While { loop_variables, statements, break_collector } => {
       let loop_variables_with_all_but_loop_value_rewritten = loop_variables
         .iter()
@@ -355,6 +375,20 @@ fn inline_rewrite_stmt(
         context: inline_rewrite_expr(context, cx),
       }
     }
+    Statement::InterfaceInit { interface_variable_name, interface_type_name, vtable, context } => {
+      Statement::InterfaceInit {
+        interface_variable_name: bind_with_mangled_name(
+          cx,
+          heap,
+          prefix,
+          interface_variable_name,
+          &Type::Id(*interface_type_name),
+        ),
+        interface_type_name: *interface_type_name,
+        vtable: vtable.clone(),
+        context: inline_rewrite_expr(context, cx),
+      }
+    }
   }
 }
 
@@ -919,6 +919,57 @@ function __$main(): int {
 function __$main(): int {
   return 0;
 }
+"#,
+    );
+  }
+
+  #[test]
+  fn test9() {
+    let heap = &mut Heap::new();
+    let table = &mut SymbolTable::new();
+
+    assert_correctly_inlined(
+      vec![
+        Function {
+          name: FunctionName::new_for_test(heap.alloc_str_for_test("fooBar")),
+          parameters: vec![PStr::LOWER_A],
+          type_: Type::new_fn_unwrapped(vec![INT_32_TYPE], INT_32_TYPE),
+          body: vec![Statement::SingleIf {
+            condition: Expression::var_name(PStr::LOWER_A, INT_32_TYPE),
+            invert_condition: false,
+            statements: vec![Statement::Return(ONE)],
+          }],
+          return_value: ZERO,
+        },
+        Function {
+          name: FunctionName::new_for_test(PStr::MAIN_FN),
+          parameters: Vec::new(),
+          type_: Type::new_fn_unwrapped(Vec::new(), INT_32_TYPE),
+          body: vec![Statement::Call {
+            callee: Callee::FunctionName(FunctionNameExpression {
+              name: FunctionName::new_for_test(heap.alloc_str_for_test("fooBar")),
+              type_: Type::new_fn_unwrapped(vec![INT_32_TYPE], INT_32_TYPE),
+            }),
+            arguments: vec![ONE],
+            return_type: INT_32_TYPE,
+            return_collector: Some(heap.alloc_str_for_test("v")),
+          }],
+          return_value: Expression::var_name(heap.alloc_str_for_test("v"), INT_32_TYPE),
+        },
+      ],
+      heap,
+      table,
+      r#"function __$fooBar(a: int): int {
+  if (a: int) {
+    return 1;
+  }
+  return 0;
+}
+
+function __$main(): int {
+  let v: int = __$fooBar(1);
+  return (v: int);
+}
 "#,
     );
   }
@@ -96,7 +96,9 @@ pub fn optimize_sources(
     let samlang_ast::mir::Sources {
       symbol_table,
       global_variables,
+      global_constants,
       closure_types,
+      interface_types,
       type_definitions,
       main_function_names,
       mut functions,
@@ -110,7 +112,9 @@ pub fn optimize_sources(
     sources = samlang_ast::mir::Sources {
       symbol_table,
       global_variables,
+      global_constants,
       closure_types,
+      interface_types,
       type_definitions,
       main_function_names,
       functions,
@@ -133,7 +137,9 @@ mod tests {
     Sources {
       symbol_table: SymbolTable::new(),
       global_variables: Vec::new(),
+      global_constants: Vec::new(),
       closure_types: Vec::new(),
+      interface_types: Vec::new(),
       type_definitions: Vec::new(),
       main_function_names: vec![FunctionName::new_for_test(PStr::MAIN_FN)],
       functions: vec![Function {
@@ -89,6 +89,7 @@ fn optimize_stmt(
       match callee {
         Callee::FunctionName(_) => {}
         Callee::Variable(v) => optimize_variable(v, variable_cx),
+        Callee::InterfaceMethod { receiver, index: _ } => optimize_expr(receiver, variable_cx),
       }
       for e in arguments {
         optimize_expr(e, variable_cx);
@@ -123,7 +124,7 @@ fn optimize_stmt(
       variable_cx.pop_scope();
       true
     }
-    Statement::Break(e) => {
+    Statement::Break(e) | Statement::Return(e) => {
       optimize_expr(e, variable_cx);
       true
     }
@@ -158,6 +159,15 @@ fn optimize_stmt(
       optimize_expr(context, variable_cx);
       true
     }
+    Statement::InterfaceInit {
+      interface_variable_name: _,
+      interface_type_name: _,
+      vtable: _,
+      context,
+    } => {
+      optimize_expr(context, variable_cx);
+      true
+    }
   }
 }
 
@@ -199,10 +199,12 @@ fn stmt_contains_break(stmt: &Statement) -> bool {
     | Statement::LateInitDeclaration { .. }
     | Statement::LateInitAssignment { .. }
     | Statement::StructInit { .. }
-    | Statement::ClosureInit { .. } => false,
+    | Statement::ClosureInit { .. }
+    | Statement::InterfaceInit { .. } => false,
     // Although it might contain break, the break never affects the outer loop,
     Statement::While { .. } => false,
     Statement::Break(_) => true,
+    Statement::Return(_) => false,
     Statement::IfElse { condition: _, s1, s2, final_assignments: _ } => {
       stmts_contains_break(s1) || stmts_contains_break(s2)
     }
@@ -216,6 +218,20 @@ fn stmts_contains_break(statements: &[Statement]) -> bool {
   statements.iter().any(stmt_contains_break)
 }
 
+fn stmts_contains_return(statements: &[Statement]) -> bool {
+  statements.iter().any(|stmt| match stmt {
+    Statement::Return(_) => true,
+    Statement::IfElse { condition: _, s1, s2, final_assignments: _ } => {
+      stmts_contains_return(s1) || stmts_contains_return(s2)
+    }
+    Statement::SingleIf { condition: _, invert_condition: _, statements }
+    | Statement::While { loop_variables: _, statements, break_collector: _ } => {
+      stmts_contains_return(statements)
+    }
+    _ => false,
+  })
+}
+
 fn merge_invariant_addition_for_loop_optimization(
   existing_value: &PotentialLoopInvariantExpression,
   added_value: &PotentialLoopInvariantExpression,
@@ -328,8 +344,10 @@ fn get_loop_invariant_expression_opt(
     // i31 currently represents the optimized form of variants.
     // We should just treat it as opaque, and shouldn't do anything funny with it.
     Expression::Int31Literal(_) |
+    // Induction variables are always ints.
+    Expression::FloatLiteral(_) |
     // We are doing algebraic operations here. Name is hopeless.
-    Expression::StringName(_) => None,
+    Expression::StringName(_) | Expression::GlobalConstant(_) => None,
     Expression::Variable(v) => {
       if !non_loop_invariant_variables.contains(&v.name) {
         Some(PotentialLoopInvariantExpression::Var(*v))
@@ -438,7 +456,8 @@ fn extract_loop_guard_structure(
     ) if name.eq(&condition_var.name)
       && single_if_stmts.len() == 1
       && stmts_contains_break(single_if_stmts)
-      && !stmts_contains_break(&stmts[2..]) =>
+      && !stmts_contains_break(&stmts[2..])
+      && !stmts_contains_return(&stmts[2..]) =>
     {
       if let (Some(guard_operator), Some(guard_expression)) = (
         get_guard_operator(*operator, *invert_condition),
@@ -572,6 +591,8 @@ fn remove_dead_code_inside_loop(
       live_variable_set.insert(var_name.name);
     }
   }
+  // Reassigned variables might be read after the loop.
+  dead_code_elimination::collect_late_init_assignment_targets(rest_stmts, &mut live_variable_set);
   dead_code_elimination::optimize_stmts(rest_stmts, &mut live_variable_set)
 }
 
@@ -1433,6 +1454,20 @@ mod tests {
     );
   }
 
+  #[test]
+  fn remove_dead_code_inside_loop_keeps_reassignments_test() {
+    let mut stmts = vec![
+      Statement::binary(PStr::LOWER_B, BinaryOperator::PLUS, ZERO, ONE),
+      Statement::LateInitAssignment {
+        name: PStr::LOWER_A,
+        assigned_expression: Expression::var_name(PStr::LOWER_B, INT_32_TYPE),
+      },
+      Statement::binary(PStr::LOWER_C, BinaryOperator::PLUS, ZERO, ONE),
+    ];
+    remove_dead_code_inside_loop(&Vec::new(), &mut stmts);
+    assert_eq!(2, stmts.len());
+  }
+
   #[test]
   fn extract_loop_guard_structure_rejection_rests() {
     let heap = &mut samlang_heap::Heap::new();
@@ -7,7 +7,7 @@ use super::loop_induction_analysis::{
 use itertools::Itertools;
 use samlang_ast::{
   hir::BinaryOperator,
-  mir::{Expression, INT_32_TYPE, IfElseFinalAssignment, Statement, VariableName},
+  mir::{Callee, Expression, INT_32_TYPE, IfElseFinalAssignment, Statement, VariableName},
 };
 
 pub(super) struct LoopInductionVariableEliminationResult {
@@ -53,7 +53,13 @@ fn stmt_uses_basic_induction_var(
       expr_uses_basic_induction_var(pointer_expression, v)
     }
     Statement::Call { callee, arguments, return_type: _, return_collector: _ } => {
-      let in_callee = callee.as_variable().is_some_and(|var| var.name.eq(&v.name));
+      let in_callee = match callee {
+        Callee::FunctionName(_) => false,
+        Callee::Variable(var) => var.name.eq(&v.name),
+        Callee::InterfaceMethod { receiver, index: _ } => {
+          expr_uses_basic_induction_var(receiver, v)
+        }
+      };
       in_callee || arguments.iter().any(|e| expr_uses_basic_induction_var(e, v))
     }
     Statement::IfElse { condition, s1, s2, final_assignments } => {
@@ -65,7 +71,7 @@ fn stmt_uses_basic_induction_var(
     Statement::SingleIf { condition, invert_condition: _, statements } => {
       expr_uses_basic_induction_var(condition, v) || stmts_uses_basic_induction_var(statements, v)
     }
-    Statement::Break(e) => expr_uses_basic_induction_var(e, v),
+    Statement::Break(e) | Statement::Return(e) => expr_uses_basic_induction_var(e, v),
     Statement::While { loop_variables, statements, break_collector: _ } => {
       loop_variables.iter().any(|loop_var| {
         expr_uses_basic_induction_var(&loop_var.initial_value, v)
@@ -86,6 +92,12 @@ fn stmt_uses_basic_induction_var(
       function_name: _,
       context,
     } => expr_uses_basic_induction_var(context, v),
+    Statement::InterfaceInit {
+      interface_variable_name: _,
+      interface_type_name: _,
+      vtable: _,
+      context,
+    } => expr_uses_basic_induction_var(context, v),
   }
 }
 
@@ -94,6 +94,19 @@ pub(super) fn optimize(
           inner_stmts.push(stmt);
         }
       }
+      Statement::InterfaceInit {
+        interface_variable_name,
+        interface_type_name: _,
+        vtable: _,
+        context,
+      } => {
+        if expression_is_loop_invariant(context, &non_loop_invariant_variables) {
+          hoisted_stmts.push(stmt);
+        } else {
+          non_loop_invariant_variables.insert(*interface_variable_name);
+          inner_stmts.push(stmt);
+        }
+      }
       Statement::Call { callee: _, arguments: _, return_type: _, return_collector } => {
         if let Some(c) = &return_collector {
           non_loop_invariant_variables.insert(*c);
@@ -106,7 +119,7 @@ pub(super) fn optimize(
         }
         inner_stmts.push(stmt);
       }
-      Statement::SingleIf { .. } | Statement::Break(_) => {
+      Statement::SingleIf { .. } | Statement::Break(_) | Statement::Return(_) => {
         inner_stmts.push(stmt);
       }
       Statement::While { loop_variables: _, statements: _, break_collector } => {
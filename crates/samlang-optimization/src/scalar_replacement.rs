@@ -2,12 +2,16 @@
 //!
 //! When a struct is allocated and only accessed via `IndexedAccess` (never passed as
 //! a value to another operation), the allocation and field reads can be eliminated by
-//! tracking each field expression directly in a substitution map.
+//! tracking each field expression directly in a substitution map. Structs are immutable, so
+//! reads from a struct that does escape can still be replaced by the field expression it was
+//! initialized with, which removes the intermediate reads of `{ ...base, field: value }`. This
+//! also holds for reads through a late-initialized variable that is only ever assigned the struct.
 //!
 //! The same idea applies to closures: when a closure is created and only ever called
 //! (never passed as an argument), the `ClosureInit` + indirect `Call` through a variable
 //! can be replaced by a direct `Call` to the underlying function with the context
-//! inlined as the first argument.
+//! inlined as the first argument. Interface values that are only ever called are
+//! devirtualized the same way, by picking the function from their vtable.
 //!
 //! # Example (struct)
 //!
@@ -44,8 +48,10 @@ use samlang_ast::mir::{
 use samlang_heap::PStr;
 use std::collections::{HashMap, HashSet};
 
+#[derive(Clone)]
 struct StructDefinition {
   fields: Vec<Expression>,
+  escaped: bool,
 }
 
 struct ClosureDefinition {
@@ -53,9 +59,17 @@ struct ClosureDefinition {
   context: Expression,
 }
 
+struct InterfaceDefinition {
+  vtable: Vec<FunctionNameExpression>,
+  context: Expression,
+}
+
 struct EscapeAnalysis {
   struct_definitions: HashMap<PStr, StructDefinition>,
   closure_definitions: HashMap<PStr, ClosureDefinition>,
+  interface_definitions: HashMap<PStr, InterfaceDefinition>,
+  /// The variable that a late-initialized variable is assigned, if it is assigned exactly once.
+  late_init_aliases: HashMap<PStr, Option<PStr>>,
   escaped: HashSet<PStr>,
 }
 
@@ -64,6 +78,8 @@ impl EscapeAnalysis {
     Self {
       struct_definitions: HashMap::new(),
       closure_definitions: HashMap::new(),
+      interface_definitions: HashMap::new(),
+      late_init_aliases: HashMap::new(),
       escaped: HashSet::new(),
     }
   }
@@ -114,7 +130,7 @@ impl EscapeAnalysis {
         self.mark_escape(condition);
         self.visit_statements(statements);
       }
-      Statement::Break(expression) => {
+      Statement::Break(expression) | Statement::Return(expression) => {
         self.mark_escape(expression);
       }
       Statement::While { loop_variables, statements, break_collector: _ } => {
@@ -124,16 +140,25 @@ impl EscapeAnalysis {
         }
         self.visit_statements(statements);
       }
-      Statement::Cast { name: _, type_: _, assigned_expression }
-      | Statement::LateInitAssignment { name: _, assigned_expression } => {
+      Statement::Cast { name: _, type_: _, assigned_expression } => {
         self.mark_escape(assigned_expression);
       }
+      Statement::LateInitAssignment { name, assigned_expression } => {
+        self.mark_escape(assigned_expression);
+        let alias = assigned_expression.as_variable().map(|variable| variable.name);
+        self
+          .late_init_aliases
+          .entry(*name)
+          .and_modify(|existing| *existing = None)
+          .or_insert(alias);
+      }
       Statement::LateInitDeclaration { name: _, type_: _ } => {}
       Statement::StructInit { struct_variable_name, type_name: _, expression_list } => {
         self.mark_escapes(expression_list);
-        self
-          .struct_definitions
-          .insert(*struct_variable_name, StructDefinition { fields: expression_list.clone() });
+        self.struct_definitions.insert(
+          *struct_variable_name,
+          StructDefinition { fields: expression_list.clone(), escaped: false },
+        );
       }
       Statement::ClosureInit {
         closure_variable_name,
@@ -147,6 +172,18 @@ impl EscapeAnalysis {
           ClosureDefinition { function_name: function_name.clone(), context: *context },
         );
       }
+      Statement::InterfaceInit {
+        interface_variable_name,
+        interface_type_name: _,
+        vtable,
+        context,
+      } => {
+        self.mark_escape(context);
+        self.interface_definitions.insert(
+          *interface_variable_name,
+          InterfaceDefinition { vtable: vtable.clone(), context: *context },
+        );
+      }
     }
   }
 }
@@ -165,6 +202,7 @@ fn resolve_expression(
 fn rewrite_statements(
   scalar_replacement_structs: &HashMap<PStr, StructDefinition>,
   scalar_replacement_closures: &HashMap<PStr, ClosureDefinition>,
+  scalar_replacement_interfaces: &HashMap<PStr, InterfaceDefinition>,
   substitution: &mut HashMap<PStr, Expression>,
   statements: &[Statement],
   output: &mut Vec<Statement>,
@@ -173,6 +211,7 @@ fn rewrite_statements(
     rewrite_statement(
       scalar_replacement_structs,
       scalar_replacement_closures,
+      scalar_replacement_interfaces,
       substitution,
       statement,
       output,
@@ -184,6 +223,7 @@ fn rewrite_statements(
 fn rewrite_statement(
   scalar_replacement_structs: &HashMap<PStr, StructDefinition>,
   scalar_replacement_closures: &HashMap<PStr, ClosureDefinition>,
+  scalar_replacement_interfaces: &HashMap<PStr, InterfaceDefinition>,
   substitution: &mut HashMap<PStr, Expression>,
   statement: &Statement,
   output: &mut Vec<Statement>,
@@ -260,6 +300,39 @@ fn rewrite_statement(
           return_collector: *return_collector,
         });
       }
+      Callee::InterfaceMethod { receiver: Expression::Variable(variable), index }
+        if scalar_replacement_interfaces.contains_key(&variable.name) =>
+      {
+        let definition = scalar_replacement_interfaces.get(&variable.name).unwrap();
+        let function_name = definition.vtable[*index].clone();
+        let resolved_context = resolve_expression(substitution, definition.context);
+        let mut direct_arguments = vec![resolved_context];
+        for argument in arguments {
+          direct_arguments.push(resolve_expression(substitution, *argument));
+        }
+        output.push(Statement::Call {
+          callee: Callee::FunctionName(function_name),
+          arguments: direct_arguments,
+          return_type: *return_type,
+          return_collector: *return_collector,
+        });
+      }
+      Callee::InterfaceMethod { receiver, index } => {
+        let resolved_callee = Callee::InterfaceMethod {
+          receiver: resolve_expression(substitution, *receiver),
+          index: *index,
+        };
+        let mut resolved_arguments = Vec::new();
+        for argument in arguments {
+          resolved_arguments.push(resolve_expression(substitution, *argument));
+        }
+        output.push(Statement::Call {
+          callee: resolved_callee,
+          arguments: resolved_arguments,
+          return_type: *return_type,
+          return_collector: *return_collector,
+        });
+      }
       Callee::FunctionName(function_name_expression) => {
         let resolved_callee = Callee::FunctionName(function_name_expression.clone());
         let mut resolved_arguments = Vec::new();
@@ -280,6 +353,7 @@ fn rewrite_statement(
       rewrite_statements(
         scalar_replacement_structs,
         scalar_replacement_closures,
+        scalar_replacement_interfaces,
         substitution,
         s1,
         &mut then_statements,
@@ -288,6 +362,7 @@ fn rewrite_statement(
       rewrite_statements(
         scalar_replacement_structs,
         scalar_replacement_closures,
+        scalar_replacement_interfaces,
         substitution,
         s2,
         &mut else_statements,
@@ -314,6 +389,7 @@ fn rewrite_statement(
       rewrite_statements(
         scalar_replacement_structs,
         scalar_replacement_closures,
+        scalar_replacement_interfaces,
         substitution,
         statements,
         &mut rewritten_statements,
@@ -327,6 +403,9 @@ fn rewrite_statement(
     Statement::Break(expression) => {
       output.push(Statement::Break(resolve_expression(substitution, *expression)));
     }
+    Statement::Return(expression) => {
+      output.push(Statement::Return(resolve_expression(substitution, *expression)));
+    }
     Statement::While { loop_variables, statements, break_collector } => {
       let mut new_loop_variables = Vec::new();
       for GenenalLoopVariable { name, type_, initial_value, loop_value } in loop_variables {
@@ -341,6 +420,7 @@ fn rewrite_statement(
       rewrite_statements(
         scalar_replacement_structs,
         scalar_replacement_closures,
+        scalar_replacement_interfaces,
         substitution,
         statements,
         &mut rewritten_statements,
@@ -368,7 +448,7 @@ fn rewrite_statement(
       });
     }
     Statement::StructInit { struct_variable_name, type_name, expression_list } => {
-      if scalar_replacement_structs.contains_key(struct_variable_name) {
+      if scalar_replacement_structs.get(struct_variable_name).is_some_and(|it| !it.escaped) {
         // dropped
       } else {
         let mut resolved_fields = Vec::new();
@@ -394,6 +474,18 @@ fn rewrite_statement(
         });
       }
     }
+    Statement::InterfaceInit { interface_variable_name, interface_type_name, vtable, context } => {
+      if scalar_replacement_interfaces.contains_key(interface_variable_name) {
+        // dropped
+      } else {
+        output.push(Statement::InterfaceInit {
+          interface_variable_name: *interface_variable_name,
+          interface_type_name: *interface_type_name,
+          vtable: vtable.clone(),
+          context: resolve_expression(substitution, *context),
+        });
+      }
+    }
   }
 }
 
@@ -402,18 +494,35 @@ pub(super) fn optimize_function(function: &mut Function) {
   analysis.visit_statements(&function.body);
   analysis.mark_escape(&function.return_value);
 
-  let scalar_replacement_structs: HashMap<PStr, StructDefinition> = analysis
+  let mut scalar_replacement_structs: HashMap<PStr, StructDefinition> = analysis
     .struct_definitions
     .into_iter()
-    .filter(|(name, _)| !analysis.escaped.contains(name))
+    .map(|(name, definition)| {
+      (name, StructDefinition { escaped: analysis.escaped.contains(&name), ..definition })
+    })
     .collect();
+  // Reads through an alias are forwarded as well, while the struct itself is kept.
+  for (alias, assigned) in analysis.late_init_aliases {
+    if let Some(definition) = assigned.and_then(|name| scalar_replacement_structs.get(&name)) {
+      let aliased_definition = StructDefinition { escaped: true, ..definition.clone() };
+      scalar_replacement_structs.insert(alias, aliased_definition);
+    }
+  }
   let scalar_replacement_closures: HashMap<PStr, ClosureDefinition> = analysis
     .closure_definitions
     .into_iter()
     .filter(|(name, _)| !analysis.escaped.contains(name))
     .collect();
+  let scalar_replacement_interfaces: HashMap<PStr, InterfaceDefinition> = analysis
+    .interface_definitions
+    .into_iter()
+    .filter(|(name, _)| !analysis.escaped.contains(name))
+    .collect();
 
-  if scalar_replacement_structs.is_empty() && scalar_replacement_closures.is_empty() {
+  if scalar_replacement_structs.is_empty()
+    && scalar_replacement_closures.is_empty()
+    && scalar_replacement_interfaces.is_empty()
+  {
     return;
   }
 
@@ -422,6 +531,7 @@ pub(super) fn optimize_function(function: &mut Function) {
   rewrite_statements(
     &scalar_replacement_structs,
     &scalar_replacement_closures,
+    &scalar_replacement_interfaces,
     &mut substitution,
     &function.body,
     &mut new_body,
@@ -383,6 +383,164 @@ mod tests {
     );
   }
 
+  #[test]
+  fn struct_update_reads_of_escaping_struct_forwarded_test() {
+    let heap = &mut Heap::new();
+    let table = &mut SymbolTable::new();
+    let base = heap.alloc_str_for_test("base");
+    let x = heap.alloc_str_for_test("x");
+    let y = heap.alloc_str_for_test("y");
+    let updated = heap.alloc_str_for_test("updated");
+    let pair = id_type(heap, table, "Pair");
+    let g = FunctionName::new_for_test(heap.alloc_str_for_test("g"));
+    assert_correctly_optimized(
+      vec![
+        Statement::StructInit {
+          struct_variable_name: base,
+          type_name: pair,
+          expression_list: vec![ZERO, ONE],
+        },
+        Statement::IndexedAccess {
+          name: x,
+          type_: INT_32_TYPE,
+          pointer_expression: Expression::var_name(base, Type::Id(pair)),
+          index: 0,
+        },
+        Statement::IndexedAccess {
+          name: y,
+          type_: INT_32_TYPE,
+          pointer_expression: Expression::var_name(base, Type::Id(pair)),
+          index: 1,
+        },
+        Statement::StructInit {
+          struct_variable_name: updated,
+          type_name: pair,
+          expression_list: vec![
+            Expression::var_name(x, INT_32_TYPE),
+            Expression::var_name(y, INT_32_TYPE),
+          ],
+        },
+        // Both structs escape, so only the reads can be removed.
+        Statement::Call {
+          callee: Callee::FunctionName(FunctionNameExpression {
+            name: g,
+            type_: Type::new_fn_unwrapped(vec![Type::Id(pair)], INT_32_TYPE),
+          }),
+          arguments: vec![Expression::var_name(base, Type::Id(pair))],
+          return_type: INT_32_TYPE,
+          return_collector: None,
+        },
+      ],
+      Expression::var_name(updated, Type::Id(pair)),
+      heap,
+      table,
+      "let base: _Pair = [0, 1];\nlet updated: _Pair = [0, 1];\n__$g((base: _Pair));\nreturn (updated: _Pair);",
+    );
+  }
+
+  #[test]
+  fn struct_update_reads_through_late_init_alias_forwarded_test() {
+    let heap = &mut Heap::new();
+    let table = &mut SymbolTable::new();
+    let base = heap.alloc_str_for_test("base");
+    let alias = heap.alloc_str_for_test("alias");
+    let y = heap.alloc_str_for_test("y");
+    let z = heap.alloc_str_for_test("z");
+    let updated = heap.alloc_str_for_test("updated");
+    let triple = id_type(heap, table, "Triple");
+    let g = FunctionName::new_for_test(heap.alloc_str_for_test("g"));
+    assert_correctly_optimized(
+      vec![
+        Statement::StructInit {
+          struct_variable_name: base,
+          type_name: triple,
+          expression_list: vec![ZERO, ONE, Expression::i32(2)],
+        },
+        Statement::LateInitDeclaration { name: alias, type_: Type::Id(triple) },
+        Statement::LateInitAssignment {
+          name: alias,
+          assigned_expression: Expression::var_name(base, Type::Id(triple)),
+        },
+        Statement::Call {
+          callee: Callee::FunctionName(FunctionNameExpression {
+            name: g,
+            type_: Type::new_fn_unwrapped(vec![Type::Id(triple)], INT_32_TYPE),
+          }),
+          arguments: vec![Expression::var_name(alias, Type::Id(triple))],
+          return_type: INT_32_TYPE,
+          return_collector: None,
+        },
+        // `{ ...alias, x: 1 }`
+        Statement::IndexedAccess {
+          name: y,
+          type_: INT_32_TYPE,
+          pointer_expression: Expression::var_name(alias, Type::Id(triple)),
+          index: 1,
+        },
+        Statement::IndexedAccess {
+          name: z,
+          type_: INT_32_TYPE,
+          pointer_expression: Expression::var_name(alias, Type::Id(triple)),
+          index: 2,
+        },
+        Statement::StructInit {
+          struct_variable_name: updated,
+          type_name: triple,
+          expression_list: vec![
+            ONE,
+            Expression::var_name(y, INT_32_TYPE),
+            Expression::var_name(z, INT_32_TYPE),
+          ],
+        },
+      ],
+      Expression::var_name(updated, Type::Id(triple)),
+      heap,
+      table,
+      "let base: _Triple = [0, 1, 2];\nlet alias: _Triple;\nalias = (base: _Triple);\n__$g((alias: _Triple));\nlet updated: _Triple = [1, 1, 2];\nreturn (updated: _Triple);",
+    );
+
+    // An alias assigned more than once may hold either struct, so its reads are kept.
+    let other = heap.alloc_str_for_test("other");
+    let condition = heap.alloc_str_for_test("c");
+    assert_correctly_optimized(
+      vec![
+        Statement::StructInit {
+          struct_variable_name: base,
+          type_name: triple,
+          expression_list: vec![ZERO, ONE, Expression::i32(2)],
+        },
+        Statement::StructInit {
+          struct_variable_name: other,
+          type_name: triple,
+          expression_list: vec![ONE, ONE, ONE],
+        },
+        Statement::LateInitDeclaration { name: alias, type_: Type::Id(triple) },
+        Statement::IfElse {
+          condition: Expression::var_name(condition, INT_32_TYPE),
+          s1: vec![Statement::LateInitAssignment {
+            name: alias,
+            assigned_expression: Expression::var_name(base, Type::Id(triple)),
+          }],
+          s2: vec![Statement::LateInitAssignment {
+            name: alias,
+            assigned_expression: Expression::var_name(other, Type::Id(triple)),
+          }],
+          final_assignments: Vec::new(),
+        },
+        Statement::IndexedAccess {
+          name: y,
+          type_: INT_32_TYPE,
+          pointer_expression: Expression::var_name(alias, Type::Id(triple)),
+          index: 1,
+        },
+      ],
+      Expression::var_name(y, INT_32_TYPE),
+      heap,
+      table,
+      "let base: _Triple = [0, 1, 2];\nlet other: _Triple = [1, 1, 1];\nlet alias: _Triple;\nif (c: int) {\n  alias = (base: _Triple);\n} else {\n  alias = (other: _Triple);\n}\nlet y: int = (alias: _Triple)[1];\nreturn (y: int);",
+    );
+  }
+
   #[test]
   fn devirtualizable_closure_test() {
     let heap = &mut Heap::new();
@@ -426,6 +584,72 @@ mod tests {
     );
   }
 
+  #[test]
+  fn devirtualizable_interface_test() {
+    let heap = &mut Heap::new();
+    let table = &mut SymbolTable::new();
+    let i = heap.alloc_str_for_test("i");
+    let r = heap.alloc_str_for_test("r");
+    let cx_t = id_type(heap, table, "Cx");
+    let i_t = id_type(heap, table, "I");
+    let f = FunctionName::new_for_test(heap.alloc_str_for_test("f"));
+    let g = FunctionName::new_for_test(heap.alloc_str_for_test("g"));
+    let method_type = FunctionType {
+      argument_types: vec![Type::Id(cx_t), INT_32_TYPE],
+      return_type: Box::new(INT_32_TYPE),
+    };
+    assert_correctly_optimized(
+      vec![
+        Statement::InterfaceInit {
+          interface_variable_name: i,
+          interface_type_name: i_t,
+          vtable: vec![
+            FunctionNameExpression { name: f, type_: method_type.clone() },
+            FunctionNameExpression { name: g, type_: method_type },
+          ],
+          context: ZERO,
+        },
+        Statement::Call {
+          callee: Callee::InterfaceMethod {
+            receiver: Expression::var_name(i, Type::Id(i_t)),
+            index: 1,
+          },
+          arguments: vec![ONE],
+          return_type: INT_32_TYPE,
+          return_collector: Some(r),
+        },
+      ],
+      Expression::var_name(r, INT_32_TYPE),
+      heap,
+      table,
+      "let r: int = __$g(0, 1);\nreturn (r: int);",
+    );
+  }
+
+  #[test]
+  fn interface_escapes_via_return_test() {
+    let heap = &mut Heap::new();
+    let table = &mut SymbolTable::new();
+    let i = heap.alloc_str_for_test("i");
+    let i_t = id_type(heap, table, "I");
+    let f = FunctionName::new_for_test(heap.alloc_str_for_test("f"));
+    assert_correctly_optimized(
+      vec![Statement::InterfaceInit {
+        interface_variable_name: i,
+        interface_type_name: i_t,
+        vtable: vec![FunctionNameExpression {
+          name: f,
+          type_: Type::new_fn_unwrapped(vec![INT_32_TYPE], INT_32_TYPE),
+        }],
+        context: ZERO,
+      }],
+      Expression::var_name(i, Type::Id(i_t)),
+      heap,
+      table,
+      "let i: _I = Interface { vtable: [__$f: (int) -> int], context: 0 };\nreturn (i: _I);",
+    );
+  }
+
   #[test]
   fn closure_escapes_via_call_arg_test() {
     let heap = &mut Heap::new();
@@ -756,7 +980,10 @@ mod tests {
         Statement::IndexedAccess {
           name: unrelated_idx_name,
           type_: INT_32_TYPE,
-          pointer_expression: Expression::var_name(escaping_struct, Type::Id(other_t)),
+          pointer_expression: Expression::var_name(
+            heap.alloc_str_for_test("untracked"),
+            Type::Id(other_t),
+          ),
           index: 0,
         },
         // ClosureInit kept (escapes via return).
@@ -844,7 +1071,7 @@ mod tests {
       table,
       "let escaping_struct: _Other = [0];\n\
        let forced_escape = (escaping_struct: _Other) as int;\n\
-       let uia: int = (escaping_struct: _Other)[0];\n\
+       let uia: int = (untracked: _Other)[0];\n\
        let cl_keep: _Cl = Closure { fun: (__$f: (int) -> int), context: 0 };\n\
        let bin_v = 0 + 1;\n\
        let not_v = !0;\n\
@@ -942,7 +1169,9 @@ mod tests {
     samlang_ast::mir::Sources {
       symbol_table: SymbolTable::new(),
       global_variables: Vec::new(),
+      global_constants: Vec::new(),
       closure_types: Vec::new(),
+      interface_types: Vec::new(),
       type_definitions: Vec::new(),
       main_function_names: vec![FunctionName::new_for_test(PStr::MAIN_FN)],
       functions,
@@ -1,8 +1,8 @@
 use itertools::Itertools;
 use samlang_ast::mir::{
   Binary, Callee, ClosureTypeDefinition, EnumTypeDefinition, Expression, Function, FunctionName,
-  GenenalLoopVariable, IfElseFinalAssignment, Sources, Statement, Type, TypeDefinition,
-  TypeDefinitionMappings, TypeNameId,
+  GenenalLoopVariable, IfElseFinalAssignment, InterfaceTypeDefinition, Sources, Statement, Type,
+  TypeDefinition, TypeDefinitionMappings, TypeNameId,
 };
 use samlang_heap::PStr;
 use std::collections::{HashMap, HashSet};
@@ -25,7 +25,10 @@ fn collect_used_names_from_expression(
   expression: &Expression,
 ) {
   match expression {
-    Expression::Int32Literal(_) | Expression::Int31Literal(_) => {}
+    Expression::Int32Literal(_) | Expression::Int31Literal(_) | Expression::FloatLiteral(_) => {}
+    Expression::GlobalConstant(g) => {
+      collect_for_type_set(&g.type_, type_set);
+    }
     Expression::Variable(v) => {
       collect_for_type_set(&v.type_, type_set);
     }
@@ -64,6 +67,9 @@ fn collect_used_names_from_statement(
           type_set.insert(n.name.type_name);
         }
         Callee::Variable(v) => collect_for_type_set(&v.type_, type_set),
+        Callee::InterfaceMethod { receiver, index: _ } => {
+          collect_used_names_from_expression(str_name_set, type_set, receiver)
+        }
       }
       for e in arguments {
         collect_used_names_from_expression(str_name_set, type_set, e);
@@ -84,7 +90,9 @@ fn collect_used_names_from_statement(
       collect_used_names_from_expression(str_name_set, type_set, condition);
       collect_used_names_from_statements(str_name_set, fn_name_set, type_set, statements);
     }
-    Statement::Break(e) => collect_used_names_from_expression(str_name_set, type_set, e),
+    Statement::Break(e) | Statement::Return(e) => {
+      collect_used_names_from_expression(str_name_set, type_set, e)
+    }
     Statement::While { loop_variables, statements, break_collector } => {
       for GenenalLoopVariable { name: _, type_, initial_value, loop_value } in loop_variables {
         collect_for_type_set(type_, type_set);
@@ -121,6 +129,19 @@ fn collect_used_names_from_statement(
       collect_used_names_from_expression(str_name_set, type_set, context);
       type_set.insert(*closure_type_name);
     }
+    Statement::InterfaceInit {
+      interface_variable_name: _,
+      interface_type_name,
+      vtable,
+      context,
+    } => {
+      for function_name in vtable {
+        fn_name_set.insert(function_name.name);
+        type_set.insert(function_name.name.type_name);
+      }
+      collect_used_names_from_expression(str_name_set, type_set, context);
+      type_set.insert(*interface_type_name);
+    }
   }
 }
 
@@ -157,6 +178,7 @@ fn get_other_functions_used_by_given_function(
 fn analyze_all_used_names(
   functions: &Vec<Function>,
   closure_types: &[ClosureTypeDefinition],
+  interface_types: &[InterfaceTypeDefinition],
   type_definitions: &[TypeDefinition],
   entry_points: &[FunctionName],
 ) -> (HashSet<PStr>, HashSet<FunctionName>, HashSet<TypeNameId>) {
@@ -171,6 +193,14 @@ fn analyze_all_used_names(
     collect_for_type_set(&d.function_type.return_type, &mut type_set);
     type_def_map.insert(d.name, type_set);
   }
+  for d in interface_types {
+    let mut type_set = HashSet::new();
+    for method in &d.methods {
+      collect_types_for_type_set(&method.argument_types, &mut type_set);
+      collect_for_type_set(&method.return_type, &mut type_set);
+    }
+    type_def_map.insert(d.name, type_set);
+  }
   for d in type_definitions {
     let mut type_set = HashSet::new();
     match &d.mappings {
@@ -236,11 +266,19 @@ fn analyze_all_used_names(
 }
 
 pub(super) fn optimize_sources(sources: &mut Sources) {
+  // Global constants are initialized eagerly, so their initializers are entry points too.
+  let entry_points = sources
+    .main_function_names
+    .iter()
+    .copied()
+    .chain(sources.global_constants.iter().map(|it| it.initializer))
+    .collect_vec();
   let (used_str_names, used_fn_names, mut used_types) = analyze_all_used_names(
     &sources.functions,
     &sources.closure_types,
+    &sources.interface_types,
     &sources.type_definitions,
-    &sources.main_function_names,
+    &entry_points,
   );
   // When a subtype is used, ensure its parent type is also marked as used.
   // This is necessary because subtypes are created in LIR lowering from parent enum definitions.
@@ -256,6 +294,7 @@ pub(super) fn optimize_sources(sources: &mut Sources) {
   }
   sources.global_variables.retain(|it| used_str_names.contains(&it.0));
   sources.closure_types.retain(|it| used_types.contains(&it.name));
+  sources.interface_types.retain(|it| used_types.contains(&it.name));
   sources.type_definitions.retain(|it| used_types.contains(&it.name));
   sources.functions.retain(|it| used_fn_names.contains(&it.name));
 }
@@ -285,6 +324,7 @@ mod tests {
         GlobalString(heap.alloc_str_for_test("bar")),
         GlobalString(heap.alloc_str_for_test("fsdfsdf")),
       ],
+      global_constants: Vec::new(),
       closure_types: vec![
         ClosureTypeDefinition {
           name: table.create_type_name_for_test(heap.alloc_str_for_test("Foo")),
@@ -299,6 +339,7 @@ mod tests {
           function_type: Type::new_fn_unwrapped(Vec::new(), INT_32_TYPE),
         },
       ],
+      interface_types: Vec::new(),
       type_definitions: vec![
         TypeDefinition {
           name: table.create_type_name_for_test(heap.alloc_str_for_test("Foo")),
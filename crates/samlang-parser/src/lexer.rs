@@ -25,6 +25,8 @@ enum LogosToken {
   KeywordMethod,
   #[token("as")]
   KeywordAs,
+  #[token("derives")]
+  KeywordDerives,
   // Keywords: Visibility modifiers
   #[token("private")]
   KeywordPrivate,
@@ -45,9 +47,19 @@ enum LogosToken {
   KeywordMatch,
   #[token("return")]
   KeywordReturn,
+  #[token("while")]
+  KeywordWhile,
+  #[token("for")]
+  KeywordFor,
+  #[token("in")]
+  KeywordIn,
+  #[token("break")]
+  KeywordBreak,
   // Keywords: Types
   #[token("int")]
   KeywordInt,
+  #[token("float")]
+  KeywordFloat,
   #[token("string")]
   KeywordString,
   #[token("bool")]
@@ -145,6 +157,22 @@ enum LogosToken {
   OpAnd,
   #[token("||")]
   OpOr,
+  #[token("|>")]
+  OpPipe,
+  #[token("&")]
+  OpBitAnd,
+  #[token("^")]
+  OpBitXor,
+  #[token("~")]
+  OpBitNot,
+  #[token("<<")]
+  OpShiftLeft,
+  #[token(">>")]
+  OpShiftRight,
+  #[token(">>>")]
+  OpUnsignedShiftRight,
+  #[token("..")]
+  OpDotDot,
   #[token("...")]
   OpDotDotDot,
   // Identifiers
@@ -152,29 +180,51 @@ enum LogosToken {
   UpperId,
   #[regex("[a-z][A-Za-z0-9]*")]
   LowerId,
-  #[regex("0|([1-9][0-9]*)")]
+  // Misplaced digit separators and out of range values are reported when the token is processed.
+  #[regex("0|([1-9][0-9_]*)|(0[xX][0-9A-Fa-f_]*)|(0[bB][01_]*)")]
   Int,
+  #[regex(r"(0|[1-9][0-9]*)\.[0-9]+([eE][+-]?[0-9]+)?")]
+  Float,
+}
+
+enum StringSegmentEnd {
+  Quote,
+  Hole,
 }
 
 struct WrappedLogosLexer<'a> {
   lexer: logos::Lexer<'a, LogosToken>,
   module_reference: ModuleReference,
   position: Position,
+  /// For each template string hole we are currently in, the number of unclosed `{` inside it.
+  template_hole_depths: Vec<usize>,
 }
 
 impl<'a> WrappedLogosLexer<'a> {
   fn new(source: &'a str, module_reference: ModuleReference) -> Self {
-    Self { lexer: LogosToken::lexer(source), module_reference, position: Position(0, 0) }
+    Self {
+      lexer: LogosToken::lexer(source),
+      module_reference,
+      position: Position(0, 0),
+      template_hole_depths: Vec::new(),
+    }
   }
 
   fn next_token(&mut self, heap: &mut Heap, error_set: &mut ErrorSet) -> Option<Token> {
     self.skip_whitespace();
 
-    if let Some((loc, s)) = self.lex_str_lit_opt() {
+    if let Some((loc, s, segment_end)) = self.lex_str_lit_opt() {
       if !string_has_valid_escape(&s) {
         error_set.report_invalid_syntax_error(loc, "Invalid escape in string.".to_string())
       }
-      return Some(Token(loc, TokenContent::StringLiteral(heap.alloc_string(s))));
+      let p_str = heap.alloc_string(s);
+      return Some(match segment_end {
+        StringSegmentEnd::Quote => Token(loc, TokenContent::StringLiteral(p_str)),
+        StringSegmentEnd::Hole => {
+          self.template_hole_depths.push(0);
+          Token(loc, TokenContent::TemplateStringHead(p_str))
+        }
+      });
     }
 
     if let Some((loc, s)) = self.lex_line_comment_opt() {
@@ -226,6 +276,7 @@ impl<'a> WrappedLogosLexer<'a> {
       LogosToken::KeywordFunction => Some(self.translate_keyword_token(Keyword::Function)),
       LogosToken::KeywordMethod => Some(self.translate_keyword_token(Keyword::Method)),
       LogosToken::KeywordAs => Some(self.translate_keyword_token(Keyword::As)),
+      LogosToken::KeywordDerives => Some(self.translate_keyword_token(Keyword::Derives)),
       LogosToken::KeywordPrivate => Some(self.translate_keyword_token(Keyword::Private)),
       LogosToken::KeywordProtected => Some(self.translate_keyword_token(Keyword::Protected)),
       LogosToken::KeywordInternal => Some(self.translate_keyword_token(Keyword::Internal)),
@@ -235,7 +286,12 @@ impl<'a> WrappedLogosLexer<'a> {
       LogosToken::KeywordElse => Some(self.translate_keyword_token(Keyword::Else)),
       LogosToken::KeywordMatch => Some(self.translate_keyword_token(Keyword::Match)),
       LogosToken::KeywordReturn => Some(self.translate_keyword_token(Keyword::Return)),
+      LogosToken::KeywordWhile => Some(self.translate_keyword_token(Keyword::While)),
+      LogosToken::KeywordFor => Some(self.translate_keyword_token(Keyword::For)),
+      LogosToken::KeywordIn => Some(self.translate_keyword_token(Keyword::In)),
+      LogosToken::KeywordBreak => Some(self.translate_keyword_token(Keyword::Break)),
       LogosToken::KeywordInt => Some(self.translate_keyword_token(Keyword::Int)),
+      LogosToken::KeywordFloat => Some(self.translate_keyword_token(Keyword::Float)),
       LogosToken::KeywordString => Some(self.translate_keyword_token(Keyword::String)),
       LogosToken::KeywordBool => Some(self.translate_keyword_token(Keyword::Bool)),
       LogosToken::KeywordUnit => Some(self.translate_keyword_token(Keyword::Unit)),
@@ -256,8 +312,23 @@ impl<'a> WrappedLogosLexer<'a> {
       LogosToken::OpUnderscore => Some(self.translate_op_token(TokenOp::Underscore)),
       LogosToken::OpLeftParenthesis => Some(self.translate_op_token(TokenOp::LeftParenthesis)),
       LogosToken::OpRightParenthesis => Some(self.translate_op_token(TokenOp::RightParenthesis)),
-      LogosToken::OpLeftBrace => Some(self.translate_op_token(TokenOp::LeftBrace)),
-      LogosToken::OpRightBrace => Some(self.translate_op_token(TokenOp::RightBrace)),
+      LogosToken::OpLeftBrace => {
+        if let Some(depth) = self.template_hole_depths.last_mut() {
+          *depth += 1;
+        }
+        Some(self.translate_op_token(TokenOp::LeftBrace))
+      }
+      LogosToken::OpRightBrace => match self.template_hole_depths.last_mut() {
+        Some(0) => {
+          self.template_hole_depths.pop();
+          Some(self.lex_template_str_continuation(heap, error_set))
+        }
+        Some(depth) => {
+          *depth -= 1;
+          Some(self.translate_op_token(TokenOp::RightBrace))
+        }
+        None => Some(self.translate_op_token(TokenOp::RightBrace)),
+      },
       LogosToken::OpLeftBracket => Some(self.translate_op_token(TokenOp::LeftBracket)),
       LogosToken::OpRightBracket => Some(self.translate_op_token(TokenOp::RightBracket)),
       LogosToken::OpQuestion => Some(self.translate_op_token(TokenOp::Question)),
@@ -285,6 +356,16 @@ impl<'a> WrappedLogosLexer<'a> {
       LogosToken::OpNotEqual => Some(self.translate_op_token(TokenOp::NotEqual)),
       LogosToken::OpAnd => Some(self.translate_op_token(TokenOp::And)),
       LogosToken::OpOr => Some(self.translate_op_token(TokenOp::Or)),
+      LogosToken::OpPipe => Some(self.translate_op_token(TokenOp::Pipe)),
+      LogosToken::OpBitAnd => Some(self.translate_op_token(TokenOp::BitAnd)),
+      LogosToken::OpBitXor => Some(self.translate_op_token(TokenOp::BitXor)),
+      LogosToken::OpBitNot => Some(self.translate_op_token(TokenOp::BitNot)),
+      LogosToken::OpShiftLeft => Some(self.translate_op_token(TokenOp::ShiftLeft)),
+      LogosToken::OpShiftRight => Some(self.translate_op_token(TokenOp::ShiftRight)),
+      LogosToken::OpUnsignedShiftRight => {
+        Some(self.translate_op_token(TokenOp::UnsignedShiftRight))
+      }
+      LogosToken::OpDotDot => Some(self.translate_op_token(TokenOp::DotDot)),
       LogosToken::OpDotDotDot => Some(self.translate_op_token(TokenOp::DotDotDot)),
       LogosToken::UpperId => {
         let loc = self.loc_of_lexer_span();
@@ -301,6 +382,11 @@ impl<'a> WrappedLogosLexer<'a> {
         let p_str = heap.alloc_string(self.lexer.slice().to_string());
         Some(Token(loc, TokenContent::IntLiteral(p_str)))
       }
+      LogosToken::Float => {
+        let loc = self.loc_of_lexer_span();
+        let p_str = heap.alloc_string(self.lexer.slice().to_string());
+        Some(Token(loc, TokenContent::FloatLiteral(p_str)))
+      }
     }
   }
 
@@ -314,37 +400,31 @@ impl<'a> WrappedLogosLexer<'a> {
     Token(loc, TokenContent::Operator(op))
   }
 
-  fn lex_str_lit_opt(&mut self) -> Option<(Location, String)> {
-    let remainder = self.lexer.remainder();
-    if !remainder.starts_with('"') {
-      return None;
-    }
-    let remainder_bytes = remainder.as_bytes();
-    let start = self.position;
-    let mut pos = 1;
+  /// Scan a string segment starting at `start`, until the closing quote or the start of a
+  /// template string hole. Returns the exclusive end of the segment.
+  fn scan_str_segment(bytes: &[u8], start: usize) -> Option<(usize, StringSegmentEnd)> {
+    let mut pos = start;
     loop {
-      if pos >= remainder_bytes.len() {
+      if pos >= bytes.len() {
         return Option::None;
       }
-      let c = remainder_bytes[pos];
-      if c == b'"' {
+      let c = bytes[pos];
+      if c == b'"' || (c == b'$' && bytes.get(pos + 1) == Some(&b'{')) {
         let mut escape_count = 0;
-        for i in (1..pos).rev() {
-          if (remainder_bytes[i]) != b'\\' {
+        for i in (start..pos).rev() {
+          if (bytes[i]) != b'\\' {
             break;
           }
           escape_count += 1;
         }
         // We don't validate escaping here.
+        // When there are even number of escapes, the character is not escaped.
         if escape_count % 2 == 0 {
-          let string = String::from_utf8(remainder_bytes[..(pos + 1)].to_vec()).unwrap();
-          self.lexer.bump(pos + 1);
-          // When there are even number of escapes, the quote is not escaped,
-          // so it's the ending quote.
-          self.position.1 += pos as u32 + 1;
-          let end = self.position;
-          let loc = Location { module_reference: self.module_reference, start, end };
-          return Some((loc, string));
+          return Some(if c == b'"' {
+            (pos + 1, StringSegmentEnd::Quote)
+          } else {
+            (pos + 2, StringSegmentEnd::Hole)
+          });
         }
       }
       if c == b'\n' {
@@ -354,6 +434,94 @@ impl<'a> WrappedLogosLexer<'a> {
     }
   }
 
+  fn lex_str_lit_opt(&mut self) -> Option<(Location, String, StringSegmentEnd)> {
+    let remainder = self.lexer.remainder();
+    if remainder.starts_with("\"\"\"") {
+      return self.lex_multiline_str_lit_opt();
+    }
+    if remainder.starts_with("r\"") {
+      return self.lex_raw_str_lit_opt();
+    }
+    if !remainder.starts_with('"') {
+      return None;
+    }
+    let remainder_bytes = remainder.as_bytes();
+    let (end, segment_end) = Self::scan_str_segment(remainder_bytes, 1)?;
+    let string = String::from_utf8(remainder_bytes[..end].to_vec()).unwrap();
+    self.lexer.bump(end);
+    let loc = self.loc_of_advance(end);
+    Some((loc, string, segment_end))
+  }
+
+  /// Lex a raw string `r"..."`, which has no escapes and no template holes.
+  fn lex_raw_str_lit_opt(&mut self) -> Option<(Location, String, StringSegmentEnd)> {
+    let remainder_bytes = self.lexer.remainder().as_bytes();
+    let len = remainder_bytes[2..].iter().position(|c| *c == b'"' || *c == b'\n')?;
+    if remainder_bytes[2 + len] != b'"' {
+      return None;
+    }
+    let end = len + 3;
+    let string = String::from_utf8(remainder_bytes[..end].to_vec()).unwrap();
+    self.lexer.bump(end);
+    let loc = self.loc_of_advance(end);
+    Some((loc, string, StringSegmentEnd::Quote))
+  }
+
+  /// Lex a triple-quoted string, which may span multiple lines but has no template holes.
+  fn lex_multiline_str_lit_opt(&mut self) -> Option<(Location, String, StringSegmentEnd)> {
+    let remainder_bytes = self.lexer.remainder().as_bytes();
+    let mut pos = 3;
+    let mut escape_count = 0;
+    loop {
+      let c = *remainder_bytes.get(pos)?;
+      if c == b'"' && escape_count % 2 == 0 && remainder_bytes[pos..].starts_with(b"\"\"\"") {
+        break;
+      }
+      escape_count = if c == b'\\' { escape_count + 1 } else { 0 };
+      pos += 1;
+    }
+    let end = pos + 3;
+    let string = String::from_utf8(remainder_bytes[..end].to_vec()).unwrap();
+    self.lexer.bump(end);
+    let start = self.position;
+    for c in string.as_bytes() {
+      self.next_line_or_column(*c);
+    }
+    let loc = Location { module_reference: self.module_reference, start, end: self.position };
+    Some((loc, string, StringSegmentEnd::Quote))
+  }
+
+  /// Lex the rest of a template string after the `}` that closes a hole.
+  fn lex_template_str_continuation(&mut self, heap: &mut Heap, error_set: &mut ErrorSet) -> Token {
+    let remainder_bytes = self.lexer.remainder().as_bytes();
+    match Self::scan_str_segment(remainder_bytes, 0) {
+      Some((end, segment_end)) => {
+        let string = format!("}}{}", String::from_utf8(remainder_bytes[..end].to_vec()).unwrap());
+        self.lexer.bump(end);
+        let loc = self.loc_of_advance(end + 1);
+        if !string_has_valid_escape(&string) {
+          error_set.report_invalid_syntax_error(loc, "Invalid escape in string.".to_string())
+        }
+        let p_str = heap.alloc_string(string);
+        match segment_end {
+          StringSegmentEnd::Quote => Token(loc, TokenContent::TemplateStringTail(p_str)),
+          StringSegmentEnd::Hole => {
+            self.template_hole_depths.push(0);
+            Token(loc, TokenContent::TemplateStringMiddle(p_str))
+          }
+        }
+      }
+      None => {
+        let len = remainder_bytes.iter().take_while(|c| **c != b'\n').count();
+        let string = format!("}}{}", String::from_utf8(remainder_bytes[..len].to_vec()).unwrap());
+        self.lexer.bump(len);
+        let loc = self.loc_of_advance(len + 1);
+        error_set.report_invalid_syntax_error(loc, "Invalid token.".to_string());
+        Token(loc, TokenContent::Error(heap.alloc_string(string)))
+      }
+    }
+  }
+
   fn lex_block_comment_opt(&mut self) -> Option<(bool, Location, String)> {
     fn post_process_block_comment(block_comment: &str) -> String {
       block_comment
@@ -486,6 +654,7 @@ pub(super) enum Keyword {
   Function,
   Method,
   As,
+  Derives,
   // Visibility modifiers
   Private,
   Protected,
@@ -497,8 +666,13 @@ pub(super) enum Keyword {
   Else,
   Match,
   Return,
+  While,
+  For,
+  In,
+  Break,
   // Type Keywords
   Int,
+  Float,
   Bool,
   Unit,
   // Some Important Literals
@@ -531,6 +705,7 @@ impl Keyword {
       Keyword::Function => "function",
       Keyword::Method => "method",
       Keyword::As => "as",
+      Keyword::Derives => "derives",
       Keyword::Private => "private",
       Keyword::Protected => "protected",
       Keyword::Internal => "internal",
@@ -540,7 +715,12 @@ impl Keyword {
       Keyword::Else => "else",
       Keyword::Match => "match",
       Keyword::Return => "return",
+      Keyword::While => "while",
+      Keyword::For => "for",
+      Keyword::In => "in",
+      Keyword::Break => "break",
       Keyword::Int => "int",
+      Keyword::Float => "float",
       Keyword::String => "string",
       Keyword::Bool => "bool",
       Keyword::Unit => "unit",
@@ -597,6 +777,14 @@ pub(super) enum TokenOp {
   NotEqual,
   And,
   Or,
+  Pipe,
+  BitAnd,
+  BitXor,
+  BitNot,
+  ShiftLeft,
+  ShiftRight,
+  UnsignedShiftRight,
+  DotDot,
   DotDotDot,
 }
 
@@ -633,6 +821,14 @@ impl TokenOp {
       TokenOp::NotEqual => "!=",
       TokenOp::And => "&&",
       TokenOp::Or => "||",
+      TokenOp::Pipe => "|>",
+      TokenOp::BitAnd => "&",
+      TokenOp::BitXor => "^",
+      TokenOp::BitNot => "~",
+      TokenOp::ShiftLeft => "<<",
+      TokenOp::ShiftRight => ">>",
+      TokenOp::UnsignedShiftRight => ">>>",
+      TokenOp::DotDot => "..",
       TokenOp::DotDotDot => "...",
     }
   }
@@ -646,7 +842,11 @@ pub(super) enum TokenContent {
   UpperId(PStr),
   LowerId(PStr),
   StringLiteral(PStr),
+  TemplateStringHead(PStr),
+  TemplateStringMiddle(PStr),
+  TemplateStringTail(PStr),
   IntLiteral(PStr),
+  FloatLiteral(PStr),
   LineComment(PStr),
   BlockComment(PStr),
   DocComment(PStr),
@@ -662,7 +862,11 @@ impl TokenContent {
       TokenContent::UpperId(s)
       | TokenContent::LowerId(s)
       | TokenContent::StringLiteral(s)
+      | TokenContent::TemplateStringHead(s)
+      | TokenContent::TemplateStringMiddle(s)
+      | TokenContent::TemplateStringTail(s)
       | TokenContent::IntLiteral(s)
+      | TokenContent::FloatLiteral(s)
       | TokenContent::LineComment(s)
       | TokenContent::BlockComment(s)
       | TokenContent::DocComment(s) => s.as_str(heap).to_string(),
@@ -682,20 +886,53 @@ impl Token {
   }
 }
 
+/// Returns the value of an int literal token, or an error message when it is malformed.
+/// Hex and binary literals can spell any 32-bit pattern, so `0xFFFF_FFFF` is `-1`, while decimal
+/// literals must fit in an i32. The only exception is `2147483648`, which is accepted here since
+/// it can be negated into `i32::MIN`.
+pub(super) fn int_literal_value(spelling: &str) -> Result<i64, &'static str> {
+  let (radix, digits) = match spelling.get(..2) {
+    Some("0x" | "0X") => (16, &spelling[2..]),
+    Some("0b" | "0B") => (2, &spelling[2..]),
+    _ => (10, spelling),
+  };
+  if digits.is_empty() {
+    return Err("Missing digits after the radix prefix.");
+  }
+  if digits.starts_with('_') || digits.ends_with('_') || digits.contains("__") {
+    return Err("Digit separators must be placed between digits.");
+  }
+  let max = if radix == 10 { (i32::MAX as i64) + 1 } else { u32::MAX as i64 };
+  match i64::from_str_radix(&digits.replace('_', ""), radix) {
+    Ok(value) if value <= max => Ok(if radix == 10 { value } else { value as u32 as i32 as i64 }),
+    _ => Err("Not a 32-bit integer."),
+  }
+}
+
 fn string_has_valid_escape(s: &str) -> bool {
-  let mut has_unprocessed_escape = false;
-  for c in s.chars() {
-    if c == '\\' {
-      has_unprocessed_escape = !has_unprocessed_escape;
+  if s.starts_with("r\"") {
+    return true;
+  }
+  let mut chars = s.chars();
+  while let Some(c) = chars.next() {
+    if c != '\\' {
       continue;
     }
-    if has_unprocessed_escape {
-      match c {
-        't' | 'v' | '0' | 'b' | 'f' | 'n' | 'r' | '"' => {
-          has_unprocessed_escape = false;
+    match chars.next() {
+      Some('t' | 'v' | '0' | 'b' | 'f' | 'n' | 'r' | '"' | '\\' | '$') => {}
+      Some('u') => {
+        if chars.next() != Some('{') {
+          return false;
+        }
+        let digits = chars.by_ref().take_while(|c| *c != '}').collect::<String>();
+        let is_valid_code_point = (1..=6).contains(&digits.len())
+          && digits.chars().all(|c| c.is_ascii_hexdigit())
+          && u32::from_str_radix(&digits, 16).ok().and_then(char::from_u32).is_some();
+        if !is_valid_code_point {
+          return false;
         }
-        _ => return false,
       }
+      _ => return false,
     }
   }
   true
@@ -739,15 +976,13 @@ impl<'a> TokenProducer<'a> {
     match token {
       Token(loc, TokenContent::IntLiteral(p_str)) => {
         let s = p_str.as_str(heap);
-        match s.parse::<i64>() {
-          Result::Err(_) => {
-            error_set.report_invalid_syntax_error(loc, "Not a 32-bit integer.".to_string());
+        match int_literal_value(s) {
+          Result::Err(message) => {
+            error_set.report_invalid_syntax_error(loc, message.to_string());
           }
           Result::Ok(i64) => {
             let maxi32_plus1 = (i32::MAX as i64) + 1;
-            if i64 > maxi32_plus1 || (i64 == maxi32_plus1 && self.pending.is_none()) {
-              error_set.report_invalid_syntax_error(loc, "Not a 32-bit integer.".to_string());
-            } else if i64 == maxi32_plus1
+            if i64 == maxi32_plus1
               && let Option::Some(Token(prev_loc, TokenContent::Operator(TokenOp::Minus))) =
                 &self.pending
             {
@@ -757,11 +992,19 @@ impl<'a> TokenProducer<'a> {
                 TokenContent::IntLiteral(heap.alloc_string(format!("-{s}"))),
               ));
               return None;
+            } else if i64 >= maxi32_plus1 {
+              error_set.report_invalid_syntax_error(loc, "Not a 32-bit integer.".to_string());
             }
           }
         };
         Some(Token(loc, TokenContent::IntLiteral(p_str)))
       }
+      Token(loc, TokenContent::FloatLiteral(p_str)) => {
+        if !p_str.as_str(heap).parse::<f64>().is_ok_and(f64::is_finite) {
+          error_set.report_invalid_syntax_error(loc, "Not a 64-bit float.".to_string());
+        }
+        Some(Token(loc, TokenContent::FloatLiteral(p_str)))
+      }
       t => Some(t),
     }
   }
@@ -789,7 +1032,7 @@ mod tests {
   use samlang_errors::ErrorSet;
   use samlang_heap::{Heap, ModuleReference};
 
-  static KEYWORDS: [Keyword; 35] = [
+  static KEYWORDS: [Keyword; 41] = [
     Keyword::Import,
     Keyword::From,
     Keyword::Class,
@@ -798,6 +1041,7 @@ mod tests {
     Keyword::Function,
     Keyword::Method,
     Keyword::As,
+    Keyword::Derives,
     Keyword::Private,
     Keyword::Protected,
     Keyword::Internal,
@@ -807,7 +1051,12 @@ mod tests {
     Keyword::Else,
     Keyword::Match,
     Keyword::Return,
+    Keyword::While,
+    Keyword::For,
+    Keyword::In,
+    Keyword::Break,
     Keyword::Int,
+    Keyword::Float,
     Keyword::String,
     Keyword::Bool,
     Keyword::Unit,
@@ -827,7 +1076,7 @@ mod tests {
     Keyword::Assert,
   ];
 
-  static TOKEN_OPS: [TokenOp; 31] = [
+  static TOKEN_OPS: [TokenOp; 39] = [
     TokenOp::Underscore,
     TokenOp::LeftParenthesis,
     TokenOp::RightParenthesis,
@@ -858,6 +1107,14 @@ mod tests {
     TokenOp::NotEqual,
     TokenOp::And,
     TokenOp::Or,
+    TokenOp::Pipe,
+    TokenOp::BitAnd,
+    TokenOp::BitXor,
+    TokenOp::BitNot,
+    TokenOp::ShiftLeft,
+    TokenOp::ShiftRight,
+    TokenOp::UnsignedShiftRight,
+    TokenOp::DotDot,
     TokenOp::DotDotDot,
   ];
 
@@ -1504,6 +1761,27 @@ class Main {
     assert_eq!(expected, lex(r#" /* comm */"#));
   }
 
+  #[test]
+  fn bitwise_operators_test() {
+    let expected = vec![
+      ".sam:1:1-1:2: ~",
+      ".sam:1:2-1:3: a",
+      ".sam:1:4-1:5: &",
+      ".sam:1:6-1:7: b",
+      ".sam:1:8-1:9: |",
+      ".sam:1:10-1:11: c",
+      ".sam:1:12-1:13: ^",
+      ".sam:1:14-1:15: d",
+      ".sam:1:16-1:18: <<",
+      ".sam:1:19-1:20: 1",
+      ".sam:1:21-1:23: >>",
+      ".sam:1:24-1:25: 2",
+      ".sam:1:26-1:29: >>>",
+      ".sam:1:30-1:31: 3",
+    ];
+    assert_eq!(expected, lex("~a & b | c ^ d << 1 >> 2 >>> 3"));
+  }
+
   #[test]
   fn line_comment_edge_case() {
     assert_eq!(vec![".sam:1:1-1:6: ss"], lex("// ss"));
@@ -1586,6 +1864,83 @@ class Main {
     assert_eq!(vec![".sam:1:1-1:5: \"\\n\""], lex(r#""\n""#));
   }
 
+  #[test]
+  fn unicode_escape_tests() {
+    assert!(super::string_has_valid_escape(r#""\u{1F600}""#));
+    assert!(super::string_has_valid_escape(r#""a\u{e9}b\u{10FFFF}""#));
+    assert!(!super::string_has_valid_escape(r#""\u1F600""#));
+    assert!(!super::string_has_valid_escape(r#""\u{}""#));
+    assert!(!super::string_has_valid_escape(r#""\u{+41}""#));
+    assert!(!super::string_has_valid_escape(r#""\u{1234567}""#));
+    assert!(!super::string_has_valid_escape(r#""\u{D800}""#));
+    assert!(!super::string_has_valid_escape(r#""\u{110000}""#));
+    assert!(!super::string_has_valid_escape(r#""\u{41""#));
+  }
+
+  #[test]
+  fn raw_string_tests() {
+    assert_eq!(vec![r#".sam:1:1-1:11: r"C:\a\b\""#], lex(r#"r"C:\a\b\""#));
+    assert_eq!(vec![r#".sam:1:1-1:8: r"${a}""#, ".sam:1:9-1:10: r"], lex(r#"r"${a}" r"#));
+    assert_eq!(vec![".sam:1:1-1:2: r", ".sam:1:2-1:6: ERROR: \"abc"], lex("r\"abc\n"));
+  }
+
+  #[test]
+  fn multiline_string_tests() {
+    assert_eq!(
+      vec![".sam:1:1-3:6: \"\"\"\n  a \"b\" ${c}\n  \"\"\"", ".sam:3:7-3:8: ;"],
+      lex("\"\"\"\n  a \"b\" ${c}\n  \"\"\" ;")
+    );
+    assert_eq!(vec![r##".sam:1:1-1:10: """a\"""""##], lex(r##""""a\"""""##));
+    assert_eq!(vec![".sam:1:1-1:7: \"\"\"\"\"\""], lex("\"\"\"\"\"\""));
+
+    let mut heap = Heap::new();
+    let mut error_set = ErrorSet::new();
+    lex_source_program("\"\"\"\n\\q\n\"\"\"", ModuleReference::ROOT, &mut heap, &mut error_set);
+    assert_eq!(
+      r#"
+Error ----------------------------------------- .sam:1:1-3:4
+
+Invalid escape in string.
+
+
+Found 1 error.
+"#
+      .trim(),
+      error_set.pretty_print_error_messages_no_frame_for_test(&heap).trim()
+    );
+  }
+
+  #[test]
+  fn template_string_tests() {
+    assert_eq!(
+      vec![
+        ".sam:1:1-1:6: \"a=${",
+        ".sam:1:6-1:7: a",
+        ".sam:1:7-1:14: }, b=${",
+        ".sam:1:14-1:15: {",
+        ".sam:1:16-1:17: b",
+        ".sam:1:18-1:19: }",
+        ".sam:1:19-1:21: }\"",
+      ],
+      lex(r#""a=${a}, b=${{ b }}""#)
+    );
+    assert_eq!(
+      vec![
+        ".sam:1:1-1:4: \"${",
+        ".sam:1:4-1:7: \"${",
+        ".sam:1:7-1:8: a",
+        ".sam:1:8-1:10: }\"",
+        ".sam:1:10-1:14: }\\\"\"",
+      ],
+      lex(r#""${"${a}"}\"""#)
+    );
+    assert_eq!(vec![".sam:1:1-1:9: \"$a {b}\""], lex(r#""$a {b}""#));
+    assert_eq!(vec![".sam:1:1-1:6: \"\\${\"", ".sam:1:6-1:7: a"], lex(r#""\${"a"#));
+    assert_eq!(vec![".sam:1:1-1:8: \"\\${a}\""], lex(r#""\${a}""#));
+    assert!(super::string_has_valid_escape(r#""\${a} costs \$3""#));
+    assert_eq!(vec![".sam:1:1-1:4: \"${", ".sam:1:4-1:6: ERROR: }a"], lex("\"${}a\n"));
+  }
+
   #[test]
   fn bad_code_multiple_string() {
     assert_eq!(
@@ -1604,6 +1959,10 @@ class Main {
     lex("else");
     lex("match");
     lex("return");
+    lex("while");
+    lex("for");
+    lex("in");
+    lex("break");
     lex("int");
     lex("string");
     lex("self");
@@ -1615,6 +1974,7 @@ class Main {
     lex("destructor");
     lex("extends");
     lex("implements");
+    lex("derives");
     lex("exports");
     lex("assert");
     lex("asserts");
@@ -1627,6 +1987,8 @@ class Main {
     lex("!");
     lex("..");
     lex("...");
+    assert_eq!(vec![".sam:1:1-1:2: a", ".sam:1:3-1:5: |>", ".sam:1:6-1:7: f"], lex("a |> f"));
+    assert_eq!(vec![".sam:1:1-1:3: ||", ".sam:1:3-1:4: >"], lex("||>"));
   }
 
   #[test]
@@ -1638,6 +2000,71 @@ class Main {
     assert_eq!(vec![".sam:1:1-1:11: 2147483648", ".sam:1:12-1:13: 3"], lex("2147483648 3"));
     assert_eq!(vec![".sam:1:1-1:2: +", ".sam:1:3-1:13: 2147483648"], lex("+ 2147483648"));
     assert_eq!(vec![".sam:1:1-1:12: -2147483648"], lex("-2147483648"));
+    assert_eq!(vec![".sam:1:1-1:8: 0xFF_ff"], lex("0xFF_ff"));
+    assert_eq!(vec![".sam:1:1-1:12: 0b1010_0101", ".sam:1:12-1:13: 2"], lex("0b1010_01012"));
+    assert_eq!(vec![".sam:1:1-1:10: 1_000_000"], lex("1_000_000"));
+    assert_eq!(vec![".sam:1:1-1:3: 0x", ".sam:1:3-1:4: y"], lex("0xy"));
+  }
+
+  #[test]
+  fn int_literal_value_tests() {
+    assert_eq!(Ok(255), super::int_literal_value("0xFF"));
+    assert_eq!(Ok(65535), super::int_literal_value("0XFF_FF"));
+    assert_eq!(Ok(10), super::int_literal_value("0b1010"));
+    assert_eq!(Ok(-1), super::int_literal_value("0xFFFF_FFFF"));
+    assert_eq!(
+      Ok(i32::MIN as i64),
+      super::int_literal_value("0B1000_0000_0000_0000_0000_0000_0000_0000")
+    );
+    assert_eq!(Ok(1_000_000), super::int_literal_value("1_000_000"));
+    assert_eq!(Ok(2147483648), super::int_literal_value("2147483648"));
+    assert_eq!(Err("Not a 32-bit integer."), super::int_literal_value("2147483649"));
+    assert_eq!(Err("Not a 32-bit integer."), super::int_literal_value("0x1_0000_0000"));
+    assert_eq!(Err("Missing digits after the radix prefix."), super::int_literal_value("0x"));
+    let bad_separator = Err("Digit separators must be placed between digits.");
+    assert_eq!(bad_separator, super::int_literal_value("1_"));
+    assert_eq!(bad_separator, super::int_literal_value("1__0"));
+    assert_eq!(bad_separator, super::int_literal_value("0x_1"));
+
+    let mut heap = Heap::new();
+    let mut error_set = ErrorSet::new();
+    lex_source_program("1_ 0b 0x1_0000_0000", ModuleReference::ROOT, &mut heap, &mut error_set);
+    assert_eq!(
+      r#"
+Error ----------------------------------------- .sam:1:1-1:3
+
+Digit separators must be placed between digits.
+
+
+Error ----------------------------------------- .sam:1:4-1:6
+
+Missing digits after the radix prefix.
+
+
+Error ---------------------------------------- .sam:1:7-1:20
+
+Not a 32-bit integer.
+
+
+Found 3 errors.
+"#
+      .trim(),
+      error_set.pretty_print_error_messages_no_frame_for_test(&heap).trim()
+    );
+  }
+
+  #[test]
+  fn float_tests() {
+    assert_eq!(vec![".sam:1:1-1:4: 0.5"], lex("0.5"));
+    assert_eq!(vec![".sam:1:1-1:9: 12.25e-3"], lex("12.25e-3"));
+    assert_eq!(vec![".sam:1:1-1:2: -", ".sam:1:2-1:7: 1.0E5"], lex("-1.0E5"));
+    assert_eq!(vec![".sam:1:1-1:2: 0", ".sam:1:2-1:4: ..", ".sam:1:4-1:5: n"], lex("0..n"));
+    assert_eq!(vec![".sam:1:1-1:2: 1", ".sam:1:2-1:3: .", ".sam:1:3-1:6: foo"], lex("1.foo"));
+
+    let mut heap = Heap::new();
+    let mut error_set = ErrorSet::new();
+    lex_source_program("1.0e400", ModuleReference::ROOT, &mut heap, &mut error_set);
+    assert!(error_set.has_errors());
   }
 
   #[test]
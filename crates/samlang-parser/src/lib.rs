@@ -7,7 +7,7 @@ mod lexer;
 mod source_parser;
 
 fn builtin_classes() -> HashSet<PStr> {
-  HashSet::from([PStr::PROCESS_TYPE, PStr::STR_TYPE, PStr::VEC_TYPE])
+  HashSet::from([PStr::PROCESS_TYPE, PStr::STR_TYPE, PStr::VEC_TYPE, PStr::FLOAT_TYPE])
 }
 
 pub fn parse_source_module_from_text(
@@ -103,7 +103,11 @@ mod tests {
     expect_good_expr("false");
     expect_good_expr("42");
     expect_good_expr("-2147483648");
+    expect_good_expr("4.25");
+    expect_good_expr("1.5e-10");
     expect_good_expr("2147483647");
+    expect_good_expr("0x7FFF_FFFF");
+    expect_good_expr("0b1010 + 1_000");
     expect_good_expr("\"Hello World!\"");
     expect_good_expr("this");
     expect_good_expr("abc");
@@ -138,6 +142,15 @@ mod tests {
     expect_good_expr("true && false");
     expect_good_expr("false || true");
     expect_good_expr("\"hello\"::\"world\"");
+    expect_good_expr("~42");
+    expect_good_expr("3 & 4");
+    expect_good_expr("3 | 4");
+    expect_good_expr("3 ^ 4");
+    expect_good_expr("3 << 4");
+    expect_good_expr("3 >> 4");
+    expect_good_expr("3 >>> 4");
+    expect_good_expr("1 | 2 ^ 3 & 4 << 5 + 6 == 7");
+    expect_good_expr("foo.bar<A<B<int>>>(3 >> 1)");
     expect_good_expr("if (true) /* a */ {3} /* a */ else /* a */ {bar}");
     expect_good_expr(
       "if let {foo as {bar /* a */ as (Fizz(baz,), Buzz, (_,)), boo},} = true {3} else {bar}",
@@ -146,6 +159,9 @@ mod tests {
     expect_good_expr("match (this) { None(_) -> match this { None(_) -> 1 }, Some(d) -> d }");
     expect_good_expr("match (this) { None(_) -> {}, Some(d) -> d }");
     expect_good_expr("match (this) { None(_) -> 0, Some(d) -> d, }");
+    expect_good_expr(r#"match (this) { 0 -> 1, -1 -> 2, "a" -> 3, true -> 4, false | _ -> 5 }"#);
+    expect_good_expr("match (this) { Some(-2147483648) -> 1, (/* a */ 1, {a as 2}) -> 2 }");
+    expect_good_expr("match (this) { Some(a) if a > 0 -> a, Some(_) | None if /* a */ b -> 0 }");
     expect_good_expr("(a, b: int, c: Type) -> 3");
     expect_good_expr("(a, b: () -> int, c: Type) -> 3");
     expect_good_expr("(a, b, c: () -> int, d: Type) -> 3");
@@ -168,6 +184,7 @@ mod tests {
     expect_good_expr("{ let a = 3; let b = 3; }");
     expect_good_expr("{ let a = 3; a }");
     expect_good_expr("{ let a: int = 3; }");
+    expect_good_expr("{ let a: float = 3.0 * -1.5; }");
     expect_good_expr("{ let a: unit = {}; }");
     expect_good_expr("{ let (foo, _): Type = (1, 2); }");
     expect_good_expr("{ let {foo, bar as baz}: Type = 3; }");
@@ -179,6 +196,45 @@ mod tests {
     expect_good_expr("{ 42; }");
     expect_good_expr("{ 42; 43; }");
     expect_good_expr("{ 42; 43 }");
+    expect_good_expr("return 1");
+    expect_good_expr("return a + b");
+    expect_good_expr("{ if (a) { return 1 } else {}; 2 }");
+    expect_good_expr("{ return /* early */ foo(); }");
+    expect_good_expr("{ var a = 1; a = a + 1; a }");
+    expect_good_expr("{ var a: int = 1; /* c */ a = 2; }");
+    expect_good_expr("while (a < 10) { a = a + 1; }");
+    expect_good_expr("while true { if (a) { break } else {} }");
+    expect_good_expr("for (i in 0..10) { foo(i); }");
+    expect_good_expr("for /* a */ (i in a+1..b) { break; }");
+    expect_good_expr("{ while a {} for (i in 0..1) {}; 1 }");
+    expect_good_expr("assert(a)");
+    expect_good_expr("assert(a > 1, \"a is too small\")");
+    expect_good_expr("{ assert /* a */ (/* b */ true); }");
+    expect_good_expr("foo()?");
+    expect_good_expr("a.b?.c(d)? + (-e)?");
+    expect_good_expr("{ let a = foo() /* early */ ?; a }");
+    expect_good_expr("\"x=${x}, name=${name}\"");
+    expect_good_expr("\"${a + b}${\"nested ${c}\"}${ { d } }\"");
+    expect_good_expr("\"costs $5, not ${ foo() /* comment */ }\"");
+    expect_good_expr("a |> f");
+    expect_good_expr("a || b |> Foo.bar(1) /* c */ |> (x) -> x |> g");
+    expect_good_expr("if a { b } else { c } |> f");
+    expect_good_expr("{ let Some(a) = b else { return 1 }; a }");
+    expect_good_expr("{ let (Foo(a), _): T = b /* c */ else /* d */ { Process.panic(\"\") }; }");
+    expect_good_expr("{ ...p }");
+    expect_good_expr("{ ...p, x: 1, }");
+    expect_good_expr("{ ...Foo.init(1, 2), x: { a }, /* c */ y /* d */: b + 1 /* e */ }");
+    expect_good_expr("(1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1)");
+    expect_good_expr("(a,b,c,d,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1)");
+    expect_good_expr("{ let _: (int, Str) = (1, \"\"); }");
+    expect_good_expr("{ let _: (/* a */ int, (bool, Foo<int>), () -> int /* b */) = 3; }");
+    expect_good_expr("{ let _: ((int, bool)) -> (int, bool) = 3; }");
+    expect_good_expr("Foo.bar(a = 1)");
+    expect_good_expr("Foo.bar(1, /* c */ b = 2, c = (x) -> x,)");
+    expect_good_expr("{ function f(x: int): int = f(x - 1); f(1) }");
+    expect_good_expr(
+      "{ /* c */ function g(): unit = {}; function h(a: int, b: Str,): () -> int = () -> a; }",
+    );
   }
 
   fn expect_bad_expr(text: &str) {
@@ -196,18 +252,29 @@ mod tests {
     expect_bad_expr("_sdfsdfdsf");
     expect_bad_expr("9223372036854775808");
     expect_bad_expr("-9223372036854775889223372036854775808");
+    expect_bad_expr("2147483648");
+    expect_bad_expr("Str.fromInt(2147483648)");
+    expect_bad_expr("1 + 2147483648");
     expect_bad_expr("SomeClass.true");
     expect_bad_expr("SomeClass.<>foo");
     expect_bad_expr("SomeClass.<foo");
     expect_bad_expr("SomeClass.");
+    expect_bad_expr("a |> ");
+    expect_bad_expr("{ var a = b else { return 1 }; }");
+    expect_bad_expr("{ let Some(a) = b else return 1; }");
+    expect_bad_expr("{ ...p, x }");
+    expect_bad_expr("{ ...p x: 1 }");
+    expect_bad_expr("Foo.bar(a = )");
+    expect_bad_expr("1__0");
+    expect_bad_expr("0x1_0000_0000");
+    expect_bad_expr("{ function f(x): int = x; }");
+    expect_bad_expr("{ function f(x: int) = x; }");
     expect_bad_expr("SomeClass.3");
     expect_bad_expr("ForTests.assertIntEquals(2444a, 1)");
     expect_bad_expr(".");
     expect_bad_expr(",");
     expect_bad_expr("()");
     expect_bad_expr("[1]");
-    expect_bad_expr("(1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1)");
-    expect_bad_expr("(a,b,c,d,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1)");
     expect_bad_expr("{: }");
     expect_bad_expr("{ hello / }");
     expect_bad_expr("{: bar}");
@@ -217,9 +284,25 @@ mod tests {
     expect_bad_expr("if (true) {3} else");
     expect_bad_expr("if (true) else 4");
     expect_bad_expr("if (true)");
+    expect_bad_expr("return");
+    expect_bad_expr("{ return; }");
+    expect_bad_expr("{ var (a, b) = (1, 2); }");
+    expect_bad_expr("while (a) 1");
+    expect_bad_expr("for i in 0..10 {}");
+    expect_bad_expr("for (i in 0...10) {}");
+    expect_bad_expr("assert");
+    expect_bad_expr("assert()");
+    expect_bad_expr("assert(a, b, c)");
+    expect_bad_expr("\"${}\"");
+    expect_bad_expr("\"${a b}\"");
+    expect_bad_expr("\"${a}\n\"");
+    expect_bad_expr("{ a = ; }");
+    expect_bad_expr("{ a = 1 }");
     expect_bad_expr("match (this) { | None _  }");
     expect_bad_expr("match (this) { |  _ -> }");
     expect_bad_expr("match (this) { |  -> }");
+    expect_bad_expr("match (this) { - -> 1 }");
+    expect_bad_expr("match (this) { Some(a) if -> a }");
     expect_bad_expr("");
     expect_bad_expr("(: int) -> 3");
     expect_bad_expr("(:) -> 3");
@@ -232,6 +315,8 @@ mod tests {
     expect_bad_expr("{ let a = int }");
     expect_bad_expr("{ let a:  = 3; a }");
     expect_bad_expr("{ let a: <int> = 3; a }");
+    expect_bad_expr("{ let a: (int) = 3; a }");
+    expect_bad_expr("{ let a: (int, ) -> = 3; a }");
     expect_bad_expr("{ let {foo, as baz}: Type = 3; }");
     expect_bad_expr("{ let {foo, bar as }: Type = 3; }");
     expect_bad_expr("{ let a: () ->  = 3; a }");
@@ -255,6 +340,14 @@ mod tests {
 
     interface Bar<T> {}
 
+    type Callback<T> = (T) -> unit;
+
+    /** docs */
+    private type Names = List<Str>;
+
+    const Max: int = 1 + 2 * 3;
+    private const Greeting: Str = "Hello " :: "World";
+
     interface Baz : Bar<int, A<(A) -> int>> {
       function foo(): () -> Str
       method bar(baz: bool): int
@@ -345,11 +438,307 @@ mod tests {
         .filter_map(|it| {
           match it {
             source::Toplevel::Class(c) => Some(c.name.name.as_str(&heap).to_string()),
-            source::Toplevel::Interface(_) => None,
+            source::Toplevel::Interface(_)
+            | source::Toplevel::TypeAlias(_)
+            | source::Toplevel::Const(_) => None,
           }
         })
         .collect_vec()
     );
+    assert_eq!(
+      vec!["Callback", "Names"],
+      parsed
+        .toplevels
+        .iter()
+        .filter_map(|it| match it {
+          source::Toplevel::TypeAlias(a) => Some(a.name.name.as_str(&heap).to_string()),
+          _ => None,
+        })
+        .collect_vec()
+    );
+    assert_eq!(
+      vec!["Max", "Greeting"],
+      parsed
+        .toplevels
+        .iter()
+        .filter_map(|it| match it {
+          source::Toplevel::Const(c) => Some(c.name.name.as_str(&heap).to_string()),
+          _ => None,
+        })
+        .collect_vec()
+    );
+  }
+
+  #[test]
+  fn test_visibility_modifiers() {
+    let mut heap = Heap::new();
+    let mut error_set = ErrorSet::new();
+    let text = r#"
+    internal interface Shape {
+      protected method area(): int
+      internal function create(): Shape
+      public method name(): Str
+    }
+    internal type Names = List<Str>;
+    internal const Limit: int = 3;
+    public class Square(val size: int) : Shape {
+      private function helper(): int = 1
+      protected method area(): int = 1
+      internal method name(): Str = ""
+    }
+"#;
+    let parsed =
+      &parse_source_module_from_text(text, ModuleReference::DUMMY, &mut heap, &mut error_set);
+    assert_eq!("", error_set.pretty_print_error_messages_no_frame_for_test(&heap));
+    assert_eq!(
+      vec!["internal", "internal", "internal", "public"],
+      parsed.toplevels.iter().map(|it| it.visibility().as_str()).collect_vec()
+    );
+    assert_eq!(
+      vec!["protected", "internal", "public", "private", "protected", "internal"],
+      parsed
+        .toplevels
+        .iter()
+        .flat_map(|it| it.members_iter())
+        .map(|it| it.visibility.as_str())
+        .collect_vec()
+    );
+
+    let text = r#"
+    protected class A {}
+    interface B { private method b(): int }
+"#;
+    parse_source_module_from_text(text, ModuleReference::DUMMY, &mut heap, &mut error_set);
+    assert_eq!(
+      r#"
+Error ----------------------------------- DUMMY.sam:2:5-2:14
+
+Unexpected `protected`
+
+
+Error ---------------------------------- DUMMY.sam:3:19-3:26
+
+Unexpected `private`
+
+
+Found 2 errors.
+"#
+      .trim(),
+      error_set.pretty_print_error_messages_no_frame_for_test(&heap).trim()
+    );
+  }
+
+  #[test]
+  fn test_interface_default_methods() {
+    let mut heap = Heap::new();
+    let mut error_set = ErrorSet::new();
+    let text = r#"
+    interface Shape {
+      method area(): int
+      method describe(): Str = Str.fromInt(this.area())
+      function create(): Shape = 1
+    }
+"#;
+    let parsed =
+      &parse_source_module_from_text(text, ModuleReference::DUMMY, &mut heap, &mut error_set);
+    let source::Toplevel::Interface(interface) = &parsed.toplevels[0] else {
+      panic!("Expected interface")
+    };
+    assert_eq!(
+      vec![false, true, true],
+      interface.members.members.iter().map(|it| it.default_body.is_some()).collect_vec()
+    );
+    assert_eq!(
+      r#"
+Error ---------------------------------- DUMMY.sam:5:34-5:35
+
+Only methods can have default implementations.
+
+
+Found 1 error.
+"#
+      .trim(),
+      error_set.pretty_print_error_messages_no_frame_for_test(&heap).trim()
+    );
+  }
+
+  #[test]
+  fn test_derives_clause() {
+    let mut heap = Heap::new();
+    let mut error_set = ErrorSet::new();
+    let text = r#"
+    class Point(val x: int, val y: int) : Foo derives equals, compare, toString {
+      method norm(): int = 1
+    }
+    class Shape(Dot, Circle(Point, int)) derives compare {}
+    class Utils derives equals {}
+    class Bad(val x: int) derives hash, equals, equals {}
+"#;
+    let parsed =
+      &parse_source_module_from_text(text, ModuleReference::DUMMY, &mut heap, &mut error_set);
+    let classes = parsed
+      .toplevels
+      .iter()
+      .map(|toplevel| match toplevel {
+        source::Toplevel::Class(c) => c,
+        _ => panic!("Expected class"),
+      })
+      .collect_vec();
+    assert_eq!(
+      vec!["norm", "equals", "compare", "toString"],
+      classes[0].members.members.iter().map(|it| it.decl.name.name.as_str(&heap)).collect_vec()
+    );
+    assert_eq!(
+      classes[0].derives.as_ref().unwrap().names[1].loc,
+      classes[0].members.members[2].decl.name.loc
+    );
+    assert_eq!(
+      vec!["Foo", "Comparable"],
+      classes[0]
+        .extends_or_implements_nodes
+        .iter()
+        .flat_map(|it| &it.nodes)
+        .map(|it| it.id.name.as_str(&heap))
+        .collect_vec()
+    );
+    assert_eq!(
+      "std.interfaces",
+      classes[1].extends_or_implements_nodes.as_ref().unwrap().nodes[0]
+        .module_reference
+        .pretty_print(&heap)
+    );
+    assert_eq!(1, classes[3].members.members.len());
+    assert_eq!(
+      r#"
+Error ---------------------------------- DUMMY.sam:6:17-6:31
+
+Only classes with a struct or enum type definition can derive members.
+
+
+Error ---------------------------------- DUMMY.sam:7:35-7:39
+
+Cannot derive `hash`. Only equals, compare and toString can be derived.
+
+
+Error ---------------------------------- DUMMY.sam:7:49-7:55
+
+`equals` is already derived.
+
+
+Found 3 errors.
+"#
+      .trim(),
+      error_set.pretty_print_error_messages_no_frame_for_test(&heap).trim()
+    );
+  }
+
+  #[test]
+  fn test_int_literal_spellings() {
+    let mut heap = Heap::new();
+    let mut error_set = ErrorSet::new();
+    let mut parse_literal = |text: &str| {
+      let (_, e) =
+        parse_source_expression_from_text(text, ModuleReference::DUMMY, &mut heap, &mut error_set);
+      let source::expr::E::Literal(_, source::Literal::Int(value, spelling)) = e else {
+        panic!("Expected int literal")
+      };
+      (value, spelling)
+    };
+    assert_eq!((42, None), parse_literal("42"));
+    assert_eq!((i32::MIN, None), parse_literal("-2147483648"));
+    let (value, spelling) = parse_literal("0xFF_FF");
+    assert_eq!(65535, value);
+    assert!(spelling.is_some());
+    assert_eq!(-1, parse_literal("0xFFFFFFFF").0);
+    assert_eq!(5, parse_literal("0b101").0);
+    assert_eq!(1000000, parse_literal("1_000_000").0);
+    assert!(!error_set.has_errors());
+
+    let (_, e) = parse_source_expression_from_text(
+      "match a { 0x10 -> 1, -0b11 -> 2, _ -> 3 }",
+      ModuleReference::DUMMY,
+      &mut heap,
+      &mut error_set,
+    );
+    let source::expr::E::Match(e) = e else { panic!("Expected match") };
+    assert_eq!(
+      vec!["0x10", "-0b11", "_"],
+      e.cases
+        .iter()
+        .map(|case| match &case.pattern {
+          source::pattern::MatchingPattern::Literal { literal, .. } => literal.pretty_print(&heap),
+          _ => "_".to_string(),
+        })
+        .collect_vec()
+    );
+    assert!(!error_set.has_errors());
+  }
+
+  #[test]
+  fn test_string_literal_values() {
+    let mut heap = Heap::new();
+    let mut error_set = ErrorSet::new();
+    let mut parse_literal = |text: &str| {
+      let (_, e) =
+        parse_source_expression_from_text(text, ModuleReference::DUMMY, &mut heap, &mut error_set);
+      let source::expr::E::Literal(_, source::Literal::String(value, spelling)) = e else {
+        panic!("Expected string literal")
+      };
+      (value.as_str(&heap).to_string(), spelling.map(|s| s.as_str(&heap).to_string()))
+    };
+    assert_eq!(("a\\\\\"b\\u{1F600}".to_string(), None), parse_literal(r#""a\\\"b\u{1F600}""#));
+    assert_eq!(
+      ("C:\\\\a\\\\${b}".to_string(), Some(r#"r"C:\a\${b}""#.to_string())),
+      parse_literal(r#"r"C:\a\${b}""#)
+    );
+    let (value, spelling) = parse_literal("\"\"\"\n    a \"b\"  \n      c\\t\n\n  \"\"\"");
+    assert_eq!("  a \"b\"\\n    c\\t\\n", value);
+    assert!(spelling.is_some());
+    assert_eq!("a\\n\\\\\"b", parse_literal("\"\"\"a\n  \\\\\"b\"\"\"").0);
+    assert_eq!("a\\nb", parse_literal("\"\"\"\n    a\n    b\n    \"\"\"").0);
+    assert_eq!("", parse_literal("\"\"\"\"\"\"").0);
+    assert!(!error_set.has_errors());
+  }
+
+  #[test]
+  fn test_default_values_and_named_arguments() {
+    let mut heap = Heap::new();
+    let mut error_set = ErrorSet::new();
+    let text = r#"
+    class Main {
+      function f(a: int, b: int = 1 + 2, c: Str = "c"): int = Main.f(c = "", a = 1)
+      function g(): int = Main.f(a = 1, 2)
+    }
+"#;
+    let parsed =
+      &parse_source_module_from_text(text, ModuleReference::DUMMY, &mut heap, &mut error_set);
+    let source::Toplevel::Class(class) = &parsed.toplevels[0] else { panic!("Expected class") };
+    let f = &class.members.members[0];
+    assert_eq!(
+      vec![false, true, true],
+      f.decl.parameters.parameters.iter().map(|it| it.default_value.is_some()).collect_vec()
+    );
+    let source::expr::E::Call(call) = &f.body else { panic!("Expected call") };
+    assert_eq!(
+      vec!["c", "a"],
+      call.binding.names.iter().map(|it| it.name.as_str(&heap)).collect_vec()
+    );
+    let source::expr::E::Call(call) = &class.members.members[1].body else {
+      panic!("Expected call")
+    };
+    assert_eq!(1, call.arguments.expressions.len());
+    assert_eq!(
+      r#"
+Error ---------------------------------- DUMMY.sam:4:41-4:42
+
+Positional arguments must come before named arguments.
+
+
+Found 1 error.
+"#
+      .trim(),
+      error_set.pretty_print_error_messages_no_frame_for_test(&heap).trim()
+    );
   }
 
   #[test]
@@ -378,6 +767,14 @@ mod tests {
 
     class DD(private d: Str) {}
 
+    type = int;
+    type Foo<T> = ;
+    type Bar = Foo
+
+    const = 1;
+    const A: = ;
+    const B: int = 1
+
     class TypeInference(val : Str, val foo: ) {
       function notAnnotated(bad: ):  = {
         let _ = (a, b, c) -> if a(b + 1) {b} else {c};
@@ -1,5 +1,5 @@
 use super::lexer::{Keyword, Token, TokenContent, TokenOp, TokenProducer};
-use samlang_ast::{Location, source::*};
+use samlang_ast::{Location, Position, source::*};
 use samlang_errors::ErrorSet;
 use samlang_heap::{Heap, ModuleReference, PStr};
 use std::collections::{HashMap, HashSet};
@@ -8,6 +8,8 @@ const MAX_STRUCT_SIZE: usize = 16;
 
 pub(super) struct SourceParser<'a> {
   token_producer: TokenProducer<'a>,
+  /// Tokens of synthesized source code (e.g. derived members), in reverse order.
+  synthesized_tokens: Vec<Token>,
   peeked: Option<Token>,
   pending_comments: Vec<Comment>,
   last_location: Location,
@@ -30,6 +32,7 @@ impl<'a> SourceParser<'a> {
   ) -> SourceParser<'a> {
     SourceParser {
       token_producer,
+      synthesized_tokens: Vec::new(),
       peeked: None,
       pending_comments: Vec::new(),
       last_location: Location::dummy(),
@@ -47,6 +50,10 @@ impl<'a> SourceParser<'a> {
     if let Some(token) = self.peeked {
       return token;
     }
+    if let Some(token) = self.synthesized_tokens.pop() {
+      self.peeked = Some(token);
+      return token;
+    }
     loop {
       match self.token_producer.next_token(self.heap, self.error_set) {
         Some(Token(loc, TokenContent::LineComment(text))) => {
@@ -104,6 +111,20 @@ impl<'a> SourceParser<'a> {
       if op == expected_kind {
         return (location, self.consume());
       }
+      if expected_kind == TokenOp::GreaterThan
+        && matches!(op, TokenOp::ShiftRight | TokenOp::UnsignedShiftRight)
+      {
+        // `>>` and `>>>` can close nested type arguments, so we only take the first `>`.
+        let split = Position(location.start.0, location.start.1 + 1);
+        let rest =
+          if op == TokenOp::ShiftRight { TokenOp::GreaterThan } else { TokenOp::ShiftRight };
+        let comments = std::mem::take(&mut self.pending_comments);
+        let consumed_location = Location { end: split, ..location };
+        self.peeked =
+          Some(Token(Location { start: split, ..location }, TokenContent::Operator(rest)));
+        self.last_location = consumed_location;
+        return (consumed_location, comments);
+      }
       self.consume()
     } else {
       Vec::new()
@@ -165,6 +186,32 @@ impl<'a> SourceParser<'a> {
     }
   }
 
+  /// Parses source code generated by the parser itself. Every token gets a distinct location that
+  /// maps back to `loc`, so that errors in the synthesized nodes point to where they are requested.
+  fn parse_synthesized<T>(
+    &mut self,
+    source: &str,
+    loc: Location,
+    parse: impl FnOnce(&mut Self) -> T,
+  ) -> T {
+    let mut token_producer = TokenProducer::new(source, self.module_reference);
+    let mut tokens = Vec::new();
+    while let Some(Token(_, content)) = token_producer.next_token(self.heap, self.error_set) {
+      tokens.push(Token(Location::synthesized(loc, tokens.len() as u32), content));
+    }
+    // The trailing end of file keeps lookaheads from reaching into the real token stream.
+    tokens.push(Token(loc, TokenContent::EndOfFile));
+    tokens.reverse();
+    let saved_peeked = self.peeked.take();
+    let saved_last_location = self.last_location;
+    self.synthesized_tokens = tokens;
+    let result = parse(self);
+    self.synthesized_tokens.clear();
+    self.peeked = saved_peeked;
+    self.last_location = saved_last_location;
+    result
+  }
+
   fn report(&mut self, loc: Location, reason: String) {
     self.error_set.report_invalid_syntax_error(loc, reason)
   }
@@ -279,7 +326,19 @@ pub fn parse_module(mut parser: SourceParser) -> Module<()> {
     }
     loop {
       match parser.peek() {
-        Token(_, TokenContent::Keyword(Keyword::Class | Keyword::Interface | Keyword::Private)) => {
+        Token(
+          _,
+          TokenContent::Keyword(
+            Keyword::Class
+            | Keyword::Interface
+            | Keyword::Type
+            | Keyword::Const
+            | Keyword::Private
+            | Keyword::Protected
+            | Keyword::Internal
+            | Keyword::Public,
+          ),
+        ) => {
           break;
         }
         Token(_, TokenContent::EndOfFile) => break 'outer,
@@ -323,25 +382,71 @@ mod toplevel_parser {
   use std::sync::Arc;
 
   pub(super) fn parse_toplevel(parser: &mut super::SourceParser) -> Toplevel<()> {
-    let (loc, is_private, is_interface, comments) =
-      parse_private_interface_or_class_keyword(parser);
-    if is_interface {
-      Toplevel::Interface(parse_interface(parser, (loc, is_private, comments)))
-    } else {
-      Toplevel::Class(parse_class(parser, (loc, is_private, comments)))
+    let (loc, visibility, keyword, comments) = parse_toplevel_keyword(parser);
+    match keyword {
+      Keyword::Interface => {
+        Toplevel::Interface(parse_interface(parser, (loc, visibility, comments)))
+      }
+      Keyword::Type => Toplevel::TypeAlias(parse_type_alias(parser, (loc, visibility, comments))),
+      Keyword::Const => Toplevel::Const(parse_const(parser, (loc, visibility, comments))),
+      _ => Toplevel::Class(parse_class(parser, (loc, visibility, comments))),
+    }
+  }
+
+  pub(super) fn parse_type_alias(
+    parser: &mut super::SourceParser,
+    (loc, visibility, mut associated_comments): (Location, Visibility, Vec<Comment>),
+  ) -> TypeAliasDefinition {
+    let name = parser.parse_upper_id();
+    parser.available_tparams = HashSet::new();
+    let type_parameters = super::type_parser::parse_type_parameters(parser);
+    associated_comments.append(&mut parser.assert_and_consume_operator(TokenOp::Assign).1);
+    let annotation = super::type_parser::parse_annotation(parser);
+    let (end_loc, mut comments) = parser.assert_and_consume_operator(TokenOp::Semicolon);
+    associated_comments.append(&mut comments);
+    TypeAliasDefinition {
+      loc: loc.union(&end_loc),
+      associated_comments: parser.comments_store.create_comment_reference(associated_comments),
+      visibility,
+      name,
+      type_parameters,
+      annotation,
+    }
+  }
+
+  pub(super) fn parse_const(
+    parser: &mut super::SourceParser,
+    (loc, visibility, mut associated_comments): (Location, Visibility, Vec<Comment>),
+  ) -> ConstDefinition<()> {
+    let name = parser.parse_upper_id();
+    parser.available_tparams = HashSet::new();
+    associated_comments.append(&mut parser.assert_and_consume_operator(TokenOp::Colon).1);
+    let annotation = super::type_parser::parse_annotation(parser);
+    associated_comments.append(&mut parser.assert_and_consume_operator(TokenOp::Assign).1);
+    let value = super::expression_parser::parse_expression(parser);
+    let (end_loc, mut comments) = parser.assert_and_consume_operator(TokenOp::Semicolon);
+    associated_comments.append(&mut comments);
+    ConstDefinition {
+      loc: loc.union(&end_loc),
+      associated_comments: parser.comments_store.create_comment_reference(associated_comments),
+      visibility,
+      name,
+      annotation,
+      value,
     }
   }
 
   pub(super) fn parse_class(
     parser: &mut super::SourceParser,
-    (mut loc, private, mut associated_comments): (Location, bool, Vec<Comment>),
+    (mut loc, visibility, mut associated_comments): (Location, Visibility, Vec<Comment>),
   ) -> ClassDefinition<()> {
     let name = parser.parse_upper_id();
     loc = loc.union(&name.loc);
     parser.available_tparams = HashSet::new();
     let type_parameters = super::type_parser::parse_type_parameters(parser);
-    let (type_definition, extends_or_implements_nodes) = match parser.peek().1 {
-      TokenContent::Operator(TokenOp::LeftBrace | TokenOp::Colon) => {
+    let (type_definition, mut extends_or_implements_nodes) = match parser.peek().1 {
+      TokenContent::Operator(TokenOp::LeftBrace | TokenOp::Colon)
+      | TokenContent::Keyword(Keyword::Derives) => {
         loc = if let Some(tparams_node) = &type_parameters {
           loc.union(&tparams_node.location)
         } else {
@@ -369,12 +474,22 @@ mod toplevel_parser {
         (Some(type_def), extends_or_implements_nodes)
       }
     };
+    let derives = parse_derives_clause(parser);
+    if let Some(clause) = &derives {
+      loc = loc.union(&clause.location);
+    }
     let mut members = Vec::new();
     let (members_start_loc, mut additional_associated_comments) =
       parser.assert_and_consume_operator(TokenOp::LeftBrace);
     associated_comments.append(&mut additional_associated_comments);
-    while let TokenContent::Keyword(Keyword::Function | Keyword::Method | Keyword::Private) =
-      parser.peek().1
+    while let TokenContent::Keyword(
+      Keyword::Function
+      | Keyword::Method
+      | Keyword::Private
+      | Keyword::Protected
+      | Keyword::Internal
+      | Keyword::Public,
+    ) = parser.peek().1
     {
       let saved_upper_type_parameters = parser.available_tparams.clone();
       members.push(parse_class_member_definition(parser));
@@ -385,13 +500,27 @@ mod toplevel_parser {
     let ending_associated_comments =
       parser.comments_store.create_comment_reference(ending_associated_comments);
     loc = loc.union(&end_loc);
+    if let Some(clause) = &derives {
+      super::derive_generator::synthesize_derived_members(
+        parser,
+        super::derive_generator::DerivingClass {
+          name: &name,
+          type_parameters: &type_parameters,
+          type_definition: type_definition.as_ref(),
+        },
+        clause,
+        &mut extends_or_implements_nodes,
+        &mut members,
+      );
+    }
     InterfaceDeclarationCommon {
       loc,
       associated_comments: parser.comments_store.create_comment_reference(associated_comments),
-      private,
+      visibility,
       name,
       type_parameters,
       extends_or_implements_nodes,
+      derives,
       type_definition,
       members: InterfaceMembersCommon {
         loc: members_start_loc.union(&end_loc),
@@ -403,8 +532,8 @@ mod toplevel_parser {
 
   pub(super) fn parse_interface(
     parser: &mut super::SourceParser,
-    (mut loc, private, mut associated_comments): (Location, bool, Vec<Comment>),
-  ) -> InterfaceDeclaration {
+    (mut loc, visibility, mut associated_comments): (Location, Visibility, Vec<Comment>),
+  ) -> InterfaceDeclaration<()> {
     let name = parser.parse_upper_id();
     parser.available_tparams = HashSet::new();
     let type_parameters = super::type_parser::parse_type_parameters(parser);
@@ -413,11 +542,17 @@ mod toplevel_parser {
     let (members_start_loc, mut additional_associated_comments) =
       parser.assert_and_consume_operator(TokenOp::LeftBrace);
     associated_comments.append(&mut additional_associated_comments);
-    while let TokenContent::Keyword(Keyword::Function | Keyword::Method | Keyword::Private) =
-      parser.peek().1
+    while let TokenContent::Keyword(
+      Keyword::Function
+      | Keyword::Method
+      | Keyword::Private
+      | Keyword::Protected
+      | Keyword::Internal
+      | Keyword::Public,
+    ) = parser.peek().1
     {
       let saved_upper_type_parameters = parser.available_tparams.clone();
-      members.push(parse_class_member_declaration(parser));
+      members.push(parse_interface_member_definition(parser));
       parser.available_tparams = saved_upper_type_parameters;
     }
     let (end_loc, ending_associated_comments) =
@@ -428,10 +563,11 @@ mod toplevel_parser {
     InterfaceDeclarationCommon {
       loc,
       associated_comments: parser.comments_store.create_comment_reference(associated_comments),
-      private,
+      visibility,
       name,
       type_parameters,
       extends_or_implements_nodes,
+      derives: None,
       type_definition: (),
       members: InterfaceMembersCommon {
         loc: members_start_loc.union(&end_loc),
@@ -441,33 +577,44 @@ mod toplevel_parser {
     }
   }
 
-  fn parse_private_interface_or_class_keyword(
+  /// Parses an optional visibility modifier. Members and toplevels without one are public.
+  fn parse_visibility_modifier(
+    parser: &mut super::SourceParser,
+    associated_comments: &mut Vec<Comment>,
+  ) -> Option<(Location, Visibility)> {
+    let (loc, visibility) = match parser.peek() {
+      Token(loc, TokenContent::Keyword(Keyword::Private)) => (loc, Visibility::Private),
+      Token(loc, TokenContent::Keyword(Keyword::Protected)) => (loc, Visibility::Protected),
+      Token(loc, TokenContent::Keyword(Keyword::Internal)) => (loc, Visibility::Internal),
+      Token(loc, TokenContent::Keyword(Keyword::Public)) => (loc, Visibility::Public),
+      _ => return None,
+    };
+    associated_comments.append(&mut parser.consume());
+    Some((loc, visibility))
+  }
+
+  fn parse_toplevel_keyword(
     parser: &mut super::SourceParser,
-  ) -> (Location, bool, bool, Vec<Comment>) {
+  ) -> (Location, Visibility, Keyword, Vec<Comment>) {
     let mut associated_comments = Vec::new();
-    if let Token(loc, TokenContent::Keyword(Keyword::Private)) = parser.peek() {
-      associated_comments.append(&mut parser.consume());
-      let is_interface = if matches!(parser.peek().1, TokenContent::Keyword(Keyword::Interface)) {
-        associated_comments.append(&mut parser.assert_and_consume_keyword(Keyword::Interface).1);
-        true
-      } else {
-        associated_comments.append(&mut parser.assert_and_consume_keyword(Keyword::Class).1);
-        false
+    let (visibility_loc, visibility) =
+      match parse_visibility_modifier(parser, &mut associated_comments) {
+        Some((loc, Visibility::Protected)) => {
+          parser.report(loc, "Unexpected `protected`".to_string());
+          (Some(loc), Visibility::Public)
+        }
+        Some((loc, visibility)) => (Some(loc), visibility),
+        None => (None, Visibility::Public),
       };
-      (loc, true, is_interface, associated_comments)
-    } else {
-      let (loc, is_interface) =
-        if matches!(parser.peek().1, TokenContent::Keyword(Keyword::Interface)) {
-          let (loc, mut comments) = parser.assert_and_consume_keyword(Keyword::Interface);
-          associated_comments.append(&mut comments);
-          (loc, true)
-        } else {
-          let (loc, mut comments) = parser.assert_and_consume_keyword(Keyword::Class);
-          associated_comments.append(&mut comments);
-          (loc, false)
-        };
-      (loc, false, is_interface, associated_comments)
-    }
+    let keyword = match parser.peek().1 {
+      TokenContent::Keyword(Keyword::Interface) => Keyword::Interface,
+      TokenContent::Keyword(Keyword::Type) => Keyword::Type,
+      TokenContent::Keyword(Keyword::Const) => Keyword::Const,
+      _ => Keyword::Class,
+    };
+    let (keyword_loc, mut comments) = parser.assert_and_consume_keyword(keyword);
+    associated_comments.append(&mut comments);
+    (visibility_loc.unwrap_or(keyword_loc), visibility, keyword, associated_comments)
   }
 
   fn parse_extends_or_implements_nodes(
@@ -493,6 +640,25 @@ mod toplevel_parser {
     }
   }
 
+  fn parse_derives_clause(parser: &mut super::SourceParser) -> Option<DerivesClause> {
+    if let Token(mut location, TokenContent::Keyword(Keyword::Derives)) = parser.peek() {
+      let comments = parser.consume();
+      let mut names = vec![parser.parse_lower_id()];
+      while let Token(_, TokenContent::Operator(TokenOp::Comma)) = parser.peek() {
+        let comments = parser.consume();
+        names.push(parser.parse_lower_id_with_comments(comments));
+      }
+      location = location.union(&names.last().unwrap().loc);
+      Some(DerivesClause {
+        location,
+        associated_comments: parser.comments_store.create_comment_reference(comments),
+        names,
+      })
+    } else {
+      None
+    }
+  }
+
   fn parse_type_definition_inner(parser: &mut super::SourceParser) -> TypeDefinition {
     let (loc_start, start_comments) = parser.assert_and_consume_operator(TokenOp::LeftParenthesis);
     if let Token(_, TokenContent::UpperId(_)) = parser.peek() {
@@ -584,10 +750,24 @@ mod toplevel_parser {
     ClassMemberDefinition { decl, body }
   }
 
-  pub(super) fn parse_class_member_declaration(
+  pub(super) fn parse_interface_member_definition(
     parser: &mut super::SourceParser,
-  ) -> ClassMemberDeclaration {
-    parse_class_member_declaration_common(parser, false)
+  ) -> InterfaceMemberDefinition<()> {
+    let mut decl = parse_class_member_declaration_common(parser, false);
+    if let TokenContent::Operator(TokenOp::Assign) = parser.peek().1 {
+      let additional_comments = parser.consume();
+      let body = super::expression_parser::parse_expression_with_additional_preceding_comments(
+        parser,
+        additional_comments,
+      );
+      if !decl.is_method {
+        parser.report(body.loc(), "Only methods can have default implementations.".to_string());
+      }
+      decl.loc = decl.loc.union(&body.loc());
+      InterfaceMemberDefinition { decl, default_body: Some(body) }
+    } else {
+      InterfaceMemberDefinition { decl, default_body: None }
+    }
   }
 
   fn parse_class_member_declaration_common(
@@ -595,18 +775,16 @@ mod toplevel_parser {
     allow_private: bool,
   ) -> ClassMemberDeclaration {
     let mut associated_comments = Vec::new();
-    let mut is_public = true;
     let mut is_method = true;
-    let mut peeked = parser.peek();
-    if let Token(peeked_loc, TokenContent::Keyword(Keyword::Private)) = peeked {
-      if allow_private {
-        is_public = false;
-      } else {
-        parser.report(peeked_loc, "Unexpected `private`".to_string());
+    let visibility = match parse_visibility_modifier(parser, &mut associated_comments) {
+      Some((loc, Visibility::Private)) if !allow_private => {
+        parser.report(loc, "Unexpected `private`".to_string());
+        Visibility::Public
       }
-      associated_comments.append(&mut parser.consume());
-      peeked = parser.peek();
-    }
+      Some((_, visibility)) => visibility,
+      None => Visibility::Public,
+    };
+    let peeked = parser.peek();
     let start_loc = &peeked.0;
     if let Token(_, TokenContent::Keyword(Keyword::Function)) = &peeked {
       is_method = false;
@@ -639,7 +817,7 @@ mod toplevel_parser {
     ClassMemberDeclaration {
       loc: start_loc.union(&fun_type_loc),
       associated_comments: parser.comments_store.create_comment_reference(associated_comments),
-      is_public,
+      visibility,
       is_method,
       name,
       type_parameters,
@@ -658,20 +836,347 @@ mod toplevel_parser {
   }
 }
 
+mod derive_generator {
+  use samlang_ast::source::*;
+  use samlang_heap::{Heap, ModuleReference, PStr};
+
+  pub(super) struct DerivingClass<'a> {
+    pub(super) name: &'a Id,
+    pub(super) type_parameters: &'a Option<annotation::TypeParameters>,
+    pub(super) type_definition: Option<&'a TypeDefinition>,
+  }
+
+  fn is_str_annotation(annotation: &annotation::T) -> bool {
+    matches!(
+      annotation,
+      annotation::T::Id(annotation::Id { module_reference, id, .. })
+      if *module_reference == ModuleReference::ROOT && id.name == PStr::STR_TYPE
+    )
+  }
+
+  fn equals_source(annotation: &annotation::T, a: &str, b: &str) -> String {
+    match annotation {
+      annotation::T::Id(_) | annotation::T::Generic(_, _) if !is_str_annotation(annotation) => {
+        format!("{a}.equals({b})")
+      }
+      _ => format!("{a} == {b}"),
+    }
+  }
+
+  fn compare_source(annotation: &annotation::T, a: &str, b: &str) -> String {
+    match annotation {
+      annotation::T::Primitive(_, _, annotation::PrimitiveTypeKind::Unit) => "0".to_string(),
+      annotation::T::Primitive(_, _, annotation::PrimitiveTypeKind::Bool) => {
+        format!("(if {a} {{ 1 }} else {{ 0 }}) - (if {b} {{ 1 }} else {{ 0 }})")
+      }
+      annotation::T::Primitive(_, _, _) => {
+        // `a < b` would start explicit type arguments when `a` is a field access.
+        format!("if {b} > {a} {{ -1 }} else if {a} > {b} {{ 1 }} else {{ 0 }}")
+      }
+      _ => format!("{a}.compare({b})"),
+    }
+  }
+
+  fn to_string_source(annotation: &annotation::T, a: &str) -> String {
+    match annotation {
+      annotation::T::Primitive(_, _, annotation::PrimitiveTypeKind::Unit) => "unit".to_string(),
+      annotation::T::Primitive(_, _, annotation::PrimitiveTypeKind::Bool) => {
+        format!("${{if {a} {{ \"true\" }} else {{ \"false\" }}}}")
+      }
+      annotation::T::Primitive(_, _, annotation::PrimitiveTypeKind::Float) => {
+        format!("${{Str.fromFloat({a})}}")
+      }
+      _ => format!("${{{a}}}"),
+    }
+  }
+
+  /// Combines per-field comparisons lexicographically: the first non-zero result wins.
+  fn lexicographic_compare_source(comparisons: Vec<String>) -> String {
+    let mut comparisons = comparisons.into_iter().enumerate().rev();
+    let Some((_, mut source)) = comparisons.next() else {
+      return "0".to_string();
+    };
+    for (i, comparison) in comparisons {
+      source =
+        format!("{{ let c{i} = {comparison}; if c{i} != 0 {{ c{i} }} else {{ {source} }} }}");
+    }
+    source
+  }
+
+  fn variant_pattern_source(heap: &Heap, variant: &VariantDefinition, prefix: &str) -> String {
+    let name = variant.name.name.as_str(heap);
+    match &variant.associated_data_types {
+      None => name.to_string(),
+      Some(list) => {
+        let bindings = (0..list.annotations.len())
+          .map(|i| if prefix.is_empty() { "_".to_string() } else { format!("{prefix}{i}") })
+          .collect::<Vec<_>>();
+        format!("{name}({})", bindings.join(", "))
+      }
+    }
+  }
+
+  fn variant_annotations(variant: &VariantDefinition) -> &[annotation::T] {
+    variant.associated_data_types.as_ref().map(|list| list.annotations.as_slice()).unwrap_or(&[])
+  }
+
+  /// `match other { ... }` with a case for each variant of `other`, or a single `_` case.
+  fn match_other_source(
+    heap: &Heap,
+    variants: &[VariantDefinition],
+    variant: &VariantDefinition,
+    matched: String,
+    fallback: &str,
+  ) -> String {
+    let mut source =
+      format!("match other {{ {} -> {matched}", variant_pattern_source(heap, variant, "b"));
+    if variants.len() > 1 {
+      source.push_str(&format!(", _ -> {fallback}"));
+    }
+    source.push_str(" }");
+    source
+  }
+
+  fn equals_body_source(heap: &Heap, type_definition: &TypeDefinition) -> String {
+    match type_definition {
+      TypeDefinition::Struct { fields, .. } => {
+        if fields.is_empty() {
+          return "true".to_string();
+        }
+        let conjuncts = fields
+          .iter()
+          .map(|field| {
+            let name = field.name.name.as_str(heap);
+            equals_source(&field.annotation, &format!("this.{name}"), &format!("other.{name}"))
+          })
+          .collect::<Vec<_>>();
+        conjuncts.join(" && ")
+      }
+      TypeDefinition::Enum { variants, .. } => {
+        let cases = variants
+          .iter()
+          .map(|variant| {
+            let annotations = variant_annotations(variant);
+            let matched = if annotations.is_empty() {
+              "true".to_string()
+            } else {
+              let conjuncts = annotations
+                .iter()
+                .enumerate()
+                .map(|(i, annot)| equals_source(annot, &format!("a{i}"), &format!("b{i}")))
+                .collect::<Vec<_>>();
+              conjuncts.join(" && ")
+            };
+            let pattern = variant_pattern_source(heap, variant, "a");
+            format!(
+              "{pattern} -> {}",
+              match_other_source(heap, variants, variant, matched, "false")
+            )
+          })
+          .collect::<Vec<_>>();
+        format!("match this {{ {} }}", cases.join(", "))
+      }
+    }
+  }
+
+  fn compare_body_source(heap: &Heap, type_definition: &TypeDefinition) -> String {
+    match type_definition {
+      TypeDefinition::Struct { fields, .. } => lexicographic_compare_source(
+        fields
+          .iter()
+          .map(|field| {
+            let name = field.name.name.as_str(heap);
+            compare_source(&field.annotation, &format!("this.{name}"), &format!("other.{name}"))
+          })
+          .collect(),
+      ),
+      TypeDefinition::Enum { variants, .. } => {
+        let other_tag = format!(
+          "(match other {{ {} }})",
+          variants
+            .iter()
+            .enumerate()
+            .map(|(i, variant)| format!("{} -> {i}", variant_pattern_source(heap, variant, "")))
+            .collect::<Vec<_>>()
+            .join(", ")
+        );
+        let cases = variants
+          .iter()
+          .enumerate()
+          .map(|(tag, variant)| {
+            let matched = lexicographic_compare_source(
+              variant_annotations(variant)
+                .iter()
+                .enumerate()
+                .map(|(i, annot)| compare_source(annot, &format!("a{i}"), &format!("b{i}")))
+                .collect(),
+            );
+            let pattern = variant_pattern_source(heap, variant, "a");
+            let fallback = format!("{tag} - {other_tag}");
+            format!(
+              "{pattern} -> {}",
+              match_other_source(heap, variants, variant, matched, &fallback)
+            )
+          })
+          .collect::<Vec<_>>();
+        format!("match this {{ {} }}", cases.join(", "))
+      }
+    }
+  }
+
+  fn to_string_body_source(
+    heap: &Heap,
+    class_name: &str,
+    type_definition: &TypeDefinition,
+  ) -> String {
+    match type_definition {
+      TypeDefinition::Struct { fields, .. } => {
+        let parts = fields
+          .iter()
+          .map(|field| {
+            let name = field.name.name.as_str(heap);
+            format!("{name}={}", to_string_source(&field.annotation, &format!("this.{name}")))
+          })
+          .collect::<Vec<_>>();
+        format!("\"{class_name}({})\"", parts.join(", "))
+      }
+      TypeDefinition::Enum { variants, .. } => {
+        let cases = variants
+          .iter()
+          .map(|variant| {
+            let annotations = variant_annotations(variant);
+            let name = variant.name.name.as_str(heap);
+            let string = if annotations.is_empty() {
+              format!("\"{name}\"")
+            } else {
+              let parts = annotations
+                .iter()
+                .enumerate()
+                .map(|(i, annot)| to_string_source(annot, &format!("a{i}")))
+                .collect::<Vec<_>>();
+              format!("\"{name}({})\"", parts.join(", "))
+            };
+            format!("{} -> {string}", variant_pattern_source(heap, variant, "a"))
+          })
+          .collect::<Vec<_>>();
+        format!("match this {{ {} }}", cases.join(", "))
+      }
+    }
+  }
+
+  pub(super) fn synthesize_derived_members(
+    parser: &mut super::SourceParser,
+    class: DerivingClass,
+    clause: &DerivesClause,
+    extends_or_implements_nodes: &mut Option<ExtendsOrImplementsNodes>,
+    members: &mut Vec<ClassMemberDefinition<()>>,
+  ) {
+    let Some(type_definition) = class.type_definition else {
+      parser.report(
+        clause.location,
+        "Only classes with a struct or enum type definition can derive members.".to_string(),
+      );
+      return;
+    };
+    let class_name = class.name.name.as_str(parser.heap).to_string();
+    let self_type = match class.type_parameters {
+      None => class_name.clone(),
+      Some(tparams) => {
+        let names =
+          tparams.parameters.iter().map(|it| it.name.name.as_str(parser.heap)).collect::<Vec<_>>();
+        format!("{class_name}<{}>", names.join(", "))
+      }
+    };
+    let mut derived = Vec::<PStr>::new();
+    for name in &clause.names {
+      if derived.contains(&name.name) {
+        parser.report(name.loc, format!("`{}` is already derived.", name.name.as_str(parser.heap)));
+        continue;
+      }
+      derived.push(name.name);
+      let source = match name.name.as_str(parser.heap) {
+        "equals" => format!(
+          "method equals(other: {self_type}): bool = {}",
+          equals_body_source(parser.heap, type_definition)
+        ),
+        "compare" => format!(
+          "method compare(other: {self_type}): int = {}",
+          compare_body_source(parser.heap, type_definition)
+        ),
+        "toString" => format!(
+          "method toString(): Str = {}",
+          to_string_body_source(parser.heap, &class_name, type_definition)
+        ),
+        other => {
+          parser.report(
+            name.loc,
+            format!("Cannot derive `{other}`. Only equals, compare and toString can be derived."),
+          );
+          continue;
+        }
+      };
+      let saved_upper_type_parameters = parser.available_tparams.clone();
+      let mut member = parser.parse_synthesized(&source, name.loc, |parser| {
+        super::toplevel_parser::parse_class_member_definition(parser)
+      });
+      parser.available_tparams = saved_upper_type_parameters;
+      member.decl.loc = name.loc;
+      member.decl.name.loc = name.loc;
+      members.push(member);
+      if name.name.as_str(parser.heap) == "compare" {
+        add_comparable_node(parser, &self_type, name.loc, extends_or_implements_nodes);
+      }
+    }
+  }
+
+  /// A class deriving `compare` implements `Comparable<Self>` from `std.interfaces`.
+  fn add_comparable_node(
+    parser: &mut super::SourceParser,
+    self_type: &str,
+    loc: samlang_ast::Location,
+    extends_or_implements_nodes: &mut Option<ExtendsOrImplementsNodes>,
+  ) {
+    let std_interfaces = parser
+      .heap
+      .alloc_module_reference_from_string_vec(vec!["std".to_string(), "interfaces".to_string()]);
+    if let Some(nodes) = extends_or_implements_nodes
+      && nodes.nodes.iter().any(|node| {
+        node.module_reference == std_interfaces && node.id.name.as_str(parser.heap) == "Comparable"
+      })
+    {
+      return;
+    }
+    let mut node = parser.parse_synthesized(&format!("Comparable<{self_type}>"), loc, |parser| {
+      let id = parser.parse_upper_id();
+      super::type_parser::parse_identifier_annot(parser, id)
+    });
+    node.location = loc;
+    node.id.loc = loc;
+    node.module_reference = std_interfaces;
+    match extends_or_implements_nodes {
+      Some(nodes) => nodes.nodes.push(node),
+      None => {
+        *extends_or_implements_nodes = Some(ExtendsOrImplementsNodes {
+          location: loc,
+          associated_comments: NO_COMMENT_REFERENCE,
+          nodes: vec![node],
+        })
+      }
+    }
+  }
+}
+
 mod expression_parser {
   use crate::source_parser::type_parser;
 
-  use super::{
-    super::lexer::{Keyword, Token, TokenContent, TokenOp},
-    MAX_STRUCT_SIZE,
-  };
+  use super::super::lexer::{Keyword, Token, TokenContent, TokenOp};
   use itertools::Itertools;
   use samlang_ast::{Location, source::*};
   use samlang_heap::PStr;
   use std::collections::HashMap;
 
   pub(super) fn parse_expression(parser: &mut super::SourceParser) -> expr::E<()> {
-    parse_match(parser)
+    parse_return_or_lower_precedence(parser)
   }
 
   pub(super) fn parse_expression_with_additional_preceding_comments(
@@ -689,6 +1194,23 @@ mod expression_parser {
     expr
   }
 
+  fn parse_return_or_lower_precedence(parser: &mut super::SourceParser) -> expr::E<()> {
+    if let Token(peeked_loc, TokenContent::Keyword(Keyword::Return)) = parser.peek() {
+      let associated_comments = parser.consume();
+      let argument = parse_expression(parser);
+      let loc = peeked_loc.union(&argument.loc());
+      return expr::E::Return(expr::Return {
+        common: expr::ExpressionCommon {
+          loc,
+          associated_comments: parser.comments_store.create_comment_reference(associated_comments),
+          type_: (),
+        },
+        argument: Box::new(argument),
+      });
+    }
+    parse_match(parser)
+  }
+
   fn parse_match(parser: &mut super::SourceParser) -> expr::E<()> {
     if let Token(peeked_loc, TokenContent::Keyword(Keyword::Match)) = parser.peek() {
       let mut associated_comments = parser.consume();
@@ -697,9 +1219,13 @@ mod expression_parser {
       let mut matching_list = vec![parse_pattern_to_expression(parser)];
       while matches!(
         parser.peek().1,
-        TokenContent::Operator(TokenOp::LeftBrace | TokenOp::LeftParenthesis | TokenOp::Underscore)
-          | TokenContent::LowerId(_)
+        TokenContent::Operator(
+          TokenOp::LeftBrace | TokenOp::LeftParenthesis | TokenOp::Underscore | TokenOp::Minus
+        ) | TokenContent::LowerId(_)
           | TokenContent::UpperId(_)
+          | TokenContent::IntLiteral(_)
+          | TokenContent::StringLiteral(_)
+          | TokenContent::Keyword(Keyword::True | Keyword::False)
       ) {
         matching_list.push(parse_pattern_to_expression(parser));
       }
@@ -726,6 +1252,15 @@ mod expression_parser {
     parser: &mut super::SourceParser,
   ) -> expr::VariantPatternToExpression<()> {
     let pattern = super::pattern_parser::parse_matching_pattern(parser, Vec::new());
+    let guard = if let Token(_, TokenContent::Keyword(Keyword::If)) = parser.peek() {
+      let comments_before_guard = parser.consume();
+      Some(Box::new(parse_expression_with_additional_preceding_comments(
+        parser,
+        comments_before_guard,
+      )))
+    } else {
+      None
+    };
     let (_, additional_comments) = parser.assert_and_consume_operator(TokenOp::Arrow);
     let expression =
       parse_expression_with_additional_preceding_comments(parser, additional_comments);
@@ -739,16 +1274,60 @@ mod expression_parser {
     expr::VariantPatternToExpression {
       loc,
       pattern,
+      guard,
       body: Box::new(expression),
       ending_associated_comments,
     }
   }
 
   fn parse_if_else_or_higher_precedence(parser: &mut super::SourceParser) -> expr::E<()> {
-    if let Token(_, TokenContent::Keyword(Keyword::If)) = parser.peek() {
-      return expr::E::IfElse(parse_if_else(parser, Vec::new()));
+    match parser.peek() {
+      Token(_, TokenContent::Keyword(Keyword::If)) => {
+        expr::E::IfElse(parse_if_else(parser, Vec::new()))
+      }
+      Token(_, TokenContent::Keyword(Keyword::While)) => parse_while(parser),
+      Token(_, TokenContent::Keyword(Keyword::For)) => parse_for(parser),
+      _ => parse_pipe(parser),
     }
-    parse_disjunction(parser)
+  }
+
+  fn parse_while(parser: &mut super::SourceParser) -> expr::E<()> {
+    let (peeked_loc, associated_comments) = parser.assert_and_consume_keyword(Keyword::While);
+    let condition = parse_expression(parser);
+    let body = parse_block(parser, Vec::new());
+    expr::E::While(expr::While {
+      common: expr::ExpressionCommon {
+        loc: peeked_loc.union(&body.common.loc),
+        associated_comments: parser.comments_store.create_comment_reference(associated_comments),
+        type_: (),
+      },
+      condition: Box::new(condition),
+      body: Box::new(body),
+    })
+  }
+
+  fn parse_for(parser: &mut super::SourceParser) -> expr::E<()> {
+    let (peeked_loc, mut associated_comments) = parser.assert_and_consume_keyword(Keyword::For);
+    associated_comments.append(&mut parser.assert_and_consume_operator(TokenOp::LeftParenthesis).1);
+    let iterator = parser.parse_lower_id();
+    associated_comments.append(&mut parser.assert_and_consume_keyword(Keyword::In).1);
+    let start = parse_expression(parser);
+    associated_comments.append(&mut parser.assert_and_consume_operator(TokenOp::DotDot).1);
+    let end = parse_expression(parser);
+    associated_comments
+      .append(&mut parser.assert_and_consume_operator(TokenOp::RightParenthesis).1);
+    let body = parse_block(parser, Vec::new());
+    expr::E::For(expr::For {
+      common: expr::ExpressionCommon {
+        loc: peeked_loc.union(&body.common.loc),
+        associated_comments: parser.comments_store.create_comment_reference(associated_comments),
+        type_: (),
+      },
+      iterator,
+      start: Box::new(start),
+      end: Box::new(end),
+      body: Box::new(body),
+    })
   }
 
   fn parse_if_else(
@@ -789,6 +1368,33 @@ mod expression_parser {
     }
   }
 
+  fn parse_pipe(parser: &mut super::SourceParser) -> expr::E<()> {
+    let e = parse_disjunction(parser);
+    parse_pipe_with_start(parser, e)
+  }
+
+  fn parse_pipe_with_start(parser: &mut super::SourceParser, mut e: expr::E<()>) -> expr::E<()> {
+    while let TokenContent::Operator(TokenOp::Pipe) = parser.peek().1 {
+      let concrete_comments = parser.consume();
+      let operator_preceding_comments =
+        parser.comments_store.create_comment_reference(concrete_comments);
+      let e2 = parse_disjunction(parser);
+      let loc = e.loc().union(&e2.loc());
+      e = expr::E::Binary(expr::Binary {
+        common: expr::ExpressionCommon {
+          loc,
+          associated_comments: parser.comments_store.create_comment_reference(Vec::new()),
+          type_: (),
+        },
+        operator_preceding_comments,
+        operator: expr::BinaryOperator::PIPE,
+        e1: Box::new(e),
+        e2: Box::new(e2),
+      })
+    }
+    e
+  }
+
   fn parse_disjunction(parser: &mut super::SourceParser) -> expr::E<()> {
     let e = parse_conjunction(parser);
     parse_disjunction_with_start(parser, e)
@@ -849,23 +1455,146 @@ mod expression_parser {
     e
   }
 
-  fn parse_comparison(parser: &mut super::SourceParser) -> expr::E<()> {
+  fn parse_comparison(parser: &mut super::SourceParser) -> expr::E<()> {
+    let e = parse_bitwise_or(parser);
+    parse_comparison_with_start(parser, e)
+  }
+
+  fn parse_comparison_with_start(
+    parser: &mut super::SourceParser,
+    mut e: expr::E<()>,
+  ) -> expr::E<()> {
+    loop {
+      let operator = match parser.peek().1 {
+        TokenContent::Operator(TokenOp::LessThan) => expr::BinaryOperator::LT,
+        TokenContent::Operator(TokenOp::LessThanOrEqual) => expr::BinaryOperator::LE,
+        TokenContent::Operator(TokenOp::GreaterThan) => expr::BinaryOperator::GT,
+        TokenContent::Operator(TokenOp::GreaterThanOrEqual) => expr::BinaryOperator::GE,
+        TokenContent::Operator(TokenOp::Equal) => expr::BinaryOperator::EQ,
+        TokenContent::Operator(TokenOp::NotEqual) => expr::BinaryOperator::NE,
+        _ => break,
+      };
+      let concrete_comments = parser.consume();
+      let operator_preceding_comments =
+        parser.comments_store.create_comment_reference(concrete_comments);
+      let e2 = parse_bitwise_or(parser);
+      let loc = e.loc().union(&e2.loc());
+      e = expr::E::Binary(expr::Binary {
+        common: expr::ExpressionCommon {
+          loc,
+          associated_comments: parser.comments_store.create_comment_reference(Vec::new()),
+          type_: (),
+        },
+        operator_preceding_comments,
+        operator,
+        e1: Box::new(e),
+        e2: Box::new(e2),
+      })
+    }
+    e
+  }
+
+  fn parse_bitwise_or(parser: &mut super::SourceParser) -> expr::E<()> {
+    let e = parse_bitwise_xor(parser);
+    parse_bitwise_or_with_start(parser, e)
+  }
+
+  fn parse_bitwise_or_with_start(
+    parser: &mut super::SourceParser,
+    mut e: expr::E<()>,
+  ) -> expr::E<()> {
+    while let TokenContent::Operator(TokenOp::Bar) = parser.peek().1 {
+      let concrete_comments = parser.consume();
+      let operator_preceding_comments =
+        parser.comments_store.create_comment_reference(concrete_comments);
+      let e2 = parse_bitwise_xor(parser);
+      let loc = e.loc().union(&e2.loc());
+      e = expr::E::Binary(expr::Binary {
+        common: expr::ExpressionCommon {
+          loc,
+          associated_comments: parser.comments_store.create_comment_reference(Vec::new()),
+          type_: (),
+        },
+        operator_preceding_comments,
+        operator: expr::BinaryOperator::LOR,
+        e1: Box::new(e),
+        e2: Box::new(e2),
+      })
+    }
+    e
+  }
+
+  fn parse_bitwise_xor(parser: &mut super::SourceParser) -> expr::E<()> {
+    let e = parse_bitwise_and(parser);
+    parse_bitwise_xor_with_start(parser, e)
+  }
+
+  fn parse_bitwise_xor_with_start(
+    parser: &mut super::SourceParser,
+    mut e: expr::E<()>,
+  ) -> expr::E<()> {
+    while let TokenContent::Operator(TokenOp::BitXor) = parser.peek().1 {
+      let concrete_comments = parser.consume();
+      let operator_preceding_comments =
+        parser.comments_store.create_comment_reference(concrete_comments);
+      let e2 = parse_bitwise_and(parser);
+      let loc = e.loc().union(&e2.loc());
+      e = expr::E::Binary(expr::Binary {
+        common: expr::ExpressionCommon {
+          loc,
+          associated_comments: parser.comments_store.create_comment_reference(Vec::new()),
+          type_: (),
+        },
+        operator_preceding_comments,
+        operator: expr::BinaryOperator::XOR,
+        e1: Box::new(e),
+        e2: Box::new(e2),
+      })
+    }
+    e
+  }
+
+  fn parse_bitwise_and(parser: &mut super::SourceParser) -> expr::E<()> {
+    let e = parse_shift(parser);
+    parse_bitwise_and_with_start(parser, e)
+  }
+
+  fn parse_bitwise_and_with_start(
+    parser: &mut super::SourceParser,
+    mut e: expr::E<()>,
+  ) -> expr::E<()> {
+    while let TokenContent::Operator(TokenOp::BitAnd) = parser.peek().1 {
+      let concrete_comments = parser.consume();
+      let operator_preceding_comments =
+        parser.comments_store.create_comment_reference(concrete_comments);
+      let e2 = parse_shift(parser);
+      let loc = e.loc().union(&e2.loc());
+      e = expr::E::Binary(expr::Binary {
+        common: expr::ExpressionCommon {
+          loc,
+          associated_comments: parser.comments_store.create_comment_reference(Vec::new()),
+          type_: (),
+        },
+        operator_preceding_comments,
+        operator: expr::BinaryOperator::LAND,
+        e1: Box::new(e),
+        e2: Box::new(e2),
+      })
+    }
+    e
+  }
+
+  fn parse_shift(parser: &mut super::SourceParser) -> expr::E<()> {
     let e = parse_term(parser);
-    parse_comparison_with_start(parser, e)
+    parse_shift_with_start(parser, e)
   }
 
-  fn parse_comparison_with_start(
-    parser: &mut super::SourceParser,
-    mut e: expr::E<()>,
-  ) -> expr::E<()> {
+  fn parse_shift_with_start(parser: &mut super::SourceParser, mut e: expr::E<()>) -> expr::E<()> {
     loop {
       let operator = match parser.peek().1 {
-        TokenContent::Operator(TokenOp::LessThan) => expr::BinaryOperator::LT,
-        TokenContent::Operator(TokenOp::LessThanOrEqual) => expr::BinaryOperator::LE,
-        TokenContent::Operator(TokenOp::GreaterThan) => expr::BinaryOperator::GT,
-        TokenContent::Operator(TokenOp::GreaterThanOrEqual) => expr::BinaryOperator::GE,
-        TokenContent::Operator(TokenOp::Equal) => expr::BinaryOperator::EQ,
-        TokenContent::Operator(TokenOp::NotEqual) => expr::BinaryOperator::NE,
+        TokenContent::Operator(TokenOp::ShiftLeft) => expr::BinaryOperator::SHL,
+        TokenContent::Operator(TokenOp::ShiftRight) => expr::BinaryOperator::ASHR,
+        TokenContent::Operator(TokenOp::UnsignedShiftRight) => expr::BinaryOperator::SHR,
         _ => break,
       };
       let concrete_comments = parser.consume();
@@ -1015,6 +1744,22 @@ mod expression_parser {
           argument: Box::new(argument),
         })
       }
+      TokenContent::Operator(TokenOp::BitNot) => {
+        let associated_comments = parser.consume();
+        let argument = parse_function_call_or_field_access(parser);
+        let loc = peeked_loc.union(&argument.loc());
+        expr::E::Unary(expr::Unary {
+          common: expr::ExpressionCommon {
+            loc,
+            associated_comments: parser
+              .comments_store
+              .create_comment_reference(associated_comments),
+            type_: (),
+          },
+          operator: expr::UnaryOperator::BNOT,
+          argument: Box::new(argument),
+        })
+      }
       _ => parse_function_call_or_field_access(parser),
     }
   }
@@ -1072,7 +1817,7 @@ mod expression_parser {
           });
         }
         Token(_, TokenContent::Operator(TokenOp::LeftParenthesis)) => {
-          let function_arguments = parse_parenthesized_expression_list(parser, usize::MAX);
+          let (function_arguments, argument_names) = parse_call_arguments(parser);
           let loc = function_expression.loc().union(&function_arguments.loc);
           function_expression = expr::E::Call(expr::Call {
             common: expr::ExpressionCommon {
@@ -1082,13 +1827,41 @@ mod expression_parser {
             },
             callee: Box::new(function_expression),
             arguments: function_arguments,
+            binding: expr::ArgumentBinding { names: argument_names, ..Default::default() },
           })
         }
+        Token(question_loc, TokenContent::Operator(TokenOp::Question)) => {
+          let associated_comments = parser.consume();
+          let loc = function_expression.loc().union(&question_loc);
+          function_expression = expr::E::Try(expr::Try {
+            common: expr::ExpressionCommon {
+              loc,
+              associated_comments: parser
+                .comments_store
+                .create_comment_reference(associated_comments),
+              type_: (),
+            },
+            argument: Box::new(function_expression),
+          });
+        }
         _ => return function_expression,
       }
     }
   }
 
+  /// Strip `prefix_len` and `suffix_len` bytes of delimiters (quotes, `}` and `${`) from a
+  /// template string token.
+  fn parse_template_string_part(
+    parser: &mut super::SourceParser,
+    token: PStr,
+    prefix_len: usize,
+    suffix_len: usize,
+  ) -> PStr {
+    let text = token.as_str(parser.heap);
+    let part = super::utils::unescape_quotes(&text[prefix_len..(text.len() - suffix_len)]);
+    parser.heap.alloc_string(part)
+  }
+
   fn parse_expression_from_base(
     parser: &mut super::SourceParser,
     base: expr::E<()>,
@@ -1097,9 +1870,14 @@ mod expression_parser {
     let e = parse_concat_with_start(parser, e);
     let e = parse_factor_with_start(parser, e);
     let e = parse_term_with_start(parser, e);
+    let e = parse_shift_with_start(parser, e);
+    let e = parse_bitwise_and_with_start(parser, e);
+    let e = parse_bitwise_xor_with_start(parser, e);
+    let e = parse_bitwise_or_with_start(parser, e);
     let e = parse_comparison_with_start(parser, e);
     let e = parse_conjunction_with_start(parser, e);
-    parse_disjunction_with_start(parser, e)
+    let e = parse_disjunction_with_start(parser, e);
+    parse_pipe_with_start(parser, e)
   }
 
   fn parse_base_expression(parser: &mut super::SourceParser) -> expr::E<()> {
@@ -1432,11 +2210,8 @@ mod expression_parser {
           }
         }
       };
-      let mut expressions_list = parse_parenthesized_expression_list_with_start(
-        parser,
-        (peeked_loc, associated_comments),
-        MAX_STRUCT_SIZE,
-      );
+      let mut expressions_list =
+        parse_parenthesized_expression_list_with_start(parser, (peeked_loc, associated_comments));
       if expressions_list.expressions.len() == 1 {
         return expressions_list.expressions.pop().unwrap();
       }
@@ -1450,9 +2225,13 @@ mod expression_parser {
       );
     }
 
-    // Statement Block: { ... }
+    // Statement Block: { ... } or struct update: { ...base, field: value }
     if let Token(_, TokenContent::Operator(TokenOp::LeftBrace)) = parser.peek() {
-      return expr::E::Block(parse_block(parser, Vec::new()));
+      let (start_loc, associated_comments) = parser.assert_and_consume_operator(TokenOp::LeftBrace);
+      if let Token(_, TokenContent::Operator(TokenOp::DotDotDot)) = parser.peek() {
+        return parse_struct_update(parser, start_loc, associated_comments);
+      }
+      return expr::E::Block(parse_block_after_left_brace(parser, start_loc, associated_comments));
     }
 
     let peeked = parser.peek();
@@ -1467,7 +2246,7 @@ mod expression_parser {
         associated_comments: NO_COMMENT_REFERENCE,
         type_: (),
       },
-      Literal::Int(0),
+      Literal::Int(0, None),
     )
   }
 
@@ -1509,14 +2288,25 @@ mod expression_parser {
               .create_comment_reference(associated_comments),
             type_: (),
           },
-          Literal::Int(i.as_str(parser.heap).parse::<i32>().unwrap_or(0)),
+          super::utils::int_literal(parser, i),
+        ))
+      }
+      Token(peeked_loc, TokenContent::FloatLiteral(f)) => {
+        let associated_comments = parser.consume();
+        Some(expr::E::Literal(
+          expr::ExpressionCommon {
+            loc: peeked_loc,
+            associated_comments: parser
+              .comments_store
+              .create_comment_reference(associated_comments),
+            type_: (),
+          },
+          Literal::float_literal(f.as_str(parser.heap).parse::<f64>().unwrap_or(0.0)),
         ))
       }
       Token(peeked_loc, TokenContent::StringLiteral(s)) => {
         let associated_comments = parser.consume();
-        let chars = s.as_str(parser.heap).chars().collect_vec();
-        let str_lit =
-          super::utils::unescape_quotes(&chars[1..(chars.len() - 1)].iter().collect::<String>());
+        let literal = super::utils::string_literal(parser, s);
         Some(expr::E::Literal(
           expr::ExpressionCommon {
             loc: peeked_loc,
@@ -1525,9 +2315,78 @@ mod expression_parser {
               .create_comment_reference(associated_comments),
             type_: (),
           },
-          Literal::String(parser.heap.alloc_string(str_lit)),
+          literal,
         ))
       }
+      Token(peeked_loc, TokenContent::TemplateStringHead(head)) => {
+        let mut associated_comments = parser.consume();
+        let mut parts = vec![parse_template_string_part(parser, head, 1, 2)];
+        let mut holes = Vec::new();
+        let mut loc = peeked_loc;
+        loop {
+          holes.push(parse_expression(parser));
+          match parser.peek() {
+            Token(_, TokenContent::TemplateStringMiddle(middle)) => {
+              associated_comments.append(&mut parser.consume());
+              parts.push(parse_template_string_part(parser, middle, 1, 2));
+            }
+            Token(tail_loc, TokenContent::TemplateStringTail(tail)) => {
+              associated_comments.append(&mut parser.consume());
+              parts.push(parse_template_string_part(parser, tail, 1, 1));
+              loc = loc.union(&tail_loc);
+              break;
+            }
+            Token(peeked_loc, content) => {
+              let actual = content.pretty_print(parser.heap);
+              parser.report(peeked_loc, format!("Expected: }}, actual: {actual}."));
+              parts.push(PStr::EMPTY);
+              loc = loc.union(&parser.last_location);
+              break;
+            }
+          }
+        }
+        Some(expr::E::TemplateString(expr::TemplateString {
+          common: expr::ExpressionCommon {
+            loc,
+            associated_comments: parser
+              .comments_store
+              .create_comment_reference(associated_comments),
+            type_: (),
+          },
+          parts,
+          holes,
+        }))
+      }
+      Token(peeked_loc, TokenContent::Keyword(Keyword::Break)) => {
+        let associated_comments = parser.consume();
+        Some(expr::E::Break(expr::ExpressionCommon {
+          loc: peeked_loc,
+          associated_comments: parser.comments_store.create_comment_reference(associated_comments),
+          type_: (),
+        }))
+      }
+      Token(peeked_loc, TokenContent::Keyword(Keyword::Assert)) => {
+        let associated_comments = parser.consume();
+        let mut arguments = parse_parenthesized_expression_list(parser);
+        let arguments_count = arguments.expressions.len();
+        if !(1..=2).contains(&arguments_count) {
+          parser.report(
+            arguments.loc,
+            format!("Expected 1 or 2 arguments for `assert`, but got {arguments_count}."),
+          );
+        }
+        arguments.expressions.truncate(2);
+        Some(expr::E::Assert(expr::Assert {
+          common: expr::ExpressionCommon {
+            loc: peeked_loc.union(&arguments.loc),
+            associated_comments: parser
+              .comments_store
+              .create_comment_reference(associated_comments),
+            type_: (),
+          },
+          arguments,
+        }))
+      }
       Token(peeked_loc, TokenContent::Keyword(Keyword::This)) => {
         let associated_comments = parser.consume();
         Some(expr::E::LocalId(
@@ -1574,34 +2433,24 @@ mod expression_parser {
 
   fn parse_parenthesized_expression_list(
     parser: &mut super::SourceParser,
-    max_size: usize,
   ) -> expr::ParenthesizedExpressionList<()> {
     let (start_loc, starting_comments) =
       parser.assert_and_consume_operator(TokenOp::LeftParenthesis);
-    parse_parenthesized_expression_list_with_start(parser, (start_loc, starting_comments), max_size)
+    parse_parenthesized_expression_list_with_start(parser, (start_loc, starting_comments))
   }
 
   fn parse_parenthesized_expression_list_with_start(
     parser: &mut super::SourceParser,
     (start_loc, starting_comments): (Location, Vec<Comment>),
-    max_size: usize,
   ) -> expr::ParenthesizedExpressionList<()> {
     let expressions =
       if matches!(parser.peek(), Token(_, TokenContent::Operator(TokenOp::RightParenthesis))) {
         Vec::new()
       } else {
-        let mut expressions = parser.parse_comma_separated_list_with_end_token(
+        parser.parse_comma_separated_list_with_end_token(
           TokenOp::RightParenthesis,
           &mut parse_expression_with_additional_preceding_comments,
-        );
-        if let Some(node) = expressions.get(max_size) {
-          parser.error_set.report_invalid_syntax_error(
-            node.loc(),
-            format!("Maximum allowed tuple size is {max_size}"),
-          );
-        }
-        expressions.truncate(max_size);
-        expressions
+        )
       };
     let (end_loc, ending_comments) = parser.assert_and_consume_operator(TokenOp::RightParenthesis);
     let loc = start_loc.union(&end_loc);
@@ -1613,6 +2462,53 @@ mod expression_parser {
     }
   }
 
+  /// Parses call arguments, where `name = value` arguments must come after positional ones.
+  /// Returns the names of the trailing named arguments together with all argument values.
+  fn parse_call_arguments(
+    parser: &mut super::SourceParser,
+  ) -> (expr::ParenthesizedExpressionList<()>, Vec<Id>) {
+    let (start_loc, starting_comments) =
+      parser.assert_and_consume_operator(TokenOp::LeftParenthesis);
+    let mut names = Vec::new();
+    let mut parse_argument = |parser: &mut super::SourceParser, comments: Vec<Comment>| {
+      let argument = parse_expression_with_additional_preceding_comments(parser, comments);
+      if let (expr::E::LocalId(common, name), Token(_, TokenContent::Operator(TokenOp::Assign))) =
+        (&argument, parser.peek())
+      {
+        let name = Id { associated_comments: common.associated_comments, ..*name };
+        let assign_comments = parser.consume();
+        names.push(name);
+        Some(parse_expression_with_additional_preceding_comments(parser, assign_comments))
+      } else if names.is_empty() {
+        Some(argument)
+      } else {
+        parser.report(
+          argument.loc(),
+          "Positional arguments must come before named arguments.".to_string(),
+        );
+        None
+      }
+    };
+    let expressions =
+      if matches!(parser.peek(), Token(_, TokenContent::Operator(TokenOp::RightParenthesis))) {
+        Vec::new()
+      } else {
+        parser
+          .parse_comma_separated_list_with_end_token(TokenOp::RightParenthesis, &mut parse_argument)
+          .into_iter()
+          .flatten()
+          .collect()
+      };
+    let (end_loc, ending_comments) = parser.assert_and_consume_operator(TokenOp::RightParenthesis);
+    let arguments = expr::ParenthesizedExpressionList {
+      loc: start_loc.union(&end_loc),
+      start_associated_comments: parser.comments_store.create_comment_reference(starting_comments),
+      ending_associated_comments: parser.comments_store.create_comment_reference(ending_comments),
+      expressions,
+    };
+    (arguments, names)
+  }
+
   fn collect_remaining_and_build_tuple(
     parser: &mut super::SourceParser,
     start_loc: Location,
@@ -1626,13 +2522,6 @@ mod expression_parser {
       }
       expressions.push(parse_expression_with_additional_preceding_comments(parser, comments));
     }
-    if let Some(node) = expressions.get(MAX_STRUCT_SIZE) {
-      parser.error_set.report_invalid_syntax_error(
-        node.loc(),
-        format!("Maximum allowed tuple size is {MAX_STRUCT_SIZE}"),
-      );
-    }
-    expressions.truncate(MAX_STRUCT_SIZE);
     let (end_loc, end_comments) = parser.assert_and_consume_operator(TokenOp::RightParenthesis);
     let loc = start_loc.union(&end_loc);
     debug_assert!(expressions.len() > 1);
@@ -1647,23 +2536,68 @@ mod expression_parser {
     )
   }
 
+  fn parse_struct_update(
+    parser: &mut super::SourceParser,
+    start_loc: Location,
+    mut associated_comments: Vec<Comment>,
+  ) -> expr::E<()> {
+    associated_comments.append(&mut parser.assert_and_consume_operator(TokenOp::DotDotDot).1);
+    let base = parse_expression(parser);
+    let mut fields = Vec::new();
+    let mut ending_comments = Vec::new();
+    while let Token(_, TokenContent::Operator(TokenOp::Comma)) = parser.peek() {
+      ending_comments.append(&mut parser.consume());
+      if let Token(_, TokenContent::Operator(TokenOp::RightBrace)) = parser.peek() {
+        break;
+      }
+      let field_name = parser.parse_lower_id();
+      let colon_comments = parser.assert_and_consume_operator(TokenOp::Colon).1;
+      let value = parse_expression_with_additional_preceding_comments(parser, colon_comments);
+      fields.push(expr::FieldUpdate {
+        loc: field_name.loc.union(&value.loc()),
+        field_name,
+        field_order: -1,
+        value,
+      });
+    }
+    let (end_loc, mut comments) = parser.assert_and_consume_operator(TokenOp::RightBrace);
+    ending_comments.append(&mut comments);
+    expr::E::StructUpdate(expr::StructUpdate {
+      common: expr::ExpressionCommon {
+        loc: start_loc.union(&end_loc),
+        associated_comments: parser.comments_store.create_comment_reference(associated_comments),
+        type_: (),
+      },
+      base: Box::new(base),
+      fields,
+      ending_associated_comments: parser.comments_store.create_comment_reference(ending_comments),
+    })
+  }
+
   fn parse_block(
     parser: &mut super::SourceParser,
     mut associated_comments: Vec<Comment>,
   ) -> expr::Block<()> {
-    let start_loc = {
-      let (loc, mut comments) = parser.assert_and_consume_operator(TokenOp::LeftBrace);
-      associated_comments.append(&mut comments);
-      loc
-    };
+    let (start_loc, mut comments) = parser.assert_and_consume_operator(TokenOp::LeftBrace);
+    associated_comments.append(&mut comments);
+    parse_block_after_left_brace(parser, start_loc, associated_comments)
+  }
 
+  fn parse_block_after_left_brace(
+    parser: &mut super::SourceParser,
+    start_loc: Location,
+    mut associated_comments: Vec<Comment>,
+  ) -> expr::Block<()> {
     let mut statements = Vec::new();
 
     loop {
       match parser.peek() {
-        Token(_, TokenContent::Keyword(Keyword::Let)) => {
+        Token(_, TokenContent::Keyword(Keyword::Let | Keyword::Var)) => {
           statements.push(parse_statement(parser));
         }
+        Token(_, TokenContent::Keyword(Keyword::Function)) => {
+          statements.push(parse_local_function_statement(parser));
+        }
         Token(_, TokenContent::Operator(TokenOp::RightBrace)) => {
           // Empty block - no final expression
           let (end_loc, ending_comments) = parser.assert_and_consume_operator(TokenOp::RightBrace);
@@ -1708,7 +2642,14 @@ mod expression_parser {
           // Try to parse as an expression statement: parse expression, expect semicolon
           let expr = parse_expression(parser);
           let peeked_after_expr = parser.peek();
-          if let Token(_, TokenContent::Operator(TokenOp::Semicolon)) = peeked_after_expr {
+          if let (
+            expr::E::LocalId(common, name),
+            Token(_, TokenContent::Operator(TokenOp::Assign)),
+          ) = (&expr, peeked_after_expr)
+          {
+            let name = Id { associated_comments: common.associated_comments, ..*name };
+            statements.push(parse_assignment_statement(parser, common.loc, name));
+          } else if let Token(_, TokenContent::Operator(TokenOp::Semicolon)) = peeked_after_expr {
             // This is an expression statement
             let (_, ending_comments) = parser.assert_and_consume_operator(TokenOp::Semicolon);
             statements.push(expr::Statement::Expression(Box::new(expr)));
@@ -1733,6 +2674,9 @@ mod expression_parser {
                 .comments_store
                 .create_comment_reference(ending_comments),
             };
+          } else if let expr::E::While(_) | expr::E::For(_) = &expr {
+            // Loops in statement position don't need a trailing semicolon.
+            statements.push(expr::Statement::Expression(Box::new(expr)));
           } else if let Token(loc, TokenContent::EndOfFile) = peeked_after_expr {
             // Unexpected end of file - expression without closing brace
             parser.report(loc, "Expected: ; or }, actual: EOF.".to_string());
@@ -1764,8 +2708,15 @@ mod expression_parser {
   }
 
   pub(super) fn parse_statement(parser: &mut super::SourceParser) -> expr::Statement<()> {
-    let (start_loc, mut concrete_comments) = parser.assert_and_consume_keyword(Keyword::Let);
-    let pattern = super::pattern_parser::parse_matching_pattern(parser, Vec::new());
+    let is_mutable = matches!(parser.peek(), Token(_, TokenContent::Keyword(Keyword::Var)));
+    let (start_loc, mut concrete_comments) =
+      parser.assert_and_consume_keyword(if is_mutable { Keyword::Var } else { Keyword::Let });
+    // Mutable variables can only be bound to a single name.
+    let pattern = if is_mutable {
+      pattern::MatchingPattern::Id(parser.parse_lower_id(), ())
+    } else {
+      super::pattern_parser::parse_matching_pattern(parser, Vec::new())
+    };
     let annotation = if let Token(_, TokenContent::Operator(TokenOp::Colon)) = parser.peek() {
       Some(super::type_parser::parse_annotation_with_colon(parser))
     } else {
@@ -1773,6 +2724,14 @@ mod expression_parser {
     };
     concrete_comments.append(&mut parser.assert_and_consume_operator(TokenOp::Assign).1);
     let assigned_expression = Box::new(parse_expression(parser));
+    let else_block = if let (false, Token(_, TokenContent::Keyword(Keyword::Else))) =
+      (is_mutable, parser.peek())
+    {
+      let else_comments = parser.consume();
+      Some(Box::new(parse_block(parser, else_comments)))
+    } else {
+      None
+    };
     let (end_loc, mut additional_comments) = parser.assert_and_consume_operator(TokenOp::Semicolon);
     concrete_comments.append(&mut additional_comments);
     let loc = start_loc.union(&end_loc);
@@ -1780,9 +2739,81 @@ mod expression_parser {
     expr::Statement::Declaration(Box::new(expr::DeclarationStatement {
       loc,
       associated_comments,
+      is_mutable,
       pattern,
       annotation,
       assigned_expression,
+      else_block,
+    }))
+  }
+
+  fn parse_local_function_statement(parser: &mut super::SourceParser) -> expr::Statement<()> {
+    let (start_loc, mut concrete_comments) = parser.assert_and_consume_keyword(Keyword::Function);
+    let name = parser.parse_lower_id();
+    let (parameters_start_loc, parameters_start_comments) =
+      parser.assert_and_consume_operator(TokenOp::LeftParenthesis);
+    let parameters = if let TokenContent::Operator(TokenOp::RightParenthesis) = parser.peek().1 {
+      Vec::new()
+    } else {
+      parser.parse_comma_separated_list_with_end_token(
+        TokenOp::RightParenthesis,
+        &mut |parser: &mut super::SourceParser, comments| {
+          let name = parser.parse_lower_id_with_comments(comments);
+          let annotation = Some(super::type_parser::parse_annotation_with_colon(parser));
+          OptionallyAnnotatedId { name, type_: (), annotation }
+        },
+      )
+    };
+    let (parameters_end_loc, parameters_end_comments) =
+      parser.assert_and_consume_operator(TokenOp::RightParenthesis);
+    let return_annotation = super::type_parser::parse_annotation_with_colon(parser);
+    concrete_comments.append(&mut parser.assert_and_consume_operator(TokenOp::Assign).1);
+    let body = parse_expression(parser);
+    let (end_loc, mut additional_comments) = parser.assert_and_consume_operator(TokenOp::Semicolon);
+    concrete_comments.append(&mut additional_comments);
+    let lambda = expr::Lambda {
+      common: expr::ExpressionCommon {
+        loc: parameters_start_loc.union(&body.loc()),
+        associated_comments: parser
+          .comments_store
+          .create_comment_reference(parameters_start_comments),
+        type_: (),
+      },
+      parameters: expr::LambdaParameters {
+        loc: parameters_start_loc.union(&parameters_end_loc),
+        parameters,
+        ending_associated_comments: parser
+          .comments_store
+          .create_comment_reference(parameters_end_comments),
+      },
+      captured: HashMap::new(),
+      body: Box::new(body),
+    };
+    expr::Statement::Function(Box::new(expr::LocalFunctionStatement {
+      loc: start_loc.union(&end_loc),
+      associated_comments: parser.comments_store.create_comment_reference(concrete_comments),
+      name,
+      return_annotation,
+      lambda,
+    }))
+  }
+
+  fn parse_assignment_statement(
+    parser: &mut super::SourceParser,
+    start_loc: Location,
+    name: Id,
+  ) -> expr::Statement<()> {
+    let mut concrete_comments = parser.assert_and_consume_operator(TokenOp::Assign).1;
+    let assigned_expression = Box::new(parse_expression(parser));
+    let (end_loc, mut additional_comments) = parser.assert_and_consume_operator(TokenOp::Semicolon);
+    concrete_comments.append(&mut additional_comments);
+    let loc = start_loc.union(&end_loc);
+    let associated_comments = parser.comments_store.create_comment_reference(concrete_comments);
+    expr::Statement::Assignment(Box::new(expr::AssignmentStatement {
+      loc,
+      associated_comments,
+      name,
+      assigned_expression,
     }))
   }
 }
@@ -1883,6 +2914,13 @@ mod pattern_parser {
         type_: (),
       });
     }
+    if let Some((location, literal)) = parse_literal_pattern(parser, &mut starting_comments) {
+      return pattern::MatchingPattern::Literal {
+        location,
+        associated_comments: parser.comments_store.create_comment_reference(starting_comments),
+        literal,
+      };
+    }
     if let Token(location, TokenContent::Operator(TokenOp::Underscore)) = peeked {
       starting_comments.append(&mut parser.consume());
       return pattern::MatchingPattern::Wildcard {
@@ -1893,6 +2931,44 @@ mod pattern_parser {
     pattern::MatchingPattern::Id(parser.parse_lower_id(), ())
   }
 
+  fn parse_literal_pattern(
+    parser: &mut super::SourceParser,
+    starting_comments: &mut Vec<Comment>,
+  ) -> Option<(samlang_ast::Location, Literal)> {
+    match parser.peek() {
+      Token(location, TokenContent::Keyword(Keyword::True)) => {
+        starting_comments.append(&mut parser.consume());
+        Some((location, Literal::Bool(true)))
+      }
+      Token(location, TokenContent::Keyword(Keyword::False)) => {
+        starting_comments.append(&mut parser.consume());
+        Some((location, Literal::Bool(false)))
+      }
+      Token(location, TokenContent::IntLiteral(i)) => {
+        starting_comments.append(&mut parser.consume());
+        Some((location, super::utils::int_literal(parser, i)))
+      }
+      Token(minus_loc, TokenContent::Operator(TokenOp::Minus)) => {
+        starting_comments.append(&mut parser.consume());
+        if let Token(location, TokenContent::IntLiteral(i)) = parser.peek() {
+          starting_comments.append(&mut parser.consume());
+          let negated = parser.heap.alloc_string(format!("-{}", i.as_str(parser.heap)));
+          Some((minus_loc.union(&location), super::utils::int_literal(parser, negated)))
+        } else {
+          let Token(loc, content) = parser.peek();
+          let actual = content.pretty_print(parser.heap);
+          parser.report(loc, format!("Expected: int, actual: {actual}."));
+          Some((minus_loc, Literal::Int(0, None)))
+        }
+      }
+      Token(location, TokenContent::StringLiteral(s)) => {
+        starting_comments.append(&mut parser.consume());
+        Some((location, super::utils::string_literal(parser, s)))
+      }
+      _ => None,
+    }
+  }
+
   fn parse_tuple_pattern(parser: &mut super::SourceParser) -> pattern::TuplePattern<()> {
     let (start_loc, starting_comments) =
       parser.assert_and_consume_operator(TokenOp::LeftParenthesis);
@@ -1965,7 +3041,16 @@ mod type_parser {
   ) -> AnnotatedId<()> {
     let name = parser.parse_lower_id_with_comments(associated_comments);
     let annotation = parse_annotation_with_colon(parser);
-    AnnotatedId { name, type_: (), annotation }
+    let default_value = if let Token(_, TokenContent::Operator(TokenOp::Assign)) = parser.peek() {
+      let assign_comments = parser.consume();
+      Some(super::expression_parser::parse_expression_with_additional_preceding_comments(
+        parser,
+        assign_comments,
+      ))
+    } else {
+      None
+    };
+    AnnotatedId { name, type_: (), annotation, default_value }
   }
 
   pub(super) fn parse_optionally_annotated_id(
@@ -2026,6 +3111,14 @@ mod type_parser {
           annotation::PrimitiveTypeKind::Int,
         )
       }
+      TokenContent::Keyword(Keyword::Float) => {
+        associated_comments.append(&mut parser.consume());
+        annotation::T::Primitive(
+          peeked.0,
+          parser.comments_store.create_comment_reference(associated_comments),
+          annotation::PrimitiveTypeKind::Float,
+        )
+      }
       TokenContent::UpperId(name) => {
         associated_comments.append(&mut parser.consume());
         let associated_comments = parser.comments_store.create_comment_reference(Vec::new());
@@ -2040,36 +3133,49 @@ mod type_parser {
       }
       TokenContent::Operator(TokenOp::LeftParenthesis) => {
         associated_comments.append(&mut parser.consume());
-        let parameters =
-          if let Token(_, TokenContent::Operator(TokenOp::RightParenthesis)) = parser.peek() {
-            let (location, mut comments) = {
-              let (loc, comments) = parser.assert_and_consume_operator(TokenOp::RightParenthesis);
-              (peeked.0.union(&loc), comments)
-            };
-            comments.append(&mut parser.assert_and_consume_operator(TokenOp::Arrow).1);
-            annotation::ParenthesizedAnnotationList {
-              location,
-              start_associated_comments: NO_COMMENT_REFERENCE,
-              ending_associated_comments: parser.comments_store.create_comment_reference(comments),
-              annotations: Vec::with_capacity(0),
-            }
-          } else {
-            let parameters = parser.parse_comma_separated_list_with_end_token(
-              TokenOp::RightParenthesis,
-              &mut parse_annotation_with_additional_comments,
-            );
-            let (additional_loc, mut comments) =
-              parser.assert_and_consume_operator(TokenOp::RightParenthesis);
-            let location = peeked.0.union(&additional_loc);
-            let (_, mut additional_comments) = parser.assert_and_consume_operator(TokenOp::Arrow);
-            comments.append(&mut additional_comments);
-            annotation::ParenthesizedAnnotationList {
+        let parameters = if let Token(_, TokenContent::Operator(TokenOp::RightParenthesis)) =
+          parser.peek()
+        {
+          let (location, mut comments) = {
+            let (loc, comments) = parser.assert_and_consume_operator(TokenOp::RightParenthesis);
+            (peeked.0.union(&loc), comments)
+          };
+          comments.append(&mut parser.assert_and_consume_operator(TokenOp::Arrow).1);
+          annotation::ParenthesizedAnnotationList {
+            location,
+            start_associated_comments: NO_COMMENT_REFERENCE,
+            ending_associated_comments: parser.comments_store.create_comment_reference(comments),
+            annotations: Vec::with_capacity(0),
+          }
+        } else {
+          let parameters = parser.parse_comma_separated_list_with_end_token(
+            TokenOp::RightParenthesis,
+            &mut parse_annotation_with_additional_comments,
+          );
+          let (additional_loc, mut comments) =
+            parser.assert_and_consume_operator(TokenOp::RightParenthesis);
+          let location = peeked.0.union(&additional_loc);
+          if parameters.len() > 1
+            && !matches!(parser.peek().1, TokenContent::Operator(TokenOp::Arrow))
+          {
+            return annotation::T::Tuple(annotation::ParenthesizedAnnotationList {
               location,
-              start_associated_comments: NO_COMMENT_REFERENCE,
+              start_associated_comments: parser
+                .comments_store
+                .create_comment_reference(associated_comments),
               ending_associated_comments: parser.comments_store.create_comment_reference(comments),
               annotations: parameters,
-            }
-          };
+            });
+          }
+          let (_, mut additional_comments) = parser.assert_and_consume_operator(TokenOp::Arrow);
+          comments.append(&mut additional_comments);
+          annotation::ParenthesizedAnnotationList {
+            location,
+            start_associated_comments: NO_COMMENT_REFERENCE,
+            ending_associated_comments: parser.comments_store.create_comment_reference(comments),
+            annotations: parameters,
+          }
+        };
         let return_type = parse_annotation(parser);
         let location = peeked.0.union(&return_type.location());
         annotation::T::Fn(annotation::Function {
@@ -2126,6 +3232,11 @@ mod type_parser {
         }
         fix_annot_with_generic_annot(parser, &mut t.return_type);
       }
+      annotation::T::Tuple(t) => {
+        for annot in &mut t.annotations {
+          fix_annot_with_generic_annot(parser, annot);
+        }
+      }
     }
   }
 
@@ -2176,7 +3287,8 @@ mod type_parser {
 }
 
 mod utils {
-  use samlang_ast::source::{Comment, CommentReference, CommentsNode};
+  use samlang_ast::source::{Comment, CommentReference, CommentsNode, Literal};
+  use samlang_heap::PStr;
 
   pub(super) fn mod_associated_comments_with_additional_preceding_comments(
     parser: &mut super::SourceParser,
@@ -2207,8 +3319,86 @@ mod utils {
     }
   }
 
+  /// Malformed literals have already been reported by the lexer, so they are parsed as 0.
+  pub(super) fn int_literal(parser: &super::SourceParser, spelling: PStr) -> Literal {
+    let spelling_str = spelling.as_str(parser.heap);
+    let (negated, unsigned_spelling) = match spelling_str.strip_prefix('-') {
+      Some(rest) => (true, rest),
+      None => (false, spelling_str),
+    };
+    let value = super::super::lexer::int_literal_value(unsigned_spelling).unwrap_or(0);
+    let value = (if negated { -value } else { value }) as i32;
+    Literal::Int(value, if value.to_string() == spelling_str { None } else { Some(spelling) })
+  }
+
   pub(super) fn unescape_quotes(source: &str) -> String {
-    source.replace("\\\"", "\"")
+    let mut unescaped = String::with_capacity(source.len());
+    let mut chars = source.chars();
+    while let Some(c) = chars.next() {
+      if c != '\\' {
+        unescaped.push(c);
+        continue;
+      }
+      match chars.next() {
+        Some('"') => unescaped.push('"'),
+        escaped => {
+          unescaped.push('\\');
+          unescaped.extend(escaped);
+        }
+      }
+    }
+    unescaped
+  }
+
+  /// Raw and multiline strings are normalized into the escaped form of ordinary strings, while
+  /// their spelling is kept for the printer.
+  pub(super) fn string_literal(parser: &mut super::SourceParser, token: PStr) -> Literal {
+    let text = token.as_str(parser.heap);
+    if let Some(raw) = text.strip_prefix("r\"") {
+      let value = raw[..(raw.len() - 1)].replace('\\', "\\\\");
+      Literal::String(parser.heap.alloc_string(value), Some(token))
+    } else if let Some(multiline) = text.strip_prefix("\"\"\"") {
+      let value = unescape_quotes(&multiline_string_value(&multiline[..(multiline.len() - 3)]));
+      Literal::String(parser.heap.alloc_string(value), Some(token))
+    } else {
+      let value = unescape_quotes(&text[1..(text.len() - 1)]);
+      Literal::String(parser.heap.alloc_string(value), None)
+    }
+  }
+
+  /// A line break right after the opening delimiter and the line of the closing delimiter are not
+  /// part of the string. Trailing whitespace and the indentation shared by the remaining lines and
+  /// the closing delimiter are stripped, and line breaks become `\n` escapes.
+  fn multiline_string_value(content: &str) -> String {
+    fn indentation(line: &str) -> usize {
+      line.len() - line.trim_start().len()
+    }
+
+    let mut lines = content.split('\n').map(|line| line.strip_suffix('\r').unwrap_or(line));
+    let first_line = lines.next().unwrap_or_default().trim_end();
+    let mut lines = lines.collect::<Vec<_>>();
+    let closing_indentation = match lines.last() {
+      Some(last) if last.trim().is_empty() => {
+        let indentation = last.len();
+        lines.pop();
+        Some(indentation)
+      }
+      _ => None,
+    };
+    let shared_indentation = lines
+      .iter()
+      .filter(|line| !line.trim().is_empty())
+      .map(|line| indentation(line))
+      .chain(closing_indentation)
+      .min()
+      .unwrap_or(0);
+    let dedented = lines.iter().map(|line| line.get(shared_indentation..).unwrap_or("").trim_end());
+    let all_lines = if first_line.is_empty() {
+      dedented.collect::<Vec<_>>()
+    } else {
+      std::iter::once(first_line).chain(dedented).collect::<Vec<_>>()
+    };
+    all_lines.join("\\n")
   }
 }
 
@@ -2303,7 +3493,7 @@ mod tests {
     super::expression_parser::parse_statement(&mut parser);
     super::type_parser::parse_annotation(&mut parser);
     super::toplevel_parser::parse_class_member_definition(&mut parser);
-    super::toplevel_parser::parse_class_member_declaration(&mut parser);
+    super::toplevel_parser::parse_interface_member_definition(&mut parser);
     super::parse_module(parser);
   }
 
@@ -1,9 +1,13 @@
 use super::prettier::Document;
 use itertools::Itertools;
-use samlang_ast::source::{
-  ClassDefinition, ClassMemberDeclaration, CommentKind, CommentReference, CommentStore,
-  ExtendsOrImplementsNodes, Id, InterfaceDeclaration, Literal, Module, NO_COMMENT_REFERENCE,
-  Toplevel, TypeDefinition, annotation, expr, pattern,
+use samlang_ast::{
+  Location,
+  source::{
+    ClassDefinition, ClassMemberDeclaration, CommentKind, CommentReference, CommentStore,
+    ConstDefinition, DerivesClause, ExtendsOrImplementsNodes, Id, InterfaceDeclaration, Literal,
+    Module, NO_COMMENT_REFERENCE, Toplevel, TypeAliasDefinition, TypeDefinition, Visibility,
+    annotation, expr, pattern,
+  },
 };
 use samlang_heap::{Heap, ModuleReference, PStr};
 use std::{collections::HashMap, rc::Rc};
@@ -12,6 +16,11 @@ fn text_pstr(heap: &Heap, s: PStr) -> Document {
   Document::non_static_str(String::from(s.as_str(heap)))
 }
 
+/// The parser unescapes quotes in string literals, so they must be escaped again.
+fn string_content_doc(heap: &Heap, s: PStr) -> Document {
+  Document::non_static_str(s.as_str(heap).replace('"', "\\\""))
+}
+
 fn parenthesis_surrounded_doc(doc: Document) -> Document {
   Document::no_space_bracket("(", doc, ")")
 }
@@ -172,6 +181,23 @@ pub(super) fn annotation_to_doc(
         annotation_to_doc(heap, comment_store, return_type),
       ]),
     ),
+    annotation::T::Tuple(annotation::ParenthesizedAnnotationList {
+      location: _,
+      start_associated_comments,
+      ending_associated_comments,
+      annotations,
+    }) => create_opt_preceding_comment_doc(
+      heap,
+      comment_store,
+      *start_associated_comments,
+      parenthesis_surrounded_doc(comma_sep_list(
+        heap,
+        comment_store,
+        annotations,
+        *ending_associated_comments,
+        |annot| annotation_to_doc(heap, comment_store, annot),
+      )),
+    ),
   }
 }
 
@@ -233,6 +259,50 @@ fn create_doc_for_subexpression_considering_precedence_level(
   }
 }
 
+/// A pipeline is printed with each stage on its own line when it doesn't fit in one line.
+fn create_doc_for_pipe(
+  heap: &Heap,
+  comment_store: &CommentStore,
+  expression: &expr::E<()>,
+) -> Document {
+  let mut stages = Vec::new();
+  let mut head = expression;
+  while let expr::E::Binary(e) = head
+    && e.operator == expr::BinaryOperator::PIPE
+  {
+    stages.push(e);
+    head = &e.e1;
+  }
+  let head_doc = create_doc_for_subexpression_considering_precedence_level(
+    heap,
+    comment_store,
+    expression,
+    head,
+    false,
+  );
+  let mut documents = vec![];
+  for stage in stages.into_iter().rev() {
+    documents.push(Document::Line);
+    documents.push(create_opt_preceding_comment_doc(
+      heap,
+      comment_store,
+      stage.operator_preceding_comments,
+      Document::Text("|> "),
+    ));
+    documents.push(create_doc_for_subexpression_considering_precedence_level(
+      heap,
+      comment_store,
+      expression,
+      &stage.e2,
+      true,
+    ));
+  }
+  Document::group(Document::concat(vec![
+    head_doc,
+    Document::Nest(2, Rc::new(Document::concat(documents))),
+  ]))
+}
+
 fn create_doc_for_if_else(
   heap: &Heap,
   comment_store: &CommentStore,
@@ -459,8 +529,7 @@ fn create_chainable_ir_docs(
       (base, chain)
     }
     expr::E::Call(e) => {
-      let args_doc =
-        create_doc_for_parenthesized_expression_list(heap, comment_store, &e.arguments);
+      let args_doc = create_doc_for_call_arguments(heap, comment_store, &e.arguments, &e.binding);
       let (mut base, mut chain) =
         create_chainable_ir_docs(heap, comment_store, expression, &e.callee);
       if let Some((_, last_docs)) = chain.last_mut() {
@@ -507,6 +576,45 @@ fn create_doc_for_parenthesized_expression_list(
   )
 }
 
+fn create_doc_for_call_arguments(
+  heap: &Heap,
+  comment_store: &CommentStore,
+  arguments: &expr::ParenthesizedExpressionList<()>,
+  binding: &expr::ArgumentBinding<()>,
+) -> Document {
+  let positional_count = arguments.expressions.len() - binding.names.len();
+  let named_arguments = arguments
+    .expressions
+    .iter()
+    .enumerate()
+    .map(|(i, e)| (i.checked_sub(positional_count).map(|j| &binding.names[j]), e))
+    .collect_vec();
+  create_opt_preceding_comment_doc(
+    heap,
+    comment_store,
+    arguments.start_associated_comments,
+    parenthesis_surrounded_doc(comma_sep_list(
+      heap,
+      comment_store,
+      &named_arguments,
+      arguments.ending_associated_comments,
+      |(name, e)| {
+        let value_doc = create_doc(heap, comment_store, e);
+        if let Some(name) = name {
+          create_opt_preceding_comment_doc(
+            heap,
+            comment_store,
+            name.associated_comments,
+            Document::concat(vec![text_pstr(heap, name.name), Document::Text(" = "), value_doc]),
+          )
+        } else {
+          value_doc
+        }
+      },
+    )),
+  )
+}
+
 fn create_doc_for_block(
   heap: &Heap,
   comment_store: &CommentStore,
@@ -577,12 +685,31 @@ fn create_doc_without_preceding_comment(
   match expression {
     expr::E::Literal(_, Literal::Bool(false)) => Document::Text("false"),
     expr::E::Literal(_, Literal::Bool(true)) => Document::Text("true"),
-    expr::E::Literal(_, Literal::Int(i)) => Document::non_static_str(i.to_string()),
-    expr::E::Literal(_, Literal::String(s)) => {
-      Document::concat(vec![Document::Text("\""), text_pstr(heap, *s), Document::Text("\"")])
+    expr::E::Literal(_, literal @ Literal::Int(_, _)) => {
+      Document::non_static_str(literal.pretty_print(heap))
+    }
+    expr::E::Literal(_, Literal::Float(f)) => Document::non_static_str(f.pretty_print()),
+    expr::E::Literal(_, literal @ Literal::String(_, Some(_))) => {
+      Document::non_static_str(literal.pretty_print(heap))
     }
+    expr::E::Literal(_, Literal::String(s, None)) => Document::concat(vec![
+      Document::Text("\""),
+      string_content_doc(heap, *s),
+      Document::Text("\""),
+    ]),
     expr::E::LocalId(_, id) | expr::E::ClassId(_, _, id) => text_pstr(heap, id.name),
     expr::E::Tuple(_, e) => create_doc_for_parenthesized_expression_list(heap, comment_store, e),
+    expr::E::TemplateString(e) => {
+      let mut docs = vec![Document::Text("\""), string_content_doc(heap, e.parts[0])];
+      for (hole, part) in e.holes.iter().zip(&e.parts[1..]) {
+        docs.push(Document::Text("${"));
+        docs.push(create_doc(heap, comment_store, hole));
+        docs.push(Document::Text("}"));
+        docs.push(string_content_doc(heap, *part));
+      }
+      docs.push(Document::Text("\""));
+      Document::concat(docs)
+    }
     expr::E::FieldAccess(_) | expr::E::MethodAccess(_) | expr::E::Call(_) => {
       create_doc_for_dotted_chain(
         heap,
@@ -602,6 +729,9 @@ fn create_doc_without_preceding_comment(
     ),
     expr::E::IfElse(e) => create_doc_for_if_else(heap, comment_store, e),
 
+    expr::E::Binary(e) if e.operator == expr::BinaryOperator::PIPE => {
+      create_doc_for_pipe(heap, comment_store, expression)
+    }
     expr::E::Binary(e) => {
       let operator_preceding_comments_docs = if let Some(doc) = associated_comments_doc(
         heap,
@@ -637,7 +767,12 @@ fn create_doc_without_preceding_comment(
       if e.e2.precedence() == expression.precedence() {
         // For the commutative operators, we can remove parentheses.
         match e.operator {
-          expr::BinaryOperator::MINUS | expr::BinaryOperator::DIV | expr::BinaryOperator::MOD => {}
+          expr::BinaryOperator::MINUS
+          | expr::BinaryOperator::DIV
+          | expr::BinaryOperator::MOD
+          | expr::BinaryOperator::SHL
+          | expr::BinaryOperator::ASHR
+          | expr::BinaryOperator::SHR => {}
           _ => {
             return Document::concat(vec![
               create_doc_for_subexpression_considering_precedence_level(
@@ -679,6 +814,10 @@ fn create_doc_without_preceding_comment(
       let mut list = Vec::new();
       for case in &e.cases {
         list.push(matching_pattern_to_document(heap, comment_store, &case.pattern));
+        if let Some(guard) = &case.guard {
+          list.push(Document::Text(" if "));
+          list.push(create_doc(heap, comment_store, guard));
+        }
         list.push(Document::Text(" -> "));
         list.push(create_doc(heap, comment_store, &case.body));
         list.push(create_opt_preceding_comment_doc(
@@ -732,6 +871,78 @@ fn create_doc_without_preceding_comment(
       ),
     ]),
 
+    expr::E::Return(e) => Document::Concat(
+      Rc::new(Document::Text("return ")),
+      Rc::new(create_doc(heap, comment_store, &e.argument)),
+    ),
+
+    expr::E::While(e) => Document::concat(vec![
+      Document::Text("while "),
+      create_doc(heap, comment_store, &e.condition),
+      Document::Text(" "),
+      create_doc_for_block(heap, comment_store, false, &e.body),
+    ]),
+
+    expr::E::For(e) => Document::concat(vec![
+      Document::Text("for ("),
+      create_opt_preceding_comment_doc(
+        heap,
+        comment_store,
+        e.iterator.associated_comments,
+        text_pstr(heap, e.iterator.name),
+      ),
+      Document::Text(" in "),
+      create_doc(heap, comment_store, &e.start),
+      Document::Text(".."),
+      create_doc(heap, comment_store, &e.end),
+      Document::Text(") "),
+      create_doc_for_block(heap, comment_store, false, &e.body),
+    ]),
+
+    expr::E::Break(_) => Document::Text("break"),
+
+    expr::E::Assert(e) => Document::Concat(
+      Rc::new(Document::Text("assert")),
+      Rc::new(create_doc_for_parenthesized_expression_list(heap, comment_store, &e.arguments)),
+    ),
+
+    expr::E::Try(e) => Document::Concat(
+      Rc::new(create_doc_for_subexpression_considering_precedence_level(
+        heap,
+        comment_store,
+        expression,
+        &e.argument,
+        false,
+      )),
+      Rc::new(Document::Text("?")),
+    ),
+
+    expr::E::StructUpdate(e) => {
+      let mut elements = vec![Document::Concat(
+        Rc::new(Document::Text("...")),
+        Rc::new(create_doc(heap, comment_store, &e.base)),
+      )];
+      for field in &e.fields {
+        elements.push(create_opt_preceding_comment_doc(
+          heap,
+          comment_store,
+          field.field_name.associated_comments,
+          Document::concat(vec![
+            text_pstr(heap, field.field_name.name),
+            Document::Text(": "),
+            create_doc(heap, comment_store, &field.value),
+          ]),
+        ));
+      }
+      braces_surrounded_doc(comma_sep_list(
+        heap,
+        comment_store,
+        &elements,
+        e.ending_associated_comments,
+        |d| d.clone(),
+      ))
+    }
+
     expr::E::Block(e) => create_doc_for_block(heap, comment_store, false, e),
   }
 }
@@ -844,6 +1055,14 @@ fn matching_pattern_to_document(
         Document::Text("_"),
       )
     }
+    pattern::MatchingPattern::Literal { location: _, associated_comments, literal } => {
+      create_opt_preceding_comment_doc(
+        heap,
+        comment_store,
+        *associated_comments,
+        Document::non_static_str(literal.pretty_print(heap)),
+      )
+    }
     pattern::MatchingPattern::Or { patterns, .. } => {
       let mut pattern_docs: Vec<Document> = Vec::with_capacity(patterns.len() * 2 - 1);
       for (i, p) in patterns.iter().enumerate() {
@@ -866,9 +1085,16 @@ pub(super) fn statement_to_document(
     expr::Statement::Declaration(decl_stmt) => {
       declaration_statement_to_document(heap, comment_store, decl_stmt)
     }
-    expr::Statement::Expression(expr) => {
-      Document::concat(vec![create_doc(heap, comment_store, expr), Document::Text(";")])
+    expr::Statement::Assignment(assign_stmt) => {
+      assignment_statement_to_document(heap, comment_store, assign_stmt)
+    }
+    expr::Statement::Function(function_stmt) => {
+      local_function_statement_to_document(heap, comment_store, function_stmt)
     }
+    expr::Statement::Expression(expr) => match expr.as_ref() {
+      expr::E::While(_) | expr::E::For(_) => create_doc(heap, comment_store, expr),
+      _ => Document::concat(vec![create_doc(heap, comment_store, expr), Document::Text(";")]),
+    },
   }
 }
 
@@ -889,7 +1115,7 @@ pub(super) fn declaration_statement_to_document(
     )
     .unwrap_or(Document::Nil),
   );
-  segments.push(Document::Text("let "));
+  segments.push(Document::Text(if stmt.is_mutable { "var " } else { "let " }));
   segments.push(pattern_doc);
   segments.push(if let Some(annot) = &stmt.annotation {
     Document::Concat(
@@ -901,10 +1127,95 @@ pub(super) fn declaration_statement_to_document(
   });
   segments.push(Document::Text(" = "));
   segments.push(create_doc(heap, comment_store, &stmt.assigned_expression));
+  if let Some(else_block) = &stmt.else_block {
+    segments.push(Document::Text(" else "));
+    if let Some(comments) = associated_comments_doc(
+      heap,
+      comment_store,
+      vec![else_block.common.associated_comments],
+      DocumentGrouping::Flattened,
+      false,
+    ) {
+      segments.push(comments);
+      segments.push(Document::Text(" "));
+    }
+    segments.push(create_doc_for_block(heap, comment_store, false, else_block));
+  }
   segments.push(Document::Text(";"));
   Document::concat(segments)
 }
 
+fn assignment_statement_to_document(
+  heap: &Heap,
+  comment_store: &CommentStore,
+  stmt: &expr::AssignmentStatement<()>,
+) -> Document {
+  Document::concat(vec![
+    associated_comments_doc(
+      heap,
+      comment_store,
+      vec![stmt.name.associated_comments, stmt.associated_comments],
+      DocumentGrouping::Grouped,
+      true,
+    )
+    .unwrap_or(Document::Nil),
+    text_pstr(heap, stmt.name.name),
+    Document::Text(" = "),
+    create_doc(heap, comment_store, &stmt.assigned_expression),
+    Document::Text(";"),
+  ])
+}
+
+fn local_function_statement_to_document(
+  heap: &Heap,
+  comment_store: &CommentStore,
+  stmt: &expr::LocalFunctionStatement<()>,
+) -> Document {
+  Document::concat(vec![
+    associated_comments_doc(
+      heap,
+      comment_store,
+      vec![stmt.associated_comments, stmt.name.associated_comments],
+      DocumentGrouping::Grouped,
+      true,
+    )
+    .unwrap_or(Document::Nil),
+    Document::Text("function "),
+    text_pstr(heap, stmt.name.name),
+    create_opt_preceding_comment_doc(
+      heap,
+      comment_store,
+      stmt.lambda.common.associated_comments,
+      parenthesis_surrounded_doc(comma_sep_list(
+        heap,
+        comment_store,
+        &stmt.lambda.parameters.parameters,
+        stmt.lambda.parameters.ending_associated_comments,
+        |param| {
+          create_opt_preceding_comment_doc(
+            heap,
+            comment_store,
+            param.name.associated_comments,
+            Document::concat(vec![
+              text_pstr(heap, param.name.name),
+              Document::Text(": "),
+              param
+                .annotation
+                .as_ref()
+                .map_or(Document::Nil, |annot| annotation_to_doc(heap, comment_store, annot)),
+            ]),
+          )
+        },
+      )),
+    ),
+    Document::Text(": "),
+    annotation_to_doc(heap, comment_store, &stmt.return_annotation),
+    Document::Text(" = "),
+    create_doc(heap, comment_store, &stmt.lambda.body),
+    Document::Text(";"),
+  ])
+}
+
 fn type_parameters_to_doc(
   heap: &Heap,
   comment_store: &CommentStore,
@@ -948,6 +1259,15 @@ pub(super) fn expression_to_document(
   create_doc(heap, comment_store, expression)
 }
 
+fn visibility_to_doc(visibility: Visibility) -> Document {
+  match visibility {
+    Visibility::Public => Document::Nil,
+    Visibility::Private => Document::Text("private "),
+    Visibility::Protected => Document::Text("protected "),
+    Visibility::Internal => Document::Text("internal "),
+  }
+}
+
 fn create_doc_for_interface_member(
   heap: &Heap,
   comment_store: &CommentStore,
@@ -978,7 +1298,7 @@ fn create_doc_for_interface_member(
       true,
     )
     .unwrap_or(Document::Nil),
-    if member.is_public { Document::Nil } else { Document::Text("private ") },
+    visibility_to_doc(member.visibility),
     Document::Text(if member.is_method { "method " } else { "function " }),
     type_parameters_to_doc(heap, comment_store, true, member.type_parameters.as_ref()),
     (text_pstr(heap, member.name.name)),
@@ -1000,6 +1320,14 @@ fn create_doc_for_interface_member(
               (text_pstr(heap, param.name.name)),
               Document::Text(": "),
               annotation_to_doc(heap, comment_store, &param.annotation),
+              if let Some(default_value) = &param.default_value {
+                Document::concat(vec![
+                  Document::Text(" = "),
+                  create_doc(heap, comment_store, default_value),
+                ])
+              } else {
+                Document::Nil
+              },
             ]),
           )
         },
@@ -1012,18 +1340,27 @@ fn create_doc_for_interface_member(
   ]
 }
 
+/// Whether a node is synthesized by the parser for an entry of the derives clause.
+fn is_derived(derives: Option<&DerivesClause>, loc: Location) -> bool {
+  derives.is_some_and(|clause| clause.names.iter().any(|name| name.loc == loc))
+}
+
 fn extends_or_implements_node_to_doc(
   heap: &Heap,
   comment_store: &CommentStore,
   nodes: Option<&ExtendsOrImplementsNodes>,
+  derives: Option<&DerivesClause>,
 ) -> Document {
   if let Some(nodes) = nodes {
     let mut expanded_ids = vec![Document::Line];
-    for id in &nodes.nodes {
+    for id in nodes.nodes.iter().filter(|id| !is_derived(derives, id.location)) {
       expanded_ids.push(id_annot_to_doc(heap, comment_store, id));
       expanded_ids.push(Document::Text(","));
       expanded_ids.push(Document::Line);
     }
+    if expanded_ids.len() == 1 {
+      return Document::Nil;
+    }
     expanded_ids.pop();
     expanded_ids.pop();
     let expanded = Document::Concat(
@@ -1041,10 +1378,44 @@ fn extends_or_implements_node_to_doc(
   }
 }
 
+fn derives_clause_to_doc(
+  heap: &Heap,
+  comment_store: &CommentStore,
+  derives: Option<&DerivesClause>,
+) -> Document {
+  if let Some(clause) = derives {
+    let mut expanded_names = vec![Document::Line];
+    for name in &clause.names {
+      expanded_names.push(create_opt_preceding_comment_doc(
+        heap,
+        comment_store,
+        name.associated_comments,
+        text_pstr(heap, name.name),
+      ));
+      expanded_names.push(Document::Text(","));
+      expanded_names.push(Document::Line);
+    }
+    expanded_names.pop();
+    expanded_names.pop();
+    let expanded = Document::Concat(
+      Rc::new(Document::Text(" derives")),
+      Rc::new(Document::Nest(2, Rc::new(Document::concat(expanded_names)))),
+    );
+    create_opt_preceding_comment_doc(
+      heap,
+      comment_store,
+      clause.associated_comments,
+      Document::group(expanded),
+    )
+  } else {
+    Document::Nil
+  }
+}
+
 fn interface_to_doc(
   heap: &Heap,
   comment_store: &CommentStore,
-  interface: &InterfaceDeclaration,
+  interface: &InterfaceDeclaration<()>,
 ) -> Vec<Document> {
   let mut documents = vec![
     associated_comments_doc(
@@ -1055,13 +1426,15 @@ fn interface_to_doc(
       true,
     )
     .unwrap_or(Document::Nil),
-    Document::Text(if interface.private { "private interface " } else { "interface " }),
+    visibility_to_doc(interface.visibility),
+    Document::Text("interface "),
     (text_pstr(heap, interface.name.name)),
     type_parameters_to_doc(heap, comment_store, false, interface.type_parameters.as_ref()),
     extends_or_implements_node_to_doc(
       heap,
       comment_store,
       interface.extends_or_implements_nodes.as_ref(),
+      None,
     ),
   ];
 
@@ -1072,7 +1445,12 @@ fn interface_to_doc(
       Rc::new(Document::concat(
         vec![Document::Line]
           .into_iter()
-          .chain(create_doc_for_interface_member(heap, comment_store, member, None))
+          .chain(create_doc_for_interface_member(
+            heap,
+            comment_store,
+            &member.decl,
+            member.default_body.as_ref(),
+          ))
           .collect(),
       )),
     ));
@@ -1107,7 +1485,8 @@ fn class_to_doc(
       true,
     )
     .unwrap_or(Document::Nil),
-    Document::Text(if class.private { "private class " } else { "class " }),
+    visibility_to_doc(class.visibility),
+    Document::Text("class "),
     text_pstr(heap, class.name.name),
     type_parameters_to_doc(heap, comment_store, false, class.type_parameters.as_ref()),
     match class.type_definition.as_ref() {
@@ -1178,11 +1557,15 @@ fn class_to_doc(
       heap,
       comment_store,
       class.extends_or_implements_nodes.as_ref(),
+      class.derives.as_ref(),
     ),
+    derives_clause_to_doc(heap, comment_store, class.derives.as_ref()),
   ];
 
   documents.push(Document::Text(" {"));
-  for member in &class.members.members {
+  for member in
+    class.members.members.iter().filter(|m| !is_derived(class.derives.as_ref(), m.decl.name.loc))
+  {
     documents.push(Document::Nest(
       2,
       Rc::new(Document::concat(
@@ -1214,6 +1597,61 @@ fn class_to_doc(
   documents
 }
 
+fn type_alias_to_doc(
+  heap: &Heap,
+  comment_store: &CommentStore,
+  type_alias: &TypeAliasDefinition,
+) -> Vec<Document> {
+  vec![
+    associated_comments_doc(
+      heap,
+      comment_store,
+      vec![type_alias.associated_comments],
+      DocumentGrouping::Grouped,
+      true,
+    )
+    .unwrap_or(Document::Nil),
+    visibility_to_doc(type_alias.visibility),
+    Document::Text("type "),
+    text_pstr(heap, type_alias.name.name),
+    type_parameters_to_doc(heap, comment_store, false, type_alias.type_parameters.as_ref()),
+    Document::Text(" = "),
+    annotation_to_doc(heap, comment_store, &type_alias.annotation),
+    Document::Text(";"),
+  ]
+}
+
+fn const_to_doc(
+  heap: &Heap,
+  comment_store: &CommentStore,
+  const_def: &ConstDefinition<()>,
+) -> Vec<Document> {
+  vec![
+    associated_comments_doc(
+      heap,
+      comment_store,
+      vec![const_def.associated_comments],
+      DocumentGrouping::Grouped,
+      true,
+    )
+    .unwrap_or(Document::Nil),
+    visibility_to_doc(const_def.visibility),
+    Document::Text("const "),
+    text_pstr(heap, const_def.name.name),
+    Document::Text(": "),
+    annotation_to_doc(heap, comment_store, &const_def.annotation),
+    Document::Text(" ="),
+    Document::group(Document::Nest(
+      2,
+      Rc::new(Document::Concat(
+        Rc::new(Document::Line),
+        Rc::new(create_doc(heap, comment_store, &const_def.value)),
+      )),
+    )),
+    Document::Text(";"),
+  ]
+}
+
 pub(super) fn import_to_document(
   heap: &Heap,
   comment_store: &CommentStore,
@@ -1254,6 +1692,8 @@ pub(super) fn toplevel_to_document(
   Document::concat(match toplevel {
     Toplevel::Interface(interface) => interface_to_doc(heap, comment_store, interface),
     Toplevel::Class(class) => class_to_doc(heap, comment_store, class),
+    Toplevel::TypeAlias(type_alias) => type_alias_to_doc(heap, comment_store, type_alias),
+    Toplevel::Const(const_def) => const_to_doc(heap, comment_store, const_def),
   })
 }
 
@@ -1359,7 +1799,10 @@ mod tests {
     for n in &m.toplevels {
       pretty_print_toplevel(&heap, 40, &m.comment_store, n);
       match n {
-        Toplevel::Interface(_) => {}
+        Toplevel::Interface(_) | Toplevel::TypeAlias(_) => {}
+        Toplevel::Const(c) => {
+          pretty_print_expression(&heap, 40, &m.comment_store, &c.value);
+        }
         Toplevel::Class(c) => {
           for member in &c.members.members {
             for p in member.decl.parameters.parameters.as_ref().iter() {
@@ -1389,6 +1832,32 @@ mod tests {
   #[test]
   fn expression_printer_tests() {
     assert_reprint_expr("1", "1");
+    assert_reprint_expr("0xFF_ff + 0B1010 * 1_000", "0xFF_ff + 0B1010 * 1_000");
+    assert_reprint_expr("-2147483648", "-2147483648");
+    assert_reprint_expr(
+      "match (a) { 0x1F -> 1, -0b1 -> 2, _ -> 3 }",
+      r#"match a {
+  0x1F -> 1,
+  -0b1 -> 2,
+  _ -> 3,
+}"#,
+    );
+    assert_reprint_expr(r#""\u{1F600}\"\t""#, r#""\u{1F600}\"\t""#);
+    assert_reprint_expr(r#"r"C:\a\${b}""#, r#"r"C:\a\${b}""#);
+    assert_reprint_expr(
+      "{ let s = \"\"\"\n    hello \"world\"\n      !\n    \"\"\"; s }",
+      "{\n  let s = \"\"\"\n    hello \"world\"\n      !\n    \"\"\";\n  s\n}",
+    );
+    assert_reprint_expr(
+      r#"match (a) { r"\d" -> 1, "\"q\"" -> 2, _ -> 3 }"#,
+      r#"match a {
+  r"\d" -> 1,
+  "\"q\"" -> 2,
+  _ -> 3,
+}"#,
+    );
+    assert_reprint_expr("1.50", "1.5");
+    assert_reprint_expr("2.0e3", "2000.0");
     assert_reprint_expr("/* dsfsd */ 1", "/* dsfsd */ 1");
     assert_reprint_expr(
       "/** long long long long long long long long long long */ 1",
@@ -1544,6 +2013,7 @@ ClassName /* b */ /* c */.classMember<
     );
 
     assert_reprint_expr("1 + 1", "1 + 1");
+    assert_reprint_expr("1.0 + 2.5 * 3.0", "1.0 + 2.5 * 3.0");
     assert_reprint_expr("/* a */ 1 /* plus */ + /* b */ 1", "/* a */ 1 /* plus */ + /* b */ 1");
     assert_reprint_expr("1 + 1 * 1", "1 + 1 * 1");
     assert_reprint_expr("(1 + 1) * 1", "(1 + 1) * 1");
@@ -1556,6 +2026,13 @@ ClassName /* b */ /* c */.classMember<
     assert_reprint_expr("true && false && true", "true && false && true");
     assert_reprint_expr("true || false || true", "true || false || true");
     assert_reprint_expr(r#""dev" :: "meggo" :: "vibez""#, r#""dev" :: "meggo" :: "vibez""#);
+    assert_reprint_expr("~(a&b)", "~(a & b)");
+    assert_reprint_expr("a | b ^ c & d", "a | b ^ c & d");
+    assert_reprint_expr("((a | b) ^ c) & d", "((a | b) ^ c) & d");
+    assert_reprint_expr("a | (b | c)", "a | b | c");
+    assert_reprint_expr("1 << (2 << 3)", "1 << (2 << 3)");
+    assert_reprint_expr("(1 >> 2) >>> 3 + 4", "1 >> 2 >>> 3 + 4");
+    assert_reprint_expr("(a & 1) == 0", "a & 1 == 0");
 
     assert_reprint_expr("if (b) {a} else {c}", "if b { a } else { c }");
     assert_reprint_expr(
@@ -1622,6 +2099,78 @@ ClassName /* b */ /* c */.classMember<
 }"#,
     );
 
+    assert_reprint_expr(
+      r#"match (v) { 0 -> 1, -1 | /* c */ "ab" -> 2, (true, false) -> 3, _ -> 4 }"#,
+      r#"match v {
+  0 -> 1,
+  -1 | /* c */ "ab" -> 2,
+  (true, false) -> 3,
+  _ -> 4,
+}"#,
+    );
+
+    assert_reprint_expr(
+      "match (v) { Some(x) if /* c */ x > 0 -> x, A | B if cond -> 1, _ -> 0 }",
+      r#"match v {
+  Some(x) if /* c */ x > 0 -> x,
+  A | B if cond -> 1,
+  _ -> 0,
+}"#,
+    );
+
+    assert_reprint_expr("return 1", "return 1");
+    assert_reprint_expr("return /* a */ 1 + 1", "return /* a */ 1 + 1");
+    assert_reprint_expr("1 + (return 1)", "1 + (return 1)");
+    assert_reprint_expr(
+      "{ if (a) { return 1 } else {}; 2 }",
+      r#"{
+  if a { return 1 } else {  };
+  2
+}"#,
+    );
+
+    assert_reprint_expr(
+      "{ var a: int = 1; /* c */ a = a + 1; a }",
+      r#"{
+  var a: int = 1;
+  /* c */
+  a = a + 1;
+  a
+}"#,
+    );
+
+    assert_reprint_expr(
+      "{ var i = 0; while (i < 10) { if i == 5 { break } else {}; i = i + 1; } }",
+      r#"{
+  var i = 0;
+  while i < 10 {
+    if i == 5 { break } else {  };
+    i = i + 1;
+  }
+}"#,
+    );
+    assert_reprint_expr(
+      "{ for (/* i */ i in 0..n+1) { foo(i); } while true {}; 1 }",
+      r#"{
+  for (/* i */ i in 0..n + 1) {
+    foo(i);
+  }
+  while true {  }
+  1
+}"#,
+    );
+    assert_reprint_expr("assert(a)", "assert(a)");
+    assert_reprint_expr("assert ( a>1 , \"msg\" )", "assert(a > 1, \"msg\")");
+    assert_reprint_expr("/* a */ assert(/* b */ true)", "/* a */ assert(/* b */ true)");
+    assert_reprint_expr("foo() ?", "foo()?");
+    assert_reprint_expr("a.b?.c(d)?", "a.b?.c(d)?");
+    assert_reprint_expr("(-a)? + (a + b)?", "(-a)? + (a + b)?");
+    assert_reprint_expr(r#""x=${ x }, name=${name}""#, r#""x=${x}, name=${name}""#);
+    assert_reprint_expr(r#""${a+b}${"in ${c}"}\"${ {d} }""#, r#""${a + b}${"in ${c}"}\"${{ d }}""#);
+    assert_reprint_expr(r#""\${a} is ${ a }""#, r#""\${a} is ${a}""#);
+    assert_reprint_expr(r#""costs \$3, \${literal}""#, r#""costs \$3, \${literal}""#);
+    assert_reprint_expr(r#""say \"hi\"""#, r#""say \"hi\"""#);
+
     assert_reprint_expr("() -> 1", "() -> 1");
     assert_reprint_expr("(a: int) -> 1", "(a: int) -> 1");
     assert_reprint_expr("(a) -> 1", "(a) -> 1");
@@ -1648,6 +2197,23 @@ ClassName /* b */ /* c */.classMember<
     // foo
   ) -> int
 ) -> 1 + 1"#,
+    );
+    assert_reprint_expr("(a: (int,Str)) -> 1", "(a: (int, Str)) -> 1");
+    assert_reprint_expr(
+      "(a: (/* a */ int, (bool, Str) /* b */)) -> a",
+      r#"(
+  a: (/* a */ int, (bool, Str), /* b */)
+) -> a"#,
+    );
+    assert_reprint_expr(
+      "(a: (int, AVeryVeryVeryLongTypeName, Foo<int>)) -> 1",
+      r#"(
+  a: (
+    int,
+    AVeryVeryVeryLongTypeName,
+    Foo<int>
+  )
+) -> 1"#,
     );
     assert_reprint_expr("(() -> 1)()", "(() -> 1)()");
 
@@ -1746,6 +2312,70 @@ ClassName /* b */ /* c */.classMember<
   Process.println("world");
 }"#,
     );
+
+    assert_reprint_expr(
+      "{ let Some(a) = b /* c */ else /* d */ { return 1 }; let (Foo(x), y) = c else { Process.panic(\"unreachable\") }; a }",
+      r#"{
+  let Some(a) = b else /* c */ /* d */ {
+    return 1
+  };
+  let (Foo(x), y) = c else {
+    Process.panic("unreachable")
+  };
+  a
+}"#,
+    );
+
+    assert_reprint_expr(
+      "{ /* a */ function fact(n: int, /* b */ acc: int): int = if n <= 1 { acc } else { fact(n - 1, acc * n) }; function noop(): unit = {}; fact(3, 1) }",
+      r#"{
+  /* a */
+  function fact(
+    n: int,
+    /* b */ acc: int
+  ): int = if n <= 1 {
+    acc
+  } else {
+    fact(n - 1, acc * n)
+  };
+  function noop(): unit = {  };
+  fact(3, 1)
+}"#,
+    );
+
+    assert_reprint_expr("{ ...p }", "{ ...p }");
+    assert_reprint_expr("{ ...p, x: 1, }", "{ ...p, x: 1 }");
+    assert_reprint_expr(
+      "{ ...Foo.init(1, 2), /* c */ x: aVeryLongName + anotherVeryLongName, y: 2 /* d */ }",
+      r#"{
+  ...Foo.init(1, 2),
+  /* c */
+  x: aVeryLongName + anotherVeryLongName,
+  y: 2,
+  /* d */
+}"#,
+    );
+
+    assert_reprint_expr("Foo.bar(1, b = 2, c = (x) -> x,)", "Foo.bar(1, b = 2, c = (x) -> x)");
+    assert_reprint_expr(
+      "Foo.bar(/* a */ aVeryLongArgumentName, second = anotherVeryLongName, third = 3)",
+      r#"Foo.bar(
+  /* a */ aVeryLongArgumentName,
+  second = anotherVeryLongName,
+  third = 3
+)"#,
+    );
+
+    assert_reprint_expr("a |> f |> (x) -> x", "a |> f |> ((x) -> x)");
+    assert_reprint_expr("(a |> f) + 1", "(a |> f) + 1");
+    assert_reprint_expr("a |> (b |> f)", "a |> (b |> f)");
+    assert_reprint_expr(
+      "List.range(0, 100) |> List.map((x) -> x * 2) /* keep */ |> List.filter(isBig) |> List.sum",
+      r#"List.range(0, 100)
+  |> List.map((x) -> x * 2)
+  /* keep */ |> List.filter(isBig)
+  |> List.sum"#,
+    );
   }
 
   #[test]
@@ -1789,6 +2419,124 @@ class Main {
 }"#,
     );
 
+    assert_reprint_module(
+      r#"
+type Names = List<Str>;
+/** docs */
+private type Fn<T> = (T, Names) -> unit;
+type LongLongLongLongName = (int, int, int, int) -> int;
+"#,
+      r#"
+type Names = List<Str>;
+
+/** docs */
+private type Fn<T> = (T, Names) -> unit;
+
+type LongLongLongLongName = (
+  int,
+  int,
+  int,
+  int
+) -> int;"#,
+    );
+
+    assert_reprint_module(
+      r#"
+internal const Limit: int = 3;
+internal interface Shape { protected method area(): int internal function create(): Shape }
+public class Square(val size: int) : Shape { protected method area(): int = 1 internal function create(): Shape = Square.init(1) public method name(): Str = "" }
+"#,
+      r#"
+internal const Limit: int = 3;
+
+internal interface Shape {
+  protected method area(): int
+
+  internal function create(): Shape
+}
+
+class Square(val size: int) : Shape {
+  protected method area(): int = 1
+
+  internal function create(): Shape =
+    Square.init(1)
+
+  method name(): Str = ""
+}"#,
+    );
+
+    assert_reprint_module(
+      r#"
+interface Shape { method area(): int method describe(): Str = "Area: " :: Str.fromInt(this.area()) }
+"#,
+      r#"
+interface Shape {
+  method area(): int
+
+  method describe(): Str =
+    "Area: " :: Str.fromInt(this.area())
+}"#,
+    );
+
+    assert_reprint_module(
+      r#"
+class Point(val x: int, val y: int) derives equals,compare {
+  method norm(): int = this.x * this.x + this.y * this.y
+}
+class Pair(val a: Point): Foo derives equals, /* c */ toString {}
+class VeryLongClassNameForWrapping(val a: int) derives equals, compare, toString {}
+"#,
+      r#"
+class Point(
+  val x: int,
+  val y: int
+) derives equals, compare {
+  method norm(): int =
+    this.x * this.x + this.y * this.y
+}
+
+class Pair(val a: Point) : Foo derives
+  equals,
+  /* c */ toString {}
+
+class VeryLongClassNameForWrapping(
+  val a: int
+) derives equals, compare, toString {}"#,
+    );
+
+    assert_reprint_module(
+      r#"
+class Defaults {
+  function f(a: int, b: int = 1+2, c: Str = /* c */ "c"): int = Defaults.f(c = "", a = 1)
+}
+"#,
+      r#"
+class Defaults {
+  function f(
+    a: int,
+    b: int = 1 + 2,
+    c: Str = /* c */ "c"
+  ): int = Defaults.f(c = "", a = 1)
+}"#,
+    );
+
+    assert_reprint_module(
+      r#"
+const Max: int = 1+2*3;
+/** docs */
+private const Greeting: Str = "Hello " :: "World" :: "!";
+const Origin: Point = Point.init(0, 0);
+"#,
+      r#"
+const Max: int = 1 + 2 * 3;
+
+/** docs */
+private const Greeting: Str =
+  "Hello " :: "World" :: "!";
+
+const Origin: Point = Point.init(0, 0);"#,
+    );
+
     assert_reprint_module(
       r#"
 import {Foo} from Foo.Baz
@@ -184,6 +184,45 @@ class Test2(val a: int) {
     assert!(query::all_references(&state, &test2_mod_ref, Position(4, 100)).is_empty());
   }
 
+  #[test]
+  fn query_tuple_test() {
+    let mut heap = Heap::new();
+    let test_mod_ref = heap.alloc_module_reference_from_string_vec(vec!["Test".to_string()]);
+    let state = server_state::ServerState::new(
+      heap,
+      false,
+      HashMap::from([(
+        test_mod_ref,
+        r#"class Test1 {
+  function test(p: (int, Str)): Str = {
+    let (a, b) = p;
+    let t = (a, b, (true, 1));
+    t.e1
+  }
+}
+"#
+        .to_string(),
+      )]),
+    );
+
+    let hover_at = |position| {
+      query::hover(&state, &test_mod_ref, position)
+        .unwrap()
+        .contents
+        .iter()
+        .map(query::TypeQueryContent::to_string)
+        .join("\n")
+    };
+    // At p in p: (int, Str)
+    assert_eq!("(int, Str) [lang=samlang]", hover_at(Position(1, 16)));
+    // At b in let (a, b)
+    assert_eq!("Str [lang=samlang]", hover_at(Position(2, 12)));
+    // At t in let t
+    assert_eq!("(int, Str, (bool, int)) [lang=samlang]", hover_at(Position(3, 8)));
+    // At e1 in t.e1
+    assert_eq!("Str [lang=samlang]", hover_at(Position(4, 6)));
+  }
+
   #[test]
   fn query_def_loc_test_1() {
     let mut heap = Heap::new();
@@ -535,7 +574,9 @@ class Main {
   function test4(): int = Func.a(0,true,"")
   function test5(): int = 1(0)
   function test6(): int = Func.a(0)
+  function test7(): int = Defaults.b(1, label = "l")
 }
+class Defaults { function b(x: int, y: int = 2, label: Str = "none"): int = 1 }
 "#
         .to_string(),
       )]),
@@ -550,34 +591,39 @@ class Main {
 
     // Mid of () in test1
     assert_eq!(
-      "(a0: int, a1: bool, a2: Str) -> int [params=a0: int,a1: bool,a2: Str, active=0]",
+      "(x: int, y: bool, z: Str) -> int [params=x: int,y: bool,z: Str, active=0]",
       query::signature_help(&state, &mod_ref, Position(3, 33)).unwrap().to_string()
     );
     // After , in test2
     assert_eq!(
-      "(a0: int, a1: bool, a2: Str) -> int [params=a0: int,a1: bool,a2: Str, active=1]",
+      "(x: int, y: bool, z: Str) -> int [params=x: int,y: bool,z: Str, active=1]",
       query::signature_help(&state, &mod_ref, Position(4, 35)).unwrap().to_string()
     );
     // At true in test2
     assert_eq!(
-      "(a0: int, a1: bool, a2: Str) -> int [params=a0: int,a1: bool,a2: Str, active=1]",
+      "(x: int, y: bool, z: Str) -> int [params=x: int,y: bool,z: Str, active=1]",
       query::signature_help(&state, &mod_ref, Position(5, 35)).unwrap().to_string()
     );
     // At final , in test2
     assert_eq!(
-      "(a0: int, a1: bool, a2: Str) -> int [params=a0: int,a1: bool,a2: Str, active=2]",
+      "(x: int, y: bool, z: Str) -> int [params=x: int,y: bool,z: Str, active=2]",
       query::signature_help(&state, &mod_ref, Position(5, 40)).unwrap().to_string()
     );
     // At true in test3
     assert_eq!(
-      "(a0: int, a1: bool, a2: Str) -> int [params=a0: int,a1: bool,a2: Str, active=1]",
+      "(x: int, y: bool, z: Str) -> int [params=x: int,y: bool,z: Str, active=1]",
       query::signature_help(&state, &mod_ref, Position(6, 35)).unwrap().to_string()
     );
     // At "" in test3
     assert_eq!(
-      "(a0: int, a1: bool, a2: Str) -> int [params=a0: int,a1: bool,a2: Str, active=2]",
+      "(x: int, y: bool, z: Str) -> int [params=x: int,y: bool,z: Str, active=2]",
       query::signature_help(&state, &mod_ref, Position(6, 40)).unwrap().to_string()
     );
+    // At "l" in test7
+    assert_eq!(
+      r#"(x: int, y: int = 2, label: Str = "none") -> int [params=x: int,y: int = 2,label: Str = "none", active=2]"#,
+      query::signature_help(&state, &mod_ref, Position(9, 48)).unwrap().to_string()
+    );
   }
 
   #[test]
@@ -841,6 +887,7 @@ interface Interface {}
     assert!(completion::auto_complete(&state, &test_mod_ref, Position(14, 22)).is_empty());
     assert_eq!(
       r#"Developer [kind=Class, detail=class Developer]
+Float [kind=Class, detail=class Float]
 Interface [kind=Interface, detail=interface Interface]
 List [kind=Class, detail=class List]
 Main [kind=Class, detail=class Main]
@@ -985,10 +1032,10 @@ class Main {
 
   #[test]
   fn autocomplete_test_6() {
-    let heap = &mut Heap::new();
+    let mut heap = Heap::new();
     let mod_ref = heap.alloc_module_reference(vec![PStr::UPPER_A]);
     let state = server_state::ServerState::new(
-      Heap::new(),
+      heap,
       false,
       HashMap::from([
         (
@@ -1001,6 +1048,8 @@ class Main {
 }
 class Developer {
   private method f(): unit = {}
+  protected method p(): unit = {}
+  internal method i(): unit = {}
   method b(): unit = {}
 }
 "#
@@ -1010,7 +1059,7 @@ class Developer {
       ]),
     );
     assert_eq!(
-      "b [kind=Method, detail=b(): unit]",
+      "b [kind=Method, detail=b(): unit]\ni [kind=Method, detail=i(): unit]",
       completion::auto_complete(&state, &mod_ref, Position(2, 46))
         .iter()
         .map(completion::AutoCompletionItem::to_string)
@@ -1019,6 +1068,7 @@ class Developer {
     assert_eq!(
       r#"
 Developer [kind=Class, detail=class Developer]
+Float [kind=Class, detail=class Float]
 Main [kind=Class, detail=class Main]
 Other [kind=Class, detail=class Other]
 Process [kind=Class, detail=class Process]
@@ -1051,6 +1101,60 @@ println [kind=Function, detail=println(a0: Str): unit]
     );
   }
 
+  #[test]
+  fn autocomplete_visibility_test() {
+    let mut heap = Heap::new();
+    let lib_mod_ref = heap.alloc_module_reference_from_string_vec(vec![
+      "a".to_string(),
+      "b".to_string(),
+      "Lib".to_string(),
+    ]);
+    let main_mod_ref =
+      heap.alloc_module_reference_from_string_vec(vec!["c".to_string(), "Main".to_string()]);
+    let state = server_state::ServerState::new(
+      heap,
+      false,
+      HashMap::from([
+        (
+          lib_mod_ref,
+          r#"
+class Lib(val x: int) {
+  internal method i(): unit = {}
+  method p(): unit = {}
+}
+internal class Helper {}
+"#
+          .to_string(),
+        ),
+        (
+          main_mod_ref,
+          r#"
+import { Lib } from a.b.Lib
+class Main {
+  function main(l: Lib): unit = l.
+  function other(): Lib = Lib.init(1)
+}
+"#
+          .to_string(),
+        ),
+      ]),
+    );
+    assert_eq!(
+      "p [kind=Method, detail=p(): unit]",
+      completion::auto_complete(&state, &main_mod_ref, Position(3, 34))
+        .iter()
+        .map(completion::AutoCompletionItem::to_string)
+        .join("\n")
+    );
+    assert_eq!(
+      "Float,Lib,Main,Process,Str,Vec",
+      completion::auto_complete(&state, &main_mod_ref, Position(4, 28))
+        .iter()
+        .map(|it| it.label.as_str())
+        .join(",")
+    );
+  }
+
   #[test]
   fn autocomplete_test_7() {
     let mod_ref = ModuleReference::DUMMY;
@@ -1110,4 +1214,177 @@ foo [kind=Variable, detail=Str]"#,
         .join("\n")
     );
   }
+
+  #[test]
+  fn type_alias_query_and_rename_tests() {
+    let mut heap = Heap::new();
+    let test_mod_ref = heap.alloc_module_reference_from_string_vec(vec!["Test".to_string()]);
+    let test2_mod_ref = heap.alloc_module_reference_from_string_vec(vec!["Test2".to_string()]);
+    let mut state = server_state::ServerState::new(
+      heap,
+      false,
+      HashMap::from([
+        (
+          test_mod_ref,
+          r#"type Callback<T> = (T) -> unit;
+private type Local = Callback<int>;
+
+class Main {
+  function run(f: Callback<int>, g: Local): unit = f(1)
+}
+"#
+          .to_string(),
+        ),
+        (
+          test2_mod_ref,
+          r#"import {Callback} from Test
+class Other { function run(f: Callback<bool>): unit = f(true) }
+"#
+          .to_string(),
+        ),
+      ]),
+    );
+
+    assert_eq!(
+      "type Callback<T> = (T) -> unit [lang=samlang]",
+      query::hover(&state, &test_mod_ref, Position(0, 7))
+        .unwrap()
+        .contents
+        .iter()
+        .map(query::TypeQueryContent::to_string)
+        .join("\n")
+    );
+    assert_eq!(
+      "(int) -> unit [lang=samlang]",
+      query::hover(&state, &test_mod_ref, Position(4, 20))
+        .unwrap()
+        .contents
+        .iter()
+        .map(query::TypeQueryContent::to_string)
+        .join("\n")
+    );
+    assert_eq!(
+      "Test.sam:1:1-1:32",
+      query::definition_location(&state, &test_mod_ref, Position(4, 20))
+        .unwrap()
+        .pretty_print(&state.heap)
+    );
+    assert_eq!(
+      vec!["Test.sam:1:6-1:14", "Test.sam:2:22-2:30", "Test.sam:5:19-5:27", "Test2.sam:2:31-2:39"],
+      query::all_references(&state, &test_mod_ref, Position(4, 20))
+        .iter()
+        .map(|it| it.pretty_print(&state.heap))
+        .collect_vec()
+    );
+    // Callback is imported by another module, so a local rewrite is not enough.
+    assert!(rewrite::rename(&mut state, &test_mod_ref, Position(4, 20), "Cb").is_none());
+    assert_eq!(
+      r#"type Callback<T> = (T) -> unit;
+
+private type Renamed = Callback<int>;
+
+class Main {
+  function run(f: Callback<int>, g: Renamed): unit = f(1)
+}
+"#,
+      rewrite::rename(&mut state, &test_mod_ref, Position(4, 37), "Renamed").unwrap()
+    );
+  }
+
+  #[test]
+  fn const_query_tests() {
+    let mut heap = Heap::new();
+    let test_mod_ref = heap.alloc_module_reference_from_string_vec(vec!["Test".to_string()]);
+    let test2_mod_ref = heap.alloc_module_reference_from_string_vec(vec!["Test2".to_string()]);
+    let state = server_state::ServerState::new(
+      heap,
+      false,
+      HashMap::from([
+        (
+          test_mod_ref,
+          r#"/** The answer. */
+const Answer: int = 40 + 2;
+const Double: int = Answer * 2;
+"#
+          .to_string(),
+        ),
+        (
+          test2_mod_ref,
+          r#"import {Answer} from Test
+class Main { function run(): int = Answer }
+"#
+          .to_string(),
+        ),
+      ]),
+    );
+
+    assert_eq!(
+      "const Answer: int [lang=samlang]\nThe answer. [lang=markdown]",
+      query::hover(&state, &test_mod_ref, Position(2, 21))
+        .unwrap()
+        .contents
+        .iter()
+        .map(query::TypeQueryContent::to_string)
+        .join("\n")
+    );
+    assert_eq!(
+      "Test.sam:2:1-2:28",
+      query::definition_location(&state, &test2_mod_ref, Position(1, 36))
+        .unwrap()
+        .pretty_print(&state.heap)
+    );
+    assert_eq!(
+      vec!["Test.sam:2:7-2:13", "Test.sam:3:21-3:27", "Test2.sam:2:36-2:42"],
+      query::all_references(&state, &test_mod_ref, Position(1, 8))
+        .iter()
+        .map(|it| it.pretty_print(&state.heap))
+        .sorted()
+        .collect_vec()
+    );
+  }
+
+  #[test]
+  fn derived_members_query_tests() {
+    let mod_ref = ModuleReference::DUMMY;
+    let state = server_state::ServerState::new(
+      Heap::new(),
+      false,
+      HashMap::from([(
+        mod_ref,
+        r#"class Point(val x: int) derives equals, toString {}
+class Main {
+  function main(p: Point): Str = p.toString()
+  function x(p: Point): int = p.x
+}
+"#
+        .to_string(),
+      )]),
+    );
+    assert_eq!(
+      "DUMMY.sam:1:41-1:49",
+      query::definition_location(&state, &mod_ref, Position(2, 37))
+        .unwrap()
+        .pretty_print(&state.heap)
+    );
+    assert_eq!(
+      vec![
+        "DUMMY.sam:1:17-1:18",
+        "DUMMY.sam:1:33-1:39",
+        "DUMMY.sam:1:41-1:49",
+        "DUMMY.sam:4:33-4:34"
+      ],
+      query::all_references(&state, &mod_ref, Position(3, 32))
+        .iter()
+        .map(|it| it.pretty_print(&state.heap))
+        .collect_vec()
+    );
+    assert_eq!(
+      r#"equals [kind=Method, detail=equals(a0: Point): bool]
+toString [kind=Method, detail=toString(): Str]"#,
+      completion::auto_complete(&state, &mod_ref, Position(2, 35))
+        .iter()
+        .map(completion::AutoCompletionItem::to_string)
+        .join("\n")
+    );
+  }
 }
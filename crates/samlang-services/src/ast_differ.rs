@@ -429,7 +429,8 @@ mod tests {
   use super::*;
   use pretty_assertions::assert_eq;
   use samlang_ast::source::{
-    Id, InterfaceDeclarationCommon, InterfaceMembersCommon, NO_COMMENT_REFERENCE, test_builder,
+    Id, InterfaceDeclarationCommon, InterfaceMembersCommon, NO_COMMENT_REFERENCE, Visibility,
+    test_builder,
   };
   use samlang_errors::ErrorSet;
   use samlang_heap::PStr;
@@ -495,6 +496,7 @@ mod tests {
     let stmt = expr::Statement::Declaration(Box::new(expr::DeclarationStatement {
       loc: Location::dummy(),
       associated_comments: NO_COMMENT_REFERENCE,
+      is_mutable: false,
       pattern: samlang_ast::source::pattern::MatchingPattern::Id(
         Id::from(heap.alloc_str_for_test("v")),
         (),
@@ -504,6 +506,7 @@ mod tests {
         expr::ExpressionCommon::dummy(()),
         Id::from(heap.alloc_str_for_test("s")),
       )),
+      else_block: None,
     }));
     assert_eq!(
       "let v: bool = s;",
@@ -529,10 +532,11 @@ mod tests {
     let toplevel = Toplevel::Interface(InterfaceDeclarationCommon {
       loc: Location::dummy(),
       associated_comments: NO_COMMENT_REFERENCE,
-      private: false,
+      visibility: Visibility::Public,
       name: Id::from(PStr::UPPER_A),
       type_parameters: None,
       extends_or_implements_nodes: None,
+      derives: None,
       type_definition: (),
       members: InterfaceMembersCommon {
         loc: Location::dummy(),
@@ -11,6 +11,7 @@ fn mark_annot(heap: &mut Heap, type_: &annotation::T) {
     annotation::T::Id(annot) => mark_id_annot(heap, annot),
     annotation::T::Generic(_, id) => heap.mark(id.name),
     annotation::T::Fn(annot) => mark_fn_annot(heap, annot),
+    annotation::T::Tuple(annot) => mark_annotations(heap, &annot.annotations),
   }
 }
 
@@ -48,6 +49,7 @@ fn mark_type(heap: &mut Heap, type_: &Type) {
     Type::Nominal(t) => mark_nominal_type(heap, t),
     Type::Generic(_, id) => heap.mark(*id),
     Type::Fn(t) => mark_fn_type(heap, t),
+    Type::Tuple(_, element_types) => mark_types(heap, element_types),
   }
 }
 
@@ -67,25 +69,40 @@ fn mark_types(heap: &mut Heap, types: &Vec<Arc<Type>>) {
   }
 }
 
+/// Types attached to the AST, which are absent in the untyped default values of parameters.
+trait MarkableType: Clone {
+  fn mark(&self, heap: &mut Heap);
+}
+
+impl MarkableType for Arc<Type> {
+  fn mark(&self, heap: &mut Heap) {
+    mark_type(heap, self)
+  }
+}
+
+impl MarkableType for () {
+  fn mark(&self, _heap: &mut Heap) {}
+}
+
 fn mark_id(heap: &mut Heap, id: &Id) {
   heap.mark(id.name);
 }
 
-fn mark_tuple_pattern(heap: &mut Heap, pattern: &pattern::TuplePattern<Arc<Type>>) {
+fn mark_tuple_pattern<T: MarkableType>(heap: &mut Heap, pattern: &pattern::TuplePattern<T>) {
   for n in &pattern.elements {
     mark_matching_pattern(heap, &n.pattern);
-    mark_type(heap, &n.type_);
+    n.type_.mark(heap);
   }
 }
 
-fn mark_matching_pattern(heap: &mut Heap, pattern: &pattern::MatchingPattern<Arc<Type>>) {
+fn mark_matching_pattern<T: MarkableType>(heap: &mut Heap, pattern: &pattern::MatchingPattern<T>) {
   match pattern {
     pattern::MatchingPattern::Tuple(p) => {
       mark_tuple_pattern(heap, p);
     }
     pattern::MatchingPattern::Object { elements, .. } => {
       for n in elements {
-        mark_type(heap, &n.type_);
+        n.type_.mark(heap);
         mark_id(heap, &n.field_name);
         mark_matching_pattern(heap, &n.pattern);
       }
@@ -97,7 +114,7 @@ fn mark_matching_pattern(heap: &mut Heap, pattern: &pattern::MatchingPattern<Arc
       data_variables,
       type_,
     }) => {
-      mark_type(heap, type_);
+      type_.mark(heap);
       mark_id(heap, tag);
       if let Some(p) = data_variables {
         mark_tuple_pattern(heap, p);
@@ -105,9 +122,10 @@ fn mark_matching_pattern(heap: &mut Heap, pattern: &pattern::MatchingPattern<Arc
     }
     pattern::MatchingPattern::Id(id, type_) => {
       mark_id(heap, id);
-      mark_type(heap, type_);
+      type_.mark(heap);
     }
     pattern::MatchingPattern::Wildcard { .. } => {}
+    pattern::MatchingPattern::Literal { literal, .. } => mark_literal(heap, literal),
     pattern::MatchingPattern::Or { patterns, .. } => {
       for p in patterns {
         mark_matching_pattern(heap, p);
@@ -116,7 +134,7 @@ fn mark_matching_pattern(heap: &mut Heap, pattern: &pattern::MatchingPattern<Arc
   }
 }
 
-fn mark_if_else(heap: &mut Heap, if_else: &expr::IfElse<Arc<Type>>) {
+fn mark_if_else<T: MarkableType>(heap: &mut Heap, if_else: &expr::IfElse<T>) {
   match if_else.condition.as_ref() {
     expr::IfElseCondition::Expression(e) => mark_expression(heap, e),
     expr::IfElseCondition::Guard(p, e) => {
@@ -131,13 +149,25 @@ fn mark_if_else(heap: &mut Heap, if_else: &expr::IfElse<Arc<Type>>) {
   }
 }
 
-fn mark_block(heap: &mut Heap, block: &expr::Block<Arc<Type>>) {
+fn mark_block<T: MarkableType>(heap: &mut Heap, block: &expr::Block<T>) {
   for stmt in &block.statements {
     match stmt {
       expr::Statement::Declaration(decl_stmt) => {
         mark_expression(heap, &decl_stmt.assigned_expression);
         mark_annot_opt(heap, &decl_stmt.annotation);
         mark_matching_pattern(heap, &decl_stmt.pattern);
+        if let Some(else_block) = &decl_stmt.else_block {
+          mark_block(heap, else_block);
+        }
+      }
+      expr::Statement::Assignment(assign_stmt) => {
+        heap.mark(assign_stmt.name.name);
+        mark_expression(heap, &assign_stmt.assigned_expression);
+      }
+      expr::Statement::Function(function_stmt) => {
+        mark_id(heap, &function_stmt.name);
+        mark_annot(heap, &function_stmt.return_annotation);
+        mark_lambda(heap, &function_stmt.lambda);
       }
       expr::Statement::Expression(expr) => {
         mark_expression(heap, expr);
@@ -149,28 +179,65 @@ fn mark_block(heap: &mut Heap, block: &expr::Block<Arc<Type>>) {
   }
 }
 
-fn mark_expression(heap: &mut Heap, expr: &expr::E<Arc<Type>>) {
-  mark_type(heap, &expr.common().type_);
+fn mark_lambda<T: MarkableType>(heap: &mut Heap, lambda: &expr::Lambda<T>) {
+  for param in &lambda.parameters.parameters {
+    mark_id(heap, &param.name);
+    mark_annot_opt(heap, &param.annotation);
+  }
+  mark_expression(heap, &lambda.body);
+}
+
+fn mark_literal(heap: &mut Heap, literal: &Literal) {
+  match literal {
+    Literal::Int(_, Some(s)) | Literal::String(s, None) => heap.mark(*s),
+    Literal::String(s, Some(spelling)) => {
+      heap.mark(*s);
+      heap.mark(*spelling);
+    }
+    Literal::Bool(_) | Literal::Int(_, None) | Literal::Float(_) => {}
+  }
+}
+
+fn mark_expression<T: MarkableType>(heap: &mut Heap, expr: &expr::E<T>) {
+  expr.common().type_.mark(heap);
   match expr {
-    expr::E::Literal(_, Literal::String(s)) => heap.mark(*s),
-    expr::E::Literal(_, _) => {}
+    expr::E::Literal(_, literal) => mark_literal(heap, literal),
     expr::E::LocalId(_, id) | expr::E::ClassId(_, _, id) => mark_id(heap, id),
     expr::E::Tuple(_, expressions) => {
       for e in &expressions.expressions {
         mark_expression(heap, e);
       }
     }
+    expr::E::TemplateString(e) => {
+      for part in &e.parts {
+        heap.mark(*part);
+      }
+      for hole in &e.holes {
+        mark_expression(heap, hole);
+      }
+    }
+    expr::E::StructUpdate(e) => {
+      mark_expression(heap, &e.base);
+      for field in &e.fields {
+        mark_id(heap, &field.field_name);
+        mark_expression(heap, &field.value);
+      }
+    }
     expr::E::FieldAccess(e) => {
       mark_expression(heap, &e.object);
       mark_id(heap, &e.field_name);
       mark_type_arguments(heap, e.explicit_type_arguments.as_ref());
-      mark_types(heap, &e.inferred_type_arguments);
+      for t in &e.inferred_type_arguments {
+        t.mark(heap);
+      }
     }
     expr::E::MethodAccess(e) => {
       mark_expression(heap, &e.object);
       mark_id(heap, &e.method_name);
       mark_type_arguments(heap, e.explicit_type_arguments.as_ref());
-      mark_types(heap, &e.inferred_type_arguments);
+      for t in &e.inferred_type_arguments {
+        t.mark(heap);
+      }
     }
     expr::E::Unary(e) => mark_expression(heap, &e.argument),
     expr::E::Call(e) => {
@@ -178,6 +245,12 @@ fn mark_expression(heap: &mut Heap, expr: &expr::E<Arc<Type>>) {
       for e in &e.arguments.expressions {
         mark_expression(heap, e);
       }
+      for name in &e.binding.names {
+        mark_id(heap, name);
+      }
+      for (_, e) in &e.binding.default_arguments {
+        mark_expression(heap, e);
+      }
     }
     expr::E::Binary(e) => {
       mark_expression(heap, &e.e1);
@@ -188,16 +261,31 @@ fn mark_expression(heap: &mut Heap, expr: &expr::E<Arc<Type>>) {
       mark_expression(heap, &e.matched);
       for case in &e.cases {
         mark_matching_pattern(heap, &case.pattern);
+        if let Some(guard) = &case.guard {
+          mark_expression(heap, guard);
+        }
         mark_expression(heap, &case.body);
       }
     }
-    expr::E::Lambda(e) => {
-      for param in &e.parameters.parameters {
-        mark_id(heap, &param.name);
-        mark_annot_opt(heap, &param.annotation);
+    expr::E::Lambda(e) => mark_lambda(heap, e),
+    expr::E::Return(e) => mark_expression(heap, &e.argument),
+    expr::E::While(e) => {
+      mark_expression(heap, &e.condition);
+      mark_block(heap, &e.body);
+    }
+    expr::E::For(e) => {
+      mark_id(heap, &e.iterator);
+      mark_expression(heap, &e.start);
+      mark_expression(heap, &e.end);
+      mark_block(heap, &e.body);
+    }
+    expr::E::Break(_) => {}
+    expr::E::Assert(e) => {
+      for e in &e.arguments.expressions {
+        mark_expression(heap, e);
       }
-      mark_expression(heap, &e.body);
     }
+    expr::E::Try(e) => mark_expression(heap, &e.argument),
     expr::E::Block(e) => mark_block(heap, e),
   }
 }
@@ -231,6 +319,10 @@ fn mark_module(heap: &mut Heap, module: &Module<Arc<Type>>) {
     for m in toplevel.members_iter() {
       mark_id(heap, &m.name);
       mark_type_parameters(heap, m.type_parameters.as_ref());
+      for default_value in m.parameters.parameters.iter().filter_map(|it| it.default_value.as_ref())
+      {
+        mark_expression(heap, default_value);
+      }
       mark_annot(heap, &m.return_type);
     }
     if let Toplevel::Class(c) = toplevel {
@@ -265,6 +357,18 @@ fn mark_module(heap: &mut Heap, module: &Module<Arc<Type>>) {
         mark_expression(heap, &m.body);
       }
     }
+    if let Toplevel::Interface(i) = toplevel {
+      for body in i.members.members.iter().filter_map(|it| it.default_body.as_ref()) {
+        mark_expression(heap, body);
+      }
+    }
+    if let Toplevel::TypeAlias(alias) = toplevel {
+      mark_annot(heap, &alias.annotation);
+    }
+    if let Toplevel::Const(c) = toplevel {
+      mark_annot(heap, &c.annotation);
+      mark_expression(heap, &c.value);
+    }
   }
 }
 
@@ -412,6 +516,7 @@ mod tests {
     );
     let (checked_sources, _) = samlang_checker::type_check_sources(
       &HashMap::from([(ModuleReference::DUMMY, parsed)]),
+      heap,
       &mut error_set,
     );
     super::perform_gc_after_recheck(heap, &checked_sources, vec![ModuleReference::ROOT]);
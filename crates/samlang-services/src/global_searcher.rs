@@ -26,6 +26,11 @@ fn search_annot(
       }
       search_annot(&annot.return_type, request, collector);
     }
+    annotation::T::Tuple(annot) => {
+      for a in &annot.annotations {
+        search_annot(a, request, collector);
+      }
+    }
   }
 }
 
@@ -38,7 +43,7 @@ fn search_id_annot(
     GlobalNameSearchRequest::Toplevel(mod_ref, name)
       if mod_ref.eq(&annotation.module_reference) && name.eq(&annotation.id.name) =>
     {
-      collector.push(annotation.location);
+      collector.push(annotation.id.loc);
     }
     _ => {}
   }
@@ -80,6 +85,7 @@ fn search_matching_pattern(
     pattern::MatchingPattern::Variant(_)
     | pattern::MatchingPattern::Id(_, _)
     | pattern::MatchingPattern::Wildcard { .. }
+    | pattern::MatchingPattern::Literal { .. }
     | pattern::MatchingPattern::Or { .. } => {}
   }
 }
@@ -129,6 +135,16 @@ fn search_block(
           collector,
         );
         search_expression(&decl_stmt.assigned_expression, request, collector);
+        if let Some(else_block) = &decl_stmt.else_block {
+          search_block(else_block, request, collector);
+        }
+      }
+      expr::Statement::Assignment(assign_stmt) => {
+        search_expression(&assign_stmt.assigned_expression, request, collector);
+      }
+      expr::Statement::Function(function_stmt) => {
+        search_annot(&function_stmt.return_annotation, request, collector);
+        search_lambda(&function_stmt.lambda, request, collector);
       }
       expr::Statement::Expression(expr) => {
         search_expression(expr, request, collector);
@@ -140,6 +156,19 @@ fn search_block(
   }
 }
 
+fn search_lambda(
+  lambda: &expr::Lambda<Arc<Type>>,
+  request: &GlobalNameSearchRequest,
+  collector: &mut Vec<Location>,
+) {
+  for param in &lambda.parameters.parameters {
+    if let Some(annot) = &param.annotation {
+      search_annot(annot, request, collector);
+    }
+  }
+  search_expression(&lambda.body, request, collector)
+}
+
 fn search_expression(
   expr: &expr::E<Arc<Type>>,
   request: &GlobalNameSearchRequest,
@@ -160,6 +189,30 @@ fn search_expression(
         search_expression(e, request, collector);
       }
     }
+    expr::E::TemplateString(e) => {
+      for hole in &e.holes {
+        search_expression(hole, request, collector);
+      }
+    }
+    expr::E::StructUpdate(e) => {
+      if let (
+        GlobalNameSearchRequest::Property(mod_ref, toplevel_name, field_name),
+        Some(nominal_type),
+      ) = (request, e.base.type_().as_nominal())
+        && mod_ref.eq(&nominal_type.module_reference)
+        && toplevel_name.eq(&nominal_type.id)
+      {
+        for field in &e.fields {
+          if field_name.eq(&field.field_name.name) {
+            collector.push(field.field_name.loc);
+          }
+        }
+      }
+      search_expression(&e.base, request, collector);
+      for field in &e.fields {
+        search_expression(&field.value, request, collector);
+      }
+    }
     expr::E::FieldAccess(e) => {
       match (request, e.object.type_().as_nominal()) {
         (
@@ -207,17 +260,30 @@ fn search_expression(
       search_expression(&e.matched, request, collector);
       for case in &e.cases {
         search_matching_pattern(&case.pattern, e.matched.type_(), request, collector);
+        if let Some(guard) = &case.guard {
+          search_expression(guard, request, collector);
+        }
         search_expression(&case.body, request, collector);
       }
     }
-    expr::E::Lambda(e) => {
-      for param in &e.parameters.parameters {
-        if let Some(annot) = &param.annotation {
-          search_annot(annot, request, collector);
-        }
+    expr::E::Lambda(e) => search_lambda(e, request, collector),
+    expr::E::Return(e) => search_expression(&e.argument, request, collector),
+    expr::E::While(e) => {
+      search_expression(&e.condition, request, collector);
+      search_block(&e.body, request, collector);
+    }
+    expr::E::For(e) => {
+      search_expression(&e.start, request, collector);
+      search_expression(&e.end, request, collector);
+      search_block(&e.body, request, collector);
+    }
+    expr::E::Break(_) => {}
+    expr::E::Assert(e) => {
+      for e in &e.arguments.expressions {
+        search_expression(e, request, collector);
       }
-      search_expression(&e.body, request, collector)
     }
+    expr::E::Try(e) => search_expression(&e.argument, request, collector),
     expr::E::Block(e) => search_block(e, request, collector),
   }
 }
@@ -238,6 +304,48 @@ pub(super) fn search_modules_globally(
         }
         _ => {}
       }
+      for tparam in toplevel.type_parameters().iter().flat_map(|it| &it.parameters) {
+        if let Some(bound) = &tparam.bound {
+          search_id_annot(bound, request, &mut collector);
+        }
+      }
+      for node in toplevel.extends_or_implements_nodes().iter().flat_map(|it| &it.nodes) {
+        search_id_annot(node, request, &mut collector);
+      }
+      if let Some(type_def) = toplevel.type_definition() {
+        match type_def {
+          TypeDefinition::Struct {
+            loc: _,
+            start_associated_comments: _,
+            ending_associated_comments: _,
+            fields,
+          } => {
+            for field in fields {
+              search_annot(&field.annotation, request, &mut collector);
+            }
+          }
+          TypeDefinition::Enum {
+            loc: _,
+            start_associated_comments: _,
+            ending_associated_comments: _,
+            variants,
+          } => {
+            for annot in variants
+              .iter()
+              .flat_map(|it| it.associated_data_types.iter().flat_map(|it| &it.annotations))
+            {
+              search_annot(annot, request, &mut collector);
+            }
+          }
+        }
+      }
+      if let Toplevel::TypeAlias(alias) = toplevel {
+        search_annot(&alias.annotation, request, &mut collector);
+      }
+      if let Toplevel::Const(c) = toplevel {
+        search_annot(&c.annotation, request, &mut collector);
+        search_expression(&c.value, request, &mut collector);
+      }
       for member in toplevel.members_iter() {
         for param in member.parameters.parameters.iter() {
           search_annot(&param.annotation, request, &mut collector);
@@ -282,6 +390,11 @@ pub(super) fn search_modules_globally(
           search_expression(&member.body, request, &mut collector);
         }
       }
+      if let Toplevel::Interface(i) = toplevel {
+        for body in i.members.members.iter().filter_map(|it| it.default_body.as_ref()) {
+          search_expression(body, request, &mut collector);
+        }
+      }
     }
   }
   collector
@@ -388,7 +501,7 @@ mod tests {
       samlang_parser::parse_source_module_from_text(source, mod_ref, heap, &mut error_set);
     let mut modules = samlang_parser::builtin_parsed_std_sources_for_tests(heap);
     modules.insert(mod_ref, parsed);
-    let (checked_sources, _) = samlang_checker::type_check_sources(&modules, &mut error_set);
+    let (checked_sources, _) = samlang_checker::type_check_sources(&modules, heap, &mut error_set);
     assert_eq!(
       r#"
 Error ---------------------------------- foo.sam:33:24-33:44
@@ -23,7 +23,11 @@ use samlang_ast::{
   },
 };
 use samlang_checker::{
-  type_::{FunctionType, ISourceType, InterfaceSignature, MemberSignature, Type},
+  expand_type_aliases,
+  type_::{
+    ConstSignature, FunctionType, ISourceType, InterfaceSignature, MemberSignature, Type,
+    TypeAliasSignature, TypeParameterSignature,
+  },
   type_check_module,
 };
 use samlang_errors::{ErrorDetail, ErrorSet};
@@ -68,6 +72,32 @@ mod state_searcher_utils {
     state.global_cx.get(module_reference).and_then(|cx| cx.interfaces.get(class_name))
   }
 
+  pub(super) fn find_type_alias<'a>(
+    state: &'a server_state::ServerState,
+    module_reference: &ModuleReference,
+    alias_name: &PStr,
+  ) -> Option<&'a TypeAliasSignature> {
+    state.global_cx.get(module_reference).and_then(|cx| cx.type_aliases.get(alias_name))
+  }
+
+  pub(super) fn find_const<'a>(
+    state: &'a server_state::ServerState,
+    module_reference: &ModuleReference,
+    const_name: &PStr,
+  ) -> Option<&'a ConstSignature> {
+    state.global_cx.get(module_reference).and_then(|cx| cx.constants.get(const_name))
+  }
+
+  /// Returns the module and name of the type alias when the type directly refers to one.
+  pub(super) fn type_alias_of_type(
+    state: &server_state::ServerState,
+    type_: &Type,
+  ) -> Option<(ModuleReference, PStr)> {
+    let nominal_type = type_.as_nominal()?;
+    find_type_alias(state, &nominal_type.module_reference, &nominal_type.id)
+      .map(|_| (nominal_type.module_reference, nominal_type.id))
+  }
+
   pub(super) fn find_type_def<'a>(
     state: &'a server_state::ServerState,
     module_reference: &ModuleReference,
@@ -98,6 +128,23 @@ mod state_searcher_utils {
       .find(|it| it.name.name.eq(member_name))
   }
 
+  pub(super) fn find_member_signature<'a>(
+    state: &'a server_state::ServerState,
+    callee: &expr::E<Arc<Type>>,
+  ) -> Option<&'a MemberSignature> {
+    let expr::E::MethodAccess(method_access) = callee else {
+      return None;
+    };
+    let nominal_type = method_access.object.type_().as_nominal()?;
+    let interface_type =
+      find_interface_type(state, &nominal_type.module_reference, &nominal_type.id)?;
+    if nominal_type.is_class_statics {
+      interface_type.functions.get(&method_access.method_name.name)
+    } else {
+      interface_type.methods.get(&method_access.method_name.name)
+    }
+  }
+
   pub(super) fn find_class_name(
     state: &server_state::ServerState,
     module_reference: &ModuleReference,
@@ -221,10 +268,27 @@ pub mod query {
         module_reference,
         class_name,
       ) => {
-        let type_content = TypeQueryContent {
-          language: "samlang",
-          value: format!("class {}", class_name.as_str(&state.heap)),
+        let value = if let Some(alias) =
+          state_searcher_utils::find_type_alias(state, &module_reference, &class_name)
+        {
+          format!(
+            "type {}{} = {}",
+            class_name.as_str(&state.heap),
+            TypeParameterSignature::pretty_print_list(&alias.type_parameters, &state.heap),
+            alias.type_.pretty_print(&state.heap)
+          )
+        } else if let Some(const_cx) =
+          state_searcher_utils::find_const(state, &module_reference, &class_name)
+        {
+          format!(
+            "const {}: {}",
+            class_name.as_str(&state.heap),
+            const_cx.type_.pretty_print(&state.heap)
+          )
+        } else {
+          format!("class {}", class_name.as_str(&state.heap))
         };
+        let type_content = TypeQueryContent { language: "samlang", value };
         Some(query_result_with_optional_document(
           state,
           loc,
@@ -254,7 +318,7 @@ pub mod query {
         Some(TypeQueryResult {
           contents: vec![TypeQueryContent {
             language: "samlang",
-            value: type_.pretty_print(&state.heap),
+            value: expand_type_aliases(&state.global_cx, &type_).pretty_print(&state.heap),
           }],
           location,
         })
@@ -310,6 +374,7 @@ pub mod query {
     all_references_opt(state, module_reference, position)
       .unwrap_or_default()
       .into_iter()
+      .map(Location::resolve_synthesized)
       .sorted()
       .dedup()
       .collect()
@@ -358,7 +423,14 @@ pub mod query {
           &global_searcher::GlobalNameSearchRequest::Toplevel(mod_ref, class_name),
         ))
       }
-      location_cover::LocationCoverSearchResult::TypedName(loc, _, _) => {
+      location_cover::LocationCoverSearchResult::TypedName(loc, type_, _) => {
+        if let Some((mod_ref, alias_name)) = state_searcher_utils::type_alias_of_type(state, &type_)
+        {
+          return Some(global_searcher::search_modules_globally(
+            &state.checked_modules,
+            &global_searcher::GlobalNameSearchRequest::Toplevel(mod_ref, alias_name),
+          ));
+        }
         let module = state.parsed_modules.get(module_reference).unwrap();
         variable_definition::VariableDefinitionLookup::new(*module_reference, module)
           .find_all_definition_and_uses(&loc)
@@ -395,7 +467,11 @@ pub mod query {
       location_cover::LocationCoverSearchResult::ToplevelName(_, mod_ref, class_name) => {
         Some(state_searcher_utils::find_toplevel(state, &mod_ref, &class_name)?.loc())
       }
-      location_cover::LocationCoverSearchResult::TypedName(loc, _, _) => {
+      location_cover::LocationCoverSearchResult::TypedName(loc, type_, _) => {
+        if let Some((mod_ref, alias_name)) = state_searcher_utils::type_alias_of_type(state, &type_)
+        {
+          return Some(state_searcher_utils::find_toplevel(state, &mod_ref, &alias_name)?.loc());
+        }
         let module = state.parsed_modules.get(module_reference).unwrap();
         variable_definition::VariableDefinitionLookup::new(*module_reference, module)
           .find_all_definition_and_uses(&loc)
@@ -417,7 +493,7 @@ pub mod query {
         let mut active_parameter = 0;
         for (i, e) in call.arguments.expressions.iter().enumerate() {
           if e.loc().contains_position(position) {
-            active_parameter = i;
+            active_parameter = call.binding.parameter_indices.get(i).copied().unwrap_or(i);
           }
         }
         if let Some(last_arg) = call.arguments.expressions.last()
@@ -426,22 +502,41 @@ pub mod query {
         {
           active_parameter = call.arguments.expressions.len();
         }
-        let label = format!(
-          "({}) -> {}",
-          signature
-            .argument_types
-            .iter()
-            .enumerate()
-            .map(|(i, t)| format!("a{}: {}", i, t.pretty_print(&state.heap)))
-            .join(", "),
-          signature.return_type.pretty_print(&state.heap)
-        );
+        let parameter_signatures = state_searcher_utils::find_member_signature(state, &call.callee)
+          .map(|it| it.parameters.as_slice())
+          .filter(|it| it.len() == signature.argument_types.len())
+          .unwrap_or_default();
         let parameters = signature
           .argument_types
           .iter()
           .enumerate()
-          .map(|(i, t)| format!("a{}: {}", i, t.pretty_print(&state.heap)))
+          .map(|(i, t)| {
+            let Some(parameter) = parameter_signatures.get(i) else {
+              return format!("a{}: {}", i, t.pretty_print(&state.heap));
+            };
+            let name = parameter.name.as_str(&state.heap);
+            let type_ = t.pretty_print(&state.heap);
+            let Some((module, default_value)) =
+              parameter.default_value.as_ref().and_then(|(mod_ref, _, default_value)| {
+                Some((state.parsed_modules.get(mod_ref)?, default_value))
+              })
+            else {
+              return format!("{name}: {type_}");
+            };
+            let default_value = samlang_printer::pretty_print_expression(
+              &state.heap,
+              100,
+              &module.comment_store,
+              default_value,
+            );
+            format!("{name}: {type_} = {}", default_value.trim_end())
+          })
           .collect_vec();
+        let label = format!(
+          "({}) -> {}",
+          parameters.join(", "),
+          signature.return_type.pretty_print(&state.heap)
+        );
         Some(SignatureHelpResult { label, parameters, active_parameter })
       }
       _ => None,
@@ -478,9 +573,13 @@ pub mod rewrite {
     new_name: &str,
   ) -> Option<String> {
     let trimmed_new_name = new_name.trim();
-    if !(trimmed_new_name.starts_with(|c: char| c.is_ascii_lowercase())
-      && trimmed_new_name.chars().all(|c: char| c.is_ascii_alphanumeric()))
-    {
+    if !trimmed_new_name.chars().all(|c: char| c.is_ascii_alphanumeric()) {
+      return None;
+    }
+    if trimmed_new_name.starts_with(|c: char| c.is_ascii_uppercase()) {
+      return rename_type_alias(state, module_reference, position, trimmed_new_name);
+    }
+    if !trimmed_new_name.starts_with(|c: char| c.is_ascii_lowercase()) {
       return None;
     }
     let def_or_use_loc =
@@ -502,6 +601,61 @@ pub mod rewrite {
     Some(samlang_printer::pretty_print_source_module(&state.heap, 100, &renamed))
   }
 
+  fn rename_type_alias(
+    state: &mut server_state::ServerState,
+    module_reference: &ModuleReference,
+    position: Position,
+    new_name: &str,
+  ) -> Option<String> {
+    let alias_name =
+      match state_searcher_utils::search_at_pos(state, module_reference, position, false)? {
+        location_cover::LocationCoverSearchResult::ToplevelName(_, mod_ref, name)
+          if mod_ref.eq(module_reference) =>
+        {
+          name
+        }
+        location_cover::LocationCoverSearchResult::TypedName(_, type_, _) => {
+          match state_searcher_utils::type_alias_of_type(state, &type_) {
+            Some((mod_ref, name)) if mod_ref.eq(module_reference) => name,
+            _ => return None,
+          }
+        }
+        _ => return None,
+      };
+    state_searcher_utils::find_type_alias(state, module_reference, &alias_name)?;
+    // We can only return the rewritten current module, so aliases used elsewhere are not renamed.
+    let is_imported_elsewhere = state.parsed_modules.values().flat_map(|m| &m.imports).any(|it| {
+      it.imported_module.eq(module_reference)
+        && it.imported_members.iter().any(|id| id.name.eq(&alias_name))
+    });
+    if is_imported_elsewhere {
+      return None;
+    }
+    let locations = global_searcher::search_modules_globally(
+      &state.checked_modules,
+      &global_searcher::GlobalNameSearchRequest::Toplevel(*module_reference, alias_name),
+    );
+    let source = state.string_sources.get(module_reference)?;
+    let mut line_start_offsets = vec![0];
+    for (i, c) in source.bytes().enumerate() {
+      if c == b'\n' {
+        line_start_offsets.push(i + 1);
+      }
+    }
+    let offset_of = |pos: Position| line_start_offsets[pos.0 as usize] + pos.1 as usize;
+    let mut renamed_source = source.clone();
+    for loc in locations.into_iter().sorted().dedup().collect_vec().into_iter().rev() {
+      renamed_source.replace_range(offset_of(loc.start)..offset_of(loc.end), new_name);
+    }
+    let renamed = samlang_parser::parse_source_module_from_text(
+      &renamed_source,
+      *module_reference,
+      &mut state.heap,
+      &mut ErrorSet::new(),
+    );
+    Some(samlang_printer::pretty_print_source_module(&state.heap, 100, &renamed))
+  }
+
   pub fn code_actions(state: &server_state::ServerState, location: Location) -> Vec<CodeAction> {
     let mut actions = Vec::new();
     for error in state.errors.get(&location.module_reference).iter().flat_map(|it| it.iter()) {
@@ -577,7 +731,7 @@ pub mod rewrite {
 
 pub mod completion {
   use super::*;
-  use samlang_checker::type_::TypeDefinitionSignature;
+  use samlang_checker::{is_member_visible, is_toplevel_visible, type_::TypeDefinitionSignature};
   use std::collections::HashSet;
 
   #[derive(Debug)]
@@ -677,6 +831,14 @@ pub mod completion {
           let mut items = Vec::new();
           for (import_mod_ref, mod_cx) in &state.global_cx {
             for (n, interface_sig) in &mod_cx.interfaces {
+              if !is_toplevel_visible(
+                &state.global_cx,
+                *module_reference,
+                *import_mod_ref,
+                interface_sig.visibility,
+              ) {
+                continue;
+              }
               let name = n.as_str(&state.heap);
               let (kind, detail) = if interface_sig.type_definition.is_some() {
                 (CompletionItemKind::Class, format!("class {name}"))
@@ -745,7 +907,12 @@ pub mod completion {
       _ => {}
     }
     for (name, info) in relevant_interface_type.methods.iter() {
-      if is_inside_class || info.is_public {
+      if is_member_visible(
+        &state.global_cx,
+        (*module_reference, class_of_expr),
+        (instance_mod_ref, instance_class_name),
+        info.visibility,
+      ) {
         completion_results.push(get_completion_result_from_type_info(
           state,
           name.as_str(&state.heap),
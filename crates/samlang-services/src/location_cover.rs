@@ -3,7 +3,7 @@ use samlang_ast::{
   Location, Position, Reason,
   source::{Module, Toplevel, annotation, expr, pattern},
 };
-use samlang_checker::type_::{FunctionType, NominalType, Type};
+use samlang_checker::type_::{FunctionType, NominalType, PrimitiveTypeKind, Type};
 use samlang_heap::{ModuleReference, PStr};
 use std::sync::Arc;
 
@@ -41,7 +41,9 @@ fn search_matching_pattern(
     pattern::MatchingPattern::Id(id, type_) if id.loc.contains_position(position) => {
       Some(LocationCoverSearchResult::TypedName(id.loc, type_.as_ref().clone(), true))
     }
-    pattern::MatchingPattern::Id(_, _) | pattern::MatchingPattern::Wildcard { .. } => None,
+    pattern::MatchingPattern::Id(_, _)
+    | pattern::MatchingPattern::Wildcard { .. }
+    | pattern::MatchingPattern::Literal { .. } => None,
     pattern::MatchingPattern::Or { patterns, .. } => {
       patterns.iter().find_map(|p| search_matching_pattern(p, position))
     }
@@ -120,6 +122,9 @@ fn search_annotation(
       .iter()
       .find_map(|it| search_annotation(it, position))
       .or_else(|| search_annotation(&fn_annot.return_type, position)),
+    annotation::T::Tuple(tuple_annot) => {
+      tuple_annot.annotations.iter().find_map(|it| search_annotation(it, position))
+    }
   }
 }
 
@@ -175,6 +180,40 @@ fn search_block(
         if let Some(found) = search_matching_pattern(&decl_stmt.pattern, position) {
           return Some(found);
         }
+        if let Some(found) =
+          decl_stmt.else_block.as_ref().and_then(|b| search_block(b, position, stop_at_call))
+        {
+          return Some(found);
+        }
+      }
+      expr::Statement::Assignment(assign_stmt) => {
+        if assign_stmt.name.loc.contains_position(position) {
+          return Some(LocationCoverSearchResult::TypedName(
+            assign_stmt.name.loc,
+            assign_stmt.assigned_expression.type_().as_ref().clone(),
+            false,
+          ));
+        }
+        if let Some(found) =
+          search_expression(&assign_stmt.assigned_expression, position, stop_at_call)
+        {
+          return Some(found);
+        }
+      }
+      expr::Statement::Function(function_stmt) => {
+        if function_stmt.name.loc.contains_position(position) {
+          return Some(LocationCoverSearchResult::TypedName(
+            function_stmt.name.loc,
+            function_stmt.lambda.common.type_.as_ref().clone(),
+            true,
+          ));
+        }
+        if let Some(found) = search_annotation(&function_stmt.return_annotation, position) {
+          return Some(found);
+        }
+        if let Some(found) = search_lambda(&function_stmt.lambda, position, stop_at_call) {
+          return Some(found);
+        }
       }
       expr::Statement::Expression(expr) => {
         if let Some(found) = search_expression(expr, position, stop_at_call) {
@@ -189,6 +228,34 @@ fn search_block(
   None
 }
 
+fn search_lambda(
+  lambda: &'_ expr::Lambda<Arc<Type>>,
+  position: Position,
+  stop_at_call: bool,
+) -> Option<LocationCoverSearchResult<'_>> {
+  for param in &lambda.parameters.parameters {
+    if param.name.loc.contains_position(position) {
+      return if let Some(annot) = &param.annotation {
+        Some(LocationCoverSearchResult::TypedName(
+          param.name.loc,
+          Type::from_annotation(annot),
+          true,
+        ))
+      } else {
+        Some(LocationCoverSearchResult::TypedName(
+          param.name.loc,
+          param.type_.as_ref().clone(),
+          false,
+        ))
+      };
+    }
+    if let Some(found) = search_optional_annotation(param.annotation.as_ref(), position) {
+      return Some(found);
+    }
+  }
+  search_expression(&lambda.body, position, stop_at_call)
+}
+
 fn search_expression(
   expr: &'_ expr::E<Arc<Type>>,
   position: Position,
@@ -209,6 +276,28 @@ fn search_expression(
     expr::E::Tuple(_, expressions) => {
       search_parenthesized_expression_list(expressions, position, stop_at_call)
     }
+    expr::E::TemplateString(e) => {
+      e.holes.iter().find_map(|hole| search_expression(hole, position, stop_at_call))
+    }
+    expr::E::StructUpdate(e) => {
+      let base_nominal_type = e.base.type_().as_nominal();
+      search_expression(&e.base, position, stop_at_call).or_else(|| {
+        e.fields.iter().find_map(|field| {
+          if field.field_name.loc.contains_position(position) {
+            base_nominal_type.map(|nominal_type| {
+              LocationCoverSearchResult::PropertyName(
+                field.field_name.loc,
+                nominal_type.module_reference,
+                nominal_type.id,
+                field.field_name.name,
+              )
+            })
+          } else {
+            search_expression(&field.value, position, stop_at_call)
+          }
+        })
+      })
+    }
     expr::E::FieldAccess(e) => {
       let found = e
         .object
@@ -235,6 +324,19 @@ fn search_expression(
             )
           }
         })
+        .or_else(|| {
+          if matches!(e.object.type_().as_ref(), Type::Tuple(_, _))
+            && e.field_name.loc.contains_position(position)
+          {
+            Some(LocationCoverSearchResult::TypedName(
+              e.field_name.loc,
+              e.common.type_.as_ref().clone(),
+              false,
+            ))
+          } else {
+            None
+          }
+        })
         .or_else(|| search_optional_type_arguments(e.explicit_type_arguments.as_ref(), position));
       if found.is_some() {
         return found;
@@ -290,33 +392,37 @@ fn search_expression(
         if Option::is_some(&found) {
           return found;
         }
+        if let Some(guard) = &case.guard {
+          found = search_expression(guard, position, stop_at_call);
+          if Option::is_some(&found) {
+            return found;
+          }
+        }
         found = search_expression(&case.body, position, stop_at_call);
       }
       found
     }
-    expr::E::Lambda(e) => {
-      for param in &e.parameters.parameters {
-        if param.name.loc.contains_position(position) {
-          return if let Some(annot) = &param.annotation {
-            Some(LocationCoverSearchResult::TypedName(
-              param.name.loc,
-              Type::from_annotation(annot),
-              true,
-            ))
-          } else {
-            Some(LocationCoverSearchResult::TypedName(
-              param.name.loc,
-              param.type_.as_ref().clone(),
-              false,
-            ))
-          };
-        }
-        if let Some(found) = search_optional_annotation(param.annotation.as_ref(), position) {
-          return Some(found);
-        }
+    expr::E::Lambda(e) => search_lambda(e, position, stop_at_call),
+    expr::E::Return(e) => search_expression(&e.argument, position, stop_at_call),
+    expr::E::While(e) => search_expression(&e.condition, position, stop_at_call)
+      .or_else(|| search_block(&e.body, position, stop_at_call)),
+    expr::E::For(e) => {
+      if e.iterator.loc.contains_position(position) {
+        return Some(LocationCoverSearchResult::TypedName(
+          e.iterator.loc,
+          Type::Primitive(Reason::new(e.iterator.loc, None), PrimitiveTypeKind::Int),
+          false,
+        ));
       }
-      search_expression(&e.body, position, stop_at_call)
+      search_expression(&e.start, position, stop_at_call)
+        .or_else(|| search_expression(&e.end, position, stop_at_call))
+        .or_else(|| search_block(&e.body, position, stop_at_call))
     }
+    expr::E::Break(_) => None,
+    expr::E::Assert(e) => {
+      search_parenthesized_expression_list(&e.arguments, position, stop_at_call)
+    }
+    expr::E::Try(e) => search_expression(&e.argument, position, stop_at_call),
     expr::E::Block(e) => search_block(e, position, stop_at_call),
   };
   if let Some(e) = found_from_children {
@@ -344,6 +450,13 @@ pub(super) fn search_module_locally(
     if let Some(found) = search_optional_type_parameters(toplevel.type_parameters(), position) {
       return Some(found);
     }
+    if let Toplevel::TypeAlias(alias) = toplevel {
+      return search_annotation(&alias.annotation, position);
+    }
+    if let Toplevel::Const(c) = toplevel {
+      return search_annotation(&c.annotation, position)
+        .or_else(|| search_expression(&c.value, position, stop_at_call));
+    }
     for member in toplevel.members_iter() {
       if !member.loc.contains_position(position) {
         continue;
@@ -389,6 +502,18 @@ pub(super) fn search_module_locally(
         }
       }
     }
+    if let Toplevel::Interface(i) = toplevel {
+      for member in &i.members.members {
+        if !member.decl.loc.contains_position(position) {
+          continue;
+        }
+        if let Some(found) =
+          member.default_body.as_ref().and_then(|it| search_expression(it, position, stop_at_call))
+        {
+          return Some(found);
+        }
+      }
+    }
   }
   None
 }
@@ -533,7 +658,7 @@ mod tests {
       samlang_parser::parse_source_module_from_text(source_code, mod_ref, heap, &mut error_set);
     let mut sources = samlang_parser::builtin_parsed_std_sources_for_tests(heap);
     sources.insert(mod_ref, parsed);
-    let (checked_sources, _) = samlang_checker::type_check_sources(&sources, &mut error_set);
+    let (checked_sources, _) = samlang_checker::type_check_sources(&sources, heap, &mut error_set);
     assert_eq!("", error_set.pretty_print_error_messages_no_frame_for_test(heap));
     for m in checked_sources.values() {
       for (i, line) in source_code.lines().enumerate() {
@@ -2,9 +2,8 @@ use super::{dep_graph::DependencyGraph, gc::perform_gc_after_recheck};
 use rayon::prelude::*;
 use samlang_ast::source::Module;
 use samlang_checker::{
-  build_module_signature,
   type_::{GlobalSignature, Type},
-  type_check_module, type_check_sources,
+  type_check_module, type_check_sources, update_global_signature,
 };
 use samlang_errors::{CompileTimeError, ErrorSet};
 use samlang_heap::{Heap, ModuleReference};
@@ -47,7 +46,7 @@ impl ServerState {
         })
         .collect::<HashMap<_, _>>();
       let dep_graph = DependencyGraph::new(&parsed_modules);
-      let (checked_modules, global_cx) = type_check_sources(&parsed_modules, &mut error_set);
+      let (checked_modules, global_cx) = type_check_sources(&parsed_modules, &heap, &mut error_set);
       let errors = error_set.group_errors();
       ServerState {
         heap,
@@ -135,12 +134,13 @@ impl ServerState {
         &mut self.heap,
         &mut error_set,
       );
-      self.global_cx.insert(mod_ref, build_module_signature(mod_ref, &parsed));
       self.string_sources.insert(mod_ref, source_code);
       self.parsed_modules.insert(mod_ref, parsed);
     }
     self.dep_graph = DependencyGraph::new(&self.parsed_modules);
     let recheck_set = self.dep_graph.affected_set(initial_update_set);
+    // Signatures of dependents are also rebuilt, since they might expand updated type aliases.
+    update_global_signature(&mut self.global_cx, &self.parsed_modules, &recheck_set, &self.heap);
     self.recheck(error_set, &recheck_set);
   }
 
@@ -166,6 +166,7 @@ impl ServerState {
       self.checked_modules.remove(&old_mod_ref);
     }
     self.dep_graph = DependencyGraph::new(&self.parsed_modules);
+    update_global_signature(&mut self.global_cx, &self.parsed_modules, &recheck_set, &self.heap);
     self.recheck(error_set, &recheck_set);
   }
 
@@ -178,6 +179,7 @@ impl ServerState {
       self.global_cx.remove(mod_ref);
     }
     self.dep_graph = DependencyGraph::new(&self.parsed_modules);
+    update_global_signature(&mut self.global_cx, &self.parsed_modules, &recheck_set, &self.heap);
     self.recheck(ErrorSet::new(), &recheck_set);
   }
 }
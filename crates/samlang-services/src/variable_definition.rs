@@ -1,8 +1,9 @@
 use samlang_ast::{
   Location,
   source::{
-    AnnotatedId, ClassDefinition, ClassMemberDeclaration, ClassMemberDefinition, Id,
-    InterfaceMembersCommon, Module, OptionallyAnnotatedId, Toplevel, expr, pattern,
+    AnnotatedId, ClassDefinition, ClassMemberDeclaration, ClassMemberDefinition, ConstDefinition,
+    Id, InterfaceDeclarationCommon, InterfaceMemberDefinition, InterfaceMembersCommon, Module,
+    OptionallyAnnotatedId, Toplevel, expr, pattern,
   },
 };
 use samlang_checker::{SsaAnalysisResult, perform_ssa_analysis_on_module};
@@ -168,7 +169,9 @@ fn apply_matching_pattern_renaming(
         (),
       )
     }
-    pattern::MatchingPattern::Wildcard { .. } => pattern.clone(),
+    pattern::MatchingPattern::Wildcard { .. } | pattern::MatchingPattern::Literal { .. } => {
+      pattern.clone()
+    }
     pattern::MatchingPattern::Or { location, patterns } => pattern::MatchingPattern::Or {
       location: *location,
       patterns: patterns
@@ -228,6 +231,32 @@ fn apply_if_else_renaming(
   }
 }
 
+fn apply_lambda_renaming(
+  lambda: &expr::Lambda<()>,
+  definition_and_uses: &DefinitionAndUses,
+  new_name: PStr,
+) -> expr::Lambda<()> {
+  expr::Lambda {
+    common: lambda.common.clone(),
+    parameters: expr::LambdaParameters {
+      loc: lambda.parameters.loc,
+      parameters: lambda
+        .parameters
+        .parameters
+        .iter()
+        .map(|OptionallyAnnotatedId { name, type_, annotation }| OptionallyAnnotatedId {
+          name: mod_def_id(name, definition_and_uses, new_name),
+          type_: *type_,
+          annotation: annotation.clone(),
+        })
+        .collect(),
+      ending_associated_comments: lambda.parameters.ending_associated_comments,
+    },
+    captured: lambda.captured.clone(),
+    body: Box::new(apply_expr_renaming(&lambda.body, definition_and_uses, new_name)),
+  }
+}
+
 fn apply_block_renaming(
   block: &expr::Block<()>,
   definition_and_uses: &DefinitionAndUses,
@@ -243,6 +272,7 @@ fn apply_block_renaming(
           expr::Statement::Declaration(Box::new(expr::DeclarationStatement {
             loc: decl.loc,
             associated_comments: decl.associated_comments,
+            is_mutable: decl.is_mutable,
             pattern: apply_matching_pattern_renaming(&decl.pattern, definition_and_uses, new_name),
             annotation: decl.annotation.clone(),
             assigned_expression: Box::new(apply_expr_renaming(
@@ -250,6 +280,35 @@ fn apply_block_renaming(
               definition_and_uses,
               new_name,
             )),
+            else_block: decl
+              .else_block
+              .as_ref()
+              .map(|b| Box::new(apply_block_renaming(b, definition_and_uses, new_name))),
+          }))
+        }
+        expr::Statement::Assignment(assign) => {
+          expr::Statement::Assignment(Box::new(expr::AssignmentStatement {
+            loc: assign.loc,
+            associated_comments: assign.associated_comments,
+            name: if definition_and_uses.use_locations.contains(&assign.name.loc) {
+              mod_id(&assign.name, new_name)
+            } else {
+              assign.name
+            },
+            assigned_expression: Box::new(apply_expr_renaming(
+              &assign.assigned_expression,
+              definition_and_uses,
+              new_name,
+            )),
+          }))
+        }
+        expr::Statement::Function(function) => {
+          expr::Statement::Function(Box::new(expr::LocalFunctionStatement {
+            loc: function.loc,
+            associated_comments: function.associated_comments,
+            name: mod_def_id(&function.name, definition_and_uses, new_name),
+            return_annotation: function.return_annotation.clone(),
+            lambda: apply_lambda_renaming(&function.lambda, definition_and_uses, new_name),
           }))
         }
         expr::Statement::Expression(expr) => expr::Statement::Expression(Box::new(
@@ -281,6 +340,30 @@ fn apply_expr_renaming(
       common.clone(),
       apply_parenthesized_expression_list_renaming(e, definition_and_uses, new_name),
     ),
+    expr::E::TemplateString(e) => expr::E::TemplateString(expr::TemplateString {
+      common: e.common.clone(),
+      parts: e.parts.clone(),
+      holes: e
+        .holes
+        .iter()
+        .map(|hole| apply_expr_renaming(hole, definition_and_uses, new_name))
+        .collect(),
+    }),
+    expr::E::StructUpdate(e) => expr::E::StructUpdate(expr::StructUpdate {
+      common: e.common.clone(),
+      base: Box::new(apply_expr_renaming(&e.base, definition_and_uses, new_name)),
+      fields: e
+        .fields
+        .iter()
+        .map(|field| expr::FieldUpdate {
+          loc: field.loc,
+          field_name: field.field_name,
+          field_order: field.field_order,
+          value: apply_expr_renaming(&field.value, definition_and_uses, new_name),
+        })
+        .collect(),
+      ending_associated_comments: e.ending_associated_comments,
+    }),
     expr::E::FieldAccess(e) => expr::E::FieldAccess(expr::FieldAccess {
       common: e.common.clone(),
       explicit_type_arguments: e.explicit_type_arguments.clone(),
@@ -309,6 +392,7 @@ fn apply_expr_renaming(
         definition_and_uses,
         new_name,
       ),
+      binding: e.binding.clone(),
     }),
     expr::E::Binary(e) => expr::E::Binary(expr::Binary {
       common: e.common.clone(),
@@ -325,10 +409,19 @@ fn apply_expr_renaming(
         .cases
         .iter()
         .map(
-          |expr::VariantPatternToExpression { loc, pattern, body, ending_associated_comments }| {
+          |expr::VariantPatternToExpression {
+             loc,
+             pattern,
+             guard,
+             body,
+             ending_associated_comments,
+           }| {
             expr::VariantPatternToExpression {
               loc: *loc,
               pattern: apply_matching_pattern_renaming(pattern, definition_and_uses, new_name),
+              guard: guard
+                .as_ref()
+                .map(|guard| Box::new(apply_expr_renaming(guard, definition_and_uses, new_name))),
               body: Box::new(apply_expr_renaming(body, definition_and_uses, new_name)),
               ending_associated_comments: *ending_associated_comments,
             }
@@ -336,26 +429,79 @@ fn apply_expr_renaming(
         )
         .collect(),
     }),
-    expr::E::Lambda(e) => expr::E::Lambda(expr::Lambda {
+    expr::E::Lambda(e) => expr::E::Lambda(apply_lambda_renaming(e, definition_and_uses, new_name)),
+    expr::E::Return(e) => expr::E::Return(expr::Return {
       common: e.common.clone(),
-      parameters: expr::LambdaParameters {
-        loc: e.parameters.loc,
-        parameters: e
-          .parameters
+      argument: Box::new(apply_expr_renaming(&e.argument, definition_and_uses, new_name)),
+    }),
+    expr::E::While(e) => expr::E::While(expr::While {
+      common: e.common.clone(),
+      condition: Box::new(apply_expr_renaming(&e.condition, definition_and_uses, new_name)),
+      body: Box::new(apply_block_renaming(&e.body, definition_and_uses, new_name)),
+    }),
+    expr::E::For(e) => expr::E::For(expr::For {
+      common: e.common.clone(),
+      iterator: mod_def_id(&e.iterator, definition_and_uses, new_name),
+      start: Box::new(apply_expr_renaming(&e.start, definition_and_uses, new_name)),
+      end: Box::new(apply_expr_renaming(&e.end, definition_and_uses, new_name)),
+      body: Box::new(apply_block_renaming(&e.body, definition_and_uses, new_name)),
+    }),
+    expr::E::Break(_) => panic!(),
+    expr::E::Assert(e) => expr::E::Assert(expr::Assert {
+      common: e.common.clone(),
+      arguments: apply_parenthesized_expression_list_renaming(
+        &e.arguments,
+        definition_and_uses,
+        new_name,
+      ),
+    }),
+    expr::E::Try(e) => expr::E::Try(expr::Try {
+      common: e.common.clone(),
+      argument: Box::new(apply_expr_renaming(&e.argument, definition_and_uses, new_name)),
+    }),
+    expr::E::Block(e) => expr::E::Block(apply_block_renaming(e, definition_and_uses, new_name)),
+  }
+}
+
+fn apply_member_declaration_renaming(
+  ClassMemberDeclaration {
+    loc,
+    associated_comments,
+    visibility,
+    is_method,
+    name,
+    type_parameters,
+    parameters,
+    return_type,
+  }: &ClassMemberDeclaration,
+  definition_and_uses: &DefinitionAndUses,
+  new_name: PStr,
+) -> ClassMemberDeclaration {
+  ClassMemberDeclaration {
+    loc: *loc,
+    associated_comments: *associated_comments,
+    visibility: *visibility,
+    is_method: *is_method,
+    name: *name,
+    type_parameters: type_parameters.clone(),
+    parameters: samlang_ast::source::FunctionParameters {
+      location: parameters.location,
+      start_associated_comments: parameters.start_associated_comments,
+      ending_associated_comments: parameters.ending_associated_comments,
+      parameters: Arc::new(
+        parameters
           .parameters
           .iter()
-          .map(|OptionallyAnnotatedId { name, type_, annotation }| OptionallyAnnotatedId {
+          .map(|AnnotatedId { name, type_, annotation, default_value }| AnnotatedId {
             name: mod_def_id(name, definition_and_uses, new_name),
             type_: *type_,
             annotation: annotation.clone(),
+            default_value: default_value.clone(),
           })
           .collect(),
-        ending_associated_comments: e.parameters.ending_associated_comments,
-      },
-      captured: e.captured.clone(),
-      body: Box::new(apply_expr_renaming(&e.body, definition_and_uses, new_name)),
-    }),
-    expr::E::Block(e) => expr::E::Block(apply_block_renaming(e, definition_and_uses, new_name)),
+      ),
+    },
+    return_type: return_type.clone(),
   }
 }
 
@@ -370,14 +516,48 @@ pub(super) fn apply_renaming(
     toplevels: toplevels
       .iter()
       .map(|toplevel| match toplevel {
-        Toplevel::Interface(i) => Toplevel::Interface(i.clone()),
+        Toplevel::Interface(i) => Toplevel::Interface(InterfaceDeclarationCommon {
+          loc: i.loc,
+          associated_comments: i.associated_comments,
+          visibility: i.visibility,
+          name: i.name,
+          type_parameters: i.type_parameters.clone(),
+          extends_or_implements_nodes: i.extends_or_implements_nodes.clone(),
+          derives: None,
+          type_definition: (),
+          members: InterfaceMembersCommon {
+            loc: i.members.loc,
+            members: i
+              .members
+              .members
+              .iter()
+              .map(|InterfaceMemberDefinition { decl, default_body }| InterfaceMemberDefinition {
+                decl: apply_member_declaration_renaming(decl, definition_and_uses, new_name),
+                default_body: default_body
+                  .as_ref()
+                  .map(|body| apply_expr_renaming(body, definition_and_uses, new_name)),
+              })
+              .collect(),
+            ending_associated_comments: i.members.ending_associated_comments,
+          },
+        }),
+        Toplevel::TypeAlias(a) => Toplevel::TypeAlias(a.clone()),
+        Toplevel::Const(c) => Toplevel::Const(ConstDefinition {
+          loc: c.loc,
+          associated_comments: c.associated_comments,
+          visibility: c.visibility,
+          name: c.name,
+          annotation: c.annotation.clone(),
+          value: apply_expr_renaming(&c.value, definition_and_uses, new_name),
+        }),
         Toplevel::Class(c) => Toplevel::Class(ClassDefinition {
           loc: c.loc,
           associated_comments: c.associated_comments,
-          private: c.private,
+          visibility: c.visibility,
           name: c.name,
           type_parameters: c.type_parameters.clone(),
           extends_or_implements_nodes: c.extends_or_implements_nodes.clone(),
+          derives: c.derives.clone(),
           type_definition: c.type_definition.clone(),
           members: InterfaceMembersCommon {
             loc: c.members.loc,
@@ -385,51 +565,10 @@ pub(super) fn apply_renaming(
               .members
               .members
               .iter()
-              .map(
-                |ClassMemberDefinition {
-                   decl:
-                     ClassMemberDeclaration {
-                       loc,
-                       associated_comments,
-                       is_public,
-                       is_method,
-                       name,
-                       type_parameters,
-                       return_type,
-                       parameters,
-                     },
-                   body,
-                 }| {
-                  ClassMemberDefinition {
-                    decl: ClassMemberDeclaration {
-                      loc: *loc,
-                      associated_comments: *associated_comments,
-                      is_public: *is_public,
-                      is_method: *is_method,
-                      name: *name,
-                      type_parameters: type_parameters.clone(),
-                      parameters: samlang_ast::source::FunctionParameters {
-                        location: parameters.location,
-                        start_associated_comments: parameters.start_associated_comments,
-                        ending_associated_comments: parameters.ending_associated_comments,
-                        parameters: Arc::new(
-                          parameters
-                            .parameters
-                            .iter()
-                            .map(|AnnotatedId { name, type_, annotation }| AnnotatedId {
-                              name: mod_def_id(name, definition_and_uses, new_name),
-                              type_: *type_,
-                              annotation: annotation.clone(),
-                            })
-                            .collect(),
-                        ),
-                      },
-                      return_type: return_type.clone(),
-                    },
-                    body: apply_expr_renaming(body, definition_and_uses, new_name),
-                  }
-                },
-              )
+              .map(|ClassMemberDefinition { decl, body }| ClassMemberDefinition {
+                decl: apply_member_declaration_renaming(decl, definition_and_uses, new_name),
+                body: apply_expr_renaming(body, definition_and_uses, new_name),
+              })
               .collect(),
             ending_associated_comments: c.members.ending_associated_comments,
           },
@@ -458,7 +597,10 @@ mod tests {
         common: expr::ExpressionCommon::dummy(()),
         explicit_type_arguments: None,
         inferred_type_arguments: Vec::new(),
-        object: Box::new(expr::E::Literal(expr::ExpressionCommon::dummy(()), Literal::Int(0))),
+        object: Box::new(expr::E::Literal(
+          expr::ExpressionCommon::dummy(()),
+          Literal::Int(0, None),
+        )),
         method_name: Id::from(PStr::LOWER_A),
       }),
       &DefinitionAndUses {
@@ -12,12 +12,21 @@ export default async function interpretWebAssemblyModule(
 
   /** @param {any} strRef */
   function gcStringToJS(strRef) {
-    const len = exports.__strLen(strRef);
-    let result = '';
-    for (let i = 0; i < len; i++) {
-      result += String.fromCharCode(exports.__strGet(strRef, i));
+    const bytes = new Uint8Array(exports.__strLen(strRef));
+    for (let i = 0; i < bytes.length; i++) {
+      bytes[i] = exports.__strGet(strRef, i);
     }
-    return result;
+    return new TextDecoder().decode(bytes);
+  }
+
+  /** @param {string} str */
+  function jsStringToGC(str) {
+    const bytes = new TextEncoder().encode(str);
+    const strRef = exports.__strNew(bytes.length);
+    for (let i = 0; i < bytes.length; i++) {
+      exports.__strSet(strRef, i, bytes[i]);
+    }
+    return strRef;
   }
 
   const builtins = {
@@ -31,6 +40,10 @@ export default async function interpretWebAssemblyModule(
     __Process$panic(_, strRef) {
       throw new Error(gcStringToJS(strRef));
     },
+    /** @param {any} _ @param {number} v */
+    __Str$fromFloat(_, v) {
+      return jsStringToGC(String(v));
+    },
   };
 
   const codeModule = await WebAssembly.instantiate(emittedWasmBinary, {
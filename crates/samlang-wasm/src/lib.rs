@@ -29,7 +29,7 @@ pub fn compile(source: String) -> Result<SourcesCompilationResult, String> {
   let heap = &mut samlang_heap::Heap::new();
   let mod_ref = demo_mod_ref(heap);
   let sources = demo_sources(heap, source);
-  match samlang_compiler::compile_sources(heap, sources, vec![mod_ref], false) {
+  match samlang_compiler::compile_sources(heap, sources, vec![mod_ref], false, false) {
     Ok(samlang_compiler::SourcesCompilationResult { mut text_code_results, wasm_file }) => {
       let ts_code = text_code_results.remove("Demo.ts").unwrap();
       let wasm_bytes = Uint8Array::from(&wasm_file as &[u8]);